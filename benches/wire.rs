@@ -0,0 +1,103 @@
+//! 链上类型的编解码与签名基准
+//!
+//! 每个类型取 `lightpool::vectors` 中的全部代表值，分别测量 bincode 编码、复用缓冲区编码与解码；
+//! 签名部分测量原始消息与整笔交易的签名、验签吞吐量。运行：`cargo bench --bench wire`。
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lightpool::crypto::{verify, Signer};
+use lightpool::order::{CancelOrderParams, OrderType, PlaceOrderParams, TimeInForce};
+use lightpool::transaction::{Action, Signature, SignedTransaction, Transaction};
+use lightpool::types::{Address, ObjectId, OrderId, OrderSide};
+use lightpool::vectors::{signer, wire_types};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// `kind` 类型的全部用例，按向量字节解码得到值
+fn cases<T: DeserializeOwned>(kind: &str) -> Vec<(&'static str, T, Vec<u8>)> {
+    let (_, vectors) = wire_types()
+        .into_iter()
+        .find(|(name, _)| *name == kind)
+        .unwrap_or_else(|| panic!("no vectors for {}", kind));
+    vectors
+        .into_iter()
+        .map(|vector| {
+            let value = bincode::deserialize(&vector.bytes).expect("vectors decode");
+            (vector.name, value, vector.bytes)
+        })
+        .collect()
+}
+
+fn codec<T: Serialize + DeserializeOwned>(c: &mut Criterion, kind: &str) {
+    let mut group = c.benchmark_group(kind);
+    for (name, value, bytes) in cases::<T>(kind) {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::new("encode", name), &value, |b, value| {
+            b.iter(|| bincode::serialize(black_box(value)).unwrap())
+        });
+        let mut buffer = Vec::with_capacity(bytes.len());
+        group.bench_with_input(
+            BenchmarkId::new("encode_reuse", name),
+            &value,
+            |b, value| {
+                b.iter(|| {
+                    buffer.clear();
+                    bincode::serialize_into(&mut buffer, black_box(value)).unwrap();
+                    buffer.len()
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("decode", name), &bytes, |b, bytes| {
+            b.iter(|| bincode::deserialize::<T>(black_box(bytes)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn wire(c: &mut Criterion) {
+    codec::<OrderSide>(c, "order-side");
+    codec::<TimeInForce>(c, "time-in-force");
+    codec::<OrderType>(c, "order-type");
+    codec::<Address>(c, "address");
+    codec::<ObjectId>(c, "object-id");
+    codec::<OrderId>(c, "order-id");
+    codec::<PlaceOrderParams>(c, "place-order");
+    codec::<CancelOrderParams>(c, "cancel-order");
+    codec::<Action>(c, "action");
+    codec::<Transaction>(c, "transaction");
+    codec::<Signature>(c, "signature");
+    codec::<SignedTransaction>(c, "signed-transaction");
+}
+
+fn signing(c: &mut Criterion) {
+    let signer = signer();
+    let public_key = signer.public_key();
+    let mut group = c.benchmark_group("signing");
+    group.throughput(Throughput::Elements(1));
+    for (name, transaction, _) in cases::<Transaction>("transaction") {
+        let bytes = transaction.signing_bytes();
+        let signature = signer.sign(&bytes);
+        let signed = transaction.clone().sign(&signer);
+        group.bench_with_input(BenchmarkId::new("sign", name), &bytes, |b, bytes| {
+            b.iter(|| signer.sign(black_box(bytes)))
+        });
+        group.bench_with_input(BenchmarkId::new("verify", name), &bytes, |b, bytes| {
+            b.iter(|| verify(&public_key, black_box(bytes), &signature))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("sign_transaction", name),
+            &transaction,
+            |b, transaction| b.iter(|| black_box(transaction).clone().sign(&signer)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("verify_transaction", name),
+            &signed,
+            |b, signed| b.iter(|| black_box(signed).verify(&public_key)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, wire, signing);
+criterion_main!(benches);
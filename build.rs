@@ -0,0 +1,5 @@
+fn main() {
+    // Node.js 原生模块需要的链接参数
+    #[cfg(feature = "node")]
+    napi_build::setup();
+}
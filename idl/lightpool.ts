@@ -0,0 +1,467 @@
+// 由 `lightpool codegen typescript` 根据链上类型的 IDL 生成，请勿手工修改
+//
+// LightPool 链上类型：字段顺序与枚举序号与 Rust 定义一致，
+// encode(XCodec, value) / decode(XCodec, bytes) 为 bincode 编解码。
+
+/* eslint-disable */
+
+export const IDL_VERSION = 1;
+
+/** 二进制编解码器 */
+export interface Codec<T> {
+  write(out: Writer, value: T): void;
+  read(input: Reader): T;
+}
+
+export class Writer {
+  buf: Uint8Array = new Uint8Array(64);
+  len: number = 0;
+
+  /** 追加 `size` 字节并返回其视图 */
+  reserve(size: number): DataView {
+    if (this.len + size > this.buf.length) {
+      const grown = new Uint8Array(Math.max(this.buf.length * 2, this.len + size));
+      grown.set(this.buf.subarray(0, this.len));
+      this.buf = grown;
+    }
+    const view = new DataView(this.buf.buffer, this.len, size);
+    this.len += size;
+    return view;
+  }
+
+  bytes(data: Uint8Array): void {
+    const start = this.len;
+    this.reserve(data.length);
+    this.buf.set(data, start);
+  }
+
+  finish(): Uint8Array {
+    return this.buf.slice(0, this.len);
+  }
+}
+
+export class Reader {
+  data: Uint8Array;
+  pos: number = 0;
+
+  constructor(data: Uint8Array) {
+    this.data = data;
+  }
+
+  /** 读取 `size` 字节的视图 */
+  take(size: number): DataView {
+    if (this.pos + size > this.data.length) {
+      throw new RangeError("unexpected end of input");
+    }
+    const view = new DataView(this.data.buffer, this.data.byteOffset + this.pos, size);
+    this.pos += size;
+    return view;
+  }
+
+  bytes(size: number): Uint8Array {
+    const view = this.take(size);
+    return new Uint8Array(view.buffer, view.byteOffset, size).slice();
+  }
+}
+
+function integer(
+  name: string,
+  size: number,
+  min: number,
+  max: number,
+  set: (view: DataView, value: number) => void,
+  get: (view: DataView) => number,
+): Codec<number> {
+  return {
+    write(out: Writer, value: number): void {
+      if (!Number.isInteger(value) || value < min || value > max) {
+        throw new RangeError(`${value} is out of range for ${name}`);
+      }
+      set(out.reserve(size), value);
+    },
+    read: (input: Reader): number => get(input.take(size)),
+  };
+}
+
+function bigInteger(name: string, bits: number, signed: boolean): Codec<bigint> {
+  const min = signed ? -(BigInt(1) << BigInt(bits - 1)) : BigInt(0);
+  const max = (BigInt(1) << BigInt(signed ? bits - 1 : bits)) - BigInt(1);
+  const size = bits / 8;
+  return {
+    write(out: Writer, value: bigint): void {
+      if (typeof value !== "bigint" || value < min || value > max) {
+        throw new RangeError(`${value} is out of range for ${name}`);
+      }
+      let rest = BigInt.asUintN(bits, value);
+      const view = out.reserve(size);
+      for (let offset = 0; offset < size; offset += 8) {
+        view.setBigUint64(offset, BigInt.asUintN(64, rest), true);
+        rest >>= BigInt(64);
+      }
+    },
+    read(input: Reader): bigint {
+      const view = input.take(size);
+      let value = BigInt(0);
+      for (let offset = size - 8; offset >= 0; offset -= 8) {
+        value = (value << BigInt(64)) | view.getBigUint64(offset, true);
+      }
+      return signed ? BigInt.asIntN(bits, value) : value;
+    },
+  };
+}
+
+export const u8 = integer("u8", 1, 0, 0xff, (view, value) => view.setUint8(0, value), (view) => view.getUint8(0));
+export const u16 = integer("u16", 2, 0, 0xffff, (view, value) => view.setUint16(0, value, true), (view) => view.getUint16(0, true));
+export const u32 = integer("u32", 4, 0, 0xffffffff, (view, value) => view.setUint32(0, value, true), (view) => view.getUint32(0, true));
+export const i8 = integer("i8", 1, -0x80, 0x7f, (view, value) => view.setInt8(0, value), (view) => view.getInt8(0));
+export const i16 = integer("i16", 2, -0x8000, 0x7fff, (view, value) => view.setInt16(0, value, true), (view) => view.getInt16(0, true));
+export const i32 = integer("i32", 4, -0x80000000, 0x7fffffff, (view, value) => view.setInt32(0, value, true), (view) => view.getInt32(0, true));
+export const u64 = bigInteger("u64", 64, false);
+export const u128 = bigInteger("u128", 128, false);
+export const i64 = bigInteger("i64", 64, true);
+export const i128 = bigInteger("i128", 128, true);
+
+export const f32: Codec<number> = {
+  write: (out: Writer, value: number): void => out.reserve(4).setFloat32(0, value, true),
+  read: (input: Reader): number => input.take(4).getFloat32(0, true),
+};
+
+export const f64: Codec<number> = {
+  write: (out: Writer, value: number): void => out.reserve(8).setFloat64(0, value, true),
+  read: (input: Reader): number => input.take(8).getFloat64(0, true),
+};
+
+export const bool: Codec<boolean> = {
+  write: (out: Writer, value: boolean): void => out.reserve(1).setUint8(0, value ? 1 : 0),
+  read(input: Reader): boolean {
+    const tag = input.take(1).getUint8(0);
+    if (tag > 1) {
+      throw new RangeError(`invalid bool: ${tag}`);
+    }
+    return tag === 1;
+  },
+};
+
+export const unit: Codec<null> = {
+  write: (): void => {},
+  read: (): null => null,
+};
+
+/** 序列与字符串的长度前缀 */
+function writeLength(out: Writer, length: number): void {
+  u64.write(out, BigInt(length));
+}
+
+function readLength(input: Reader): number {
+  const length = u64.read(input);
+  if (length > BigInt(input.data.length - input.pos)) {
+    throw new RangeError("unexpected end of input");
+  }
+  return Number(length);
+}
+
+export const str: Codec<string> = {
+  write(out: Writer, value: string): void {
+    const data = new TextEncoder().encode(value);
+    writeLength(out, data.length);
+    out.bytes(data);
+  },
+  read: (input: Reader): string => new TextDecoder("utf-8", { fatal: true }).decode(input.bytes(readLength(input))),
+};
+
+export const bytes: Codec<Uint8Array> = {
+  write(out: Writer, value: Uint8Array): void {
+    writeLength(out, value.length);
+    out.bytes(value);
+  },
+  read: (input: Reader): Uint8Array => input.bytes(readLength(input)),
+};
+
+export function fixedBytes(size: number): Codec<Uint8Array> {
+  return {
+    write(out: Writer, value: Uint8Array): void {
+      if (value.length !== size) {
+        throw new RangeError(`expected ${size} bytes, got ${value.length}`);
+      }
+      out.bytes(value);
+    },
+    read: (input: Reader): Uint8Array => input.bytes(size),
+  };
+}
+
+export function option<T>(inner: Codec<T>): Codec<T | null> {
+  return {
+    write(out: Writer, value: T | null): void {
+      if (value === null || value === undefined) {
+        u8.write(out, 0);
+      } else {
+        u8.write(out, 1);
+        inner.write(out, value);
+      }
+    },
+    read(input: Reader): T | null {
+      const tag = u8.read(input);
+      if (tag > 1) {
+        throw new RangeError(`invalid option tag: ${tag}`);
+      }
+      return tag === 1 ? inner.read(input) : null;
+    },
+  };
+}
+
+export function seq<T>(inner: Codec<T>): Codec<T[]> {
+  return {
+    write(out: Writer, value: T[]): void {
+      writeLength(out, value.length);
+      for (const item of value) {
+        inner.write(out, item);
+      }
+    },
+    read(input: Reader): T[] {
+      const length = readLength(input);
+      const items: T[] = [];
+      for (let i = 0; i < length; i++) {
+        items.push(inner.read(input));
+      }
+      return items;
+    },
+  };
+}
+
+export function array<T>(inner: Codec<T>, size: number): Codec<T[]> {
+  return {
+    write(out: Writer, value: T[]): void {
+      if (value.length !== size) {
+        throw new RangeError(`expected ${size} items, got ${value.length}`);
+      }
+      for (const item of value) {
+        inner.write(out, item);
+      }
+    },
+    read(input: Reader): T[] {
+      const items: T[] = [];
+      for (let i = 0; i < size; i++) {
+        items.push(inner.read(input));
+      }
+      return items;
+    },
+  };
+}
+
+export function tuple(...items: Codec<any>[]): Codec<any[]> {
+  return {
+    write(out: Writer, value: any[]): void {
+      if (value.length !== items.length) {
+        throw new RangeError(`expected ${items.length} items, got ${value.length}`);
+      }
+      items.forEach((codec, i) => codec.write(out, value[i]));
+    },
+    read: (input: Reader): any[] => items.map((codec) => codec.read(input)),
+  };
+}
+
+/** 按 Map 的迭代顺序编码，须与 Rust 一侧的映射顺序一致 */
+export function map<K, V>(key: Codec<K>, value: Codec<V>): Codec<Map<K, V>> {
+  return {
+    write(out: Writer, entries: Map<K, V>): void {
+      writeLength(out, entries.size);
+      for (const [k, v] of entries) {
+        key.write(out, k);
+        value.write(out, v);
+      }
+    },
+    read(input: Reader): Map<K, V> {
+      const length = readLength(input);
+      const entries = new Map<K, V>();
+      for (let i = 0; i < length; i++) {
+        const k = key.read(input);
+        entries.set(k, value.read(input));
+      }
+      return entries;
+    },
+  };
+}
+
+/** 延迟引用，允许前向引用本模块中的编解码器 */
+export function ref<T>(get: () => Codec<T>): Codec<T> {
+  return {
+    write: (out: Writer, value: T): void => get().write(out, value),
+    read: (input: Reader): T => get().read(input),
+  };
+}
+
+type Fields = [string, Codec<any>][];
+
+function writeFields(out: Writer, fields: Fields, value: any): void {
+  for (const [name, codec] of fields) {
+    codec.write(out, value[name]);
+  }
+}
+
+function readFields(input: Reader, fields: Fields, value: any): any {
+  for (const [name, codec] of fields) {
+    value[name] = codec.read(input);
+  }
+  return value;
+}
+
+export function struct<T>(fields: Fields): Codec<T> {
+  return {
+    write: (out: Writer, value: T): void => writeFields(out, fields, value),
+    read: (input: Reader): T => readFields(input, fields, {}),
+  };
+}
+
+/** 只含单元变体的枚举，值即编码序号 */
+export function unitEnum<T extends number>(name: string, indices: number[]): Codec<T> {
+  return {
+    write(out: Writer, value: T): void {
+      if (!indices.includes(value)) {
+        throw new RangeError(`invalid ${name} variant: ${value}`);
+      }
+      u32.write(out, value);
+    },
+    read(input: Reader): T {
+      const index = u32.read(input);
+      if (!indices.includes(index)) {
+        throw new RangeError(`invalid ${name} variant: ${index}`);
+      }
+      return index as T;
+    },
+  };
+}
+
+/** 带数据的枚举，变体以 `kind` 区分，按声明顺序编号 */
+export function union<T extends { kind: string }>(name: string, variants: [string, Fields][]): Codec<T> {
+  return {
+    write(out: Writer, value: T): void {
+      const index = variants.findIndex(([kind]) => kind === value.kind);
+      if (index < 0) {
+        throw new RangeError(`invalid ${name} variant: ${value.kind}`);
+      }
+      u32.write(out, index);
+      writeFields(out, variants[index][1], value);
+    },
+    read(input: Reader): T {
+      const index = u32.read(input);
+      if (index >= variants.length) {
+        throw new RangeError(`invalid ${name} variant: ${index}`);
+      }
+      const [kind, fields] = variants[index];
+      return readFields(input, fields, { kind });
+    },
+  };
+}
+
+/** bincode 编码 */
+export function encode<T>(codec: Codec<T>, value: T): Uint8Array {
+  const out = new Writer();
+  codec.write(out, value);
+  return out.finish();
+}
+
+/** bincode 解码，不允许多余字节 */
+export function decode<T>(codec: Codec<T>, data: Uint8Array): T {
+  const input = new Reader(data);
+  const value = codec.read(input);
+  if (input.pos !== data.length) {
+    throw new RangeError(`${data.length - input.pos} trailing bytes`);
+  }
+  return value;
+}
+
+export interface Action {
+  inputs: Array<ObjectId>;
+  contract: Address;
+  action: bigint;
+  params: Uint8Array;
+}
+export const ActionCodec: Codec<Action> = struct([
+  ["inputs", seq(ref(() => ObjectIdCodec))],
+  ["contract", ref(() => AddressCodec)],
+  ["action", u64],
+  ["params", bytes],
+]);
+
+export type Address = Uint8Array;
+export const AddressCodec: Codec<Address> = fixedBytes(32);
+
+export interface CancelOrderParams {
+  order_id: OrderId;
+}
+export const CancelOrderParamsCodec: Codec<CancelOrderParams> = struct([
+  ["order_id", ref(() => OrderIdCodec)],
+]);
+
+export type ObjectId = Uint8Array;
+export const ObjectIdCodec: Codec<ObjectId> = fixedBytes(32);
+
+export type OrderId = Uint8Array;
+export const OrderIdCodec: Codec<OrderId> = fixedBytes(32);
+
+export const OrderSide = {
+  Buy: 0,
+  Sell: 1,
+} as const;
+export type OrderSide = (typeof OrderSide)[keyof typeof OrderSide];
+export const OrderSideCodec: Codec<OrderSide> = unitEnum("OrderSide", [0, 1]);
+
+export type OrderType =
+  | { kind: "Limit"; tif: TimeInForce }
+  | { kind: "Market"; slippage: bigint }
+  | { kind: "Trigger"; trigger_price: bigint; is_market: boolean; trigger_type: number };
+export const OrderTypeCodec: Codec<OrderType> = union("OrderType", [
+  ["Limit", [["tif", ref(() => TimeInForceCodec)]]],
+  ["Market", [["slippage", u64]]],
+  ["Trigger", [["trigger_price", u64], ["is_market", bool], ["trigger_type", u8]]],
+]);
+
+export interface PlaceOrderParams {
+  side: OrderSide;
+  amount: bigint;
+  order_type: OrderType;
+  limit_price: bigint;
+}
+export const PlaceOrderParamsCodec: Codec<PlaceOrderParams> = struct([
+  ["side", ref(() => OrderSideCodec)],
+  ["amount", u64],
+  ["order_type", ref(() => OrderTypeCodec)],
+  ["limit_price", u64],
+]);
+
+export interface Signature {
+  part1: Uint8Array;
+  part2: Uint8Array;
+}
+export const SignatureCodec: Codec<Signature> = struct([
+  ["part1", fixedBytes(32)],
+  ["part2", fixedBytes(32)],
+]);
+
+export interface SignedTransaction {
+  transaction: Transaction;
+  signatures: Array<Signature>;
+}
+export const SignedTransactionCodec: Codec<SignedTransaction> = struct([
+  ["transaction", ref(() => TransactionCodec)],
+  ["signatures", seq(ref(() => SignatureCodec))],
+]);
+
+export const TimeInForce = {
+  GTC: 0,
+  IOC: 1,
+  FOK: 2,
+} as const;
+export type TimeInForce = (typeof TimeInForce)[keyof typeof TimeInForce];
+export const TimeInForceCodec: Codec<TimeInForce> = unitEnum("TimeInForce", [0, 1, 2]);
+
+export interface Transaction {
+  sender: Address;
+  expiration: bigint;
+  actions: Array<Action>;
+}
+export const TransactionCodec: Codec<Transaction> = struct([
+  ["sender", ref(() => AddressCodec)],
+  ["expiration", u64],
+  ["actions", seq(ref(() => ActionCodec))],
+]);
@@ -0,0 +1,190 @@
+/* LightPool C 接口，由 cbindgen 生成，请勿手工修改 */
+
+#ifndef LIGHTPOOL_H
+#define LIGHTPOOL_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * 调用结果
+ */
+typedef enum LightpoolStatus {
+  LIGHTPOOL_STATUS_OK = 0,
+  /**
+   * 必需的指针参数为空
+   */
+  LIGHTPOOL_STATUS_NULL_POINTER = 1,
+  /**
+   * 参数不合法
+   */
+  LIGHTPOOL_STATUS_INVALID_ARGUMENT = 2,
+} LightpoolStatus;
+
+/**
+ * 下单参数，对应 [`PlaceOrderParams`]
+ */
+typedef struct LightpoolPlaceOrder {
+  /**
+   * 0 为买，1 为卖
+   */
+  uint8_t side;
+  uint64_t amount;
+  /**
+   * 限价；市价单为滑点参考价
+   */
+  uint64_t limit_price;
+  /**
+   * 0 为限价单，1 为市价单，2 为触发单
+   */
+  uint8_t order_type;
+  /**
+   * 限价单有效期：0 为 GTC，1 为 IOC，2 为 FOK
+   */
+  uint8_t time_in_force;
+  /**
+   * 市价单最大滑点（基点）
+   */
+  uint64_t slippage_bps;
+  /**
+   * 触发单的触发价
+   */
+  uint64_t trigger_price;
+  /**
+   * 触发单触发后是否按市价成交，0 或 1
+   */
+  uint8_t trigger_is_market;
+  /**
+   * 触发单的触发类型
+   */
+  uint8_t trigger_type;
+} LightpoolPlaceOrder;
+
+/**
+ * 由本库分配的字节缓冲区
+ */
+typedef struct LightpoolBuffer {
+  uint8_t *data;
+  size_t len;
+} LightpoolBuffer;
+
+/**
+ * 撤单参数，对应 [`CancelOrderParams`]
+ */
+typedef struct LightpoolCancelOrder {
+  uint8_t order_id[32];
+} LightpoolCancelOrder;
+
+/**
+ * 合约调用
+ */
+typedef struct LightpoolAction {
+  /**
+   * 目标合约地址
+   */
+  uint8_t contract[32];
+  /**
+   * 操作名，以 NUL 结尾，如 `ord_place`
+   */
+  const char *name;
+  /**
+   * 依次排列的输入对象ID，每个 32 字节
+   */
+  const uint8_t *inputs;
+  /**
+   * 输入对象个数
+   */
+  size_t inputs_len;
+  /**
+   * bincode 编码的参数
+   */
+  const uint8_t *params;
+  size_t params_len;
+} LightpoolAction;
+
+/**
+ * 64 字节 Ed25519 签名，对应 [`Signature`]
+ */
+typedef struct LightpoolSignature {
+  uint8_t part1[32];
+  uint8_t part2[32];
+} LightpoolSignature;
+
+
+
+
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * 当前线程最近一次失败的错误信息，没有时返回空指针
+ *
+ * 返回的字符串在本线程下一次调用本库函数前有效，不得释放。
+ */
+const char *lightpool_last_error(void);
+
+/**
+ * 编码下单参数，结果为 `ord_place` 操作的 bincode 参数
+ *
+ * # Safety
+ *
+ * `order` 须指向有效的 [`LightpoolPlaceOrder`]，`out` 须指向可写的 [`LightpoolBuffer`]。
+ */
+enum LightpoolStatus lightpool_encode_place_order(const struct LightpoolPlaceOrder *order,
+                                                  struct LightpoolBuffer *out);
+
+/**
+ * 解码 `ord_place` 操作的 bincode 参数
+ *
+ * # Safety
+ *
+ * `data` 须在 `len` 字节内可读，`out` 须指向可写的 [`LightpoolPlaceOrder`]。
+ */
+enum LightpoolStatus lightpool_decode_place_order(const uint8_t *data,
+                                                  size_t len,
+                                                  struct LightpoolPlaceOrder *out);
+
+/**
+ * 编码撤单参数，结果为 `ord_cancel` 操作的 bincode 参数
+ *
+ * # Safety
+ *
+ * `order` 须指向有效的 [`LightpoolCancelOrder`]，`out` 须指向可写的 [`LightpoolBuffer`]。
+ */
+enum LightpoolStatus lightpool_encode_cancel_order(const struct LightpoolCancelOrder *order,
+                                                   struct LightpoolBuffer *out);
+
+/**
+ * 以 `secret_key` 对应的地址为发送者，构建只含一个操作的交易并签名
+ *
+ * 结果为已签名交易的 JSON，即 `submitTransaction` 的 `tx` 参数；`expiration` 为 `UINT64_MAX` 时不过期。
+ *
+ * # Safety
+ *
+ * `secret_key` 须指向 32 字节私钥，`action` 须指向有效的 [`LightpoolAction`]，
+ * 其中的数组指针须在给定长度内可读；`out` 须指向可写的 [`LightpoolBuffer`]。
+ */
+enum LightpoolStatus lightpool_sign_action(const uint8_t *secret_key,
+                                           const struct LightpoolAction *action,
+                                           uint64_t expiration,
+                                           struct LightpoolBuffer *out);
+
+/**
+ * 释放本库分配的缓冲区，释放后 `buffer` 被置空；重复释放是安全的
+ *
+ * # Safety
+ *
+ * `buffer` 须为空指针或指向由本库写入的 [`LightpoolBuffer`]。
+ */
+void lightpool_free_buffer(struct LightpoolBuffer *buffer);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* LIGHTPOOL_H */
@@ -0,0 +1,404 @@
+# 由 `lightpool codegen python` 根据链上类型的 IDL 生成，请勿手工修改
+"""
+LightPool 链上类型
+
+字段顺序与枚举序号与 Rust 定义一致，encode() / decode() 为 bincode 编解码。
+"""
+
+from __future__ import annotations
+
+import enum
+import struct
+from dataclasses import dataclass
+from typing import Dict, List, Optional, Tuple
+
+IDL_VERSION = 1
+
+
+class _Reader:
+    def __init__(self, data: bytes):
+        self.data = bytes(data)
+        self.pos = 0
+
+    def take(self, size: int) -> bytes:
+        if self.pos + size > len(self.data):
+            raise ValueError("unexpected end of input")
+        chunk = self.data[self.pos:self.pos + size]
+        self.pos += size
+        return chunk
+
+
+class _Prim:
+    def __init__(self, fmt: str):
+        self.struct = struct.Struct(fmt)
+
+    def pack(self, value, out: bytearray) -> None:
+        out += self.struct.pack(value)
+
+    def unpack(self, reader: _Reader):
+        return self.struct.unpack(reader.take(self.struct.size))[0]
+
+
+class _Int128:
+    def __init__(self, signed: bool):
+        self.signed = signed
+
+    def pack(self, value: int, out: bytearray) -> None:
+        out += value.to_bytes(16, "little", signed=self.signed)
+
+    def unpack(self, reader: _Reader) -> int:
+        return int.from_bytes(reader.take(16), "little", signed=self.signed)
+
+
+_U8 = _Prim("<B")
+_U16 = _Prim("<H")
+_U32 = _Prim("<I")
+_U64 = _Prim("<Q")
+_U128 = _Int128(False)
+_I8 = _Prim("<b")
+_I16 = _Prim("<h")
+_I32 = _Prim("<i")
+_I64 = _Prim("<q")
+_I128 = _Int128(True)
+_F32 = _Prim("<f")
+_F64 = _Prim("<d")
+_BOOL = _Prim("<?")
+# 序列与字符串的长度前缀
+_LEN = _U64
+
+
+class _Unit:
+    def pack(self, value, out: bytearray) -> None:
+        pass
+
+    def unpack(self, reader: _Reader):
+        return None
+
+
+class _Str:
+    def pack(self, value: str, out: bytearray) -> None:
+        data = value.encode("utf-8")
+        _LEN.pack(len(data), out)
+        out += data
+
+    def unpack(self, reader: _Reader) -> str:
+        return reader.take(_LEN.unpack(reader)).decode("utf-8")
+
+
+class _Bytes:
+    def pack(self, value: bytes, out: bytearray) -> None:
+        _LEN.pack(len(value), out)
+        out += value
+
+    def unpack(self, reader: _Reader) -> bytes:
+        return reader.take(_LEN.unpack(reader))
+
+
+class _FixedBytes:
+    def __init__(self, size: int):
+        self.size = size
+
+    def pack(self, value: bytes, out: bytearray) -> None:
+        if len(value) != self.size:
+            raise ValueError(f"expected {self.size} bytes, got {len(value)}")
+        out += value
+
+    def unpack(self, reader: _Reader) -> bytes:
+        return reader.take(self.size)
+
+
+class _Option:
+    def __init__(self, inner):
+        self.inner = inner
+
+    def pack(self, value, out: bytearray) -> None:
+        if value is None:
+            out.append(0)
+        else:
+            out.append(1)
+            self.inner.pack(value, out)
+
+    def unpack(self, reader: _Reader):
+        tag = reader.take(1)[0]
+        if tag > 1:
+            raise ValueError(f"invalid option tag: {tag}")
+        return self.inner.unpack(reader) if tag else None
+
+
+class _Seq:
+    def __init__(self, inner):
+        self.inner = inner
+
+    def pack(self, value, out: bytearray) -> None:
+        _LEN.pack(len(value), out)
+        for item in value:
+            self.inner.pack(item, out)
+
+    def unpack(self, reader: _Reader) -> list:
+        return [self.inner.unpack(reader) for _ in range(_LEN.unpack(reader))]
+
+
+class _Array:
+    def __init__(self, inner, size: int):
+        self.inner = inner
+        self.size = size
+
+    def pack(self, value, out: bytearray) -> None:
+        if len(value) != self.size:
+            raise ValueError(f"expected {self.size} items, got {len(value)}")
+        for item in value:
+            self.inner.pack(item, out)
+
+    def unpack(self, reader: _Reader) -> list:
+        return [self.inner.unpack(reader) for _ in range(self.size)]
+
+
+class _Tuple:
+    def __init__(self, *items):
+        self.items = items
+
+    def pack(self, value, out: bytearray) -> None:
+        if len(value) != len(self.items):
+            raise ValueError(f"expected {len(self.items)} items, got {len(value)}")
+        for codec, item in zip(self.items, value):
+            codec.pack(item, out)
+
+    def unpack(self, reader: _Reader) -> tuple:
+        return tuple(codec.unpack(reader) for codec in self.items)
+
+
+class _Map:
+    """按 dict 的迭代顺序编码，须与 Rust 一侧的映射顺序一致"""
+
+    def __init__(self, key, value):
+        self.key = key
+        self.value = value
+
+    def pack(self, value: dict, out: bytearray) -> None:
+        _LEN.pack(len(value), out)
+        for key, item in value.items():
+            self.key.pack(key, out)
+            self.value.pack(item, out)
+
+    def unpack(self, reader: _Reader) -> dict:
+        return {
+            self.key.unpack(reader): self.value.unpack(reader)
+            for _ in range(_LEN.unpack(reader))
+        }
+
+
+class _Ref:
+    """按名称引用本模块中的类型，允许前向引用"""
+
+    def __init__(self, name: str):
+        self.name = name
+
+    def pack(self, value, out: bytearray) -> None:
+        value._pack(out)
+
+    def unpack(self, reader: _Reader):
+        return globals()[self.name]._unpack(reader)
+
+
+class _Wire:
+    def encode(self) -> bytes:
+        """bincode 编码"""
+        out = bytearray()
+        self._pack(out)
+        return bytes(out)
+
+    @classmethod
+    def decode(cls, data: bytes):
+        """bincode 解码，不允许多余字节"""
+        reader = _Reader(data)
+        value = cls._unpack(reader)
+        if reader.pos != len(reader.data):
+            raise ValueError(f"{len(reader.data) - reader.pos} trailing bytes")
+        return value
+
+
+class _Struct(_Wire):
+    _FIELDS: tuple = ()
+
+    def _pack(self, out: bytearray) -> None:
+        for name, codec in self._FIELDS:
+            codec.pack(getattr(self, name), out)
+
+    @classmethod
+    def _unpack(cls, reader: _Reader):
+        return cls(*(codec.unpack(reader) for _, codec in cls._FIELDS))
+
+
+class _UnitEnum(_Wire):
+    def _pack(self, out: bytearray) -> None:
+        _U32.pack(self.value, out)
+
+    @classmethod
+    def _unpack(cls, reader: _Reader):
+        index = _U32.unpack(reader)
+        try:
+            return cls(index)
+        except ValueError:
+            raise ValueError(f"invalid {cls.__name__} variant: {index}") from None
+
+
+class _Enum(_Wire):
+    """带数据的枚举，每个变体是一个子类"""
+
+    _INDEX: int = 0
+    _FIELDS: tuple = ()
+    _VARIANTS: dict = {}
+
+    def _pack(self, out: bytearray) -> None:
+        _U32.pack(self._INDEX, out)
+        for name, codec in self._FIELDS:
+            codec.pack(getattr(self, name), out)
+
+    @classmethod
+    def _unpack(cls, reader: _Reader):
+        index = _U32.unpack(reader)
+        variant = cls._VARIANTS.get(index)
+        if variant is None:
+            raise ValueError(f"invalid {cls.__name__} variant: {index}")
+        return variant(*(codec.unpack(reader) for _, codec in variant._FIELDS))
+
+
+@dataclass
+class Action(_Struct):
+    inputs: List[ObjectId]
+    contract: Address
+    action: int
+    params: bytes
+    _FIELDS = (
+        ("inputs", _Seq(_Ref("ObjectId"))),
+        ("contract", _Ref("Address")),
+        ("action", _U64),
+        ("params", _Bytes()),
+    )
+
+
+@dataclass
+class Address(_Struct):
+    value: bytes
+    _FIELDS = (
+        ("value", _FixedBytes(32)),
+    )
+
+
+@dataclass
+class CancelOrderParams(_Struct):
+    order_id: OrderId
+    _FIELDS = (
+        ("order_id", _Ref("OrderId")),
+    )
+
+
+@dataclass
+class ObjectId(_Struct):
+    value: bytes
+    _FIELDS = (
+        ("value", _FixedBytes(32)),
+    )
+
+
+@dataclass
+class OrderId(_Struct):
+    value: bytes
+    _FIELDS = (
+        ("value", _FixedBytes(32)),
+    )
+
+
+class OrderSide(_UnitEnum, enum.IntEnum):
+    Buy = 0
+    Sell = 1
+
+
+class OrderType(_Enum):
+    pass
+
+
+@dataclass
+class OrderTypeLimit(OrderType):
+    tif: TimeInForce
+    _INDEX = 0
+    _FIELDS = (
+        ("tif", _Ref("TimeInForce")),
+    )
+
+
+@dataclass
+class OrderTypeMarket(OrderType):
+    slippage: int
+    _INDEX = 1
+    _FIELDS = (
+        ("slippage", _U64),
+    )
+
+
+@dataclass
+class OrderTypeTrigger(OrderType):
+    trigger_price: int
+    is_market: bool
+    trigger_type: int
+    _INDEX = 2
+    _FIELDS = (
+        ("trigger_price", _U64),
+        ("is_market", _BOOL),
+        ("trigger_type", _U8),
+    )
+
+
+OrderType._VARIANTS = {0: OrderTypeLimit, 1: OrderTypeMarket, 2: OrderTypeTrigger}
+
+
+@dataclass
+class PlaceOrderParams(_Struct):
+    side: OrderSide
+    amount: int
+    order_type: OrderType
+    limit_price: int
+    _FIELDS = (
+        ("side", _Ref("OrderSide")),
+        ("amount", _U64),
+        ("order_type", _Ref("OrderType")),
+        ("limit_price", _U64),
+    )
+
+
+@dataclass
+class Signature(_Struct):
+    part1: bytes
+    part2: bytes
+    _FIELDS = (
+        ("part1", _FixedBytes(32)),
+        ("part2", _FixedBytes(32)),
+    )
+
+
+@dataclass
+class SignedTransaction(_Struct):
+    transaction: Transaction
+    signatures: List[Signature]
+    _FIELDS = (
+        ("transaction", _Ref("Transaction")),
+        ("signatures", _Seq(_Ref("Signature"))),
+    )
+
+
+class TimeInForce(_UnitEnum, enum.IntEnum):
+    GTC = 0
+    IOC = 1
+    FOK = 2
+
+
+@dataclass
+class Transaction(_Struct):
+    sender: Address
+    expiration: int
+    actions: List[Action]
+    _FIELDS = (
+        ("sender", _Ref("Address")),
+        ("expiration", _U64),
+        ("actions", _Seq(_Ref("Action"))),
+    )
@@ -0,0 +1,287 @@
+//! 跨市场价差监控
+//!
+//! [`SpreadMonitor`] 跟踪多个市场的最优买卖价，对每一对市场计算
+//! 「在 A 市场按卖一买入、在 B 市场按买一卖出」的价差（基点）。
+//! 价差升至阈值以上时产出 [`SpreadEventKind::Opened`]，回落到阈值以下时产出
+//! [`SpreadEventKind::Closed`]，期间不重复产出。价差不含手续费与滑点。
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::stream::SelectAll;
+use futures_util::Stream;
+
+use crate::error::{Error, Result};
+use crate::ws::Ticker;
+
+/// 价差事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpreadEventKind {
+    /// 价差升至阈值以上
+    Opened,
+    /// 价差回落到阈值以下，或任一侧报价消失
+    Closed,
+}
+
+/// 价差穿越阈值事件
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadEvent {
+    pub kind: SpreadEventKind,
+    /// 买入的市场，按该市场卖一成交
+    pub buy_market: String,
+    /// 卖出的市场，按该市场买一成交
+    pub sell_market: String,
+    /// 买入市场的卖一价；报价消失时为 None
+    pub buy_price: Option<u64>,
+    /// 卖出市场的买一价；报价消失时为 None
+    pub sell_price: Option<u64>,
+    /// `(sell_price - buy_price) / buy_price`，单位基点；报价消失时为 None
+    pub spread_bps: Option<f64>,
+    /// 触发事件的行情时间戳
+    pub ts: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Quote {
+    bid: Option<u64>,
+    ask: Option<u64>,
+}
+
+/// 跨市场价差监控
+#[derive(Debug, Clone)]
+pub struct SpreadMonitor {
+    threshold_bps: f64,
+    quotes: BTreeMap<String, Quote>,
+    /// 价差处于阈值以上的（买入市场, 卖出市场）
+    open: BTreeSet<(String, String)>,
+}
+
+impl SpreadMonitor {
+    /// 监控 `markets` 之间的价差，至少需要两个不同的市场
+    pub fn new<I, S>(markets: I, threshold_bps: f64) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let quotes: BTreeMap<String, Quote> = markets
+            .into_iter()
+            .map(|market| (market.into(), Quote::default()))
+            .collect();
+        if quotes.len() < 2 {
+            return Err(Error::Validation(
+                "spread monitor needs at least two markets".to_string(),
+            ));
+        }
+        if !threshold_bps.is_finite() {
+            return Err(Error::Validation(format!(
+                "invalid spread threshold: {}",
+                threshold_bps
+            )));
+        }
+        Ok(Self {
+            threshold_bps,
+            quotes,
+            open: BTreeSet::new(),
+        })
+    }
+
+    /// 阈值（基点）
+    pub fn threshold_bps(&self) -> f64 {
+        self.threshold_bps
+    }
+
+    /// 当前 `buy_market` 买入、`sell_market` 卖出的价差（基点）；任一侧缺少报价时返回 None
+    pub fn spread_bps(&self, buy_market: &str, sell_market: &str) -> Option<f64> {
+        let ask = self.quotes.get(buy_market)?.ask?;
+        let bid = self.quotes.get(sell_market)?.bid?;
+        if ask == 0 {
+            return None;
+        }
+        Some((bid as f64 - ask as f64) / ask as f64 * 10_000.0)
+    }
+
+    /// 用行情摘要更新报价，返回穿越阈值的事件；未监控的市场被忽略
+    pub fn update(&mut self, ticker: &Ticker) -> Vec<SpreadEvent> {
+        self.update_quote(&ticker.market, ticker.best_bid, ticker.best_ask, ticker.ts)
+    }
+
+    /// 用最优买卖价更新报价，可由本地订单簿驱动
+    pub fn update_quote(
+        &mut self,
+        market: &str,
+        bid: Option<u64>,
+        ask: Option<u64>,
+        ts: u64,
+    ) -> Vec<SpreadEvent> {
+        let Some(quote) = self.quotes.get_mut(market) else {
+            return Vec::new();
+        };
+        *quote = Quote { bid, ask };
+
+        let others: Vec<String> = self
+            .quotes
+            .keys()
+            .filter(|other| *other != market)
+            .cloned()
+            .collect();
+        let mut events = Vec::new();
+        for other in others {
+            for (buy, sell) in [(market, other.as_str()), (other.as_str(), market)] {
+                if let Some(event) = self.check(buy, sell, ts) {
+                    events.push(event);
+                }
+            }
+        }
+        events
+    }
+
+    fn check(&mut self, buy_market: &str, sell_market: &str, ts: u64) -> Option<SpreadEvent> {
+        let spread_bps = self.spread_bps(buy_market, sell_market);
+        let above = spread_bps.is_some_and(|spread| spread >= self.threshold_bps);
+        let key = (buy_market.to_string(), sell_market.to_string());
+        let kind = match (self.open.contains(&key), above) {
+            (false, true) => {
+                self.open.insert(key);
+                SpreadEventKind::Opened
+            }
+            (true, false) => {
+                self.open.remove(&key);
+                SpreadEventKind::Closed
+            }
+            _ => return None,
+        };
+        Some(SpreadEvent {
+            kind,
+            buy_market: buy_market.to_string(),
+            sell_market: sell_market.to_string(),
+            buy_price: self.quotes[buy_market].ask,
+            sell_price: self.quotes[sell_market].bid,
+            spread_bps,
+            ts,
+        })
+    }
+
+    /// 合并多个市场的行情摘要流，产出价差事件
+    pub fn watch<S>(self, streams: impl IntoIterator<Item = S>) -> SpreadEvents<S>
+    where
+        S: Stream<Item = Ticker> + Unpin,
+    {
+        SpreadEvents {
+            monitor: self,
+            streams: futures_util::stream::select_all(streams),
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+/// 价差事件流，由 [`SpreadMonitor::watch`] 创建；所有输入流结束后结束
+pub struct SpreadEvents<S> {
+    monitor: SpreadMonitor,
+    streams: SelectAll<S>,
+    pending: VecDeque<SpreadEvent>,
+}
+
+impl<S> SpreadEvents<S> {
+    pub fn monitor(&self) -> &SpreadMonitor {
+        &self.monitor
+    }
+}
+
+impl<S> Stream for SpreadEvents<S>
+where
+    S: Stream<Item = Ticker> + Unpin,
+{
+    type Item = SpreadEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<SpreadEvent>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+            match Pin::new(&mut this.streams).poll_next(cx) {
+                Poll::Ready(Some(ticker)) => this.pending.extend(this.monitor.update(&ticker)),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{stream, StreamExt};
+
+    use super::*;
+
+    fn ticker(market: &str, bid: u64, ask: u64, ts: u64) -> Ticker {
+        Ticker {
+            market: market.to_string(),
+            best_bid: Some(bid),
+            best_ask: Some(ask),
+            last_price: bid,
+            volume_24h: 0,
+            ts,
+        }
+    }
+
+    #[test]
+    fn emits_once_per_threshold_crossing() {
+        let mut monitor = SpreadMonitor::new(["A", "B"], 50.0).unwrap();
+        assert!(monitor.update(&ticker("A", 9_990, 10_000, 1)).is_empty());
+        assert!(monitor.update(&ticker("B", 10_020, 10_030, 2)).is_empty());
+
+        // A 买入 10000，B 卖出 10060，价差 60 基点
+        let events = monitor.update(&ticker("B", 10_060, 10_070, 3));
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.kind, SpreadEventKind::Opened);
+        assert_eq!(
+            (event.buy_market.as_str(), event.sell_market.as_str()),
+            ("A", "B")
+        );
+        assert_eq!(
+            (event.buy_price, event.sell_price),
+            (Some(10_000), Some(10_060))
+        );
+        assert!((event.spread_bps.unwrap() - 60.0).abs() < 1e-9);
+
+        assert!(monitor.update(&ticker("B", 10_080, 10_090, 4)).is_empty());
+        let events = monitor.update(&ticker("A", 10_050, 10_060, 5));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, SpreadEventKind::Closed);
+    }
+
+    #[test]
+    fn missing_quote_closes_open_spread() {
+        let mut monitor = SpreadMonitor::new(["A", "B", "C"], 10.0).unwrap();
+        monitor.update(&ticker("A", 99, 100, 1));
+        let events = monitor.update(&ticker("C", 105, 106, 2));
+        assert_eq!(events[0].sell_market, "C");
+        let events = monitor.update_quote("C", None, Some(106), 3);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, SpreadEventKind::Closed);
+        assert_eq!(events[0].spread_bps, None);
+        assert!(monitor.update(&ticker("D", 1, 2, 4)).is_empty());
+    }
+
+    #[test]
+    fn requires_two_markets() {
+        assert!(SpreadMonitor::new(["A", "A"], 10.0).is_err());
+        assert!(SpreadMonitor::new(["A", "B"], f64::NAN).is_err());
+    }
+
+    #[tokio::test]
+    async fn watches_merged_streams() {
+        let a = stream::iter(vec![ticker("A", 99, 100, 1)]);
+        let b = stream::iter(vec![ticker("B", 110, 111, 2)]);
+        let events: Vec<_> = SpreadMonitor::new(["A", "B"], 100.0)
+            .unwrap()
+            .watch([a, b])
+            .collect()
+            .await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].buy_market, "A");
+    }
+}
@@ -0,0 +1,146 @@
+//! LightPool 编码与签名边车
+//!
+//! 以 gRPC 提供 SDK 的规范编码器与签名者，接口见 `proto/lightpool/sidecar/v1/sidecar.proto`。
+//! 非 Rust 服务（包括 Python SDK）委托同一个进程编码与签名，保证与链上格式逐字节一致。
+//! 私钥取自 `--key` 指定的加密私钥文件（口令取自 LIGHTPOOL_KEYSTORE_PASSWORD）或 `--private-key`；
+//! 两者都未提供时只提供编码方法。边车不访问网络，默认只监听本机地址。
+//! 设置 LIGHTPOOL_SIDECAR_TOKEN 后每个请求须携带 `authorization: Bearer <token>`；
+//! 监听非本机地址须同时指定 `--allow-remote` 并设置该令牌。
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use lightpool::crypto::{Ed25519Signer, Signer};
+use lightpool::keystore::Keystore;
+use lightpool::{Error, Result};
+use service::Sidecar;
+
+mod proto;
+mod service;
+
+/// 解锁加密私钥文件的口令
+const PASSWORD_ENV: &str = "LIGHTPOOL_KEYSTORE_PASSWORD";
+/// 客户端须携带的 bearer 令牌
+const TOKEN_ENV: &str = "LIGHTPOOL_SIDECAR_TOKEN";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "lightpool-sidecar",
+    version,
+    about = "LightPool 编码与签名 gRPC 边车"
+)]
+struct Args {
+    /// 监听地址
+    #[arg(
+        long,
+        env = "LIGHTPOOL_SIDECAR_LISTEN",
+        default_value = "127.0.0.1:50151"
+    )]
+    listen: SocketAddr,
+    /// 允许监听非本机地址，须同时设置 LIGHTPOOL_SIDECAR_TOKEN
+    #[arg(long)]
+    allow_remote: bool,
+    /// 加密私钥文件
+    #[arg(long = "key", value_name = "KEYSTORE")]
+    keystore: Option<PathBuf>,
+    /// 十六进制私钥，优先级低于 --key
+    #[arg(long, env = "LIGHTPOOL_PRIVATE_KEY", hide_env_values = true)]
+    private_key: Option<String>,
+}
+
+impl Args {
+    /// 非本机地址只在显式允许且设置了令牌时监听
+    fn check_listen(&self, token: Option<&str>) -> Result<()> {
+        if self.listen.ip().is_loopback() {
+            return Ok(());
+        }
+        if !self.allow_remote {
+            return Err(Error::Validation(format!(
+                "refusing to listen on non-loopback address {} without --allow-remote",
+                self.listen
+            )));
+        }
+        if token.is_none() {
+            return Err(Error::Validation(format!(
+                "{} is required when listening on {}",
+                TOKEN_ENV, self.listen
+            )));
+        }
+        Ok(())
+    }
+
+    fn signer(&self) -> Result<Option<Ed25519Signer>> {
+        if let Some(path) = &self.keystore {
+            let keystore = Keystore::load(path)?;
+            let password = std::env::var(PASSWORD_ENV).map_err(|_| {
+                Error::Validation(format!("{} is required to unlock --key", PASSWORD_ENV))
+            })?;
+            return keystore.decrypt(&password).map(Some);
+        }
+        self.private_key
+            .as_deref()
+            .map(Ed25519Signer::from_hex)
+            .transpose()
+    }
+}
+
+async fn run(args: Args) -> Result<()> {
+    let token = std::env::var(TOKEN_ENV)
+        .ok()
+        .filter(|token| !token.is_empty());
+    args.check_listen(token.as_deref())?;
+    let signer = args.signer()?;
+    match &signer {
+        Some(signer) => eprintln!("signing as {}", signer.address()),
+        None => eprintln!("no signing key loaded, only encoding is available"),
+    }
+    eprintln!("listening on {}", args.listen);
+    let mut sidecar = Sidecar::new(signer);
+    if let Some(token) = token {
+        sidecar = sidecar.with_token(token);
+    }
+    tonic::transport::Server::builder()
+        .add_service(sidecar)
+        .serve(args.listen)
+        .await
+        .map_err(|e| Error::Network(format!("grpc: {}", e)))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Args::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(listen: &str, allow_remote: bool) -> Args {
+        let mut argv = vec!["lightpool-sidecar", "--listen", listen];
+        if allow_remote {
+            argv.push("--allow-remote");
+        }
+        Args::try_parse_from(argv).unwrap()
+    }
+
+    #[test]
+    fn remote_listen_requires_flag_and_token() {
+        assert!(args("127.0.0.1:50151", false).check_listen(None).is_ok());
+        assert!(args("[::1]:50151", false).check_listen(None).is_ok());
+        assert!(args("0.0.0.0:50151", false)
+            .check_listen(Some("secret"))
+            .is_err());
+        assert!(args("0.0.0.0:50151", true).check_listen(None).is_err());
+        assert!(args("0.0.0.0:50151", true)
+            .check_listen(Some("secret"))
+            .is_ok());
+    }
+}
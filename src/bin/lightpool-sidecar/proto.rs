@@ -0,0 +1,135 @@
+//! `proto/lightpool/sidecar/v1/sidecar.proto` 的消息类型
+//!
+//! 与 proto 文件手工保持一致，构建时无需 protoc。
+
+/// 服务的 gRPC 路径前缀
+pub const SERVICE: &str = "lightpool.sidecar.v1.Sidecar";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Side {
+    Unspecified = 0,
+    Buy = 1,
+    Sell = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum OrderType {
+    Limit = 0,
+    Market = 1,
+    Trigger = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum TimeInForce {
+    Gtc = 0,
+    Ioc = 1,
+    Fok = 2,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Action {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub inputs: Vec<Vec<u8>>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub contract: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub action: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub params: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EncodePlaceOrderRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub market_id: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub balance_id: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub market_address: Vec<u8>,
+    #[prost(enumeration = "Side", tag = "4")]
+    pub side: i32,
+    #[prost(uint64, tag = "5")]
+    pub amount: u64,
+    #[prost(uint64, tag = "6")]
+    pub limit_price: u64,
+    #[prost(enumeration = "OrderType", tag = "7")]
+    pub order_type: i32,
+    #[prost(enumeration = "TimeInForce", tag = "8")]
+    pub time_in_force: i32,
+    #[prost(uint64, tag = "9")]
+    pub slippage_bps: u64,
+    #[prost(uint64, tag = "10")]
+    pub trigger_price: u64,
+    #[prost(bool, tag = "11")]
+    pub trigger_is_market: bool,
+    #[prost(uint32, tag = "12")]
+    pub trigger_type: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EncodeCancelOrderRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub market_id: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub order_id: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub market_address: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EncodeResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub params: Vec<u8>,
+    #[prost(message, optional, tag = "2")]
+    pub action: Option<Action>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EncodeTransactionRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub sender: Vec<u8>,
+    #[prost(uint64, optional, tag = "2")]
+    pub expiration: Option<u64>,
+    #[prost(message, repeated, tag = "3")]
+    pub actions: Vec<Action>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EncodeTransactionResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub signing_bytes: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignTransactionRequest {
+    #[prost(uint64, optional, tag = "1")]
+    pub expiration: Option<u64>,
+    #[prost(message, repeated, tag = "2")]
+    pub actions: Vec<Action>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignTransactionResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub signing_bytes: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub signed_transaction: Vec<u8>,
+    #[prost(string, tag = "4")]
+    pub json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetSignerRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetSignerResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub address: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub public_key: Vec<u8>,
+}
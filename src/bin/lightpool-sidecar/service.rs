@@ -0,0 +1,492 @@
+//! gRPC 服务实现
+//!
+//! 方法都是同步的编码或签名，不访问网络；请求中的字段错误返回 `INVALID_ARGUMENT`，
+//! 未加载私钥时签名类方法返回 `FAILED_PRECONDITION`；配置了令牌时缺少或不匹配的
+//! `authorization` 返回 `UNAUTHENTICATED`。
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use lightpool::crypto::{Ed25519Signer, Signer};
+use lightpool::order::{cancel_action, OrderRequest, OrderType, PlaceOrderParams, TimeInForce};
+use lightpool::transaction::{Action, Transaction, SPOT_CONTRACT};
+use lightpool::types::{Address, ObjectId, OrderId, OrderSide};
+use lightpool::Error;
+use tonic::codegen::{http, Body, BoxFuture, Context, Poll, Service, StdError};
+use tonic::server::{Grpc, NamedService, UnaryService};
+use tonic::{Code, Status};
+use tonic_prost::ProstCodec;
+
+use crate::proto;
+
+/// 编码与签名服务
+#[derive(Clone, Default)]
+pub struct Sidecar {
+    signer: Option<Arc<Ed25519Signer>>,
+    token: Option<Arc<str>>,
+}
+
+impl Sidecar {
+    /// `signer` 为 None 时只提供编码方法
+    pub fn new(signer: Option<Ed25519Signer>) -> Self {
+        Self {
+            signer: signer.map(Arc::new),
+            token: None,
+        }
+    }
+
+    /// 要求每个请求携带 `authorization: Bearer <token>`
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(Arc::from(token.into()));
+        self
+    }
+
+    fn authorized<B>(&self, request: &http::Request<B>) -> bool {
+        let Some(token) = &self.token else {
+            return true;
+        };
+        request
+            .headers()
+            .get(http::header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|presented| constant_time_eq(presented.as_bytes(), token.as_bytes()))
+    }
+
+    fn signer(&self) -> Result<&Ed25519Signer, Status> {
+        self.signer
+            .as_deref()
+            .ok_or_else(|| Status::failed_precondition("no signing key is loaded"))
+    }
+
+    fn encode_place_order(
+        &self,
+        request: proto::EncodePlaceOrderRequest,
+    ) -> Result<proto::EncodeResponse, Status> {
+        let side = match proto::Side::try_from(request.side) {
+            Ok(proto::Side::Buy) => OrderSide::Buy,
+            Ok(proto::Side::Sell) => OrderSide::Sell,
+            _ => return Err(Status::invalid_argument("side must be buy or sell")),
+        };
+        let order_type = match proto::OrderType::try_from(request.order_type) {
+            Ok(proto::OrderType::Limit) => OrderType::Limit {
+                tif: match proto::TimeInForce::try_from(request.time_in_force) {
+                    Ok(proto::TimeInForce::Gtc) => TimeInForce::Gtc,
+                    Ok(proto::TimeInForce::Ioc) => TimeInForce::Ioc,
+                    Ok(proto::TimeInForce::Fok) => TimeInForce::Fok,
+                    Err(_) => return Err(Status::invalid_argument("invalid time in force")),
+                },
+            },
+            Ok(proto::OrderType::Market) => OrderType::Market {
+                slippage: request.slippage_bps,
+            },
+            Ok(proto::OrderType::Trigger) => OrderType::Trigger {
+                trigger_price: request.trigger_price,
+                is_market: request.trigger_is_market,
+                trigger_type: u8::try_from(request.trigger_type)
+                    .map_err(|_| Status::invalid_argument("trigger type must fit in u8"))?,
+            },
+            Err(_) => return Err(Status::invalid_argument("invalid order type")),
+        };
+        let params = PlaceOrderParams {
+            side,
+            amount: request.amount,
+            order_type,
+            limit_price: request.limit_price,
+        };
+        let mut order = OrderRequest::spot(
+            ObjectId(id(&request.market_id, "market id")?),
+            ObjectId(id(&request.balance_id, "balance id")?),
+            params,
+        );
+        order.market_address = market_address(&request.market_address)?;
+        Ok(proto::EncodeResponse {
+            params: order.params.encode(),
+            action: Some(to_proto(&order.to_action().map_err(status)?)),
+        })
+    }
+
+    fn encode_cancel_order(
+        &self,
+        request: proto::EncodeCancelOrderRequest,
+    ) -> Result<proto::EncodeResponse, Status> {
+        let action = cancel_action(
+            market_address(&request.market_address)?,
+            ObjectId(id(&request.market_id, "market id")?),
+            OrderId(id(&request.order_id, "order id")?),
+        )
+        .map_err(status)?;
+        Ok(proto::EncodeResponse {
+            params: action.params.clone(),
+            action: Some(to_proto(&action)),
+        })
+    }
+
+    fn encode_transaction(
+        &self,
+        request: proto::EncodeTransactionRequest,
+    ) -> Result<proto::EncodeTransactionResponse, Status> {
+        let transaction = transaction(
+            Address(id(&request.sender, "sender")?),
+            request.expiration,
+            &request.actions,
+        )?;
+        Ok(proto::EncodeTransactionResponse {
+            signing_bytes: transaction.signing_bytes(),
+        })
+    }
+
+    fn sign_transaction(
+        &self,
+        request: proto::SignTransactionRequest,
+    ) -> Result<proto::SignTransactionResponse, Status> {
+        let signer = self.signer()?;
+        let transaction = transaction(signer.address(), request.expiration, &request.actions)?;
+        let signing_bytes = transaction.signing_bytes();
+        let signed = transaction.sign(signer);
+        Ok(proto::SignTransactionResponse {
+            signing_bytes,
+            signature: signed.signatures[0].to_bytes().to_vec(),
+            signed_transaction: bincode::serialize(&signed)
+                .expect("signed transaction is always serializable"),
+            json: serde_json::to_string(&signed).map_err(|e| status(e.into()))?,
+        })
+    }
+
+    fn get_signer(
+        &self,
+        _request: proto::GetSignerRequest,
+    ) -> Result<proto::GetSignerResponse, Status> {
+        let signer = self.signer()?;
+        Ok(proto::GetSignerResponse {
+            address: signer.address().to_bytes().to_vec(),
+            public_key: signer.public_key().to_vec(),
+        })
+    }
+}
+
+/// 比较耗时与首个不同字节的位置无关
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn status(error: Error) -> Status {
+    match error {
+        Error::Validation(msg) | Error::Decode(msg) => Status::invalid_argument(msg),
+        other => Status::internal(other.to_string()),
+    }
+}
+
+fn id(bytes: &[u8], what: &str) -> Result<[u8; 32], Status> {
+    bytes.try_into().map_err(|_| {
+        Status::invalid_argument(format!("{} must be 32 bytes, got {}", what, bytes.len()))
+    })
+}
+
+/// 为空时为现货合约
+fn market_address(bytes: &[u8]) -> Result<Address, Status> {
+    if bytes.is_empty() {
+        return Ok(SPOT_CONTRACT);
+    }
+    Ok(Address(id(bytes, "market address")?))
+}
+
+fn to_proto(action: &Action) -> proto::Action {
+    proto::Action {
+        inputs: action.inputs.iter().map(|input| input.0.to_vec()).collect(),
+        contract: action.contract.to_bytes().to_vec(),
+        action: action.action,
+        params: action.params.clone(),
+    }
+}
+
+fn from_proto(action: &proto::Action) -> Result<Action, Status> {
+    Ok(Action {
+        inputs: action
+            .inputs
+            .iter()
+            .map(|input| id(input, "action input").map(ObjectId))
+            .collect::<Result<_, _>>()?,
+        contract: Address(id(&action.contract, "contract")?),
+        action: action.action,
+        params: action.params.clone(),
+    })
+}
+
+fn transaction(
+    sender: Address,
+    expiration: Option<u64>,
+    actions: &[proto::Action],
+) -> Result<Transaction, Status> {
+    if actions.is_empty() {
+        return Err(Status::invalid_argument("at least one action is required"));
+    }
+    Ok(Transaction {
+        sender,
+        expiration: expiration.unwrap_or(u64::MAX),
+        actions: actions.iter().map(from_proto).collect::<Result<_, _>>()?,
+    })
+}
+
+/// 以同步函数实现的一元方法
+struct Unary<F>(F);
+
+impl<Req, Resp, F> UnaryService<Req> for Unary<F>
+where
+    F: FnMut(Req) -> Result<Resp, Status>,
+{
+    type Response = Resp;
+    type Future = std::future::Ready<Result<tonic::Response<Resp>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+        std::future::ready((self.0)(request.into_inner()).map(tonic::Response::new))
+    }
+}
+
+impl NamedService for Sidecar {
+    const NAME: &'static str = proto::SERVICE;
+}
+
+impl<B> Service<http::Request<B>> for Sidecar
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        macro_rules! route {
+            ($method:ident) => {{
+                let sidecar = self.clone();
+                Box::pin(async move {
+                    let handler = Unary(move |req| sidecar.$method(req));
+                    Ok(Grpc::new(ProstCodec::default())
+                        .unary(handler, request)
+                        .await)
+                })
+            }};
+        }
+
+        if !self.authorized(&request) {
+            return Box::pin(async move {
+                Ok(Status::unauthenticated("missing or invalid bearer token").into_http())
+            });
+        }
+        let method = request
+            .uri()
+            .path()
+            .strip_prefix(&format!("/{}/", proto::SERVICE))
+            .unwrap_or_default()
+            .to_string();
+        match method.as_str() {
+            "EncodePlaceOrder" => route!(encode_place_order),
+            "EncodeCancelOrder" => route!(encode_cancel_order),
+            "EncodeTransaction" => route!(encode_transaction),
+            "SignTransaction" => route!(sign_transaction),
+            "GetSigner" => route!(get_signer),
+            _ => Box::pin(async move {
+                Ok(
+                    Status::new(Code::Unimplemented, format!("unknown method: {}", method))
+                        .into_http(),
+                )
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::client::Grpc as Client;
+    use tonic::codegen::http::uri::PathAndQuery;
+    use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
+    use tonic::transport::{Channel, Server};
+
+    async fn serve(sidecar: Sidecar) -> Client<Channel> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(
+            Server::builder()
+                .add_service(sidecar)
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        Client::new(Channel::from_shared(url).unwrap().connect().await.unwrap())
+    }
+
+    async fn call<Req, Resp>(
+        client: &mut Client<Channel>,
+        method: &str,
+        request: Req,
+    ) -> Result<Resp, Status>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        send(client, method, tonic::Request::new(request)).await
+    }
+
+    async fn send<Req, Resp>(
+        client: &mut Client<Channel>,
+        method: &str,
+        request: tonic::Request<Req>,
+    ) -> Result<Resp, Status>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        client.ready().await.unwrap();
+        let path = PathAndQuery::try_from(format!("/{}/{}", proto::SERVICE, method)).unwrap();
+        client
+            .unary(request, path, ProstCodec::default())
+            .await
+            .map(tonic::Response::into_inner)
+    }
+
+    #[tokio::test]
+    async fn encodes_byte_exact_with_the_sdk() {
+        let mut client = serve(Sidecar::default()).await;
+        let response: proto::EncodeResponse = call(
+            &mut client,
+            "EncodePlaceOrder",
+            proto::EncodePlaceOrderRequest {
+                market_id: vec![1; 32],
+                balance_id: vec![2; 32],
+                side: proto::Side::Sell as i32,
+                amount: 5_000_000,
+                limit_price: 50_000_000_000,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let expected = OrderRequest::spot(
+            ObjectId([1; 32]),
+            ObjectId([2; 32]),
+            PlaceOrderParams::limit(OrderSide::Sell, 5_000_000, 50_000_000_000),
+        );
+        assert_eq!(response.params, expected.params.encode());
+        let action = response.action.unwrap();
+        assert_eq!(from_proto(&action).unwrap(), expected.to_action().unwrap());
+
+        let signer = Ed25519Signer::generate();
+        let encoded: proto::EncodeTransactionResponse = call(
+            &mut client,
+            "EncodeTransaction",
+            proto::EncodeTransactionRequest {
+                sender: signer.address().to_bytes().to_vec(),
+                expiration: Some(100),
+                actions: vec![action],
+            },
+        )
+        .await
+        .unwrap();
+        let transaction = Transaction {
+            sender: signer.address(),
+            expiration: 100,
+            actions: vec![expected.to_action().unwrap()],
+        };
+        assert_eq!(encoded.signing_bytes, transaction.signing_bytes());
+
+        let rejected = call::<_, proto::EncodeResponse>(
+            &mut client,
+            "EncodePlaceOrder",
+            proto::EncodePlaceOrderRequest::default(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(rejected.code(), Code::InvalidArgument);
+        let unsigned = call::<_, proto::GetSignerResponse>(
+            &mut client,
+            "GetSigner",
+            proto::GetSignerRequest {},
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(unsigned.code(), Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn signs_with_the_loaded_key() {
+        let signer = Ed25519Signer::generate();
+        let mut client = serve(Sidecar::new(Some(signer.clone()))).await;
+        let cancel = cancel_action(SPOT_CONTRACT, ObjectId([1; 32]), OrderId([3; 32])).unwrap();
+        let encoded: proto::EncodeResponse = call(
+            &mut client,
+            "EncodeCancelOrder",
+            proto::EncodeCancelOrderRequest {
+                market_id: vec![1; 32],
+                order_id: vec![3; 32],
+                market_address: Vec::new(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(encoded.action, Some(to_proto(&cancel)));
+
+        let response: proto::SignTransactionResponse = call(
+            &mut client,
+            "SignTransaction",
+            proto::SignTransactionRequest {
+                expiration: None,
+                actions: vec![to_proto(&cancel)],
+            },
+        )
+        .await
+        .unwrap();
+        let expected = Transaction {
+            sender: signer.address(),
+            expiration: u64::MAX,
+            actions: vec![cancel],
+        }
+        .sign(&signer);
+        assert_eq!(response.signature, expected.signatures[0].to_bytes());
+        assert_eq!(
+            response.signed_transaction,
+            bincode::serialize(&expected).unwrap()
+        );
+        assert_eq!(response.json, serde_json::to_string(&expected).unwrap());
+
+        let key: proto::GetSignerResponse =
+            call(&mut client, "GetSigner", proto::GetSignerRequest {})
+                .await
+                .unwrap();
+        assert_eq!(key.public_key, signer.public_key());
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_without_the_token() {
+        let signer = Ed25519Signer::generate();
+        let mut client = serve(Sidecar::new(Some(signer.clone())).with_token("secret")).await;
+        let missing = call::<_, proto::GetSignerResponse>(
+            &mut client,
+            "GetSigner",
+            proto::GetSignerRequest {},
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(missing.code(), Code::Unauthenticated);
+
+        let bearer = |token: &str| {
+            let mut request = tonic::Request::new(proto::GetSignerRequest {});
+            request.metadata_mut().insert(
+                "authorization",
+                format!("Bearer {}", token).parse().unwrap(),
+            );
+            request
+        };
+        let wrong = send::<_, proto::GetSignerResponse>(&mut client, "GetSigner", bearer("nope"))
+            .await
+            .unwrap_err();
+        assert_eq!(wrong.code(), Code::Unauthenticated);
+        let key: proto::GetSignerResponse = send(&mut client, "GetSigner", bearer("secret"))
+            .await
+            .unwrap();
+        assert_eq!(key.public_key, signer.public_key());
+    }
+}
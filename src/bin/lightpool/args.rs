@@ -0,0 +1,161 @@
+//! 各命令共用的参数类型与输出
+
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+use lightpool::crypto::Ed25519Signer;
+use lightpool::keystore::Keystore;
+use lightpool::order::TimeInForce;
+use lightpool::transaction::SignedTransaction;
+use lightpool::types::{Address, OrderSide};
+use lightpool::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// 解锁私钥文件的口令所在的环境变量
+const PASSWORD_ENV: &str = "LIGHTPOOL_KEYSTORE_PASSWORD";
+
+/// 签名私钥来源
+#[derive(Debug, Clone, Args)]
+pub struct KeyArgs {
+    /// 加密私钥文件；口令取自 LIGHTPOOL_KEYSTORE_PASSWORD，未设置时交互输入
+    #[arg(long = "key", value_name = "KEYSTORE")]
+    keystore: Option<PathBuf>,
+    /// 十六进制私钥，优先级低于 --key
+    #[arg(long, env = "LIGHTPOOL_PRIVATE_KEY", hide_env_values = true)]
+    private_key: Option<String>,
+}
+
+impl KeyArgs {
+    /// 未指定任何私钥来源时使用 `keystore`
+    pub fn default_keystore(&mut self, keystore: Option<PathBuf>) {
+        if self.keystore.is_none() && self.private_key.is_none() {
+            self.keystore = keystore;
+        }
+    }
+
+    /// 解锁签名私钥
+    pub fn signer(&self) -> Result<Ed25519Signer> {
+        self.try_signer()?.ok_or_else(|| {
+            Error::Validation("a signing key is required: pass --key or --private-key".to_string())
+        })
+    }
+
+    /// 解锁签名私钥，两种来源都未提供时返回 None
+    pub fn try_signer(&self) -> Result<Option<Ed25519Signer>> {
+        if let Some(path) = &self.keystore {
+            let keystore = Keystore::load(path)?;
+            let password = match std::env::var(PASSWORD_ENV) {
+                Ok(password) => password,
+                Err(_) => {
+                    rpassword::prompt_password(format!("password for {}: ", keystore.address()))?
+                }
+            };
+            return keystore.decrypt(&password).map(Some);
+        }
+        self.private_key
+            .as_deref()
+            .map(Ed25519Signer::from_hex)
+            .transpose()
+    }
+}
+
+/// 配置与缓存目录 `~/.lightpool`
+pub fn lightpool_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".lightpool"))
+}
+
+/// 读取加密私钥文件中明文保存的地址，无需口令
+pub fn keystore_address(path: &Path) -> Result<Address> {
+    Keystore::load(path)?.address().parse()
+}
+
+/// 读取新私钥文件的口令：优先取环境变量，否则交互输入两次确认
+pub fn new_password() -> Result<String> {
+    if let Ok(password) = std::env::var(PASSWORD_ENV) {
+        return non_empty(password);
+    }
+    let password = rpassword::prompt_password("new keystore password: ")?;
+    let confirm = rpassword::prompt_password("repeat password: ")?;
+    if password != confirm {
+        return Err(Error::Validation("passwords do not match".to_string()));
+    }
+    non_empty(password)
+}
+
+fn non_empty(password: String) -> Result<String> {
+    if password.is_empty() {
+        return Err(Error::Validation(
+            "keystore password must not be empty".to_string(),
+        ));
+    }
+    Ok(password)
+}
+
+/// 订单方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl From<Side> for OrderSide {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Buy => OrderSide::Buy,
+            Side::Sell => OrderSide::Sell,
+        }
+    }
+}
+
+/// 订单有效期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tif {
+    Gtc,
+    Ioc,
+    Fok,
+}
+
+impl From<Tif> for TimeInForce {
+    fn from(tif: Tif) -> Self {
+        match tif {
+            Tif::Gtc => TimeInForce::Gtc,
+            Tif::Ioc => TimeInForce::Ioc,
+            Tif::Fok => TimeInForce::Fok,
+        }
+    }
+}
+
+/// 读取 JSON 文件
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let text = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// 读取 JSON 或 TOML 文件，扩展名为 `.toml` 时按 TOML 解析
+pub fn read_input<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    if path.extension().is_some_and(|ext| ext == "toml") {
+        let text = std::fs::read_to_string(path)?;
+        return toml::from_str(&text)
+            .map_err(|e| Error::Decode(format!("{}: {}", path.display(), e)));
+    }
+    read_json(path)
+}
+
+/// 以格式化 JSON 输出到标准输出
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// 读取已签名交易；扩展名为 `.bin` 时按 bincode 解析，否则按 JSON 解析
+pub fn read_signed(path: &Path) -> Result<SignedTransaction> {
+    if path.extension().is_some_and(|ext| ext == "bin") {
+        let bytes = std::fs::read(path)?;
+        return bincode::deserialize(&bytes)
+            .map_err(|e| Error::Decode(format!("invalid signed transaction: {}", e)));
+    }
+    read_json(path)
+}
@@ -0,0 +1,318 @@
+//! 批量交易：`orders place --csv` 与共用的分批签名提交
+//!
+//! CSV 首行为列名，`tif` 列可省略或留空（默认 GTC），数量与价格按登记表中的小数位数书写：
+//!
+//! ```text
+//! market,side,size,price,tif
+//! BTC-USDC,buy,0.5,50000,gtc
+//! BTC-USDC,sell,0.25,51000,
+//! ```
+//!
+//! 全部行先按登记表与 profile 的风控限额校验，有无效行时不提交任何交易，除非指定 `--skip-invalid`。
+//! 有效行按 `--batch-size` 打包为多笔交易，报告逐行给出所在批次与执行结果。
+
+use std::path::PathBuf;
+
+use clap::Args;
+use lightpool::crypto::Ed25519Signer;
+use lightpool::order::{OrderType, PlaceOrderParams, TimeInForce};
+use lightpool::transaction::{Action, TransactionBuilder};
+use lightpool::{Error, LightPoolClient, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::args::{KeyArgs, Side, Tif};
+use crate::markets::Registry;
+use crate::output::{emit, Output};
+use crate::submit::SubmitMode;
+
+#[derive(Debug, Args)]
+pub struct PlaceArgs {
+    #[command(flatten)]
+    pub key: KeyArgs,
+    /// 订单 CSV 文件
+    #[arg(long)]
+    csv: PathBuf,
+    /// 每笔交易包含的订单数
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    batch_size: u32,
+    /// 跳过无效行，只提交有效的订单
+    #[arg(long)]
+    skip_invalid: bool,
+}
+
+/// CSV 中的一行
+#[derive(Debug, Deserialize)]
+struct Row {
+    market: String,
+    side: Side,
+    size: String,
+    price: String,
+    #[serde(default)]
+    tif: Option<Tif>,
+}
+
+/// 报告中的一行或一个条目
+#[derive(Debug, Clone, Serialize)]
+pub struct Outcome {
+    /// CSV 行号，或条目的序号
+    pub row: u64,
+    /// 条目的描述字段，例如市场与订单ID
+    #[serde(flatten)]
+    pub detail: Map<String, Value>,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Outcome {
+    pub fn new(row: u64, detail: Value) -> Self {
+        let detail = match detail {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        };
+        Self {
+            row,
+            detail,
+            status: "pending".to_string(),
+            batch: None,
+            digest: None,
+            error: None,
+        }
+    }
+
+    pub fn invalid(mut self, error: Error) -> Self {
+        self.status = "invalid".to_string();
+        self.error = Some(error.to_string());
+        self
+    }
+
+    /// 已签名、模拟或执行成功
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status.as_str(), "signed" | "success" | "pending")
+    }
+}
+
+/// 分批签名并按模式提交，逐条更新结果；返回每笔交易的摘要
+///
+/// 单笔交易提交失败不影响其余批次。
+pub async fn submit(
+    client: &LightPoolClient,
+    signer: &Ed25519Signer,
+    items: &mut [(Outcome, Action)],
+    batch_size: usize,
+    mode: SubmitMode,
+) -> Result<Vec<Value>> {
+    let mut transactions = Vec::new();
+    for (batch, chunk) in items.chunks_mut(batch_size).enumerate() {
+        let tx = chunk
+            .iter()
+            .fold(TransactionBuilder::new(), |builder, (_, action)| {
+                builder.action(action.clone())
+            })
+            .build_and_sign(signer)?;
+        let (status, digest, error, summary) = match mode {
+            SubmitMode::DryRun => (
+                "signed".to_string(),
+                None,
+                None,
+                json!({ "batch": batch, "transaction": tx }),
+            ),
+            SubmitMode::Simulate => match client.simulate_transaction(&tx).await {
+                Ok(receipt) => (
+                    receipt.status.clone(),
+                    None,
+                    None,
+                    json!({ "batch": batch, "transaction": tx, "status": receipt.status, "events": receipt.events }),
+                ),
+                Err(e) => (
+                    "error".to_string(),
+                    None,
+                    Some(e.to_string()),
+                    json!({ "batch": batch, "error": e.to_string() }),
+                ),
+            },
+            SubmitMode::Submit => match client.submit_transaction(&tx).await {
+                Ok(result) => {
+                    let status = if result.receipt.status.is_empty() {
+                        "pending".to_string()
+                    } else {
+                        result.receipt.status.clone()
+                    };
+                    (
+                        status.clone(),
+                        Some(result.digest.clone()),
+                        None,
+                        json!({ "batch": batch, "digest": result.digest, "status": status }),
+                    )
+                }
+                Err(e) => (
+                    "error".to_string(),
+                    None,
+                    Some(e.to_string()),
+                    json!({ "batch": batch, "error": e.to_string() }),
+                ),
+            },
+        };
+        tracing::info!(batch, actions = chunk.len(), status = %status, "batch processed");
+        for (outcome, _) in chunk.iter_mut() {
+            outcome.status = status.clone();
+            outcome.batch = Some(batch);
+            outcome.digest = digest.clone();
+            outcome.error = error.clone();
+        }
+        transactions.push(summary);
+    }
+    Ok(transactions)
+}
+
+/// 输出报告：JSON 包含逐条结果与每笔交易，其余格式只列出逐条结果
+pub fn report(
+    output: Option<Output>,
+    command: &str,
+    outcomes: &[Outcome],
+    transactions: &[Value],
+) -> Result<()> {
+    match output.unwrap_or(Output::Json) {
+        Output::Json => emit(
+            Output::Json,
+            command,
+            &json!({ "rows": outcomes, "transactions": transactions }),
+        ),
+        output => emit(output, command, outcomes),
+    }
+}
+
+/// 有失败的条目时返回错误：有无效条目时为参数错误，其次为 RPC 错误，否则为交易执行失败
+pub fn check(outcomes: &[Outcome]) -> Result<()> {
+    let failed: Vec<&Outcome> = outcomes.iter().filter(|outcome| !outcome.is_ok()).collect();
+    let Some(first) = failed.first() else {
+        return Ok(());
+    };
+    let message = format!("{} of {} entries failed", failed.len(), outcomes.len());
+    let has = |status: &str| failed.iter().any(|outcome| outcome.status == status);
+    if has("invalid") {
+        Err(Error::Validation(message))
+    } else if has("error") {
+        Err(Error::Rpc {
+            code: None,
+            message,
+        })
+    } else {
+        Err(Error::Rejected {
+            digest: first.digest.clone().unwrap_or_default(),
+            status: message,
+        })
+    }
+}
+
+/// 按登记表与客户端的风控限额校验一行并生成下单操作
+fn order_action(client: &LightPoolClient, registry: &Registry, row: &Row) -> Result<Action> {
+    let (market, entry) = registry.get(&row.market)?;
+    let side = row.side.into();
+    let amount = entry.size(&row.size)?;
+    let limit_price = entry.price(&row.price)?;
+    if amount == 0 || limit_price == 0 {
+        return Err(Error::Validation(
+            "size and price must be positive".to_string(),
+        ));
+    }
+    let params = PlaceOrderParams {
+        side,
+        amount,
+        order_type: OrderType::Limit {
+            tif: row.tif.map_or(TimeInForce::Gtc, Into::into),
+        },
+        limit_price,
+    };
+    client.check_risk(&params)?;
+    entry.order_request(&market, params)?.to_action()
+}
+
+/// 读取 CSV，逐行校验
+fn read_rows(
+    client: &LightPoolClient,
+    path: &std::path::Path,
+    registry: &Registry,
+) -> Result<Vec<(Outcome, Option<Action>)>> {
+    let csv_error = |e: csv::Error| Error::Decode(format!("{}: {}", path.display(), e));
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(csv_error)?;
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        let line = record.position().map_or(0, |position| position.line());
+        let detail: Map<String, Value> = headers
+            .iter()
+            .zip(record.iter())
+            .map(|(key, value)| (key.to_string(), Value::String(value.to_string())))
+            .collect();
+        let outcome = Outcome::new(line, Value::Object(detail));
+        let action = record
+            .deserialize::<Row>(Some(&headers))
+            .map_err(|e| Error::Decode(e.to_string()))
+            .and_then(|row| order_action(client, registry, &row));
+        rows.push(match action {
+            Ok(action) => (outcome, Some(action)),
+            Err(e) => (outcome.invalid(e), None),
+        });
+    }
+    if rows.is_empty() {
+        return Err(Error::Validation(format!(
+            "{} contains no orders",
+            path.display()
+        )));
+    }
+    Ok(rows)
+}
+
+pub async fn place(
+    client: &LightPoolClient,
+    registry: &Registry,
+    args: &PlaceArgs,
+    mode: SubmitMode,
+    output: Option<Output>,
+) -> Result<()> {
+    let rows = read_rows(client, &args.csv, registry)?;
+    let invalid = rows.iter().filter(|(_, action)| action.is_none()).count();
+    if invalid > 0 && !args.skip_invalid {
+        let outcomes: Vec<Outcome> = rows
+            .into_iter()
+            .map(|(mut outcome, action)| {
+                if action.is_some() {
+                    outcome.status = "skipped".to_string();
+                }
+                outcome
+            })
+            .collect();
+        report(output, "orders place", &outcomes, &[])?;
+        return Err(Error::Validation(format!(
+            "{} invalid row(s), nothing was submitted; pass --skip-invalid to submit the rest",
+            invalid
+        )));
+    }
+    let signer = args.key.signer()?;
+    let mut items = Vec::new();
+    let mut outcomes = Vec::new();
+    for (outcome, action) in rows {
+        match action {
+            Some(action) => items.push((outcome, action)),
+            None => outcomes.push(outcome),
+        }
+    }
+    let transactions = submit(client, &signer, &mut items, args.batch_size as usize, mode).await?;
+    // 已跳过的无效行不影响退出状态
+    let submitted: Vec<Outcome> = items.into_iter().map(|(outcome, _)| outcome).collect();
+    let result = check(&submitted);
+    outcomes.extend(submitted);
+    outcomes.sort_by_key(|outcome| outcome.row);
+    report(output, "orders place", &outcomes, &transactions)?;
+    result
+}
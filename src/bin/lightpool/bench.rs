@@ -0,0 +1,123 @@
+//! `bench`：测量本机编码、哈希与签名的吞吐量和延迟
+//!
+//! 用于部署做市程序之前检查主机性能。每项操作先预热，再逐次计时，
+//! 输出吞吐量与延迟分位数（微秒）。交易为固定内容的下单交易，签名私钥为随机生成。
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use lightpool::crypto::{Ed25519Signer, Signer};
+use lightpool::order::{OrderRequest, PlaceOrderParams};
+use lightpool::transaction::Transaction;
+use lightpool::types::{ObjectId, OrderSide};
+use lightpool::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha512};
+
+use crate::output::{emit, Output};
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// 每项操作的计时次数
+    #[arg(long, default_value_t = 10_000, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+    /// 计时前的预热次数
+    #[arg(long, default_value_t = 1_000)]
+    warmup: u32,
+    /// 每笔交易包含的下单操作数
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    actions: u32,
+}
+
+/// 一项操作的测量结果，延迟单位为微秒
+#[derive(Debug, Serialize)]
+struct Report {
+    op: &'static str,
+    iterations: u32,
+    ops_per_sec: f64,
+    mean_us: f64,
+    p50_us: f64,
+    p90_us: f64,
+    p99_us: f64,
+    max_us: f64,
+}
+
+/// 最近秩法取分位数，`sorted` 需升序且非空
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// 保留三位小数，纳秒以下的精度没有意义
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+fn micros(duration: Duration) -> f64 {
+    round(duration.as_secs_f64() * 1e6)
+}
+
+fn measure<T>(op: &'static str, args: &BenchArgs, mut f: impl FnMut() -> T) -> Report {
+    for _ in 0..args.warmup {
+        black_box(f());
+    }
+    let mut samples = Vec::with_capacity(args.iterations as usize);
+    let started = Instant::now();
+    for _ in 0..args.iterations {
+        let start = Instant::now();
+        black_box(f());
+        samples.push(start.elapsed());
+    }
+    let total = started.elapsed();
+    samples.sort_unstable();
+    let sum: Duration = samples.iter().sum();
+    Report {
+        op,
+        iterations: args.iterations,
+        ops_per_sec: (args.iterations as f64 / total.as_secs_f64()).round(),
+        mean_us: micros(sum / args.iterations),
+        p50_us: micros(percentile(&samples, 50.0)),
+        p90_us: micros(percentile(&samples, 90.0)),
+        p99_us: micros(percentile(&samples, 99.0)),
+        max_us: micros(samples[samples.len() - 1]),
+    }
+}
+
+fn transaction(signer: &Ed25519Signer, actions: u32) -> Result<Transaction> {
+    let params = PlaceOrderParams::limit(OrderSide::Buy, 5_000_000, 50_000_000_000);
+    let action = OrderRequest::spot(ObjectId([1; 32]), ObjectId([2; 32]), params).to_action()?;
+    Ok(Transaction {
+        sender: signer.address(),
+        expiration: u64::MAX,
+        actions: vec![action; actions as usize],
+    })
+}
+
+pub fn run(args: &BenchArgs, output: Option<Output>) -> Result<()> {
+    let signer = Ed25519Signer::generate();
+    let tx = transaction(&signer, args.actions)?;
+    let bytes = tx.signing_bytes();
+    let reports = vec![
+        measure("encode", args, || tx.signing_bytes()),
+        measure("hash", args, || Sha512::digest(&bytes)),
+        measure("sign", args, || signer.sign(&bytes)),
+        measure("encode+sign", args, || tx.clone().sign(&signer)),
+    ];
+    emit(output.unwrap_or(Output::Table), "bench", &reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let samples: Vec<Duration> = (1..=10).map(Duration::from_micros).collect();
+        assert_eq!(percentile(&samples, 50.0), Duration::from_micros(5));
+        assert_eq!(percentile(&samples, 90.0), Duration::from_micros(9));
+        assert_eq!(percentile(&samples, 99.0), Duration::from_micros(10));
+        assert_eq!(percentile(&samples, 0.0), Duration::from_micros(1));
+        assert_eq!(percentile(&samples[..1], 99.0), Duration::from_micros(1));
+    }
+}
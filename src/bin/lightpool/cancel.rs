@@ -0,0 +1,117 @@
+//! `cancel-all`：撤销账户的全部挂单
+//!
+//! 挂单取自节点，可按市场过滤，撤单按 `--batch-size` 打包为多笔交易。
+//! 实际提交前在终端询问确认，`--yes` 跳过确认；`--dry-run` 时不询问。
+
+use std::io::{BufRead, Write};
+
+use clap::Args;
+use lightpool::crypto::Signer;
+use lightpool::order::cancel_action;
+use lightpool::transaction::{Action, SPOT_CONTRACT};
+use lightpool::types::{Address, ObjectId, OrderId};
+use lightpool::{Error, LightPoolClient, Result};
+use serde_json::{json, Value};
+
+use crate::args::KeyArgs;
+use crate::batch::{check, report, submit, Outcome};
+use crate::markets::Registry;
+use crate::output::Output;
+use crate::query::{market_id, order_str};
+use crate::submit::SubmitMode;
+
+#[derive(Debug, Args)]
+pub struct CancelAllArgs {
+    #[command(flatten)]
+    pub key: KeyArgs,
+    /// 只撤销该市场的挂单
+    #[arg(long)]
+    market_id: Option<String>,
+    /// 按登记表中的交易对名称指定市场
+    #[arg(long, conflicts_with = "market_id")]
+    market: Option<String>,
+    /// 每笔交易包含的撤单数
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    batch_size: u32,
+    /// 不询问确认
+    #[arg(long, short)]
+    yes: bool,
+}
+
+/// 由节点返回的订单生成撤单操作；订单未带市场ID时使用过滤的市场
+///
+/// 合约地址依次取订单字段、登记表中的同一市场，最后为现货合约。
+fn cancel(registry: &Registry, order: &Value, market_id: Option<&str>) -> Result<Action> {
+    let order_id: OrderId = order_str(order, &["order_id", "orderId", "id"])
+        .ok_or_else(|| Error::Decode("order has no order_id".to_string()))?
+        .parse()?;
+    let market_id: ObjectId = order_str(order, &["market_id", "marketId"])
+        .or(market_id)
+        .ok_or_else(|| Error::Decode("order has no market_id".to_string()))?
+        .parse()?;
+    let market_address = match order_str(order, &["market_address", "marketAddress"]) {
+        Some(address) => address.parse::<Address>()?,
+        None => registry
+            .by_market_id(&market_id)
+            .map_or(SPOT_CONTRACT, |(_, entry)| entry.market_address),
+    };
+    cancel_action(market_address, market_id, order_id)
+}
+
+/// 在标准错误上询问，只有回答 y 或 yes 时继续
+fn confirm(prompt: &str) -> Result<bool> {
+    eprint!("{} [y/N] ", prompt);
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+pub async fn run(
+    client: &LightPoolClient,
+    registry: &Registry,
+    args: &CancelAllArgs,
+    mode: SubmitMode,
+    output: Option<Output>,
+) -> Result<()> {
+    let signer = args.key.signer()?;
+    let address = signer.address();
+    let market_id = market_id(registry, &args.market_id, &args.market)?;
+    let orders = client.get_orders(&address, market_id.as_deref()).await?;
+
+    let mut items = Vec::new();
+    let mut outcomes = Vec::new();
+    for (index, order) in orders.iter().enumerate() {
+        let detail = json!({
+            "order_id": order_str(order, &["order_id", "orderId", "id"]),
+            "market_id": order_str(order, &["market_id", "marketId"]).or(market_id.as_deref()),
+        });
+        let outcome = Outcome::new(index as u64 + 1, detail);
+        match cancel(registry, order, market_id.as_deref()) {
+            Ok(action) => items.push((outcome, action)),
+            Err(e) => outcomes.push(outcome.invalid(e)),
+        }
+    }
+
+    if !items.is_empty()
+        && mode == SubmitMode::Submit
+        && !args.yes
+        && !confirm(&format!(
+            "cancel {} open order(s) of {}?",
+            items.len(),
+            address
+        ))?
+    {
+        return Err(Error::Validation(
+            "aborted, nothing was cancelled".to_string(),
+        ));
+    }
+    let transactions = submit(client, &signer, &mut items, args.batch_size as usize, mode).await?;
+    outcomes.extend(items.into_iter().map(|(outcome, _)| outcome));
+    outcomes.sort_by_key(|outcome| outcome.row);
+    report(output, "cancel-all", &outcomes, &transactions)?;
+    check(&outcomes)
+}
@@ -0,0 +1,352 @@
+//! `codegen kaitai`：bincode 布局的 Kaitai Struct 描述
+//!
+//! 生成的 `.ksy` 可在 Kaitai Web IDE 等通用二进制查看器中逐字段解析抓取的报文。
+//! 根类型由 `--root` 指定，默认为已签名交易；只含单元变体的枚举生成 `enums`，
+//! 带数据的枚举生成以编码序号 `tag` 分派的类型。`Action.params` 按操作名解析为对应的参数类型。
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use lightpool::idl::Idl;
+use lightpool::transaction::action_name;
+use lightpool::{Error, Result};
+use serde_reflection::{ContainerFormat, Format, Named, VariantFormat};
+
+use super::{unsupported, untraced};
+
+/// 按操作名解析的嵌套参数：操作名与参数类型
+const ACTION_PARAMS: &[(&str, &str)] = &[
+    ("ord_place", "PlaceOrderParams"),
+    ("ord_cancel", "CancelOrderParams"),
+];
+
+/// `PlaceOrderParams` → `place_order_params`
+fn snake(name: &str) -> String {
+    let mut out = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if prev_lower {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else {
+            out.push(c);
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        }
+    }
+    out
+}
+
+struct Generator<'a> {
+    idl: &'a Idl,
+    src: String,
+    /// 当前 seq 条目的缩进
+    indent: usize,
+}
+
+impl Generator<'_> {
+    /// 是否为只含单元变体的枚举
+    fn is_unit_enum(&self, name: &str) -> bool {
+        matches!(
+            self.idl.types.get(name),
+            Some(ContainerFormat::Enum(variants))
+                if variants.values().all(|variant| variant.value == VariantFormat::Unit)
+        )
+    }
+
+    /// 单个 seq 条目即可描述的格式
+    fn scalar(&self, format: &Format) -> Option<Vec<String>> {
+        let ty = |ty: &str| Some(vec![format!("type: {}", ty)]);
+        match format {
+            Format::U8 | Format::Bool => ty("u1"),
+            Format::U16 => ty("u2"),
+            Format::U32 => ty("u4"),
+            Format::U64 => ty("u8"),
+            Format::I8 => ty("s1"),
+            Format::I16 => ty("s2"),
+            Format::I32 => ty("s4"),
+            Format::I64 => ty("s8"),
+            Format::F32 => ty("f4"),
+            Format::F64 => ty("f8"),
+            Format::U128 | Format::I128 => Some(vec!["size: 16".to_string()]),
+            Format::TupleArray { content, size } if **content == Format::U8 => {
+                Some(vec![format!("size: {}", size)])
+            }
+            Format::TypeName(name) if self.is_unit_enum(name) => Some(vec![
+                "type: u4".to_string(),
+                format!("enum: {}", snake(name)),
+            ]),
+            Format::TypeName(name) => ty(&snake(name)),
+            _ => None,
+        }
+    }
+
+    fn entry(&mut self, id: &str, attrs: &[String], doc: Option<&str>) {
+        let indent = " ".repeat(self.indent);
+        writeln!(self.src, "{}- id: {}", indent, id).unwrap();
+        for attr in attrs {
+            writeln!(self.src, "{}  {}", indent, attr).unwrap();
+        }
+        if let Some(doc) = doc {
+            writeln!(self.src, "{}  doc: {}", indent, doc).unwrap();
+        }
+    }
+
+    fn length(&mut self, id: &str) -> String {
+        let len = format!("{}_len", id);
+        self.entry(&len, &["type: u8".to_string()], None);
+        len
+    }
+
+    /// 字段的 seq 条目；长度前缀与 Option 标记单独占一个条目
+    fn field(&mut self, id: &str, format: &Format, extra: &[String]) -> Result<()> {
+        if let Some(mut attrs) = self.scalar(format) {
+            attrs.extend_from_slice(extra);
+            let doc = match format {
+                Format::Bool => Some("bool"),
+                Format::U128 => Some("u128, little-endian"),
+                Format::I128 => Some("i128, little-endian"),
+                _ => None,
+            };
+            self.entry(id, &attrs, doc);
+            return Ok(());
+        }
+        match format {
+            Format::Str => {
+                let len = self.length(id);
+                let attrs = [
+                    "type: str".to_string(),
+                    format!("size: {}", len),
+                    "encoding: UTF-8".to_string(),
+                ];
+                self.entry(id, &attrs, None);
+            }
+            Format::Bytes => {
+                let len = self.length(id);
+                let mut attrs = vec![format!("size: {}", len)];
+                attrs.extend_from_slice(extra);
+                self.entry(id, &attrs, None);
+            }
+            Format::Seq(inner) if **inner == Format::U8 => {
+                let len = self.length(id);
+                let mut attrs = vec![format!("size: {}", len)];
+                attrs.extend_from_slice(extra);
+                self.entry(id, &attrs, None);
+            }
+            Format::Seq(inner) => {
+                let mut attrs = self.scalar(inner).ok_or_else(|| unsupported(format))?;
+                let len = self.length(id);
+                attrs.push("repeat: expr".to_string());
+                attrs.push(format!("repeat-expr: {}", len));
+                self.entry(id, &attrs, None);
+            }
+            Format::TupleArray { content, size } => {
+                let mut attrs = self.scalar(content).ok_or_else(|| unsupported(format))?;
+                attrs.push("repeat: expr".to_string());
+                attrs.push(format!("repeat-expr: {}", size));
+                self.entry(id, &attrs, None);
+            }
+            Format::Option(inner) => {
+                let mut attrs = self.scalar(inner).ok_or_else(|| unsupported(format))?;
+                let tag = format!("{}_tag", id);
+                self.entry(&tag, &["type: u1".to_string()], None);
+                attrs.push(format!("if: {} == 1", tag));
+                self.entry(id, &attrs, None);
+            }
+            _ => return Err(unsupported(format)),
+        }
+        Ok(())
+    }
+
+    fn fields(&mut self, name: &str, fields: &[Named<Format>]) -> Result<()> {
+        let mut action = false;
+        for field in fields {
+            let mut extra = Vec::new();
+            if name == "Action" && field.name == "action" {
+                extra.push("enum: action_name".to_string());
+                action = true;
+            }
+            if name == "Action" && field.name == "params" && action {
+                extra.push("type:".to_string());
+                extra.push("  switch-on: action".to_string());
+                extra.push("  cases:".to_string());
+                for (action, ty) in ACTION_PARAMS {
+                    extra.push(format!("    'action_name::{}': {}", action, snake(ty)));
+                }
+            }
+            self.field(&field.name, &field.value, &extra)?;
+        }
+        Ok(())
+    }
+
+    fn container(&mut self, name: &str, container: &ContainerFormat) -> Result<()> {
+        if self.is_unit_enum(name) {
+            return Ok(());
+        }
+        writeln!(self.src, "  {}:", snake(name)).unwrap();
+        match container {
+            ContainerFormat::UnitStruct => {
+                self.src.push_str("    doc: empty\n");
+            }
+            ContainerFormat::NewTypeStruct(inner) => {
+                self.src.push_str("    seq:\n");
+                self.field("value", inner, &[])?;
+            }
+            ContainerFormat::TupleStruct(formats) => {
+                self.src.push_str("    seq:\n");
+                for (i, format) in formats.iter().enumerate() {
+                    self.field(&format!("field{}", i), format, &[])?;
+                }
+            }
+            ContainerFormat::Struct(fields) => {
+                self.src.push_str("    seq:\n");
+                self.fields(name, fields)?;
+            }
+            ContainerFormat::Enum(variants) => self.data_enum(name, variants)?,
+        }
+        Ok(())
+    }
+
+    /// 带数据的枚举：`tag` 为编码序号，`value` 按序号分派到各变体类型
+    fn data_enum(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        let kind = format!("{}_kind", snake(name));
+        writeln!(
+            self.src,
+            "    seq:\n      - id: tag\n        type: u4\n        enum: {}\n      - id: value\n        type:\n          switch-on: tag\n          cases:",
+            kind
+        )
+        .unwrap();
+        for variant in variants.values() {
+            if variant.value != VariantFormat::Unit {
+                writeln!(
+                    self.src,
+                    "            '{}::{}': {}_{}",
+                    kind,
+                    snake(&variant.name),
+                    snake(name),
+                    snake(&variant.name)
+                )
+                .unwrap();
+            }
+        }
+        self.src.push_str("    types:\n");
+        for variant in variants.values() {
+            let fields: Vec<(String, &Format)> = match &variant.value {
+                VariantFormat::Unit => continue,
+                VariantFormat::NewType(inner) => vec![("value".to_string(), &**inner)],
+                VariantFormat::Tuple(formats) => formats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, format)| (format!("field{}", i), format))
+                    .collect(),
+                VariantFormat::Struct(fields) => fields
+                    .iter()
+                    .map(|field| (field.name.clone(), &field.value))
+                    .collect(),
+                VariantFormat::Variable(_) => return Err(untraced(name, &variant.name)),
+            };
+            writeln!(
+                self.src,
+                "      {}_{}:\n        seq:",
+                snake(name),
+                snake(&variant.name)
+            )
+            .unwrap();
+            // 变体类型嵌套在枚举类型之内
+            self.indent = 10;
+            for (id, format) in fields {
+                self.field(&id, format, &[])?;
+            }
+            self.indent = 6;
+        }
+        Ok(())
+    }
+
+    fn enums(&mut self) -> Result<()> {
+        self.src.push_str("enums:\n");
+        for (name, container) in &self.idl.types {
+            if let ContainerFormat::Enum(variants) = container {
+                let snake_name = snake(name);
+                let unit = self.is_unit_enum(name);
+                writeln!(
+                    self.src,
+                    "  {}:",
+                    if unit {
+                        snake_name
+                    } else {
+                        format!("{}_kind", snake_name)
+                    }
+                )
+                .unwrap();
+                for (index, variant) in variants {
+                    writeln!(self.src, "    {}: {}", index, snake(&variant.name)).unwrap();
+                }
+            }
+        }
+        self.src.push_str("  action_name:\n");
+        for (name, _) in ACTION_PARAMS {
+            writeln!(self.src, "    {}: {}", action_name(name)?, name).unwrap();
+        }
+        Ok(())
+    }
+}
+
+/// 生成以 `root` 为根类型的 Kaitai Struct 描述
+pub fn generate(idl: &Idl, root: &str) -> Result<String> {
+    let root_format = if idl.types.contains_key(root) {
+        Format::TypeName(root.to_string())
+    } else {
+        return Err(Error::Validation(format!("unknown type: {}", root)));
+    };
+    let mut generator = Generator {
+        idl,
+        src: format!(
+            "# 由 `lightpool codegen kaitai` 根据链上类型的 IDL 生成，请勿手工修改\nmeta:\n  id: lightpool\n  title: LightPool {} (bincode, IDL version {})\n  endian: le\ndoc: |\n  bincode 1 编码：定长小端整数，枚举序号为 u4，序列与字节串以 u8 长度为前缀。\nseq:\n",
+            root, idl.version
+        ),
+        indent: 2,
+    };
+    generator.field("value", &root_format, &[])?;
+    generator.indent = 6;
+    generator.src.push_str("types:\n");
+    for (name, container) in &idl.types {
+        generator.container(name, container)?;
+    }
+    generator.enums()?;
+    Ok(generator.src)
+}
+
+#[cfg(test)]
+mod tests {
+    use lightpool::idl::wire_idl;
+
+    use super::*;
+
+    #[test]
+    fn describes_layouts_in_encoding_order() {
+        let src = generate(&wire_idl(), "SignedTransaction").unwrap();
+        assert!(src.contains(
+            "  place_order_params:\n    seq:\n      - id: side\n        type: u4\n        enum: order_side\n      - id: amount\n        type: u8\n"
+        ));
+        assert!(src.contains("  time_in_force:\n    0: gtc\n    1: ioc\n    2: fok\n"));
+        assert!(src.contains("            'order_type_kind::trigger': order_type_trigger\n"));
+        assert!(src.contains("      - id: params_len\n        type: u8\n      - id: params\n        size: params_len\n"));
+        assert!(src.contains("    746789037603618816: ord_place\n"));
+        assert!(generate(&wire_idl(), "Nope").is_err());
+    }
+
+    #[test]
+    fn committed_kaitai_spec_is_current() {
+        let committed = include_str!("../../../../idl/lightpool.ksy");
+        assert!(
+            committed == generate(&wire_idl(), "SignedTransaction").unwrap(),
+            "idl/lightpool.ksy is stale; regenerate it with `lightpool codegen kaitai --out idl/lightpool.ksy`"
+        );
+    }
+}
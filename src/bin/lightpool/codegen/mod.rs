@@ -0,0 +1,84 @@
+//! `codegen`：由链上类型的 IDL 生成其他语言的类型定义
+//!
+//! 字段顺序与枚举序号取自 [`lightpool::idl::wire_idl`]，生成的编解码与 Rust 的 bincode 编码逐字节一致。
+//! 仓库中的生成文件请勿手工修改，Rust 类型调整后重新生成：
+//! - `lightpool_sdk/wire.py`：`lightpool codegen python --out lightpool_sdk/wire.py`
+//! - `idl/lightpool.ts`：`lightpool codegen typescript --out idl/lightpool.ts`
+//! - `idl/lightpool.ksy`：`lightpool codegen kaitai --out idl/lightpool.ksy`
+
+mod kaitai;
+mod python;
+mod typescript;
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use lightpool::idl::wire_idl;
+use lightpool::{Error, Result};
+use serde_json::json;
+use serde_reflection::Format;
+
+use crate::output::{emit, Output};
+
+#[derive(Debug, Args)]
+pub struct CodegenArgs {
+    #[command(subcommand)]
+    language: Language,
+}
+
+#[derive(Debug, Subcommand)]
+enum Language {
+    /// Python dataclass 与编解码
+    Python {
+        /// 输出文件，默认打印到标准输出
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// TypeScript 接口与编解码
+    Typescript {
+        /// 输出文件，默认打印到标准输出
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Kaitai Struct 描述，供通用二进制查看器解析报文
+    Kaitai {
+        /// 根类型，取 IDL 中的类型名
+        #[arg(long, default_value = "SignedTransaction")]
+        root: String,
+        /// 输出文件，默认打印到标准输出
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+fn unsupported(format: &Format) -> Error {
+    Error::Validation(format!("codegen does not support {:?}", format))
+}
+
+fn untraced(name: &str, variant: &str) -> Error {
+    Error::Validation(format!("variant {}::{} is not traced", name, variant))
+}
+
+pub fn run(args: &CodegenArgs, output: Option<Output>) -> Result<()> {
+    let idl = wire_idl();
+    let (language, src, out) = match &args.language {
+        Language::Python { out } => ("python", python::generate(&idl)?, out),
+        Language::Typescript { out } => ("typescript", typescript::generate(&idl)?, out),
+        Language::Kaitai { root, out } => ("kaitai", kaitai::generate(&idl, root)?, out),
+    };
+    match out {
+        Some(path) => {
+            std::fs::write(path, &src)?;
+            let written = json!({
+                "language": language,
+                "types": idl.types.len(),
+                "file": path,
+            });
+            emit(output.unwrap_or(Output::Table), "codegen", &written)
+        }
+        None => {
+            print!("{}", src);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,513 @@
+//! `codegen python`：dataclass 与基于 `struct` 的编解码
+
+use std::fmt::Write as _;
+
+use lightpool::idl::Idl;
+use lightpool::Result;
+use serde_reflection::{ContainerFormat, Format, Named, VariantFormat};
+
+use super::{unsupported, untraced};
+
+/// 生成代码共用的编解码实现
+const PYTHON_RUNTIME: &str = r#"class _Reader:
+    def __init__(self, data: bytes):
+        self.data = bytes(data)
+        self.pos = 0
+
+    def take(self, size: int) -> bytes:
+        if self.pos + size > len(self.data):
+            raise ValueError("unexpected end of input")
+        chunk = self.data[self.pos:self.pos + size]
+        self.pos += size
+        return chunk
+
+
+class _Prim:
+    def __init__(self, fmt: str):
+        self.struct = struct.Struct(fmt)
+
+    def pack(self, value, out: bytearray) -> None:
+        out += self.struct.pack(value)
+
+    def unpack(self, reader: _Reader):
+        return self.struct.unpack(reader.take(self.struct.size))[0]
+
+
+class _Int128:
+    def __init__(self, signed: bool):
+        self.signed = signed
+
+    def pack(self, value: int, out: bytearray) -> None:
+        out += value.to_bytes(16, "little", signed=self.signed)
+
+    def unpack(self, reader: _Reader) -> int:
+        return int.from_bytes(reader.take(16), "little", signed=self.signed)
+
+
+_U8 = _Prim("<B")
+_U16 = _Prim("<H")
+_U32 = _Prim("<I")
+_U64 = _Prim("<Q")
+_U128 = _Int128(False)
+_I8 = _Prim("<b")
+_I16 = _Prim("<h")
+_I32 = _Prim("<i")
+_I64 = _Prim("<q")
+_I128 = _Int128(True)
+_F32 = _Prim("<f")
+_F64 = _Prim("<d")
+_BOOL = _Prim("<?")
+# 序列与字符串的长度前缀
+_LEN = _U64
+
+
+class _Unit:
+    def pack(self, value, out: bytearray) -> None:
+        pass
+
+    def unpack(self, reader: _Reader):
+        return None
+
+
+class _Str:
+    def pack(self, value: str, out: bytearray) -> None:
+        data = value.encode("utf-8")
+        _LEN.pack(len(data), out)
+        out += data
+
+    def unpack(self, reader: _Reader) -> str:
+        return reader.take(_LEN.unpack(reader)).decode("utf-8")
+
+
+class _Bytes:
+    def pack(self, value: bytes, out: bytearray) -> None:
+        _LEN.pack(len(value), out)
+        out += value
+
+    def unpack(self, reader: _Reader) -> bytes:
+        return reader.take(_LEN.unpack(reader))
+
+
+class _FixedBytes:
+    def __init__(self, size: int):
+        self.size = size
+
+    def pack(self, value: bytes, out: bytearray) -> None:
+        if len(value) != self.size:
+            raise ValueError(f"expected {self.size} bytes, got {len(value)}")
+        out += value
+
+    def unpack(self, reader: _Reader) -> bytes:
+        return reader.take(self.size)
+
+
+class _Option:
+    def __init__(self, inner):
+        self.inner = inner
+
+    def pack(self, value, out: bytearray) -> None:
+        if value is None:
+            out.append(0)
+        else:
+            out.append(1)
+            self.inner.pack(value, out)
+
+    def unpack(self, reader: _Reader):
+        tag = reader.take(1)[0]
+        if tag > 1:
+            raise ValueError(f"invalid option tag: {tag}")
+        return self.inner.unpack(reader) if tag else None
+
+
+class _Seq:
+    def __init__(self, inner):
+        self.inner = inner
+
+    def pack(self, value, out: bytearray) -> None:
+        _LEN.pack(len(value), out)
+        for item in value:
+            self.inner.pack(item, out)
+
+    def unpack(self, reader: _Reader) -> list:
+        return [self.inner.unpack(reader) for _ in range(_LEN.unpack(reader))]
+
+
+class _Array:
+    def __init__(self, inner, size: int):
+        self.inner = inner
+        self.size = size
+
+    def pack(self, value, out: bytearray) -> None:
+        if len(value) != self.size:
+            raise ValueError(f"expected {self.size} items, got {len(value)}")
+        for item in value:
+            self.inner.pack(item, out)
+
+    def unpack(self, reader: _Reader) -> list:
+        return [self.inner.unpack(reader) for _ in range(self.size)]
+
+
+class _Tuple:
+    def __init__(self, *items):
+        self.items = items
+
+    def pack(self, value, out: bytearray) -> None:
+        if len(value) != len(self.items):
+            raise ValueError(f"expected {len(self.items)} items, got {len(value)}")
+        for codec, item in zip(self.items, value):
+            codec.pack(item, out)
+
+    def unpack(self, reader: _Reader) -> tuple:
+        return tuple(codec.unpack(reader) for codec in self.items)
+
+
+class _Map:
+    """按 dict 的迭代顺序编码，须与 Rust 一侧的映射顺序一致"""
+
+    def __init__(self, key, value):
+        self.key = key
+        self.value = value
+
+    def pack(self, value: dict, out: bytearray) -> None:
+        _LEN.pack(len(value), out)
+        for key, item in value.items():
+            self.key.pack(key, out)
+            self.value.pack(item, out)
+
+    def unpack(self, reader: _Reader) -> dict:
+        return {
+            self.key.unpack(reader): self.value.unpack(reader)
+            for _ in range(_LEN.unpack(reader))
+        }
+
+
+class _Ref:
+    """按名称引用本模块中的类型，允许前向引用"""
+
+    def __init__(self, name: str):
+        self.name = name
+
+    def pack(self, value, out: bytearray) -> None:
+        value._pack(out)
+
+    def unpack(self, reader: _Reader):
+        return globals()[self.name]._unpack(reader)
+
+
+class _Wire:
+    def encode(self) -> bytes:
+        """bincode 编码"""
+        out = bytearray()
+        self._pack(out)
+        return bytes(out)
+
+    @classmethod
+    def decode(cls, data: bytes):
+        """bincode 解码，不允许多余字节"""
+        reader = _Reader(data)
+        value = cls._unpack(reader)
+        if reader.pos != len(reader.data):
+            raise ValueError(f"{len(reader.data) - reader.pos} trailing bytes")
+        return value
+
+
+class _Struct(_Wire):
+    _FIELDS: tuple = ()
+
+    def _pack(self, out: bytearray) -> None:
+        for name, codec in self._FIELDS:
+            codec.pack(getattr(self, name), out)
+
+    @classmethod
+    def _unpack(cls, reader: _Reader):
+        return cls(*(codec.unpack(reader) for _, codec in cls._FIELDS))
+
+
+class _UnitEnum(_Wire):
+    def _pack(self, out: bytearray) -> None:
+        _U32.pack(self.value, out)
+
+    @classmethod
+    def _unpack(cls, reader: _Reader):
+        index = _U32.unpack(reader)
+        try:
+            return cls(index)
+        except ValueError:
+            raise ValueError(f"invalid {cls.__name__} variant: {index}") from None
+
+
+class _Enum(_Wire):
+    """带数据的枚举，每个变体是一个子类"""
+
+    _INDEX: int = 0
+    _FIELDS: tuple = ()
+    _VARIANTS: dict = {}
+
+    def _pack(self, out: bytearray) -> None:
+        _U32.pack(self._INDEX, out)
+        for name, codec in self._FIELDS:
+            codec.pack(getattr(self, name), out)
+
+    @classmethod
+    def _unpack(cls, reader: _Reader):
+        index = _U32.unpack(reader)
+        variant = cls._VARIANTS.get(index)
+        if variant is None:
+            raise ValueError(f"invalid {cls.__name__} variant: {index}")
+        return variant(*(codec.unpack(reader) for _, codec in variant._FIELDS))
+"#;
+
+/// Python 关键字，用作字段名时追加下划线
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+fn ident(name: &str) -> String {
+    if PYTHON_KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// 字段的类型注解
+fn python_type(format: &Format) -> Result<String> {
+    Ok(match format {
+        Format::TypeName(name) => name.clone(),
+        Format::Unit => "None".to_string(),
+        Format::Bool => "bool".to_string(),
+        Format::I8
+        | Format::I16
+        | Format::I32
+        | Format::I64
+        | Format::I128
+        | Format::U8
+        | Format::U16
+        | Format::U32
+        | Format::U64
+        | Format::U128 => "int".to_string(),
+        Format::F32 | Format::F64 => "float".to_string(),
+        Format::Str => "str".to_string(),
+        Format::Bytes => "bytes".to_string(),
+        Format::Seq(inner) | Format::TupleArray { content: inner, .. } if **inner == Format::U8 => {
+            "bytes".to_string()
+        }
+        Format::Option(inner) => format!("Optional[{}]", python_type(inner)?),
+        Format::Seq(inner) | Format::TupleArray { content: inner, .. } => {
+            format!("List[{}]", python_type(inner)?)
+        }
+        Format::Map { key, value } => {
+            format!("Dict[{}, {}]", python_type(key)?, python_type(value)?)
+        }
+        Format::Tuple(items) => format!(
+            "Tuple[{}]",
+            items
+                .iter()
+                .map(python_type)
+                .collect::<Result<Vec<_>>>()?
+                .join(", ")
+        ),
+        Format::Variable(_) | Format::Char => return Err(unsupported(format)),
+    })
+}
+
+/// 字段的编解码器表达式
+fn python_codec(format: &Format) -> Result<String> {
+    Ok(match format {
+        Format::TypeName(name) => format!("_Ref({:?})", name),
+        Format::Unit => "_Unit()".to_string(),
+        Format::Bool => "_BOOL".to_string(),
+        Format::I8 => "_I8".to_string(),
+        Format::I16 => "_I16".to_string(),
+        Format::I32 => "_I32".to_string(),
+        Format::I64 => "_I64".to_string(),
+        Format::I128 => "_I128".to_string(),
+        Format::U8 => "_U8".to_string(),
+        Format::U16 => "_U16".to_string(),
+        Format::U32 => "_U32".to_string(),
+        Format::U64 => "_U64".to_string(),
+        Format::U128 => "_U128".to_string(),
+        Format::F32 => "_F32".to_string(),
+        Format::F64 => "_F64".to_string(),
+        Format::Str => "_Str()".to_string(),
+        Format::Bytes => "_Bytes()".to_string(),
+        Format::Seq(inner) if **inner == Format::U8 => "_Bytes()".to_string(),
+        Format::TupleArray { content, size } if **content == Format::U8 => {
+            format!("_FixedBytes({})", size)
+        }
+        Format::Option(inner) => format!("_Option({})", python_codec(inner)?),
+        Format::Seq(inner) => format!("_Seq({})", python_codec(inner)?),
+        Format::TupleArray { content, size } => {
+            format!("_Array({}, {})", python_codec(content)?, size)
+        }
+        Format::Map { key, value } => {
+            format!("_Map({}, {})", python_codec(key)?, python_codec(value)?)
+        }
+        Format::Tuple(items) => format!(
+            "_Tuple({})",
+            items
+                .iter()
+                .map(python_codec)
+                .collect::<Result<Vec<_>>>()?
+                .join(", ")
+        ),
+        Format::Variable(_) | Format::Char => return Err(unsupported(format)),
+    })
+}
+
+/// 具名字段；元组与 newtype 的成员依次命名为 `field0`、`field1` 或 `value`
+fn named_fields(fields: &[Named<Format>]) -> Vec<(String, &Format)> {
+    fields
+        .iter()
+        .map(|field| (ident(&field.name), &field.value))
+        .collect()
+}
+
+fn tuple_fields(formats: &[Format]) -> Vec<(String, &Format)> {
+    formats
+        .iter()
+        .enumerate()
+        .map(|(i, format)| (format!("field{}", i), format))
+        .collect()
+}
+
+/// dataclass 的字段声明与 `_FIELDS` 表
+fn python_body(src: &mut String, fields: &[(String, &Format)], index: Option<u32>) -> Result<()> {
+    for (name, format) in fields {
+        writeln!(src, "    {}: {}", name, python_type(format)?).unwrap();
+    }
+    if let Some(index) = index {
+        writeln!(src, "    _INDEX = {}", index).unwrap();
+    }
+    if fields.is_empty() {
+        if index.is_none() {
+            src.push_str("    pass\n");
+        }
+        return Ok(());
+    }
+    src.push_str("    _FIELDS = (\n");
+    for (name, format) in fields {
+        writeln!(src, "        ({:?}, {}),", name, python_codec(format)?).unwrap();
+    }
+    src.push_str("    )\n");
+    Ok(())
+}
+
+fn python_container(src: &mut String, name: &str, container: &ContainerFormat) -> Result<()> {
+    let fields = match container {
+        ContainerFormat::UnitStruct => Vec::new(),
+        ContainerFormat::NewTypeStruct(inner) => vec![("value".to_string(), &**inner)],
+        ContainerFormat::TupleStruct(formats) => tuple_fields(formats),
+        ContainerFormat::Struct(fields) => named_fields(fields),
+        ContainerFormat::Enum(variants) => return python_enum(src, name, variants),
+    };
+    writeln!(src, "\n\n@dataclass\nclass {}(_Struct):", name).unwrap();
+    python_body(src, &fields, None)
+}
+
+fn python_enum(
+    src: &mut String,
+    name: &str,
+    variants: &std::collections::BTreeMap<u32, Named<VariantFormat>>,
+) -> Result<()> {
+    if variants
+        .values()
+        .all(|variant| variant.value == VariantFormat::Unit)
+    {
+        writeln!(src, "\n\nclass {}(_UnitEnum, enum.IntEnum):", name).unwrap();
+        for (index, variant) in variants {
+            writeln!(src, "    {} = {}", variant.name, index).unwrap();
+        }
+        return Ok(());
+    }
+
+    writeln!(src, "\n\nclass {}(_Enum):\n    pass", name).unwrap();
+    for (index, variant) in variants {
+        let fields = match &variant.value {
+            VariantFormat::Unit => Vec::new(),
+            VariantFormat::NewType(inner) => vec![("value".to_string(), &**inner)],
+            VariantFormat::Tuple(formats) => tuple_fields(formats),
+            VariantFormat::Struct(fields) => named_fields(fields),
+            VariantFormat::Variable(_) => return Err(untraced(name, &variant.name)),
+        };
+        writeln!(
+            src,
+            "\n\n@dataclass\nclass {}{}({}):",
+            name, variant.name, name
+        )
+        .unwrap();
+        python_body(src, &fields, Some(*index))?;
+    }
+    let members: Vec<String> = variants
+        .iter()
+        .map(|(index, variant)| format!("{}: {}{}", index, name, variant.name))
+        .collect();
+    writeln!(src, "\n\n{}._VARIANTS = {{{}}}", name, members.join(", ")).unwrap();
+    Ok(())
+}
+
+/// 生成 Python 模块源码
+pub fn generate(idl: &Idl) -> Result<String> {
+    let mut src = format!(
+        r#"# 由 `lightpool codegen python` 根据链上类型的 IDL 生成，请勿手工修改
+"""
+LightPool 链上类型
+
+字段顺序与枚举序号与 Rust 定义一致，encode() / decode() 为 bincode 编解码。
+"""
+
+from __future__ import annotations
+
+import enum
+import struct
+from dataclasses import dataclass
+from typing import Dict, List, Optional, Tuple
+
+IDL_VERSION = {}
+
+
+"#,
+        idl.version
+    );
+    src.push_str(PYTHON_RUNTIME);
+    for (name, container) in &idl.types {
+        python_container(&mut src, name, container)?;
+    }
+    Ok(src)
+}
+
+#[cfg(test)]
+mod tests {
+    use lightpool::idl::wire_idl;
+
+    use super::*;
+
+    #[test]
+    fn python_follows_field_order_and_enum_indices() {
+        let src = generate(&wire_idl()).unwrap();
+        assert!(src.contains(
+            "class PlaceOrderParams(_Struct):\n    side: OrderSide\n    amount: int\n    order_type: OrderType\n    limit_price: int\n"
+        ));
+        assert!(src.contains(
+            "class TimeInForce(_UnitEnum, enum.IntEnum):\n    GTC = 0\n    IOC = 1\n    FOK = 2\n"
+        ));
+        assert!(src.contains("class OrderTypeTrigger(OrderType):\n    trigger_price: int\n    is_market: bool\n    trigger_type: int\n    _INDEX = 2\n"));
+        assert!(src.contains("(\"trigger_type\", _U8),"));
+        assert!(src.contains(
+            "    value: bytes\n    _FIELDS = (\n        (\"value\", _FixedBytes(32)),\n"
+        ));
+        assert!(src.contains("(\"params\", _Bytes()),"));
+    }
+
+    #[test]
+    fn committed_python_module_is_current() {
+        let committed = include_str!("../../../../lightpool_sdk/wire.py");
+        assert!(
+            committed == generate(&wire_idl()).unwrap(),
+            "lightpool_sdk/wire.py is stale; regenerate it with `lightpool codegen python --out lightpool_sdk/wire.py`"
+        );
+    }
+}
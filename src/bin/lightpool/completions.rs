@@ -0,0 +1,48 @@
+//! `completions`：由命令行定义生成 shell 补全脚本
+//!
+//! 生成时把登记表中的交易对名称写入 `--market` 的候选值，登记表变化后需重新生成。
+
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::Args;
+use clap_complete::Shell;
+use lightpool::Result;
+
+use crate::markets::Registry;
+
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    #[arg(value_enum)]
+    shell: Shell,
+}
+
+/// 为所有子命令的 `--market` 参数补上候选的交易对名称
+fn with_market_names(mut command: clap::Command, names: &[PossibleValue]) -> clap::Command {
+    if command.get_arguments().any(|arg| arg.get_id() == "market") {
+        command = command.mut_arg("market", |arg| {
+            arg.value_parser(PossibleValuesParser::new(names.to_vec()))
+        });
+    }
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        command = command.mut_subcommand(name, |sub| with_market_names(sub, names));
+    }
+    command
+}
+
+pub fn run(command: clap::Command, registry: &Registry, args: &CompletionsArgs) -> Result<()> {
+    let names: Vec<PossibleValue> = registry
+        .names()
+        .map(|name| PossibleValue::new(name.to_string()))
+        .collect();
+    let mut command = if names.is_empty() {
+        command
+    } else {
+        with_market_names(command, &names)
+    };
+    let name = command.get_name().to_string();
+    clap_complete::generate(args.shell, &mut command, name, &mut std::io::stdout());
+    Ok(())
+}
@@ -0,0 +1,311 @@
+//! 配置文件与 profile
+//!
+//! 默认读取 `~/.lightpool/config.toml`：
+//!
+//! ```toml
+//! default_profile = "testnet"
+//!
+//! [profiles.testnet]
+//! network = "testnet"
+//! key = "~/.lightpool/testnet.json"
+//! market = "BTC-USDC"
+//!
+//! [profiles.testnet.risk]
+//! max_order_notional = 1000000000000000
+//! ```
+//!
+//! `risk` 为下单前的风控限额（见 [`RiskLimits`]），`orders place`、`repl` 等下单命令在签名前检查。
+//! `network` 选择内置网络（见 [`NETWORKS`]），profile 未给出 `rpc_url`、`ws_url` 时使用该网络的地址。
+//! 命令行参数与环境变量优先于 profile，profile 优先于内置默认值。
+//! 除各命令行参数对应的环境变量（如 `LIGHTPOOL_RPC_URL`）外，以下环境变量覆盖 profile 中的同名字段，
+//! 没有配置文件时也生效，便于在容器中部署：
+//!
+//! | 环境变量 | 字段 |
+//! |---|---|
+//! | `LIGHTPOOL_NETWORK` | `network` |
+//! | `LIGHTPOOL_KEYSTORE` | `key` |
+//! | `LIGHTPOOL_MARKET` | `market` |
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use lightpool::risk::RiskLimits;
+use lightpool::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::args::lightpool_dir;
+
+/// 内置网络：名称、RPC 节点地址、行情网关地址
+pub const NETWORKS: &[(&str, &str, &str)] = &[
+    (
+        "mainnet",
+        "https://rpc.lightpool.io",
+        "wss://rpc.lightpool.io/ws",
+    ),
+    (
+        "testnet",
+        "https://testnet-rpc.lightpool.io",
+        "wss://testnet-rpc.lightpool.io/ws",
+    ),
+    ("local", "http://localhost:26300", "ws://localhost:26300/ws"),
+];
+
+/// 一组命名的默认参数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// 内置网络名称，提供默认的节点与行情网关地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+    /// 行情网关地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws_url: Option<String>,
+    /// RPC 请求超时（秒）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// 默认的加密私钥文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<PathBuf>,
+    /// 默认的交易对名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market: Option<String>,
+    /// 市场登记表
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markets: Option<PathBuf>,
+    /// 下单前的风控限额
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<RiskConfig>,
+}
+
+/// profile 中的风控限额，字段同 [`RiskLimits`]
+///
+/// TOML 整数最大为 i64，名义价值上限因此以 u64 书写。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RiskConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_order_notional: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_position: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_band: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_open_orders: Option<usize>,
+}
+
+impl RiskConfig {
+    pub fn limits(&self) -> RiskLimits {
+        RiskLimits {
+            max_order_notional: self.max_order_notional.map(u128::from),
+            max_position: self.max_position,
+            price_band: self.price_band,
+            max_open_orders: self.max_open_orders,
+        }
+    }
+}
+
+impl Profile {
+    /// 以环境变量覆盖字段，`var` 按名称取值，未设置或为空时不覆盖
+    pub fn override_from(&mut self, var: impl Fn(&str) -> Option<String>) {
+        let var = |name: &str| var(name).filter(|value| !value.is_empty());
+        if let Some(network) = var("LIGHTPOOL_NETWORK") {
+            self.network = Some(network);
+        }
+        if let Some(key) = var("LIGHTPOOL_KEYSTORE") {
+            self.key = Some(expand_home(key.into()));
+        }
+        if let Some(market) = var("LIGHTPOOL_MARKET") {
+            self.market = Some(market);
+        }
+    }
+
+    /// 生效的 RPC 节点与行情网关地址：显式配置的字段优先，其次为 `network` 的内置地址
+    ///
+    /// `network` 不是内置网络时返回错误。
+    pub fn endpoints(&self) -> Result<(Option<String>, Option<String>)> {
+        let network = match self.network.as_deref() {
+            Some(name) => Some(
+                NETWORKS
+                    .iter()
+                    .find(|(network, _, _)| *network == name)
+                    .ok_or_else(|| {
+                        let known: Vec<&str> = NETWORKS.iter().map(|(name, _, _)| *name).collect();
+                        Error::Validation(format!(
+                            "unknown network {}; expected one of {}",
+                            name,
+                            known.join(", ")
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
+        let rpc_url = self
+            .rpc_url
+            .clone()
+            .or_else(|| network.map(|(_, rpc_url, _)| rpc_url.to_string()));
+        let ws_url = self
+            .ws_url
+            .clone()
+            .or_else(|| network.map(|(_, _, ws_url)| ws_url.to_string()));
+        Ok((rpc_url, ws_url))
+    }
+}
+
+/// 配置文件
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    default_profile: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+/// 展开路径开头的 `~/`
+fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path,
+    }
+}
+
+impl Config {
+    /// 默认路径 `~/.lightpool/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        lightpool_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    /// 读取配置文件，文件不存在时返回空配置
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)?;
+        toml::from_str(&text).map_err(|e| Error::Decode(format!("{}: {}", path.display(), e)))
+    }
+
+    /// 选出生效的 profile：指定的名称、`default_profile`、名为 `default` 的 profile，依次取第一个
+    ///
+    /// 显式指定的 profile 不存在时返回错误。
+    pub fn profile(&self, name: Option<&str>) -> Result<Option<(String, Profile)>> {
+        let name = match name.or(self.default_profile.as_deref()) {
+            Some(name) => name,
+            None if self.profiles.contains_key("default") => "default",
+            None => return Ok(None),
+        };
+        let mut profile = self
+            .profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::Validation(format!("profile {} is not defined", name)))?;
+        profile.key = profile.key.map(expand_home);
+        profile.markets = profile.markets.map(expand_home);
+        Ok(Some((name.to_string(), profile)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_are_selected_in_order() {
+        let config: Config = toml::from_str(
+            r#"
+            default_profile = "testnet"
+
+            [profiles.testnet]
+            rpc_url = "http://10.0.0.1:26300"
+            key = "keys/testnet.json"
+
+            [profiles.default]
+            timeout = 5
+
+            [profiles.default.risk]
+            max_order_notional = 1000
+            "#,
+        )
+        .unwrap();
+        let (name, profile) = config.profile(None).unwrap().unwrap();
+        assert_eq!(name, "testnet");
+        assert_eq!(profile.rpc_url.as_deref(), Some("http://10.0.0.1:26300"));
+        assert_eq!(profile.key, Some(PathBuf::from("keys/testnet.json")));
+        let (name, profile) = config.profile(Some("default")).unwrap().unwrap();
+        assert_eq!((name.as_str(), profile.timeout), ("default", Some(5)));
+        assert_eq!(
+            profile.risk.unwrap().limits().max_order_notional,
+            Some(1000)
+        );
+        assert!(config.profile(Some("mainnet")).is_err());
+
+        let config: Config = toml::from_str("[profiles.default]\nmarket = \"BTC-USDC\"").unwrap();
+        assert_eq!(config.profile(None).unwrap().unwrap().0, "default");
+        assert_eq!(Config::default().profile(None).unwrap(), None);
+        assert!(toml::from_str::<Config>("[profiles.x]\nrpc = \"typo\"").is_err());
+        assert!(toml::from_str::<Config>("[profiles.x.risk]\nmax_notional = 1").is_err());
+    }
+
+    #[test]
+    fn network_supplies_default_endpoints() {
+        let config: Config = toml::from_str(
+            r#"
+            [profiles.testnet]
+            network = "testnet"
+            rpc_url = "http://10.0.0.1:26300"
+
+            [profiles.mainnet]
+            network = "mainnet"
+
+            [profiles.typo]
+            network = "mainet"
+            "#,
+        )
+        .unwrap();
+        let (_, testnet) = config.profile(Some("testnet")).unwrap().unwrap();
+        assert_eq!(testnet.network.as_deref(), Some("testnet"));
+        assert_eq!(
+            testnet.endpoints().unwrap(),
+            (
+                Some("http://10.0.0.1:26300".to_string()),
+                Some("wss://testnet-rpc.lightpool.io/ws".to_string())
+            )
+        );
+        let (_, mainnet) = config.profile(Some("mainnet")).unwrap().unwrap();
+        assert_eq!(
+            mainnet.endpoints().unwrap(),
+            (
+                Some("https://rpc.lightpool.io".to_string()),
+                Some("wss://rpc.lightpool.io/ws".to_string())
+            )
+        );
+        let (_, typo) = config.profile(Some("typo")).unwrap().unwrap();
+        assert!(typo.endpoints().is_err());
+        assert_eq!(Profile::default().endpoints().unwrap(), (None, None));
+    }
+
+    #[test]
+    fn environment_overrides_profile_fields() {
+        let mut profile = Profile {
+            network: Some("testnet".to_string()),
+            key: Some(PathBuf::from("keys/testnet.json")),
+            market: Some("BTC-USDC".to_string()),
+            ..Profile::default()
+        };
+        profile.override_from(|name| match name {
+            "LIGHTPOOL_NETWORK" => Some("mainnet".to_string()),
+            "LIGHTPOOL_KEYSTORE" => Some("/run/secrets/key.json".to_string()),
+            "LIGHTPOOL_MARKET" => Some(String::new()),
+            _ => None,
+        });
+        assert_eq!(profile.network.as_deref(), Some("mainnet"));
+        assert_eq!(
+            profile.endpoints().unwrap().0.as_deref(),
+            Some("https://rpc.lightpool.io")
+        );
+        assert_eq!(profile.key, Some(PathBuf::from("/run/secrets/key.json")));
+        // 空值不覆盖
+        assert_eq!(profile.market.as_deref(), Some("BTC-USDC"));
+    }
+}
@@ -0,0 +1,394 @@
+//! `decode`：按字段解析 bincode 十六进制
+//!
+//! 逐字段输出字节区间、原始字节与解码值。未指定 `--type` 时依次尝试各类型，
+//! 取第一个恰好用完全部字节的类型；同一段字节可能同时符合多个类型，此时在标准错误中提示。
+
+use clap::{Args, ValueEnum};
+use lightpool::transaction::decode_action_name;
+use lightpool::{Error, Result};
+use serde_json::json;
+
+use crate::args::print_json;
+use crate::output::Output;
+
+#[derive(Debug, Args)]
+pub struct DecodeArgs {
+    /// 负载类型，省略时自动识别
+    #[arg(long = "type", value_enum)]
+    payload: Option<Payload>,
+    /// 十六进制字节，可带 `0x` 前缀
+    hex: String,
+}
+
+/// 可解码的负载类型，按自动识别的优先级排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Payload {
+    SignedTransaction,
+    Transaction,
+    Action,
+    PlaceOrder,
+    CancelOrder,
+}
+
+impl Payload {
+    pub fn name(self) -> &'static str {
+        match self {
+            Payload::SignedTransaction => "signed-transaction",
+            Payload::Transaction => "transaction",
+            Payload::Action => "action",
+            Payload::PlaceOrder => "place-order",
+            Payload::CancelOrder => "cancel-order",
+        }
+    }
+}
+
+/// 一个已解码字段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub start: usize,
+    pub end: usize,
+    pub name: String,
+    pub value: String,
+}
+
+struct Annotator<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    fields: Vec<Field>,
+}
+
+impl<'a> Annotator<'a> {
+    fn take(&mut self, len: usize, name: &str) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| {
+                Error::Decode(format!(
+                    "{}: unexpected end of input at byte {}",
+                    name, self.pos
+                ))
+            })?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn push(&mut self, start: usize, name: &str, value: String) {
+        self.fields.push(Field {
+            start,
+            end: self.pos,
+            name: name.to_string(),
+            value,
+        });
+    }
+
+    fn uint(&mut self, len: usize, name: &str) -> Result<u64> {
+        let start = self.pos;
+        let mut buf = [0u8; 8];
+        buf[..len].copy_from_slice(self.take(len, name)?);
+        let value = u64::from_le_bytes(buf);
+        self.push(start, name, value.to_string());
+        Ok(value)
+    }
+
+    fn bool(&mut self, name: &str) -> Result<bool> {
+        let start = self.pos;
+        let value = match self.take(1, name)?[0] {
+            0 => false,
+            1 => true,
+            other => return Err(Error::Decode(format!("{}: invalid bool {}", name, other))),
+        };
+        self.push(start, name, value.to_string());
+        Ok(value)
+    }
+
+    fn variant(&mut self, name: &str, variants: &[&str]) -> Result<usize> {
+        let start = self.pos;
+        let bytes = self.take(4, name)?;
+        let index = u32::from_le_bytes(bytes.try_into().unwrap()) as usize;
+        let variant = variants
+            .get(index)
+            .ok_or_else(|| Error::Decode(format!("{}: invalid variant {}", name, index)))?;
+        self.push(start, name, variant.to_string());
+        Ok(index)
+    }
+
+    fn id(&mut self, name: &str) -> Result<()> {
+        let start = self.pos;
+        let bytes = self.take(32, name)?;
+        self.push(start, name, format!("0x{}", hex::encode(bytes)));
+        Ok(())
+    }
+
+    /// 序列长度前缀，长度不可能超过剩余字节数
+    fn len(&mut self, name: &str, item_size: usize) -> Result<usize> {
+        let len = self.uint(8, name)? as usize;
+        let remaining = self.bytes.len() - self.pos;
+        if len.saturating_mul(item_size) > remaining {
+            return Err(Error::Decode(format!(
+                "{}: length {} exceeds remaining {} bytes",
+                name, len, remaining
+            )));
+        }
+        Ok(len)
+    }
+
+    fn place_order(&mut self, prefix: &str) -> Result<()> {
+        self.variant(&format!("{}side", prefix), &["Buy", "Sell"])?;
+        self.uint(8, &format!("{}amount", prefix))?;
+        let order_type = format!("{}order_type", prefix);
+        match self.variant(&order_type, &["Limit", "Market", "Trigger"])? {
+            0 => {
+                self.variant(&format!("{}.tif", order_type), &["Gtc", "Ioc", "Fok"])?;
+            }
+            1 => {
+                self.uint(8, &format!("{}.slippage", order_type))?;
+            }
+            _ => {
+                self.uint(8, &format!("{}.trigger_price", order_type))?;
+                self.bool(&format!("{}.is_market", order_type))?;
+                self.uint(1, &format!("{}.trigger_type", order_type))?;
+            }
+        }
+        self.uint(8, &format!("{}limit_price", prefix))?;
+        Ok(())
+    }
+
+    fn cancel_order(&mut self, prefix: &str) -> Result<()> {
+        self.id(&format!("{}order_id", prefix))
+    }
+
+    fn action(&mut self, prefix: &str) -> Result<()> {
+        let inputs = self.len(&format!("{}inputs.len", prefix), 32)?;
+        for i in 0..inputs {
+            self.id(&format!("{}inputs[{}]", prefix, i))?;
+        }
+        self.id(&format!("{}contract", prefix))?;
+        let start = self.pos;
+        let name = format!("{}action", prefix);
+        let bytes = self.take(8, &name)?;
+        let action = u64::from_le_bytes(bytes.try_into().unwrap());
+        let action_name = decode_action_name(action);
+        let value = match &action_name {
+            Some(action_name) => format!("{} ({})", action, action_name),
+            None => action.to_string(),
+        };
+        self.push(start, &name, value);
+
+        let params = format!("{}params", prefix);
+        let len = self.len(&format!("{}.len", params), 1)?;
+        let end = self.pos + len;
+        let nested = match action_name.as_deref() {
+            Some("ord_place") => Some(Payload::PlaceOrder),
+            Some("ord_cancel") => Some(Payload::CancelOrder),
+            _ => None,
+        };
+        let (pos, count) = (self.pos, self.fields.len());
+        if let Some(payload) = nested {
+            let prefix = format!("{}.", params);
+            if self.payload(payload, &prefix).is_ok() && self.pos == end {
+                return Ok(());
+            }
+            self.pos = pos;
+            self.fields.truncate(count);
+        }
+        self.take(len, &params)?;
+        self.push(pos, &params, format!("{} bytes", len));
+        Ok(())
+    }
+
+    fn transaction(&mut self, prefix: &str) -> Result<()> {
+        self.id(&format!("{}sender", prefix))?;
+        self.uint(8, &format!("{}expiration", prefix))?;
+        // 每个 Action 至少 8 + 32 + 8 + 8 字节
+        let actions = self.len(&format!("{}actions.len", prefix), 56)?;
+        for i in 0..actions {
+            self.action(&format!("{}actions[{}].", prefix, i))?;
+        }
+        Ok(())
+    }
+
+    fn signed_transaction(&mut self) -> Result<()> {
+        self.transaction("transaction.")?;
+        let signatures = self.len("signatures.len", 64)?;
+        for i in 0..signatures {
+            self.id(&format!("signatures[{}].part1", i))?;
+            self.id(&format!("signatures[{}].part2", i))?;
+        }
+        Ok(())
+    }
+
+    fn payload(&mut self, payload: Payload, prefix: &str) -> Result<()> {
+        match payload {
+            Payload::SignedTransaction => self.signed_transaction(),
+            Payload::Transaction => self.transaction(prefix),
+            Payload::Action => self.action(prefix),
+            Payload::PlaceOrder => self.place_order(prefix),
+            Payload::CancelOrder => self.cancel_order(prefix),
+        }
+    }
+}
+
+/// 按指定类型解析，必须恰好用完全部字节
+pub fn annotate(bytes: &[u8], payload: Payload) -> Result<Vec<Field>> {
+    let mut annotator = Annotator {
+        bytes,
+        pos: 0,
+        fields: Vec::new(),
+    };
+    annotator.payload(payload, "")?;
+    if annotator.pos != bytes.len() {
+        return Err(Error::Decode(format!(
+            "{} trailing bytes after {}",
+            bytes.len() - annotator.pos,
+            payload.name()
+        )));
+    }
+    Ok(annotator.fields)
+}
+
+/// 尝试所有类型，按优先级返回能恰好解析全部字节的类型
+pub fn detect(bytes: &[u8]) -> Vec<(Payload, Vec<Field>)> {
+    Payload::value_variants()
+        .iter()
+        .filter_map(|&payload| Some((payload, annotate(bytes, payload).ok()?)))
+        .collect()
+}
+
+/// 解析十六进制字符串
+pub fn parse_hex(hex_str: &str) -> Result<Vec<u8>> {
+    let hex_str = hex_str.trim();
+    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+    hex::decode(hex_str).map_err(|e| Error::Decode(format!("invalid hex: {}", e)))
+}
+
+/// 输出字段表
+pub fn print_fields(bytes: &[u8], fields: &[Field]) {
+    let ranges: Vec<String> = fields
+        .iter()
+        .map(|field| format!("{}..{}", field.start, field.end))
+        .collect();
+    let range_width = ranges.iter().map(String::len).max().unwrap_or(0);
+    let name_width = fields.iter().map(|f| f.name.len()).max().unwrap_or(0);
+    for (field, range) in fields.iter().zip(&ranges) {
+        println!(
+            "  {:<rw$}  {:<nw$} = {}",
+            range,
+            field.name,
+            field.value,
+            rw = range_width,
+            nw = name_width
+        );
+        if field.end > field.start {
+            println!(
+                "  {:<rw$}    {}",
+                "",
+                hex::encode(&bytes[field.start..field.end]),
+                rw = range_width
+            );
+        }
+    }
+}
+
+pub fn run(args: &DecodeArgs, output: Option<Output>) -> Result<()> {
+    let bytes = parse_hex(&args.hex)?;
+    let (payload, fields) = match args.payload {
+        Some(payload) => (payload, annotate(&bytes, payload)?),
+        None => {
+            let matches = detect(&bytes);
+            if matches.len() > 1 {
+                let others: Vec<&str> = matches[1..].iter().map(|(p, _)| p.name()).collect();
+                eprintln!(
+                    "note: bytes also decode as {}; use --type to choose",
+                    others.join(", ")
+                );
+            }
+            matches.into_iter().next().ok_or_else(|| {
+                Error::Decode(format!(
+                    "{} bytes do not match any known payload type",
+                    bytes.len()
+                ))
+            })?
+        }
+    };
+    match output.unwrap_or(Output::Table) {
+        Output::Table => {
+            println!("{} ({} bytes)", payload.name(), bytes.len());
+            print_fields(&bytes, &fields);
+        }
+        Output::Json => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|field| {
+                    json!({
+                        "start": field.start,
+                        "end": field.end,
+                        "name": field.name,
+                        "value": field.value,
+                        "bytes": hex::encode(&bytes[field.start..field.end]),
+                    })
+                })
+                .collect();
+            print_json(&json!({
+                "type": payload.name(),
+                "length": bytes.len(),
+                "fields": fields,
+            }))?;
+        }
+        Output::Hex => println!("{}", hex::encode(&bytes)),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use lightpool::order::PlaceOrderParams;
+    use lightpool::transaction::{Action, SPOT_CONTRACT};
+    use lightpool::types::{ObjectId, OrderSide};
+
+    use super::*;
+
+    fn names(fields: &[Field]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|field| format!("{}={}", field.name, field.value))
+            .collect()
+    }
+
+    #[test]
+    fn place_order_fields_are_annotated() {
+        let bytes = PlaceOrderParams::market(OrderSide::Buy, 7, 100, 50).encode();
+        let fields = annotate(&bytes, Payload::PlaceOrder).unwrap();
+        assert_eq!(
+            names(&fields),
+            vec![
+                "side=Buy",
+                "amount=7",
+                "order_type=Market",
+                "order_type.slippage=50",
+                "limit_price=100"
+            ]
+        );
+        assert_eq!((fields[1].start, fields[1].end), (4, 12));
+        assert!(annotate(&bytes[..10], Payload::PlaceOrder).is_err());
+    }
+
+    #[test]
+    fn action_params_are_decoded_by_action_name() {
+        let params = PlaceOrderParams::limit(OrderSide::Sell, 1, 2);
+        let action = Action::new(
+            SPOT_CONTRACT,
+            "ord_place",
+            vec![ObjectId([1; 32])],
+            params.encode(),
+        )
+        .unwrap();
+        let bytes = bincode::serialize(&action).unwrap();
+        let fields = annotate(&bytes, Payload::Action).unwrap();
+        let names = names(&fields);
+        assert!(names.contains(&"action=746789037603618816 (ord_place)".to_string()));
+        assert!(names.contains(&"params.order_type.tif=Gtc".to_string()));
+        assert!(annotate(&bytes, Payload::PlaceOrder).is_err());
+    }
+}
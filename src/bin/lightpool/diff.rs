@@ -0,0 +1,238 @@
+//! `diff`：逐字段比较两段编码
+//!
+//! 两侧按同一类型解析后以字段名对齐，变长字段导致的偏移变化不会让后续字段全部显示为不同。
+//! 实际值无法按该类型解析时，报告解析错误与第一个不同的字节所在的期望字段。
+//! 存在差异时以非零状态退出。
+
+use std::path::Path;
+
+use clap::Args;
+use lightpool::{Error, Result};
+use serde_json::json;
+
+use crate::args::print_json;
+use crate::decode::{annotate, detect, parse_hex, Field, Payload};
+use crate::output::Output;
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// 期望的编码：十六进制字符串，或内容为十六进制的文件（`.bin` 文件按原始字节读取）
+    expected: String,
+    /// 实际的编码，格式同上
+    actual: String,
+    /// 负载类型，省略时按期望值自动识别
+    #[arg(long = "type", value_enum)]
+    payload: Option<Payload>,
+}
+
+/// 读取参数：存在同名文件时读文件，否则按十六进制解析
+fn read_bytes(arg: &str) -> Result<Vec<u8>> {
+    let path = Path::new(arg);
+    if !path.is_file() {
+        return parse_hex(arg);
+    }
+    if path.extension().is_some_and(|ext| ext == "bin") {
+        return Ok(std::fs::read(path)?);
+    }
+    parse_hex(&std::fs::read_to_string(path)?)
+}
+
+/// 一个字段在两侧的取值，缺失的一侧为 None
+#[derive(Debug, PartialEq, Eq)]
+struct FieldDiff<'a> {
+    name: &'a str,
+    expected: Option<&'a Field>,
+    actual: Option<&'a Field>,
+}
+
+/// 按字段名对齐两侧字段，只返回取值或字节不同的字段
+fn diff_fields<'a>(
+    expected: &'a [u8],
+    expected_fields: &'a [Field],
+    actual: &'a [u8],
+    actual_fields: &'a [Field],
+) -> Vec<FieldDiff<'a>> {
+    let bytes = |bytes: &'a [u8], field: &Field| &bytes[field.start..field.end];
+    let mut diffs: Vec<FieldDiff> = expected_fields
+        .iter()
+        .map(|field| {
+            let other = actual_fields.iter().find(|other| other.name == field.name);
+            FieldDiff {
+                name: &field.name,
+                expected: Some(field),
+                actual: other,
+            }
+        })
+        .filter(|diff| match diff.actual {
+            Some(other) => {
+                let field = diff.expected.unwrap();
+                field.value != other.value || bytes(expected, field) != bytes(actual, other)
+            }
+            None => true,
+        })
+        .collect();
+    diffs.extend(
+        actual_fields
+            .iter()
+            .filter(|field| expected_fields.iter().all(|other| other.name != field.name))
+            .map(|field| FieldDiff {
+                name: &field.name,
+                expected: None,
+                actual: Some(field),
+            }),
+    );
+    diffs
+}
+
+/// 第一个不同的字节偏移；一侧是另一侧的前缀时为较短一侧的长度
+fn first_difference(expected: &[u8], actual: &[u8]) -> Option<usize> {
+    expected
+        .iter()
+        .zip(actual)
+        .position(|(a, b)| a != b)
+        .or_else(|| (expected.len() != actual.len()).then(|| expected.len().min(actual.len())))
+}
+
+fn value(field: Option<&Field>) -> &str {
+    field.map_or("(missing)", |field| field.value.as_str())
+}
+
+fn field_bytes(bytes: &[u8], field: Option<&Field>) -> String {
+    field.map_or_else(String::new, |field| {
+        hex::encode(&bytes[field.start..field.end])
+    })
+}
+
+pub fn run(args: &DiffArgs, output: Option<Output>) -> Result<()> {
+    let expected = read_bytes(&args.expected)?;
+    let actual = read_bytes(&args.actual)?;
+    let (payload, expected_fields) = match args.payload {
+        Some(payload) => (payload, annotate(&expected, payload)?),
+        None => detect(&expected).into_iter().next().ok_or_else(|| {
+            Error::Decode(format!(
+                "expected: {} bytes do not match any known payload type",
+                expected.len()
+            ))
+        })?,
+    };
+    let actual_fields = annotate(&actual, payload);
+    let first = first_difference(&expected, &actual);
+    // 第一个不同字节所在的期望字段
+    let first_field = first.and_then(|offset| {
+        expected_fields
+            .iter()
+            .find(|field| field.start <= offset && offset < field.end)
+    });
+    let diffs = match &actual_fields {
+        Ok(actual_fields) => diff_fields(&expected, &expected_fields, &actual, actual_fields),
+        Err(_) => Vec::new(),
+    };
+
+    match output.unwrap_or(Output::Table) {
+        Output::Json => {
+            let fields: Vec<_> = diffs
+                .iter()
+                .map(|diff| {
+                    json!({
+                        "name": diff.name,
+                        "expected": diff.expected.map(|f| &f.value),
+                        "actual": diff.actual.map(|f| &f.value),
+                        "expected_bytes": field_bytes(&expected, diff.expected),
+                        "actual_bytes": field_bytes(&actual, diff.actual),
+                    })
+                })
+                .collect();
+            print_json(&json!({
+                "type": payload.name(),
+                "equal": first.is_none(),
+                "expected_length": expected.len(),
+                "actual_length": actual.len(),
+                "actual_error": actual_fields.as_ref().err().map(ToString::to_string),
+                "first_difference": first,
+                "first_difference_field": first_field.map(|f| &f.name),
+                "fields": fields,
+            }))?;
+        }
+        Output::Table => {
+            println!(
+                "{} (expected {} bytes, actual {} bytes)",
+                payload.name(),
+                expected.len(),
+                actual.len()
+            );
+            if let Err(e) = &actual_fields {
+                println!("  actual does not decode as {}: {}", payload.name(), e);
+            }
+            let width = diffs.iter().map(|diff| diff.name.len()).max().unwrap_or(0);
+            for diff in &diffs {
+                println!(
+                    "  {:<w$}  expected {}, actual {}",
+                    diff.name,
+                    value(diff.expected),
+                    value(diff.actual),
+                    w = width
+                );
+                println!(
+                    "  {:<w$}  {} -> {}",
+                    "",
+                    field_bytes(&expected, diff.expected),
+                    field_bytes(&actual, diff.actual),
+                    w = width
+                );
+            }
+            match (first, first_field) {
+                (None, _) => println!("  identical"),
+                (Some(offset), Some(field)) => {
+                    println!("  first difference at byte {} ({})", offset, field.name)
+                }
+                (Some(offset), None) => println!("  first difference at byte {}", offset),
+            }
+        }
+        Output::Hex => return Err(crate::output::unsupported(Output::Hex, "diff")),
+    }
+
+    match (first, &actual_fields) {
+        (None, _) => Ok(()),
+        (Some(_), Err(_)) => Err(Error::Decode(format!(
+            "actual does not decode as {}",
+            payload.name()
+        ))),
+        (Some(_), Ok(_)) => Err(Error::Validation(format!(
+            "{} field(s) differ",
+            diffs.len()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lightpool::order::PlaceOrderParams;
+    use lightpool::types::OrderSide;
+
+    use super::*;
+
+    #[test]
+    fn fields_are_aligned_by_name() {
+        let expected = PlaceOrderParams::limit(OrderSide::Sell, 5, 100).encode();
+        let actual = PlaceOrderParams::market(OrderSide::Sell, 6, 100, 50).encode();
+        let expected_fields = annotate(&expected, Payload::PlaceOrder).unwrap();
+        let actual_fields = annotate(&actual, Payload::PlaceOrder).unwrap();
+        let diffs = diff_fields(&expected, &expected_fields, &actual, &actual_fields);
+        let names: Vec<&str> = diffs.iter().map(|diff| diff.name).collect();
+        // limit_price 的偏移变了但取值相同，不算差异
+        assert_eq!(
+            names,
+            [
+                "amount",
+                "order_type",
+                "order_type.tif",
+                "order_type.slippage"
+            ]
+        );
+        assert!(diffs[2].actual.is_none());
+        assert!(diffs[3].expected.is_none());
+        assert_eq!(first_difference(&expected, &actual), Some(4));
+        assert_eq!(first_difference(&expected, &expected[..10]), Some(10));
+        assert_eq!(first_difference(&expected, &expected), None);
+    }
+}
@@ -0,0 +1,216 @@
+//! `encode`：生成现货合约参数的 bincode 十六进制
+//!
+//! 参数可由命令行给出，也可由 `--file` 指定的 JSON / TOML 文件给出，两者同时出现时以命令行为准。
+//! 给出市场ID（下单还需余额ID）时额外输出完整的 `Action` JSON。
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use lightpool::order::{
+    cancel_action, CancelOrderParams, OrderRequest, OrderType, PlaceOrderParams, TimeInForce,
+};
+use lightpool::transaction::{Action, SPOT_CONTRACT};
+use lightpool::types::{Address, ObjectId, OrderId};
+use lightpool::{Error, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::args::{read_input, Side, Tif};
+use crate::output::{emit, Output};
+
+#[derive(Debug, Args)]
+pub struct EncodeArgs {
+    #[command(subcommand)]
+    kind: EncodeKind,
+}
+
+#[derive(Debug, Subcommand)]
+enum EncodeKind {
+    /// `ord_place` 参数
+    PlaceOrder {
+        /// 参数文件（JSON 或 TOML）
+        #[arg(long)]
+        file: Option<PathBuf>,
+        #[command(flatten)]
+        input: PlaceOrderInput,
+    },
+    /// `ord_cancel` 参数
+    CancelOrder {
+        /// 参数文件（JSON 或 TOML）
+        #[arg(long)]
+        file: Option<PathBuf>,
+        #[command(flatten)]
+        input: CancelOrderInput,
+    },
+}
+
+/// 下单参数，字段名与命令行参数一致
+#[derive(Debug, Clone, Default, Args, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlaceOrderInput {
+    #[arg(long, value_enum)]
+    side: Option<Side>,
+    #[arg(long)]
+    amount: Option<u64>,
+    /// 限价；市价单为滑点参考价
+    #[arg(long)]
+    price: Option<u64>,
+    /// 限价单有效期，默认 gtc
+    #[arg(long, value_enum)]
+    tif: Option<Tif>,
+    /// 以市价单提交，允许的最大滑点（基点）
+    #[arg(long)]
+    slippage: Option<u64>,
+    /// 市场ID
+    #[arg(long)]
+    market_id: Option<String>,
+    /// 扣款的余额对象ID
+    #[arg(long)]
+    balance_id: Option<String>,
+    /// 市场合约地址，默认现货合约
+    #[arg(long)]
+    market_address: Option<String>,
+}
+
+/// 撤单参数，字段名与命令行参数一致
+#[derive(Debug, Clone, Default, Args, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CancelOrderInput {
+    /// 订单ID（32字节十六进制）
+    #[arg(long)]
+    order_id: Option<String>,
+    /// 市场ID
+    #[arg(long)]
+    market_id: Option<String>,
+    /// 市场合约地址，默认现货合约
+    #[arg(long)]
+    market_address: Option<String>,
+}
+
+fn required<T>(value: Option<T>, name: &str) -> Result<T> {
+    value.ok_or_else(|| Error::Validation(format!("missing required field: {}", name)))
+}
+
+fn market_address(value: &Option<String>) -> Result<Address> {
+    value.as_deref().map_or(Ok(SPOT_CONTRACT), str::parse)
+}
+
+impl PlaceOrderInput {
+    /// 以 `self` 为准，缺失的字段取自 `base`
+    fn or(self, base: Self) -> Self {
+        Self {
+            side: self.side.or(base.side),
+            amount: self.amount.or(base.amount),
+            price: self.price.or(base.price),
+            tif: self.tif.or(base.tif),
+            slippage: self.slippage.or(base.slippage),
+            market_id: self.market_id.or(base.market_id),
+            balance_id: self.balance_id.or(base.balance_id),
+            market_address: self.market_address.or(base.market_address),
+        }
+    }
+
+    pub fn params(&self) -> Result<PlaceOrderParams> {
+        let order_type = match (self.slippage, self.tif) {
+            (Some(_), Some(_)) => {
+                return Err(Error::Validation(
+                    "tif and slippage are mutually exclusive".to_string(),
+                ))
+            }
+            (Some(slippage), None) => OrderType::Market { slippage },
+            (None, tif) => OrderType::Limit {
+                tif: tif.map_or(TimeInForce::Gtc, TimeInForce::from),
+            },
+        };
+        Ok(PlaceOrderParams {
+            side: required(self.side, "side")?.into(),
+            amount: required(self.amount, "amount")?,
+            order_type,
+            limit_price: required(self.price, "price")?,
+        })
+    }
+
+    /// 市场ID与余额ID齐全时生成 `ord_place` 操作
+    fn action(&self, params: PlaceOrderParams) -> Result<Option<Action>> {
+        let (Some(market_id), Some(balance_id)) = (&self.market_id, &self.balance_id) else {
+            return Ok(None);
+        };
+        let order = OrderRequest {
+            market_address: market_address(&self.market_address)?,
+            ..OrderRequest::spot(market_id.parse()?, balance_id.parse()?, params)
+        };
+        order.to_action().map(Some)
+    }
+}
+
+impl CancelOrderInput {
+    fn or(self, base: Self) -> Self {
+        Self {
+            order_id: self.order_id.or(base.order_id),
+            market_id: self.market_id.or(base.market_id),
+            market_address: self.market_address.or(base.market_address),
+        }
+    }
+
+    fn order_id(&self) -> Result<OrderId> {
+        required(self.order_id.as_deref(), "order_id")?.parse()
+    }
+
+    fn action(&self) -> Result<Option<Action>> {
+        let Some(market_id) = &self.market_id else {
+            return Ok(None);
+        };
+        let market_id: ObjectId = market_id.parse()?;
+        cancel_action(
+            market_address(&self.market_address)?,
+            market_id,
+            self.order_id()?,
+        )
+        .map(Some)
+    }
+}
+
+fn load<T: Default + for<'de> Deserialize<'de>>(file: &Option<PathBuf>) -> Result<T> {
+    file.as_deref().map_or_else(|| Ok(T::default()), read_input)
+}
+
+/// 默认只有参数时输出十六进制，带 ID 时输出参数与完整操作的 JSON
+fn print(params: &[u8], action: Option<Action>, output: Option<Output>) -> Result<()> {
+    let default = if action.is_some() {
+        Output::Json
+    } else {
+        Output::Hex
+    };
+    match output.unwrap_or(default) {
+        Output::Hex => {
+            println!("{}", hex::encode(params));
+            Ok(())
+        }
+        output => {
+            let mut value = json!({ "params": hex::encode(params) });
+            if let Some(action) = action {
+                value["action"] = serde_json::to_value(action)?;
+            }
+            emit(output, "encode", &value)
+        }
+    }
+}
+
+pub fn run(args: &EncodeArgs, output: Option<Output>) -> Result<()> {
+    match &args.kind {
+        EncodeKind::PlaceOrder { file, input } => {
+            let input = input.clone().or(load(file)?);
+            let params = input.params()?;
+            let bytes = params.encode();
+            print(&bytes, input.action(params)?, output)
+        }
+        EncodeKind::CancelOrder { file, input } => {
+            let input = input.clone().or(load(file)?);
+            let bytes = bincode::serialize(&CancelOrderParams {
+                order_id: input.order_id()?,
+            })
+            .expect("cancel params are always serializable");
+            print(&bytes, input.action()?, output)
+        }
+    }
+}
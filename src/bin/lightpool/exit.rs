@@ -0,0 +1,83 @@
+//! 退出码与错误输出
+//!
+//! | 退出码 | 含义 |
+//! |---|---|
+//! | 0 | 成功 |
+//! | 1 | 其他错误，例如文件读写失败 |
+//! | 2 | 命令行参数错误 |
+//! | 3 | 参数或输入校验失败，包括无法解析的输入与风控检查 |
+//! | 4 | 节点或行情网关的网络、RPC 错误 |
+//! | 5 | 交易已执行但被拒绝 |
+//! | 6 | 私钥或签名错误 |
+//!
+//! `--output json` 时错误以单行 JSON 输出到标准错误：
+//! `{"error":{"kind":"rpc","exit_code":4,"message":"..","rpc_code":-32000}}`。
+
+use lightpool::Error;
+use serde_json::json;
+
+use crate::output::Output;
+
+/// 错误的类别与退出码
+pub fn classify(error: &Error) -> (&'static str, u8) {
+    match error {
+        Error::Validation(_) | Error::Decode(_) | Error::Risk(_) => ("validation", 3),
+        Error::Network(_)
+        | Error::Rpc { .. }
+        | Error::WebSocket(_)
+        | Error::Auth(_)
+        | Error::ConnectionClosed => ("rpc", 4),
+        Error::Rejected { .. } => ("rejected", 5),
+        Error::Crypto(_) => ("signing", 6),
+        Error::Io(_) | Error::Json(_) => ("other", 1),
+    }
+}
+
+/// 输出错误并返回退出码
+pub fn report(error: &Error, output: Option<Output>) -> u8 {
+    let (kind, code) = classify(error);
+    if output == Some(Output::Json) {
+        let mut body = json!({
+            "kind": kind,
+            "exit_code": code,
+            "message": error.to_string(),
+        });
+        match error {
+            Error::Rpc {
+                code: Some(rpc_code),
+                ..
+            } => body["rpc_code"] = json!(rpc_code),
+            Error::Rejected { digest, .. } if !digest.is_empty() => body["digest"] = json!(digest),
+            _ => {}
+        }
+        eprintln!("{}", json!({ "error": body }));
+    } else {
+        eprintln!("error: {}", error);
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_distinct_per_kind() {
+        let errors = [
+            Error::Validation("bad".to_string()),
+            Error::Rpc {
+                code: Some(-32000),
+                message: "down".to_string(),
+            },
+            Error::Rejected {
+                digest: "0x01".to_string(),
+                status: "failure".to_string(),
+            },
+            Error::Crypto("bad key".to_string()),
+            Error::Io(std::io::Error::other("disk")),
+        ];
+        let codes: Vec<u8> = errors.iter().map(|e| classify(e).1).collect();
+        assert_eq!(codes, [3, 4, 5, 6, 1]);
+        assert_eq!(classify(&Error::ConnectionClosed), ("rpc", 4));
+    }
+}
@@ -0,0 +1,184 @@
+//! `gen-fixtures`：生成 Python SDK 测试比对的黄金文件
+//!
+//! 每种链上类型写一个 `<类型>.json`，内容为用例数组，每个用例包含提交时使用的 JSON 编码
+//! 与 bincode 十六进制。用例只依赖固定的私钥与字节模式，重新生成的文件逐字节相同。
+//!
+//! `gen-corpus` 把同一批用例的原始字节按 `<类型>/<用例>` 写成模糊测试种子，
+//! Python SDK 与本仓库的模糊测试共用这份语料。
+//!
+//! `gen-vectors` 写出带版本号、摘要与签名的向量文件，见 [`lightpool::vectors`]；
+//! `gen-idl` 写出链上类型的语言无关描述，见 [`lightpool::idl`]。
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use lightpool::crypto::Signer;
+use lightpool::idl::{self, export_idl};
+use lightpool::vectors::{self, export_test_vectors, signer, Vector};
+use lightpool::Result;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::output::{emit, Output};
+
+#[derive(Debug, Args)]
+pub struct FixturesArgs {
+    /// 输出目录，不存在时创建
+    #[arg(long, default_value = "fixtures")]
+    out: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct CorpusArgs {
+    /// 输出目录，每种类型一个子目录
+    #[arg(long, default_value = "corpus")]
+    out: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct VectorsArgs {
+    /// 输出文件
+    #[arg(long, default_value = "vectors.json")]
+    out: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct IdlArgs {
+    /// 输出文件
+    #[arg(long, default_value = "lightpool.json")]
+    out: PathBuf,
+}
+
+/// 一个类型的一个代表值
+#[derive(Debug, Serialize)]
+pub struct Case {
+    pub name: &'static str,
+    pub json: Value,
+    #[serde(serialize_with = "hex_bytes")]
+    pub hex: Vec<u8>,
+    /// 签名用例的私钥与公钥，便于对端重新签名比对
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<Value>,
+}
+
+fn hex_bytes<S: serde::Serializer>(
+    bytes: &[u8],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex::encode(bytes))
+}
+
+impl From<Vector> for Case {
+    fn from(vector: Vector) -> Self {
+        Self {
+            name: vector.name,
+            json: vector.input,
+            hex: vector.bytes,
+            signer: None,
+        }
+    }
+}
+
+/// 按类型列出全部用例，签名交易附带签名者的密钥
+pub fn wire_types() -> Vec<(&'static str, Vec<Case>)> {
+    let signer = signer();
+    vectors::wire_types()
+        .into_iter()
+        .map(|(name, vectors)| {
+            let mut cases: Vec<Case> = vectors.into_iter().map(Case::from).collect();
+            if name == "signed-transaction" {
+                for case in &mut cases {
+                    case.signer = Some(json!({
+                        "secret_key": hex::encode(signer.secret_key_bytes()),
+                        "public_key": hex::encode(signer.public_key()),
+                    }));
+                }
+            }
+            (name, cases)
+        })
+        .collect()
+}
+
+fn write(dir: &Path, name: &str, cases: &[Case]) -> Result<PathBuf> {
+    let path = dir.join(format!("{}.json", name));
+    let mut text = serde_json::to_string_pretty(cases)?;
+    text.push('\n');
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
+pub fn run(args: &FixturesArgs, output: Option<Output>) -> Result<()> {
+    std::fs::create_dir_all(&args.out)?;
+    let mut written = Vec::new();
+    for (name, cases) in wire_types() {
+        let path = write(&args.out, name, &cases)?;
+        written.push(json!({ "type": name, "cases": cases.len(), "file": path }));
+    }
+    emit(output.unwrap_or(Output::Table), "gen-fixtures", &written)
+}
+
+pub fn corpus(args: &CorpusArgs, output: Option<Output>) -> Result<()> {
+    let mut written = Vec::new();
+    for (name, cases) in wire_types() {
+        let dir = args.out.join(name);
+        std::fs::create_dir_all(&dir)?;
+        for case in &cases {
+            std::fs::write(dir.join(case.name), &case.hex)?;
+        }
+        written.push(json!({ "type": name, "seeds": cases.len(), "dir": dir }));
+    }
+    emit(output.unwrap_or(Output::Table), "gen-corpus", &written)
+}
+
+pub fn vectors(args: &VectorsArgs, output: Option<Output>) -> Result<()> {
+    export_test_vectors(&args.out)?;
+    let written = json!({
+        "version": vectors::VECTORS_VERSION,
+        "vectors": vectors::test_vectors().vectors.len(),
+        "file": args.out,
+    });
+    emit(output.unwrap_or(Output::Table), "gen-vectors", &written)
+}
+
+pub fn idl(args: &IdlArgs, output: Option<Output>) -> Result<()> {
+    export_idl(&args.out)?;
+    let written = json!({
+        "version": idl::IDL_VERSION,
+        "types": idl::wire_idl().types.len(),
+        "file": args.out,
+    });
+    emit(output.unwrap_or(Output::Table), "gen-idl", &written)
+}
+
+#[cfg(test)]
+mod tests {
+    use lightpool::transaction::SignedTransaction;
+
+    use super::*;
+
+    #[test]
+    fn fixtures_round_trip_and_are_stable() {
+        let types = wire_types();
+        let signed = &types
+            .iter()
+            .find(|(name, _)| *name == "signed-transaction")
+            .unwrap()
+            .1[0];
+        let tx: SignedTransaction = bincode::deserialize(&signed.hex).unwrap();
+        assert_eq!(serde_json::to_value(&tx).unwrap(), signed.json);
+        assert!(tx.verify(&signer().public_key()));
+
+        let place = &types
+            .iter()
+            .find(|(name, _)| *name == "place-order")
+            .unwrap()
+            .1[0];
+        assert_eq!(
+            hex::encode(&place.hex),
+            "01000000404b4c0000000000000000000000000000743ba40b000000"
+        );
+        // 重新生成的结果完全相同
+        let again = serde_json::to_string(&wire_types()).unwrap();
+        assert_eq!(serde_json::to_string(&types).unwrap(), again);
+    }
+}
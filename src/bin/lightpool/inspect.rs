@@ -0,0 +1,187 @@
+//! `inspect-tx`：按摘要或原始字节展示交易的完整解码视图
+//!
+//! 交易没有 nonce，重放由 `expiration` 限制。地址是公钥的哈希，
+//! 签名只有在提供 `--public-key` 时才能校验。
+
+use std::path::Path;
+
+use clap::Args;
+use lightpool::crypto::{address_from_public_key, verify};
+use lightpool::transaction::{decode_action_name, Action, SignedTransaction};
+use lightpool::{Error, LightPoolClient, Result};
+use serde_json::{json, Value};
+
+use crate::args::{print_json, read_signed};
+use crate::decode::{annotate, parse_hex, Field, Payload};
+use crate::output::Output;
+
+#[derive(Debug, Args)]
+pub struct InspectArgs {
+    /// 交易摘要、已签名交易的十六进制 bincode 编码，或 `sign` 输出的文件
+    tx: String,
+    /// 强制把参数当作摘要向节点查询
+    #[arg(long)]
+    digest: bool,
+    /// 发送者的十六进制公钥，用于校验签名
+    #[arg(long)]
+    public_key: Option<String>,
+}
+
+/// 签名校验结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Validity {
+    Valid,
+    Invalid,
+    Unverified,
+}
+
+async fn load(
+    client: &LightPoolClient,
+    args: &InspectArgs,
+) -> Result<(Option<String>, SignedTransaction)> {
+    let input = args.tx.trim();
+    if !args.digest && Path::new(input).is_file() {
+        return Ok((None, read_signed(Path::new(input))?));
+    }
+    match parse_hex(input) {
+        // 摘要为 32 字节，不可能是完整交易
+        Ok(bytes) if !args.digest && bytes.len() != 32 => {
+            annotate(&bytes, Payload::SignedTransaction)?;
+            let tx = bincode::deserialize(&bytes)
+                .map_err(|e| Error::Decode(format!("invalid signed transaction: {}", e)))?;
+            Ok((None, tx))
+        }
+        _ => {
+            let tx = client
+                .get_transaction(input)
+                .await?
+                .ok_or_else(|| Error::Validation(format!("transaction {} not found", input)))?;
+            Ok((Some(input.to_string()), tx))
+        }
+    }
+}
+
+fn parse_public_key(hex_str: &str) -> Result<[u8; 32]> {
+    parse_hex(hex_str)?
+        .try_into()
+        .map_err(|_| Error::Validation("public key must be 32 bytes".to_string()))
+}
+
+fn validities(tx: &SignedTransaction, public_key: Option<&[u8; 32]>) -> Vec<Validity> {
+    let message = tx.transaction.signing_bytes();
+    tx.signatures
+        .iter()
+        .map(|signature| match public_key {
+            None => Validity::Unverified,
+            Some(key) if address_from_public_key(key) != tx.transaction.sender => Validity::Invalid,
+            Some(key) if verify(key, &message, &signature.to_bytes()) => Validity::Valid,
+            Some(_) => Validity::Invalid,
+        })
+        .collect()
+}
+
+/// 解码 Action 的各字段，合约与动作名另行展示
+fn action_fields(action: &Action) -> Result<Vec<Field>> {
+    let bytes = bincode::serialize(action).expect("action is always serializable");
+    Ok(annotate(&bytes, Payload::Action)?
+        .into_iter()
+        .filter(|f| {
+            !matches!(
+                f.name.as_str(),
+                "inputs.len" | "contract" | "action" | "params.len"
+            )
+        })
+        .collect())
+}
+
+impl Validity {
+    fn name(self) -> &'static str {
+        match self {
+            Validity::Valid => "valid",
+            Validity::Invalid => "invalid",
+            Validity::Unverified => "unverified",
+        }
+    }
+}
+
+pub async fn run(
+    client: &LightPoolClient,
+    args: &InspectArgs,
+    output: Option<Output>,
+) -> Result<()> {
+    let (digest, tx) = load(client, args).await?;
+    let public_key = args
+        .public_key
+        .as_deref()
+        .map(parse_public_key)
+        .transpose()?;
+    let key_matches = public_key
+        .as_ref()
+        .map(|key| address_from_public_key(key) == tx.transaction.sender);
+    let validities = validities(&tx, public_key.as_ref());
+
+    match output.unwrap_or(Output::Table) {
+        Output::Hex => {
+            let bytes = bincode::serialize(&tx).expect("transaction is always serializable");
+            println!("{}", hex::encode(bytes));
+        }
+        Output::Json => {
+            let mut actions = Vec::new();
+            for action in &tx.transaction.actions {
+                let fields: serde_json::Map<String, Value> = action_fields(action)?
+                    .into_iter()
+                    .map(|field| (field.name, Value::String(field.value)))
+                    .collect();
+                actions.push(json!({
+                    "name": decode_action_name(action.action),
+                    "action": action.action,
+                    "contract": action.contract.to_string(),
+                    "fields": fields,
+                }));
+            }
+            let signatures: Vec<&str> = validities.iter().map(|v| v.name()).collect();
+            print_json(&json!({
+                "digest": digest,
+                "sender": tx.transaction.sender.to_string(),
+                "expiration": tx.transaction.expiration,
+                "actions": actions,
+                "public_key_matches_sender": key_matches,
+                "signatures": signatures,
+            }))?;
+        }
+        Output::Table => {
+            if let Some(digest) = digest {
+                println!("digest:     {}", digest);
+            }
+            println!("sender:     {}", tx.transaction.sender);
+            println!("expiration: {}", tx.transaction.expiration);
+            println!("actions ({}):", tx.transaction.actions.len());
+            for (i, action) in tx.transaction.actions.iter().enumerate() {
+                let name =
+                    decode_action_name(action.action).unwrap_or_else(|| action.action.to_string());
+                println!("  [{}] {} @ {}", i, name, action.contract);
+                let fields = action_fields(action)?;
+                let width = fields.iter().map(|f| f.name.len()).max().unwrap_or(0);
+                for field in &fields {
+                    println!("      {:<w$} = {}", field.name, field.value, w = width);
+                }
+            }
+            if key_matches == Some(false) {
+                println!("public key does not match sender");
+            }
+            println!("signatures ({}):", validities.len());
+            for (i, validity) in validities.iter().enumerate() {
+                match validity {
+                    Validity::Unverified => {
+                        println!("  [{}] unverified (pass --public-key to check)", i)
+                    }
+                    validity => println!("  [{}] {}", i, validity.name()),
+                }
+            }
+        }
+    }
+    if validities.contains(&Validity::Invalid) {
+        return Err(Error::Crypto("signature verification failed".to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+//! `keygen`：生成私钥与助记词并写入加密私钥文件
+//!
+//! 链上只校验 ed25519 签名，因此只生成 ed25519 私钥。
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::Args;
+use lightpool::crypto::{Ed25519Signer, Signer};
+use lightpool::keystore::{generate_mnemonic, signer_from_mnemonic, Keystore, ScryptParams};
+use lightpool::{Error, Result};
+
+use serde_json::json;
+
+use crate::args::{new_password, print_json};
+use crate::output::Output;
+
+#[derive(Debug, Args)]
+pub struct KeygenArgs {
+    /// 加密私钥文件的写入路径
+    #[arg(long, short, default_value = "keystore.json")]
+    out: PathBuf,
+    /// 覆盖已存在的私钥文件
+    #[arg(long)]
+    force: bool,
+    /// 助记词单词数
+    #[arg(long, default_value_t = 12, value_parser = PossibleValuesParser::new(["12", "24"]).map(|s| s.parse::<usize>().unwrap()))]
+    words: usize,
+    /// 交互输入已有的助记词恢复私钥，而不是生成新私钥
+    #[arg(long, conflicts_with_all = ["words", "no_mnemonic"])]
+    recover: bool,
+    /// 直接生成随机私钥，不输出助记词
+    #[arg(long, conflicts_with = "words")]
+    no_mnemonic: bool,
+    /// scrypt 代价参数 log2(N)，仅在测试等场景下调低
+    #[arg(long, default_value_t = ScryptParams::default().log_n, hide = true)]
+    scrypt_log_n: u8,
+}
+
+pub fn run(args: &KeygenArgs, output: Option<Output>) -> Result<()> {
+    let output = output.unwrap_or(Output::Table);
+    if args.out.exists() && !args.force {
+        return Err(Error::Validation(format!(
+            "{} already exists; pass --force to overwrite it",
+            args.out.display()
+        )));
+    }
+
+    let (signer, mnemonic) = if args.recover {
+        let phrase = rpassword::prompt_password("mnemonic: ")?;
+        (signer_from_mnemonic(&phrase)?, None)
+    } else if args.no_mnemonic {
+        (Ed25519Signer::generate(), None)
+    } else {
+        let phrase = generate_mnemonic(args.words)?;
+        (signer_from_mnemonic(&phrase)?, Some(phrase))
+    };
+
+    let password = new_password()?;
+    let params = ScryptParams {
+        log_n: args.scrypt_log_n,
+        ..ScryptParams::default()
+    };
+    Keystore::encrypt_with(&signer, &password, params)?.save(&args.out)?;
+
+    if mnemonic.is_some() {
+        eprintln!();
+        eprintln!("WARNING: the mnemonic is the only backup of this key.");
+        eprintln!(
+            "Write it down offline and never share it; anyone holding it controls the account."
+        );
+        if std::io::stdout().is_terminal() {
+            eprintln!("Clear your terminal scrollback once it is stored safely.");
+        }
+    }
+    match output {
+        Output::Json => print_json(&json!({
+            "address": signer.address().to_string(),
+            "keystore": args.out,
+            "mnemonic": mnemonic,
+        })),
+        _ => {
+            println!("address:  {}", signer.address());
+            println!("keystore: {}", args.out.display());
+            if let Some(phrase) = mnemonic {
+                println!("mnemonic: {}", phrase);
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,387 @@
+//! `book --live`：终端订单簿界面
+//!
+//! 订单簿经行情网关订阅并校验序列号，出现缺口时按登记表中的市场ID通过 RPC 拉取快照重同步。
+//! 指定账户时每隔几秒查询一次挂单，所在价位高亮显示。按 `q` 或 Esc 退出。
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures_util::StreamExt;
+use lightpool::orderbook::Orderbook;
+use lightpool::types::{Address, OrderSide};
+use lightpool::ws::{OrderbookUpdate, PriceLevel, SnapshotSource, Trade, WsClient};
+use lightpool::{Error, LightPoolClient, Result};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use serde_json::Value;
+
+use crate::markets::{MarketEntry, Registry};
+use crate::query::resting_order;
+
+/// 保留的最近成交数量
+const TRADE_HISTORY: usize = 50;
+
+/// 查询挂单的间隔
+const ORDERS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 重同步时拉取的快照深度
+const SNAPSHOT_DEPTH: u32 = 1000;
+
+/// 按市场ID拉取快照，并改写为行情频道使用的市场名
+struct Snapshots {
+    client: LightPoolClient,
+    market: String,
+    market_id: String,
+}
+
+impl SnapshotSource for Snapshots {
+    async fn fetch_snapshot(&self, _market: &str) -> Result<OrderbookUpdate> {
+        let mut snapshot = self
+            .client
+            .get_order_book(&self.market_id, SNAPSHOT_DEPTH)
+            .await?;
+        snapshot.market = self.market.clone();
+        Ok(snapshot)
+    }
+}
+
+/// 把链上整数按 `decimals` 位小数显示，省略末尾的 0
+fn units(value: u64, decimals: u32) -> String {
+    let scale = 10u64.pow(decimals);
+    let frac = format!("{:0width$}", value % scale, width = decimals as usize);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        (value / scale).to_string()
+    } else {
+        format!("{}.{}", value / scale, frac)
+    }
+}
+
+/// 毫秒时间戳的 UTC 时分秒
+fn clock(ts: u64) -> String {
+    let secs = ts / 1000 % 86_400;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn side_color(side: OrderSide) -> Color {
+    match side {
+        OrderSide::Buy => Color::Green,
+        OrderSide::Sell => Color::Red,
+    }
+}
+
+/// 界面状态
+struct Live<'a> {
+    market: String,
+    entry: &'a MarketEntry,
+    depth: usize,
+    book: Option<Orderbook>,
+    trades: VecDeque<Trade>,
+    resting: Vec<(OrderSide, u64)>,
+    status: String,
+}
+
+impl<'a> Live<'a> {
+    fn new(market: String, entry: &'a MarketEntry, depth: usize) -> Self {
+        Self {
+            market,
+            entry,
+            depth,
+            book: None,
+            trades: VecDeque::new(),
+            resting: Vec::new(),
+            status: "waiting for snapshot".to_string(),
+        }
+    }
+
+    fn price(&self, value: u64) -> String {
+        units(value, self.entry.price_decimals)
+    }
+
+    fn size(&self, value: u64) -> String {
+        units(value, self.entry.size_decimals)
+    }
+
+    fn on_book(&mut self, update: &OrderbookUpdate) {
+        let result = match (&mut self.book, update.snapshot) {
+            (_, true) => Orderbook::from_snapshot(update).map(|book| self.book = Some(book)),
+            (Some(book), false) => book.apply(update).map(|_| ()),
+            (None, false) => Ok(()),
+        };
+        self.status = match result {
+            Ok(()) => String::new(),
+            Err(e) => e.to_string(),
+        };
+    }
+
+    fn on_trade(&mut self, trade: Trade) {
+        self.trades.push_front(trade);
+        self.trades.truncate(TRADE_HISTORY);
+    }
+
+    fn set_orders(&mut self, orders: &[Value]) {
+        self.resting = orders.iter().filter_map(resting_order).collect();
+    }
+
+    fn level_row(&self, side: OrderSide, level: PriceLevel) -> Row<'static> {
+        let own = self.resting.contains(&(side, level.price));
+        let mut style = Style::default().fg(side_color(side));
+        if own {
+            style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        }
+        Row::new([
+            if own { "*" } else { "" }.to_string(),
+            self.price(level.price),
+            self.size(level.size),
+        ])
+        .style(style)
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [ladder, trades] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(body);
+
+        let mut title = self.market.clone();
+        if let Some(book) = &self.book {
+            title.push_str(&format!("  seq {}", book.seq()));
+            if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+                title.push_str(&format!(
+                    "  spread {}",
+                    self.price(ask.price.saturating_sub(bid.price))
+                ));
+            }
+        }
+        frame.render_widget(Line::from(title).style(Modifier::BOLD), header);
+
+        let levels = self.book.as_ref().map(|book| book.levels(self.depth));
+        let rows: Vec<Row> = levels
+            .iter()
+            .flat_map(|depth| {
+                let asks = depth.asks.iter().rev().map(|l| (OrderSide::Sell, *l));
+                let bids = depth.bids.iter().map(|l| (OrderSide::Buy, *l));
+                asks.chain(bids)
+            })
+            .map(|(side, level)| self.level_row(side, level))
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["", "price", "size"]).style(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL).title("book"));
+        frame.render_widget(table, ladder);
+
+        let rows: Vec<Row> = self
+            .trades
+            .iter()
+            .map(|trade| {
+                Row::new([
+                    clock(trade.ts),
+                    self.price(trade.price),
+                    self.size(trade.size),
+                ])
+                .style(Style::default().fg(side_color(trade.side)))
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["time", "price", "size"]).style(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL).title("trades"));
+        frame.render_widget(table, trades);
+
+        let status = if self.status.is_empty() {
+            "q: quit".to_string()
+        } else {
+            format!("{}  q: quit", self.status)
+        };
+        frame.render_widget(Line::from(status), footer);
+    }
+}
+
+fn is_quit(key: &KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && (matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+            || key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+pub async fn run(
+    client: &LightPoolClient,
+    ws_url: &str,
+    registry: &Registry,
+    market: &str,
+    depth: u32,
+    address: Option<&Address>,
+) -> Result<()> {
+    let (market, entry) = registry.get(market)?;
+    let market_id = entry.market_id.to_string();
+    let ws = WsClient::connect(ws_url).await?;
+    let source = Snapshots {
+        client: client.clone(),
+        market: market.clone(),
+        market_id: market_id.clone(),
+    };
+    let mut book = ws.subscribe_orderbook_synced(&market, source)?;
+    let mut trades = ws.subscribe_trades(&market)?;
+
+    let mut live = Live::new(market, entry, depth as usize);
+    // 最近成交按时间升序返回，逐条压入后最新的在最前
+    for trade in client
+        .get_trades(&market_id, TRADE_HISTORY as u32)
+        .await
+        .unwrap_or_default()
+    {
+        live.on_trade(trade);
+    }
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(
+        &mut terminal,
+        &mut live,
+        client,
+        &market_id,
+        address,
+        &mut book,
+        &mut trades,
+    )
+    .await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop<S: SnapshotSource>(
+    terminal: &mut DefaultTerminal,
+    live: &mut Live<'_>,
+    client: &LightPoolClient,
+    market_id: &str,
+    address: Option<&Address>,
+    book: &mut lightpool::ws::SyncedOrderbook<S>,
+    trades: &mut lightpool::ws::Subscription<Trade>,
+) -> Result<()> {
+    let mut keys = EventStream::new();
+    let mut poll = tokio::time::interval(ORDERS_POLL_INTERVAL);
+    loop {
+        terminal.draw(|frame| live.draw(frame))?;
+        tokio::select! {
+            update = book.next() => live.on_book(&update.ok_or(Error::ConnectionClosed)??),
+            trade = trades.next() => live.on_trade(trade.ok_or(Error::ConnectionClosed)?),
+            _ = poll.tick(), if address.is_some() => {
+                let address = address.expect("guarded by the branch condition");
+                match client.get_orders(address, Some(market_id)).await {
+                    Ok(orders) => live.set_orders(&orders),
+                    Err(e) => live.status = format!("orders: {}", e),
+                }
+            }
+            event = keys.next() => match event {
+                Some(Ok(Event::Key(key))) if is_quit(&key) => return Ok(()),
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(()),
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn units_drop_trailing_zeros() {
+        assert_eq!(units(500_000, 6), "0.5");
+        assert_eq!(units(50_000_000_000, 6), "50000");
+        assert_eq!(units(25, 2), "0.25");
+        assert_eq!(units(3, 0), "3");
+    }
+
+    #[test]
+    fn ladder_highlights_resting_orders() {
+        let entry: MarketEntry = serde_json::from_value(json!({
+            "market_id": format!("0x{}", "11".repeat(32)),
+            "size_decimals": 2,
+            "price_decimals": 1,
+        }))
+        .unwrap();
+        let mut live = Live::new("BTC-USDC".to_string(), &entry, 5);
+        live.on_book(&OrderbookUpdate {
+            market: "BTC-USDC".to_string(),
+            seq: 7,
+            snapshot: true,
+            bids: vec![PriceLevel {
+                price: 4990,
+                size: 150,
+            }],
+            asks: vec![
+                PriceLevel {
+                    price: 5000,
+                    size: 25,
+                },
+                PriceLevel {
+                    price: 5010,
+                    size: 300,
+                },
+            ],
+            ts: 0,
+        });
+        live.on_trade(Trade {
+            market: "BTC-USDC".to_string(),
+            price: 5000,
+            size: 10,
+            side: OrderSide::Buy,
+            trade_id: None,
+            ts: 3_723_000,
+        });
+        live.set_orders(&[
+            json!({ "side": "Buy", "price": 4990 }),
+            json!({ "side": "sell", "limit_price": "5010" }),
+            json!({ "status": "unknown" }),
+        ]);
+        assert_eq!(
+            live.resting,
+            [(OrderSide::Buy, 4990), (OrderSide::Sell, 5010)]
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal.draw(|frame| live.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let lines: Vec<String> = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect();
+        assert!(lines[0].starts_with("BTC-USDC  seq 7  spread 1"));
+        // 卖盘价格从高到低排在买盘之上，自己的挂单带 `*` 并高亮
+        assert!(lines[3].starts_with("│* 501            3 "));
+        assert!(lines[4].starts_with("│  500            0.25 "));
+        assert!(lines[5].starts_with("│* 499            1.5 "));
+        assert!(buffer[(1, 3)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(1, 4)].modifier.contains(Modifier::REVERSED));
+        assert!(lines[3].contains("│01:02:03 500      0.1 "));
+    }
+}
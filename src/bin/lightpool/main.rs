@@ -0,0 +1,382 @@
+//! LightPool 命令行工具
+//!
+//! 离线命令（`keygen`、`encode`、`decode`、`diff`、`sign`、`gen-fixtures`、`gen-corpus`、`gen-vectors`、`gen-idl`、`codegen`、`bench`）不访问网络；
+//! `watch` 连接 `--ws-url` 指定的行情网关，其余命令通过 `--rpc-url` 指定的节点查询或提交。
+//! 未在命令行给出的节点地址、私钥文件、默认市场等取自配置文件中的 profile，见 [`config`]。
+//! 失败时的退出码按错误类别区分，见 [`exit`]。
+//! 日志由 `-v` 开启，输出到标准错误，`--log-json` 时为每行一个 JSON 对象。
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{CommandFactory, Parser, Subcommand};
+use config::{Config, Profile, RiskConfig};
+use lightpool::{LightPoolClient, Result};
+use markets::Registry;
+use output::Output;
+use serde_json::json;
+use submit::SubmitMode;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::fmt::fmt;
+use tracing_subscriber::prelude::*;
+
+/// 未配置时使用的 RPC 节点地址
+const DEFAULT_RPC_URL: &str = "http://localhost:26300";
+
+/// 未配置时使用的行情网关地址
+const DEFAULT_WS_URL: &str = "ws://localhost:26300/ws";
+
+/// 未配置时的 RPC 请求超时（秒）
+const DEFAULT_TIMEOUT: u64 = 30;
+
+mod args;
+mod batch;
+mod bench;
+mod cancel;
+mod codegen;
+mod completions;
+mod config;
+mod decode;
+mod diff;
+mod encode;
+mod exit;
+mod fixtures;
+mod inspect;
+mod keygen;
+#[cfg(feature = "tui")]
+mod live;
+mod markets;
+mod output;
+mod portfolio;
+mod query;
+mod repl;
+mod sign;
+mod submit;
+mod watch;
+
+#[derive(Debug, Parser)]
+#[command(name = "lightpool", version, about = "LightPool 命令行工具")]
+struct Cli {
+    /// 配置文件，默认 `~/.lightpool/config.toml`
+    #[arg(long, global = true, env = "LIGHTPOOL_CONFIG")]
+    config: Option<PathBuf>,
+    /// 使用配置文件中的 profile
+    #[arg(long, global = true, env = "LIGHTPOOL_PROFILE")]
+    profile: Option<String>,
+    /// RPC 节点地址，默认 http://localhost:26300
+    #[arg(long, global = true, env = "LIGHTPOOL_RPC_URL")]
+    rpc_url: Option<String>,
+    /// 行情网关地址，默认 ws://localhost:26300/ws
+    #[arg(long, global = true, env = "LIGHTPOOL_WS_URL")]
+    ws_url: Option<String>,
+    /// RPC 请求超时（秒），默认 30
+    #[arg(long, global = true, env = "LIGHTPOOL_TIMEOUT")]
+    timeout: Option<u64>,
+    /// 输出格式，省略时使用各命令的默认格式
+    #[arg(long, global = true, value_enum)]
+    output: Option<Output>,
+    /// 会改变链上状态的命令只构建、签名并输出交易，不提交
+    #[arg(long, global = true)]
+    dry_run: bool,
+    /// 与 `--dry-run` 一起使用，由节点模拟执行交易
+    #[arg(long, global = true, requires = "dry_run")]
+    simulate: bool,
+    /// 市场登记表，默认 `~/.lightpool/markets.json`
+    #[arg(long, global = true, env = "LIGHTPOOL_MARKETS")]
+    markets: Option<PathBuf>,
+    /// 在标准错误输出日志：`-v` 为 info，`-vv` 为 debug，`-vvv` 为 trace
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// 日志每行输出一个 JSON 对象，便于日志管道采集
+    #[arg(long, global = true)]
+    log_json: bool,
+    /// profile 中的风控限额
+    #[arg(skip)]
+    risk: Option<RiskConfig>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// 生成 ed25519 私钥、助记词与加密私钥文件（链上只支持 ed25519 签名）
+    Keygen(keygen::KeygenArgs),
+    /// 编码下单、撤单参数
+    Encode(encode::EncodeArgs),
+    /// 解码十六进制参数
+    Decode(decode::DecodeArgs),
+    /// 逐字段比较两段编码
+    Diff(diff::DiffArgs),
+    /// 签名交易
+    Sign(sign::SignArgs),
+    /// 生成 Python SDK 测试比对的黄金文件
+    GenFixtures(fixtures::FixturesArgs),
+    /// 导出各类型的编码字节，作为模糊测试的种子语料
+    GenCorpus(fixtures::CorpusArgs),
+    /// 导出带版本号、摘要与签名的跨语言测试向量
+    GenVectors(fixtures::VectorsArgs),
+    /// 导出链上类型的语言无关描述（IDL）
+    GenIdl(fixtures::IdlArgs),
+    /// 由 IDL 生成其他语言 SDK 的类型与编解码
+    Codegen(codegen::CodegenArgs),
+    /// 测量本机编码、哈希与签名的吞吐量和延迟
+    Bench(bench::BenchArgs),
+    /// 展示交易的完整解码视图与签名校验结果
+    InspectTx(inspect::InspectArgs),
+    /// 提交已签名交易
+    Submit(submit::SubmitArgs),
+    /// 查询账户挂单
+    Orders(query::OrdersArgs),
+    /// 撤销账户的全部挂单
+    CancelAll(cancel::CancelAllArgs),
+    /// 查询订单簿
+    Book(query::BookArgs),
+    /// 查询账户余额
+    Balances(query::BalancesArgs),
+    /// 列出登记表中的市场，可从节点刷新
+    Markets(markets::MarketsArgs),
+    /// 汇总账户余额、持仓、盈亏与挂单敞口
+    Portfolio(portfolio::PortfolioArgs),
+    /// 订阅行情网关并逐行输出事件
+    Watch(watch::WatchArgs),
+    /// 交互式会话，可手动下单与查询
+    Repl(repl::ReplArgs),
+    /// 生成 shell 补全脚本
+    Completions(completions::CompletionsArgs),
+    /// 显示生效的 profile 与参数
+    Profile,
+}
+
+impl Command {
+    fn name(&self) -> &'static str {
+        match self {
+            Command::Keygen(_) => "keygen",
+            Command::Encode(_) => "encode",
+            Command::Decode(_) => "decode",
+            Command::Diff(_) => "diff",
+            Command::Sign(_) => "sign",
+            Command::GenFixtures(_) => "gen-fixtures",
+            Command::GenCorpus(_) => "gen-corpus",
+            Command::GenVectors(_) => "gen-vectors",
+            Command::GenIdl(_) => "gen-idl",
+            Command::Codegen(_) => "codegen",
+            Command::Bench(_) => "bench",
+            Command::InspectTx(_) => "inspect-tx",
+            Command::Submit(_) => "submit",
+            Command::Orders(args) => match args.command {
+                Some(query::OrdersCommand::Place(_)) => "orders place",
+                None => "orders",
+            },
+            Command::CancelAll(_) => "cancel-all",
+            Command::Book(_) => "book",
+            Command::Balances(_) => "balances",
+            Command::Markets(_) => "markets",
+            Command::Portfolio(_) => "portfolio",
+            Command::Watch(args) => args.name(),
+            Command::Repl(_) => "repl",
+            Command::Completions(_) => "completions",
+            Command::Profile => "profile",
+        }
+    }
+
+    /// 只有产出字节的命令支持 `--output hex`
+    fn supports_hex(&self, mode: SubmitMode) -> bool {
+        match self {
+            Command::Encode(_) | Command::Decode(_) | Command::Sign(_) | Command::InspectTx(_) => {
+                true
+            }
+            Command::Submit(_) => mode == SubmitMode::DryRun,
+            _ => false,
+        }
+    }
+}
+
+impl Cli {
+    fn rpc_url(&self) -> &str {
+        self.rpc_url.as_deref().unwrap_or(DEFAULT_RPC_URL)
+    }
+
+    fn ws_url(&self) -> &str {
+        self.ws_url.as_deref().unwrap_or(DEFAULT_WS_URL)
+    }
+
+    fn timeout(&self) -> u64 {
+        self.timeout.unwrap_or(DEFAULT_TIMEOUT)
+    }
+
+    fn submit_mode(&self) -> SubmitMode {
+        match (self.dry_run, self.simulate) {
+            (_, true) => SubmitMode::Simulate,
+            (true, false) => SubmitMode::DryRun,
+            (false, false) => SubmitMode::Submit,
+        }
+    }
+
+    /// 按 `-v` 次数设置日志级别，默认不输出日志；依赖库的日志到 `-vvv` 才完整输出
+    fn init_logging(&self) {
+        let (ours, others) = match self.verbose {
+            0 => return,
+            1 => (LevelFilter::INFO, LevelFilter::WARN),
+            2 => (LevelFilter::DEBUG, LevelFilter::WARN),
+            _ => (LevelFilter::TRACE, LevelFilter::TRACE),
+        };
+        let filter = Targets::new()
+            .with_target("lightpool", ours)
+            .with_default(others);
+        let logs = fmt().with_writer(std::io::stderr).with_max_level(ours);
+        if self.log_json {
+            logs.json().finish().with(filter).init();
+        } else {
+            let ansi = std::io::stderr().is_terminal();
+            logs.with_ansi(ansi).finish().with(filter).init();
+        }
+    }
+
+    fn client(&self) -> Result<LightPoolClient> {
+        let client = LightPoolClient::new(self.rpc_url(), Duration::from_secs(self.timeout()))?;
+        Ok(match &self.risk {
+            Some(risk) => client.with_risk_limits(risk.limits()),
+            None => client,
+        })
+    }
+
+    /// 以生效的 profile 补全命令行未给出的参数，返回 profile 名称与覆盖环境变量之后的 profile
+    fn apply_profile(&mut self) -> Result<(Option<String>, Profile)> {
+        let config = match self.config.clone().or_else(Config::default_path) {
+            Some(path) => Config::load(&path)?,
+            None => Config::default(),
+        };
+        let (name, mut profile) = match config.profile(self.profile.as_deref())? {
+            Some((name, profile)) => (Some(name), profile),
+            None => (None, Profile::default()),
+        };
+        profile.override_from(|name| std::env::var(name).ok());
+        let (rpc_url, ws_url) = profile.endpoints()?;
+        self.rpc_url = self.rpc_url.take().or(rpc_url);
+        self.ws_url = self.ws_url.take().or(ws_url);
+        self.timeout = self.timeout.or(profile.timeout);
+        self.markets = self.markets.take().or_else(|| profile.markets.clone());
+        self.risk = profile.risk.clone();
+        let key = profile.key.as_deref();
+        match &mut self.command {
+            Command::Sign(args) => args.key.default_keystore(key.map(Into::into)),
+            Command::Repl(args) => {
+                args.key.default_keystore(key.map(Into::into));
+                args.default_market(profile.market.clone());
+            }
+            Command::Orders(args) => match &mut args.command {
+                Some(query::OrdersCommand::Place(args)) => {
+                    args.key.default_keystore(key.map(Into::into))
+                }
+                None => args.default_address(key)?,
+            },
+            Command::CancelAll(args) => args.key.default_keystore(key.map(Into::into)),
+            Command::Balances(args) => args.default_address(key)?,
+            Command::Portfolio(args) => args.default_address(key)?,
+            Command::Book(args) => {
+                args.default_market(profile.market.clone());
+                #[cfg(feature = "tui")]
+                args.default_address(key)?;
+            }
+            Command::Watch(args) => {
+                args.default_market(profile.market.clone());
+                if let Some(args) = args.key() {
+                    args.default_keystore(key.map(Into::into));
+                }
+            }
+            _ => {}
+        }
+        Ok((name, profile))
+    }
+
+    /// 读取市场登记表，找不到默认路径时视为空表
+    fn registry(&self) -> Result<Registry> {
+        match self.markets.clone().or_else(Registry::default_path) {
+            Some(path) => Registry::load(&path),
+            None => Ok(Registry::default()),
+        }
+    }
+}
+
+async fn run(mut cli: Cli) -> Result<()> {
+    cli.init_logging();
+    let (name, profile) = cli.apply_profile()?;
+    tracing::info!(
+        command = cli.command.name(),
+        profile = name.as_deref(),
+        rpc_url = cli.rpc_url(),
+        "running"
+    );
+    let output = cli.output;
+    let mode = cli.submit_mode();
+    // 执行之前检查，避免交易已提交却无法输出结果
+    if output == Some(Output::Hex) && !cli.command.supports_hex(mode) {
+        return Err(output::unsupported(Output::Hex, cli.command.name()));
+    }
+    match &cli.command {
+        Command::Keygen(args) => keygen::run(args, output),
+        Command::Encode(args) => encode::run(args, output),
+        Command::Decode(args) => decode::run(args, output),
+        Command::Diff(args) => diff::run(args, output),
+        Command::Sign(args) => sign::run(args, output),
+        Command::GenFixtures(args) => fixtures::run(args, output),
+        Command::GenCorpus(args) => fixtures::corpus(args, output),
+        Command::GenVectors(args) => fixtures::vectors(args, output),
+        Command::GenIdl(args) => fixtures::idl(args, output),
+        Command::Codegen(args) => codegen::run(args, output),
+        Command::Bench(args) => bench::run(args, output),
+        Command::InspectTx(args) => inspect::run(&cli.client()?, args, output).await,
+        Command::Submit(args) => submit::run(&cli.client()?, args, mode, output).await,
+        Command::Orders(args) => match &args.command {
+            Some(query::OrdersCommand::Place(args)) => {
+                batch::place(&cli.client()?, &cli.registry()?, args, mode, output).await
+            }
+            None => query::orders(&cli.client()?, &cli.registry()?, args, output).await,
+        },
+        Command::CancelAll(args) => {
+            cancel::run(&cli.client()?, &cli.registry()?, args, mode, output).await
+        }
+        #[cfg(feature = "tui")]
+        Command::Book(args) if args.is_live() => {
+            query::live_book(&cli.client()?, cli.ws_url(), &cli.registry()?, args).await
+        }
+        Command::Book(args) => query::book(&cli.client()?, &cli.registry()?, args, output).await,
+        Command::Balances(args) => query::balances(&cli.client()?, args, output).await,
+        Command::Markets(args) => {
+            let path = cli.markets.clone().or_else(Registry::default_path);
+            markets::run(&cli.client()?, path.as_deref(), args, output).await
+        }
+        Command::Portfolio(args) => {
+            portfolio::run(&cli.client()?, &cli.registry()?, args, output).await
+        }
+        Command::Watch(args) => watch::run(cli.ws_url(), args, output).await,
+        Command::Repl(args) => repl::run(&cli.client()?, cli.registry()?, args, mode, output).await,
+        Command::Completions(args) => completions::run(Cli::command(), &cli.registry()?, args),
+        Command::Profile => output::emit(
+            output.unwrap_or(Output::Json),
+            "profile",
+            &json!({
+                "profile": name,
+                "network": profile.network,
+                "rpc_url": cli.rpc_url(),
+                "ws_url": cli.ws_url(),
+                "timeout": cli.timeout(),
+                "key": profile.key,
+                "market": profile.market,
+                "markets": cli.markets.clone().or_else(Registry::default_path),
+            }),
+        ),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let output = cli.output;
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => ExitCode::from(exit::report(&e, output)),
+    }
+}
@@ -0,0 +1,287 @@
+//! 本地市场登记表与 `markets` 命令
+//!
+//! 节点只能按市场ID查询，登记表把交易对名称映射到链上ID、余额对象与小数位数，
+//! 默认位于 `~/.lightpool/markets.json`：
+//!
+//! ```json
+//! { "markets": { "BTC-USDC": { "market_id": "0x..", "base_balance": "0x..", "quote_balance": "0x.." } } }
+//! ```
+//!
+//! `markets --refresh` 按登记的市场ID从节点取回价格单位、最小下单数量与状态，写回登记表。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use lightpool::order::{parse_units, Market, OrderRequest, PlaceOrderParams};
+use lightpool::transaction::SPOT_CONTRACT;
+use lightpool::types::{Address, ObjectId};
+use lightpool::{Error, LightPoolClient, Result, Symbol};
+use serde::{Deserialize, Serialize};
+
+use crate::args::{lightpool_dir, read_json};
+use crate::output::{emit, Output};
+
+/// 数量与价格的默认小数位数
+const DEFAULT_DECIMALS: u32 = 6;
+
+fn default_decimals() -> u32 {
+    DEFAULT_DECIMALS
+}
+
+fn spot_contract() -> Address {
+    SPOT_CONTRACT
+}
+
+/// 以十六进制字符串读写ID，便于手工编辑登记表
+mod hex_str {
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<T: Display, S: Serializer>(
+            value: &Option<T>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => serializer.collect_str(value),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            T: FromStr,
+            T::Err: Display,
+            D: Deserializer<'de>,
+        {
+            Option::<String>::deserialize(deserializer)?
+                .map(|s| s.parse().map_err(D::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+/// 登记表中的一个市场
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarketEntry {
+    #[serde(with = "hex_str")]
+    pub market_id: ObjectId,
+    #[serde(with = "hex_str", default = "spot_contract")]
+    pub market_address: Address,
+    /// 卖单扣款的基础币余额对象
+    #[serde(with = "hex_str::option", default)]
+    pub base_balance: Option<ObjectId>,
+    /// 买单扣款的报价币余额对象
+    #[serde(with = "hex_str::option", default)]
+    pub quote_balance: Option<ObjectId>,
+    #[serde(default = "default_decimals")]
+    pub size_decimals: u32,
+    #[serde(default = "default_decimals")]
+    pub price_decimals: u32,
+    /// 最小价格变动单位；此字段与以下字段取自节点，由 `markets --refresh` 更新
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tick_size: Option<u64>,
+    /// 最小下单数量
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lot_size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+impl MarketEntry {
+    /// 登记的链上市场
+    pub fn market(&self) -> Market {
+        Market {
+            market_address: self.market_address,
+            market_id: self.market_id,
+            base_balance: self.base_balance,
+            quote_balance: self.quote_balance,
+        }
+    }
+
+    /// 在登记名为 `name` 的市场下单的请求；缺少扣款的余额对象时返回错误
+    pub fn order_request(&self, name: &str, params: PlaceOrderParams) -> Result<OrderRequest> {
+        self.market()
+            .order_request(params)
+            .map_err(|e| Error::Validation(format!("{} in the registry: {}", name, e)))
+    }
+
+    /// 把十进制数量换算为链上整数
+    pub fn size(&self, value: &str) -> Result<u64> {
+        parse_units(value, self.size_decimals)
+    }
+
+    /// 把十进制价格换算为链上整数
+    pub fn price(&self, value: &str) -> Result<u64> {
+        parse_units(value, self.price_decimals)
+    }
+}
+
+/// 交易对名称到市场的映射
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Registry {
+    #[serde(default)]
+    markets: BTreeMap<String, MarketEntry>,
+}
+
+impl Registry {
+    /// 默认路径 `~/.lightpool/markets.json`
+    pub fn default_path() -> Option<PathBuf> {
+        lightpool_dir().map(|dir| dir.join("markets.json"))
+    }
+
+    /// 读取登记表，文件不存在时返回空表
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let registry: Registry = read_json(path)?;
+        // 名称统一为 `BASE-QUOTE`
+        let mut markets = BTreeMap::new();
+        for (name, entry) in registry.markets {
+            markets.insert(Symbol::parse(&name)?.market(), entry);
+        }
+        Ok(Self { markets })
+    }
+
+    /// 按交易对名称查找市场，返回规范名称
+    pub fn get(&self, name: &str) -> Result<(String, &MarketEntry)> {
+        let market = Symbol::parse(name)?.market();
+        match self.markets.get(&market) {
+            Some(entry) => Ok((market, entry)),
+            None => Err(Error::Validation(format!(
+                "market {} is not in the registry",
+                market
+            ))),
+        }
+    }
+
+    /// 按市场ID反查登记表
+    pub fn by_market_id(&self, market_id: &ObjectId) -> Option<(&str, &MarketEntry)> {
+        self.markets
+            .iter()
+            .find(|(_, entry)| entry.market_id == *market_id)
+            .map(|(name, entry)| (name.as_str(), entry))
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.markets.keys().map(String::as_str)
+    }
+
+    /// 写回登记表，名称为规范写法
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut text = serde_json::to_string_pretty(self)?;
+        text.push('\n');
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// 按节点的市场信息更新合约地址、价格单位、最小数量与状态，返回查询失败的市场
+    pub async fn refresh(&mut self, client: &LightPoolClient) -> Vec<(String, Error)> {
+        let mut failed = Vec::new();
+        for (name, entry) in &mut self.markets {
+            match client.get_market_info(&entry.market_id.to_string()).await {
+                Ok(info) => {
+                    if let Ok(address) = info.market_address.parse() {
+                        entry.market_address = address;
+                    }
+                    entry.tick_size = Some(info.tick_size);
+                    entry.lot_size = Some(info.min_order_size);
+                    entry.status = info.status;
+                }
+                Err(e) => {
+                    entry.status = Some("unavailable".to_string());
+                    failed.push((name.clone(), e));
+                }
+            }
+        }
+        failed
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct MarketsArgs {
+    /// 先从节点查询各市场的信息并写回登记表
+    #[arg(long)]
+    refresh: bool,
+}
+
+/// 列表中的一行
+#[derive(Serialize)]
+struct Listing<'a> {
+    name: &'a str,
+    #[serde(flatten)]
+    entry: &'a MarketEntry,
+}
+
+pub async fn run(
+    client: &LightPoolClient,
+    path: Option<&Path>,
+    args: &MarketsArgs,
+    output: Option<Output>,
+) -> Result<()> {
+    let mut registry = match path {
+        Some(path) => Registry::load(path)?,
+        None => Registry::default(),
+    };
+    if args.refresh {
+        let path =
+            path.ok_or_else(|| Error::Validation("no registry path: pass --markets".to_string()))?;
+        for (name, error) in registry.refresh(client).await {
+            eprintln!("{}: {}", name, error);
+        }
+        registry.save(path)?;
+    }
+    let listing: Vec<Listing> = registry
+        .markets
+        .iter()
+        .map(|(name, entry)| Listing { name, entry })
+        .collect();
+    emit(output.unwrap_or(Output::Table), "markets", &listing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_amounts_are_scaled() {
+        assert_eq!(parse_units("0.5", 6).unwrap(), 500_000);
+        assert_eq!(parse_units("50000", 6).unwrap(), 50_000_000_000);
+        assert_eq!(parse_units(".25", 2).unwrap(), 25);
+        assert_eq!(parse_units("3.", 0).unwrap(), 3);
+        assert!(parse_units("0.1234567", 6).is_err());
+        assert!(parse_units("1e5", 6).is_err());
+        assert!(parse_units("-1", 6).is_err());
+        assert!(parse_units(".", 6).is_err());
+        assert!(parse_units("99999999999999999999", 0).is_err());
+    }
+}
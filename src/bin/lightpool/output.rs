@@ -0,0 +1,232 @@
+//! 输出格式
+//!
+//! `--output` 省略时各命令使用各自的默认格式：`decode`、`inspect-tx`、`keygen` 为表格，
+//! 不带 ID 的 `encode` 为十六进制，其余为 JSON。十六进制只适用于产出字节的命令。
+
+use clap::ValueEnum;
+use lightpool::{Error, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::args::print_json;
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    /// 格式化 JSON，便于交给 jq 等工具处理
+    Json,
+    /// 对齐的文本表格
+    Table,
+    /// 十六进制 bincode 编码
+    Hex,
+}
+
+impl Output {
+    fn name(self) -> &'static str {
+        match self {
+            Output::Json => "json",
+            Output::Table => "table",
+            Output::Hex => "hex",
+        }
+    }
+}
+
+/// 命令不支持该输出格式
+pub fn unsupported(output: Output, command: &str) -> Error {
+    Error::Validation(format!(
+        "--output {} is not supported by {}",
+        output.name(),
+        command
+    ))
+}
+
+/// 以 JSON 或表格输出结果
+pub fn emit<T: Serialize + ?Sized>(output: Output, command: &str, value: &T) -> Result<()> {
+    match output {
+        Output::Json => print_json(value),
+        Output::Table => {
+            println!("{}", table(&serde_json::to_value(value)?));
+            Ok(())
+        }
+        Output::Hex => Err(unsupported(output, command)),
+    }
+}
+
+/// 输出流式事件的一行：JSON 为单行对象（ndjson），表格为空格分隔的 `字段=值`
+pub fn line<T: Serialize + ?Sized>(output: Output, command: &str, value: &T) -> Result<()> {
+    match output {
+        Output::Json => println!("{}", serde_json::to_string(value)?),
+        Output::Table => {
+            let mut fields = Vec::new();
+            flatten(String::new(), &serde_json::to_value(value)?, &mut fields);
+            let fields: Vec<String> = fields
+                .into_iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            println!("{}", fields.join(" "));
+        }
+        Output::Hex => return Err(unsupported(output, command)),
+    }
+    Ok(())
+}
+
+/// 32 字节的ID与地址在 JSON 中是整数数组，表格中显示为十六进制
+fn as_id(items: &[Value]) -> Option<String> {
+    let bytes: Vec<u8> = items
+        .iter()
+        .map(|item| item.as_u64().and_then(|n| u8::try_from(n).ok()))
+        .collect::<Option<_>>()?;
+    (bytes.len() == 32).then(|| format!("0x{}", hex::encode(bytes)))
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => as_id(items).unwrap_or_else(|| value.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// 按字段路径展开嵌套的对象与数组
+fn flatten(path: String, value: &Value, rows: &mut Vec<(String, String)>) {
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                flatten(join(key), value, rows);
+            }
+        }
+        Value::Array(items) if !items.is_empty() && as_id(items).is_none() => {
+            for (i, value) in items.iter().enumerate() {
+                flatten(format!("{}[{}]", path, i), value, rows);
+            }
+        }
+        _ => rows.push((path, cell(value))),
+    }
+}
+
+fn render(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:<width$}", cell))
+                .collect();
+            line.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 渲染为表格：对象数组每个元素一行、字段为列，其余值按字段路径逐行展开
+pub fn table(value: &Value) -> String {
+    match value {
+        Value::Array(items) if items.is_empty() => "(empty)".to_string(),
+        Value::Array(items) if items.iter().all(Value::is_object) => {
+            let flattened: Vec<Vec<(String, String)>> = items
+                .iter()
+                .map(|item| {
+                    let mut rows = Vec::new();
+                    flatten(String::new(), item, &mut rows);
+                    rows
+                })
+                .collect();
+            // 列为各行字段的并集，按首次出现的顺序排列
+            let mut headers: Vec<String> = Vec::new();
+            for (key, _) in flattened.iter().flatten() {
+                if !headers.contains(key) {
+                    headers.push(key.clone());
+                }
+            }
+            let mut rows = vec![headers.clone()];
+            for item in &flattened {
+                rows.push(
+                    headers
+                        .iter()
+                        .map(|header| {
+                            item.iter()
+                                .find(|(key, _)| key == header)
+                                .map_or_else(|| "-".to_string(), |(_, value)| value.clone())
+                        })
+                        .collect(),
+                );
+            }
+            render(&rows)
+        }
+        _ => {
+            let mut rows = Vec::new();
+            flatten(String::new(), value, &mut rows);
+            if let [(path, value)] = rows.as_slice() {
+                if path.is_empty() {
+                    return value.clone();
+                }
+            }
+            let rows: Vec<Vec<String>> = rows.into_iter().map(|(k, v)| vec![k, v]).collect();
+            render(&rows)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn arrays_of_objects_become_columns() {
+        let value = json!([
+            { "token_address": "0xaa", "balance": 10 },
+            { "token_address": "0xbbbb", "balance": 2, "locked": true },
+        ]);
+        assert_eq!(
+            table(&value),
+            "balance  token_address  locked\n\
+             10       0xaa           -\n\
+             2        0xbbbb         true"
+        );
+        assert_eq!(table(&json!([])), "(empty)");
+    }
+
+    #[test]
+    fn objects_are_flattened_with_ids_as_hex() {
+        let value = json!({
+            "digest": "0xabc",
+            "receipt": { "status": "success", "events": [] },
+            "sender": vec![1u8; 32],
+            "levels": [[1, 2]],
+        });
+        let id = format!("0x{}", "01".repeat(32));
+        assert_eq!(
+            table(&value),
+            format!(
+                "digest          0xabc\n\
+                 levels[0][0]    1\n\
+                 levels[0][1]    2\n\
+                 receipt.events  []\n\
+                 receipt.status  success\n\
+                 sender          {}",
+                id
+            )
+        );
+        assert_eq!(table(&json!("plain")), "plain");
+    }
+}
@@ -0,0 +1,235 @@
+//! `portfolio`：账户余额、持仓、盈亏与挂单敞口的汇总视图
+//!
+//! 节点不提供成交历史，持仓由 `--fills` 给出的成交记录重建，
+//! 文件为每行一个成交的 JSON（即 `watch fills --output json` 的输出），只计入本账户的成交。
+//! 未实现盈亏按节点的标记价格计算，不在登记表中或没有标记价格的市场记为 0。
+//! 数量与价格均为链上整数。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use lightpool::position::{Position, PositionTracker};
+use lightpool::types::{Address, OrderSide};
+use lightpool::ws::Fill;
+use lightpool::{Error, LightPoolClient, Result};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::markets::Registry;
+use crate::output::{emit, Output};
+use crate::query::{default_address, order_str, order_u64, required_address, resting_order};
+
+#[derive(Debug, Args)]
+pub struct PortfolioArgs {
+    /// 账户地址，默认取 profile 中私钥文件的地址
+    #[arg(long)]
+    address: Option<Address>,
+    /// 成交记录，每行一个 JSON，用于重建持仓
+    #[arg(long)]
+    fills: Option<PathBuf>,
+}
+
+impl PortfolioArgs {
+    pub fn default_address(&mut self, keystore: Option<&Path>) -> Result<()> {
+        default_address(&mut self.address, keystore)
+    }
+}
+
+/// 一个市场的挂单敞口
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct Exposure {
+    market: String,
+    buy_orders: u64,
+    buy_size: u64,
+    /// 买单按挂单价计算的名义金额
+    buy_notional: f64,
+    sell_orders: u64,
+    sell_size: u64,
+    sell_notional: f64,
+}
+
+/// 读取成交记录，跳过空行与其他账户的成交
+fn read_fills(path: &Path, address: &Address) -> Result<Vec<Fill>> {
+    let account = address.to_string();
+    let text = std::fs::read_to_string(path)?;
+    let mut fills = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fill: Fill = serde_json::from_str(line)
+            .map_err(|e| Error::Decode(format!("{}:{}: {}", path.display(), index + 1, e)))?;
+        if fill.account.eq_ignore_ascii_case(&account) {
+            fills.push(fill);
+        }
+    }
+    Ok(fills)
+}
+
+/// 按市场汇总挂单的剩余数量与名义金额，市场ID能在登记表中找到时显示交易对名称
+fn exposure(registry: &Registry, orders: &[Value]) -> Vec<Exposure> {
+    let mut markets: BTreeMap<String, Exposure> = BTreeMap::new();
+    for order in orders {
+        let Some((side, price)) = resting_order(order) else {
+            continue;
+        };
+        let size = order_u64(
+            order,
+            &[
+                "remaining",
+                "remaining_size",
+                "remainingSize",
+                "size",
+                "amount",
+            ],
+        )
+        .unwrap_or(0);
+        let market_id = order_str(order, &["market_id", "marketId"]).unwrap_or("-");
+        let market = market_id
+            .parse()
+            .ok()
+            .and_then(|id| registry.by_market_id(&id))
+            .map_or(market_id, |(name, _)| name);
+        let entry = markets
+            .entry(market.to_string())
+            .or_insert_with(|| Exposure {
+                market: market.to_string(),
+                ..Exposure::default()
+            });
+        let notional = price as f64 * size as f64;
+        match side {
+            OrderSide::Buy => {
+                entry.buy_orders += 1;
+                entry.buy_size += size;
+                entry.buy_notional += notional;
+            }
+            OrderSide::Sell => {
+                entry.sell_orders += 1;
+                entry.sell_size += size;
+                entry.sell_notional += notional;
+            }
+        }
+    }
+    markets.into_values().collect()
+}
+
+fn position(position: &Position) -> Value {
+    json!({
+        "market": position.market,
+        "size": position.size,
+        "avg_entry_price": position.avg_entry_price,
+        "cost_basis": position.cost_basis,
+        "mark_price": position.mark_price,
+        "unrealized_pnl": position.unrealized_pnl,
+        "realized_pnl": position.realized_pnl,
+        "funding_pnl": position.funding_pnl,
+        "fees": position.fees,
+    })
+}
+
+pub async fn run(
+    client: &LightPoolClient,
+    registry: &Registry,
+    args: &PortfolioArgs,
+    output: Option<Output>,
+) -> Result<()> {
+    let address = required_address(&args.address)?;
+    let balances = client.get_balances(address).await?;
+    let orders = client.get_orders(address, None).await?;
+
+    let mut tracker = PositionTracker::new();
+    if let Some(path) = &args.fills {
+        for fill in read_fills(path, address)? {
+            tracker.apply(&fill);
+        }
+    }
+    let open: Vec<String> = tracker
+        .snapshot()
+        .into_iter()
+        .filter(|position| !position.is_flat())
+        .map(|position| position.market)
+        .collect();
+    for market in &open {
+        let Ok((_, entry)) = registry.get(market) else {
+            continue;
+        };
+        if let Ok(mark) = client.get_mark_price(&entry.market_id.to_string()).await {
+            tracker.update_mark(market, mark.price);
+        }
+    }
+    let positions: Vec<Value> = open
+        .iter()
+        .filter_map(|market| tracker.position(market))
+        .map(position)
+        .collect();
+    let exposure = exposure(registry, &orders);
+    let totals = json!({
+        "realized_pnl": tracker.total_realized_pnl(),
+        "unrealized_pnl": tracker.total_unrealized_pnl(),
+        "funding_pnl": tracker.total_funding_pnl(),
+        "open_orders": orders.len(),
+    });
+
+    match output.unwrap_or(Output::Json) {
+        Output::Table => {
+            for (title, section) in [
+                ("balances", serde_json::to_value(&balances)?),
+                ("positions", Value::from(positions)),
+                ("open orders", serde_json::to_value(&exposure)?),
+                ("totals", totals),
+            ] {
+                println!("{}", title);
+                emit(Output::Table, "portfolio", &section)?;
+                println!();
+            }
+            Ok(())
+        }
+        output => emit(
+            output,
+            "portfolio",
+            &json!({
+                "address": address,
+                "balances": balances,
+                "positions": positions,
+                "open_orders": exposure,
+                "totals": totals,
+            }),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exposure_sums_resting_orders_by_market() {
+        let orders = vec![
+            json!({ "side": "Buy", "price": 100, "remaining": 3, "market_id": "m1" }),
+            json!({ "side": "buy", "limit_price": "90", "size": 2, "market_id": "m1" }),
+            json!({ "side": "Sell", "price": 110, "amount": 5, "marketId": "m2" }),
+            json!({ "price": 1, "size": 1 }),
+        ];
+        let exposure = exposure(&Registry::default(), &orders);
+        assert_eq!(
+            exposure,
+            [
+                Exposure {
+                    market: "m1".to_string(),
+                    buy_orders: 2,
+                    buy_size: 5,
+                    buy_notional: 480.0,
+                    ..Exposure::default()
+                },
+                Exposure {
+                    market: "m2".to_string(),
+                    sell_orders: 1,
+                    sell_size: 5,
+                    sell_notional: 550.0,
+                    ..Exposure::default()
+                },
+            ]
+        );
+    }
+}
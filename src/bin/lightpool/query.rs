@@ -0,0 +1,232 @@
+//! 查询命令：`orders`、`book`、`balances`
+//!
+//! `orders place` 批量下单，见 [`crate::batch`]；批量撤单见 [`crate::cancel`]。
+
+use std::path::Path;
+
+use clap::{Args, Subcommand};
+use lightpool::types::{Address, OrderSide};
+use lightpool::{Error, LightPoolClient, Result};
+
+use serde_json::{json, Value};
+
+use crate::args::keystore_address;
+use crate::markets::Registry;
+use crate::output::{emit, Output};
+
+#[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct OrdersArgs {
+    #[command(subcommand)]
+    pub command: Option<OrdersCommand>,
+    /// 账户地址，默认取 profile 中私钥文件的地址
+    #[arg(long)]
+    address: Option<Address>,
+    /// 只查询该市场的挂单
+    #[arg(long)]
+    market_id: Option<String>,
+    /// 按登记表中的交易对名称指定市场
+    #[arg(long, conflicts_with = "market_id")]
+    market: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum OrdersCommand {
+    /// 从 CSV 批量下单
+    Place(crate::batch::PlaceArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct BookArgs {
+    /// 交易对名称，与 `--market` 相同
+    #[arg(value_name = "MARKET", conflicts_with_all = ["market_id", "market"])]
+    name: Option<String>,
+    /// 市场ID；与 `--market` 都省略时取 profile 中的默认市场
+    #[arg(long)]
+    market_id: Option<String>,
+    /// 按登记表中的交易对名称指定市场
+    #[arg(long, conflicts_with = "market_id")]
+    market: Option<String>,
+    #[arg(long, default_value_t = 10)]
+    depth: u32,
+    /// 在终端界面中实时显示订单簿、最近成交与自己的挂单
+    #[cfg(feature = "tui")]
+    #[arg(long, conflicts_with = "market_id")]
+    live: bool,
+    /// 实时界面中高亮该账户的挂单，默认取 profile 中私钥文件的地址
+    #[cfg(feature = "tui")]
+    #[arg(long, requires = "live")]
+    address: Option<Address>,
+}
+
+#[derive(Debug, Args)]
+pub struct BalancesArgs {
+    /// 账户地址，默认取 profile 中私钥文件的地址
+    #[arg(long)]
+    address: Option<Address>,
+}
+
+/// 未指定地址时取私钥文件中的地址
+pub fn default_address(address: &mut Option<Address>, keystore: Option<&Path>) -> Result<()> {
+    if let (None, Some(keystore)) = (&address, keystore) {
+        *address = Some(keystore_address(keystore)?);
+    }
+    Ok(())
+}
+
+pub fn required_address(address: &Option<Address>) -> Result<&Address> {
+    address.as_ref().ok_or_else(|| {
+        Error::Validation(
+            "an address is required: pass --address or set `key` in the profile".to_string(),
+        )
+    })
+}
+
+impl OrdersArgs {
+    pub fn default_address(&mut self, keystore: Option<&Path>) -> Result<()> {
+        default_address(&mut self.address, keystore)
+    }
+}
+
+impl BalancesArgs {
+    pub fn default_address(&mut self, keystore: Option<&Path>) -> Result<()> {
+        default_address(&mut self.address, keystore)
+    }
+}
+
+impl BookArgs {
+    pub fn default_market(&mut self, market: Option<String>) {
+        if self.market_id.is_none() && self.market.is_none() && self.name.is_none() {
+            self.market = market;
+        }
+    }
+
+    #[cfg(feature = "tui")]
+    pub fn is_live(&self) -> bool {
+        self.live
+    }
+
+    #[cfg(feature = "tui")]
+    pub fn default_address(&mut self, keystore: Option<&Path>) -> Result<()> {
+        if self.live {
+            default_address(&mut self.address, keystore)?;
+        }
+        Ok(())
+    }
+}
+
+/// 按候选字段名取订单的字符串字段，兼容不同节点版本的命名
+pub fn order_str<'a>(order: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|key| order.get(*key)?.as_str())
+}
+
+/// 按候选字段名取订单的整数字段，数字与十进制字符串均可
+pub fn order_u64(order: &Value, keys: &[&str]) -> Option<u64> {
+    match keys.iter().find_map(|key| order.get(*key))? {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// 从节点返回的订单 JSON 中取出方向与价格，字段缺失时返回 None
+pub fn resting_order(order: &Value) -> Option<(OrderSide, u64)> {
+    let side = match order_str(order, &["side"])?.to_ascii_lowercase().as_str() {
+        "buy" => OrderSide::Buy,
+        "sell" => OrderSide::Sell,
+        _ => return None,
+    };
+    let price = order_u64(order, &["price", "limit_price", "limitPrice"])?;
+    Some((side, price))
+}
+
+/// 取市场ID，交易对名称经登记表解析
+pub fn market_id(
+    registry: &Registry,
+    market_id: &Option<String>,
+    market: &Option<String>,
+) -> Result<Option<String>> {
+    match market {
+        Some(market) => Ok(Some(registry.get(market)?.1.market_id.to_string())),
+        None => Ok(market_id.clone()),
+    }
+}
+
+pub async fn orders(
+    client: &LightPoolClient,
+    registry: &Registry,
+    args: &OrdersArgs,
+    output: Option<Output>,
+) -> Result<()> {
+    let market_id = market_id(registry, &args.market_id, &args.market)?;
+    let orders = client
+        .get_orders(required_address(&args.address)?, market_id.as_deref())
+        .await?;
+    emit(output.unwrap_or(Output::Json), "orders", &orders)
+}
+
+pub async fn book(
+    client: &LightPoolClient,
+    registry: &Registry,
+    args: &BookArgs,
+    output: Option<Output>,
+) -> Result<()> {
+    let market = args.market.as_ref().or(args.name.as_ref()).cloned();
+    let market_id = market_id(registry, &args.market_id, &market)?.ok_or_else(|| {
+        Error::Validation(
+            "a market is required: pass --market-id or --market, or set `market` in the profile"
+                .to_string(),
+        )
+    })?;
+    let book = client.get_order_book(&market_id, args.depth).await?;
+    match output.unwrap_or(Output::Json) {
+        // 表格按价格从高到低排列，卖盘在上
+        Output::Table => {
+            let levels: Vec<_> = book
+                .asks
+                .iter()
+                .rev()
+                .map(|level| ("ask", level))
+                .chain(book.bids.iter().map(|level| ("bid", level)))
+                .map(|(side, level)| json!({ "side": side, "price": level.price, "size": level.size }))
+                .collect();
+            emit(Output::Table, "book", &levels)
+        }
+        output => emit(output, "book", &book),
+    }
+}
+
+/// `book --live`
+#[cfg(feature = "tui")]
+pub async fn live_book(
+    client: &LightPoolClient,
+    ws_url: &str,
+    registry: &Registry,
+    args: &BookArgs,
+) -> Result<()> {
+    let market = args.market.as_ref().or(args.name.as_ref()).ok_or_else(|| {
+        Error::Validation(
+            "a market name is required: pass MARKET or set `market` in the profile".to_string(),
+        )
+    })?;
+    crate::live::run(
+        client,
+        ws_url,
+        registry,
+        market,
+        args.depth,
+        args.address.as_ref(),
+    )
+    .await
+}
+
+pub async fn balances(
+    client: &LightPoolClient,
+    args: &BalancesArgs,
+    output: Option<Output>,
+) -> Result<()> {
+    let balances = client
+        .get_balances(required_address(&args.address)?)
+        .await?;
+    emit(output.unwrap_or(Output::Json), "balances", &balances)
+}
@@ -0,0 +1,371 @@
+//! `repl`：保持客户端与已解锁私钥的交互会话
+//!
+//! 每行一条命令，数量与价格按登记表中的小数位数以十进制书写：
+//!
+//! ```text
+//! buy BTC-USDC 0.5 @ 50000 [gtc|ioc|fok]
+//! sell 0.5 @ 51000          # 省略市场时使用 --market 或 profile 中的默认市场
+//! cancel BTC-USDC <order-id>
+//! orders [MARKET] | book [MARKET [DEPTH]] | balances | markets | address | help | quit
+//! ```
+
+use std::io::{BufRead, IsTerminal, Write};
+
+use clap::Args;
+use lightpool::crypto::{Ed25519Signer, Signer};
+use lightpool::order::{OrderType, PlaceOrderParams, TimeInForce};
+use lightpool::transaction::TransactionBuilder;
+use lightpool::types::{OrderId, OrderSide};
+use lightpool::{Error, LightPoolClient, Result};
+use serde_json::json;
+
+use crate::args::KeyArgs;
+use crate::markets::Registry;
+use crate::output::{emit, Output};
+use crate::submit::{preview, SubmitMode};
+
+const HELP: &str = "\
+commands:
+  buy [MARKET] SIZE @ PRICE [gtc|ioc|fok]   place a limit buy order
+  sell [MARKET] SIZE @ PRICE [gtc|ioc|fok]  place a limit sell order
+  cancel MARKET ORDER_ID                    cancel an order
+  orders [MARKET]                           list open orders
+  book [MARKET [DEPTH]]                     show the order book
+  balances                                  list token balances
+  markets                                   list markets in the registry
+  address                                   show the session address
+  help                                      show this help
+  quit                                      leave the session
+MARKET defaults to --market or the profile market";
+
+#[derive(Debug, Args)]
+pub struct ReplArgs {
+    #[command(flatten)]
+    pub key: KeyArgs,
+    /// 省略市场的下单与订单簿命令使用的市场
+    #[arg(long)]
+    market: Option<String>,
+}
+
+/// 一行会话命令
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Line {
+    Order {
+        side: OrderSide,
+        /// 省略时使用会话的默认市场
+        market: Option<String>,
+        size: String,
+        price: String,
+        tif: TimeInForce,
+    },
+    Cancel {
+        market: String,
+        order_id: OrderId,
+    },
+    Orders {
+        market: Option<String>,
+    },
+    Book {
+        market: Option<String>,
+        depth: u32,
+    },
+    Balances,
+    Markets,
+    Address,
+    Help,
+    Quit,
+}
+
+const ORDER_USAGE: &str = "buy|sell [MARKET] SIZE @ PRICE [gtc|ioc|fok]";
+
+fn usage(usage: &str) -> Error {
+    Error::Validation(format!("usage: {}", usage))
+}
+
+/// 解析一行命令；空行与 `#` 注释返回 None
+fn parse_line(line: &str) -> Result<Option<Line>> {
+    let line = line
+        .split('#')
+        .next()
+        .unwrap_or_default()
+        .replace('@', " @ ");
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let Some((&command, rest)) = words.split_first() else {
+        return Ok(None);
+    };
+    let line = match (command.to_ascii_lowercase().as_str(), rest) {
+        (side @ ("buy" | "sell"), args) => {
+            let (market, size, price, tif) = match args {
+                [size, "@", price, tif @ ..] => (None, size, price, tif),
+                [market, size, "@", price, tif @ ..] => (Some(market), size, price, tif),
+                _ => return Err(usage(ORDER_USAGE)),
+            };
+            let tif = match tif {
+                [] => TimeInForce::Gtc,
+                [tif] => match tif.to_ascii_lowercase().as_str() {
+                    "gtc" => TimeInForce::Gtc,
+                    "ioc" => TimeInForce::Ioc,
+                    "fok" => TimeInForce::Fok,
+                    _ => return Err(Error::Validation(format!("unknown time in force: {}", tif))),
+                },
+                _ => return Err(usage(ORDER_USAGE)),
+            };
+            Line::Order {
+                side: if side == "buy" {
+                    OrderSide::Buy
+                } else {
+                    OrderSide::Sell
+                },
+                market: market.map(|m| m.to_string()),
+                size: size.to_string(),
+                price: price.to_string(),
+                tif,
+            }
+        }
+        ("cancel", [market, order_id]) => Line::Cancel {
+            market: market.to_string(),
+            order_id: order_id.parse()?,
+        },
+        ("cancel", _) => return Err(usage("cancel MARKET ORDER_ID")),
+        ("orders", market) if market.len() <= 1 => Line::Orders {
+            market: market.first().map(|m| m.to_string()),
+        },
+        ("book", []) => Line::Book {
+            market: None,
+            depth: 10,
+        },
+        ("book", [market]) => Line::Book {
+            market: Some(market.to_string()),
+            depth: 10,
+        },
+        ("book", [market, depth]) => Line::Book {
+            market: Some(market.to_string()),
+            depth: depth
+                .parse()
+                .map_err(|_| Error::Validation(format!("invalid depth: {}", depth)))?,
+        },
+        ("book", _) => return Err(usage("book MARKET [DEPTH]")),
+        ("balances", []) => Line::Balances,
+        ("markets", []) => Line::Markets,
+        ("address", []) => Line::Address,
+        ("help" | "?", _) => Line::Help,
+        ("quit" | "exit", []) => Line::Quit,
+        _ => {
+            return Err(Error::Validation(format!(
+                "unknown command: {} (type `help`)",
+                words.join(" ")
+            )))
+        }
+    };
+    Ok(Some(line))
+}
+
+struct Session<'a> {
+    client: &'a LightPoolClient,
+    signer: Option<Ed25519Signer>,
+    registry: Registry,
+    market: Option<String>,
+    mode: SubmitMode,
+    output: Output,
+}
+
+impl Session<'_> {
+    fn print<T: serde::Serialize>(&self, value: &T) -> Result<()> {
+        emit(self.output, "repl", value)
+    }
+
+    fn signer(&self) -> Result<&Ed25519Signer> {
+        self.signer.as_ref().ok_or_else(|| {
+            Error::Validation("no key unlocked: restart with --key or --private-key".to_string())
+        })
+    }
+
+    /// 省略市场时取默认市场
+    fn market(&self, market: Option<String>) -> Result<String> {
+        market.or_else(|| self.market.clone()).ok_or_else(|| {
+            Error::Validation("a market is required: no default market is set".to_string())
+        })
+    }
+
+    async fn execute(&self, line: Line) -> Result<()> {
+        match line {
+            Line::Order {
+                side,
+                market,
+                size,
+                price,
+                tif,
+            } => {
+                let (market, entry) = self.registry.get(&self.market(market)?)?;
+                let params = PlaceOrderParams {
+                    side,
+                    amount: entry.size(&size)?,
+                    order_type: OrderType::Limit { tif },
+                    limit_price: entry.price(&price)?,
+                };
+                let order = entry.order_request(&market, params)?;
+                let signer = self.signer()?;
+                if self.mode != SubmitMode::Submit {
+                    // 提交时由 place_order 检查
+                    self.client.check_risk(&order.params)?;
+                    let tx = TransactionBuilder::new()
+                        .action(order.to_action()?)
+                        .build_and_sign(signer)?;
+                    return preview(self.client, &tx, self.mode, self.output, "repl").await;
+                }
+                let result = self.client.place_order(signer, &order).await?;
+                self.print(&json!({ "digest": result.digest, "status": result.receipt.status }))
+            }
+            Line::Cancel { market, order_id } => {
+                let (_, entry) = self.registry.get(&market)?;
+                let action = entry.market().cancel_action(order_id)?;
+                let tx = TransactionBuilder::new()
+                    .action(action)
+                    .build_and_sign(self.signer()?)?;
+                if self.mode != SubmitMode::Submit {
+                    return preview(self.client, &tx, self.mode, self.output, "repl").await;
+                }
+                let result = self.client.submit_transaction(&tx).await?;
+                self.print(&json!({ "digest": result.digest, "status": result.receipt.status }))
+            }
+            Line::Orders { market } => {
+                let market_id = match market {
+                    Some(market) => Some(self.registry.get(&market)?.1.market_id.to_string()),
+                    None => None,
+                };
+                let orders = self
+                    .client
+                    .get_orders(&self.signer()?.address(), market_id.as_deref())
+                    .await?;
+                self.print(&orders)
+            }
+            Line::Book { market, depth } => {
+                let (_, entry) = self.registry.get(&self.market(market)?)?;
+                let book = self
+                    .client
+                    .get_order_book(&entry.market_id.to_string(), depth)
+                    .await?;
+                self.print(&book)
+            }
+            Line::Balances => {
+                let balances = self.client.get_balances(&self.signer()?.address()).await?;
+                self.print(&balances)
+            }
+            Line::Markets => {
+                for name in self.registry.names() {
+                    println!("{}", name);
+                }
+                Ok(())
+            }
+            Line::Address => {
+                println!("{}", self.signer()?.address());
+                Ok(())
+            }
+            Line::Help => {
+                println!("{}", HELP);
+                Ok(())
+            }
+            Line::Quit => Ok(()),
+        }
+    }
+}
+
+impl ReplArgs {
+    pub fn default_market(&mut self, market: Option<String>) {
+        self.market = self.market.take().or(market);
+    }
+}
+
+pub async fn run(
+    client: &LightPoolClient,
+    registry: Registry,
+    args: &ReplArgs,
+    mode: SubmitMode,
+    output: Option<Output>,
+) -> Result<()> {
+    let output = output.unwrap_or(Output::Json);
+    let session = Session {
+        client,
+        signer: args.key.try_signer()?,
+        registry,
+        market: args.market.clone(),
+        mode,
+        output,
+    };
+
+    let interactive = std::io::stdin().is_terminal();
+    if interactive {
+        match &session.signer {
+            Some(signer) => eprintln!("unlocked {}; type `help` for commands", signer.address()),
+            None => eprintln!("read-only session; type `help` for commands"),
+        }
+    }
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        if interactive {
+            print!("lightpool> ");
+            std::io::stdout().flush()?;
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        match parse_line(&line?) {
+            Ok(Some(Line::Quit)) => break,
+            Ok(Some(line)) => {
+                if let Err(e) = session.execute(line).await {
+                    eprintln!("error: {}", e);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("error: {}", e),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_lines_are_parsed() {
+        assert_eq!(
+            parse_line("buy BTC-USDC 0.5 @ 50000").unwrap(),
+            Some(Line::Order {
+                side: OrderSide::Buy,
+                market: Some("BTC-USDC".to_string()),
+                size: "0.5".to_string(),
+                price: "50000".to_string(),
+                tif: TimeInForce::Gtc,
+            })
+        );
+        assert!(matches!(
+            parse_line("SELL btc/usdc 1 @51000 ioc # close").unwrap(),
+            Some(Line::Order {
+                side: OrderSide::Sell,
+                tif: TimeInForce::Ioc,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_line("buy 2 @ 3 fok").unwrap(),
+            Some(Line::Order {
+                market: None,
+                tif: TimeInForce::Fok,
+                ..
+            })
+        ));
+        assert_eq!(parse_line("  # comment").unwrap(), None);
+        assert_eq!(
+            parse_line("book ETH-USDC 5").unwrap(),
+            Some(Line::Book {
+                market: Some("ETH-USDC".to_string()),
+                depth: 5
+            })
+        );
+        assert!(parse_line("buy BTC-USDC 0.5").is_err());
+        assert!(parse_line("buy BTC-USDC 0.5 @ 1 day").is_err());
+        assert!(parse_line("cancel BTC-USDC nothex").is_err());
+        assert!(parse_line("launch").is_err());
+    }
+}
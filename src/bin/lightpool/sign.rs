@@ -0,0 +1,48 @@
+//! `sign`：以私钥或加密私钥文件签名交易
+
+use std::path::PathBuf;
+
+use clap::Args;
+use lightpool::crypto::Signer;
+use lightpool::transaction::Transaction;
+use lightpool::types::Address;
+use lightpool::{Error, Result};
+
+use crate::args::{read_json, KeyArgs};
+use crate::output::{emit, Output};
+
+#[derive(Debug, Args)]
+pub struct SignArgs {
+    #[command(flatten)]
+    pub key: KeyArgs,
+    /// 未签名交易的 JSON 文件；`sender` 为零地址时使用私钥对应的地址
+    tx: PathBuf,
+    /// 写入文件而不是打印；扩展名为 `.bin` 时写入 bincode 编码，否则写入 JSON，不受 `--output` 影响
+    #[arg(long, short)]
+    out: Option<PathBuf>,
+}
+
+pub fn run(args: &SignArgs, output: Option<Output>) -> Result<()> {
+    let signer = args.key.signer()?;
+    let mut tx: Transaction = read_json(&args.tx)?;
+    if tx.sender == Address::ZERO {
+        tx.sender = signer.address();
+    } else if tx.sender != signer.address() {
+        return Err(Error::Validation(format!(
+            "transaction sender {} does not match key address {}",
+            tx.sender,
+            signer.address()
+        )));
+    }
+    let signed = tx.sign(&signer);
+    let bytes = || bincode::serialize(&signed).expect("transaction is always serializable");
+    match (&args.out, output.unwrap_or(Output::Json)) {
+        (Some(path), _) if path.extension().is_some_and(|ext| ext == "bin") => {
+            std::fs::write(path, bytes())?
+        }
+        (Some(path), _) => std::fs::write(path, serde_json::to_string_pretty(&signed)?)?,
+        (None, Output::Hex) => println!("{}", hex::encode(bytes())),
+        (None, output) => emit(output, "sign", &signed)?,
+    }
+    Ok(())
+}
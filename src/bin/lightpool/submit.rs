@@ -0,0 +1,110 @@
+//! `submit`：提交已签名交易，可等待执行完毕
+//!
+//! 全局 `--dry-run` 时改为输出交易而不提交，加上 `--simulate` 时再由节点模拟执行。
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Args;
+use lightpool::transaction::SignedTransaction;
+use lightpool::{Error, LightPoolClient, Result};
+use serde_json::json;
+
+use crate::args::read_signed;
+use crate::output::{emit, Output};
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    /// `sign` 输出的已签名交易；扩展名为 `.bin` 时按 bincode 解析，否则按 JSON 解析
+    tx: PathBuf,
+    /// 轮询交易回执直到执行完毕
+    #[arg(long)]
+    wait: bool,
+    /// 轮询间隔（毫秒）
+    #[arg(long, default_value_t = 500, requires = "wait")]
+    poll_interval: u64,
+    /// 等待回执的超时（秒）
+    #[arg(long, default_value_t = 60, requires = "wait")]
+    wait_timeout: u64,
+}
+
+/// 会改变链上状态的命令如何处理签好的交易
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitMode {
+    Submit,
+    /// 只输出交易
+    DryRun,
+    /// 输出交易与节点模拟执行的回执
+    Simulate,
+}
+
+/// 不提交交易时的输出
+pub async fn preview(
+    client: &LightPoolClient,
+    tx: &SignedTransaction,
+    mode: SubmitMode,
+    output: Output,
+    command: &str,
+) -> Result<()> {
+    match (mode, output) {
+        (SubmitMode::Simulate, output) => {
+            let receipt = client.simulate_transaction(tx).await?;
+            emit(
+                output,
+                command,
+                &json!({
+                    "transaction": tx,
+                    "status": receipt.status,
+                    "events": receipt.events,
+                }),
+            )
+        }
+        (_, Output::Hex) => {
+            let bytes = bincode::serialize(tx).expect("transaction is always serializable");
+            println!("{}", hex::encode(bytes));
+            Ok(())
+        }
+        (_, output) => emit(output, command, tx),
+    }
+}
+
+pub async fn run(
+    client: &LightPoolClient,
+    args: &SubmitArgs,
+    mode: SubmitMode,
+    output: Option<Output>,
+) -> Result<()> {
+    let output = output.unwrap_or(Output::Json);
+    let tx = read_signed(&args.tx)?;
+    if mode != SubmitMode::Submit {
+        return preview(client, &tx, mode, output, "submit").await;
+    }
+    let result = client.submit_transaction(&tx).await?;
+    let receipt = if args.wait && !result.receipt.is_final() {
+        client
+            .wait_for_receipt(
+                &result.digest,
+                Duration::from_millis(args.poll_interval),
+                Duration::from_secs(args.wait_timeout),
+            )
+            .await?
+    } else {
+        result.receipt
+    };
+    emit(
+        output,
+        "submit",
+        &json!({
+            "digest": result.digest,
+            "status": receipt.status,
+            "events": receipt.events,
+        }),
+    )?;
+    if receipt.is_final() && !receipt.is_success() {
+        return Err(Error::Rejected {
+            digest: result.digest,
+            status: receipt.status,
+        });
+    }
+    Ok(())
+}
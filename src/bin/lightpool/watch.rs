@@ -0,0 +1,129 @@
+//! `watch`：订阅行情网关并逐行输出事件，便于调试推送
+//!
+//! `--output json` 时每行一个 JSON 对象（ndjson），默认每行为 `字段=值` 形式。
+//! 断线与重连提示输出到标准错误，不混入事件流。
+
+use clap::{Args, Subcommand};
+use futures_util::{Stream, StreamExt};
+use lightpool::crypto::Signer;
+use lightpool::ws::{AccountEvent, ConnectionEvent, WsClient};
+use lightpool::{Error, Result, Symbol};
+use serde::Serialize;
+
+use crate::args::KeyArgs;
+use crate::output::{line, Output};
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    #[command(subcommand)]
+    feed: Feed,
+    /// 收到指定数量的事件后退出
+    #[arg(long, global = true)]
+    count: Option<u64>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Feed {
+    /// 公共成交
+    Trades {
+        /// 交易对名称，例如 BTC-USDC；省略时取 profile 中的默认市场
+        market: Option<String>,
+    },
+    /// 签名私钥对应账户的成交回报，需先完成连接认证
+    Fills {
+        #[command(flatten)]
+        key: KeyArgs,
+    },
+}
+
+impl WatchArgs {
+    pub fn name(&self) -> &'static str {
+        match self.feed {
+            Feed::Trades { .. } => "watch trades",
+            Feed::Fills { .. } => "watch fills",
+        }
+    }
+
+    pub fn default_market(&mut self, market: Option<String>) {
+        if let Feed::Trades { market: current } = &mut self.feed {
+            if current.is_none() {
+                *current = market;
+            }
+        }
+    }
+
+    pub fn key(&mut self) -> Option<&mut KeyArgs> {
+        match &mut self.feed {
+            Feed::Fills { key } => Some(key),
+            Feed::Trades { .. } => None,
+        }
+    }
+}
+
+/// 逐行输出事件，直到达到 `count`；事件流提前结束说明连接已放弃重连
+async fn print<S, T>(mut events: S, output: Output, command: &str, count: Option<u64>) -> Result<()>
+where
+    S: Stream<Item = T> + Unpin,
+    T: Serialize,
+{
+    let mut received = 0;
+    while count.is_none_or(|count| received < count) {
+        let event = events.next().await.ok_or(Error::ConnectionClosed)?;
+        line(output, command, &event)?;
+        received += 1;
+    }
+    Ok(())
+}
+
+pub async fn run(ws_url: &str, args: &WatchArgs, output: Option<Output>) -> Result<()> {
+    let output = output.unwrap_or(Output::Table);
+    let command = args.name();
+    let client = WsClient::connect(ws_url).await?;
+    let mut connection = client.connection_events();
+    tokio::spawn(async move {
+        while let Ok(event) = connection.recv().await {
+            match event {
+                ConnectionEvent::Disconnected => eprintln!("disconnected, reconnecting"),
+                ConnectionEvent::Reconnected => eprintln!("reconnected"),
+                ConnectionEvent::ServerError(error) => {
+                    eprintln!("gateway error: {}", error.message)
+                }
+                ConnectionEvent::AuthFailed(message) => {
+                    eprintln!("re-authentication failed: {}", message)
+                }
+                ConnectionEvent::RestoreFailed(message) => {
+                    eprintln!("session restore failed, reconnecting: {}", message)
+                }
+                ConnectionEvent::ConnectionFailed(diagnostics) => eprintln!(
+                    "giving up on {} after {} attempts",
+                    diagnostics.url, diagnostics.attempts
+                ),
+            }
+        }
+    });
+    match &args.feed {
+        Feed::Trades { market } => {
+            let market = market.as_deref().ok_or_else(|| {
+                Error::Validation(
+                    "a market is required: pass MARKET or set `market` in the profile".to_string(),
+                )
+            })?;
+            let trades = client.subscribe_trades(&Symbol::parse(market)?.market())?;
+            print(trades, output, command, args.count).await
+        }
+        Feed::Fills { key } => {
+            let signer = key.signer()?;
+            let address = signer.address().to_string();
+            client.authenticate(signer).await?;
+            let fills = client
+                .subscribe_account(&address)?
+                .filter_map(|event| async {
+                    match event {
+                        AccountEvent::Fill(fill) => Some(fill),
+                        AccountEvent::Order(_) => None,
+                    }
+                });
+            print(Box::pin(fills), output, command, args.count).await
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! 生成移动端 Kotlin/Swift 绑定代码，用法见 `lightpool::mobile`
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -0,0 +1,264 @@
+//! 由成交流聚合K线
+//!
+//! [`CandleBuilder`] 以收到的最大成交时间戳为水位线：K线在水位线越过
+//! `收盘时间 + 允许延迟` 后才收盘，窗口内乱序到达的成交仍计入对应K线；
+//! 超过窗口的迟到成交被丢弃并计数。无成交的周期可选择以前收盘价补齐。
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::ws::{Candle, CandleInterval, Trade};
+
+/// 未收盘K线及其首末成交时间，用于乱序成交时确定开盘价与收盘价
+#[derive(Debug, Clone)]
+struct Pending {
+    candle: Candle,
+    first_ts: u64,
+    last_ts: u64,
+}
+
+/// 由成交流聚合K线
+#[derive(Debug, Clone)]
+pub struct CandleBuilder {
+    market: String,
+    interval: CandleInterval,
+    lateness: u64,
+    fill_gaps: bool,
+    pending: BTreeMap<u64, Pending>,
+    watermark: Option<u64>,
+    /// 下一根待收盘K线的开盘时间，之前的周期均已产出
+    next_open: Option<u64>,
+    last_close: Option<u64>,
+    late_trades: u64,
+}
+
+impl CandleBuilder {
+    /// 创建指定市场与周期的聚合器，默认不允许迟到、不补齐空周期
+    pub fn new(market: &str, interval: CandleInterval) -> Self {
+        Self {
+            market: market.to_string(),
+            interval,
+            lateness: 0,
+            fill_gaps: false,
+            pending: BTreeMap::new(),
+            watermark: None,
+            next_open: None,
+            last_close: None,
+            late_trades: 0,
+        }
+    }
+
+    /// 收盘前等待迟到成交的时长
+    pub fn lateness(mut self, lateness: Duration) -> Self {
+        self.lateness = lateness.as_millis() as u64;
+        self
+    }
+
+    /// 无成交的周期是否以前收盘价产出成交量为 0 的K线
+    pub fn fill_gaps(mut self, fill_gaps: bool) -> Self {
+        self.fill_gaps = fill_gaps;
+        self
+    }
+
+    /// 计入一笔成交，返回因此收盘的K线；其他市场的成交被忽略
+    pub fn push(&mut self, trade: &Trade) -> Vec<Candle> {
+        if trade.market != self.market {
+            return Vec::new();
+        }
+        let open_time = self.open_time(trade.ts);
+        if self.next_open.is_some_and(|next| open_time < next) {
+            self.late_trades += 1;
+            return Vec::new();
+        }
+        self.pending
+            .entry(open_time)
+            .and_modify(|pending| {
+                let candle = &mut pending.candle;
+                candle.high = candle.high.max(trade.price);
+                candle.low = candle.low.min(trade.price);
+                candle.volume += trade.size;
+                if trade.ts < pending.first_ts {
+                    pending.first_ts = trade.ts;
+                    candle.open = trade.price;
+                }
+                if trade.ts >= pending.last_ts {
+                    pending.last_ts = trade.ts;
+                    candle.close = trade.price;
+                }
+            })
+            .or_insert_with(|| Pending {
+                candle: Candle {
+                    market: self.market.clone(),
+                    interval: self.interval,
+                    open_time,
+                    open: trade.price,
+                    high: trade.price,
+                    low: trade.price,
+                    close: trade.price,
+                    volume: trade.size,
+                    closed: false,
+                },
+                first_ts: trade.ts,
+                last_ts: trade.ts,
+            });
+        self.advance(trade.ts)
+    }
+
+    /// 推进水位线（毫秒），没有新成交时由定时器调用以按时收盘
+    pub fn advance(&mut self, now: u64) -> Vec<Candle> {
+        let watermark = self.watermark.map_or(now, |w| w.max(now));
+        self.watermark = Some(watermark);
+        let step = self.interval.as_millis();
+        let mut closed = Vec::new();
+        loop {
+            let next = match (self.next_open, self.pending.keys().next()) {
+                (Some(next), _) if self.fill_gaps && self.last_close.is_some() => next,
+                (_, Some(&first)) => first,
+                _ => break,
+            };
+            if next + step + self.lateness > watermark {
+                break;
+            }
+            let candle = match self.pending.remove(&next) {
+                Some(pending) => pending.candle,
+                None => {
+                    let price = self.last_close.unwrap_or_default();
+                    Candle {
+                        market: self.market.clone(),
+                        interval: self.interval,
+                        open_time: next,
+                        open: price,
+                        high: price,
+                        low: price,
+                        close: price,
+                        volume: 0,
+                        closed: false,
+                    }
+                }
+            };
+            self.last_close = Some(candle.close);
+            self.next_open = Some(next + step);
+            closed.push(Candle {
+                closed: true,
+                ..candle
+            });
+        }
+        closed
+    }
+
+    /// 当前最新一根未收盘K线
+    pub fn current(&self) -> Option<Candle> {
+        self.pending
+            .values()
+            .next_back()
+            .map(|pending| pending.candle.clone())
+    }
+
+    /// 因超过等待窗口而被丢弃的迟到成交数
+    pub fn late_trades(&self) -> u64 {
+        self.late_trades
+    }
+
+    fn open_time(&self, ts: u64) -> u64 {
+        ts - ts % self.interval.as_millis()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderSide;
+
+    fn trade(ts: u64, price: u64, size: u64) -> Trade {
+        Trade {
+            market: "BTC-USDC".to_string(),
+            price,
+            size,
+            side: OrderSide::Buy,
+            trade_id: None,
+            ts,
+        }
+    }
+
+    fn ohlcv(candle: &Candle) -> (u64, u64, u64, u64, u64, u64) {
+        (
+            candle.open_time,
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.volume,
+        )
+    }
+
+    #[test]
+    fn trades_roll_into_closed_candles() {
+        let mut builder = CandleBuilder::new("BTC-USDC", CandleInterval::Sec1);
+        assert!(builder.push(&trade(100, 10, 1)).is_empty());
+        assert!(builder.push(&trade(500, 12, 2)).is_empty());
+        assert!(builder.push(&trade(900, 9, 1)).is_empty());
+        assert_eq!(ohlcv(&builder.current().unwrap()), (0, 10, 12, 9, 9, 4));
+
+        let closed = builder.push(&trade(1_200, 11, 1));
+        assert_eq!(closed.len(), 1);
+        assert!(closed[0].closed);
+        assert_eq!(ohlcv(&closed[0]), (0, 10, 12, 9, 9, 4));
+        assert_eq!(builder.advance(2_000).len(), 1);
+        assert!(builder.current().is_none());
+    }
+
+    #[test]
+    fn out_of_order_trades_within_lateness() {
+        let mut builder = CandleBuilder::new("BTC-USDC", CandleInterval::Sec1)
+            .lateness(Duration::from_millis(500));
+        builder.push(&trade(600, 11, 1));
+        // 下一周期的成交尚未超过等待窗口
+        assert!(builder.push(&trade(1_300, 20, 1)).is_empty());
+        // 更早的成交决定开盘价，时间戳最大的成交决定收盘价
+        builder.push(&trade(200, 10, 1));
+        builder.push(&trade(400, 15, 1));
+        let closed = builder.push(&trade(1_500, 21, 1));
+        assert_eq!(ohlcv(&closed[0]), (0, 10, 15, 10, 11, 3));
+
+        assert!(builder.push(&trade(900, 1, 1)).is_empty());
+        assert_eq!(builder.late_trades(), 1);
+    }
+
+    #[test]
+    fn gaps_are_skipped_by_default() {
+        let mut builder = CandleBuilder::new("BTC-USDC", CandleInterval::Sec1);
+        builder.push(&trade(100, 10, 1));
+        let closed = builder.push(&trade(3_100, 12, 1));
+        assert_eq!(closed.len(), 1);
+        assert_eq!(builder.current().unwrap().open_time, 3_000);
+    }
+
+    #[test]
+    fn gaps_are_filled_with_previous_close() {
+        let mut builder = CandleBuilder::new("BTC-USDC", CandleInterval::Sec1).fill_gaps(true);
+        builder.push(&trade(100, 10, 1));
+        let closed = builder.push(&trade(3_100, 12, 1));
+        let bars: Vec<_> = closed.iter().map(ohlcv).collect();
+        assert_eq!(
+            bars,
+            vec![
+                (0, 10, 10, 10, 10, 1),
+                (1_000, 10, 10, 10, 10, 0),
+                (2_000, 10, 10, 10, 10, 0),
+            ]
+        );
+        // 定时推进也会补齐
+        let closed = builder.advance(6_000);
+        assert_eq!(closed.len(), 3);
+        assert_eq!(ohlcv(&closed[2]), (5_000, 12, 12, 12, 12, 0));
+    }
+
+    #[test]
+    fn ignores_other_markets() {
+        let mut builder = CandleBuilder::new("BTC-USDC", CandleInterval::Min1);
+        let mut other = trade(0, 1, 1);
+        other.market = "ETH-USDC".to_string();
+        assert!(builder.push(&other).is_empty());
+        assert!(builder.current().is_none());
+    }
+}
@@ -0,0 +1,442 @@
+//! LightPool RPC客户端
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::Instrument;
+
+use crate::crypto::Signer;
+use crate::error::{Error, Result};
+use crate::fees::FeeSchedule;
+use crate::order::{AccountLimits, OrderRequest, PlaceOrderParams};
+use crate::risk::{RiskChecker, RiskContext, RiskLimits};
+use crate::symbol::Symbol;
+use crate::transaction::{SignedTransaction, TransactionBuilder};
+use crate::types::Address;
+use crate::ws::{IndexPrice, MarkPrice, MarketStats, OrderbookUpdate, Trade};
+
+/// JSON-RPC 响应
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcErrorObject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcErrorObject {
+    code: Option<i64>,
+    message: Option<String>,
+}
+
+/// 市场信息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketInfo {
+    pub market_id: String,
+    pub market_address: String,
+    /// 交易对名称，例如 `BTC/USDT`
+    pub name: String,
+    #[serde(default)]
+    pub base_symbol: String,
+    #[serde(default)]
+    pub quote_symbol: String,
+    /// 最小下单数量
+    #[serde(default)]
+    pub min_order_size: u64,
+    /// 最小价格变动单位
+    #[serde(default)]
+    pub tick_size: u64,
+    pub maker_fee_bps: u16,
+    pub taker_fee_bps: u16,
+    /// 市场状态，例如 `active`、`halted`；旧版节点不返回
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+impl MarketInfo {
+    /// 由市场名称解析交易对符号
+    pub fn symbol(&self) -> Result<Symbol> {
+        Symbol::parse(&self.name)
+    }
+}
+
+/// 交易执行回执
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    /// `success` 或 `failure`
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub events: Vec<Value>,
+}
+
+impl TransactionReceipt {
+    pub fn is_success(&self) -> bool {
+        self.status == "success"
+    }
+
+    /// 交易是否已执行完毕（成功或失败）
+    pub fn is_final(&self) -> bool {
+        self.is_success() || self.status == "failure"
+    }
+}
+
+/// 交易提交结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmitResult {
+    pub digest: String,
+    pub receipt: TransactionReceipt,
+}
+
+/// 账户持有的代币余额
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Balance {
+    pub token_address: String,
+    pub balance: u64,
+}
+
+/// LightPool RPC客户端
+#[derive(Debug, Clone)]
+pub struct LightPoolClient {
+    base_url: String,
+    http: reqwest::Client,
+    /// 下单前的风控检查，见 [`LightPoolClient::with_risk_limits`]
+    risk: Option<RiskChecker>,
+    /// 风控检查使用的账户与行情状态，各克隆共享
+    risk_context: Arc<Mutex<RiskContext>>,
+}
+
+impl LightPoolClient {
+    /// 创建客户端，`base_url` 例如 `http://localhost:26300`
+    pub fn new(base_url: &str, timeout: Duration) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| Error::Network(e.to_string()))?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http,
+            risk: None,
+            risk_context: Arc::default(),
+        })
+    }
+
+    /// 下单前以 `limits` 检查每笔订单，未通过时返回 [`Error::Risk`]，不签名也不提交
+    pub fn with_risk_limits(mut self, limits: RiskLimits) -> Self {
+        self.risk = Some(RiskChecker::new(limits));
+        self
+    }
+
+    /// 以自定义的检查器（例如计入手续费的 [`RiskChecker::with_fees`]）检查每笔订单
+    pub fn with_risk_checker(mut self, checker: RiskChecker) -> Self {
+        self.risk = Some(checker);
+        self
+    }
+
+    /// 更新风控检查使用的持仓、中间价与挂单数；未更新时按零持仓、无挂单检查
+    pub fn set_risk_context(&self, context: RiskContext) {
+        *self.risk_context.lock().unwrap() = context;
+    }
+
+    /// 以客户端的风控限额检查订单；未设置限额时总是通过
+    pub fn check_risk(&self, order: &PlaceOrderParams) -> Result<()> {
+        let Some(checker) = &self.risk else {
+            return Ok(());
+        };
+        let context = *self.risk_context.lock().unwrap();
+        let violations = checker.check(order, &context);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Risk(violations))
+        }
+    }
+
+    /// 发送RPC请求
+    ///
+    /// jsonrpsee 使用位置参数，参数对象包装在数组中传递。
+    async fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        self.request_optional(method, params)
+            .await?
+            .ok_or_else(|| Error::Rpc {
+                code: None,
+                message: "missing result".to_string(),
+            })
+    }
+
+    /// 发送RPC请求，`result` 为 null 时返回 None
+    async fn request_optional<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<T>> {
+        #[cfg(feature = "metrics")]
+        let started = std::time::Instant::now();
+        let result = self
+            .send_request(method, params)
+            .instrument(tracing::info_span!("rpc_request", method))
+            .await;
+        #[cfg(feature = "metrics")]
+        crate::telemetry::rpc_request(method, started.elapsed(), result.is_ok());
+        result
+    }
+
+    async fn send_request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<T>> {
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": [params],
+        });
+
+        let started = std::time::Instant::now();
+        tracing::debug!(method, "rpc request");
+        let request = self.http.post(format!("{}/rpc", self.base_url));
+        #[cfg(feature = "otel")]
+        let request = request.headers(crate::otel::trace_headers());
+        let response = request.json(&payload).send().await.map_err(|e| {
+            tracing::warn!(method, error = %e, "rpc request failed");
+            Error::Network(e.to_string())
+        })?;
+        tracing::debug!(
+            method,
+            status = response.status().as_u16(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "rpc response"
+        );
+        if !response.status().is_success() {
+            return Err(Error::Network(format!("HTTP {}", response.status())));
+        }
+
+        let response: RpcResponse<T> = response
+            .json()
+            .await
+            .map_err(|e| Error::Network(format!("Invalid JSON response: {}", e)))?;
+        if let Some(error) = response.error {
+            tracing::debug!(
+                method,
+                code = error.code,
+                message = error.message.as_deref(),
+                "rpc error"
+            );
+            return Err(Error::Rpc {
+                code: error.code,
+                message: error
+                    .message
+                    .unwrap_or_else(|| "Unknown RPC error".to_string()),
+            });
+        }
+        Ok(response.result)
+    }
+
+    /// 获取订单簿快照
+    pub async fn get_order_book(&self, market_id: &str, depth: u32) -> Result<OrderbookUpdate> {
+        let mut book: OrderbookUpdate = self
+            .request(
+                "getOrderBook",
+                json!({ "marketId": market_id, "depth": depth }),
+            )
+            .await?;
+        book.snapshot = true;
+        Ok(book)
+    }
+
+    /// 获取市场最近的成交，按时间升序
+    pub async fn get_trades(&self, market_id: &str, limit: u32) -> Result<Vec<Trade>> {
+        #[derive(Deserialize)]
+        struct Trades {
+            #[serde(default)]
+            trades: Vec<Trade>,
+        }
+
+        let mut result: Trades = self
+            .request(
+                "getTrades",
+                json!({ "marketId": market_id, "limit": limit }),
+            )
+            .await?;
+        result.trades.sort_by_key(|trade| trade.ts);
+        Ok(result.trades)
+    }
+
+    /// 获取账户的挂单；订单字段随节点版本变化，保留原始 JSON
+    pub async fn get_orders(
+        &self,
+        address: &Address,
+        market_id: Option<&str>,
+    ) -> Result<Vec<Value>> {
+        #[derive(Deserialize)]
+        struct Orders {
+            #[serde(default)]
+            orders: Vec<Value>,
+        }
+
+        let mut params = json!({ "address": address.to_string() });
+        if let Some(market_id) = market_id {
+            params["marketId"] = json!(market_id);
+        }
+        let result: Orders = self.request("getOrders", params).await?;
+        Ok(result.orders)
+    }
+
+    /// 获取账户持有的全部代币余额
+    pub async fn get_balances(&self, address: &Address) -> Result<Vec<Balance>> {
+        #[derive(Deserialize)]
+        struct Balances {
+            #[serde(default)]
+            balances: Vec<Balance>,
+        }
+
+        let result: Balances = self
+            .request("getAllBalance", json!({ "address": address.to_string() }))
+            .await?;
+        Ok(result.balances)
+    }
+
+    /// 获取市场当前的标记价格
+    pub async fn get_mark_price(&self, market_id: &str) -> Result<MarkPrice> {
+        self.request("getMarkPrice", json!({ "marketId": market_id }))
+            .await
+    }
+
+    /// 获取市场当前的指数价格
+    pub async fn get_index_price(&self, market_id: &str) -> Result<IndexPrice> {
+        self.request("getIndexPrice", json!({ "marketId": market_id }))
+            .await
+    }
+
+    /// 获取市场的 24 小时统计与未平仓量
+    pub async fn get_market_stats(&self, market_id: &str) -> Result<MarketStats> {
+        self.request("getMarketStats", json!({ "marketId": market_id }))
+            .await
+    }
+
+    /// 获取市场信息
+    pub async fn get_market_info(&self, market_id: &str) -> Result<MarketInfo> {
+        self.request("getMarketInfo", json!({ "marketId": market_id }))
+            .await
+    }
+
+    /// 按市场信息中的费率获取手续费表
+    pub async fn get_fee_schedule(&self, market_id: &str) -> Result<FeeSchedule> {
+        Ok(FeeSchedule::from_market(
+            &self.get_market_info(market_id).await?,
+        ))
+    }
+
+    /// 提交已签名交易
+    pub async fn submit_transaction(&self, tx: &SignedTransaction) -> Result<SubmitResult> {
+        self.request("submitTransaction", json!({ "tx": tx })).await
+    }
+
+    /// 在节点上模拟执行已签名交易，不上链；需要节点支持 `simulateTransaction`
+    pub async fn simulate_transaction(&self, tx: &SignedTransaction) -> Result<TransactionReceipt> {
+        self.request("simulateTransaction", json!({ "tx": tx }))
+            .await
+    }
+
+    /// 按摘要获取已提交的交易；节点未收录该交易时返回 None
+    pub async fn get_transaction(&self, digest: &str) -> Result<Option<SignedTransaction>> {
+        self.request_optional("getTransaction", json!({ "digest": digest }))
+            .await
+    }
+
+    /// 获取交易回执；节点尚未收录该交易时返回 None
+    pub async fn get_transaction_receipt(
+        &self,
+        digest: &str,
+    ) -> Result<Option<TransactionReceipt>> {
+        self.request_optional("getTransactionReceipt", json!({ "digest": digest }))
+            .await
+    }
+
+    /// 按 `interval` 轮询直到交易执行完毕，超过 `timeout` 返回错误
+    pub async fn wait_for_receipt(
+        &self,
+        digest: &str,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            if let Some(receipt) = self.get_transaction_receipt(digest).await? {
+                if receipt.is_final() {
+                    return Ok(receipt);
+                }
+            }
+            if tokio::time::Instant::now() + interval > deadline {
+                return Err(Error::Network(format!(
+                    "timed out waiting for receipt of {}",
+                    digest
+                )));
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// 获取账户的保证金交易限额；RPC 未返回的字段按现货账户处理
+    pub async fn get_account_limits(&self, address: &Address) -> Result<AccountLimits> {
+        self.request("getAccountInfo", json!({ "address": address.to_string() }))
+            .await
+    }
+
+    /// 签名并提交下单交易
+    ///
+    /// 先以 [`LightPoolClient::with_risk_limits`] 设置的限额检查订单，未通过时不会提交；
+    /// 协议不支持保证金下单，带保证金设置的订单返回 [`Error::Validation`]。
+    #[tracing::instrument(
+        name = "place_order",
+        skip_all,
+        fields(side = ?order.params.side, amount = order.params.amount, digest)
+    )]
+    pub async fn place_order(
+        &self,
+        signer: &dyn Signer,
+        order: &OrderRequest,
+    ) -> Result<SubmitResult> {
+        let result = self.sign_and_submit(signer, order).await;
+        if let Ok(submitted) = &result {
+            tracing::Span::current().record("digest", submitted.digest.as_str());
+        }
+        #[cfg(feature = "metrics")]
+        crate::telemetry::order_result(order.params.side, &result);
+        result
+    }
+
+    async fn sign_and_submit(
+        &self,
+        signer: &dyn Signer,
+        order: &OrderRequest,
+    ) -> Result<SubmitResult> {
+        let action = order.to_action()?;
+        self.check_risk(&order.params)?;
+        let tx = TransactionBuilder::new()
+            .action(action)
+            .build_and_sign(signer)?;
+        self.submit_transaction(&tx).await
+    }
+
+    /// 通过风控检查后签名并提交下单交易，未通过时返回 [`Error::Risk`]
+    pub async fn place_order_checked(
+        &self,
+        signer: &dyn Signer,
+        order: &OrderRequest,
+        checker: &RiskChecker,
+        context: &RiskContext,
+    ) -> Result<SubmitResult> {
+        let violations = checker.check(&order.params, context);
+        if !violations.is_empty() {
+            let rejected = Err(Error::Risk(violations));
+            #[cfg(feature = "metrics")]
+            crate::telemetry::order_result(order.params.side, &rejected);
+            return rejected;
+        }
+        self.place_order(signer, order).await
+    }
+}
@@ -0,0 +1,119 @@
+//! LightPool SDK 加密模块
+
+use ed25519_dalek::{Signature, SigningKey, Verifier, VerifyingKey};
+use rand_core::OsRng;
+use sha2::{Digest, Sha512};
+
+use crate::error::{Error, Result};
+use crate::types::Address;
+
+/// 签名者
+///
+/// 交易签名与 WebSocket 认证共用同一套密钥。
+pub trait Signer: Send + Sync {
+    /// 签名者地址
+    fn address(&self) -> Address;
+
+    /// Ed25519 公钥
+    fn public_key(&self) -> [u8; 32];
+
+    /// 对消息签名，返回 64 字节的 raw Ed25519 签名
+    fn sign(&self, message: &[u8]) -> [u8; 64];
+}
+
+/// 由公钥计算地址：SHA512 哈希后取前32字节，与 Python SDK 一致
+pub fn address_from_public_key(public_key: &[u8; 32]) -> Address {
+    let hash = Sha512::digest(public_key);
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&hash[..32]);
+    Address(bytes)
+}
+
+/// 验证 Ed25519 签名
+pub fn verify(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let Ok(key) = VerifyingKey::from_bytes(public_key) else {
+        return false;
+    };
+    key.verify(message, &Signature::from_bytes(signature))
+        .is_ok()
+}
+
+/// 基于本地私钥的 Ed25519 签名者
+#[derive(Clone)]
+pub struct Ed25519Signer {
+    key: SigningKey,
+}
+
+impl Ed25519Signer {
+    /// 生成新的随机密钥对
+    pub fn generate() -> Self {
+        Self {
+            key: SigningKey::generate(&mut OsRng),
+        }
+    }
+
+    /// 从私钥字节数组创建签名者
+    pub fn from_secret_key_bytes(secret_key: &[u8; 32]) -> Self {
+        Self {
+            key: SigningKey::from_bytes(secret_key),
+        }
+    }
+
+    /// 从十六进制字符串创建签名者
+    pub fn from_hex(hex_str: &str) -> Result<Self> {
+        let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+        let mut secret_key = [0u8; 32];
+        hex::decode_to_slice(hex_str, &mut secret_key)
+            .map_err(|e| Error::Crypto(format!("Invalid private key: {}", e)))?;
+        Ok(Self::from_secret_key_bytes(&secret_key))
+    }
+
+    /// 获取私钥字节数组
+    pub fn secret_key_bytes(&self) -> [u8; 32] {
+        self.key.to_bytes()
+    }
+}
+
+impl Signer for Ed25519Signer {
+    fn address(&self) -> Address {
+        address_from_public_key(&self.public_key())
+    }
+
+    fn public_key(&self) -> [u8; 32] {
+        self.key.verifying_key().to_bytes()
+    }
+
+    fn sign(&self, message: &[u8]) -> [u8; 64] {
+        ed25519_dalek::Signer::sign(&self.key, message).to_bytes()
+    }
+}
+
+impl std::fmt::Debug for Ed25519Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // 不输出私钥
+        write!(f, "Ed25519Signer(address={})", self.address())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        let signer = Ed25519Signer::from_secret_key_bytes(&[7u8; 32]);
+        let signature = signer.sign(b"lightpool");
+        assert!(verify(&signer.public_key(), b"lightpool", &signature));
+        assert!(!verify(&signer.public_key(), b"tampered", &signature));
+    }
+
+    #[test]
+    fn hex_import_matches_bytes() {
+        let signer = Ed25519Signer::from_hex(&format!("0x{}", "07".repeat(32))).unwrap();
+        assert_eq!(signer.secret_key_bytes(), [7u8; 32]);
+        assert_eq!(
+            signer.address(),
+            address_from_public_key(&signer.public_key())
+        );
+    }
+}
@@ -0,0 +1,365 @@
+//! 终端与 evcxr/Jupyter 笔记本中的表格展示
+//!
+//! 订单簿、订单与余额实现了对齐的文本 `Display`；订单簿可用精度指定档数，例如 `{:.5}`。
+//! [`Table`] 与订单簿、[`OrderStore`] 提供 `evcxr_display`，在 evcxr 内核中以 HTML 表格渲染。
+//! 价格与数量均为链上最小单位。
+
+use std::fmt;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::client::Balance;
+use crate::order::{OrderStore, TrackedOrder};
+use crate::orderbook::Orderbook;
+use crate::ws::{OrderbookUpdate, PriceLevel};
+
+/// 本地订单簿 `Display` 默认显示的档数
+pub const BOOK_DEPTH: usize = 10;
+
+/// 对齐的文本表格，数值列右对齐
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// 追加一行
+    pub fn row<I, S>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.push(cells);
+        self
+    }
+
+    pub fn push<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// HTML `<table>`，数值列右对齐
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table><thead><tr>");
+        for header in &self.headers {
+            html.push_str(&format!("<th>{}</th>", escape(header)));
+        }
+        html.push_str("</tr></thead><tbody>");
+        for row in &self.rows {
+            html.push_str("<tr>");
+            for cell in row {
+                let style = if is_numeric(cell) {
+                    " style=\"text-align:right\""
+                } else {
+                    ""
+                };
+                html.push_str(&format!("<td{}>{}</td>", style, escape(cell)));
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</tbody></table>");
+        html
+    }
+
+    /// evcxr 内核调用此方法渲染单元格的输出
+    pub fn evcxr_display(&self) {
+        evcxr_html(&self.to_html());
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let columns = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([self.headers.len()])
+            .max()
+            .unwrap_or(0);
+        let lines = std::iter::once(&self.headers).chain(&self.rows);
+        let widths: Vec<usize> = (0..columns)
+            .map(|i| {
+                lines
+                    .clone()
+                    .filter_map(|line| line.get(i))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        for (n, line) in lines.enumerate() {
+            if n > 0 {
+                writeln!(f)?;
+            }
+            let mut text = String::new();
+            for (i, cell) in line.iter().enumerate() {
+                if i > 0 {
+                    text.push_str("  ");
+                }
+                // 表头与文本左对齐，数值右对齐
+                if n > 0 && is_numeric(cell) {
+                    text.push_str(&format!("{:>width$}", cell, width = widths[i]));
+                } else {
+                    text.push_str(&format!("{:<width$}", cell, width = widths[i]));
+                }
+            }
+            write!(f, "{}", text.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+fn is_numeric(cell: &str) -> bool {
+    !cell.is_empty() && cell.parse::<f64>().is_ok()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn evcxr_html(html: &str) {
+    println!("EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT", html);
+}
+
+/// 订单簿档位表：卖盘在上、价格降序，买盘在下
+pub fn book(bids: &[PriceLevel], asks: &[PriceLevel]) -> Table {
+    let mut table = Table::new(["side", "price", "size"]);
+    for level in asks.iter().rev() {
+        table.push([
+            "ask".to_string(),
+            level.price.to_string(),
+            level.size.to_string(),
+        ]);
+    }
+    for level in bids {
+        table.push([
+            "bid".to_string(),
+            level.price.to_string(),
+            level.size.to_string(),
+        ]);
+    }
+    table
+}
+
+/// 订单表，按提交时间排序
+pub fn orders<'a>(orders: impl IntoIterator<Item = &'a TrackedOrder>) -> Table {
+    let mut orders: Vec<&TrackedOrder> = orders.into_iter().collect();
+    orders.sort_by(|a, b| (a.submitted_at, &a.cloid).cmp(&(b.submitted_at, &b.cloid)));
+    let mut table = Table::new([
+        "cloid", "order_id", "market", "side", "price", "amount", "filled", "state",
+    ]);
+    for order in orders {
+        table.push([
+            order.cloid.clone(),
+            order.order_id.clone().unwrap_or_else(|| "-".to_string()),
+            order.market.clone(),
+            format!("{:?}", order.side),
+            order.price.to_string(),
+            order.lifecycle.amount().to_string(),
+            order.lifecycle.filled().to_string(),
+            format!("{:?}", order.lifecycle.state()),
+        ]);
+    }
+    table
+}
+
+/// 余额表
+#[cfg(not(target_arch = "wasm32"))]
+pub fn balances(balances: &[Balance]) -> Table {
+    let mut table = Table::new(["token", "balance"]);
+    for balance in balances {
+        table.push([balance.token_address.clone(), balance.balance.to_string()]);
+    }
+    table
+}
+
+fn take(levels: &[PriceLevel], depth: Option<usize>) -> &[PriceLevel] {
+    &levels[..depth.unwrap_or(levels.len()).min(levels.len())]
+}
+
+/// 精度指定每侧显示的档数，默认全部
+impl fmt::Display for OrderbookUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.snapshot { "snapshot" } else { "diff" };
+        writeln!(f, "{} seq={} {}", self.market, self.seq, kind)?;
+        let depth = f.precision();
+        write!(
+            f,
+            "{}",
+            book(take(&self.bids, depth), take(&self.asks, depth))
+        )
+    }
+}
+
+impl OrderbookUpdate {
+    pub fn evcxr_display(&self) {
+        let table = book(&self.bids, &self.asks);
+        evcxr_html(&format!(
+            "<b>{} seq={}</b>{}",
+            escape(&self.market),
+            self.seq,
+            table.to_html()
+        ));
+    }
+}
+
+/// 精度指定每侧显示的档数，默认 [`BOOK_DEPTH`]
+impl fmt::Display for Orderbook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let depth = self.levels(f.precision().unwrap_or(BOOK_DEPTH));
+        writeln!(f, "{} seq={}", self.market(), self.seq())?;
+        write!(f, "{}", book(&depth.bids, &depth.asks))
+    }
+}
+
+impl Orderbook {
+    pub fn evcxr_display(&self) {
+        let depth = self.levels(BOOK_DEPTH);
+        evcxr_html(&format!(
+            "<b>{} seq={}</b>{}",
+            escape(self.market()),
+            self.seq(),
+            book(&depth.bids, &depth.asks).to_html()
+        ));
+    }
+}
+
+impl fmt::Display for TrackedOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {:?} {}/{} @ {} {:?}",
+            self.cloid,
+            self.market,
+            self.side,
+            self.lifecycle.filled(),
+            self.lifecycle.amount(),
+            self.price,
+            self.lifecycle.state()
+        )
+    }
+}
+
+impl fmt::Display for OrderStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", orders(self.orders()))
+    }
+}
+
+impl OrderStore {
+    pub fn evcxr_display(&self) {
+        orders(self.orders()).evcxr_display();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl fmt::Display for Balance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.token_address, self.balance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderSide;
+
+    fn update() -> OrderbookUpdate {
+        OrderbookUpdate {
+            market: "BTC-USDC".to_string(),
+            seq: 7,
+            snapshot: true,
+            bids: vec![
+                PriceLevel {
+                    price: 49_900,
+                    size: 3,
+                },
+                PriceLevel {
+                    price: 49_800,
+                    size: 12,
+                },
+            ],
+            asks: vec![
+                PriceLevel {
+                    price: 50_000,
+                    size: 1,
+                },
+                PriceLevel {
+                    price: 50_100,
+                    size: 250,
+                },
+            ],
+            ts: 1,
+        }
+    }
+
+    #[test]
+    fn books_render_asks_above_bids() {
+        let expected = "\
+BTC-USDC seq=7 snapshot
+side  price  size
+ask   50100   250
+ask   50000     1
+bid   49900     3
+bid   49800    12";
+        assert_eq!(update().to_string(), expected);
+        let local = Orderbook::from_snapshot(&update()).unwrap();
+        assert_eq!(
+            format!("{:.1}", local),
+            "BTC-USDC seq=7\nside  price  size\nask   50000     1\nbid   49900     3"
+        );
+    }
+
+    #[test]
+    fn orders_render_as_table_and_html() {
+        let mut store = OrderStore::new();
+        let cloid = store
+            .submit("BTC-USDC", OrderSide::Buy, 50_000, 10, 1)
+            .unwrap();
+        let order = store.get(&cloid).unwrap();
+        assert_eq!(
+            order.to_string(),
+            format!("{} BTC-USDC Buy 0/10 @ 50000 PendingSubmit", cloid)
+        );
+        assert_eq!(
+            format!("{:?}", crate::types::OrderId([0xab; 32])),
+            format!("OrderId(0x{})", "ab".repeat(32))
+        );
+        let text = store.to_string();
+        assert!(text.starts_with("cloid"));
+        assert!(text.lines().nth(1).unwrap().contains("PendingSubmit"));
+
+        let html = Table::new(["token", "balance"])
+            .row(["<x>", "42"])
+            .to_html();
+        assert_eq!(
+            html,
+            "<table><thead><tr><th>token</th><th>balance</th></tr></thead>\
+             <tbody><tr><td>&lt;x&gt;</td><td style=\"text-align:right\">42</td></tr></tbody></table>"
+        );
+    }
+}
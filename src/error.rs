@@ -0,0 +1,111 @@
+//! LightPool SDK 错误类型定义
+
+use std::fmt;
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio_tungstenite::tungstenite;
+
+use crate::risk::RiskViolation;
+
+/// SDK 统一结果类型
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// LightPool SDK 基础错误类型
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "uniffi",
+    derive(uniffi::Error),
+    uniffi(flat_error, name = "LightpoolError")
+)]
+pub enum Error {
+    /// 网络相关错误
+    Network(String),
+    /// RPC调用错误
+    Rpc { code: Option<i64>, message: String },
+    /// WebSocket 协议错误
+    #[cfg(not(target_arch = "wasm32"))]
+    WebSocket(tungstenite::Error),
+    /// JSON 编解码错误
+    Json(serde_json::Error),
+    /// 数据解析错误
+    Decode(String),
+    /// 参数验证错误
+    Validation(String),
+    /// 加密相关错误
+    Crypto(String),
+    /// WebSocket 认证失败
+    Auth(String),
+    /// 文件读写错误
+    Io(std::io::Error),
+    /// 连接已关闭
+    ConnectionClosed,
+    /// 订单未通过下单前风控检查
+    Risk(Vec<RiskViolation>),
+    /// 交易已执行但结果不是成功
+    Rejected { digest: String, status: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Network(msg) => write!(f, "network error: {}", msg),
+            Error::Rpc { code, message } => match code {
+                Some(code) => write!(f, "rpc error {}: {}", code, message),
+                None => write!(f, "rpc error: {}", message),
+            },
+            #[cfg(not(target_arch = "wasm32"))]
+            Error::WebSocket(e) => write!(f, "websocket error: {}", e),
+            Error::Json(e) => write!(f, "json error: {}", e),
+            Error::Decode(msg) => write!(f, "decode error: {}", msg),
+            Error::Validation(msg) => write!(f, "validation error: {}", msg),
+            Error::Crypto(msg) => write!(f, "crypto error: {}", msg),
+            Error::Auth(msg) => write!(f, "authentication failed: {}", msg),
+            Error::Io(e) => write!(f, "io error: {}", e),
+            Error::ConnectionClosed => write!(f, "connection closed"),
+            Error::Rejected { digest, status } => {
+                write!(f, "transaction {} rejected: {}", digest, status)
+            }
+            Error::Risk(violations) => {
+                write!(f, "risk check failed: ")?;
+                for (i, violation) in violations.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", violation)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Error::WebSocket(e) => Some(e),
+            Error::Json(e) => Some(e),
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<tungstenite::Error> for Error {
+    fn from(e: tungstenite::Error) -> Self {
+        Error::WebSocket(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
@@ -0,0 +1,210 @@
+//! 通用交易所适配接口
+//!
+//! 多交易所交易框架面向 [`ExchangeAdapter`] 编写下单、撤单、余额与订单簿查询，
+//! [`LightPoolAdapter`] 是 LightPool 的实现：Symbol 可用 [`Symbol`] 接受的任意写法，
+//! 按规范市场名（例如 `BTC-USDC`）查找登记的链上市场，签名并提交交易。
+//! 方法返回装箱的 future，框架可以用 `Vec<Box<dyn ExchangeAdapter>>` 同时持有多个交易所。
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::client::{Balance, LightPoolClient, SubmitResult};
+use crate::crypto::Signer;
+use crate::error::{Error, Result};
+use crate::order::{Market, OrderRequest, PlaceOrderParams};
+use crate::symbol::Symbol;
+use crate::transaction::TransactionBuilder;
+use crate::types::OrderId;
+use crate::ws::OrderbookUpdate;
+
+/// [`ExchangeAdapter`] 方法返回的 future
+pub type AdapterFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// 交易所适配接口
+///
+/// 下单与撤单返回交易所侧的请求引用；对 LightPool 而言是交易摘要，订单ID随账户推送下发。
+pub trait ExchangeAdapter: Send + Sync {
+    /// 交易所名称，例如 `lightpool`
+    fn name(&self) -> &str;
+
+    /// 在指定市场下单
+    fn place_order<'a>(
+        &'a self,
+        symbol: &'a str,
+        params: &'a PlaceOrderParams,
+    ) -> AdapterFuture<'a, String>;
+
+    /// 撤销指定市场的订单
+    fn cancel_order<'a>(&'a self, symbol: &'a str, order_id: &'a str) -> AdapterFuture<'a, String>;
+
+    /// 账户持有的全部余额
+    fn balances(&self) -> AdapterFuture<'_, Vec<Balance>>;
+
+    /// 指定市场的订单簿快照
+    fn order_book<'a>(&'a self, symbol: &'a str, depth: u32) -> AdapterFuture<'a, OrderbookUpdate>;
+}
+
+/// LightPool 的 [`ExchangeAdapter`] 实现
+pub struct LightPoolAdapter {
+    client: LightPoolClient,
+    signer: Box<dyn Signer>,
+    markets: HashMap<String, Market>,
+}
+
+impl LightPoolAdapter {
+    /// 以 `signer` 的账户交易，需先用 [`LightPoolAdapter::market`] 登记市场
+    pub fn new(client: LightPoolClient, signer: impl Signer + 'static) -> Self {
+        Self {
+            client,
+            signer: Box::new(signer),
+            markets: HashMap::new(),
+        }
+    }
+
+    /// 登记 Symbol 对应的市场
+    pub fn market(mut self, symbol: impl Into<Symbol>, market: Market) -> Self {
+        self.markets.insert(symbol.into().market(), market);
+        self
+    }
+
+    pub fn client(&self) -> &LightPoolClient {
+        &self.client
+    }
+
+    /// 按规范市场名查找，返回规范名与市场
+    fn market_for(&self, symbol: &str) -> Result<(String, &Market)> {
+        let name = Symbol::parse(symbol)?.market();
+        match self.markets.get(&name) {
+            Some(market) => Ok((name, market)),
+            None => Err(Error::Validation(format!("unknown symbol: {}", symbol))),
+        }
+    }
+
+    fn order_request(&self, symbol: &str, params: &PlaceOrderParams) -> Result<OrderRequest> {
+        let (_, market) = self.market_for(symbol)?;
+        market.order_request(params.clone())
+    }
+}
+
+/// 已执行但失败的交易视为错误，框架无需再检查回执
+fn accepted(result: SubmitResult) -> Result<String> {
+    let receipt = result.receipt;
+    if receipt.is_final() && !receipt.is_success() {
+        return Err(Error::Rejected {
+            digest: result.digest,
+            status: receipt.status,
+        });
+    }
+    Ok(result.digest)
+}
+
+impl ExchangeAdapter for LightPoolAdapter {
+    fn name(&self) -> &str {
+        "lightpool"
+    }
+
+    fn place_order<'a>(
+        &'a self,
+        symbol: &'a str,
+        params: &'a PlaceOrderParams,
+    ) -> AdapterFuture<'a, String> {
+        Box::pin(async move {
+            let order = self.order_request(symbol, params)?;
+            accepted(
+                self.client
+                    .place_order(self.signer.as_ref(), &order)
+                    .await?,
+            )
+        })
+    }
+
+    fn cancel_order<'a>(&'a self, symbol: &'a str, order_id: &'a str) -> AdapterFuture<'a, String> {
+        Box::pin(async move {
+            let (_, market) = self.market_for(symbol)?;
+            let order_id: OrderId = order_id.parse()?;
+            let tx = TransactionBuilder::new()
+                .action(market.cancel_action(order_id)?)
+                .build_and_sign(self.signer.as_ref())?;
+            accepted(self.client.submit_transaction(&tx).await?)
+        })
+    }
+
+    fn balances(&self) -> AdapterFuture<'_, Vec<Balance>> {
+        Box::pin(async move { self.client.get_balances(&self.signer.address()).await })
+    }
+
+    fn order_book<'a>(&'a self, symbol: &'a str, depth: u32) -> AdapterFuture<'a, OrderbookUpdate> {
+        Box::pin(async move {
+            let (name, _) = self.market_for(symbol)?;
+            self.client.get_order_book(&name, depth).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::crypto::Ed25519Signer;
+    use crate::risk::RiskLimits;
+    use crate::types::{ObjectId, OrderSide};
+
+    fn adapter() -> LightPoolAdapter {
+        let client = LightPoolClient::new("http://127.0.0.1:1", Duration::from_secs(1)).unwrap();
+        LightPoolAdapter::new(client, Ed25519Signer::generate()).market(
+            Symbol::parse("BTC-USDC").unwrap(),
+            Market::spot(ObjectId([1; 32]), ObjectId([2; 32]), ObjectId([3; 32])),
+        )
+    }
+
+    #[test]
+    fn orders_debit_the_balance_of_the_paid_asset() {
+        let adapter = adapter();
+        let buy = PlaceOrderParams::limit(OrderSide::Buy, 1_000, 50_000);
+        let sell = PlaceOrderParams::limit(OrderSide::Sell, 1_000, 50_000);
+        let order = adapter.order_request("BTC-USDC", &buy).unwrap();
+        assert_eq!(order.market_id, ObjectId([1; 32]));
+        assert_eq!(order.balance_id, ObjectId([3; 32]));
+        assert_eq!(
+            adapter.order_request("BTC-USDC", &sell).unwrap().balance_id,
+            ObjectId([2; 32])
+        );
+        assert_eq!(
+            adapter.order_request("btcusdc", &sell).unwrap().market_id,
+            ObjectId([1; 32])
+        );
+        assert!(adapter.order_request("ETH-USDC", &buy).is_err());
+    }
+
+    #[tokio::test]
+    async fn client_risk_limits_apply_to_adapter_orders() {
+        let client = LightPoolClient::new("http://127.0.0.1:1", Duration::from_secs(1))
+            .unwrap()
+            .with_risk_limits(RiskLimits {
+                max_order_notional: Some(1_000_000),
+                ..RiskLimits::default()
+            });
+        let adapter = LightPoolAdapter::new(client, Ed25519Signer::generate()).market(
+            Symbol::parse("BTC-USDC").unwrap(),
+            Market::spot(ObjectId([1; 32]), ObjectId([2; 32]), ObjectId([3; 32])),
+        );
+        // 未通过风控时不会连接节点，因此不是网络错误
+        let buy = PlaceOrderParams::limit(OrderSide::Buy, 1_000, 50_000);
+        let err = adapter.place_order("BTC-USDC", &buy).await.unwrap_err();
+        assert!(matches!(err, Error::Risk(_)), "{}", err);
+        let small = PlaceOrderParams::limit(OrderSide::Buy, 10, 50_000);
+        let err = adapter.place_order("BTC-USDC", &small).await.unwrap_err();
+        assert!(matches!(err, Error::Network(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_fail_before_submission() {
+        let adapter = adapter();
+        let unknown = adapter.cancel_order("ETH-USDC", &"00".repeat(32)).await;
+        assert!(unknown.unwrap_err().to_string().contains("unknown symbol"));
+        assert!(adapter.cancel_order("BTC-USDC", "not-an-id").await.is_err());
+        assert!(adapter.order_book("ETH-USDC", 10).await.is_err());
+    }
+}
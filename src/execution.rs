@@ -0,0 +1,208 @@
+//! 成交汇总与执行分析
+//!
+//! 成交回报不携带策略信息，生成策略报告时由调用方提供订单到策略的映射，
+//! 例如基于 [`OrderStore`](crate::order::OrderStore) 中记录的 cloid。
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::types::OrderSide;
+use crate::ws::Fill;
+
+/// 单个订单的成交汇总
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderExecution {
+    pub order_id: String,
+    pub market: String,
+    pub side: OrderSide,
+    /// 成交笔数
+    pub fills: usize,
+    /// 累计成交数量
+    pub filled: u64,
+    /// 成交金额（价格 × 数量之和）
+    pub notional: u128,
+    pub fees: u64,
+    /// 挂单方成交的数量
+    pub maker_size: u64,
+    pub first_ts: u64,
+    pub last_ts: u64,
+}
+
+impl OrderExecution {
+    /// 成交量加权均价
+    pub fn vwap(&self) -> f64 {
+        self.notional as f64 / self.filled as f64
+    }
+
+    /// 挂单方成交占比
+    pub fn maker_ratio(&self) -> f64 {
+        self.maker_size as f64 / self.filled as f64
+    }
+}
+
+/// 按订单汇总成交，结果按首笔成交时间排序
+pub fn aggregate_fills<'a>(fills: impl IntoIterator<Item = &'a Fill>) -> Vec<OrderExecution> {
+    let mut orders: BTreeMap<&str, OrderExecution> = BTreeMap::new();
+    for fill in fills {
+        let order = orders
+            .entry(&fill.order_id)
+            .or_insert_with(|| OrderExecution {
+                order_id: fill.order_id.clone(),
+                market: fill.market.clone(),
+                side: fill.side,
+                fills: 0,
+                filled: 0,
+                notional: 0,
+                fees: 0,
+                maker_size: 0,
+                first_ts: fill.ts,
+                last_ts: fill.ts,
+            });
+        order.fills += 1;
+        order.filled += fill.size;
+        order.notional += fill.price as u128 * fill.size as u128;
+        order.fees += fill.fee;
+        if fill.is_maker {
+            order.maker_size += fill.size;
+        }
+        order.first_ts = order.first_ts.min(fill.ts);
+        order.last_ts = order.last_ts.max(fill.ts);
+    }
+    let mut orders: Vec<OrderExecution> = orders.into_values().collect();
+    orders.sort_by_key(|order| order.first_ts);
+    orders
+}
+
+/// 某个策略在一段时间内的执行报告
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionReport {
+    pub strategy: String,
+    /// 统计的时间范围（毫秒，左闭右开）
+    pub range: Range<u64>,
+    pub orders: usize,
+    pub fills: usize,
+    pub buy_volume: u64,
+    pub sell_volume: u64,
+    pub notional: u128,
+    pub fees: u64,
+    pub maker_volume: u64,
+}
+
+impl ExecutionReport {
+    /// 总成交量
+    pub fn volume(&self) -> u64 {
+        self.buy_volume + self.sell_volume
+    }
+
+    /// 以基点计的手续费率
+    pub fn fee_bps(&self) -> f64 {
+        self.fees as f64 / self.notional as f64 * 10_000.0
+    }
+}
+
+/// 按策略生成执行报告
+///
+/// `strategy_of` 返回成交所属的策略，返回 None 的成交不计入；时间范围外的成交被忽略。
+pub fn execution_reports<'a, F>(
+    fills: impl IntoIterator<Item = &'a Fill>,
+    range: Range<u64>,
+    mut strategy_of: F,
+) -> Vec<ExecutionReport>
+where
+    F: FnMut(&Fill) -> Option<String>,
+{
+    let mut by_strategy: BTreeMap<String, Vec<&Fill>> = BTreeMap::new();
+    for fill in fills {
+        if !range.contains(&fill.ts) {
+            continue;
+        }
+        if let Some(strategy) = strategy_of(fill) {
+            by_strategy.entry(strategy).or_default().push(fill);
+        }
+    }
+    by_strategy
+        .into_iter()
+        .map(|(strategy, fills)| {
+            let orders = aggregate_fills(fills.iter().copied());
+            let mut report = ExecutionReport {
+                strategy,
+                range: range.clone(),
+                orders: orders.len(),
+                fills: fills.len(),
+                buy_volume: 0,
+                sell_volume: 0,
+                notional: 0,
+                fees: 0,
+                maker_volume: 0,
+            };
+            for order in &orders {
+                match order.side {
+                    OrderSide::Buy => report.buy_volume += order.filled,
+                    OrderSide::Sell => report.sell_volume += order.filled,
+                }
+                report.notional += order.notional;
+                report.fees += order.fees;
+                report.maker_volume += order.maker_size;
+            }
+            report
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(order_id: &str, side: OrderSide, price: u64, size: u64, ts: u64) -> Fill {
+        Fill {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: order_id.to_string(),
+            side,
+            price,
+            size,
+            fee: size,
+            is_maker: order_id == "b",
+            trade_id: None,
+            ts,
+        }
+    }
+
+    fn fills() -> Vec<Fill> {
+        vec![
+            fill("a", OrderSide::Buy, 100, 1, 10),
+            fill("b", OrderSide::Sell, 110, 2, 5),
+            fill("a", OrderSide::Buy, 103, 2, 20),
+            fill("c", OrderSide::Buy, 90, 1, 50),
+        ]
+    }
+
+    #[test]
+    fn per_order_totals_and_vwap() {
+        let orders = aggregate_fills(&fills());
+        let ids: Vec<&str> = orders.iter().map(|o| o.order_id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a", "c"]);
+        let a = &orders[1];
+        assert_eq!((a.fills, a.filled, a.fees), (2, 3, 3));
+        assert_eq!(a.vwap(), 102.0);
+        assert_eq!((a.first_ts, a.last_ts), (10, 20));
+        assert_eq!(orders[0].maker_ratio(), 1.0);
+    }
+
+    #[test]
+    fn reports_group_by_strategy_within_range() {
+        let reports = execution_reports(&fills(), 0..40, |fill| {
+            Some(if fill.order_id == "b" { "mm" } else { "taker" }.to_string())
+        });
+        assert_eq!(reports.len(), 2);
+        let mm = &reports[0];
+        assert_eq!(
+            (mm.strategy.as_str(), mm.sell_volume, mm.maker_volume),
+            ("mm", 2, 2)
+        );
+        let taker = &reports[1];
+        // order c 在时间范围外
+        assert_eq!((taker.orders, taker.fills, taker.volume()), (1, 2, 3));
+        assert_eq!(taker.notional, 306);
+    }
+}
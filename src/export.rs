@@ -0,0 +1,404 @@
+//! 行情数据导出为 Arrow / Parquet（`export` feature）
+//!
+//! 成交、K线与订单簿更新各自转为一个 [`RecordBatch`]，列名沿用推送消息的 JSON 字段，
+//! 价格与数量为 UInt64，时间戳为 UTC 毫秒 `Timestamp`，可由 pandas / polars 直接读取。
+//! 订单簿按档位展开为长表，每行一个档位，`level` 为该档在所属一侧中的位置；不含档位的更新不产生行。
+//! [`export_recording`] 将录制文件（见 [`Recorder`](crate::ws::Recorder)）按类型拆分写出 Parquet 文件。
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    BooleanBuilder, StringBuilder, TimestampMillisecondBuilder, UInt32Builder, UInt64Builder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use crate::error::{Error, Result};
+use crate::types::OrderSide;
+use crate::ws::{Candle, OrderbookUpdate, PriceLevel, Record, Trade, WsMessage};
+
+fn timestamp(name: &str) -> Field {
+    Field::new(
+        name,
+        DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+        false,
+    )
+}
+
+fn millis(ts: u64) -> i64 {
+    i64::try_from(ts).unwrap_or(i64::MAX)
+}
+
+fn side(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "Buy",
+        OrderSide::Sell => "Sell",
+    }
+}
+
+fn batch(schema: SchemaRef, columns: Vec<ArrayRef>) -> RecordBatch {
+    RecordBatch::try_new(schema, columns).expect("columns match the schema")
+}
+
+/// 成交表的结构
+pub fn trade_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("market", DataType::Utf8, false),
+        Field::new("price", DataType::UInt64, false),
+        Field::new("size", DataType::UInt64, false),
+        Field::new("side", DataType::Utf8, false),
+        Field::new("trade_id", DataType::UInt64, true),
+        timestamp("ts"),
+    ]))
+}
+
+/// 成交转为一批记录
+pub fn trades_to_batch(trades: &[Trade]) -> RecordBatch {
+    let mut market = StringBuilder::new();
+    let mut price = UInt64Builder::with_capacity(trades.len());
+    let mut size = UInt64Builder::with_capacity(trades.len());
+    let mut sides = StringBuilder::new();
+    let mut trade_id = UInt64Builder::with_capacity(trades.len());
+    let mut ts = TimestampMillisecondBuilder::with_capacity(trades.len()).with_timezone("UTC");
+    for trade in trades {
+        market.append_value(&trade.market);
+        price.append_value(trade.price);
+        size.append_value(trade.size);
+        sides.append_value(side(trade.side));
+        trade_id.append_option(trade.trade_id);
+        ts.append_value(millis(trade.ts));
+    }
+    batch(
+        trade_schema(),
+        vec![
+            Arc::new(market.finish()),
+            Arc::new(price.finish()),
+            Arc::new(size.finish()),
+            Arc::new(sides.finish()),
+            Arc::new(trade_id.finish()),
+            Arc::new(ts.finish()),
+        ],
+    )
+}
+
+/// K线表的结构
+pub fn candle_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("market", DataType::Utf8, false),
+        Field::new("interval", DataType::Utf8, false),
+        timestamp("open_time"),
+        Field::new("open", DataType::UInt64, false),
+        Field::new("high", DataType::UInt64, false),
+        Field::new("low", DataType::UInt64, false),
+        Field::new("close", DataType::UInt64, false),
+        Field::new("volume", DataType::UInt64, false),
+        Field::new("closed", DataType::Boolean, false),
+    ]))
+}
+
+/// K线转为一批记录，`interval` 沿用推送中的写法（如 `1m`）
+pub fn candles_to_batch(candles: &[Candle]) -> RecordBatch {
+    let n = candles.len();
+    let mut market = StringBuilder::new();
+    let mut interval = StringBuilder::new();
+    let mut open_time = TimestampMillisecondBuilder::with_capacity(n).with_timezone("UTC");
+    let mut ohlcv: [UInt64Builder; 5] = std::array::from_fn(|_| UInt64Builder::with_capacity(n));
+    let mut closed = BooleanBuilder::with_capacity(n);
+    for candle in candles {
+        market.append_value(&candle.market);
+        let name = serde_json::to_value(candle.interval).expect("intervals serialize to strings");
+        interval.append_value(name.as_str().unwrap_or_default());
+        open_time.append_value(millis(candle.open_time));
+        for (builder, value) in ohlcv.iter_mut().zip([
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.volume,
+        ]) {
+            builder.append_value(value);
+        }
+        closed.append_value(candle.closed);
+    }
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(market.finish()),
+        Arc::new(interval.finish()),
+        Arc::new(open_time.finish()),
+    ];
+    columns.extend(
+        ohlcv
+            .iter_mut()
+            .map(|builder| Arc::new(builder.finish()) as ArrayRef),
+    );
+    columns.push(Arc::new(closed.finish()));
+    batch(candle_schema(), columns)
+}
+
+/// 订单簿长表的结构
+pub fn book_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("market", DataType::Utf8, false),
+        Field::new("seq", DataType::UInt64, false),
+        Field::new("snapshot", DataType::Boolean, false),
+        Field::new("side", DataType::Utf8, false),
+        Field::new("level", DataType::UInt32, false),
+        Field::new("price", DataType::UInt64, false),
+        Field::new("size", DataType::UInt64, false),
+        timestamp("ts"),
+    ]))
+}
+
+/// 订单簿快照与增量按档位展开为一批记录，`side` 为 `bid` 或 `ask`
+pub fn book_to_batch(updates: &[OrderbookUpdate]) -> RecordBatch {
+    let rows: usize = updates
+        .iter()
+        .map(|update| update.bids.len() + update.asks.len())
+        .sum();
+    let mut market = StringBuilder::new();
+    let mut seq = UInt64Builder::with_capacity(rows);
+    let mut snapshot = BooleanBuilder::with_capacity(rows);
+    let mut sides = StringBuilder::new();
+    let mut level = UInt32Builder::with_capacity(rows);
+    let mut price = UInt64Builder::with_capacity(rows);
+    let mut size = UInt64Builder::with_capacity(rows);
+    let mut ts = TimestampMillisecondBuilder::with_capacity(rows).with_timezone("UTC");
+    for update in updates {
+        let levels: [(&str, &[PriceLevel]); 2] = [("bid", &update.bids), ("ask", &update.asks)];
+        for (name, levels) in levels {
+            for (i, entry) in levels.iter().enumerate() {
+                market.append_value(&update.market);
+                seq.append_value(update.seq);
+                snapshot.append_value(update.snapshot);
+                sides.append_value(name);
+                level.append_value(i as u32);
+                price.append_value(entry.price);
+                size.append_value(entry.size);
+                ts.append_value(millis(update.ts));
+            }
+        }
+    }
+    batch(
+        book_schema(),
+        vec![
+            Arc::new(market.finish()),
+            Arc::new(seq.finish()),
+            Arc::new(snapshot.finish()),
+            Arc::new(sides.finish()),
+            Arc::new(level.finish()),
+            Arc::new(price.finish()),
+            Arc::new(size.finish()),
+            Arc::new(ts.finish()),
+        ],
+    )
+}
+
+/// 以 Snappy 压缩写出 Parquet 文件
+pub fn write_parquet(path: impl AsRef<Path>, batch: &RecordBatch) -> Result<()> {
+    let parquet_error = |e: parquet::errors::ParquetError| Error::Io(std::io::Error::other(e));
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(File::create(path)?, batch.schema(), Some(properties))
+        .map_err(parquet_error)?;
+    writer.write(batch).map_err(parquet_error)?;
+    writer.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+/// [`export_recording`] 写出的各表行数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportSummary {
+    pub trades: usize,
+    pub candles: usize,
+    /// 订单簿档位行数
+    pub book_levels: usize,
+}
+
+/// 将录制文件中的成交、K线与订单簿更新分别写为 `dir` 下的
+/// `trades.parquet`、`candles.parquet` 与 `book.parquet`，其余消息忽略
+///
+/// 没有对应消息时仍写出只含表结构的空文件，便于脚本按固定文件名读取。
+pub fn export_recording(
+    recording: impl AsRef<Path>,
+    dir: impl AsRef<Path>,
+) -> Result<ExportSummary> {
+    let mut trades = Vec::new();
+    let mut candles = Vec::new();
+    let mut books = Vec::new();
+    for line in BufReader::new(File::open(recording)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record = serde_json::from_str(&line)?;
+        match record.message {
+            WsMessage::Trade(trade) => trades.push(trade),
+            WsMessage::Candle(candle) => candles.push(candle),
+            WsMessage::Book(update) => books.push(update),
+            _ => {}
+        }
+    }
+
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    let book = book_to_batch(&books);
+    write_parquet(dir.join("trades.parquet"), &trades_to_batch(&trades))?;
+    write_parquet(dir.join("candles.parquet"), &candles_to_batch(&candles))?;
+    write_parquet(dir.join("book.parquet"), &book)?;
+    Ok(ExportSummary {
+        trades: trades.len(),
+        candles: candles.len(),
+        book_levels: book.num_rows(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{TimestampMillisecondType, UInt32Type, UInt64Type};
+    use arrow_array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+    use crate::ws::CandleInterval;
+
+    fn trade(price: u64, trade_id: Option<u64>) -> Trade {
+        Trade {
+            market: "BTC-USDC".to_string(),
+            price,
+            size: 2,
+            side: OrderSide::Sell,
+            trade_id,
+            ts: 1_700_000_000_000,
+        }
+    }
+
+    fn read_parquet(path: &Path) -> RecordBatch {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap()).unwrap();
+        let schema = builder.schema().clone();
+        let batches: Vec<RecordBatch> = builder.build().unwrap().map(|b| b.unwrap()).collect();
+        match batches.as_slice() {
+            [] => RecordBatch::new_empty(schema),
+            [batch] => batch.clone(),
+            _ => panic!("expected a single batch"),
+        }
+    }
+
+    #[test]
+    fn trades_keep_nullable_ids_and_utc_timestamps() {
+        let batch = trades_to_batch(&[trade(100, Some(7)), trade(101, None)]);
+        assert_eq!(batch.num_rows(), 2);
+        let ids = batch
+            .column_by_name("trade_id")
+            .unwrap()
+            .as_primitive::<UInt64Type>();
+        assert_eq!(ids.value(0), 7);
+        assert!(ids.is_null(1));
+        let ts = batch
+            .column_by_name("ts")
+            .unwrap()
+            .as_primitive::<TimestampMillisecondType>();
+        assert_eq!(ts.value(1), 1_700_000_000_000);
+        assert_eq!(
+            batch.schema().field_with_name("ts").unwrap().data_type(),
+            &DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+        );
+        assert_eq!(
+            batch
+                .column_by_name("side")
+                .unwrap()
+                .as_string::<i32>()
+                .value(0),
+            "Sell"
+        );
+    }
+
+    #[test]
+    fn book_updates_expand_to_one_row_per_level() {
+        let update = OrderbookUpdate {
+            market: "BTC-USDC".to_string(),
+            seq: 3,
+            snapshot: true,
+            bids: vec![
+                PriceLevel { price: 99, size: 1 },
+                PriceLevel { price: 98, size: 2 },
+            ],
+            asks: vec![PriceLevel {
+                price: 101,
+                size: 3,
+            }],
+            ts: 5,
+        };
+        let batch = book_to_batch(&[update]);
+        assert_eq!(batch.num_rows(), 3);
+        let sides = batch.column_by_name("side").unwrap().as_string::<i32>();
+        let levels = batch
+            .column_by_name("level")
+            .unwrap()
+            .as_primitive::<UInt32Type>();
+        assert_eq!(
+            (0..3)
+                .map(|i| (sides.value(i), levels.value(i)))
+                .collect::<Vec<_>>(),
+            [("bid", 0), ("bid", 1), ("ask", 0)]
+        );
+    }
+
+    #[test]
+    fn exports_a_recording_to_parquet() {
+        let dir = std::env::temp_dir().join(format!("lightpool-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let recording = dir.join("recording.jsonl");
+        let candle = Candle {
+            market: "BTC-USDC".to_string(),
+            interval: CandleInterval::Min1,
+            open_time: 60_000,
+            open: 1,
+            high: 4,
+            low: 1,
+            close: 3,
+            volume: 10,
+            closed: true,
+        };
+        let lines = [
+            serde_json::json!({"ts": 1, "message": WsMessage::Trade(trade(100, Some(1)))}),
+            serde_json::json!({"ts": 2, "message": WsMessage::Candle(candle.clone())}),
+            serde_json::json!({"ts": 3, "message": {"type": "pong", "ts": 3}}),
+        ];
+        let text: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+        std::fs::write(&recording, text).unwrap();
+
+        let summary = export_recording(&recording, dir.join("out")).unwrap();
+        assert_eq!(
+            summary,
+            ExportSummary {
+                trades: 1,
+                candles: 1,
+                book_levels: 0,
+            }
+        );
+        let trades = read_parquet(&dir.join("out/trades.parquet"));
+        let candles = read_parquet(&dir.join("out/candles.parquet"));
+        let book = read_parquet(&dir.join("out/book.parquet"));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(trades, trades_to_batch(&[trade(100, Some(1))]));
+        assert_eq!(candles, candles_to_batch(&[candle]));
+        assert_eq!(
+            candles
+                .column_by_name("interval")
+                .unwrap()
+                .as_string::<i32>()
+                .value(0),
+            "1m"
+        );
+        assert_eq!(book.num_rows(), 0);
+        assert_eq!(book.schema(), book_schema());
+    }
+}
@@ -0,0 +1,184 @@
+//! 挂单/吃单手续费模型
+//!
+//! 费率以基点计，负数表示返佣。档位按 30 日成交量划分，可由市场信息或配置文件加载，
+//! 例如 `{"tiers":[{"min_volume":0,"maker_bps":2,"taker_bps":5},{"min_volume":1000000,"maker_bps":-1,"taker_bps":3}]}`。
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::client::MarketInfo;
+use crate::error::Result;
+use crate::order::{OrderType, PlaceOrderParams, TimeInForce};
+use crate::orderbook::FillEstimate;
+use crate::ws::Fill;
+
+/// 流动性方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+impl Liquidity {
+    /// 按订单类型推断：GTC 限价单按挂单计，其余按吃单计
+    pub fn of(order: &PlaceOrderParams) -> Self {
+        match order.order_type {
+            OrderType::Limit {
+                tif: TimeInForce::Gtc,
+            } => Liquidity::Maker,
+            _ => Liquidity::Taker,
+        }
+    }
+}
+
+/// 手续费档位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeTier {
+    /// 适用的最低 30 日成交量
+    pub min_volume: u64,
+    /// 挂单费率（基点），负数为返佣
+    pub maker_bps: i32,
+    /// 吃单费率（基点）
+    pub taker_bps: i32,
+}
+
+/// 分档手续费表
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    tiers: Vec<FeeTier>,
+    /// 账户 30 日成交量，决定适用档位
+    #[serde(default)]
+    volume_30d: u64,
+}
+
+impl FeeSchedule {
+    /// 按 `min_volume` 排序档位
+    pub fn new(mut tiers: Vec<FeeTier>) -> Self {
+        tiers.sort_by_key(|tier| tier.min_volume);
+        Self {
+            tiers,
+            volume_30d: 0,
+        }
+    }
+
+    /// 单一费率
+    pub fn flat(maker_bps: i32, taker_bps: i32) -> Self {
+        Self::new(vec![FeeTier {
+            min_volume: 0,
+            maker_bps,
+            taker_bps,
+        }])
+    }
+
+    /// 使用市场信息中的费率
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_market(market: &MarketInfo) -> Self {
+        Self::flat(market.maker_fee_bps.into(), market.taker_fee_bps.into())
+    }
+
+    /// 从 JSON 配置加载
+    pub fn from_json(json: &str) -> Result<Self> {
+        let schedule: Self = serde_json::from_str(json)?;
+        Ok(Self::new(schedule.tiers).with_volume(schedule.volume_30d))
+    }
+
+    /// 设置账户 30 日成交量
+    pub fn with_volume(mut self, volume_30d: u64) -> Self {
+        self.volume_30d = volume_30d;
+        self
+    }
+
+    /// 当前成交量适用的档位；没有档位时费率为 0
+    pub fn tier(&self) -> FeeTier {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| self.volume_30d >= tier.min_volume)
+            .or(self.tiers.first())
+            .copied()
+            .unwrap_or(FeeTier {
+                min_volume: 0,
+                maker_bps: 0,
+                taker_bps: 0,
+            })
+    }
+
+    /// 费率（基点）
+    pub fn rate_bps(&self, liquidity: Liquidity) -> i32 {
+        let tier = self.tier();
+        match liquidity {
+            Liquidity::Maker => tier.maker_bps,
+            Liquidity::Taker => tier.taker_bps,
+        }
+    }
+
+    /// 按成交金额计算手续费，负数为返佣
+    pub fn fee(&self, liquidity: Liquidity, notional: u128) -> f64 {
+        notional as f64 * self.rate_bps(liquidity) as f64 / 10_000.0
+    }
+
+    /// 下单前估算手续费：流动性方向由订单类型推断，金额取自成交估算
+    pub fn estimate_fee(&self, order: &PlaceOrderParams, fill: &FillEstimate) -> f64 {
+        self.fee(Liquidity::of(order), fill.notional)
+    }
+
+    /// 已成交回报按本费率表应收的手续费，可用于核对 [`Fill::fee`]
+    pub fn fill_fee(&self, fill: &Fill) -> f64 {
+        let liquidity = if fill.is_maker {
+            Liquidity::Maker
+        } else {
+            Liquidity::Taker
+        };
+        self.fee(liquidity, fill.price as u128 * fill.size as u128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderSide;
+
+    #[test]
+    fn tiers_follow_volume() {
+        let schedule = FeeSchedule::from_json(
+            r#"{"tiers":[{"min_volume":1000,"maker_bps":-1,"taker_bps":3},{"min_volume":0,"maker_bps":2,"taker_bps":5}]}"#,
+        )
+        .unwrap();
+        assert_eq!(schedule.rate_bps(Liquidity::Taker), 5);
+        let vip = schedule.with_volume(5_000);
+        assert_eq!(vip.rate_bps(Liquidity::Maker), -1);
+        assert_eq!(vip.fee(Liquidity::Maker, 100_000), -10.0);
+        assert_eq!(FeeSchedule::new(vec![]).rate_bps(Liquidity::Taker), 0);
+    }
+
+    #[test]
+    fn estimates_by_order_type_and_fill_side() {
+        let schedule = FeeSchedule::flat(2, 10);
+        let estimate = FillEstimate {
+            side: OrderSide::Buy,
+            requested: 10,
+            filled: 10,
+            notional: 1_000_000,
+            best_price: 100_000,
+            worst_price: 100_000,
+        };
+        let limit = PlaceOrderParams::limit(OrderSide::Buy, 10, 100_000);
+        let market = PlaceOrderParams::market(OrderSide::Buy, 10, 100_000, 50);
+        assert_eq!(schedule.estimate_fee(&limit, &estimate), 200.0);
+        assert_eq!(schedule.estimate_fee(&market, &estimate), 1_000.0);
+
+        let fill = Fill {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: "00".to_string(),
+            side: OrderSide::Sell,
+            price: 1_000,
+            size: 10,
+            fee: 10,
+            is_maker: false,
+            trade_id: None,
+            ts: 0,
+        };
+        assert_eq!(schedule.fill_fee(&fill), 10.0);
+    }
+}
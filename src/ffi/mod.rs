@@ -0,0 +1,320 @@
+//! C 接口（`ffi` feature）
+//!
+//! 供 C/C++ 交易系统复用同一套下单参数编码与交易签名。头文件 `include/lightpool.h`
+//! 由 cbindgen 按 `cbindgen.toml` 生成，接口变化后执行
+//! `cbindgen --config cbindgen.toml --output include/lightpool.h` 重新生成。
+//! 静态库可由 `cargo rustc --release --lib --no-default-features --features ffi --crate-type staticlib` 构建。
+//!
+//! 所有函数返回 [`LightpoolStatus`]，失败时可由 [`lightpool_last_error`] 取得当前线程最近一次的错误信息。
+//! 输出写入调用方提供的 [`LightpoolBuffer`]，使用完毕后须以 [`lightpool_free_buffer`] 释放。
+//! 参数结构体是 serde 类型的 `#[repr(C)]` 镜像，布局由编译期断言固定。
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use crate::crypto::{Ed25519Signer, Signer};
+use crate::error::Error;
+use crate::order::{CancelOrderParams, PlaceOrderParams};
+use crate::transaction::{Action, Transaction};
+use crate::types::{Address, ObjectId};
+
+mod repr;
+
+pub use repr::{
+    LightpoolAction, LightpoolBuffer, LightpoolCancelOrder, LightpoolPlaceOrder,
+    LightpoolSignature, LightpoolStatus,
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn fail(status: LightpoolStatus, message: String) -> LightpoolStatus {
+    let message = CString::new(message).unwrap_or_else(|_| c"invalid error message".into());
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    status
+}
+
+fn invalid(error: Error) -> LightpoolStatus {
+    fail(LightpoolStatus::InvalidArgument, error.to_string())
+}
+
+fn null(what: &str) -> LightpoolStatus {
+    fail(
+        LightpoolStatus::NullPointer,
+        format!("{} must not be null", what),
+    )
+}
+
+/// 将 `bytes` 移交给调用方
+unsafe fn write(out: *mut LightpoolBuffer, bytes: Vec<u8>) -> LightpoolStatus {
+    let bytes = bytes.into_boxed_slice();
+    let len = bytes.len();
+    out.write(LightpoolBuffer {
+        data: Box::into_raw(bytes).cast(),
+        len,
+    });
+    LightpoolStatus::Ok
+}
+
+/// 长度为 0 时允许空指针
+unsafe fn slice<'a, T>(data: *const T, len: usize) -> Option<&'a [T]> {
+    match (data.is_null(), len) {
+        (_, 0) => Some(&[]),
+        (true, _) => None,
+        (false, len) => Some(std::slice::from_raw_parts(data, len)),
+    }
+}
+
+/// 当前线程最近一次失败的错误信息，没有时返回空指针
+///
+/// 返回的字符串在本线程下一次调用本库函数前有效，不得释放。
+#[no_mangle]
+pub extern "C" fn lightpool_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |e| e.as_ptr()))
+}
+
+/// 编码下单参数，结果为 `ord_place` 操作的 bincode 参数
+///
+/// # Safety
+///
+/// `order` 须指向有效的 [`LightpoolPlaceOrder`]，`out` 须指向可写的 [`LightpoolBuffer`]。
+#[no_mangle]
+pub unsafe extern "C" fn lightpool_encode_place_order(
+    order: *const LightpoolPlaceOrder,
+    out: *mut LightpoolBuffer,
+) -> LightpoolStatus {
+    let (Some(order), false) = (order.as_ref(), out.is_null()) else {
+        return null("order and out");
+    };
+    match PlaceOrderParams::try_from(order) {
+        Ok(params) => write(out, params.encode()),
+        Err(e) => invalid(e),
+    }
+}
+
+/// 解码 `ord_place` 操作的 bincode 参数
+///
+/// # Safety
+///
+/// `data` 须在 `len` 字节内可读，`out` 须指向可写的 [`LightpoolPlaceOrder`]。
+#[no_mangle]
+pub unsafe extern "C" fn lightpool_decode_place_order(
+    data: *const u8,
+    len: usize,
+    out: *mut LightpoolPlaceOrder,
+) -> LightpoolStatus {
+    let (Some(data), false) = (slice(data, len), out.is_null()) else {
+        return null("data and out");
+    };
+    match bincode::deserialize::<PlaceOrderParams>(data) {
+        Ok(params) => {
+            out.write(LightpoolPlaceOrder::from(&params));
+            LightpoolStatus::Ok
+        }
+        Err(e) => invalid(Error::Decode(format!("invalid order params: {}", e))),
+    }
+}
+
+/// 编码撤单参数，结果为 `ord_cancel` 操作的 bincode 参数
+///
+/// # Safety
+///
+/// `order` 须指向有效的 [`LightpoolCancelOrder`]，`out` 须指向可写的 [`LightpoolBuffer`]。
+#[no_mangle]
+pub unsafe extern "C" fn lightpool_encode_cancel_order(
+    order: *const LightpoolCancelOrder,
+    out: *mut LightpoolBuffer,
+) -> LightpoolStatus {
+    let (Some(order), false) = (order.as_ref(), out.is_null()) else {
+        return null("order and out");
+    };
+    let params = CancelOrderParams::from(order);
+    write(
+        out,
+        bincode::serialize(&params).expect("cancel params are always serializable"),
+    )
+}
+
+/// 以 `secret_key` 对应的地址为发送者，构建只含一个操作的交易并签名
+///
+/// 结果为已签名交易的 JSON，即 `submitTransaction` 的 `tx` 参数；`expiration` 为 `UINT64_MAX` 时不过期。
+///
+/// # Safety
+///
+/// `secret_key` 须指向 32 字节私钥，`action` 须指向有效的 [`LightpoolAction`]，
+/// 其中的数组指针须在给定长度内可读；`out` 须指向可写的 [`LightpoolBuffer`]。
+#[no_mangle]
+pub unsafe extern "C" fn lightpool_sign_action(
+    secret_key: *const u8,
+    action: *const LightpoolAction,
+    expiration: u64,
+    out: *mut LightpoolBuffer,
+) -> LightpoolStatus {
+    let (false, Some(action), false) = (secret_key.is_null(), action.as_ref(), out.is_null())
+    else {
+        return null("secret_key, action and out");
+    };
+    if action.name.is_null() {
+        return null("action name");
+    }
+    let Ok(name) = CStr::from_ptr(action.name).to_str() else {
+        return invalid(Error::Validation("action name is not utf-8".to_string()));
+    };
+    let (Some(inputs), Some(params)) = (
+        slice(action.inputs, action.inputs_len.saturating_mul(32)),
+        slice(action.params, action.params_len),
+    ) else {
+        return null("action inputs and params");
+    };
+    let inputs = inputs
+        .chunks_exact(32)
+        .map(|id| ObjectId(id.try_into().expect("chunks are 32 bytes")))
+        .collect();
+    let action = match Action::new(Address(action.contract), name, inputs, params.to_vec()) {
+        Ok(action) => action,
+        Err(e) => return invalid(e),
+    };
+    let signer = Ed25519Signer::from_secret_key_bytes(&*secret_key.cast::<[u8; 32]>());
+    let signed = Transaction {
+        sender: signer.address(),
+        expiration,
+        actions: vec![action],
+    }
+    .sign(&signer);
+    match serde_json::to_vec(&signed) {
+        Ok(json) => write(out, json),
+        Err(e) => invalid(e.into()),
+    }
+}
+
+/// 释放本库分配的缓冲区，释放后 `buffer` 被置空；重复释放是安全的
+///
+/// # Safety
+///
+/// `buffer` 须为空指针或指向由本库写入的 [`LightpoolBuffer`]。
+#[no_mangle]
+pub unsafe extern "C" fn lightpool_free_buffer(buffer: *mut LightpoolBuffer) {
+    let Some(buffer) = buffer.as_mut() else {
+        return;
+    };
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+    buffer.data = ptr::null_mut();
+    buffer.len = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::{self, OrderRequest};
+    use crate::transaction::{SignedTransaction, SPOT_CONTRACT};
+    use crate::types::{OrderId, OrderSide};
+
+    fn empty() -> LightpoolBuffer {
+        LightpoolBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    unsafe fn bytes(buffer: &LightpoolBuffer) -> Vec<u8> {
+        std::slice::from_raw_parts(buffer.data, buffer.len).to_vec()
+    }
+
+    #[test]
+    fn encodes_and_signs_like_the_rust_api() {
+        let params = PlaceOrderParams::limit(OrderSide::Buy, 5_000_000, 50_000_000_000);
+        let order = LightpoolPlaceOrder::from(&params);
+        let mut out = empty();
+        unsafe {
+            assert_eq!(
+                lightpool_encode_place_order(&order, &mut out),
+                LightpoolStatus::Ok
+            );
+            assert_eq!(bytes(&out), params.encode());
+            let mut decoded =
+                LightpoolPlaceOrder::from(&PlaceOrderParams::market(OrderSide::Sell, 1, 1, 1));
+            assert_eq!(
+                lightpool_decode_place_order(out.data, out.len, &mut decoded),
+                LightpoolStatus::Ok
+            );
+            assert_eq!(decoded, order);
+            lightpool_free_buffer(&mut out);
+            assert!(out.data.is_null());
+            lightpool_free_buffer(&mut out);
+        }
+
+        let expected = OrderRequest::spot(ObjectId([1; 32]), ObjectId([2; 32]), params)
+            .to_action()
+            .unwrap();
+        let inputs = [[1u8; 32], [2u8; 32]];
+        let action = LightpoolAction {
+            contract: SPOT_CONTRACT.0,
+            name: c"ord_place".as_ptr(),
+            inputs: inputs.as_ptr().cast(),
+            inputs_len: inputs.len(),
+            params: expected.params.as_ptr(),
+            params_len: expected.params.len(),
+        };
+        let secret_key = [7u8; 32];
+        let signed: SignedTransaction = unsafe {
+            assert_eq!(
+                lightpool_sign_action(secret_key.as_ptr(), &action, u64::MAX, &mut out),
+                LightpoolStatus::Ok
+            );
+            let signed = serde_json::from_slice(&bytes(&out)).unwrap();
+            lightpool_free_buffer(&mut out);
+            signed
+        };
+        let signer = Ed25519Signer::from_secret_key_bytes(&secret_key);
+        assert_eq!(signed.transaction.actions, [expected]);
+        assert!(signed.verify(&signer.public_key()));
+
+        let cancel = LightpoolCancelOrder { order_id: [3; 32] };
+        let expected =
+            order::cancel_action(SPOT_CONTRACT, ObjectId([1; 32]), OrderId([3; 32])).unwrap();
+        unsafe {
+            assert_eq!(
+                lightpool_encode_cancel_order(&cancel, &mut out),
+                LightpoolStatus::Ok
+            );
+            assert_eq!(bytes(&out), expected.params);
+            lightpool_free_buffer(&mut out);
+        }
+    }
+
+    #[test]
+    fn failures_set_the_last_error() {
+        let order = LightpoolPlaceOrder {
+            side: 2,
+            amount: 1,
+            limit_price: 1,
+            order_type: 0,
+            time_in_force: 0,
+            slippage_bps: 0,
+            trigger_price: 0,
+            trigger_is_market: 0,
+            trigger_type: 0,
+        };
+        let mut out = empty();
+        unsafe {
+            assert_eq!(
+                lightpool_encode_place_order(&order, &mut out),
+                LightpoolStatus::InvalidArgument
+            );
+            let message = CStr::from_ptr(lightpool_last_error()).to_str().unwrap();
+            assert_eq!(message, "validation error: invalid order side: 2");
+            assert_eq!(
+                lightpool_encode_place_order(ptr::null(), &mut out),
+                LightpoolStatus::NullPointer
+            );
+        }
+        assert!(out.data.is_null());
+    }
+}
@@ -0,0 +1,306 @@
+//! C 接口的 `#[repr(C)]` 镜像类型
+//!
+//! 与 serde 类型分离定义并相互转换，Rust 侧结构调整不会改变 C 接口的内存布局。
+//! 字段偏移、大小与对齐由文件末尾的编译期断言固定，修改布局须同步更新断言与头文件。
+
+use std::ffi::{c_char, c_int};
+use std::mem::{align_of, offset_of, size_of};
+
+use crate::error::Error;
+use crate::order::{CancelOrderParams, OrderType, PlaceOrderParams, TimeInForce};
+use crate::transaction::Signature;
+use crate::types::{OrderId, OrderSide};
+
+/// 调用结果
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightpoolStatus {
+    Ok = 0,
+    /// 必需的指针参数为空
+    NullPointer = 1,
+    /// 参数不合法
+    InvalidArgument = 2,
+}
+
+/// 由本库分配的字节缓冲区
+#[repr(C)]
+#[derive(Debug)]
+pub struct LightpoolBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+/// 下单参数，对应 [`PlaceOrderParams`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LightpoolPlaceOrder {
+    /// 0 为买，1 为卖
+    pub side: u8,
+    pub amount: u64,
+    /// 限价；市价单为滑点参考价
+    pub limit_price: u64,
+    /// 0 为限价单，1 为市价单，2 为触发单
+    pub order_type: u8,
+    /// 限价单有效期：0 为 GTC，1 为 IOC，2 为 FOK
+    pub time_in_force: u8,
+    /// 市价单最大滑点（基点）
+    pub slippage_bps: u64,
+    /// 触发单的触发价
+    pub trigger_price: u64,
+    /// 触发单触发后是否按市价成交，0 或 1
+    pub trigger_is_market: u8,
+    /// 触发单的触发类型
+    pub trigger_type: u8,
+}
+
+/// 撤单参数，对应 [`CancelOrderParams`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LightpoolCancelOrder {
+    pub order_id: [u8; 32],
+}
+
+/// 64 字节 Ed25519 签名，对应 [`Signature`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LightpoolSignature {
+    pub part1: [u8; 32],
+    pub part2: [u8; 32],
+}
+
+/// 合约调用
+#[repr(C)]
+#[derive(Debug)]
+pub struct LightpoolAction {
+    /// 目标合约地址
+    pub contract: [u8; 32],
+    /// 操作名，以 NUL 结尾，如 `ord_place`
+    pub name: *const c_char,
+    /// 依次排列的输入对象ID，每个 32 字节
+    pub inputs: *const u8,
+    /// 输入对象个数
+    pub inputs_len: usize,
+    /// bincode 编码的参数
+    pub params: *const u8,
+    pub params_len: usize,
+}
+
+impl From<&PlaceOrderParams> for LightpoolPlaceOrder {
+    fn from(params: &PlaceOrderParams) -> Self {
+        let mut order = Self {
+            side: match params.side {
+                OrderSide::Buy => 0,
+                OrderSide::Sell => 1,
+            },
+            amount: params.amount,
+            limit_price: params.limit_price,
+            order_type: 0,
+            time_in_force: 0,
+            slippage_bps: 0,
+            trigger_price: 0,
+            trigger_is_market: 0,
+            trigger_type: 0,
+        };
+        match params.order_type {
+            OrderType::Limit { tif } => {
+                order.time_in_force = match tif {
+                    TimeInForce::Gtc => 0,
+                    TimeInForce::Ioc => 1,
+                    TimeInForce::Fok => 2,
+                };
+            }
+            OrderType::Market { slippage } => {
+                order.order_type = 1;
+                order.slippage_bps = slippage;
+            }
+            OrderType::Trigger {
+                trigger_price,
+                is_market,
+                trigger_type,
+            } => {
+                order.order_type = 2;
+                order.trigger_price = trigger_price;
+                order.trigger_is_market = is_market.into();
+                order.trigger_type = trigger_type;
+            }
+        }
+        order
+    }
+}
+
+impl TryFrom<&LightpoolPlaceOrder> for PlaceOrderParams {
+    type Error = Error;
+
+    fn try_from(order: &LightpoolPlaceOrder) -> Result<Self, Error> {
+        let side = match order.side {
+            0 => OrderSide::Buy,
+            1 => OrderSide::Sell,
+            side => return Err(Error::Validation(format!("invalid order side: {}", side))),
+        };
+        let order_type = match order.order_type {
+            0 => OrderType::Limit {
+                tif: match order.time_in_force {
+                    0 => TimeInForce::Gtc,
+                    1 => TimeInForce::Ioc,
+                    2 => TimeInForce::Fok,
+                    tif => {
+                        return Err(Error::Validation(format!("invalid time in force: {}", tif)))
+                    }
+                },
+            },
+            1 => OrderType::Market {
+                slippage: order.slippage_bps,
+            },
+            2 => OrderType::Trigger {
+                trigger_price: order.trigger_price,
+                is_market: match order.trigger_is_market {
+                    0 => false,
+                    1 => true,
+                    flag => {
+                        return Err(Error::Validation(format!(
+                            "invalid trigger market flag: {}",
+                            flag
+                        )))
+                    }
+                },
+                trigger_type: order.trigger_type,
+            },
+            kind => return Err(Error::Validation(format!("invalid order type: {}", kind))),
+        };
+        Ok(PlaceOrderParams {
+            side,
+            amount: order.amount,
+            order_type,
+            limit_price: order.limit_price,
+        })
+    }
+}
+
+impl From<&CancelOrderParams> for LightpoolCancelOrder {
+    fn from(params: &CancelOrderParams) -> Self {
+        Self {
+            order_id: params.order_id.0,
+        }
+    }
+}
+
+impl From<&LightpoolCancelOrder> for CancelOrderParams {
+    fn from(order: &LightpoolCancelOrder) -> Self {
+        Self {
+            order_id: OrderId(order.order_id),
+        }
+    }
+}
+
+impl From<Signature> for LightpoolSignature {
+    fn from(signature: Signature) -> Self {
+        Self {
+            part1: signature.part1,
+            part2: signature.part2,
+        }
+    }
+}
+
+impl From<LightpoolSignature> for Signature {
+    fn from(signature: LightpoolSignature) -> Self {
+        Self {
+            part1: signature.part1,
+            part2: signature.part2,
+        }
+    }
+}
+
+const PTR: usize = size_of::<usize>();
+
+const _: () = {
+    assert!(size_of::<LightpoolStatus>() == size_of::<c_int>());
+
+    assert!(size_of::<LightpoolBuffer>() == 2 * PTR);
+    assert!(offset_of!(LightpoolBuffer, data) == 0);
+    assert!(offset_of!(LightpoolBuffer, len) == PTR);
+
+    assert!(size_of::<LightpoolPlaceOrder>() == 56);
+    assert!(align_of::<LightpoolPlaceOrder>() == align_of::<u64>());
+    assert!(offset_of!(LightpoolPlaceOrder, side) == 0);
+    assert!(offset_of!(LightpoolPlaceOrder, amount) == 8);
+    assert!(offset_of!(LightpoolPlaceOrder, limit_price) == 16);
+    assert!(offset_of!(LightpoolPlaceOrder, order_type) == 24);
+    assert!(offset_of!(LightpoolPlaceOrder, time_in_force) == 25);
+    assert!(offset_of!(LightpoolPlaceOrder, slippage_bps) == 32);
+    assert!(offset_of!(LightpoolPlaceOrder, trigger_price) == 40);
+    assert!(offset_of!(LightpoolPlaceOrder, trigger_is_market) == 48);
+    assert!(offset_of!(LightpoolPlaceOrder, trigger_type) == 49);
+
+    assert!(size_of::<LightpoolCancelOrder>() == 32);
+    assert!(align_of::<LightpoolCancelOrder>() == 1);
+
+    assert!(size_of::<LightpoolSignature>() == 64);
+    assert!(align_of::<LightpoolSignature>() == 1);
+    assert!(offset_of!(LightpoolSignature, part2) == 32);
+
+    assert!(size_of::<LightpoolAction>() == 32 + 5 * PTR);
+    assert!(offset_of!(LightpoolAction, contract) == 0);
+    assert!(offset_of!(LightpoolAction, name) == 32);
+    assert!(offset_of!(LightpoolAction, inputs) == 32 + PTR);
+    assert!(offset_of!(LightpoolAction, inputs_len) == 32 + 2 * PTR);
+    assert!(offset_of!(LightpoolAction, params) == 32 + 3 * PTR);
+    assert!(offset_of!(LightpoolAction, params_len) == 32 + 4 * PTR);
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn place_orders_round_trip_through_the_mirror() {
+        let orders = [
+            PlaceOrderParams::limit(OrderSide::Buy, 5_000_000, 50_000_000_000),
+            PlaceOrderParams {
+                order_type: OrderType::Limit {
+                    tif: TimeInForce::Fok,
+                },
+                ..PlaceOrderParams::limit(OrderSide::Sell, 1, 2)
+            },
+            PlaceOrderParams::market(OrderSide::Sell, 1_000, 42_000, 50),
+            PlaceOrderParams {
+                order_type: OrderType::Trigger {
+                    trigger_price: 41_000,
+                    is_market: true,
+                    trigger_type: 2,
+                },
+                ..PlaceOrderParams::limit(OrderSide::Buy, 3, 40_000)
+            },
+        ];
+        for params in &orders {
+            let mirror = LightpoolPlaceOrder::from(params);
+            assert_eq!(&PlaceOrderParams::try_from(&mirror).unwrap(), params);
+        }
+
+        let mut mirror = LightpoolPlaceOrder::from(&orders[3]);
+        mirror.trigger_is_market = 2;
+        let err = PlaceOrderParams::try_from(&mirror).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "validation error: invalid trigger market flag: 2"
+        );
+    }
+
+    #[test]
+    fn signatures_and_cancels_round_trip_through_the_mirror() {
+        let signature = Signature {
+            part1: [1; 32],
+            part2: [2; 32],
+        };
+        assert_eq!(
+            Signature::from(LightpoolSignature::from(signature)),
+            signature
+        );
+        let cancel = CancelOrderParams {
+            order_id: OrderId([3; 32]),
+        };
+        assert_eq!(
+            CancelOrderParams::from(&LightpoolCancelOrder::from(&cancel)),
+            cancel
+        );
+    }
+}
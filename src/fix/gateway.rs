@@ -0,0 +1,778 @@
+//! FIX 订单请求与链上操作、账户推送与 ExecutionReport 之间的转换
+
+use std::collections::HashMap;
+
+use super::{msg_type, tag, utc_timestamp, FixMessage};
+use crate::error::{Error, Result};
+use crate::order::{
+    format_units, parse_units, Decimals, Market, OrderRequest, OrderState, OrderStore, OrderType,
+    PlaceOrderParams, Reconciled, TimeInForce, TrackedOrder,
+};
+use crate::risk::{RiskChecker, RiskContext, RiskLimits};
+use crate::symbol::Symbol;
+use crate::transaction::Action;
+use crate::types::{ObjectId, OrderId, OrderSide};
+use crate::ws::AccountEvent;
+
+/// 市价单默认允许的滑点（基点）；FIX 市价单不带滑点，按此值提交
+pub const DEFAULT_SLIPPAGE_BPS: u64 = 50;
+
+/// FIX Symbol 对应的链上市场
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixMarket {
+    pub market: Market,
+    /// FIX 中十进制价格与数量对应的链上小数位数
+    pub decimals: Decimals,
+}
+
+impl FixMarket {
+    pub fn new(market: Market, decimals: Decimals) -> Self {
+        Self { market, decimals }
+    }
+
+    /// 现货市场
+    pub fn spot(
+        market_id: ObjectId,
+        base_balance: ObjectId,
+        quote_balance: ObjectId,
+        decimals: Decimals,
+    ) -> Self {
+        Self::new(
+            Market::spot(market_id, base_balance, quote_balance),
+            decimals,
+        )
+    }
+}
+
+/// 由 FIX 消息转换得到的链上请求
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixRequest {
+    /// NewOrderSingle，签名提交后调用 [`FixGateway::submitted`] 或 [`FixGateway::submit_failed`]
+    Place {
+        cl_ord_id: String,
+        order: OrderRequest,
+    },
+    /// OrderCancelRequest
+    Cancel {
+        cl_ord_id: String,
+        orig_cl_ord_id: String,
+        order_id: OrderId,
+        action: Action,
+    },
+}
+
+/// 经 FIX 提交的订单已回报的进度
+#[derive(Debug, Clone, Default)]
+struct FixOrder {
+    cl_ord_id: String,
+    /// 是否已回报 New
+    acked: bool,
+    cum_qty: u64,
+    /// 累计成交金额（价格 × 数量），用于计算 AvgPx
+    notional: u128,
+    /// 最近一次撤单请求的 ClOrdID
+    cancel: Option<String>,
+}
+
+/// FIX 订单网关
+///
+/// Symbol 可用 [`Symbol`] 接受的任意写法（例如 `BTCUSDC`），按规范市场名（`BTC-USDC`）匹配
+/// 用 [`FixGateway::market`] 登记的市场，订单与回报中的 Symbol 均为规范市场名。
+/// 订单在 [`OrderStore`] 中跟踪，回报以携带累计成交量的订单更新为准：
+/// 首次确认回报 New，累计成交量增加时回报 Trade，撤单、过期与拒绝回报对应的终态。
+/// Trade 的 LastPx 取此前收到的该订单成交回报价格，未收到时取订单价格。
+#[derive(Debug)]
+pub struct FixGateway {
+    markets: HashMap<String, FixMarket>,
+    store: OrderStore,
+    /// OrderStore 的 cloid → 经 FIX 提交的订单
+    orders: HashMap<String, FixOrder>,
+    /// FIX ClOrdID → OrderStore 的 cloid
+    cloids: HashMap<String, String>,
+    /// 交易所订单ID → 最近一笔成交价格
+    last_px: HashMap<String, u64>,
+    slippage_bps: u64,
+    /// NewOrderSingle 的风控检查，见 [`FixGateway::risk`]
+    risk: Option<RiskChecker>,
+    next_exec_id: u64,
+}
+
+impl Default for FixGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FixGateway {
+    pub fn new() -> Self {
+        Self {
+            markets: HashMap::new(),
+            store: OrderStore::new(),
+            orders: HashMap::new(),
+            cloids: HashMap::new(),
+            last_px: HashMap::new(),
+            slippage_bps: DEFAULT_SLIPPAGE_BPS,
+            risk: None,
+            next_exec_id: 1,
+        }
+    }
+
+    /// 登记 Symbol 对应的市场
+    pub fn market(mut self, symbol: impl Into<Symbol>, market: FixMarket) -> Self {
+        self.markets.insert(symbol.into().market(), market);
+        self
+    }
+
+    /// 市价单的滑点（基点）
+    pub fn slippage(mut self, bps: u64) -> Self {
+        self.slippage_bps = bps;
+        self
+    }
+
+    /// 以 `limits` 检查 NewOrderSingle，未通过时 [`FixGateway::handle`] 返回 [`Error::Risk`]
+    ///
+    /// 持仓取该市场经网关成交的净数量，挂单数取网关跟踪的未完结订单。
+    pub fn risk(mut self, limits: RiskLimits) -> Self {
+        self.risk = Some(RiskChecker::new(limits));
+        self
+    }
+
+    /// 经网关提交的订单在 `market` 上的风控状态
+    fn risk_context(&self, market: &str) -> RiskContext {
+        let position = self
+            .store
+            .orders()
+            .filter(|order| order.market == market)
+            .map(|order| {
+                let filled = order.lifecycle.filled() as i64;
+                match order.side {
+                    OrderSide::Buy => filled,
+                    OrderSide::Sell => -filled,
+                }
+            })
+            .sum();
+        RiskContext {
+            position,
+            mid_price: None,
+            open_orders: self.store.open_orders().len(),
+        }
+    }
+
+    /// 网关跟踪的订单
+    pub fn store(&self) -> &OrderStore {
+        &self.store
+    }
+
+    /// 转换 NewOrderSingle 或 OrderCancelRequest；失败时可用 [`FixGateway::reject`] 生成拒绝回报
+    pub fn handle(&mut self, msg: &FixMessage, now: u64) -> Result<FixRequest> {
+        match msg.msg_type() {
+            msg_type::NEW_ORDER_SINGLE => self.new_order(msg, now),
+            msg_type::ORDER_CANCEL_REQUEST => self.cancel(msg),
+            other => Err(Error::Validation(format!(
+                "unsupported message type: {}",
+                other
+            ))),
+        }
+    }
+
+    fn new_order(&mut self, msg: &FixMessage, now: u64) -> Result<FixRequest> {
+        let cl_ord_id = msg.require(tag::CL_ORD_ID)?;
+        if self.cloids.contains_key(cl_ord_id) {
+            return Err(Error::Validation(format!(
+                "duplicate ClOrdID: {}",
+                cl_ord_id
+            )));
+        }
+        let (symbol, market) = self.market_for(msg.require(tag::SYMBOL)?)?;
+        let side = match msg.require(tag::SIDE)? {
+            "1" => OrderSide::Buy,
+            "2" => OrderSide::Sell,
+            other => return Err(Error::Validation(format!("unsupported side: {}", other))),
+        };
+        let amount = parse_units(msg.require(tag::ORDER_QTY)?, market.decimals.size)?;
+        if amount == 0 {
+            return Err(Error::Validation(
+                "order quantity must be positive".to_string(),
+            ));
+        }
+        // 市价单的 Price 作为滑点参考价，同样必填
+        let price = parse_units(msg.require(tag::PRICE)?, market.decimals.price)?;
+        let params = match msg.require(tag::ORD_TYPE)? {
+            "1" => PlaceOrderParams::market(side, amount, price, self.slippage_bps),
+            "2" => {
+                // 未指定 TimeInForce 时按 GTC 处理
+                let tif = match msg.get(tag::TIME_IN_FORCE).unwrap_or("1") {
+                    "1" => TimeInForce::Gtc,
+                    "3" => TimeInForce::Ioc,
+                    "4" => TimeInForce::Fok,
+                    other => {
+                        return Err(Error::Validation(format!(
+                            "unsupported time in force: {}",
+                            other
+                        )))
+                    }
+                };
+                PlaceOrderParams {
+                    side,
+                    amount,
+                    order_type: OrderType::Limit { tif },
+                    limit_price: price,
+                }
+            }
+            other => {
+                return Err(Error::Validation(format!(
+                    "unsupported order type: {}",
+                    other
+                )))
+            }
+        };
+        let order = market.market.order_request(params)?;
+        if let Some(checker) = &self.risk {
+            let violations = checker.check(&order.params, &self.risk_context(&symbol));
+            if !violations.is_empty() {
+                return Err(Error::Risk(violations));
+            }
+        }
+
+        let cloid = self.store.submit(&symbol, side, price, amount, now)?;
+        self.cloids.insert(cl_ord_id.to_string(), cloid.clone());
+        self.orders.insert(
+            cloid,
+            FixOrder {
+                cl_ord_id: cl_ord_id.to_string(),
+                ..FixOrder::default()
+            },
+        );
+        Ok(FixRequest::Place {
+            cl_ord_id: cl_ord_id.to_string(),
+            order,
+        })
+    }
+
+    fn cancel(&mut self, msg: &FixMessage) -> Result<FixRequest> {
+        let cl_ord_id = msg.require(tag::CL_ORD_ID)?;
+        let orig_cl_ord_id = msg.require(tag::ORIG_CL_ORD_ID)?;
+        let order = self.tracked(orig_cl_ord_id)?;
+        if order.lifecycle.state().is_terminal() {
+            return Err(Error::Validation(format!(
+                "order {} is already {:?}",
+                orig_cl_ord_id,
+                order.lifecycle.state()
+            )));
+        }
+        // 以推送确认的订单ID为准，尚未确认时使用请求中的 OrderID
+        let order_id: OrderId = order
+            .order_id
+            .as_deref()
+            .or(msg.get(tag::ORDER_ID))
+            .ok_or_else(|| {
+                Error::Validation(format!(
+                    "order {} has not been acknowledged yet",
+                    orig_cl_ord_id
+                ))
+            })?
+            .parse()?;
+        let (_, market) = self.market_for(&order.market)?;
+        let action = market.market.cancel_action(order_id)?;
+        let cloid = self.cloids[orig_cl_ord_id].clone();
+        if let Some(fix_order) = self.orders.get_mut(&cloid) {
+            fix_order.cancel = Some(cl_ord_id.to_string());
+        }
+        Ok(FixRequest::Cancel {
+            cl_ord_id: cl_ord_id.to_string(),
+            orig_cl_ord_id: orig_cl_ord_id.to_string(),
+            order_id,
+            action,
+        })
+    }
+
+    /// 下单交易已提交，记录交易摘要
+    pub fn submitted(&mut self, cl_ord_id: &str, digest: &str) -> Result<()> {
+        let cloid = self.cloid(cl_ord_id)?.to_string();
+        self.store.record_digest(&cloid, digest)
+    }
+
+    /// 下单交易提交失败或执行失败，标记为拒绝并返回 Rejected 回报
+    pub fn submit_failed(&mut self, cl_ord_id: &str, reason: &str, now: u64) -> Result<FixMessage> {
+        let cloid = self.cloid(cl_ord_id)?.to_string();
+        self.store.reject(&cloid, now)?;
+        let mut report = self.report(&cloid, "8", "8", now);
+        report.push(tag::ORD_REJ_REASON, 99);
+        report.push(tag::TEXT, reason);
+        Ok(report)
+    }
+
+    /// 按账户推送生成 ExecutionReport；不是经本网关提交的订单返回空列表
+    pub fn on_account_event(&mut self, event: &AccountEvent, now: u64) -> Result<Vec<FixMessage>> {
+        let update = match event {
+            AccountEvent::Fill(fill) => {
+                self.last_px.insert(fill.order_id.clone(), fill.price);
+                return Ok(Vec::new());
+            }
+            AccountEvent::Order(update) => update,
+        };
+        let Reconciled::Updated { cloid, state } = self.store.apply_update(update)? else {
+            return Ok(Vec::new());
+        };
+        let order = self
+            .store
+            .get(&cloid)
+            .expect("reconciled orders are tracked");
+        let (filled, price) = (order.lifecycle.filled(), order.price);
+        let Some(fix_order) = self.orders.get_mut(&cloid) else {
+            return Ok(Vec::new());
+        };
+        let new_ack = !fix_order.acked && state != OrderState::Rejected;
+        fix_order.acked |= new_ack;
+        let last = (filled > fix_order.cum_qty).then(|| {
+            let px = self.last_px.get(&update.order_id).copied();
+            (filled - fix_order.cum_qty, px.unwrap_or(price))
+        });
+
+        let mut reports = Vec::new();
+        if new_ack {
+            reports.push(self.report(&cloid, "0", "0", now));
+        }
+        if let Some((qty, px)) = last {
+            let fix_order = self.orders.get_mut(&cloid).unwrap();
+            fix_order.cum_qty = filled;
+            fix_order.notional += qty as u128 * px as u128;
+            let status = if state == OrderState::Filled {
+                "2"
+            } else {
+                "1"
+            };
+            let decimals = self.decimals(&cloid);
+            let mut report = self.report(&cloid, "F", status, now);
+            report.push(tag::LAST_QTY, format_units(qty.into(), decimals.size));
+            report.push(tag::LAST_PX, format_units(px.into(), decimals.price));
+            reports.push(report);
+        }
+        match state {
+            OrderState::Cancelled => reports.push(self.report(&cloid, "4", "4", now)),
+            OrderState::Expired => reports.push(self.report(&cloid, "C", "C", now)),
+            OrderState::Rejected => {
+                let mut report = self.report(&cloid, "8", "8", now);
+                report.push(tag::ORD_REJ_REASON, 99);
+                reports.push(report);
+            }
+            _ => {}
+        }
+        if state.is_terminal() {
+            self.last_px.remove(&update.order_id);
+        }
+        Ok(reports)
+    }
+
+    /// 无法处理的请求的拒绝回报：NewOrderSingle 回 Rejected 的 ExecutionReport，
+    /// OrderCancelRequest 回 OrderCancelReject，其他消息回 BusinessMessageReject
+    pub fn reject(&mut self, msg: &FixMessage, error: &Error, now: u64) -> FixMessage {
+        let echo = |mut reply: FixMessage, tags: &[u32]| {
+            for &tag in tags {
+                if let Some(value) = msg.get(tag) {
+                    reply.push(tag, value);
+                }
+            }
+            reply
+        };
+        match msg.msg_type() {
+            msg_type::NEW_ORDER_SINGLE => {
+                let reply = FixMessage::new(msg_type::EXECUTION_REPORT)
+                    .field(tag::ORDER_ID, "NONE")
+                    .field(tag::CL_ORD_ID, msg.get(tag::CL_ORD_ID).unwrap_or("NONE"))
+                    .field(tag::EXEC_ID, self.exec_id())
+                    .field(tag::EXEC_TYPE, "8")
+                    .field(tag::ORD_STATUS, "8");
+                echo(reply, &[tag::SYMBOL, tag::SIDE, tag::ORDER_QTY, tag::PRICE])
+                    .field(tag::LEAVES_QTY, 0)
+                    .field(tag::CUM_QTY, 0)
+                    .field(tag::AVG_PX, 0)
+                    .field(tag::TRANSACT_TIME, utc_timestamp(now))
+                    .field(tag::ORD_REJ_REASON, 99)
+                    .field(tag::TEXT, error)
+            }
+            msg_type::ORDER_CANCEL_REQUEST => {
+                let orig = msg
+                    .get(tag::ORIG_CL_ORD_ID)
+                    .and_then(|id| self.tracked(id).ok());
+                let (order_id, status) = match orig {
+                    Some(order) => (
+                        order.order_id.as_deref().unwrap_or("NONE"),
+                        ord_status(order.lifecycle.state()),
+                    ),
+                    None => ("NONE", "8"),
+                };
+                FixMessage::new(msg_type::ORDER_CANCEL_REJECT)
+                    .field(tag::ORDER_ID, order_id)
+                    .field(tag::CL_ORD_ID, msg.get(tag::CL_ORD_ID).unwrap_or("NONE"))
+                    .field(
+                        tag::ORIG_CL_ORD_ID,
+                        msg.get(tag::ORIG_CL_ORD_ID).unwrap_or("NONE"),
+                    )
+                    .field(tag::ORD_STATUS, status)
+                    .field(tag::CXL_REJ_RESPONSE_TO, 1)
+                    // 1 = Unknown order，99 = Other
+                    .field(tag::CXL_REJ_REASON, if orig.is_some() { 99 } else { 1 })
+                    .field(tag::TEXT, error)
+            }
+            other => FixMessage::new(msg_type::BUSINESS_MESSAGE_REJECT)
+                .field(tag::REF_MSG_TYPE, other)
+                // 3 = Unsupported Message Type
+                .field(tag::BUSINESS_REJECT_REASON, 3)
+                .field(tag::TEXT, error),
+        }
+    }
+
+    /// 按规范市场名查找，返回规范名与市场
+    fn market_for(&self, symbol: &str) -> Result<(String, &FixMarket)> {
+        let name = Symbol::parse(symbol)?.market();
+        match self.markets.get(&name) {
+            Some(market) => Ok((name, market)),
+            None => Err(Error::Validation(format!("unknown symbol: {}", symbol))),
+        }
+    }
+
+    fn cloid(&self, cl_ord_id: &str) -> Result<&str> {
+        self.cloids
+            .get(cl_ord_id)
+            .map(String::as_str)
+            .ok_or_else(|| Error::Validation(format!("unknown ClOrdID: {}", cl_ord_id)))
+    }
+
+    fn tracked(&self, cl_ord_id: &str) -> Result<&TrackedOrder> {
+        let cloid = self.cloid(cl_ord_id)?;
+        Ok(self.store.get(cloid).expect("fix orders are tracked"))
+    }
+
+    fn decimals(&self, cloid: &str) -> Decimals {
+        self.store
+            .get(cloid)
+            .and_then(|order| self.markets.get(&order.market))
+            .map(|market| market.decimals)
+            .unwrap_or_default()
+    }
+
+    fn exec_id(&mut self) -> String {
+        let id = self.next_exec_id;
+        self.next_exec_id += 1;
+        format!("E{}", id)
+    }
+
+    /// 订单当前进度的 ExecutionReport
+    fn report(&mut self, cloid: &str, exec_type: &str, status: &str, now: u64) -> FixMessage {
+        let exec_id = self.exec_id();
+        let decimals = self.decimals(cloid);
+        let order = self.store.get(cloid).expect("fix orders are tracked");
+        let fix_order = &self.orders[cloid];
+        let amount = order.lifecycle.amount();
+        let leaves = if matches!(status, "2" | "4" | "8" | "C") {
+            0
+        } else {
+            amount - fix_order.cum_qty.min(amount)
+        };
+        let avg_px = match fix_order.cum_qty {
+            0 => 0,
+            qty => fix_order.notional / qty as u128,
+        };
+        let size = |value: u64| format_units(value.into(), decimals.size);
+        let mut report = FixMessage::new(msg_type::EXECUTION_REPORT)
+            .field(tag::ORDER_ID, order.order_id.as_deref().unwrap_or("NONE"));
+        // 撤单回报的 ClOrdID 为撤单请求的 ClOrdID
+        match (&fix_order.cancel, exec_type) {
+            (Some(cancel), "4") => {
+                report.push(tag::CL_ORD_ID, cancel);
+                report.push(tag::ORIG_CL_ORD_ID, &fix_order.cl_ord_id);
+            }
+            _ => report.push(tag::CL_ORD_ID, &fix_order.cl_ord_id),
+        }
+        report
+            .field(tag::EXEC_ID, exec_id)
+            .field(tag::EXEC_TYPE, exec_type)
+            .field(tag::ORD_STATUS, status)
+            .field(tag::SYMBOL, &order.market)
+            .field(tag::SIDE, side_code(order.side))
+            .field(tag::ORDER_QTY, size(amount))
+            .field(tag::PRICE, format_units(order.price.into(), decimals.price))
+            .field(tag::LEAVES_QTY, size(leaves))
+            .field(tag::CUM_QTY, size(fix_order.cum_qty))
+            .field(tag::AVG_PX, format_units(avg_px, decimals.price))
+            .field(tag::TRANSACT_TIME, utc_timestamp(now))
+    }
+}
+
+fn side_code(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "1",
+        OrderSide::Sell => "2",
+    }
+}
+
+fn ord_status(state: OrderState) -> &'static str {
+    match state {
+        OrderState::PendingSubmit => "A",
+        OrderState::Acked => "0",
+        OrderState::PartiallyFilled => "1",
+        OrderState::Filled => "2",
+        OrderState::Cancelled => "4",
+        OrderState::Rejected => "8",
+        OrderState::Expired => "C",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::{Fill, OrderStatus, OrderUpdate};
+
+    const ORDER_ID: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+    fn gateway() -> FixGateway {
+        let decimals = Decimals {
+            price: 2,
+            size: 3,
+            fee: 2,
+        };
+        FixGateway::new().market(
+            Symbol::parse("BTC-USDC").unwrap(),
+            FixMarket::spot(
+                ObjectId([1; 32]),
+                ObjectId([2; 32]),
+                ObjectId([3; 32]),
+                decimals,
+            ),
+        )
+    }
+
+    fn new_order(cl_ord_id: &str) -> FixMessage {
+        FixMessage::new(msg_type::NEW_ORDER_SINGLE)
+            .field(tag::CL_ORD_ID, cl_ord_id)
+            .field(tag::SYMBOL, "BTC-USDC")
+            .field(tag::SIDE, 1)
+            .field(tag::ORDER_QTY, "1.5")
+            .field(tag::ORD_TYPE, 2)
+            .field(tag::PRICE, "100.25")
+            .field(tag::TIME_IN_FORCE, 3)
+    }
+
+    fn update(status: OrderStatus, filled: u64) -> AccountEvent {
+        AccountEvent::Order(OrderUpdate {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: ORDER_ID.to_string(),
+            side: OrderSide::Buy,
+            status,
+            price: 10_025,
+            amount: 1_500,
+            filled,
+            ts: 2,
+        })
+    }
+
+    fn fields(report: &FixMessage, tags: &[u32]) -> Vec<String> {
+        tags.iter()
+            .map(|&tag| report.get(tag).unwrap_or("-").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn new_order_single_becomes_place_order() {
+        let mut gateway = gateway();
+        let FixRequest::Place { cl_ord_id, order } = gateway.handle(&new_order("A1"), 1).unwrap()
+        else {
+            panic!("expected a place request");
+        };
+        assert_eq!(cl_ord_id, "A1");
+        assert_eq!(order.balance_id, ObjectId([3; 32]));
+        assert_eq!(
+            order.params,
+            PlaceOrderParams {
+                side: OrderSide::Buy,
+                amount: 1_500,
+                order_type: OrderType::Limit {
+                    tif: TimeInForce::Ioc
+                },
+                limit_price: 10_025,
+            }
+        );
+        assert_eq!(order.to_action().unwrap().params, order.params.encode());
+
+        let err = gateway.handle(&new_order("A1"), 1).unwrap_err();
+        assert!(err.to_string().contains("duplicate ClOrdID"));
+        let reject = gateway.reject(&new_order("A1"), &err, 1);
+        assert_eq!(
+            fields(
+                &reject,
+                &[
+                    tag::MSG_TYPE,
+                    tag::CL_ORD_ID,
+                    tag::EXEC_TYPE,
+                    tag::ORDER_QTY
+                ]
+            ),
+            ["8", "A1", "8", "1.5"]
+        );
+    }
+
+    #[test]
+    fn risk_limits_reject_new_orders() {
+        let mut limited = gateway().risk(RiskLimits {
+            max_open_orders: Some(1),
+            ..RiskLimits::default()
+        });
+        assert!(limited.handle(&new_order("A1"), 1).is_ok());
+        let err = limited.handle(&new_order("A2"), 2).unwrap_err();
+        assert!(matches!(&err, Error::Risk(violations) if violations.len() == 1));
+        assert!(limited.cloid("A2").is_err());
+        let reject = limited.reject(&new_order("A2"), &err, 2);
+        assert_eq!(
+            fields(&reject, &[tag::CL_ORD_ID, tag::EXEC_TYPE]),
+            ["A2", "8"]
+        );
+
+        // 1.5 × 100.25 的名义价值为 15_037_500 个最小单位
+        let notional = |limit| {
+            gateway()
+                .risk(RiskLimits {
+                    max_order_notional: Some(limit),
+                    ..RiskLimits::default()
+                })
+                .handle(&new_order("A1"), 1)
+        };
+        assert!(matches!(notional(15_000_000), Err(Error::Risk(_))));
+        assert!(notional(15_037_500).is_ok());
+    }
+
+    #[test]
+    fn symbols_match_in_any_convention() {
+        let order = |cl_ord_id: &str, symbol: &str| {
+            FixMessage::new(msg_type::NEW_ORDER_SINGLE)
+                .field(tag::CL_ORD_ID, cl_ord_id)
+                .field(tag::SYMBOL, symbol)
+                .field(tag::SIDE, 2)
+                .field(tag::ORDER_QTY, "1")
+                .field(tag::ORD_TYPE, 2)
+                .field(tag::PRICE, "100")
+        };
+        let mut gateway = gateway();
+        let FixRequest::Place { order: placed, .. } =
+            gateway.handle(&order("B1", "btcusdc"), 1).unwrap()
+        else {
+            panic!("expected a place request");
+        };
+        assert_eq!(placed.balance_id, ObjectId([2; 32]));
+        assert_eq!(gateway.store().orders().next().unwrap().market, "BTC-USDC");
+        assert!(gateway.handle(&order("B2", "ETHUSDC"), 1).is_err());
+    }
+
+    #[test]
+    fn account_events_become_execution_reports() {
+        let mut gateway = gateway();
+        gateway.handle(&new_order("A1"), 1).unwrap();
+        gateway
+            .on_account_event(
+                &AccountEvent::Fill(Fill {
+                    account: "0x01".to_string(),
+                    market: "BTC-USDC".to_string(),
+                    order_id: ORDER_ID.to_string(),
+                    side: OrderSide::Buy,
+                    price: 10_000,
+                    size: 500,
+                    fee: 1,
+                    is_maker: false,
+                    trade_id: Some(9),
+                    ts: 2,
+                }),
+                2,
+            )
+            .unwrap();
+
+        // 首条推送已部分成交：先回报 New，再回报 Trade
+        let reports = gateway
+            .on_account_event(&update(OrderStatus::PartiallyFilled, 500), 2)
+            .unwrap();
+        let tags = [
+            tag::EXEC_TYPE,
+            tag::ORD_STATUS,
+            tag::LAST_QTY,
+            tag::LAST_PX,
+            tag::CUM_QTY,
+            tag::LEAVES_QTY,
+            tag::AVG_PX,
+        ];
+        assert_eq!(reports.len(), 2);
+        assert_eq!(
+            fields(&reports[0], &tags),
+            ["0", "0", "-", "-", "0.000", "1.500", "0.00"]
+        );
+        assert_eq!(
+            fields(&reports[1], &tags),
+            ["F", "1", "0.500", "100.00", "0.500", "1.000", "100.00"]
+        );
+        assert_eq!(reports[1].get(tag::ORDER_ID), Some(ORDER_ID));
+        // 重复推送不再回报
+        assert!(gateway
+            .on_account_event(&update(OrderStatus::PartiallyFilled, 500), 3)
+            .unwrap()
+            .is_empty());
+
+        let cancel = FixMessage::new(msg_type::ORDER_CANCEL_REQUEST)
+            .field(tag::CL_ORD_ID, "A2")
+            .field(tag::ORIG_CL_ORD_ID, "A1");
+        let FixRequest::Cancel {
+            order_id, action, ..
+        } = gateway.handle(&cancel, 3).unwrap()
+        else {
+            panic!("expected a cancel request");
+        };
+        assert_eq!(order_id, OrderId([1; 32]));
+        assert_eq!(
+            action.action,
+            crate::transaction::action_name("ord_cancel").unwrap()
+        );
+
+        let reports = gateway
+            .on_account_event(&update(OrderStatus::Cancelled, 500), 4)
+            .unwrap();
+        assert_eq!(
+            fields(
+                &reports[0],
+                &[
+                    tag::EXEC_TYPE,
+                    tag::CL_ORD_ID,
+                    tag::ORIG_CL_ORD_ID,
+                    tag::LEAVES_QTY
+                ]
+            ),
+            ["4", "A2", "A1", "0.000"]
+        );
+        let err = gateway.handle(&cancel, 6).unwrap_err();
+        let reject = gateway.reject(&cancel, &err, 6);
+        assert_eq!(
+            fields(
+                &reject,
+                &[tag::MSG_TYPE, tag::ORD_STATUS, tag::CXL_REJ_REASON]
+            ),
+            ["9", "4", "99"]
+        );
+    }
+
+    #[test]
+    fn failed_submission_is_reported_as_rejected() {
+        let mut gateway = gateway();
+        gateway.handle(&new_order("A1"), 1).unwrap();
+        let report = gateway
+            .submit_failed("A1", "insufficient balance", 2)
+            .unwrap();
+        assert_eq!(
+            fields(&report, &[tag::EXEC_TYPE, tag::ORD_STATUS, tag::TEXT]),
+            ["8", "8", "insufficient balance"]
+        );
+        assert_eq!(
+            gateway.store().get("c0").unwrap().lifecycle.state(),
+            OrderState::Rejected
+        );
+        let unsupported = FixMessage::new("AE");
+        let err = gateway.handle(&unsupported, 3).unwrap_err();
+        assert_eq!(gateway.reject(&unsupported, &err, 3).msg_type(), "j");
+    }
+}
@@ -0,0 +1,287 @@
+//! FIX 4.4 订单网关
+//!
+//! 机构 OMS 以 FIX tag=value 消息下单：[`FixMessage`] 负责编解码并校验 BodyLength 与 CheckSum，
+//! [`FixGateway`] 把 NewOrderSingle（`35=D`）转换为 `ord_place` 操作、OrderCancelRequest（`35=F`）转换为
+//! `ord_cancel` 操作，并把账户推送转换为 ExecutionReport（`35=8`）发回 OMS。
+//!
+//! 这里只处理应用层消息；Logon、心跳、消息序号与重传等会话层由调用方的 FIX 引擎负责，
+//! 网关生成的消息不含 SenderCompID、TargetCompID、MsgSeqNum 与 SendingTime。
+
+mod gateway;
+
+use std::fmt;
+
+use crate::error::{Error, Result};
+
+pub use gateway::{FixGateway, FixMarket, FixRequest, DEFAULT_SLIPPAGE_BPS};
+
+/// 字段分隔符 SOH
+pub const SOH: u8 = 0x01;
+
+/// 默认的 BeginString
+pub const FIX_44: &str = "FIX.4.4";
+
+/// 网关使用的字段 tag
+pub mod tag {
+    pub const AVG_PX: u32 = 6;
+    pub const BEGIN_STRING: u32 = 8;
+    pub const BODY_LENGTH: u32 = 9;
+    pub const CHECK_SUM: u32 = 10;
+    pub const CL_ORD_ID: u32 = 11;
+    pub const CUM_QTY: u32 = 14;
+    pub const EXEC_ID: u32 = 17;
+    pub const LAST_PX: u32 = 31;
+    pub const LAST_QTY: u32 = 32;
+    pub const MSG_TYPE: u32 = 35;
+    pub const ORDER_ID: u32 = 37;
+    pub const ORDER_QTY: u32 = 38;
+    pub const ORD_STATUS: u32 = 39;
+    pub const ORD_TYPE: u32 = 40;
+    pub const ORIG_CL_ORD_ID: u32 = 41;
+    pub const PRICE: u32 = 44;
+    pub const SIDE: u32 = 54;
+    pub const SYMBOL: u32 = 55;
+    pub const TEXT: u32 = 58;
+    pub const TIME_IN_FORCE: u32 = 59;
+    pub const TRANSACT_TIME: u32 = 60;
+    pub const CXL_REJ_REASON: u32 = 102;
+    pub const ORD_REJ_REASON: u32 = 103;
+    pub const EXEC_TYPE: u32 = 150;
+    pub const LEAVES_QTY: u32 = 151;
+    pub const REF_MSG_TYPE: u32 = 372;
+    pub const BUSINESS_REJECT_REASON: u32 = 380;
+    pub const CXL_REJ_RESPONSE_TO: u32 = 434;
+}
+
+/// 网关处理的消息类型（tag 35）
+pub mod msg_type {
+    pub const EXECUTION_REPORT: &str = "8";
+    pub const ORDER_CANCEL_REJECT: &str = "9";
+    pub const NEW_ORDER_SINGLE: &str = "D";
+    pub const ORDER_CANCEL_REQUEST: &str = "F";
+    pub const BUSINESS_MESSAGE_REJECT: &str = "j";
+}
+
+/// 一条 FIX 消息
+///
+/// `fields` 按出现顺序保存 MsgType 及之后的字段，BeginString、BodyLength 与 CheckSum 在编码时生成。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixMessage {
+    begin_string: String,
+    fields: Vec<(u32, String)>,
+}
+
+impl FixMessage {
+    /// 指定 MsgType 的 FIX 4.4 消息
+    pub fn new(msg_type: &str) -> Self {
+        Self {
+            begin_string: FIX_44.to_string(),
+            fields: vec![(tag::MSG_TYPE, msg_type.to_string())],
+        }
+    }
+
+    /// 追加一个字段
+    pub fn field(mut self, tag: u32, value: impl fmt::Display) -> Self {
+        self.push(tag, value);
+        self
+    }
+
+    pub fn push(&mut self, tag: u32, value: impl fmt::Display) {
+        self.fields.push((tag, value.to_string()));
+    }
+
+    pub fn begin_string(&self) -> &str {
+        &self.begin_string
+    }
+
+    pub fn msg_type(&self) -> &str {
+        self.get(tag::MSG_TYPE).unwrap_or_default()
+    }
+
+    /// 字段的值，重复的 tag 取第一个
+    pub fn get(&self, tag: u32) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn fields(&self) -> &[(u32, String)] {
+        &self.fields
+    }
+
+    /// 必填字段，缺失或为空时报错
+    pub(crate) fn require(&self, tag: u32) -> Result<&str> {
+        match self.get(tag) {
+            Some(value) if !value.is_empty() => Ok(value),
+            _ => Err(Error::Validation(format!("missing tag {}", tag))),
+        }
+    }
+
+    /// 编码为 tag=value 字节流
+    pub fn encode(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (tag, value) in &self.fields {
+            body.extend_from_slice(format!("{}={}", tag, value).as_bytes());
+            body.push(SOH);
+        }
+        let mut out = format!(
+            "{}={}\x01{}={}\x01",
+            tag::BEGIN_STRING,
+            self.begin_string,
+            tag::BODY_LENGTH,
+            body.len()
+        )
+        .into_bytes();
+        out.extend_from_slice(&body);
+        let checksum = checksum(&out);
+        out.extend_from_slice(format!("{}={:03}\x01", tag::CHECK_SUM, checksum).as_bytes());
+        out
+    }
+
+    /// 解码一条完整的消息，校验 BodyLength 与 CheckSum
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let invalid = |msg: &str| Error::Decode(format!("fix: {}", msg));
+        let text = std::str::from_utf8(bytes).map_err(|_| invalid("message is not valid utf-8"))?;
+        let text = text
+            .strip_suffix('\x01')
+            .ok_or_else(|| invalid("message must end with SOH"))?;
+        let mut fields = Vec::new();
+        for field in text.split('\x01') {
+            let (tag, value) = field
+                .split_once('=')
+                .ok_or_else(|| invalid(&format!("malformed field: {}", field)))?;
+            let tag: u32 = tag
+                .parse()
+                .map_err(|_| invalid(&format!("malformed tag: {}", tag)))?;
+            fields.push((tag, value.to_string()));
+        }
+
+        let [(tag::BEGIN_STRING, begin_string), (tag::BODY_LENGTH, body_length), ..] =
+            fields.as_slice()
+        else {
+            return Err(invalid(
+                "message must start with BeginString and BodyLength",
+            ));
+        };
+        let Some((tag::CHECK_SUM, expected)) = fields.last() else {
+            return Err(invalid("message must end with CheckSum"));
+        };
+        // CheckSum 覆盖其前的全部字节，BodyLength 覆盖 BodyLength 之后到 CheckSum 之前的字节
+        let trailer = format!("{}={}\x01", tag::CHECK_SUM, expected).len();
+        let covered = &bytes[..bytes.len() - trailer];
+        let header = format!(
+            "{}={}\x01{}={}\x01",
+            tag::BEGIN_STRING,
+            begin_string,
+            tag::BODY_LENGTH,
+            body_length
+        )
+        .len();
+        if body_length.parse::<usize>().ok() != Some(covered.len() - header) {
+            return Err(invalid(&format!("body length mismatch: {}", body_length)));
+        }
+        if expected.parse::<u32>().ok() != Some(checksum(covered)) {
+            return Err(invalid(&format!("checksum mismatch: {}", expected)));
+        }
+
+        let begin_string = begin_string.clone();
+        fields.truncate(fields.len() - 1);
+        fields.drain(..2);
+        if fields.first().map(|(tag, _)| *tag) != Some(tag::MSG_TYPE) {
+            return Err(invalid("MsgType must follow BodyLength"));
+        }
+        Ok(Self {
+            begin_string,
+            fields,
+        })
+    }
+}
+
+/// 以 `|` 代替 SOH 输出，便于记录日志
+impl fmt::Display for FixMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = self.encode();
+        let text = String::from_utf8_lossy(&encoded);
+        write!(f, "{}", text.replace('\x01', "|"))
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().map(|&b| b as u32).sum::<u32>() % 256
+}
+
+/// 毫秒时间戳格式化为 FIX UTCTimestamp，例如 `20231114-22:13:20.123`
+pub fn utc_timestamp(ms: u64) -> String {
+    let secs = ms / 1000;
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    // 由 1970-01-01 起的天数推算公历日期
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}-{:02}:{:02}:{:02}.{:03}",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60,
+        ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoding_round_trips_with_length_and_checksum() {
+        let msg = FixMessage::new(msg_type::NEW_ORDER_SINGLE)
+            .field(tag::CL_ORD_ID, "a=1")
+            .field(tag::SIDE, 1);
+        let bytes = msg.encode();
+        assert_eq!(
+            String::from_utf8(bytes.clone()).unwrap(),
+            "8=FIX.4.4\x019=17\x0135=D\x0111=a=1\x0154=1\x0110=049\x01"
+        );
+        let decoded = FixMessage::decode(&bytes).unwrap();
+        assert_eq!(decoded, msg);
+        assert_eq!(decoded.get(tag::CL_ORD_ID), Some("a=1"));
+        assert_eq!(msg.to_string(), "8=FIX.4.4|9=17|35=D|11=a=1|54=1|10=049|");
+    }
+
+    #[test]
+    fn corrupted_messages_are_rejected() {
+        let bytes = FixMessage::new("D").field(tag::SIDE, 1).encode();
+        let mut tampered = bytes.clone();
+        let at = tampered.len() - 9;
+        tampered[at] = b'2';
+        assert!(FixMessage::decode(&tampered)
+            .unwrap_err()
+            .to_string()
+            .contains("checksum mismatch"));
+        let short = String::from_utf8(bytes)
+            .unwrap()
+            .replace("9=10", "9=11")
+            .into_bytes();
+        assert!(FixMessage::decode(&short)
+            .unwrap_err()
+            .to_string()
+            .contains("body length mismatch"));
+        assert!(FixMessage::decode(b"35=D\x01").is_err());
+    }
+
+    #[test]
+    fn timestamps_use_utc_calendar_dates() {
+        assert_eq!(utc_timestamp(0), "19700101-00:00:00.000");
+        assert_eq!(utc_timestamp(1_700_000_000_123), "20231114-22:13:20.123");
+        assert_eq!(utc_timestamp(951_782_400_000), "20000229-00:00:00.000");
+    }
+}
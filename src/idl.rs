@@ -0,0 +1,105 @@
+//! 链上类型的语言无关描述（`idl` feature）
+//!
+//! 由 serde-reflection 追踪各类型的 serde 实现生成，与 bincode 编码同源，Rust 类型调整后重新生成即可同步。
+//! `types` 以类型名为键：结构体按编码顺序列出字段，枚举以编码序号为键列出变体。
+//! 各语言 SDK 以仓库中的 `idl/lightpool.json` 为准生成或校验编解码代码，
+//! 该文件由 `lightpool gen-idl --out idl/lightpool.json` 生成。
+
+use std::path::Path;
+
+use serde::Serialize;
+use serde_reflection::{Registry, Samples, Tracer, TracerConfig};
+
+use crate::error::Result;
+use crate::order::{CancelOrderParams, OrderType, PlaceOrderParams, TimeInForce};
+use crate::transaction::SignedTransaction;
+use crate::types::OrderSide;
+
+/// IDL 文件格式的版本
+pub const IDL_VERSION: u32 = 1;
+
+/// IDL 文件
+#[derive(Debug, Clone, Serialize)]
+pub struct Idl {
+    pub version: u32,
+    /// 字节编码规则
+    pub encoding: &'static str,
+    pub types: Registry,
+}
+
+/// 追踪全部链上类型
+pub fn wire_idl() -> Idl {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let samples = Samples::new();
+    // 枚举须先单独追踪，才能记录全部变体
+    tracer
+        .trace_simple_type::<OrderSide>()
+        .and_then(|_| tracer.trace_simple_type::<TimeInForce>())
+        .and_then(|_| tracer.trace_simple_type::<OrderType>())
+        .and_then(|_| tracer.trace_type::<PlaceOrderParams>(&samples))
+        .and_then(|_| tracer.trace_type::<CancelOrderParams>(&samples))
+        .and_then(|_| tracer.trace_type::<SignedTransaction>(&samples))
+        .expect("wire types are traceable");
+    Idl {
+        version: IDL_VERSION,
+        encoding: "bincode 1: little-endian fixed-width integers, u32 enum tags, u64 lengths",
+        types: tracer.registry().expect("all enum variants are traced"),
+    }
+}
+
+/// 将 [`wire_idl`] 以格式化 JSON 写入 `path`
+pub fn export_idl(path: impl AsRef<Path>) -> Result<()> {
+    let mut text = serde_json::to_string_pretty(&wire_idl())?;
+    text.push('\n');
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    #[test]
+    fn committed_idl_matches_the_rust_types() {
+        let committed: Value = serde_json::from_str(include_str!("../idl/lightpool.json")).unwrap();
+        let current = serde_json::to_value(wire_idl()).unwrap();
+        assert!(
+            committed == current,
+            "idl/lightpool.json is stale; regenerate it with `lightpool gen-idl --out idl/lightpool.json`"
+        );
+    }
+
+    #[test]
+    fn describes_fields_in_encoding_order() {
+        let idl = wire_idl();
+        let names: Vec<&str> = idl.types.keys().map(String::as_str).collect();
+        for name in [
+            "Action",
+            "Address",
+            "CancelOrderParams",
+            "ObjectId",
+            "OrderId",
+            "OrderSide",
+            "OrderType",
+            "PlaceOrderParams",
+            "Signature",
+            "SignedTransaction",
+            "TimeInForce",
+            "Transaction",
+        ] {
+            assert!(names.contains(&name), "missing {}", name);
+        }
+        let place = serde_json::to_value(&idl.types["PlaceOrderParams"]).unwrap();
+        let fields: Vec<&str> = place["STRUCT"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| field.as_object().unwrap().keys().next().unwrap().as_str())
+            .collect();
+        assert_eq!(fields, ["side", "amount", "order_type", "limit_price"]);
+        let tif = serde_json::to_value(&idl.types["TimeInForce"]).unwrap();
+        assert_eq!(tif["ENUM"]["2"]["FOK"], "UNIT");
+    }
+}
@@ -0,0 +1,310 @@
+//! 口令加密的私钥文件与助记词
+//!
+//! 助记词遵循 BIP-39，私钥为助记词种子（空口令）按 SLIP-0010 派生的 ed25519 主私钥。
+//!
+//! 私钥以 scrypt 派生的密钥经 XChaCha20-Poly1305 加密保存，地址明文保存并作为附加认证数据，
+//! 篡改地址或口令错误都会导致解密失败。文件格式：
+//!
+//! ```json
+//! {"version":1,"address":"0x...","crypto":{"kdf":"scrypt","kdfparams":{"log_n":15,"r":8,"p":1,"salt":"..."},
+//!  "cipher":"xchacha20poly1305","nonce":"...","ciphertext":"..."}}
+//! ```
+
+use std::path::Path;
+
+use bip39::Mnemonic;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use hmac::{Hmac, Mac};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+
+use crate::crypto::{Ed25519Signer, Signer};
+use crate::error::{Error, Result};
+
+const VERSION: u32 = 1;
+const KDF: &str = "scrypt";
+const CIPHER: &str = "xchacha20poly1305";
+
+/// scrypt 参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    /// 约需 32 MiB 内存，普通机器上解锁耗时一秒以内
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KdfParams {
+    #[serde(flatten)]
+    scrypt: ScryptParams,
+    salt: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Crypto {
+    kdf: String,
+    kdfparams: KdfParams,
+    cipher: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// 加密的私钥文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    version: u32,
+    address: String,
+    crypto: Crypto,
+}
+
+/// 生成 12 或 24 个单词的英文助记词
+pub fn generate_mnemonic(words: usize) -> Result<String> {
+    let mut entropy = [0u8; 32];
+    let len = match words {
+        12 => 16,
+        24 => 32,
+        _ => {
+            return Err(Error::Validation(format!(
+                "mnemonic must have 12 or 24 words, got {}",
+                words
+            )))
+        }
+    };
+    OsRng.fill_bytes(&mut entropy[..len]);
+    let mnemonic = Mnemonic::from_entropy(&entropy[..len])
+        .map_err(|e| Error::Crypto(format!("mnemonic generation failed: {}", e)))?;
+    Ok(mnemonic.to_string())
+}
+
+/// 由助记词恢复签名者
+pub fn signer_from_mnemonic(phrase: &str) -> Result<Ed25519Signer> {
+    let mnemonic = Mnemonic::parse(phrase.trim())
+        .map_err(|e| Error::Crypto(format!("invalid mnemonic: {}", e)))?;
+    let (secret, _) = slip10_ed25519(&mnemonic.to_seed(""), &[]);
+    Ok(Ed25519Signer::from_secret_key_bytes(&secret))
+}
+
+/// SLIP-0010 硬化索引标志位
+const HARDENED: u32 = 0x8000_0000;
+
+/// 按 SLIP-0010 由种子沿 `path` 派生 ed25519 私钥与链码
+///
+/// ed25519 只支持硬化派生，`path` 中的索引均按硬化索引处理；空路径即主私钥 `m`。
+fn slip10_ed25519(seed: &[u8], path: &[u32]) -> ([u8; 32], [u8; 32]) {
+    let mut node = hmac_sha512(b"ed25519 seed", &[seed]);
+    for index in path {
+        let (key, chain_code) = node.split_at(32);
+        node = hmac_sha512(chain_code, &[&[0], key, &(index | HARDENED).to_be_bytes()]);
+    }
+    let mut secret = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    secret.copy_from_slice(&node[..32]);
+    chain_code.copy_from_slice(&node[32..]);
+    (secret, chain_code)
+}
+
+fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> [u8; 64] {
+    let mut mac = <Hmac<Sha512> as Mac>::new_from_slice(key).expect("hmac accepts any key size");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+fn derive_key(password: &str, salt: &[u8], params: ScryptParams) -> Result<[u8; 32]> {
+    let params = scrypt::Params::new(params.log_n, params.r, params.p, 32)
+        .map_err(|e| Error::Crypto(format!("invalid scrypt params: {}", e)))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| Error::Crypto(format!("key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>> {
+    hex::decode(value).map_err(|e| Error::Crypto(format!("invalid keystore {}: {}", field, e)))
+}
+
+impl Keystore {
+    /// 以默认 scrypt 参数加密私钥
+    pub fn encrypt(signer: &Ed25519Signer, password: &str) -> Result<Self> {
+        Self::encrypt_with(signer, password, ScryptParams::default())
+    }
+
+    /// 以指定 scrypt 参数加密私钥
+    pub fn encrypt_with(
+        signer: &Ed25519Signer,
+        password: &str,
+        params: ScryptParams,
+    ) -> Result<Self> {
+        let mut salt = [0u8; 32];
+        let mut nonce = [0u8; 24];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+        let address = signer.address().to_string();
+        let key = derive_key(password, &salt, params)?;
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&key))
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &signer.secret_key_bytes(),
+                    aad: address.as_bytes(),
+                },
+            )
+            .map_err(|_| Error::Crypto("keystore encryption failed".to_string()))?;
+        Ok(Self {
+            version: VERSION,
+            address,
+            crypto: Crypto {
+                kdf: KDF.to_string(),
+                kdfparams: KdfParams {
+                    scrypt: params,
+                    salt: hex::encode(salt),
+                },
+                cipher: CIPHER.to_string(),
+                nonce: hex::encode(nonce),
+                ciphertext: hex::encode(ciphertext),
+            },
+        })
+    }
+
+    /// 私钥对应的地址，无需口令
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// 以口令解密私钥
+    pub fn decrypt(&self, password: &str) -> Result<Ed25519Signer> {
+        if self.version != VERSION || self.crypto.kdf != KDF || self.crypto.cipher != CIPHER {
+            return Err(Error::Crypto(format!(
+                "unsupported keystore: version {}, kdf {}, cipher {}",
+                self.version, self.crypto.kdf, self.crypto.cipher
+            )));
+        }
+        let salt = decode_hex("salt", &self.crypto.kdfparams.salt)?;
+        let nonce = decode_hex("nonce", &self.crypto.nonce)?;
+        let ciphertext = decode_hex("ciphertext", &self.crypto.ciphertext)?;
+        if nonce.len() != 24 {
+            return Err(Error::Crypto("invalid keystore nonce length".to_string()));
+        }
+        let key = derive_key(password, &salt, self.crypto.kdfparams.scrypt)?;
+        let secret = XChaCha20Poly1305::new(Key::from_slice(&key))
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: self.address.as_bytes(),
+                },
+            )
+            .map_err(|_| Error::Crypto("wrong password or corrupted keystore".to_string()))?;
+        let secret: [u8; 32] = secret
+            .try_into()
+            .map_err(|_| Error::Crypto("invalid keystore secret length".to_string()))?;
+        Ok(Ed25519Signer::from_secret_key_bytes(&secret))
+    }
+
+    /// 读取私钥文件
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// 写入私钥文件，已存在的文件会被覆盖
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试用的低强度参数
+    const FAST: ScryptParams = ScryptParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn roundtrips_with_password() {
+        let signer = Ed25519Signer::from_secret_key_bytes(&[9u8; 32]);
+        let keystore = Keystore::encrypt_with(&signer, "hunter2", FAST).unwrap();
+        assert_eq!(keystore.address(), signer.address().to_string());
+
+        let json = serde_json::to_string(&keystore).unwrap();
+        let loaded: Keystore = serde_json::from_str(&json).unwrap();
+        let unlocked = loaded.decrypt("hunter2").unwrap();
+        assert_eq!(unlocked.secret_key_bytes(), [9u8; 32]);
+        assert!(loaded.decrypt("wrong").is_err());
+    }
+
+    #[test]
+    fn slip10_test_vector_1() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let (secret, chain_code) = slip10_ed25519(&seed, &[]);
+        assert_eq!(
+            hex::encode(secret),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            hex::encode(chain_code),
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb"
+        );
+
+        // m/0'
+        let (secret, chain_code) = slip10_ed25519(&seed, &[0]);
+        assert_eq!(
+            hex::encode(secret),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+        assert_eq!(
+            hex::encode(chain_code),
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69"
+        );
+    }
+
+    #[test]
+    fn mnemonic_derivation_is_deterministic() {
+        // BIP-39 测试向量：全零熵，空口令种子为 5eb00bbd…9e38e4
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let signer = signer_from_mnemonic(phrase).unwrap();
+        assert_eq!(
+            hex::encode(signer.secret_key_bytes()),
+            "560f9f3c94558b6551928bb781cf6092c6b8800b4fc544af2c9444ed126d51aa"
+        );
+        assert_eq!(
+            signer.secret_key_bytes(),
+            signer_from_mnemonic(&format!("  {}\n", phrase))
+                .unwrap()
+                .secret_key_bytes()
+        );
+        assert!(signer_from_mnemonic("abandon abandon").is_err());
+
+        let generated = generate_mnemonic(24).unwrap();
+        assert_eq!(generated.split_whitespace().count(), 24);
+        assert!(signer_from_mnemonic(&generated).is_ok());
+        assert!(generate_mnemonic(13).is_err());
+    }
+
+    #[test]
+    fn tampered_address_is_rejected() {
+        let signer = Ed25519Signer::from_secret_key_bytes(&[9u8; 32]);
+        let mut keystore = Keystore::encrypt_with(&signer, "pw", FAST).unwrap();
+        keystore.address = format!("0x{}", "00".repeat(32));
+        assert!(keystore.decrypt("pw").is_err());
+    }
+}
@@ -0,0 +1,61 @@
+//! LightPool Rust SDK
+//!
+//! 与 Python SDK 共享同一套链上数据格式，提供行情订阅等功能。
+
+pub mod arbitrage;
+pub mod candle;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod client;
+pub mod crypto;
+pub mod display;
+pub mod error;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod exchange;
+pub mod execution;
+#[cfg(all(feature = "export", not(target_arch = "wasm32")))]
+pub mod export;
+pub mod fees;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fix")]
+pub mod fix;
+#[cfg(feature = "idl")]
+pub mod idl;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+pub mod margin;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+#[cfg(all(feature = "node", not(target_arch = "wasm32")))]
+pub mod node;
+pub mod order;
+pub mod orderbook;
+#[cfg(all(feature = "otel", not(target_arch = "wasm32")))]
+pub mod otel;
+pub mod position;
+pub mod prelude;
+#[cfg(feature = "python")]
+mod python;
+pub mod risk;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod storage;
+pub mod symbol;
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+pub mod telemetry;
+pub mod transaction;
+pub mod types;
+pub mod vectors;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod ws;
+
+pub use candle::CandleBuilder;
+#[cfg(not(target_arch = "wasm32"))]
+pub use client::LightPoolClient;
+pub use error::{Error, Result};
+pub use orderbook::{L3Book, Orderbook};
+pub use position::{funding_payment, AccountingMode, Position, PositionTracker};
+pub use symbol::Symbol;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!("lightpool");
@@ -0,0 +1,177 @@
+//! 维持保证金与强平价格估算
+//!
+//! 强平条件为 `保证金 + 未实现盈亏 = 维持保证金率 × 持仓名义价值`。
+//! 维持保证金率按名义价值分档，档位取决于强平价格本身，因此迭代求解直到档位稳定。
+
+use serde::{Deserialize, Serialize};
+
+use crate::order::{Margin, MarginMode};
+use crate::position::Position;
+
+/// 维持保证金档位
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceTier {
+    /// 适用的最大持仓名义价值（含）
+    pub max_notional: f64,
+    /// 维持保证金率，例如 0.005 表示 0.5%
+    pub rate: f64,
+}
+
+/// 分档维持保证金表
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginSchedule {
+    tiers: Vec<MaintenanceTier>,
+}
+
+impl MarginSchedule {
+    /// 按 `max_notional` 排序档位；超出最高档的名义价值沿用最高档费率
+    pub fn new(mut tiers: Vec<MaintenanceTier>) -> Self {
+        tiers.sort_by(|a, b| a.max_notional.total_cmp(&b.max_notional));
+        Self { tiers }
+    }
+
+    /// 单一维持保证金率
+    pub fn flat(rate: f64) -> Self {
+        Self::new(vec![MaintenanceTier {
+            max_notional: f64::INFINITY,
+            rate,
+        }])
+    }
+
+    /// 名义价值对应的维持保证金率；空表返回 0
+    pub fn rate_for(&self, notional: f64) -> f64 {
+        self.tiers
+            .iter()
+            .find(|tier| notional <= tier.max_notional)
+            .or(self.tiers.last())
+            .map_or(0.0, |tier| tier.rate)
+    }
+
+    /// 持仓在指定价格下的维持保证金
+    pub fn maintenance_margin(&self, size: i64, price: f64) -> f64 {
+        let notional = size.unsigned_abs() as f64 * price;
+        notional * self.rate_for(notional)
+    }
+}
+
+/// 估算强平价格
+///
+/// 逐仓以 `持仓成本 / 杠杆` 为保证金，全仓以 `balance`（不含该仓位未实现盈亏的账户权益）为保证金。
+/// 空仓或保证金足以覆盖全部亏损（多头强平价不为正）时返回 None。
+pub fn liquidation_price(
+    position: &Position,
+    margin: &Margin,
+    balance: f64,
+    schedule: &MarginSchedule,
+) -> Option<f64> {
+    if position.size == 0 {
+        return None;
+    }
+    let collateral = match margin.mode {
+        MarginMode::Isolated => position.cost_basis / margin.leverage.max(1) as f64,
+        MarginMode::Cross => balance,
+    };
+    let qty = position.size.unsigned_abs() as f64;
+    let entry = position.avg_entry_price;
+    let solve = |rate: f64| {
+        if position.size > 0 {
+            (qty * entry - collateral) / (qty * (1.0 - rate))
+        } else {
+            (collateral + qty * entry) / (qty * (1.0 + rate))
+        }
+    };
+
+    let mut rate = schedule.rate_for(qty * entry);
+    let mut price = solve(rate);
+    for _ in 0..=schedule.tiers.len() {
+        let next = schedule.rate_for(qty * price.max(0.0));
+        if next == rate {
+            break;
+        }
+        rate = next;
+        price = solve(rate);
+    }
+    (price > 0.0).then_some(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(size: i64, entry: f64) -> Position {
+        Position {
+            market: "BTC-PERP".to_string(),
+            size,
+            avg_entry_price: entry,
+            cost_basis: size.unsigned_abs() as f64 * entry,
+            realized_pnl: 0.0,
+            unrealized_pnl: 0.0,
+            mark_price: None,
+            funding_pnl: 0.0,
+            last_funding_time: None,
+            fees: 0,
+            fee_cost: 0.0,
+            ts: 0,
+        }
+    }
+
+    fn isolated(leverage: u32) -> Margin {
+        Margin {
+            mode: MarginMode::Isolated,
+            leverage,
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    #[test]
+    fn isolated_long_and_short() {
+        let schedule = MarginSchedule::flat(0.005);
+        let long = liquidation_price(&position(1, 100.0), &isolated(10), 0.0, &schedule).unwrap();
+        assert!(close(long, 90.0 / 0.995));
+        let short = liquidation_price(&position(-1, 100.0), &isolated(10), 0.0, &schedule).unwrap();
+        assert!(close(short, 110.0 / 1.005));
+    }
+
+    #[test]
+    fn cross_uses_account_balance() {
+        let schedule = MarginSchedule::flat(0.0);
+        let cross = Margin {
+            mode: MarginMode::Cross,
+            leverage: 1,
+        };
+        let price = liquidation_price(&position(2, 100.0), &cross, 50.0, &schedule).unwrap();
+        assert!(close(price, 75.0));
+        // 保证金覆盖全部亏损的多头不会被强平
+        assert_eq!(
+            liquidation_price(&position(2, 100.0), &cross, 200.0, &schedule),
+            None
+        );
+        assert_eq!(
+            liquidation_price(&position(0, 0.0), &cross, 1.0, &schedule),
+            None
+        );
+    }
+
+    #[test]
+    fn tier_is_chosen_at_liquidation_notional() {
+        let schedule = MarginSchedule::new(vec![
+            MaintenanceTier {
+                max_notional: f64::INFINITY,
+                rate: 0.05,
+            },
+            MaintenanceTier {
+                max_notional: 1_000.0,
+                rate: 0.01,
+            },
+        ]);
+        assert_eq!(schedule.rate_for(500.0), 0.01);
+        assert_eq!(schedule.rate_for(5_000.0), 0.05);
+        // 空头开仓名义价值 1000 落在低档，强平价上涨后进入高档
+        let price = liquidation_price(&position(-10, 100.0), &isolated(5), 0.0, &schedule).unwrap();
+        assert!(close(price, 1_200.0 / (10.0 * 1.05)));
+        assert!(close(schedule.maintenance_margin(-10, 100.0), 10.0));
+    }
+}
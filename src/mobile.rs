@@ -0,0 +1,196 @@
+//! 移动端 Kotlin/Swift 绑定（`uniffi` feature）
+//!
+//! 以 UniFFI 导出下单参数、交易等核心类型与签名者，移动钱包在本地构造并签名订单，
+//! 由自身的网络层提交 [`signed_transaction_json`] 的结果。
+//! 地址与对象ID映射为 `0x` 前缀的十六进制字符串，签名映射为 64 字节数组；错误导出为 `LightpoolError`（Kotlin 中为 `LightpoolException`）。
+//! 生成绑定：
+//!
+//! ```text
+//! cargo build --release --lib --no-default-features --features uniffi
+//! cargo run --features uniffi-bindgen --bin uniffi-bindgen -- generate \
+//!     --library target/release/liblightpool.so --language kotlin --out-dir bindings
+//! ```
+//!
+//! 链接库须为 cdylib（Android）或 staticlib（iOS），可通过 `cargo rustc --crate-type` 指定。
+
+use std::sync::Arc;
+
+use crate::crypto::{self, Ed25519Signer, Signer as _};
+use crate::error::{Error, Result};
+use crate::order::{self, OrderRequest, PlaceOrderParams};
+use crate::transaction::{Action, Signature, SignedTransaction, Transaction, SPOT_CONTRACT};
+use crate::types::{Address, ObjectId, OrderId};
+
+uniffi::custom_type!(Address, String, {
+    try_lift: |value| Ok(value.parse()?),
+    lower: |address| address.to_string(),
+});
+
+uniffi::custom_type!(ObjectId, String, {
+    try_lift: |value| Ok(value.parse()?),
+    lower: |id| id.to_string(),
+});
+
+uniffi::custom_type!(OrderId, String, {
+    try_lift: |value| Ok(value.parse()?),
+    lower: |id| id.to_string(),
+});
+
+uniffi::custom_type!(Signature, Vec<u8>, {
+    try_lift: |bytes| {
+        let bytes: [u8; 64] = bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| Error::Validation(format!("signature must be 64 bytes, got {}", bytes.len())))?;
+        let mut part1 = [0u8; 32];
+        let mut part2 = [0u8; 32];
+        part1.copy_from_slice(&bytes[..32]);
+        part2.copy_from_slice(&bytes[32..]);
+        Ok(Signature { part1, part2 })
+    },
+    lower: |signature| signature.to_bytes().to_vec(),
+});
+
+/// 下单参数的 bincode 编码
+#[uniffi::export]
+pub fn encode_place_order(params: &PlaceOrderParams) -> Vec<u8> {
+    params.encode()
+}
+
+/// `ord_place` 操作，`market_address` 默认为现货合约
+#[uniffi::export(default(market_address = None))]
+pub fn place_order_action(
+    market_id: ObjectId,
+    balance_id: ObjectId,
+    params: PlaceOrderParams,
+    market_address: Option<Address>,
+) -> Result<Action> {
+    let mut request = OrderRequest::spot(market_id, balance_id, params);
+    if let Some(address) = market_address {
+        request.market_address = address;
+    }
+    request.to_action()
+}
+
+/// `ord_cancel` 操作，`market_address` 默认为现货合约
+#[uniffi::export(default(market_address = None))]
+pub fn cancel_order_action(
+    market_id: ObjectId,
+    order_id: OrderId,
+    market_address: Option<Address>,
+) -> Result<Action> {
+    order::cancel_action(market_address.unwrap_or(SPOT_CONTRACT), market_id, order_id)
+}
+
+/// 交易的签名内容（bincode 编码）
+#[uniffi::export]
+pub fn signing_bytes(transaction: &Transaction) -> Vec<u8> {
+    transaction.signing_bytes()
+}
+
+/// 已签名交易的 JSON，即 `submitTransaction` 的 `tx` 参数
+#[uniffi::export]
+pub fn signed_transaction_json(transaction: &SignedTransaction) -> Result<String> {
+    Ok(serde_json::to_string(transaction)?)
+}
+
+/// 校验公钥对应发送者地址且每个签名都有效
+#[uniffi::export]
+pub fn verify_transaction(transaction: &SignedTransaction, public_key: Vec<u8>) -> Result<bool> {
+    Ok(transaction.verify(&public_key_bytes(public_key)?))
+}
+
+/// 公钥对应的地址
+#[uniffi::export]
+pub fn address_from_public_key(public_key: Vec<u8>) -> Result<Address> {
+    Ok(crypto::address_from_public_key(&public_key_bytes(
+        public_key,
+    )?))
+}
+
+fn public_key_bytes(public_key: Vec<u8>) -> Result<[u8; 32]> {
+    public_key.try_into().map_err(|key: Vec<u8>| {
+        Error::Validation(format!("public key must be 32 bytes, got {}", key.len()))
+    })
+}
+
+/// Ed25519 签名者
+#[derive(uniffi::Object)]
+pub struct MobileSigner(Ed25519Signer);
+
+#[uniffi::export]
+impl MobileSigner {
+    /// 由 32 字节私钥创建
+    #[uniffi::constructor]
+    pub fn new(secret_key: Vec<u8>) -> Result<Arc<Self>> {
+        let secret_key: [u8; 32] = secret_key.try_into().map_err(|key: Vec<u8>| {
+            Error::Validation(format!("secret key must be 32 bytes, got {}", key.len()))
+        })?;
+        Ok(Arc::new(Self(Ed25519Signer::from_secret_key_bytes(
+            &secret_key,
+        ))))
+    }
+
+    /// 随机生成私钥
+    #[uniffi::constructor]
+    pub fn generate() -> Arc<Self> {
+        Arc::new(Self(Ed25519Signer::generate()))
+    }
+
+    /// 由十六进制私钥创建
+    #[uniffi::constructor]
+    pub fn from_hex(secret_key: String) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self(Ed25519Signer::from_hex(&secret_key)?)))
+    }
+
+    pub fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        self.0.public_key().to_vec()
+    }
+
+    /// 签名任意消息，返回 64 字节签名
+    pub fn sign(&self, message: Vec<u8>) -> Vec<u8> {
+        self.0.sign(&message).to_vec()
+    }
+
+    /// 以本签名者为发送者签名一组操作，`expiration` 默认不过期
+    #[uniffi::method(default(expiration = None))]
+    pub fn sign_actions(
+        &self,
+        actions: Vec<Action>,
+        expiration: Option<u64>,
+    ) -> Result<SignedTransaction> {
+        if actions.is_empty() {
+            return Err(Error::Validation(
+                "at least one action is required".to_string(),
+            ));
+        }
+        let transaction = Transaction {
+            sender: self.0.address(),
+            expiration: expiration.unwrap_or(u64::MAX),
+            actions,
+        };
+        Ok(transaction.sign(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderSide;
+
+    #[test]
+    fn signer_signs_actions_built_from_core_types() {
+        let params = PlaceOrderParams::limit(OrderSide::Sell, 1_000, 42_000);
+        let action =
+            place_order_action(ObjectId([1; 32]), ObjectId([2; 32]), params, None).unwrap();
+        let signer = MobileSigner::new(vec![9; 32]).unwrap();
+        let signed = signer.sign_actions(vec![action], None).unwrap();
+        assert!(verify_transaction(&signed, signer.public_key()).unwrap());
+        assert_eq!(signed.transaction.sender, signer.address());
+        assert!(signer.sign_actions(Vec::new(), None).is_err());
+        assert!(MobileSigner::new(vec![0; 31]).is_err());
+    }
+}
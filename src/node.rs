@@ -0,0 +1,305 @@
+//! Node.js 原生模块（`node` feature）
+//!
+//! 以 napi-rs 导出 RPC 客户端与编码、签名接口，供 TypeScript 网关层直接复用 SDK。
+//! 地址与对象ID为 `0x` 前缀的十六进制字符串，字节内容为 `Buffer`，数量与价格为 `bigint`；
+//! 操作、交易与 RPC 结果以与节点 JSON 相同结构的对象传递，失败时 Promise 以 `Error` 拒绝。
+//! 构建方式：
+//!
+//! ```text
+//! cargo rustc --release --lib --no-default-features --features node --crate-type cdylib
+//! cp target/release/liblightpool.so lightpool.node
+//! ```
+//!
+//! ```js
+//! const { Client, Signer, encodeLimitOrder, placeOrderAction } = require("./lightpool.node");
+//! const params = encodeLimitOrder("buy", 5000000n, 50000000000n);
+//! const tx = signer.signTransaction([placeOrderAction(marketId, balanceId, params)]);
+//! const result = await new Client("http://localhost:26300").submitTransaction(tx);
+//! ```
+
+use std::time::Duration;
+
+use napi::bindgen_prelude::{BigInt, Buffer};
+use napi_derive::napi;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::client::LightPoolClient;
+use crate::crypto::{Ed25519Signer, Signer as _};
+use crate::error::{Error, Result};
+use crate::order::{self, OrderRequest, OrderType, PlaceOrderParams, TimeInForce};
+use crate::transaction::{Action, SignedTransaction, Transaction, SPOT_CONTRACT};
+use crate::types::{Address, OrderSide};
+
+/// 未指定时的 RPC 请求超时
+const DEFAULT_TIMEOUT_MS: u32 = 30_000;
+
+impl From<Error> for napi::Error {
+    fn from(error: Error) -> Self {
+        napi::Error::from_reason(error.to_string())
+    }
+}
+
+fn u64_arg(value: BigInt, what: &str) -> Result<u64> {
+    match value.get_u64() {
+        (false, value, true) => Ok(value),
+        _ => Err(Error::Validation(format!("{} must fit in u64", what))),
+    }
+}
+
+fn side(value: &str) -> Result<OrderSide> {
+    match value.to_ascii_lowercase().as_str() {
+        "buy" => Ok(OrderSide::Buy),
+        "sell" => Ok(OrderSide::Sell),
+        _ => Err(Error::Validation(format!("invalid order side: {}", value))),
+    }
+}
+
+fn tif(value: &str) -> Result<TimeInForce> {
+    match value.to_ascii_uppercase().as_str() {
+        "GTC" => Ok(TimeInForce::Gtc),
+        "IOC" => Ok(TimeInForce::Ioc),
+        "FOK" => Ok(TimeInForce::Fok),
+        _ => Err(Error::Validation(format!(
+            "invalid time in force: {}",
+            value
+        ))),
+    }
+}
+
+fn params(data: &[u8]) -> Result<PlaceOrderParams> {
+    bincode::deserialize(data).map_err(|e| Error::Decode(format!("invalid order params: {}", e)))
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// 限价单参数的 bincode 编码，`tif` 为 GTC（默认）、IOC 或 FOK
+#[napi]
+pub fn encode_limit_order(
+    side: String,
+    amount: BigInt,
+    limit_price: BigInt,
+    tif: Option<String>,
+) -> napi::Result<Buffer> {
+    let mut params = PlaceOrderParams::limit(
+        self::side(&side)?,
+        u64_arg(amount, "amount")?,
+        u64_arg(limit_price, "limit price")?,
+    );
+    if let Some(tif) = tif {
+        params.order_type = OrderType::Limit {
+            tif: self::tif(&tif)?,
+        };
+    }
+    Ok(params.encode().into())
+}
+
+/// 市价单参数的 bincode 编码，`limit_price` 为滑点参考价
+#[napi]
+pub fn encode_market_order(
+    side: String,
+    amount: BigInt,
+    limit_price: BigInt,
+    slippage_bps: u32,
+) -> napi::Result<Buffer> {
+    let params = PlaceOrderParams::market(
+        self::side(&side)?,
+        u64_arg(amount, "amount")?,
+        u64_arg(limit_price, "limit price")?,
+        slippage_bps.into(),
+    );
+    Ok(params.encode().into())
+}
+
+/// `ord_place` 操作，`market_address` 默认为现货合约
+#[napi]
+pub fn place_order_action(
+    market_id: String,
+    balance_id: String,
+    params: Buffer,
+    market_address: Option<String>,
+) -> napi::Result<Value> {
+    let mut request = OrderRequest::spot(
+        market_id.parse()?,
+        balance_id.parse()?,
+        self::params(&params)?,
+    );
+    if let Some(address) = market_address {
+        request.market_address = address.parse()?;
+    }
+    Ok(to_value(&request.to_action()?)?)
+}
+
+/// `ord_cancel` 操作，`market_address` 默认为现货合约
+#[napi]
+pub fn cancel_order_action(
+    market_id: String,
+    order_id: String,
+    market_address: Option<String>,
+) -> napi::Result<Value> {
+    let market_address = match market_address {
+        Some(address) => address.parse()?,
+        None => SPOT_CONTRACT,
+    };
+    let action = order::cancel_action(market_address, market_id.parse()?, order_id.parse()?)?;
+    Ok(to_value(&action)?)
+}
+
+/// Ed25519 签名者
+#[napi(js_name = "Signer")]
+pub struct NodeSigner(Ed25519Signer);
+
+#[napi]
+impl NodeSigner {
+    /// 由 32 字节私钥创建
+    #[napi(constructor)]
+    pub fn new(secret_key: Buffer) -> napi::Result<Self> {
+        let secret_key: [u8; 32] = secret_key[..].try_into().map_err(|_| {
+            Error::Validation(format!(
+                "secret key must be 32 bytes, got {}",
+                secret_key.len()
+            ))
+        })?;
+        Ok(Self(Ed25519Signer::from_secret_key_bytes(&secret_key)))
+    }
+
+    /// 随机生成私钥
+    #[napi(factory)]
+    pub fn generate() -> Self {
+        Self(Ed25519Signer::generate())
+    }
+
+    /// 由十六进制私钥创建
+    #[napi(factory)]
+    pub fn from_hex(secret_key: String) -> napi::Result<Self> {
+        Ok(Self(Ed25519Signer::from_hex(&secret_key)?))
+    }
+
+    #[napi(getter)]
+    pub fn address(&self) -> String {
+        self.0.address().to_string()
+    }
+
+    #[napi(getter)]
+    pub fn public_key(&self) -> Buffer {
+        self.0.public_key().to_vec().into()
+    }
+
+    /// 签名任意消息，返回 64 字节签名
+    #[napi]
+    pub fn sign(&self, message: Buffer) -> Buffer {
+        self.0.sign(&message).to_vec().into()
+    }
+
+    /// 以本签名者为发送者签名交易，`expiration` 默认不过期
+    #[napi]
+    pub fn sign_transaction(
+        &self,
+        actions: Vec<Value>,
+        expiration: Option<BigInt>,
+    ) -> napi::Result<Value> {
+        let actions = actions
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<serde_json::Result<Vec<Action>>>()
+            .map_err(Error::from)?;
+        if actions.is_empty() {
+            return Err(Error::Validation("at least one action is required".to_string()).into());
+        }
+        let expiration = match expiration {
+            Some(expiration) => u64_arg(expiration, "expiration")?,
+            None => u64::MAX,
+        };
+        let signed = Transaction {
+            sender: self.0.address(),
+            expiration,
+            actions,
+        }
+        .sign(&self.0);
+        Ok(to_value(&signed)?)
+    }
+}
+
+/// RPC 客户端
+#[napi(js_name = "Client")]
+pub struct NodeClient(LightPoolClient);
+
+#[napi]
+impl NodeClient {
+    /// `timeout_ms` 默认 30 秒
+    #[napi(constructor)]
+    pub fn new(base_url: String, timeout_ms: Option<u32>) -> napi::Result<Self> {
+        let timeout = Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).into());
+        Ok(Self(LightPoolClient::new(&base_url, timeout)?))
+    }
+
+    /// 订单簿快照，`depth` 默认 10 档
+    #[napi]
+    pub async fn get_order_book(
+        &self,
+        market_id: String,
+        depth: Option<u32>,
+    ) -> napi::Result<Value> {
+        let book = self
+            .0
+            .get_order_book(&market_id, depth.unwrap_or(10))
+            .await?;
+        Ok(to_value(&book)?)
+    }
+
+    /// 最近成交，`limit` 默认 10 笔
+    #[napi]
+    pub async fn get_trades(&self, market_id: String, limit: Option<u32>) -> napi::Result<Value> {
+        let trades = self.0.get_trades(&market_id, limit.unwrap_or(10)).await?;
+        Ok(to_value(&trades)?)
+    }
+
+    #[napi]
+    pub async fn get_orders(
+        &self,
+        address: String,
+        market_id: Option<String>,
+    ) -> napi::Result<Value> {
+        let address: Address = address.parse()?;
+        let orders = self.0.get_orders(&address, market_id.as_deref()).await?;
+        Ok(Value::from(orders))
+    }
+
+    #[napi]
+    pub async fn get_balances(&self, address: String) -> napi::Result<Value> {
+        let address: Address = address.parse()?;
+        Ok(to_value(&self.0.get_balances(&address).await?)?)
+    }
+
+    #[napi]
+    pub async fn get_mark_price(&self, market_id: String) -> napi::Result<Value> {
+        Ok(to_value(&self.0.get_mark_price(&market_id).await?)?)
+    }
+
+    #[napi]
+    pub async fn get_market_info(&self, market_id: String) -> napi::Result<Value> {
+        Ok(to_value(&self.0.get_market_info(&market_id).await?)?)
+    }
+
+    /// 交易回执，节点尚未收录时为 `null`
+    #[napi]
+    pub async fn get_transaction_receipt(&self, digest: String) -> napi::Result<Value> {
+        Ok(to_value(&self.0.get_transaction_receipt(&digest).await?)?)
+    }
+
+    /// 提交 [`NodeSigner::sign_transaction`] 签名的交易
+    #[napi]
+    pub async fn submit_transaction(&self, tx: Value) -> napi::Result<Value> {
+        let tx: SignedTransaction = serde_json::from_value(tx).map_err(Error::from)?;
+        Ok(to_value(&self.0.submit_transaction(&tx).await?)?)
+    }
+
+    /// 在节点上模拟执行已签名交易，不上链
+    #[napi]
+    pub async fn simulate_transaction(&self, tx: Value) -> napi::Result<Value> {
+        let tx: SignedTransaction = serde_json::from_value(tx).map_err(Error::from)?;
+        Ok(to_value(&self.0.simulate_transaction(&tx).await?)?)
+    }
+}
@@ -0,0 +1,193 @@
+//! 账户成交回报的本地记录
+
+use std::collections::HashSet;
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::report::{format_units, write_csv, CsvColumn, CsvOptions, Decimals};
+use crate::error::Result;
+use crate::ws::{AccountEvent, Fill};
+
+/// [`FillHistory::export_csv`] 的列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FillColumn {
+    Ts,
+    Account,
+    Market,
+    OrderId,
+    TradeId,
+    Side,
+    Price,
+    Size,
+    /// 成交金额（价格 × 数量）
+    Notional,
+    Fee,
+    /// `maker` 或 `taker`
+    Liquidity,
+}
+
+impl CsvColumn for FillColumn {
+    const ALL: &'static [Self] = &[
+        FillColumn::Ts,
+        FillColumn::Account,
+        FillColumn::Market,
+        FillColumn::OrderId,
+        FillColumn::TradeId,
+        FillColumn::Side,
+        FillColumn::Price,
+        FillColumn::Size,
+        FillColumn::Notional,
+        FillColumn::Fee,
+        FillColumn::Liquidity,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            FillColumn::Ts => "ts",
+            FillColumn::Account => "account",
+            FillColumn::Market => "market",
+            FillColumn::OrderId => "order_id",
+            FillColumn::TradeId => "trade_id",
+            FillColumn::Side => "side",
+            FillColumn::Price => "price",
+            FillColumn::Size => "size",
+            FillColumn::Notional => "notional",
+            FillColumn::Fee => "fee",
+            FillColumn::Liquidity => "liquidity",
+        }
+    }
+}
+
+fn fill_cell(fill: &Fill, column: FillColumn, decimals: &Decimals) -> String {
+    match column {
+        FillColumn::Ts => fill.ts.to_string(),
+        FillColumn::Account => fill.account.clone(),
+        FillColumn::Market => fill.market.clone(),
+        FillColumn::OrderId => fill.order_id.clone(),
+        FillColumn::TradeId => fill.trade_id.map(|id| id.to_string()).unwrap_or_default(),
+        FillColumn::Side => format!("{:?}", fill.side),
+        FillColumn::Price => format_units(fill.price.into(), decimals.price),
+        FillColumn::Size => format_units(fill.size.into(), decimals.size),
+        FillColumn::Notional => {
+            format_units(fill.price as u128 * fill.size as u128, decimals.notional())
+        }
+        FillColumn::Fee => format_units(fill.fee.into(), decimals.fee),
+        FillColumn::Liquidity => if fill.is_maker { "maker" } else { "taker" }.to_string(),
+    }
+}
+
+/// 按到达顺序保存的成交回报；带 `trade_id` 的重复推送只记一次
+#[derive(Debug, Default)]
+pub struct FillHistory {
+    fills: Vec<Fill>,
+    seen: HashSet<(String, u64)>,
+}
+
+impl FillHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一笔成交，重复推送时返回 false
+    pub fn record(&mut self, fill: Fill) -> bool {
+        if let Some(trade_id) = fill.trade_id {
+            if !self.seen.insert((fill.order_id.clone(), trade_id)) {
+                return false;
+            }
+        }
+        self.fills.push(fill);
+        true
+    }
+
+    /// 记录账户事件中的成交，订单更新忽略
+    pub fn apply(&mut self, event: &AccountEvent) -> bool {
+        match event {
+            AccountEvent::Fill(fill) => self.record(fill.clone()),
+            AccountEvent::Order(_) => false,
+        }
+    }
+
+    pub fn fills(&self) -> &[Fill] {
+        &self.fills
+    }
+
+    pub fn len(&self) -> usize {
+        self.fills.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fills.is_empty()
+    }
+
+    /// 以 CSV 写出全部成交，按时间排序，同一时间保持到达顺序
+    pub fn export_csv(&self, writer: impl Write, options: &CsvOptions<FillColumn>) -> Result<()> {
+        let mut fills: Vec<&Fill> = self.fills.iter().collect();
+        fills.sort_by_key(|fill| fill.ts);
+        write_csv(writer, options, fills, |fill, column, decimals| {
+            fill_cell(fill, column, decimals)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderSide;
+
+    fn fill(trade_id: Option<u64>, price: u64, size: u64, is_maker: bool, ts: u64) -> Fill {
+        Fill {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: "0xaa".to_string(),
+            side: OrderSide::Sell,
+            price,
+            size,
+            fee: 1_250,
+            is_maker,
+            trade_id,
+            ts,
+        }
+    }
+
+    #[test]
+    fn duplicate_trade_ids_are_recorded_once() {
+        let mut history = FillHistory::new();
+        assert!(history.record(fill(Some(7), 100, 1, true, 1)));
+        assert!(!history.apply(&AccountEvent::Fill(fill(Some(7), 100, 1, true, 1))));
+        assert!(history.record(fill(None, 100, 1, true, 2)));
+        assert!(history.record(fill(None, 100, 1, true, 2)));
+        assert_eq!(history.len(), 3);
+    }
+
+    #[test]
+    fn exports_notional_and_liquidity() {
+        let mut history = FillHistory::new();
+        history.record(fill(Some(2), 50_000_000_000, 5_000_000, false, 20));
+        history.record(fill(Some(1), 49_990_000_000, 100_000, true, 10));
+
+        let options = CsvOptions::columns([
+            FillColumn::Ts,
+            FillColumn::TradeId,
+            FillColumn::Price,
+            FillColumn::Size,
+            FillColumn::Notional,
+            FillColumn::Fee,
+            FillColumn::Liquidity,
+        ])
+        .decimals(Decimals {
+            price: 6,
+            size: 6,
+            fee: 6,
+        });
+        let mut out = Vec::new();
+        history.export_csv(&mut out, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "ts,trade_id,price,size,notional,fee,liquidity\n\
+             10,1,49990.000000,0.100000,4999.000000000000,0.001250,maker\n\
+             20,2,50000.000000,5.000000,250000.000000000000,0.001250,taker\n"
+        );
+    }
+}
@@ -0,0 +1,390 @@
+//! 下单参数与订单状态
+//!
+//! [`PlaceOrderParams`] 与 [`CancelOrderParams`] 以 bincode 编码后作为现货合约
+//! `ord_place` / `ord_cancel` 操作的参数。链上下单参数没有保证金字段，
+//! 协议目前不支持保证金下单：带保证金设置的订单在本地即被拒绝，不会静默丢弃杠杆。
+//! [`Margin`] 与 [`AccountLimits`] 仅用于本地的强平估算与限额查询。
+//! 提交后的订单状态由 [`OrderLifecycle`] 跟踪，[`OrderStore`] 负责与账户推送对账，
+//! 成交回报记入 [`FillHistory`]；二者均可导出 CSV 用于记账与对账。
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::transaction::{Action, SPOT_CONTRACT};
+use crate::types::{Address, ObjectId, OrderId, OrderSide};
+
+mod fills;
+mod report;
+mod rounding;
+mod state;
+mod store;
+
+pub use fills::{FillColumn, FillHistory};
+pub use report::{format_units, parse_columns, parse_units, CsvColumn, CsvOptions, Decimals};
+pub use rounding::{round_price_to_tick, round_size_to_lot, PriceRounding, SizeRounding};
+pub use state::{OrderEvent, OrderLifecycle, OrderState};
+pub use store::{OrderColumn, OrderStore, Reconciled, TrackedOrder};
+
+/// 订单有效期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum TimeInForce {
+    /// 撤单前一直有效
+    #[serde(rename = "GTC")]
+    Gtc,
+    /// 立即成交，剩余部分撤销
+    #[serde(rename = "IOC")]
+    Ioc,
+    /// 全部成交否则撤销
+    #[serde(rename = "FOK")]
+    Fok,
+}
+
+/// 订单类型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum OrderType {
+    Limit {
+        tif: TimeInForce,
+    },
+    /// 市价单，`slippage` 为相对参考价允许的最大滑点（基点）
+    Market {
+        slippage: u64,
+    },
+    Trigger {
+        trigger_price: u64,
+        is_market: bool,
+        trigger_type: u8,
+    },
+}
+
+/// 现货合约下单参数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PlaceOrderParams {
+    pub side: OrderSide,
+    pub amount: u64,
+    pub order_type: OrderType,
+    pub limit_price: u64,
+}
+
+impl PlaceOrderParams {
+    /// GTC 限价单
+    pub fn limit(side: OrderSide, amount: u64, limit_price: u64) -> Self {
+        Self {
+            side,
+            amount,
+            order_type: OrderType::Limit {
+                tif: TimeInForce::Gtc,
+            },
+            limit_price,
+        }
+    }
+
+    /// 市价单，`limit_price` 为滑点参考价
+    pub fn market(side: OrderSide, amount: u64, limit_price: u64, slippage_bps: u64) -> Self {
+        Self {
+            side,
+            amount,
+            order_type: OrderType::Market {
+                slippage: slippage_bps,
+            },
+            limit_price,
+        }
+    }
+
+    /// bincode 编码
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("order params are always serializable")
+    }
+}
+
+/// 撤单参数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CancelOrderParams {
+    pub order_id: OrderId,
+}
+
+/// 保证金模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarginMode {
+    /// 全仓，账户余额共同作为保证金
+    Cross,
+    /// 逐仓，仅占用该仓位的保证金
+    Isolated,
+}
+
+/// 订单的保证金设置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Margin {
+    pub mode: MarginMode,
+    /// 杠杆倍数，1 表示不加杠杆
+    pub leverage: u32,
+}
+
+/// 账户的保证金交易限额
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountLimits {
+    /// 最大杠杆倍数
+    #[serde(default = "default_max_leverage")]
+    pub max_leverage: u32,
+    /// 已开通的保证金模式
+    #[serde(default)]
+    pub margin_modes: Vec<MarginMode>,
+}
+
+fn default_max_leverage() -> u32 {
+    1
+}
+
+impl Default for AccountLimits {
+    fn default() -> Self {
+        Self {
+            max_leverage: default_max_leverage(),
+            margin_modes: Vec::new(),
+        }
+    }
+}
+
+impl AccountLimits {
+    /// 校验保证金设置；不加杠杆的订单总是允许
+    pub fn check(&self, margin: &Margin) -> Result<()> {
+        if margin.leverage == 0 {
+            return Err(Error::Validation("leverage must be at least 1".to_string()));
+        }
+        if margin.leverage == 1 {
+            return Ok(());
+        }
+        if !self.margin_modes.contains(&margin.mode) {
+            return Err(Error::Validation(format!(
+                "margin mode {:?} is not enabled for this account",
+                margin.mode
+            )));
+        }
+        if margin.leverage > self.max_leverage {
+            return Err(Error::Validation(format!(
+                "leverage {} exceeds account limit {}",
+                margin.leverage, self.max_leverage
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 一笔下单请求
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderRequest {
+    /// 市场合约地址，现货市场为 [`SPOT_CONTRACT`]
+    pub market_address: Address,
+    pub market_id: ObjectId,
+    /// 扣款的余额对象：买单为报价币，卖单为基础币
+    pub balance_id: ObjectId,
+    pub params: PlaceOrderParams,
+    /// 保证金设置；None 表示现货下单
+    pub margin: Option<Margin>,
+}
+
+impl OrderRequest {
+    /// 现货下单请求
+    pub fn spot(market_id: ObjectId, balance_id: ObjectId, params: PlaceOrderParams) -> Self {
+        Self {
+            market_address: SPOT_CONTRACT,
+            market_id,
+            balance_id,
+            params,
+            margin: None,
+        }
+    }
+
+    /// 设置保证金模式与杠杆
+    pub fn with_margin(mut self, mode: MarginMode, leverage: u32) -> Self {
+        self.margin = Some(Margin { mode, leverage });
+        self
+    }
+
+    /// 生成 `ord_place` 操作
+    ///
+    /// [`PlaceOrderParams`] 的链上编码没有保证金字段，带保证金设置的订单
+    /// 无论市场均返回 [`Error::Validation`]，避免以现货方式成交。
+    pub fn to_action(&self) -> Result<Action> {
+        if self.margin.is_some() {
+            return Err(Error::Validation(
+                "margin not supported by protocol".to_string(),
+            ));
+        }
+        Action::new(
+            self.market_address,
+            "ord_place",
+            vec![self.market_id, self.balance_id],
+            self.params.encode(),
+        )
+    }
+}
+
+/// 生成 `ord_cancel` 操作
+pub fn cancel_action(
+    market_address: Address,
+    market_id: ObjectId,
+    order_id: OrderId,
+) -> Result<Action> {
+    let params = bincode::serialize(&CancelOrderParams { order_id })
+        .expect("cancel params are always serializable");
+    Action::new(market_address, "ord_cancel", vec![market_id], params)
+}
+
+/// 交易对对应的链上市场与扣款余额对象
+///
+/// FIX 网关、交易所适配器与命令行的市场登记表共用此描述；余额对象为 None 时不能向该方向下单。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Market {
+    /// 市场合约地址，现货市场为 [`SPOT_CONTRACT`]
+    pub market_address: Address,
+    pub market_id: ObjectId,
+    /// 卖单扣款的基础币余额对象
+    pub base_balance: Option<ObjectId>,
+    /// 买单扣款的报价币余额对象
+    pub quote_balance: Option<ObjectId>,
+}
+
+impl Market {
+    /// 现货市场
+    pub fn spot(market_id: ObjectId, base_balance: ObjectId, quote_balance: ObjectId) -> Self {
+        Self {
+            market_address: SPOT_CONTRACT,
+            market_id,
+            base_balance: Some(base_balance),
+            quote_balance: Some(quote_balance),
+        }
+    }
+
+    /// 按订单方向取扣款的余额对象：买单为报价币，卖单为基础币
+    pub fn balance_for(&self, side: OrderSide) -> Result<ObjectId> {
+        let (balance, field) = match side {
+            OrderSide::Buy => (self.quote_balance, "quote_balance"),
+            OrderSide::Sell => (self.base_balance, "base_balance"),
+        };
+        balance
+            .ok_or_else(|| Error::Validation(format!("market {} has no {}", self.market_id, field)))
+    }
+
+    /// 在此市场下单的请求，不带保证金设置
+    pub fn order_request(&self, params: PlaceOrderParams) -> Result<OrderRequest> {
+        Ok(OrderRequest {
+            market_address: self.market_address,
+            market_id: self.market_id,
+            balance_id: self.balance_for(params.side)?,
+            params,
+            margin: None,
+        })
+    }
+
+    /// 撤销此市场订单的 `ord_cancel` 操作
+    pub fn cancel_action(&self, order_id: OrderId) -> Result<Action> {
+        cancel_action(self.market_address, self.market_id, order_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_order_encoding_matches_chain() {
+        let params = PlaceOrderParams::limit(OrderSide::Sell, 5_000_000, 50_000_000_000);
+        assert_eq!(
+            params.encode(),
+            vec![
+                1, 0, 0, 0, 64, 75, 76, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 116, 59, 164, 11,
+                0, 0, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn leverage_is_checked_against_limits() {
+        let spot = AccountLimits::default();
+        let unlevered = Margin {
+            mode: MarginMode::Cross,
+            leverage: 1,
+        };
+        assert!(spot.check(&unlevered).is_ok());
+        assert!(spot
+            .check(&Margin {
+                leverage: 3,
+                ..unlevered
+            })
+            .is_err());
+
+        let limits: AccountLimits =
+            serde_json::from_str(r#"{"maxLeverage":5,"marginModes":["cross"]}"#).unwrap();
+        let cross = Margin {
+            mode: MarginMode::Cross,
+            leverage: 5,
+        };
+        assert!(limits.check(&cross).is_ok());
+        assert!(limits
+            .check(&Margin {
+                leverage: 6,
+                ..cross
+            })
+            .is_err());
+        assert!(limits
+            .check(&Margin {
+                mode: MarginMode::Isolated,
+                ..cross
+            })
+            .is_err());
+        assert!(limits
+            .check(&Margin {
+                leverage: 0,
+                ..cross
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn markets_debit_the_balance_of_the_paid_asset() {
+        let market = Market::spot(ObjectId([1; 32]), ObjectId([2; 32]), ObjectId([3; 32]));
+        let buy = PlaceOrderParams::limit(OrderSide::Buy, 1_000, 50_000);
+        let order = market.order_request(buy.clone()).unwrap();
+        assert_eq!(order.market_id, ObjectId([1; 32]));
+        assert_eq!(order.balance_id, ObjectId([3; 32]));
+        assert_eq!(
+            market.balance_for(OrderSide::Sell).unwrap(),
+            ObjectId([2; 32])
+        );
+
+        let buy_only = Market {
+            base_balance: None,
+            ..market
+        };
+        assert!(buy_only.order_request(buy).is_ok());
+        let err = buy_only.balance_for(OrderSide::Sell).unwrap_err();
+        assert!(err.to_string().contains("no base_balance"));
+    }
+
+    #[test]
+    fn margin_is_rejected_by_protocol() {
+        let params = PlaceOrderParams::limit(OrderSide::Buy, 1_000_000, 50_000_000_000);
+        let spot = OrderRequest::spot(ObjectId([1; 32]), ObjectId([2; 32]), params.clone());
+        assert!(spot.to_action().is_ok());
+        assert!(matches!(
+            spot.clone().with_margin(MarginMode::Cross, 1).to_action(),
+            Err(Error::Validation(_))
+        ));
+
+        let other = OrderRequest {
+            market_address: Address([9; 32]),
+            ..spot
+        };
+        let action = other.to_action().unwrap();
+        assert_eq!(action.contract, Address([9; 32]));
+        assert_eq!(action.params, params.encode());
+        assert!(matches!(
+            other.with_margin(MarginMode::Isolated, 5).to_action(),
+            Err(Error::Validation(message)) if message == "margin not supported by protocol"
+        ));
+    }
+}
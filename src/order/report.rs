@@ -0,0 +1,186 @@
+//! 订单与成交的 CSV 导出
+//!
+//! 列由 [`CsvOptions::columns`] 选择并按给定顺序输出，表头为列名（如 `order_id`）；
+//! 价格、数量与手续费按 [`Decimals`] 换算为定长小数，便于在电子表格与对账脚本中直接比对。
+
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// 链上整数的小数位数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Decimals {
+    pub price: u32,
+    pub size: u32,
+    /// 手续费（计价资产）的小数位数
+    pub fee: u32,
+}
+
+impl Decimals {
+    /// 成交金额（价格 × 数量）的小数位数
+    pub fn notional(&self) -> u32 {
+        self.price + self.size
+    }
+}
+
+/// CSV 导出选项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions<C> {
+    /// 输出的列及其顺序
+    pub columns: Vec<C>,
+    pub decimals: Decimals,
+    pub delimiter: u8,
+}
+
+impl<C: CsvColumn> Default for CsvOptions<C> {
+    /// 全部列、逗号分隔，金额不换算小数
+    fn default() -> Self {
+        Self {
+            columns: C::ALL.to_vec(),
+            decimals: Decimals::default(),
+            delimiter: b',',
+        }
+    }
+}
+
+impl<C> CsvOptions<C> {
+    /// 指定列，其余选项取默认值
+    pub fn columns(columns: impl IntoIterator<Item = C>) -> Self {
+        Self {
+            columns: columns.into_iter().collect(),
+            decimals: Decimals::default(),
+            delimiter: b',',
+        }
+    }
+
+    pub fn decimals(mut self, decimals: Decimals) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+}
+
+/// 可导出的列
+pub trait CsvColumn: Copy + 'static {
+    /// 全部列，按默认顺序
+    const ALL: &'static [Self];
+
+    /// 表头中的列名
+    fn name(&self) -> &'static str;
+}
+
+/// 解析逗号分隔的列名列表，如 `ts,market,price`
+pub fn parse_columns<C: CsvColumn>(list: &str) -> Result<Vec<C>> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            C::ALL
+                .iter()
+                .find(|column| column.name() == name)
+                .copied()
+                .ok_or_else(|| Error::Validation(format!("unknown column: {}", name)))
+        })
+        .collect()
+}
+
+/// 把链上整数按 `decimals` 位小数格式化，保留末尾的 0
+pub fn format_units(value: u128, decimals: u32) -> String {
+    if decimals == 0 {
+        return value.to_string();
+    }
+    let scale = 10u128.pow(decimals);
+    format!(
+        "{}.{:0width$}",
+        value / scale,
+        value % scale,
+        width = decimals as usize
+    )
+}
+
+/// 把十进制字符串按 `decimals` 位小数换算为整数，不允许截断
+pub fn parse_units(value: &str, decimals: u32) -> Result<u64> {
+    let invalid = || Error::Validation(format!("invalid amount: {}", value));
+    let (int, frac) = value.split_once('.').unwrap_or((value, ""));
+    if int.is_empty() && frac.is_empty()
+        || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    if frac.len() > decimals as usize {
+        return Err(Error::Validation(format!(
+            "{} has more than {} decimal places",
+            value, decimals
+        )));
+    }
+    let digits = format!("{}{:0<width$}", int, frac, width = decimals as usize);
+    digits.parse::<u64>().map_err(|_| invalid())
+}
+
+/// 需要时加引号，内部的引号写两次
+fn quote(field: &str, delimiter: u8) -> String {
+    let needs_quotes = field
+        .bytes()
+        .any(|b| b == delimiter || b == b'"' || b == b'\n' || b == b'\r');
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// 写出表头与各行，`cell` 给出一行中某列的文本
+pub(crate) fn write_csv<C: CsvColumn, R>(
+    mut writer: impl Write,
+    options: &CsvOptions<C>,
+    rows: impl IntoIterator<Item = R>,
+    cell: impl Fn(&R, C, &Decimals) -> String,
+) -> Result<()> {
+    let delimiter = (options.delimiter as char).to_string();
+    let header: Vec<String> = options
+        .columns
+        .iter()
+        .map(|column| quote(column.name(), options.delimiter))
+        .collect();
+    writeln!(writer, "{}", header.join(&delimiter))?;
+    for row in rows {
+        let line: Vec<String> = options
+            .columns
+            .iter()
+            .map(|&column| quote(&cell(&row, column, &options.decimals), options.delimiter))
+            .collect();
+        writeln!(writer, "{}", line.join(&delimiter))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_fixed_decimals() {
+        assert_eq!(format_units(50_000_000_000, 6), "50000.000000");
+        assert_eq!(format_units(5, 3), "0.005");
+        assert_eq!(format_units(1234, 0), "1234");
+        assert_eq!(
+            format_units(u64::MAX as u128 * u64::MAX as u128, 12),
+            "340282366920938463426481119.284349108225"
+        );
+    }
+
+    #[test]
+    fn quotes_delimiters_and_quotes() {
+        assert_eq!(quote("BTC-USDC", b','), "BTC-USDC");
+        assert_eq!(quote("a,b", b','), "\"a,b\"");
+        assert_eq!(quote("a,b", b';'), "a,b");
+        assert_eq!(quote("say \"hi\"", b','), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,110 @@
+//! 价格与数量取整
+//!
+//! 链上拒绝不在最小价格变动单位（tick）或最小数量单位（lot）整数倍上的订单。
+//! 价格取整方向以订单方向区分：偏被动（买单向下、卖单向上）不会比原价更激进，
+//! 偏主动（买单向上、卖单向下）保证不低于原价的成交意愿。
+
+use crate::error::{Error, Result};
+use crate::types::OrderSide;
+
+/// 价格取整方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceRounding {
+    /// 远离对手方：买单向下、卖单向上
+    Passive,
+    /// 靠近对手方：买单向上、卖单向下
+    Aggressive,
+}
+
+/// 数量取整方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeRounding {
+    Down,
+    Up,
+}
+
+fn round(value: u64, step: u64, up: bool) -> Option<u64> {
+    let floor = value - value % step;
+    if up && floor != value {
+        floor.checked_add(step)
+    } else {
+        Some(floor)
+    }
+}
+
+/// 将价格取整到 `tick` 的整数倍；`tick` 为 0、取整结果为 0 或溢出时返回错误
+pub fn round_price_to_tick(
+    price: u64,
+    tick: u64,
+    side: OrderSide,
+    rounding: PriceRounding,
+) -> Result<u64> {
+    if tick == 0 {
+        return Err(Error::Validation("tick size must be positive".to_string()));
+    }
+    let up = matches!(
+        (side, rounding),
+        (OrderSide::Buy, PriceRounding::Aggressive) | (OrderSide::Sell, PriceRounding::Passive)
+    );
+    match round(price, tick, up) {
+        Some(rounded) if rounded > 0 => Ok(rounded),
+        _ => Err(Error::Validation(format!(
+            "price {} cannot be rounded to tick {}",
+            price, tick
+        ))),
+    }
+}
+
+/// 将数量取整到 `lot` 的整数倍；`lot` 为 0、取整结果为 0 或溢出时返回错误
+pub fn round_size_to_lot(size: u64, lot: u64, rounding: SizeRounding) -> Result<u64> {
+    if lot == 0 {
+        return Err(Error::Validation("lot size must be positive".to_string()));
+    }
+    match round(size, lot, rounding == SizeRounding::Up) {
+        Some(rounded) if rounded > 0 => Ok(rounded),
+        _ => Err(Error::Validation(format!(
+            "size {} cannot be rounded to lot {}",
+            size, lot
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_direction_depends_on_side() {
+        let round = |side, rounding| round_price_to_tick(1_005, 10, side, rounding).unwrap();
+        assert_eq!(round(OrderSide::Buy, PriceRounding::Passive), 1_000);
+        assert_eq!(round(OrderSide::Buy, PriceRounding::Aggressive), 1_010);
+        assert_eq!(round(OrderSide::Sell, PriceRounding::Passive), 1_010);
+        assert_eq!(round(OrderSide::Sell, PriceRounding::Aggressive), 1_000);
+    }
+
+    #[test]
+    fn aligned_values_are_unchanged() {
+        for rounding in [PriceRounding::Passive, PriceRounding::Aggressive] {
+            assert_eq!(
+                round_price_to_tick(1_000, 10, OrderSide::Sell, rounding).unwrap(),
+                1_000
+            );
+        }
+        assert_eq!(round_size_to_lot(300, 100, SizeRounding::Up).unwrap(), 300);
+    }
+
+    #[test]
+    fn degenerate_inputs_are_rejected() {
+        assert!(round_price_to_tick(5, 10, OrderSide::Buy, PriceRounding::Passive).is_err());
+        assert!(round_price_to_tick(5, 0, OrderSide::Buy, PriceRounding::Passive).is_err());
+        assert!(
+            round_price_to_tick(u64::MAX, 10, OrderSide::Buy, PriceRounding::Aggressive).is_err()
+        );
+        assert!(round_size_to_lot(50, 100, SizeRounding::Down).is_err());
+        assert_eq!(round_size_to_lot(50, 100, SizeRounding::Up).unwrap(), 100);
+        assert_eq!(
+            round_size_to_lot(250, 100, SizeRounding::Down).unwrap(),
+            200
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! 订单生命周期状态机
+//!
+//! 网关推送可能乱序或重复：成交回报可能先于确认到达，因此 `PendingSubmit`
+//! 可以直接进入成交状态；累计成交量不增加的成交事件视为重复并忽略。
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::ws::{OrderStatus, OrderUpdate};
+
+/// 订单状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OrderState {
+    /// 已签名提交，尚未收到确认
+    PendingSubmit,
+    Acked,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Rejected,
+    Expired,
+}
+
+impl OrderState {
+    /// 是否为终态
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderState::Filled | OrderState::Cancelled | OrderState::Rejected | OrderState::Expired
+        )
+    }
+
+    /// 是否允许转移到 `next`
+    pub fn can_transition(&self, next: OrderState) -> bool {
+        use OrderState::*;
+        match self {
+            PendingSubmit => next != PendingSubmit,
+            Acked => matches!(next, PartiallyFilled | Filled | Cancelled | Expired),
+            PartiallyFilled => matches!(next, PartiallyFilled | Filled | Cancelled | Expired),
+            Filled | Cancelled | Rejected | Expired => false,
+        }
+    }
+}
+
+/// 订单事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderEvent {
+    Acked,
+    /// 累计成交数量
+    Filled {
+        filled: u64,
+    },
+    Cancelled,
+    Rejected,
+    Expired,
+}
+
+impl From<&OrderUpdate> for OrderEvent {
+    fn from(update: &OrderUpdate) -> Self {
+        match update.status {
+            OrderStatus::Acked => OrderEvent::Acked,
+            OrderStatus::PartiallyFilled | OrderStatus::Filled => OrderEvent::Filled {
+                filled: update.filled,
+            },
+            OrderStatus::Cancelled => OrderEvent::Cancelled,
+            OrderStatus::Rejected => OrderEvent::Rejected,
+            OrderStatus::Expired => OrderEvent::Expired,
+        }
+    }
+}
+
+/// 单个订单的状态与累计成交
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderLifecycle {
+    state: OrderState,
+    amount: u64,
+    filled: u64,
+}
+
+impl OrderLifecycle {
+    /// 新提交的订单
+    pub fn new(amount: u64) -> Self {
+        Self {
+            state: OrderState::PendingSubmit,
+            amount,
+            filled: 0,
+        }
+    }
+
+    /// 从提交起依次回放事件重建状态
+    pub fn replay<'a>(
+        amount: u64,
+        events: impl IntoIterator<Item = &'a OrderEvent>,
+    ) -> Result<Self> {
+        let mut order = Self::new(amount);
+        for event in events {
+            order.apply(event)?;
+        }
+        Ok(order)
+    }
+
+    pub fn state(&self) -> OrderState {
+        self.state
+    }
+
+    pub fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    /// 累计成交数量
+    pub fn filled(&self) -> u64 {
+        self.filled
+    }
+
+    /// 未成交数量
+    pub fn remaining(&self) -> u64 {
+        self.amount - self.filled
+    }
+
+    /// 应用事件，返回新状态；非法转移返回错误且不修改状态
+    pub fn apply(&mut self, event: &OrderEvent) -> Result<OrderState> {
+        let (next, filled) = match *event {
+            OrderEvent::Acked if self.state == OrderState::Acked => return Ok(self.state),
+            // 确认晚于成交到达
+            OrderEvent::Acked if self.filled > 0 && !self.state.is_terminal() => {
+                return Ok(self.state)
+            }
+            OrderEvent::Acked => (OrderState::Acked, self.filled),
+            OrderEvent::Filled { filled } if filled <= self.filled => return Ok(self.state),
+            OrderEvent::Filled { filled } if filled > self.amount => {
+                return Err(Error::Validation(format!(
+                    "filled {} exceeds order amount {}",
+                    filled, self.amount
+                )))
+            }
+            OrderEvent::Filled { filled } if filled == self.amount => (OrderState::Filled, filled),
+            OrderEvent::Filled { filled } => (OrderState::PartiallyFilled, filled),
+            OrderEvent::Cancelled => (OrderState::Cancelled, self.filled),
+            OrderEvent::Rejected => (OrderState::Rejected, self.filled),
+            OrderEvent::Expired => (OrderState::Expired, self.filled),
+        };
+        if !self.state.can_transition(next) {
+            return Err(Error::Validation(format!(
+                "invalid order transition {:?} -> {:?}",
+                self.state, next
+            )));
+        }
+        self.state = next;
+        self.filled = filled;
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn happy_path_through_partial_fills() {
+        let mut order = OrderLifecycle::new(10);
+        assert_eq!(order.apply(&OrderEvent::Acked).unwrap(), OrderState::Acked);
+        assert_eq!(
+            order.apply(&OrderEvent::Filled { filled: 4 }).unwrap(),
+            OrderState::PartiallyFilled
+        );
+        assert_eq!(order.remaining(), 6);
+        assert_eq!(
+            order.apply(&OrderEvent::Filled { filled: 10 }).unwrap(),
+            OrderState::Filled
+        );
+        assert!(order.state().is_terminal());
+    }
+
+    #[test]
+    fn terminal_states_reject_further_events() {
+        let mut order =
+            OrderLifecycle::replay(10, &[OrderEvent::Acked, OrderEvent::Cancelled]).unwrap();
+        assert!(order.apply(&OrderEvent::Filled { filled: 1 }).is_err());
+        assert!(order.apply(&OrderEvent::Acked).is_err());
+        assert_eq!(order.state(), OrderState::Cancelled);
+
+        let mut rejected = OrderLifecycle::replay(10, &[OrderEvent::Rejected]).unwrap();
+        assert!(rejected.apply(&OrderEvent::Cancelled).is_err());
+        // 已确认的订单不能再被拒绝
+        let mut acked = OrderLifecycle::replay(10, &[OrderEvent::Acked]).unwrap();
+        assert!(acked.apply(&OrderEvent::Rejected).is_err());
+    }
+
+    #[test]
+    fn out_of_order_and_duplicate_events() {
+        let events = [
+            OrderEvent::Filled { filled: 3 },
+            OrderEvent::Acked,
+            OrderEvent::Filled { filled: 3 },
+            OrderEvent::Filled { filled: 2 },
+            OrderEvent::Cancelled,
+        ];
+        let order = OrderLifecycle::replay(10, &events).unwrap();
+        assert_eq!(order.state(), OrderState::Cancelled);
+        assert_eq!(order.filled(), 3);
+    }
+
+    #[test]
+    fn overfill_is_rejected() {
+        let mut order = OrderLifecycle::new(5);
+        assert!(order.apply(&OrderEvent::Filled { filled: 6 }).is_err());
+        assert_eq!(order.state(), OrderState::PendingSubmit);
+    }
+
+    #[test]
+    fn updates_map_to_events() {
+        let update = OrderUpdate {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: "00".to_string(),
+            side: crate::types::OrderSide::Buy,
+            status: OrderStatus::PartiallyFilled,
+            price: 100,
+            amount: 10,
+            filled: 7,
+            ts: 0,
+        };
+        assert_eq!(OrderEvent::from(&update), OrderEvent::Filled { filled: 7 });
+
+        let expired = OrderUpdate {
+            status: OrderStatus::Expired,
+            ..update
+        };
+        assert_eq!(OrderEvent::from(&expired), OrderEvent::Expired);
+        let mut order = OrderLifecycle::new(10);
+        order.apply(&OrderEvent::Filled { filled: 7 }).unwrap();
+        assert_eq!(
+            order.apply(&OrderEvent::from(&expired)).unwrap(),
+            OrderState::Expired
+        );
+        assert_eq!(order.filled(), 7);
+    }
+}
@@ -0,0 +1,490 @@
+//! 本地订单簿记
+//!
+//! 链上订单没有客户端订单号（cloid），[`OrderStore`] 在提交时分配 cloid，
+//! 收到账户频道的首条订单更新时按市场、方向、价格与数量匹配最早提交且尚未关联的订单，
+//! 此后按交易所订单ID路由。无法匹配的推送记为未知订单，超时未确认的提交记为孤儿订单。
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::report::{format_units, write_csv, CsvColumn, CsvOptions, Decimals};
+use super::state::{OrderEvent, OrderLifecycle, OrderState};
+use crate::error::Result;
+use crate::types::OrderSide;
+use crate::ws::{AccountEvent, OrderUpdate};
+
+/// 本地跟踪的订单
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackedOrder {
+    /// 客户端订单号
+    pub cloid: String,
+    /// 交易所订单ID，收到首条订单更新后填入
+    pub order_id: Option<String>,
+    pub market: String,
+    pub side: OrderSide,
+    pub price: u64,
+    pub lifecycle: OrderLifecycle,
+    /// 提交交易的摘要
+    pub digest: Option<String>,
+    /// 提交时间（毫秒）
+    pub submitted_at: u64,
+    /// 最后更新时间（毫秒）
+    pub updated_at: u64,
+}
+
+/// [`OrderStore::export_csv`] 的列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderColumn {
+    Cloid,
+    OrderId,
+    Market,
+    Side,
+    Price,
+    Amount,
+    Filled,
+    Remaining,
+    State,
+    Digest,
+    SubmittedAt,
+    UpdatedAt,
+}
+
+impl CsvColumn for OrderColumn {
+    const ALL: &'static [Self] = &[
+        OrderColumn::Cloid,
+        OrderColumn::OrderId,
+        OrderColumn::Market,
+        OrderColumn::Side,
+        OrderColumn::Price,
+        OrderColumn::Amount,
+        OrderColumn::Filled,
+        OrderColumn::Remaining,
+        OrderColumn::State,
+        OrderColumn::Digest,
+        OrderColumn::SubmittedAt,
+        OrderColumn::UpdatedAt,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            OrderColumn::Cloid => "cloid",
+            OrderColumn::OrderId => "order_id",
+            OrderColumn::Market => "market",
+            OrderColumn::Side => "side",
+            OrderColumn::Price => "price",
+            OrderColumn::Amount => "amount",
+            OrderColumn::Filled => "filled",
+            OrderColumn::Remaining => "remaining",
+            OrderColumn::State => "state",
+            OrderColumn::Digest => "digest",
+            OrderColumn::SubmittedAt => "submitted_at",
+            OrderColumn::UpdatedAt => "updated_at",
+        }
+    }
+}
+
+impl TrackedOrder {
+    fn csv_cell(&self, column: OrderColumn, decimals: &Decimals) -> String {
+        let size = |value: u64| format_units(value.into(), decimals.size);
+        match column {
+            OrderColumn::Cloid => self.cloid.clone(),
+            OrderColumn::OrderId => self.order_id.clone().unwrap_or_default(),
+            OrderColumn::Market => self.market.clone(),
+            OrderColumn::Side => format!("{:?}", self.side),
+            OrderColumn::Price => format_units(self.price.into(), decimals.price),
+            OrderColumn::Amount => size(self.lifecycle.amount()),
+            OrderColumn::Filled => size(self.lifecycle.filled()),
+            OrderColumn::Remaining => size(self.lifecycle.remaining()),
+            OrderColumn::State => format!("{:?}", self.lifecycle.state()),
+            OrderColumn::Digest => self.digest.clone().unwrap_or_default(),
+            OrderColumn::SubmittedAt => self.submitted_at.to_string(),
+            OrderColumn::UpdatedAt => self.updated_at.to_string(),
+        }
+    }
+}
+
+/// 账户事件的匹配结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reconciled {
+    /// 已更新本地订单
+    Updated { cloid: String, state: OrderState },
+    /// 重复推送，状态未变
+    Unchanged { cloid: String },
+    /// 不是本 store 提交的订单
+    Unknown,
+}
+
+/// 按 cloid 与交易所订单ID索引的订单簿记，可选持久化到 JSON 文件
+#[derive(Debug, Default)]
+pub struct OrderStore {
+    orders: HashMap<String, TrackedOrder>,
+    by_order_id: HashMap<String, String>,
+    unknown: Vec<OrderUpdate>,
+    next_cloid: u64,
+    path: Option<PathBuf>,
+}
+
+impl OrderStore {
+    /// 仅保存在内存中的 store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 打开持久化 store，文件不存在时创建空 store；每次修改后整体重写文件
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut store = Self {
+            path: Some(path.clone()),
+            ..Self::default()
+        };
+        if path.exists() {
+            let orders: Vec<TrackedOrder> = serde_json::from_slice(&fs::read(&path)?)?;
+            for order in orders {
+                store.index(order);
+            }
+        }
+        Ok(store)
+    }
+
+    /// 由已保存的订单重建内存 store，cloid 从已有的最大值之后继续分配
+    pub fn from_orders(orders: impl IntoIterator<Item = TrackedOrder>) -> Self {
+        let mut store = Self::default();
+        for order in orders {
+            store.index(order);
+        }
+        store
+    }
+
+    fn index(&mut self, order: TrackedOrder) {
+        if let Some(cloid) = order.cloid.strip_prefix('c') {
+            if let Ok(n) = cloid.parse::<u64>() {
+                self.next_cloid = self.next_cloid.max(n + 1);
+            }
+        }
+        if let Some(order_id) = &order.order_id {
+            self.by_order_id
+                .insert(order_id.clone(), order.cloid.clone());
+        }
+        self.orders.insert(order.cloid.clone(), order);
+    }
+
+    fn persist(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut orders: Vec<&TrackedOrder> = self.orders.values().collect();
+        orders.sort_by(|a, b| a.cloid.cmp(&b.cloid));
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(&orders)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// 登记一笔即将提交的订单，返回分配的 cloid
+    pub fn submit(
+        &mut self,
+        market: &str,
+        side: OrderSide,
+        price: u64,
+        amount: u64,
+        now: u64,
+    ) -> Result<String> {
+        let cloid = format!("c{}", self.next_cloid);
+        self.next_cloid += 1;
+        self.orders.insert(
+            cloid.clone(),
+            TrackedOrder {
+                cloid: cloid.clone(),
+                order_id: None,
+                market: market.to_string(),
+                side,
+                price,
+                lifecycle: OrderLifecycle::new(amount),
+                digest: None,
+                submitted_at: now,
+                updated_at: now,
+            },
+        );
+        self.persist()?;
+        Ok(cloid)
+    }
+
+    /// 记录提交交易的摘要
+    pub fn record_digest(&mut self, cloid: &str, digest: &str) -> Result<()> {
+        if let Some(order) = self.orders.get_mut(cloid) {
+            order.digest = Some(digest.to_string());
+            self.persist()?;
+        }
+        Ok(())
+    }
+
+    /// 提交失败（交易未上链）时标记为拒绝
+    pub fn reject(&mut self, cloid: &str, now: u64) -> Result<()> {
+        if let Some(order) = self.orders.get_mut(cloid) {
+            order.lifecycle.apply(&OrderEvent::Rejected)?;
+            order.updated_at = now;
+            self.persist()?;
+        }
+        Ok(())
+    }
+
+    /// 按账户频道事件更新订单
+    ///
+    /// 成交回报只用于关联订单ID，状态以携带累计成交量的订单更新为准，避免重复计量。
+    pub fn apply(&mut self, event: &AccountEvent) -> Result<Reconciled> {
+        match event {
+            AccountEvent::Order(update) => self.apply_update(update),
+            AccountEvent::Fill(fill) => Ok(match self.by_order_id.get(&fill.order_id) {
+                Some(cloid) => Reconciled::Unchanged {
+                    cloid: cloid.clone(),
+                },
+                None => Reconciled::Unknown,
+            }),
+        }
+    }
+
+    /// 按订单更新推进订单状态
+    pub fn apply_update(&mut self, update: &OrderUpdate) -> Result<Reconciled> {
+        let cloid = match self.by_order_id.get(&update.order_id) {
+            Some(cloid) => cloid.clone(),
+            None => match self.match_pending(update) {
+                Some(cloid) => {
+                    self.by_order_id
+                        .insert(update.order_id.clone(), cloid.clone());
+                    self.orders.get_mut(&cloid).unwrap().order_id = Some(update.order_id.clone());
+                    cloid
+                }
+                None => {
+                    self.unknown.push(update.clone());
+                    return Ok(Reconciled::Unknown);
+                }
+            },
+        };
+        let order = self.orders.get_mut(&cloid).unwrap();
+        let before = order.lifecycle.clone();
+        let state = order.lifecycle.apply(&OrderEvent::from(update))?;
+        order.updated_at = order.updated_at.max(update.ts);
+        let changed = order.lifecycle != before;
+        self.persist()?;
+        Ok(if changed {
+            Reconciled::Updated { cloid, state }
+        } else {
+            Reconciled::Unchanged { cloid }
+        })
+    }
+
+    fn match_pending(&self, update: &OrderUpdate) -> Option<String> {
+        self.orders
+            .values()
+            .filter(|order| {
+                order.order_id.is_none()
+                    && !order.lifecycle.state().is_terminal()
+                    && order.market == update.market
+                    && order.side == update.side
+                    && order.price == update.price
+                    && order.lifecycle.amount() == update.amount
+            })
+            .min_by_key(|order| (order.submitted_at, order.cloid.clone()))
+            .map(|order| order.cloid.clone())
+    }
+
+    /// 按 cloid 查询
+    pub fn get(&self, cloid: &str) -> Option<&TrackedOrder> {
+        self.orders.get(cloid)
+    }
+
+    /// 按交易所订单ID查询
+    pub fn get_by_order_id(&self, order_id: &str) -> Option<&TrackedOrder> {
+        self.orders.get(self.by_order_id.get(order_id)?)
+    }
+
+    /// 全部订单，顺序不定
+    pub fn orders(&self) -> impl Iterator<Item = &TrackedOrder> {
+        self.orders.values()
+    }
+
+    /// 未到终态的订单，按提交时间排序
+    pub fn open_orders(&self) -> Vec<&TrackedOrder> {
+        let mut open: Vec<&TrackedOrder> = self
+            .orders
+            .values()
+            .filter(|order| !order.lifecycle.state().is_terminal())
+            .collect();
+        open.sort_by_key(|order| order.submitted_at);
+        open
+    }
+
+    /// 提交超过 `timeout_ms` 仍未收到任何订单更新的孤儿订单
+    pub fn orphans(&self, now: u64, timeout_ms: u64) -> Vec<&TrackedOrder> {
+        let mut orphans: Vec<&TrackedOrder> = self
+            .orders
+            .values()
+            .filter(|order| {
+                order.lifecycle.state() == OrderState::PendingSubmit
+                    && now.saturating_sub(order.submitted_at) > timeout_ms
+            })
+            .collect();
+        orphans.sort_by_key(|order| order.submitted_at);
+        orphans
+    }
+
+    /// 无法匹配到本地提交的订单更新
+    pub fn unknown(&self) -> &[OrderUpdate] {
+        &self.unknown
+    }
+
+    /// 以 CSV 写出全部订单，按提交时间与 cloid 排序
+    pub fn export_csv(&self, writer: impl Write, options: &CsvOptions<OrderColumn>) -> Result<()> {
+        let mut orders: Vec<&TrackedOrder> = self.orders.values().collect();
+        orders.sort_by(|a, b| (a.submitted_at, &a.cloid).cmp(&(b.submitted_at, &b.cloid)));
+        write_csv(writer, options, orders, |order, column, decimals| {
+            order.csv_cell(column, decimals)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::OrderStatus;
+
+    fn update(order_id: &str, status: OrderStatus, filled: u64, ts: u64) -> OrderUpdate {
+        OrderUpdate {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: order_id.to_string(),
+            side: OrderSide::Buy,
+            status,
+            price: 100,
+            amount: 10,
+            filled,
+            ts,
+        }
+    }
+
+    #[test]
+    fn first_update_links_oldest_matching_submission() {
+        let mut store = OrderStore::new();
+        let first = store
+            .submit("BTC-USDC", OrderSide::Buy, 100, 10, 1)
+            .unwrap();
+        let second = store
+            .submit("BTC-USDC", OrderSide::Buy, 100, 10, 2)
+            .unwrap();
+
+        let result = store
+            .apply_update(&update("0xaa", OrderStatus::Acked, 0, 5))
+            .unwrap();
+        assert_eq!(
+            result,
+            Reconciled::Updated {
+                cloid: first.clone(),
+                state: OrderState::Acked
+            }
+        );
+        store
+            .apply_update(&update("0xbb", OrderStatus::PartiallyFilled, 4, 6))
+            .unwrap();
+        assert_eq!(store.get_by_order_id("0xbb").unwrap().cloid, second);
+        assert_eq!(
+            store
+                .apply_update(&update("0xaa", OrderStatus::Acked, 0, 7))
+                .unwrap(),
+            Reconciled::Unchanged { cloid: first }
+        );
+        assert_eq!(store.open_orders().len(), 2);
+    }
+
+    #[test]
+    fn unknown_and_orphaned_orders_are_reported() {
+        let mut store = OrderStore::new();
+        let cloid = store
+            .submit("BTC-USDC", OrderSide::Sell, 100, 10, 0)
+            .unwrap();
+        assert_eq!(
+            store
+                .apply_update(&update("0xcc", OrderStatus::Acked, 0, 1))
+                .unwrap(),
+            Reconciled::Unknown
+        );
+        assert_eq!(store.unknown().len(), 1);
+        assert!(store.orphans(1_000, 5_000).is_empty());
+        assert_eq!(store.orphans(10_000, 5_000)[0].cloid, cloid);
+
+        store.reject(&cloid, 10_000).unwrap();
+        assert!(store.open_orders().is_empty());
+    }
+
+    #[test]
+    fn exports_selected_columns_with_decimals() {
+        use crate::order::{parse_columns, Decimals};
+
+        let mut store = OrderStore::new();
+        let cloid = store
+            .submit("BTC-USDC", OrderSide::Buy, 100, 10, 1)
+            .unwrap();
+        store.record_digest(&cloid, "0xdd").unwrap();
+        store
+            .apply_update(&update("0xaa", OrderStatus::PartiallyFilled, 4, 2))
+            .unwrap();
+        store.submit("ETH-USDC", OrderSide::Sell, 7, 3, 0).unwrap();
+
+        let options = CsvOptions::columns(
+            parse_columns::<OrderColumn>(
+                "cloid, order_id,side,price,filled,remaining,state,digest",
+            )
+            .unwrap(),
+        )
+        .decimals(Decimals {
+            price: 2,
+            size: 1,
+            fee: 0,
+        });
+        let mut out = Vec::new();
+        store.export_csv(&mut out, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "cloid,order_id,side,price,filled,remaining,state,digest\n\
+             c1,,Sell,0.07,0.0,0.3,PendingSubmit,\n\
+             c0,0xaa,Buy,1.00,0.4,0.6,PartiallyFilled,0xdd\n"
+        );
+
+        let mut out = Vec::new();
+        store
+            .export_csv(&mut out, &CsvOptions::default().delimiter(b';'))
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("cloid;order_id;market;side;price;amount;"));
+        assert!(parse_columns::<OrderColumn>("cloid,nope").is_err());
+    }
+
+    #[test]
+    fn persisted_orders_survive_reopen() {
+        let path =
+            std::env::temp_dir().join(format!("lightpool-orders-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        {
+            let mut store = OrderStore::open(&path).unwrap();
+            store
+                .submit("BTC-USDC", OrderSide::Buy, 100, 10, 1)
+                .unwrap();
+            store
+                .apply_update(&update("0xaa", OrderStatus::Filled, 10, 2))
+                .unwrap();
+        }
+        let mut store = OrderStore::open(&path).unwrap();
+        let order = store.get_by_order_id("0xaa").unwrap();
+        assert_eq!(order.lifecycle.state(), OrderState::Filled);
+        // 重新打开后继续分配新的 cloid
+        assert_eq!(
+            store.submit("BTC-USDC", OrderSide::Buy, 1, 1, 3).unwrap(),
+            "c1"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,195 @@
+//! 基于订单簿的常用信号
+
+use super::{Depth, Orderbook};
+use crate::error::{Error, Result};
+use crate::ws::PriceLevel;
+
+impl Orderbook {
+    /// 买卖价差；任一侧为空时返回 None
+    pub fn spread(&self) -> Option<u64> {
+        Some(
+            self.best_ask()?
+                .price
+                .saturating_sub(self.best_bid()?.price),
+        )
+    }
+
+    /// 以最小价格变动单位计的价差
+    pub fn spread_ticks(&self, tick_size: u64) -> Option<u64> {
+        if tick_size == 0 {
+            return None;
+        }
+        Some(self.spread()? / tick_size)
+    }
+
+    /// 按最优档数量加权的微观价格：`(bid * ask_size + ask * bid_size) / (bid_size + ask_size)`
+    ///
+    /// 买盘数量越大越接近卖价，反映下一笔成交更可能发生的方向。
+    pub fn microprice(&self) -> Option<f64> {
+        let bid = self.best_bid()?;
+        let ask = self.best_ask()?;
+        let total = (bid.size + ask.size) as f64;
+        Some((bid.price as f64 * ask.size as f64 + ask.price as f64 * bid.size as f64) / total)
+    }
+
+    /// 前 `depth` 档的买卖数量失衡：`(bid - ask) / (bid + ask)`，取值范围 [-1, 1]
+    ///
+    /// 为正表示买盘更厚；两侧均为空时返回 None。
+    pub fn imbalance(&self, depth: usize) -> Option<f64> {
+        let bid: u64 = self.bid_levels().take(depth).map(|level| level.size).sum();
+        let ask: u64 = self.ask_levels().take(depth).map(|level| level.size).sum();
+        if bid + ask == 0 {
+            return None;
+        }
+        Some((bid as f64 - ask as f64) / (bid + ask) as f64)
+    }
+
+    /// 前 `depth` 档的 CRC32 校验和，用于跨进程比对本地订单簿
+    ///
+    /// 网关推送不带校验和。按档位从优到劣交替取买、卖档，拼接为
+    /// `bid_price:bid_size:ask_price:ask_size:...`，一侧档位不足时跳过该侧。
+    pub fn checksum(&self, depth: usize) -> u32 {
+        let mut bids = self.bid_levels().take(depth);
+        let mut asks = self.ask_levels().take(depth);
+        let mut parts = Vec::new();
+        loop {
+            let (bid, ask) = (bids.next(), asks.next());
+            if bid.is_none() && ask.is_none() {
+                break;
+            }
+            for level in [bid, ask].into_iter().flatten() {
+                parts.push(format!("{}:{}", level.price, level.size));
+            }
+        }
+        crc32fast::hash(parts.join(":").as_bytes())
+    }
+
+    /// 按 `bucket_size` 合并价位，用于图表与粗粒度风控
+    ///
+    /// 买盘向下取整、卖盘向上取整到桶边界，使合并后的买卖价不会比实际更优；
+    /// 价格恰好位于边界时保持不变。
+    pub fn aggregate(&self, bucket_size: u64) -> Result<Depth> {
+        if bucket_size == 0 {
+            return Err(Error::Validation(
+                "bucket size must be positive".to_string(),
+            ));
+        }
+        let bids = bucketed(self.bid_levels(), |price| price - price % bucket_size);
+        let asks = bucketed(self.ask_levels(), |price| {
+            price.div_ceil(bucket_size).saturating_mul(bucket_size)
+        });
+        Ok(Depth { bids, asks })
+    }
+}
+
+/// 合并相邻档位；输入已按价格排序，同一桶的档位必然相邻
+fn bucketed(
+    levels: impl Iterator<Item = PriceLevel>,
+    bucket: impl Fn(u64) -> u64,
+) -> Vec<PriceLevel> {
+    let mut out: Vec<PriceLevel> = Vec::new();
+    for level in levels {
+        let price = bucket(level.price);
+        match out.last_mut() {
+            Some(last) if last.price == price => last.size += level.size,
+            _ => out.push(PriceLevel {
+                price,
+                size: level.size,
+            }),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::fixtures::snapshot;
+
+    #[test]
+    fn spread_in_ticks() {
+        let book = snapshot(&[(990, 1)], &[(1010, 1)]);
+        assert_eq!(book.spread(), Some(20));
+        assert_eq!(book.spread_ticks(5), Some(4));
+        assert_eq!(book.spread_ticks(0), None);
+        assert_eq!(snapshot(&[(990, 1)], &[]).spread(), None);
+    }
+
+    #[test]
+    fn microprice_leans_toward_thin_side() {
+        assert_eq!(snapshot(&[(99, 1)], &[(101, 1)]).microprice(), Some(100.0));
+        // 买盘更厚，微观价格靠近卖价
+        assert_eq!(snapshot(&[(99, 3)], &[(101, 1)]).microprice(), Some(100.5));
+    }
+
+    #[test]
+    fn imbalance_over_top_levels() {
+        let book = snapshot(&[(99, 3), (98, 5)], &[(101, 1), (102, 1)]);
+        assert_eq!(book.imbalance(1), Some(0.5));
+        assert_eq!(book.imbalance(2), Some(0.6));
+        assert_eq!(book.imbalance(0), None);
+        assert_eq!(snapshot(&[], &[]).imbalance(5), None);
+    }
+
+    #[test]
+    fn aggregate_rounds_away_from_the_spread() {
+        let book = snapshot(
+            &[(1009, 1), (1000, 2), (999, 3), (990, 4)],
+            &[(1010, 1), (1011, 2), (1020, 3), (1021, 4)],
+        );
+        let depth = book.aggregate(10).unwrap();
+        assert_eq!(
+            depth.bids,
+            vec![
+                PriceLevel {
+                    price: 1000,
+                    size: 3
+                },
+                PriceLevel {
+                    price: 990,
+                    size: 7
+                },
+            ]
+        );
+        assert_eq!(
+            depth.asks,
+            vec![
+                PriceLevel {
+                    price: 1010,
+                    size: 1
+                },
+                PriceLevel {
+                    price: 1020,
+                    size: 5
+                },
+                PriceLevel {
+                    price: 1030,
+                    size: 4
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn aggregate_by_one_is_identity() {
+        let book = snapshot(&[(99, 1), (98, 2)], &[(101, 3)]);
+        assert_eq!(book.aggregate(1).unwrap(), book.levels(usize::MAX));
+        assert!(book.aggregate(0).is_err());
+    }
+
+    #[test]
+    fn checksum_is_deterministic_and_depth_limited() {
+        let book = snapshot(&[(99, 1), (98, 2), (97, 3)], &[(101, 4)]);
+        assert_eq!(book.checksum(10), crc32fast::hash(b"99:1:101:4:98:2:97:3"));
+        assert_eq!(book.checksum(2), crc32fast::hash(b"99:1:101:4:98:2"));
+        assert_eq!(
+            book.checksum(10),
+            snapshot(&[(97, 3), (98, 2), (99, 1)], &[(101, 4)]).checksum(10)
+        );
+        assert_ne!(
+            book.checksum(10),
+            snapshot(&[(99, 2)], &[(101, 4)]).checksum(10)
+        );
+        assert_eq!(snapshot(&[], &[]).checksum(10), crc32fast::hash(b""));
+    }
+}
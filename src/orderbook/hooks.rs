@@ -0,0 +1,318 @@
+//! 订单簿事件回调
+
+use std::collections::BTreeMap;
+
+use super::Orderbook;
+use crate::error::{Error, Result};
+use crate::types::OrderSide;
+use crate::ws::{OrderbookUpdate, PriceLevel};
+
+/// 档位数量变化；`old_size` 为 0 表示新增档位，`new_size` 为 0 表示档位被移除
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelChange {
+    pub side: OrderSide,
+    pub price: u64,
+    pub old_size: u64,
+    pub new_size: u64,
+}
+
+/// 某一方的新档位与对手方旧档位交叉，对手方交叉档位已被移除
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cross {
+    /// 新档位所在的一方
+    pub side: OrderSide,
+    pub price: u64,
+    /// 被移除的对手方档位，按价格升序
+    pub removed: Vec<PriceLevel>,
+}
+
+/// 买一卖一价差变化；任一方为空时价差为 None
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpreadChange {
+    pub old: Option<u64>,
+    pub new: Option<u64>,
+    pub best_bid: Option<u64>,
+    pub best_ask: Option<u64>,
+}
+
+/// 订单簿事件回调，未实现的方法忽略对应事件
+///
+/// 回调在更新应用过程中同步调用：档位变化按更新中档位的顺序触发，
+/// 交叉移除的对手方档位先各自触发 [`on_level_change`](BookHooks::on_level_change) 再触发
+/// [`on_cross`](BookHooks::on_cross)，价差变化在整条更新应用完后触发一次。
+/// 快照与本地状态逐档比较后触发档位变化，不触发交叉事件。
+pub trait BookHooks {
+    fn on_level_change(&mut self, _change: &LevelChange) {}
+
+    fn on_cross(&mut self, _cross: &Cross) {}
+
+    fn on_spread_change(&mut self, _change: &SpreadChange) {}
+}
+
+impl BookHooks for () {}
+
+impl Orderbook {
+    /// 应用一条更新并触发回调，规则同 [`Orderbook::apply`]；被忽略或出错的更新不触发回调
+    pub fn apply_with<H: BookHooks>(
+        &mut self,
+        update: &OrderbookUpdate,
+        hooks: &mut H,
+    ) -> Result<bool> {
+        if update.market != self.market {
+            return Err(Error::Validation(format!(
+                "update for market {} applied to book {}",
+                update.market, self.market
+            )));
+        }
+        let spread = self.spread();
+        if update.snapshot {
+            let old = std::mem::replace(self, Self::from_snapshot(update)?);
+            diff(OrderSide::Buy, &old.bids, &self.bids, hooks);
+            diff(OrderSide::Sell, &old.asks, &self.asks, hooks);
+        } else {
+            if update.seq <= self.seq {
+                return Ok(false);
+            }
+            if update.seq != self.seq + 1 {
+                return Err(Error::Validation(format!(
+                    "sequence gap: expected {}, got {}",
+                    self.seq + 1,
+                    update.seq
+                )));
+            }
+            self.apply_levels(update, hooks);
+            self.seq = update.seq;
+            self.ts = update.ts;
+        }
+        if self.spread() != spread {
+            hooks.on_spread_change(&SpreadChange {
+                old: spread,
+                new: self.spread(),
+                best_bid: self.best_bid().map(|level| level.price),
+                best_ask: self.best_ask().map(|level| level.price),
+            });
+        }
+        Ok(true)
+    }
+
+    pub(super) fn apply_levels<H: BookHooks>(&mut self, update: &OrderbookUpdate, hooks: &mut H) {
+        for level in &update.bids {
+            set_level(&mut self.bids, OrderSide::Buy, level, hooks);
+            if level.size > 0 {
+                let removed = match level.price.checked_add(1) {
+                    Some(above) => {
+                        let rest = self.asks.split_off(&above);
+                        std::mem::replace(&mut self.asks, rest)
+                    }
+                    None => std::mem::take(&mut self.asks),
+                };
+                crossed(OrderSide::Buy, level.price, removed, hooks);
+            }
+        }
+        for level in &update.asks {
+            set_level(&mut self.asks, OrderSide::Sell, level, hooks);
+            if level.size > 0 {
+                let removed = self.bids.split_off(&level.price);
+                crossed(OrderSide::Sell, level.price, removed, hooks);
+            }
+        }
+    }
+}
+
+fn set_level<H: BookHooks>(
+    side_levels: &mut BTreeMap<u64, u64>,
+    side: OrderSide,
+    level: &PriceLevel,
+    hooks: &mut H,
+) {
+    let old = if level.size == 0 {
+        side_levels.remove(&level.price)
+    } else {
+        side_levels.insert(level.price, level.size)
+    };
+    let old_size = old.unwrap_or(0);
+    if old_size != level.size {
+        hooks.on_level_change(&LevelChange {
+            side,
+            price: level.price,
+            old_size,
+            new_size: level.size,
+        });
+    }
+}
+
+fn crossed<H: BookHooks>(side: OrderSide, price: u64, removed: BTreeMap<u64, u64>, hooks: &mut H) {
+    if removed.is_empty() {
+        return;
+    }
+    let opposite = match side {
+        OrderSide::Buy => OrderSide::Sell,
+        OrderSide::Sell => OrderSide::Buy,
+    };
+    let removed: Vec<PriceLevel> = removed
+        .into_iter()
+        .map(|(price, size)| PriceLevel { price, size })
+        .collect();
+    for level in &removed {
+        hooks.on_level_change(&LevelChange {
+            side: opposite,
+            price: level.price,
+            old_size: level.size,
+            new_size: 0,
+        });
+    }
+    hooks.on_cross(&Cross {
+        side,
+        price,
+        removed,
+    });
+}
+
+fn diff<H: BookHooks>(
+    side: OrderSide,
+    old: &BTreeMap<u64, u64>,
+    new: &BTreeMap<u64, u64>,
+    hooks: &mut H,
+) {
+    let mut prices: Vec<u64> = old.keys().chain(new.keys()).copied().collect();
+    prices.sort_unstable();
+    prices.dedup();
+    for price in prices {
+        let old_size = old.get(&price).copied().unwrap_or(0);
+        let new_size = new.get(&price).copied().unwrap_or(0);
+        if old_size != new_size {
+            hooks.on_level_change(&LevelChange {
+                side,
+                price,
+                old_size,
+                new_size,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::fixtures::{snapshot, update};
+
+    #[derive(Default)]
+    struct Recorded {
+        levels: Vec<LevelChange>,
+        crosses: Vec<Cross>,
+        spreads: Vec<SpreadChange>,
+    }
+
+    impl BookHooks for Recorded {
+        fn on_level_change(&mut self, change: &LevelChange) {
+            self.levels.push(*change);
+        }
+
+        fn on_cross(&mut self, cross: &Cross) {
+            self.crosses.push(cross.clone());
+        }
+
+        fn on_spread_change(&mut self, change: &SpreadChange) {
+            self.spreads.push(*change);
+        }
+    }
+
+    fn book() -> Orderbook {
+        snapshot(&[(99, 1), (98, 2)], &[(101, 1), (102, 2)])
+    }
+
+    #[test]
+    fn level_changes_and_spread() {
+        let mut book = book();
+        let mut hooks = Recorded::default();
+        // 未变化的档位不触发回调
+        let delta = update(2, false, &[(99, 1), (100, 4)], &[(102, 0)]);
+        assert!(book.apply_with(&delta, &mut hooks).unwrap());
+        assert_eq!(
+            hooks.levels,
+            vec![
+                LevelChange {
+                    side: OrderSide::Buy,
+                    price: 100,
+                    old_size: 0,
+                    new_size: 4,
+                },
+                LevelChange {
+                    side: OrderSide::Sell,
+                    price: 102,
+                    old_size: 2,
+                    new_size: 0,
+                },
+            ]
+        );
+        assert_eq!(
+            hooks.spreads,
+            vec![SpreadChange {
+                old: Some(2),
+                new: Some(1),
+                best_bid: Some(100),
+                best_ask: Some(101),
+            }]
+        );
+        assert!(hooks.crosses.is_empty());
+    }
+
+    #[test]
+    fn crossing_bid_removes_asks() {
+        let mut book = book();
+        let mut hooks = Recorded::default();
+        book.apply_with(&update(2, false, &[(102, 5)], &[]), &mut hooks)
+            .unwrap();
+        assert_eq!(
+            hooks.crosses,
+            vec![Cross {
+                side: OrderSide::Buy,
+                price: 102,
+                removed: vec![
+                    PriceLevel {
+                        price: 101,
+                        size: 1
+                    },
+                    PriceLevel {
+                        price: 102,
+                        size: 2
+                    },
+                ],
+            }]
+        );
+        assert_eq!(hooks.levels.len(), 3);
+        assert_eq!(hooks.spreads[0].new, None);
+        assert_eq!(book, {
+            let mut plain = self::book();
+            plain.apply(&update(2, false, &[(102, 5)], &[])).unwrap();
+            plain
+        });
+    }
+
+    #[test]
+    fn snapshot_is_diffed_and_stale_updates_are_silent() {
+        let mut book = book();
+        let mut hooks = Recorded::default();
+        assert!(!book
+            .apply_with(&update(1, false, &[(50, 1)], &[]), &mut hooks)
+            .unwrap());
+        assert!(hooks.levels.is_empty());
+
+        book.apply_with(&update(5, true, &[(99, 3)], &[(101, 1)]), &mut hooks)
+            .unwrap();
+        let changes: Vec<_> = hooks
+            .levels
+            .iter()
+            .map(|change| (change.side, change.price, change.new_size))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (OrderSide::Buy, 98, 0),
+                (OrderSide::Buy, 99, 3),
+                (OrderSide::Sell, 102, 0),
+            ]
+        );
+        assert!(hooks.spreads.is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+//! 市价冲击与成交均价估算
+
+use super::Orderbook;
+use crate::types::OrderSide;
+
+/// 按当前订单簿吃单的成交估算
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillEstimate {
+    /// 吃单方向，买单消耗卖盘
+    pub side: OrderSide,
+    /// 请求数量
+    pub requested: u64,
+    /// 订单簿深度内可成交的数量
+    pub filled: u64,
+    /// 成交金额（价格 × 数量之和）
+    pub notional: u128,
+    /// 对手方最优价
+    pub best_price: u64,
+    /// 最后一笔成交触及的价格
+    pub worst_price: u64,
+}
+
+impl FillEstimate {
+    /// 深度是否足以完全成交
+    pub fn is_complete(&self) -> bool {
+        self.filled == self.requested
+    }
+
+    /// 成交均价
+    pub fn average_price(&self) -> f64 {
+        self.notional as f64 / self.filled as f64
+    }
+
+    /// 成交均价相对最优价的滑点（基点）
+    pub fn slippage_bps(&self) -> f64 {
+        (self.average_price() - self.best_price as f64).abs() / self.best_price as f64 * 10_000.0
+    }
+
+    /// 覆盖最差成交价所需的滑点（基点，向上取整），可直接用作市价单的 slippage 参数
+    pub fn max_slippage_bps(&self) -> u64 {
+        let diff = self.worst_price.abs_diff(self.best_price) as u128 * 10_000;
+        diff.div_ceil(self.best_price as u128) as u64
+    }
+}
+
+impl Orderbook {
+    /// 估算以 `size` 吃单时的成交情况，用于下单前检查与设置市价单滑点
+    ///
+    /// 买单自最优卖价向上、卖单自最优买价向下逐档成交；对手方为空或 `size` 为 0 时返回 None。
+    /// 深度不足时按可成交部分计算，可通过 [`FillEstimate::is_complete`] 判断。
+    /// 价格为 0 的档位不可成交，估算时跳过，因此 `best_price` 总是大于 0。
+    pub fn estimate_fill(&self, side: OrderSide, size: u64) -> Option<FillEstimate> {
+        if size == 0 {
+            return None;
+        }
+        let levels: Box<dyn Iterator<Item = _>> = match side {
+            OrderSide::Buy => Box::new(self.ask_levels()),
+            OrderSide::Sell => Box::new(self.bid_levels()),
+        };
+        let mut levels = levels.filter(|level| level.price > 0);
+        let best = levels.next()?;
+        let mut estimate = FillEstimate {
+            side,
+            requested: size,
+            filled: 0,
+            notional: 0,
+            best_price: best.price,
+            worst_price: best.price,
+        };
+        for level in std::iter::once(best).chain(levels) {
+            let take = level.size.min(size - estimate.filled);
+            estimate.filled += take;
+            estimate.notional += level.price as u128 * take as u128;
+            estimate.worst_price = level.price;
+            if estimate.filled == size {
+                break;
+            }
+        }
+        Some(estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::fixtures;
+
+    fn snapshot() -> Orderbook {
+        fixtures::snapshot(&[(9_990, 2), (9_980, 2)], &[(10_000, 1), (10_010, 3)])
+    }
+
+    #[test]
+    fn walks_levels_for_average_price() {
+        let estimate = snapshot().estimate_fill(OrderSide::Buy, 3).unwrap();
+        assert!(estimate.is_complete());
+        assert_eq!(estimate.notional, 10_000 + 2 * 10_010);
+        assert_eq!(estimate.worst_price, 10_010);
+        assert!((estimate.average_price() - 30_020.0 / 3.0).abs() < 1e-9);
+        assert!((estimate.slippage_bps() - 20.0 / 3.0).abs() < 1e-9);
+        assert_eq!(estimate.max_slippage_bps(), 10);
+    }
+
+    #[test]
+    fn sell_walks_bids_downward() {
+        let estimate = snapshot().estimate_fill(OrderSide::Sell, 2).unwrap();
+        assert_eq!((estimate.best_price, estimate.worst_price), (9_990, 9_990));
+        assert_eq!(estimate.max_slippage_bps(), 0);
+
+        let estimate = snapshot().estimate_fill(OrderSide::Sell, 3).unwrap();
+        assert_eq!(estimate.worst_price, 9_980);
+        // 10 / 9990 约 10.01 基点，向上取整
+        assert_eq!(estimate.max_slippage_bps(), 11);
+    }
+
+    #[test]
+    fn partial_fill_when_depth_is_exhausted() {
+        let estimate = snapshot().estimate_fill(OrderSide::Buy, 10).unwrap();
+        assert!(!estimate.is_complete());
+        assert_eq!(estimate.filled, 4);
+        assert!(snapshot().estimate_fill(OrderSide::Buy, 0).is_none());
+    }
+
+    #[test]
+    fn zero_price_levels_are_skipped() {
+        let asks = fixtures::snapshot(&[], &[(0, 5), (10_000, 1)]);
+        let estimate = asks.estimate_fill(OrderSide::Buy, 1).unwrap();
+        assert_eq!(estimate.best_price, 10_000);
+        assert_eq!(estimate.slippage_bps(), 0.0);
+        assert_eq!(estimate.max_slippage_bps(), 0);
+
+        let bids = fixtures::snapshot(&[(9_990, 2), (0, 5)], &[]);
+        let estimate = bids.estimate_fill(OrderSide::Sell, 3).unwrap();
+        assert_eq!((estimate.filled, estimate.worst_price), (2, 9_990));
+        assert!(fixtures::snapshot(&[], &[(0, 5)])
+            .estimate_fill(OrderSide::Buy, 1)
+            .is_none());
+    }
+}
@@ -0,0 +1,399 @@
+//! 逐笔（L3）订单簿
+//!
+//! 按订单维护每笔挂单，同价位内按时间优先级排队。挂单数量减少（部分成交）时保留
+//! 优先级；价格变化或数量增加视为重新排队，与撮合引擎的规则一致。
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::error::{Error, Result};
+use crate::types::OrderSide;
+use crate::ws::{L3Action, L3Event, OrderbookUpdate, PriceLevel};
+
+/// 订单簿中的一笔挂单
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestingOrder {
+    pub order_id: String,
+    pub side: OrderSide,
+    pub price: u64,
+    /// 剩余数量
+    pub size: u64,
+    /// 同价位内的时间优先级，越小越靠前
+    pub priority: u64,
+}
+
+/// 挂单在其价位队列中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueuePosition {
+    /// 排在前面的挂单数
+    pub orders_ahead: usize,
+    /// 排在前面的挂单剩余数量之和
+    pub size_ahead: u64,
+}
+
+/// 逐笔订单簿
+#[derive(Debug, Clone, Default)]
+pub struct L3Book {
+    market: String,
+    seq: Option<u64>,
+    orders: HashMap<String, RestingOrder>,
+    /// 价格 -> (优先级 -> 订单ID)
+    bids: BTreeMap<u64, BTreeMap<u64, String>>,
+    asks: BTreeMap<u64, BTreeMap<u64, String>>,
+    next_priority: u64,
+}
+
+impl L3Book {
+    /// 创建空订单簿，接受的第一条事件确定起始序号
+    pub fn new(market: &str) -> Self {
+        Self {
+            market: market.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// 由全量挂单快照创建；`orders` 须按时间优先级排列，原有 `priority` 会被重新编号
+    pub fn from_orders(
+        market: &str,
+        seq: u64,
+        orders: impl IntoIterator<Item = RestingOrder>,
+    ) -> Result<Self> {
+        let mut book = Self::new(market);
+        book.seq = Some(seq);
+        for order in orders {
+            book.insert(order.order_id, order.side, order.price, order.size)?;
+        }
+        Ok(book)
+    }
+
+    /// 应用一条逐笔事件
+    ///
+    /// 序号不大于当前序号的事件视为重复并忽略，返回 false；序号不连续、市场不一致
+    /// 或订单ID无法对应时返回错误且不修改本地状态，调用方应重新获取快照。
+    pub fn apply(&mut self, event: &L3Event) -> Result<bool> {
+        if event.market != self.market {
+            return Err(Error::Validation(format!(
+                "event for market {} applied to book {}",
+                event.market, self.market
+            )));
+        }
+        if let Some(seq) = self.seq {
+            if event.seq <= seq {
+                return Ok(false);
+            }
+            if event.seq != seq + 1 {
+                return Err(Error::Validation(format!(
+                    "sequence gap: expected {}, got {}",
+                    seq + 1,
+                    event.seq
+                )));
+            }
+        }
+        match event.action {
+            L3Action::Add => {
+                self.insert(event.order_id.clone(), event.side, event.price, event.size)?
+            }
+            L3Action::Modify => self.modify(&event.order_id, event.price, event.size)?,
+            L3Action::Remove => {
+                self.remove(&event.order_id)?;
+            }
+        }
+        self.seq = Some(event.seq);
+        Ok(true)
+    }
+
+    fn insert(&mut self, order_id: String, side: OrderSide, price: u64, size: u64) -> Result<()> {
+        if self.orders.contains_key(&order_id) {
+            return Err(Error::Validation(format!(
+                "order {} is already resting",
+                order_id
+            )));
+        }
+        let priority = self.next_priority;
+        self.next_priority += 1;
+        self.side_mut(side)
+            .entry(price)
+            .or_default()
+            .insert(priority, order_id.clone());
+        self.orders.insert(
+            order_id.clone(),
+            RestingOrder {
+                order_id,
+                side,
+                price,
+                size,
+                priority,
+            },
+        );
+        Ok(())
+    }
+
+    fn modify(&mut self, order_id: &str, price: u64, size: u64) -> Result<()> {
+        let order = self
+            .order(order_id)
+            .ok_or_else(|| unknown_order(order_id))?;
+        if size == 0 {
+            self.remove(order_id)?;
+        } else if order.price == price && size <= order.size {
+            self.orders.get_mut(order_id).unwrap().size = size;
+        } else {
+            let side = order.side;
+            self.remove(order_id)?;
+            self.insert(order_id.to_string(), side, price, size)?;
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, order_id: &str) -> Result<RestingOrder> {
+        let order = self
+            .orders
+            .remove(order_id)
+            .ok_or_else(|| unknown_order(order_id))?;
+        let levels = self.side_mut(order.side);
+        if let Some(queue) = levels.get_mut(&order.price) {
+            queue.remove(&order.priority);
+            if queue.is_empty() {
+                levels.remove(&order.price);
+            }
+        }
+        Ok(order)
+    }
+
+    fn side(&self, side: OrderSide) -> &BTreeMap<u64, BTreeMap<u64, String>> {
+        match side {
+            OrderSide::Buy => &self.bids,
+            OrderSide::Sell => &self.asks,
+        }
+    }
+
+    fn side_mut(&mut self, side: OrderSide) -> &mut BTreeMap<u64, BTreeMap<u64, String>> {
+        match side {
+            OrderSide::Buy => &mut self.bids,
+            OrderSide::Sell => &mut self.asks,
+        }
+    }
+
+    /// 市场ID
+    pub fn market(&self) -> &str {
+        &self.market
+    }
+
+    /// 最后应用的事件序号
+    pub fn seq(&self) -> Option<u64> {
+        self.seq
+    }
+
+    /// 挂单数量
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// 是否没有任何挂单
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// 查询挂单
+    pub fn order(&self, order_id: &str) -> Option<&RestingOrder> {
+        self.orders.get(order_id)
+    }
+
+    /// 指定价位的挂单，按优先级排列
+    pub fn orders_at(&self, side: OrderSide, price: u64) -> impl Iterator<Item = &RestingOrder> {
+        self.side(side)
+            .get(&price)
+            .into_iter()
+            .flat_map(|queue| queue.values())
+            .map(|order_id| &self.orders[order_id])
+    }
+
+    /// 挂单在其价位队列中的位置；订单不存在时返回 None
+    pub fn queue_position(&self, order_id: &str) -> Option<QueuePosition> {
+        let order = self.order(order_id)?;
+        let ahead = self
+            .orders_at(order.side, order.price)
+            .take_while(|other| other.priority < order.priority);
+        let mut position = QueuePosition {
+            orders_ahead: 0,
+            size_ahead: 0,
+        };
+        for other in ahead {
+            position.orders_ahead += 1;
+            position.size_ahead += other.size;
+        }
+        Some(position)
+    }
+
+    fn level(&self, price: u64, queue: &BTreeMap<u64, String>) -> PriceLevel {
+        PriceLevel {
+            price,
+            size: queue.values().map(|id| self.orders[id].size).sum(),
+        }
+    }
+
+    /// 最优买价档位（按价位汇总）
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        let (&price, queue) = self.bids.iter().next_back()?;
+        Some(self.level(price, queue))
+    }
+
+    /// 最优卖价档位（按价位汇总）
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        let (&price, queue) = self.asks.iter().next()?;
+        Some(self.level(price, queue))
+    }
+
+    /// 汇总为 L2 全量快照，可用于初始化 [`Orderbook`](super::Orderbook)
+    pub fn to_snapshot(&self) -> OrderbookUpdate {
+        OrderbookUpdate {
+            market: self.market.clone(),
+            seq: self.seq.unwrap_or(0),
+            snapshot: true,
+            bids: self
+                .bids
+                .iter()
+                .rev()
+                .map(|(&price, queue)| self.level(price, queue))
+                .collect(),
+            asks: self
+                .asks
+                .iter()
+                .map(|(&price, queue)| self.level(price, queue))
+                .collect(),
+            ts: 0,
+        }
+    }
+}
+
+fn unknown_order(order_id: &str) -> Error {
+    Error::Validation(format!("order {} is not resting", order_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        seq: u64,
+        action: L3Action,
+        id: &str,
+        side: OrderSide,
+        price: u64,
+        size: u64,
+    ) -> L3Event {
+        L3Event {
+            market: "BTC-USDC".to_string(),
+            seq,
+            action,
+            order_id: id.to_string(),
+            side,
+            price,
+            size,
+            ts: seq,
+        }
+    }
+
+    fn book() -> L3Book {
+        let mut book = L3Book::new("BTC-USDC");
+        for (seq, id, side, price, size) in [
+            (1, "a", OrderSide::Buy, 99, 5),
+            (2, "b", OrderSide::Buy, 99, 3),
+            (3, "c", OrderSide::Buy, 99, 2),
+            (4, "d", OrderSide::Sell, 101, 4),
+        ] {
+            book.apply(&event(seq, L3Action::Add, id, side, price, size))
+                .unwrap();
+        }
+        book
+    }
+
+    #[test]
+    fn aggregates_levels_and_queue_position() {
+        let book = book();
+        assert_eq!(
+            book.best_bid(),
+            Some(PriceLevel {
+                price: 99,
+                size: 10
+            })
+        );
+        assert_eq!(
+            book.best_ask(),
+            Some(PriceLevel {
+                price: 101,
+                size: 4
+            })
+        );
+        assert_eq!(
+            book.queue_position("c"),
+            Some(QueuePosition {
+                orders_ahead: 2,
+                size_ahead: 8
+            })
+        );
+        let ids: Vec<&str> = book
+            .orders_at(OrderSide::Buy, 99)
+            .map(|order| order.order_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn partial_fill_keeps_priority() {
+        let mut book = book();
+        book.apply(&event(5, L3Action::Modify, "a", OrderSide::Buy, 99, 1))
+            .unwrap();
+        assert_eq!(book.queue_position("b").unwrap().size_ahead, 1);
+        assert_eq!(book.queue_position("a").unwrap().orders_ahead, 0);
+    }
+
+    #[test]
+    fn size_increase_or_reprice_loses_priority() {
+        let mut book = book();
+        book.apply(&event(5, L3Action::Modify, "a", OrderSide::Buy, 99, 6))
+            .unwrap();
+        assert_eq!(book.queue_position("a").unwrap().orders_ahead, 2);
+
+        book.apply(&event(6, L3Action::Modify, "b", OrderSide::Buy, 98, 3))
+            .unwrap();
+        assert_eq!(book.orders_at(OrderSide::Buy, 99).count(), 2);
+        assert_eq!(book.order("b").unwrap().price, 98);
+    }
+
+    #[test]
+    fn remove_frees_level() {
+        let mut book = book();
+        book.apply(&event(5, L3Action::Remove, "d", OrderSide::Sell, 101, 0))
+            .unwrap();
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.len(), 3);
+        assert_eq!(book.queue_position("d"), None);
+    }
+
+    #[test]
+    fn inconsistent_events_are_rejected() {
+        let mut book = book();
+        assert!(book
+            .apply(&event(5, L3Action::Add, "a", OrderSide::Buy, 99, 1))
+            .is_err());
+        assert!(book
+            .apply(&event(5, L3Action::Remove, "zz", OrderSide::Buy, 99, 0))
+            .is_err());
+        assert!(book
+            .apply(&event(7, L3Action::Add, "e", OrderSide::Buy, 99, 1))
+            .is_err());
+        assert!(!book
+            .apply(&event(4, L3Action::Add, "e", OrderSide::Buy, 99, 1))
+            .unwrap());
+        assert_eq!(book.seq(), Some(4));
+        assert_eq!(book.len(), 4);
+    }
+
+    #[test]
+    fn snapshot_matches_l2_book() {
+        let book = book();
+        let l2 = super::super::Orderbook::from_snapshot(&book.to_snapshot()).unwrap();
+        assert_eq!(l2.best_bid(), book.best_bid());
+        assert_eq!(l2.best_ask(), book.best_ask());
+        assert_eq!(l2.seq(), 4);
+    }
+}
@@ -0,0 +1,357 @@
+//! 本地 L2 订单簿
+//!
+//! 由快照初始化，按序应用增量更新。增量中 size 为 0 表示移除该档位。
+//! 网关推送的增量偶尔会与本地旧档位交叉（对手方档位的移除晚于新挂单到达），
+//! 应用某一方的档位时会移除对手方所有与之交叉的档位，以最新更新为准。
+//!
+//! 策略可实现 [`BookHooks`] 并通过 [`Orderbook::apply_with`] 应用更新，
+//! 在档位变化、档位交叉与价差变化时得到回调。
+//!
+//! 需要逐笔挂单与排队位置时使用 [`L3Book`]。
+
+use std::collections::BTreeMap;
+
+use crate::error::{Error, Result};
+use crate::ws::{OrderbookUpdate, PriceLevel};
+
+mod analytics;
+mod hooks;
+mod impact;
+mod l3;
+
+pub use hooks::{BookHooks, Cross, LevelChange, SpreadChange};
+pub use impact::FillEstimate;
+pub use l3::{L3Book, QueuePosition, RestingOrder};
+
+/// 订单簿的前 N 档
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Depth {
+    /// 买盘，价格降序
+    pub bids: Vec<PriceLevel>,
+    /// 卖盘，价格升序
+    pub asks: Vec<PriceLevel>,
+}
+
+/// 本地 L2 订单簿
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orderbook {
+    market: String,
+    seq: u64,
+    ts: u64,
+    bids: BTreeMap<u64, u64>,
+    asks: BTreeMap<u64, u64>,
+}
+
+impl Orderbook {
+    /// 由全量快照创建
+    pub fn from_snapshot(snapshot: &OrderbookUpdate) -> Result<Self> {
+        if !snapshot.snapshot {
+            return Err(Error::Validation(format!(
+                "seq {} is an incremental update, not a snapshot",
+                snapshot.seq
+            )));
+        }
+        let mut book = Self {
+            market: snapshot.market.clone(),
+            seq: snapshot.seq,
+            ts: snapshot.ts,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        };
+        book.apply_levels(snapshot, &mut ());
+        Ok(book)
+    }
+
+    /// 应用一条更新
+    ///
+    /// 快照直接替换本地状态；序号不大于当前序号的增量视为重复并忽略，返回 false；
+    /// 序号不连续或市场不一致时返回错误，调用方应重新拉取快照。
+    pub fn apply(&mut self, update: &OrderbookUpdate) -> Result<bool> {
+        self.apply_with(update, &mut ())
+    }
+
+    /// 市场ID
+    pub fn market(&self) -> &str {
+        &self.market
+    }
+
+    /// 最后应用的更新序号
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// 最后应用的更新的服务端时间戳（毫秒）
+    pub fn ts(&self) -> u64 {
+        self.ts
+    }
+
+    /// 最优买价档位
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids
+            .iter()
+            .next_back()
+            .map(|(&price, &size)| PriceLevel { price, size })
+    }
+
+    /// 最优卖价档位
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks
+            .iter()
+            .next()
+            .map(|(&price, &size)| PriceLevel { price, size })
+    }
+
+    /// 中间价；任一侧为空时返回 None
+    pub fn mid_price(&self) -> Option<f64> {
+        let bid = self.best_bid()?.price as f64;
+        let ask = self.best_ask()?.price as f64;
+        Some((bid + ask) / 2.0)
+    }
+
+    /// 买卖双方各前 `depth` 档
+    pub fn levels(&self, depth: usize) -> Depth {
+        Depth {
+            bids: self.bid_levels().take(depth).collect(),
+            asks: self.ask_levels().take(depth).collect(),
+        }
+    }
+
+    /// 按价格降序遍历买盘
+    pub fn bid_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.bids
+            .iter()
+            .rev()
+            .map(|(&price, &size)| PriceLevel { price, size })
+    }
+
+    /// 按价格升序遍历卖盘
+    pub fn ask_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.asks
+            .iter()
+            .map(|(&price, &size)| PriceLevel { price, size })
+    }
+
+    /// 导出为全量快照
+    pub fn to_snapshot(&self) -> OrderbookUpdate {
+        OrderbookUpdate {
+            market: self.market.clone(),
+            seq: self.seq,
+            snapshot: true,
+            bids: self.bid_levels().collect(),
+            asks: self.ask_levels().collect(),
+            ts: self.ts,
+        }
+    }
+}
+
+/// 各子模块测试共用的订单簿构造，档位写作 `(price, size)`
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::Orderbook;
+    use crate::ws::{OrderbookUpdate, PriceLevel};
+
+    pub(crate) fn levels(side: &[(u64, u64)]) -> Vec<PriceLevel> {
+        side.iter()
+            .map(|&(price, size)| PriceLevel { price, size })
+            .collect()
+    }
+
+    pub(crate) fn update(
+        seq: u64,
+        snapshot: bool,
+        bids: &[(u64, u64)],
+        asks: &[(u64, u64)],
+    ) -> OrderbookUpdate {
+        OrderbookUpdate {
+            market: "BTC-USDC".to_string(),
+            seq,
+            snapshot,
+            bids: levels(bids),
+            asks: levels(asks),
+            ts: seq * 10,
+        }
+    }
+
+    /// 序号为 1 的快照
+    pub(crate) fn snapshot(bids: &[(u64, u64)], asks: &[(u64, u64)]) -> Orderbook {
+        Orderbook::from_snapshot(&update(1, true, bids, asks)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::{levels, update};
+    use super::*;
+
+    fn book() -> Orderbook {
+        Orderbook::from_snapshot(&update(
+            10,
+            true,
+            &[(99, 1), (98, 2), (97, 3)],
+            &[(101, 1), (102, 2), (103, 3)],
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn snapshot_initializes_sorted_levels() {
+        let book = book();
+        assert_eq!(book.best_bid(), Some(PriceLevel { price: 99, size: 1 }));
+        assert_eq!(
+            book.best_ask(),
+            Some(PriceLevel {
+                price: 101,
+                size: 1
+            })
+        );
+        assert_eq!(book.mid_price(), Some(100.0));
+        assert_eq!(
+            book.levels(2),
+            Depth {
+                bids: levels(&[(99, 1), (98, 2)]),
+                asks: levels(&[(101, 1), (102, 2)]),
+            }
+        );
+        assert_eq!((book.seq(), book.ts()), (10, 100));
+    }
+
+    #[test]
+    fn snapshot_drops_zero_size_levels() {
+        let book = Orderbook::from_snapshot(&update(1, true, &[(99, 0), (98, 1)], &[])).unwrap();
+        assert_eq!(book.best_bid(), Some(PriceLevel { price: 98, size: 1 }));
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.mid_price(), None);
+    }
+
+    #[test]
+    fn rejects_diff_as_initial_state() {
+        assert!(Orderbook::from_snapshot(&update(1, false, &[], &[])).is_err());
+    }
+
+    #[test]
+    fn diff_updates_and_removes_levels() {
+        let mut book = book();
+        assert!(book
+            .apply(&update(11, false, &[(99, 5), (97, 0)], &[(101, 0)]))
+            .unwrap());
+        assert_eq!(
+            book.levels(10),
+            Depth {
+                bids: levels(&[(99, 5), (98, 2)]),
+                asks: levels(&[(102, 2), (103, 3)]),
+            }
+        );
+        assert_eq!(book.mid_price(), Some(100.5));
+    }
+
+    #[test]
+    fn removing_missing_level_is_noop() {
+        let mut book = book();
+        let before = book.levels(10);
+        book.apply(&update(11, false, &[(50, 0)], &[(500, 0)]))
+            .unwrap();
+        assert_eq!(book.levels(10), before);
+    }
+
+    #[test]
+    fn removing_last_level_empties_side() {
+        let mut book = Orderbook::from_snapshot(&update(1, true, &[(99, 1)], &[(101, 1)])).unwrap();
+        book.apply(&update(2, false, &[(99, 0)], &[])).unwrap();
+        assert_eq!(book.best_bid(), None);
+        assert_eq!(book.mid_price(), None);
+        assert_eq!(book.levels(5).bids, vec![]);
+    }
+
+    #[test]
+    fn crossing_bid_removes_stale_asks() {
+        let mut book = book();
+        book.apply(&update(11, false, &[(102, 4)], &[])).unwrap();
+        assert_eq!(
+            book.best_bid(),
+            Some(PriceLevel {
+                price: 102,
+                size: 4
+            })
+        );
+        assert_eq!(
+            book.best_ask(),
+            Some(PriceLevel {
+                price: 103,
+                size: 3
+            })
+        );
+    }
+
+    #[test]
+    fn crossing_ask_removes_stale_bids() {
+        let mut book = book();
+        book.apply(&update(11, false, &[], &[(98, 7)])).unwrap();
+        assert_eq!(book.best_ask(), Some(PriceLevel { price: 98, size: 7 }));
+        assert_eq!(book.best_bid(), Some(PriceLevel { price: 97, size: 3 }));
+    }
+
+    #[test]
+    fn locked_level_replaces_opposite_side() {
+        let mut book = book();
+        book.apply(&update(11, false, &[(101, 2)], &[])).unwrap();
+        assert_eq!(
+            book.best_bid(),
+            Some(PriceLevel {
+                price: 101,
+                size: 2
+            })
+        );
+        assert_eq!(
+            book.best_ask(),
+            Some(PriceLevel {
+                price: 102,
+                size: 2
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_diff_is_ignored() {
+        let mut book = book();
+        assert!(!book.apply(&update(10, false, &[(99, 9)], &[])).unwrap());
+        assert_eq!(book.best_bid(), Some(PriceLevel { price: 99, size: 1 }));
+    }
+
+    #[test]
+    fn sequence_gap_is_an_error() {
+        let mut book = book();
+        assert!(book.apply(&update(12, false, &[(99, 9)], &[])).is_err());
+        // 出错时不修改本地状态
+        assert_eq!(book.seq(), 10);
+        assert_eq!(book.best_bid(), Some(PriceLevel { price: 99, size: 1 }));
+    }
+
+    #[test]
+    fn snapshot_replaces_state() {
+        let mut book = book();
+        book.apply(&update(20, true, &[(50, 1)], &[(60, 1)]))
+            .unwrap();
+        assert_eq!(book.seq(), 20);
+        assert_eq!(
+            book.levels(10),
+            Depth {
+                bids: levels(&[(50, 1)]),
+                asks: levels(&[(60, 1)]),
+            }
+        );
+    }
+
+    #[test]
+    fn other_market_is_rejected() {
+        let mut book = book();
+        let mut other = update(11, false, &[], &[]);
+        other.market = "ETH-USDC".to_string();
+        assert!(book.apply(&other).is_err());
+    }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let book = book();
+        assert_eq!(Orderbook::from_snapshot(&book.to_snapshot()).unwrap(), book);
+    }
+}
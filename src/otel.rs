@@ -0,0 +1,112 @@
+//! OpenTelemetry 链路追踪
+//!
+//! SDK 以 `tracing` span 记录 RPC 调用（`rpc_request`）、下单（`place_order`，成功后带交易 `digest`）、
+//! 交易签名（`sign_transaction`）、行情连接认证（`ws_authenticate`）与账户推送
+//! （`ws_order_update` / `ws_fill`，带 `order_id`）。
+//! [`init_otlp`] 创建经 OTLP/HTTP 导出的 tracer provider，[`layer`] 把这些 span 桥接到 provider。
+//! RPC 请求在 HTTP 头中携带当前 span 的 W3C `traceparent`，节点侧的 span 可据此接入同一条链路。
+//!
+//! ```no_run
+//! use tracing_subscriber::prelude::*;
+//!
+//! # fn main() -> lightpool::Result<()> {
+//! let provider = lightpool::otel::init_otlp("http://localhost:4318/v1/traces", "market-maker")?;
+//! tracing_subscriber::registry()
+//!     .with(lightpool::otel::layer(&provider))
+//!     .init();
+//! // ... 退出前调用 provider.shutdown() 发送剩余的 span
+//! # Ok(())
+//! # }
+//! ```
+
+use opentelemetry::propagation::Injector;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{SdkTracerProvider, Tracer};
+use opentelemetry_sdk::Resource;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use tracing::Subscriber;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::error::{Error, Result};
+
+/// 创建向 `endpoint`（例如 `http://localhost:4318/v1/traces`）批量导出 span 的 tracer provider
+///
+/// 同时将其设为全局 provider，并把 W3C Trace Context 设为全局传播格式。
+pub fn init_otlp(endpoint: &str, service_name: &str) -> Result<SdkTracerProvider> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| Error::Network(format!("otlp: {}", e)))?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_string())
+                .build(),
+        )
+        .build();
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    Ok(provider)
+}
+
+/// 把 tracing span 导出到 `provider` 的订阅层
+pub fn layer<S>(provider: &SdkTracerProvider) -> OpenTelemetryLayer<S, Tracer>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer("lightpool"))
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// 当前 span 的链路上下文，按全局传播格式编码为 HTTP 头；未接入 OpenTelemetry 时为空
+pub(crate) fn trace_headers() -> HeaderMap {
+    let context = tracing::Span::current().context();
+    let mut headers = HeaderMap::new();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(&mut headers))
+    });
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TraceContextExt;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn trace_context_is_injected_into_rpc_headers() {
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry().with(layer(&provider));
+
+        tracing::subscriber::with_default(subscriber, || {
+            // 没有活动 span 时不注入
+            assert!(trace_headers().is_empty());
+
+            let span = tracing::info_span!("place_order");
+            let _entered = span.enter();
+            let trace_id = span.context().span().span_context().trace_id();
+            let headers = trace_headers();
+            let traceparent = headers["traceparent"].to_str().unwrap();
+            assert!(traceparent.starts_with(&format!("00-{}-", trace_id)));
+        });
+    }
+}
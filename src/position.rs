@@ -0,0 +1,513 @@
+//! 由成交回报维护持仓与盈亏
+//!
+//! [`PositionTracker`] 消费账户私有频道的 [`Fill`]，按市场维护带方向的持仓数量、
+//! 开仓均价与持仓成本。同一 tracker 应只接收一个账户的成交。
+//! 减仓时按 [`AccountingMode`] 计算已实现盈亏，标记价格更新后重新计算未实现盈亏。
+//! 永续合约的资金费用通过 [`PositionTracker::apply_funding`] 计入。
+//! 设置 [`FeeSchedule`] 后每笔成交按挂单/吃单费率计提手续费（可为返佣），否则使用回报中的 `fee`。
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::fees::FeeSchedule;
+use crate::types::OrderSide;
+use crate::ws::{Deduplicator, Fill, FundingRate, MarkPrice};
+
+/// 减仓时成本的结转方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AccountingMode {
+    /// 按持仓均价结转
+    #[default]
+    AverageCost,
+    /// 先开先平，按最早开仓的批次价格结转
+    Fifo,
+}
+
+/// 单个市场的持仓快照
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub market: String,
+    /// 持仓数量，正数为多头，负数为空头
+    pub size: i64,
+    /// 开仓均价，空仓时为 0
+    pub avg_entry_price: f64,
+    /// 持仓成本（开仓均价 × 持仓数量绝对值）
+    pub cost_basis: f64,
+    /// 累计已实现盈亏，不含手续费
+    pub realized_pnl: f64,
+    /// 按最新标记价格计算的未实现盈亏；尚无标记价格时为 0
+    pub unrealized_pnl: f64,
+    /// 最新标记价格
+    pub mark_price: Option<u64>,
+    /// 累计资金费用，正数为收入
+    pub funding_pnl: f64,
+    /// 最后计入的资金费率结算时间（毫秒）
+    pub last_funding_time: Option<u64>,
+    /// 成交回报中的累计手续费
+    pub fees: u64,
+    /// 计入盈亏的累计手续费，负数为返佣；未设置费率表时等于 `fees`
+    pub fee_cost: f64,
+    /// 最后一笔成交的时间戳（毫秒）
+    pub ts: u64,
+}
+
+impl Position {
+    fn flat(market: &str) -> Self {
+        Self {
+            market: market.to_string(),
+            size: 0,
+            avg_entry_price: 0.0,
+            cost_basis: 0.0,
+            realized_pnl: 0.0,
+            unrealized_pnl: 0.0,
+            mark_price: None,
+            funding_pnl: 0.0,
+            last_funding_time: None,
+            fees: 0,
+            fee_cost: 0.0,
+            ts: 0,
+        }
+    }
+
+    /// 是否为空仓
+    pub fn is_flat(&self) -> bool {
+        self.size == 0
+    }
+
+    /// 扣除手续费后的已实现盈亏
+    pub fn net_realized_pnl(&self) -> f64 {
+        self.realized_pnl - self.fee_cost
+    }
+
+    /// 计入资金费用与手续费后的总盈亏
+    pub fn net_pnl(&self) -> f64 {
+        self.realized_pnl + self.unrealized_pnl + self.funding_pnl - self.fee_cost
+    }
+
+    fn revalue(&mut self) {
+        self.avg_entry_price = if self.size == 0 {
+            self.cost_basis = 0.0;
+            0.0
+        } else {
+            self.cost_basis / self.size.unsigned_abs() as f64
+        };
+        self.unrealized_pnl = match self.mark_price {
+            Some(mark) if self.size != 0 => (mark as f64 - self.avg_entry_price) * self.size as f64,
+            _ => 0.0,
+        };
+    }
+}
+
+/// 按资金费率计算持仓的资金费用，正数为收入
+///
+/// 多头在费率为正时支付，空头收取；费率为负时相反。
+pub fn funding_payment(size: i64, mark_price: u64, rate: f64) -> f64 {
+    -(size as f64) * mark_price as f64 * rate
+}
+
+/// FIFO 模式下的开仓批次
+#[derive(Debug, Clone)]
+struct Lot {
+    price: u64,
+    size: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    position: Position,
+    lots: VecDeque<Lot>,
+}
+
+impl Entry {
+    fn apply(&mut self, fill: &Fill, mode: AccountingMode, fees: Option<&FeeSchedule>) {
+        let position = &mut self.position;
+        let sign = match fill.side {
+            OrderSide::Buy => 1,
+            OrderSide::Sell => -1,
+        };
+        let price = fill.price as f64;
+        position.fees += fill.fee;
+        position.fee_cost += fees.map_or(fill.fee as f64, |fees| fees.fill_fee(fill));
+        position.ts = position.ts.max(fill.ts);
+
+        let mut remaining = fill.size;
+        if position.size != 0 && position.size.signum() != sign {
+            // 多头平仓盈亏为 (成交价 - 成本价) × 数量，空头相反
+            let direction = position.size.signum() as f64;
+            let close = remaining.min(position.size.unsigned_abs());
+            match mode {
+                AccountingMode::AverageCost => {
+                    position.realized_pnl +=
+                        (price - position.avg_entry_price) * close as f64 * direction;
+                    position.size += sign * close as i64;
+                    position.cost_basis =
+                        position.avg_entry_price * position.size.unsigned_abs() as f64;
+                }
+                AccountingMode::Fifo => {
+                    let mut left = close;
+                    while left > 0 {
+                        let lot = self.lots.front_mut().expect("lots cover the position");
+                        let take = left.min(lot.size);
+                        position.realized_pnl +=
+                            (price - lot.price as f64) * take as f64 * direction;
+                        position.cost_basis -= lot.price as f64 * take as f64;
+                        lot.size -= take;
+                        if lot.size == 0 {
+                            self.lots.pop_front();
+                        }
+                        left -= take;
+                    }
+                    position.size += sign * close as i64;
+                }
+            }
+            remaining -= close;
+        }
+        if remaining > 0 {
+            position.size += sign * remaining as i64;
+            position.cost_basis += price * remaining as f64;
+            if mode == AccountingMode::Fifo {
+                self.lots.push_back(Lot {
+                    price: fill.price,
+                    size: remaining,
+                });
+            }
+        }
+        position.revalue();
+    }
+}
+
+fn entry<'a>(entries: &'a mut BTreeMap<String, Entry>, market: &str) -> &'a mut Entry {
+    entries.entry(market.to_string()).or_insert_with(|| Entry {
+        position: Position::flat(market),
+        lots: VecDeque::new(),
+    })
+}
+
+/// 按市场维护持仓与盈亏
+#[derive(Debug, Clone, Default)]
+pub struct PositionTracker {
+    mode: AccountingMode,
+    entries: BTreeMap<String, Entry>,
+    dedup: Option<Deduplicator>,
+    fees: Option<FeeSchedule>,
+}
+
+impl PositionTracker {
+    /// 使用均价法结转成本
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的成本结转方式
+    pub fn with_mode(mode: AccountingMode) -> Self {
+        Self {
+            mode,
+            entries: BTreeMap::new(),
+            dedup: None,
+            fees: None,
+        }
+    }
+
+    /// 忽略重复的成交回报，`window` 为记住的最近成交数量
+    ///
+    /// 重连或回放时网关可能重发成交，开启后同一成交只计入一次。
+    pub fn dedup(mut self, window: usize) -> Self {
+        self.dedup = Some(Deduplicator::new(window));
+        self
+    }
+
+    /// 按 `schedule` 的挂单/吃单费率计提每笔成交的手续费，代替回报中的 `fee`
+    pub fn with_fees(mut self, schedule: FeeSchedule) -> Self {
+        self.fees = Some(schedule);
+        self
+    }
+
+    /// 成本结转方式
+    pub fn mode(&self) -> AccountingMode {
+        self.mode
+    }
+
+    fn entry(&mut self, market: &str) -> &mut Entry {
+        entry(&mut self.entries, market)
+    }
+
+    /// 计入一笔成交，返回更新后的持仓；开启去重时重复的成交不计入
+    pub fn apply(&mut self, fill: &Fill) -> &Position {
+        let fresh = self.dedup.as_mut().is_none_or(|dedup| dedup.is_new(fill));
+        let entry = entry(&mut self.entries, &fill.market);
+        if fresh {
+            entry.apply(fill, self.mode, self.fees.as_ref());
+        }
+        &entry.position
+    }
+
+    /// 更新标记价格并重新计算未实现盈亏，返回更新后的持仓
+    pub fn update_mark(&mut self, market: &str, price: u64) -> &Position {
+        let position = &mut self.entry(market).position;
+        position.mark_price = Some(price);
+        position.revalue();
+        position
+    }
+
+    /// 按标记价格推送更新未实现盈亏
+    pub fn apply_mark(&mut self, mark: &MarkPrice) -> &Position {
+        self.update_mark(&mark.market, mark.price)
+    }
+
+    /// 按资金费率结算计提当前持仓的资金费用，返回本次金额
+    ///
+    /// 同一结算时间只计提一次，重复或更早的推送返回 0；从未成交的市场不计提。
+    pub fn apply_funding(&mut self, funding: &FundingRate) -> f64 {
+        let Some(entry) = self.entries.get_mut(&funding.market) else {
+            return 0.0;
+        };
+        let position = &mut entry.position;
+        if position
+            .last_funding_time
+            .is_some_and(|last| funding.funding_time <= last)
+        {
+            return 0.0;
+        }
+        position.last_funding_time = Some(funding.funding_time);
+        let payment = funding_payment(position.size, funding.mark_price, funding.rate);
+        position.funding_pnl += payment;
+        payment
+    }
+
+    /// 所有市场的累计资金费用之和
+    pub fn total_funding_pnl(&self) -> f64 {
+        self.entries
+            .values()
+            .map(|entry| entry.position.funding_pnl)
+            .sum()
+    }
+
+    /// 查询指定市场的持仓；从未成交的市场返回 None
+    pub fn position(&self, market: &str) -> Option<&Position> {
+        self.entries.get(market).map(|entry| &entry.position)
+    }
+
+    /// 所有市场的持仓快照，按市场排序，包含已平仓的市场
+    pub fn snapshot(&self) -> Vec<Position> {
+        self.entries
+            .values()
+            .map(|entry| entry.position.clone())
+            .collect()
+    }
+
+    /// 所有市场的已实现盈亏之和
+    pub fn total_realized_pnl(&self) -> f64 {
+        self.entries
+            .values()
+            .map(|entry| entry.position.realized_pnl)
+            .sum()
+    }
+
+    /// 所有市场扣除手续费后的已实现盈亏之和
+    pub fn total_net_realized_pnl(&self) -> f64 {
+        self.entries
+            .values()
+            .map(|entry| entry.position.net_realized_pnl())
+            .sum()
+    }
+
+    /// 所有市场的未实现盈亏之和
+    pub fn total_unrealized_pnl(&self) -> f64 {
+        self.entries
+            .values()
+            .map(|entry| entry.position.unrealized_pnl)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(side: OrderSide, price: u64, size: u64) -> Fill {
+        Fill {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: "00".to_string(),
+            side,
+            price,
+            size,
+            fee: 1,
+            is_maker: false,
+            trade_id: None,
+            ts: 0,
+        }
+    }
+
+    #[test]
+    fn replayed_fills_are_counted_once() {
+        let mut tracker = PositionTracker::new().dedup(64);
+        let first = Fill {
+            trade_id: Some(1),
+            ..fill(OrderSide::Buy, 100, 1)
+        };
+        tracker.apply(&first);
+        tracker.apply(&first);
+        let position = tracker.apply(&Fill {
+            trade_id: Some(2),
+            ..first.clone()
+        });
+        assert_eq!(position.size, 2);
+        assert_eq!(position.fees, 2);
+    }
+
+    #[test]
+    fn adding_averages_entry_price() {
+        let mut tracker = PositionTracker::new();
+        tracker.apply(&fill(OrderSide::Buy, 100, 1));
+        let position = tracker.apply(&fill(OrderSide::Buy, 130, 2));
+        assert_eq!(position.size, 3);
+        assert_eq!(position.avg_entry_price, 120.0);
+        assert_eq!(position.cost_basis, 360.0);
+        assert_eq!(position.fees, 2);
+    }
+
+    #[test]
+    fn reducing_keeps_entry_price_until_flat() {
+        let mut tracker = PositionTracker::new();
+        tracker.apply(&fill(OrderSide::Sell, 100, 4));
+        let position = tracker.apply(&fill(OrderSide::Buy, 90, 1));
+        assert_eq!(position.size, -3);
+        assert_eq!(position.avg_entry_price, 100.0);
+        assert_eq!(position.cost_basis, 300.0);
+        assert_eq!(position.realized_pnl, 10.0);
+
+        let position = tracker.apply(&fill(OrderSide::Buy, 80, 3));
+        assert!(position.is_flat());
+        assert_eq!(position.avg_entry_price, 0.0);
+        assert_eq!(position.cost_basis, 0.0);
+        assert_eq!(position.realized_pnl, 70.0);
+    }
+
+    #[test]
+    fn crossing_zero_opens_at_fill_price() {
+        let mut tracker = PositionTracker::new();
+        tracker.apply(&fill(OrderSide::Buy, 100, 2));
+        let position = tracker.apply(&fill(OrderSide::Sell, 110, 5));
+        assert_eq!(position.size, -3);
+        assert_eq!(position.avg_entry_price, 110.0);
+        assert_eq!(position.realized_pnl, 20.0);
+    }
+
+    #[test]
+    fn fifo_and_average_cost_realize_differently() {
+        let fills = [
+            fill(OrderSide::Buy, 100, 1),
+            fill(OrderSide::Buy, 120, 1),
+            fill(OrderSide::Sell, 130, 1),
+        ];
+        let mut average = PositionTracker::new();
+        let mut fifo = PositionTracker::with_mode(AccountingMode::Fifo);
+        for fill in &fills {
+            average.apply(fill);
+            fifo.apply(fill);
+        }
+        let average = average.position("BTC-USDC").unwrap();
+        assert_eq!(average.realized_pnl, 20.0);
+        assert_eq!(average.avg_entry_price, 110.0);
+
+        // FIFO 先平掉 100 的批次，剩余持仓成本为 120
+        let fifo_position = fifo.position("BTC-USDC").unwrap();
+        assert_eq!(fifo_position.realized_pnl, 30.0);
+        assert_eq!(fifo_position.avg_entry_price, 120.0);
+        assert_eq!(fifo.total_realized_pnl(), 30.0);
+    }
+
+    #[test]
+    fn fifo_flip_consumes_all_lots() {
+        let mut tracker = PositionTracker::with_mode(AccountingMode::Fifo);
+        tracker.apply(&fill(OrderSide::Sell, 100, 1));
+        tracker.apply(&fill(OrderSide::Sell, 90, 1));
+        let position = tracker.apply(&fill(OrderSide::Buy, 95, 3));
+        assert_eq!(position.size, 1);
+        // 100 的空头批次盈利 5，90 的批次亏损 5
+        assert_eq!(position.realized_pnl, 0.0);
+        assert_eq!(position.avg_entry_price, 95.0);
+    }
+
+    #[test]
+    fn mark_price_updates_unrealized_pnl() {
+        let mut tracker = PositionTracker::new();
+        tracker.apply(&fill(OrderSide::Sell, 100, 2));
+        let position = tracker.update_mark("BTC-USDC", 90);
+        assert_eq!(position.unrealized_pnl, 20.0);
+        assert_eq!(position.net_pnl(), 19.0);
+
+        // 成交后以已知的标记价格重新估值
+        let position = tracker.apply(&fill(OrderSide::Buy, 95, 1));
+        assert_eq!(position.unrealized_pnl, 10.0);
+        assert_eq!(position.realized_pnl, 5.0);
+        assert_eq!(tracker.total_unrealized_pnl(), 10.0);
+    }
+
+    #[test]
+    fn fee_schedule_charges_each_fill() {
+        let fills = [
+            fill(OrderSide::Buy, 100, 1_000),
+            Fill {
+                is_maker: true,
+                ..fill(OrderSide::Sell, 110, 1_000)
+            },
+        ];
+        let mut plain = PositionTracker::new();
+        // 吃单 10 基点，挂单返佣 1 基点
+        let mut charged = PositionTracker::new().with_fees(FeeSchedule::flat(-1, 10));
+        for fill in &fills {
+            plain.apply(fill);
+            charged.apply(fill);
+        }
+        let (plain, charged) = (
+            plain.position("BTC-USDC").unwrap(),
+            charged.position("BTC-USDC").unwrap(),
+        );
+        assert_eq!(
+            (plain.realized_pnl, charged.realized_pnl),
+            (10_000.0, 10_000.0)
+        );
+        assert_eq!(plain.net_realized_pnl(), 9_998.0);
+        assert_eq!(charged.fee_cost, 100.0 - 11.0);
+        assert_eq!(charged.net_realized_pnl(), 9_911.0);
+        assert_eq!(charged.fees, 2);
+    }
+
+    #[test]
+    fn snapshot_lists_markets() {
+        let mut tracker = PositionTracker::new();
+        let mut eth = fill(OrderSide::Buy, 10, 1);
+        eth.market = "ETH-USDC".to_string();
+        tracker.apply(&eth);
+        tracker.apply(&fill(OrderSide::Buy, 100, 1));
+        let markets: Vec<String> = tracker.snapshot().into_iter().map(|p| p.market).collect();
+        assert_eq!(markets, vec!["BTC-USDC", "ETH-USDC"]);
+        assert!(tracker.position("SOL-USDC").is_none());
+    }
+
+    #[test]
+    fn funding_accrues_once_per_settlement() {
+        let mut tracker = PositionTracker::new();
+        tracker.apply(&fill(OrderSide::Buy, 100, 2));
+        let funding = FundingRate {
+            market: "BTC-USDC".to_string(),
+            rate: 0.001,
+            mark_price: 1_000,
+            funding_time: 8,
+            ts: 8,
+        };
+        // 多头在正费率下支付
+        assert_eq!(tracker.apply_funding(&funding), -2.0);
+        assert_eq!(tracker.apply_funding(&funding), 0.0);
+
+        tracker.apply(&fill(OrderSide::Sell, 100, 4));
+        let next = FundingRate {
+            funding_time: 16,
+            ..funding
+        };
+        assert_eq!(tracker.apply_funding(&next), 2.0);
+        assert_eq!(tracker.total_funding_pnl(), 0.0);
+        assert_eq!(funding_payment(-1, 100, -0.01), -1.0);
+    }
+}
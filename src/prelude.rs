@@ -0,0 +1,29 @@
+//! 常用类型
+//!
+//! `use lightpool::prelude::*;` 一次引入客户端、下单参数、行情消息与本地簿记的常用类型，
+//! 适合 evcxr 笔记本与脚本。
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::client::{Balance, LightPoolClient, MarketInfo, SubmitResult, TransactionReceipt};
+pub use crate::crypto::{Ed25519Signer, Signer};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::display::balances;
+pub use crate::display::{book, orders, Table};
+pub use crate::error::{Error, Result};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::exchange::{ExchangeAdapter, LightPoolAdapter};
+pub use crate::order::{
+    format_units, parse_units, Decimals, Market, OrderRequest, OrderStore, OrderType,
+    PlaceOrderParams, TimeInForce, TrackedOrder,
+};
+pub use crate::orderbook::{L3Book, Orderbook};
+pub use crate::position::{Position, PositionTracker};
+pub use crate::symbol::Symbol;
+pub use crate::transaction::TransactionBuilder;
+pub use crate::types::{Address, ObjectId, OrderId, OrderSide};
+pub use crate::ws::{
+    AccountEvent, Candle, CandleInterval, Fill, OrderUpdate, OrderbookUpdate, PriceLevel, Ticker,
+    Trade, WsConfig,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::ws::{MarketStream, WsClient};
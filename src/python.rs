@@ -0,0 +1,581 @@
+//! Python 原生扩展（`python` feature）
+//!
+//! 以 PyO3 导出 `lightpool_native` 模块，Python SDK 直接调用 Rust 的编码与签名，
+//! 不再在 Python 侧重新实现 bincode 格式。地址与对象ID以 `0x` 前缀的十六进制字符串传入，
+//! 字节内容以 `bytes` 传入传出；数据不合法时抛出 `ValueError`。
+//!
+//! ```python
+//! from lightpool_native import Action, PlaceOrderParams, Signer, Transaction
+//!
+//! params = PlaceOrderParams.limit("buy", 5_000_000, 50_000_000_000)
+//! action = Action.place_order(market_id, balance_id, params)
+//! signed = Transaction(signer.address, [action]).sign(signer)
+//! payload = signed.to_json()
+//! ```
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::crypto::{self, Ed25519Signer, Signer as _};
+use crate::error::Error;
+use crate::order::{self, OrderRequest, OrderType, TimeInForce};
+use crate::transaction::{self, decode_action_name, SPOT_CONTRACT};
+use crate::types::{Address, ObjectId, OrderSide};
+
+impl From<Error> for PyErr {
+    fn from(error: Error) -> Self {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+fn parse<T: std::str::FromStr<Err = Error>>(value: &str) -> PyResult<T> {
+    Ok(value.parse()?)
+}
+
+fn side(value: &str) -> PyResult<OrderSide> {
+    match value.to_ascii_lowercase().as_str() {
+        "buy" => Ok(OrderSide::Buy),
+        "sell" => Ok(OrderSide::Sell),
+        _ => Err(PyValueError::new_err(format!(
+            "invalid order side: {}",
+            value
+        ))),
+    }
+}
+
+fn tif(value: &str) -> PyResult<TimeInForce> {
+    match value.to_ascii_uppercase().as_str() {
+        "GTC" => Ok(TimeInForce::Gtc),
+        "IOC" => Ok(TimeInForce::Ioc),
+        "FOK" => Ok(TimeInForce::Fok),
+        _ => Err(PyValueError::new_err(format!(
+            "invalid time in force: {}",
+            value
+        ))),
+    }
+}
+
+fn fixed<const N: usize>(bytes: &[u8], what: &str) -> PyResult<[u8; N]> {
+    bytes.try_into().map_err(|_| {
+        PyValueError::new_err(format!("{} must be {} bytes, got {}", what, N, bytes.len()))
+    })
+}
+
+fn json<T: serde::Serialize>(value: &T) -> PyResult<String> {
+    Ok(serde_json::to_string(value).map_err(Error::from)?)
+}
+
+/// 现货下单参数
+#[pyclass(name = "PlaceOrderParams", module = "lightpool_native", frozen, eq)]
+#[derive(PartialEq)]
+pub struct PyPlaceOrderParams(order::PlaceOrderParams);
+
+#[pymethods]
+impl PyPlaceOrderParams {
+    /// 限价单，`tif` 为 GTC、IOC 或 FOK
+    #[staticmethod]
+    #[pyo3(signature = (side, amount, limit_price, tif = "GTC"))]
+    fn limit(side: &str, amount: u64, limit_price: u64, tif: &str) -> PyResult<Self> {
+        let mut params = order::PlaceOrderParams::limit(self::side(side)?, amount, limit_price);
+        params.order_type = OrderType::Limit {
+            tif: self::tif(tif)?,
+        };
+        Ok(Self(params))
+    }
+
+    /// 市价单，`limit_price` 为滑点参考价，`slippage_bps` 为最大滑点（基点）
+    #[staticmethod]
+    fn market(side: &str, amount: u64, limit_price: u64, slippage_bps: u64) -> PyResult<Self> {
+        Ok(Self(order::PlaceOrderParams::market(
+            self::side(side)?,
+            amount,
+            limit_price,
+            slippage_bps,
+        )))
+    }
+
+    /// 触发单，价格触及 `trigger_price` 后以限价 `limit_price` 或市价（`is_market`）下单
+    #[staticmethod]
+    #[pyo3(signature = (side, amount, limit_price, trigger_price, is_market = false, trigger_type = 0))]
+    fn trigger(
+        side: &str,
+        amount: u64,
+        limit_price: u64,
+        trigger_price: u64,
+        is_market: bool,
+        trigger_type: u8,
+    ) -> PyResult<Self> {
+        Ok(Self(order::PlaceOrderParams {
+            side: self::side(side)?,
+            amount,
+            order_type: OrderType::Trigger {
+                trigger_price,
+                is_market,
+                trigger_type,
+            },
+            limit_price,
+        }))
+    }
+
+    /// 由 bincode 编码还原
+    #[staticmethod]
+    fn decode(data: &[u8]) -> PyResult<Self> {
+        bincode::deserialize(data)
+            .map(Self)
+            .map_err(|e| Error::Decode(format!("invalid order params: {}", e)).into())
+    }
+
+    /// bincode 编码
+    fn encode<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.encode())
+    }
+
+    #[getter]
+    fn side(&self) -> &'static str {
+        match self.0.side {
+            OrderSide::Buy => "buy",
+            OrderSide::Sell => "sell",
+        }
+    }
+
+    #[getter]
+    fn amount(&self) -> u64 {
+        self.0.amount
+    }
+
+    #[getter]
+    fn limit_price(&self) -> u64 {
+        self.0.limit_price
+    }
+
+    /// limit、market 或 trigger
+    #[getter]
+    fn order_type(&self) -> &'static str {
+        match self.0.order_type {
+            OrderType::Limit { .. } => "limit",
+            OrderType::Market { .. } => "market",
+            OrderType::Trigger { .. } => "trigger",
+        }
+    }
+
+    /// 限价单的 GTC、IOC 或 FOK
+    #[getter]
+    fn tif(&self) -> Option<&'static str> {
+        match self.0.order_type {
+            OrderType::Limit { tif } => Some(match tif {
+                TimeInForce::Gtc => "GTC",
+                TimeInForce::Ioc => "IOC",
+                TimeInForce::Fok => "FOK",
+            }),
+            _ => None,
+        }
+    }
+
+    /// 市价单的最大滑点（基点）
+    #[getter]
+    fn slippage_bps(&self) -> Option<u64> {
+        match self.0.order_type {
+            OrderType::Market { slippage } => Some(slippage),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn trigger_price(&self) -> Option<u64> {
+        match self.0.order_type {
+            OrderType::Trigger { trigger_price, .. } => Some(trigger_price),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn is_market(&self) -> Option<bool> {
+        match self.0.order_type {
+            OrderType::Trigger { is_market, .. } => Some(is_market),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn trigger_type(&self) -> Option<u8> {
+        match self.0.order_type {
+            OrderType::Trigger { trigger_type, .. } => Some(trigger_type),
+            _ => None,
+        }
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        json(&self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PlaceOrderParams({:?})", self.0)
+    }
+}
+
+/// 合约调用
+#[pyclass(name = "Action", module = "lightpool_native", frozen, eq)]
+#[derive(PartialEq)]
+pub struct PyAction(transaction::Action);
+
+#[pymethods]
+impl PyAction {
+    /// 任意合约调用，`name` 为操作名，`params` 为 bincode 编码的参数
+    #[new]
+    fn new(contract: &str, name: &str, inputs: Vec<String>, params: Vec<u8>) -> PyResult<Self> {
+        let inputs = inputs
+            .iter()
+            .map(|id| parse(id))
+            .collect::<PyResult<Vec<ObjectId>>>()?;
+        Ok(Self(transaction::Action::new(
+            parse(contract)?,
+            name,
+            inputs,
+            params,
+        )?))
+    }
+
+    /// `ord_place` 操作，`market_address` 默认为现货合约
+    #[staticmethod]
+    #[pyo3(signature = (market_id, balance_id, params, market_address = None))]
+    fn place_order(
+        market_id: &str,
+        balance_id: &str,
+        params: &PyPlaceOrderParams,
+        market_address: Option<&str>,
+    ) -> PyResult<Self> {
+        let mut request =
+            OrderRequest::spot(parse(market_id)?, parse(balance_id)?, params.0.clone());
+        if let Some(address) = market_address {
+            request.market_address = parse(address)?;
+        }
+        Ok(Self(request.to_action()?))
+    }
+
+    /// `ord_cancel` 操作，`market_address` 默认为现货合约
+    #[staticmethod]
+    #[pyo3(signature = (market_id, order_id, market_address = None))]
+    fn cancel_order(
+        market_id: &str,
+        order_id: &str,
+        market_address: Option<&str>,
+    ) -> PyResult<Self> {
+        let market_address = market_address.map_or(Ok(SPOT_CONTRACT), parse)?;
+        Ok(Self(order::cancel_action(
+            market_address,
+            parse(market_id)?,
+            parse(order_id)?,
+        )?))
+    }
+
+    #[getter]
+    fn contract(&self) -> String {
+        self.0.contract.to_string()
+    }
+
+    /// 操作名编码
+    #[getter]
+    fn action(&self) -> u64 {
+        self.0.action
+    }
+
+    #[getter]
+    fn name(&self) -> Option<String> {
+        decode_action_name(self.0.action)
+    }
+
+    #[getter]
+    fn inputs(&self) -> Vec<String> {
+        self.0.inputs.iter().map(ToString::to_string).collect()
+    }
+
+    #[getter]
+    fn params<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.params)
+    }
+
+    /// bincode 编码
+    fn encode<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let bytes = bincode::serialize(&self.0).expect("action is always serializable");
+        PyBytes::new(py, &bytes)
+    }
+
+    /// 节点接受的 JSON 编码
+    fn to_json(&self) -> PyResult<String> {
+        json(&self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Action(contract={}, name={:?}, inputs={:?})",
+            self.0.contract,
+            decode_action_name(self.0.action).unwrap_or_default(),
+            self.inputs()
+        )
+    }
+}
+
+/// Ed25519 签名者
+#[pyclass(name = "Signer", module = "lightpool_native", frozen)]
+pub struct PySigner(Ed25519Signer);
+
+#[pymethods]
+impl PySigner {
+    /// 由 32 字节私钥创建
+    #[new]
+    fn new(secret_key: &[u8]) -> PyResult<Self> {
+        let secret_key = fixed::<32>(secret_key, "secret key")?;
+        Ok(Self(Ed25519Signer::from_secret_key_bytes(&secret_key)))
+    }
+
+    /// 随机生成私钥
+    #[staticmethod]
+    fn generate() -> Self {
+        Self(Ed25519Signer::generate())
+    }
+
+    /// 由十六进制私钥创建
+    #[staticmethod]
+    fn from_hex(secret_key: &str) -> PyResult<Self> {
+        Ok(Self(Ed25519Signer::from_hex(secret_key)?))
+    }
+
+    #[getter]
+    fn address(&self) -> String {
+        self.0.address().to_string()
+    }
+
+    #[getter]
+    fn public_key<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.public_key())
+    }
+
+    fn secret_key<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.secret_key_bytes())
+    }
+
+    /// 签名任意消息，返回 64 字节签名
+    fn sign<'py>(&self, py: Python<'py>, message: &[u8]) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.sign(message))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Signer(address={})", self.0.address())
+    }
+}
+
+/// 未签名交易
+#[pyclass(name = "Transaction", module = "lightpool_native", frozen)]
+pub struct PyTransaction(transaction::Transaction);
+
+#[pymethods]
+impl PyTransaction {
+    /// 过期时间默认不过期
+    #[new]
+    #[pyo3(signature = (sender, actions, expiration = u64::MAX))]
+    fn new(sender: &str, actions: Vec<PyRef<'_, PyAction>>, expiration: u64) -> PyResult<Self> {
+        Ok(Self(transaction::Transaction {
+            sender: parse::<Address>(sender)?,
+            expiration,
+            actions: actions.iter().map(|action| action.0.clone()).collect(),
+        }))
+    }
+
+    /// 签名内容：交易的 bincode 编码
+    fn signing_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.signing_bytes())
+    }
+
+    fn sign(&self, signer: &PySigner) -> PySignedTransaction {
+        PySignedTransaction(self.0.clone().sign(&signer.0))
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        json(&self.0)
+    }
+}
+
+/// 已签名交易
+#[pyclass(name = "SignedTransaction", module = "lightpool_native", frozen)]
+pub struct PySignedTransaction(transaction::SignedTransaction);
+
+#[pymethods]
+impl PySignedTransaction {
+    /// 64 字节签名，依次排列
+    #[getter]
+    fn signatures<'py>(&self, py: Python<'py>) -> Vec<Bound<'py, PyBytes>> {
+        self.0
+            .signatures
+            .iter()
+            .map(|signature| PyBytes::new(py, &signature.to_bytes()))
+            .collect()
+    }
+
+    /// 校验公钥对应发送者地址且签名有效
+    fn verify(&self, public_key: &[u8]) -> PyResult<bool> {
+        Ok(self.0.verify(&fixed::<32>(public_key, "public key")?))
+    }
+
+    /// 提交给节点的 JSON 编码
+    fn to_json(&self) -> PyResult<String> {
+        json(&self.0)
+    }
+}
+
+/// 公钥对应的地址
+#[pyfunction]
+fn address_from_public_key(public_key: &[u8]) -> PyResult<String> {
+    let public_key = fixed::<32>(public_key, "public key")?;
+    Ok(crypto::address_from_public_key(&public_key).to_string())
+}
+
+/// 校验 Ed25519 签名
+#[pyfunction]
+fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> PyResult<bool> {
+    Ok(crypto::verify(
+        &fixed::<32>(public_key, "public key")?,
+        message,
+        &fixed::<64>(signature, "signature")?,
+    ))
+}
+
+#[pymodule]
+fn lightpool_native(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPlaceOrderParams>()?;
+    m.add_class::<PyAction>()?;
+    m.add_class::<PySigner>()?;
+    m.add_class::<PyTransaction>()?;
+    m.add_class::<PySignedTransaction>()?;
+    m.add_function(wrap_pyfunction!(address_from_public_key, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add("SPOT_CONTRACT", SPOT_CONTRACT.to_string())?;
+    m.add("TOKEN_CONTRACT", transaction::TOKEN_CONTRACT.to_string())?;
+    Ok(())
+}
+
+// `extension-module` 不链接 libpython，运行这些测试需显式链接，例如
+// `RUSTFLAGS="-C link-arg=-lpython3.11" cargo test --features python --lib python::`
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vectors::{test_vectors, TestVector};
+
+    fn vectors(kind: &str) -> Vec<TestVector> {
+        test_vectors()
+            .vectors
+            .into_iter()
+            .filter(|vector| vector.kind == kind)
+            .collect()
+    }
+
+    fn bytes(kind: &str, name: &str) -> Vec<u8> {
+        let vector = vectors(kind)
+            .into_iter()
+            .find(|vector| vector.name == name)
+            .unwrap_or_else(|| panic!("no {} vector {}", kind, name));
+        hex::decode(vector.bytes).unwrap()
+    }
+
+    #[test]
+    fn constructors_encode_the_vectors() {
+        let cases = [
+            (
+                "limit-sell",
+                PyPlaceOrderParams::limit("sell", 5_000_000, 50_000_000_000, "GTC"),
+            ),
+            (
+                "limit-buy-ioc",
+                PyPlaceOrderParams::limit("BUY", 1, 1, "ioc"),
+            ),
+            (
+                "market-buy",
+                PyPlaceOrderParams::market("buy", 2_500_000, 51_000_000_000, 100),
+            ),
+            (
+                "trigger-sell",
+                PyPlaceOrderParams::trigger(
+                    "sell",
+                    1_000_000,
+                    48_900_000_000,
+                    49_000_000_000,
+                    false,
+                    2,
+                ),
+            ),
+        ];
+        for (name, params) in cases {
+            assert_eq!(
+                params.unwrap().0.encode(),
+                bytes("place-order", name),
+                "{}",
+                name
+            );
+        }
+        let trigger = PyPlaceOrderParams::decode(&bytes("place-order", "trigger-sell")).unwrap();
+        assert_eq!(trigger.order_type(), "trigger");
+        assert_eq!(
+            (
+                trigger.trigger_price(),
+                trigger.is_market(),
+                trigger.trigger_type()
+            ),
+            (Some(49_000_000_000), Some(false), Some(2))
+        );
+        assert_eq!((trigger.tif(), trigger.slippage_bps()), (None, None));
+        let market = PyPlaceOrderParams::decode(&bytes("place-order", "market-buy")).unwrap();
+        assert_eq!(
+            (market.order_type(), market.slippage_bps()),
+            ("market", Some(100))
+        );
+        for vector in vectors("place-order") {
+            let encoded = hex::decode(&vector.bytes).unwrap();
+            let params = PyPlaceOrderParams::decode(&encoded).unwrap();
+            assert_eq!(params.0.encode(), encoded, "{}", vector.name);
+        }
+    }
+
+    #[test]
+    fn signs_the_place_and_cancel_vector() {
+        let fixture = test_vectors();
+        let pattern = |tag: u8| {
+            let id: Vec<u8> = (0..32).map(|i| tag.wrapping_add(i)).collect();
+            format!("0x{}", hex::encode(id))
+        };
+        let params = PyPlaceOrderParams::limit("sell", 5_000_000, 50_000_000_000, "GTC").unwrap();
+        let place = PyAction::place_order(&pattern(0x10), &pattern(0x20), &params, None).unwrap();
+        let cancel = PyAction::cancel_order(&pattern(0x10), &pattern(0x30), None).unwrap();
+        assert_eq!(
+            bincode::serialize(&place.0).unwrap(),
+            bytes("action", "ord-place")
+        );
+        assert_eq!(
+            bincode::serialize(&cancel.0).unwrap(),
+            bytes("action", "ord-cancel")
+        );
+
+        let signer = PySigner::from_hex(&fixture.signer.secret_key).unwrap();
+        assert_eq!(signer.address(), fixture.signer.address);
+        let transaction = PyTransaction(transaction::Transaction {
+            sender: parse(&signer.address()).unwrap(),
+            expiration: 1_700_000_000,
+            actions: vec![place.0, cancel.0],
+        });
+        let signing_bytes = transaction.0.signing_bytes();
+        assert_eq!(signing_bytes, bytes("transaction", "place-and-cancel"));
+        let signed = transaction.sign(&signer);
+        assert_eq!(
+            bincode::serialize(&signed.0).unwrap(),
+            bytes("signed-transaction", "place-and-cancel")
+        );
+        let vector = vectors("transaction")
+            .into_iter()
+            .find(|vector| vector.name == "place-and-cancel")
+            .unwrap();
+        assert_eq!(
+            hex::encode(signed.0.signatures[0].to_bytes()),
+            vector.signature
+        );
+        assert!(signed.verify(&signer.0.public_key()).unwrap());
+    }
+}
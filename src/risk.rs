@@ -0,0 +1,284 @@
+//! 下单前风控检查
+//!
+//! [`RiskChecker`] 在签名前检查订单，一次返回全部违规项，调用方可据此调整订单
+//! 或直接拒绝。市价单以 `limit_price`（滑点参考价）计算名义价值与价格偏离。
+//! 设置 [`FeeSchedule`] 后名义价值计入按吃单费率估算的手续费。
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fees::{FeeSchedule, Liquidity};
+use crate::order::PlaceOrderParams;
+use crate::types::OrderSide;
+
+/// 风控限额，None 表示不限制
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskLimits {
+    /// 单笔订单名义价值上限（价格 × 数量）
+    pub max_order_notional: Option<u128>,
+    /// 成交后持仓数量绝对值上限
+    pub max_position: Option<u64>,
+    /// 订单价格相对中间价的最大偏离比例，例如 0.05 表示 5%
+    pub price_band: Option<f64>,
+    /// 同时挂单数上限
+    pub max_open_orders: Option<usize>,
+}
+
+/// 检查订单所需的账户与行情状态
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RiskContext {
+    /// 当前持仓，正数为多头
+    pub position: i64,
+    /// 当前中间价；未知时跳过价格偏离检查
+    pub mid_price: Option<f64>,
+    /// 当前挂单数
+    pub open_orders: usize,
+}
+
+/// 风控违规项
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskViolation {
+    OrderNotional { notional: u128, limit: u128 },
+    Position { resulting: i128, limit: u64 },
+    PriceBand { price: u64, mid: f64, limit: f64 },
+    OpenOrders { open: usize, limit: usize },
+}
+
+impl fmt::Display for RiskViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskViolation::OrderNotional { notional, limit } => {
+                write!(f, "order notional {} exceeds {}", notional, limit)
+            }
+            RiskViolation::Position { resulting, limit } => {
+                write!(f, "resulting position {} exceeds {}", resulting, limit)
+            }
+            RiskViolation::PriceBand { price, mid, limit } => write!(
+                f,
+                "price {} deviates more than {:.2}% from mid {}",
+                price,
+                limit * 100.0,
+                mid
+            ),
+            RiskViolation::OpenOrders { open, limit } => {
+                write!(f, "{} open orders reach limit {}", open, limit)
+            }
+        }
+    }
+}
+
+/// 下单前风控检查
+#[derive(Debug, Clone, Default)]
+pub struct RiskChecker {
+    limits: RiskLimits,
+    fees: Option<FeeSchedule>,
+}
+
+impl RiskChecker {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self { limits, fees: None }
+    }
+
+    /// 名义价值检查计入按 `schedule` 吃单费率估算的手续费
+    pub fn with_fees(mut self, schedule: FeeSchedule) -> Self {
+        self.fees = Some(schedule);
+        self
+    }
+
+    /// 订单的名义价值，设置费率表时加上估算的吃单手续费（向上取整，返佣不抵扣）
+    pub fn notional(&self, order: &PlaceOrderParams) -> u128 {
+        let notional = order.limit_price as u128 * order.amount as u128;
+        let rate = self
+            .fees
+            .as_ref()
+            .map_or(0, |fees| fees.rate_bps(Liquidity::Taker).max(0));
+        notional + (notional * rate as u128).div_ceil(10_000)
+    }
+
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// 检查订单，返回全部违规项；为空表示通过
+    pub fn check(&self, order: &PlaceOrderParams, context: &RiskContext) -> Vec<RiskViolation> {
+        let mut violations = Vec::new();
+        let limits = &self.limits;
+
+        if let Some(limit) = limits.max_order_notional {
+            let notional = self.notional(order);
+            if notional > limit {
+                violations.push(RiskViolation::OrderNotional { notional, limit });
+            }
+        }
+        if let Some(limit) = limits.max_position {
+            // i128 容纳任意 u64 数量与 i64 持仓之和，不会溢出
+            let position = context.position as i128;
+            let resulting = match order.side {
+                OrderSide::Buy => position + order.amount as i128,
+                OrderSide::Sell => position - order.amount as i128,
+            };
+            // 不增加敞口的订单总是允许
+            if resulting.unsigned_abs() > limit as u128
+                && resulting.unsigned_abs() > position.unsigned_abs()
+            {
+                violations.push(RiskViolation::Position { resulting, limit });
+            }
+        }
+        if let (Some(limit), Some(mid)) = (limits.price_band, context.mid_price) {
+            if mid > 0.0 && ((order.limit_price as f64 - mid) / mid).abs() > limit {
+                violations.push(RiskViolation::PriceBand {
+                    price: order.limit_price,
+                    mid,
+                    limit,
+                });
+            }
+        }
+        if let Some(limit) = limits.max_open_orders {
+            if context.open_orders >= limit {
+                violations.push(RiskViolation::OpenOrders {
+                    open: context.open_orders,
+                    limit,
+                });
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker() -> RiskChecker {
+        RiskChecker::new(RiskLimits {
+            max_order_notional: Some(10_000),
+            max_position: Some(50),
+            price_band: Some(0.05),
+            max_open_orders: Some(3),
+        })
+    }
+
+    #[test]
+    fn passing_order_has_no_violations() {
+        let order = PlaceOrderParams::limit(OrderSide::Buy, 10, 100);
+        let context = RiskContext {
+            position: 0,
+            mid_price: Some(101.0),
+            open_orders: 0,
+        };
+        assert!(checker().check(&order, &context).is_empty());
+    }
+
+    #[test]
+    fn taker_fees_count_toward_notional() {
+        let order = PlaceOrderParams::limit(OrderSide::Buy, 50, 200);
+        let context = RiskContext::default();
+        assert!(checker().check(&order, &context).is_empty());
+        // 10_000 的名义价值加上 5 基点吃单费后超过限额
+        let with_fees = checker().with_fees(FeeSchedule::flat(-1, 5));
+        assert_eq!(with_fees.notional(&order), 10_005);
+        assert_eq!(
+            with_fees.check(&order, &context),
+            vec![RiskViolation::OrderNotional {
+                notional: 10_005,
+                limit: 10_000
+            }]
+        );
+        // 返佣费率不降低名义价值
+        let rebate = checker().with_fees(FeeSchedule::flat(-1, -1));
+        assert_eq!(rebate.notional(&order), 10_000);
+    }
+
+    #[test]
+    fn reports_every_violation() {
+        let order = PlaceOrderParams::limit(OrderSide::Buy, 60, 200);
+        let context = RiskContext {
+            position: 0,
+            mid_price: Some(100.0),
+            open_orders: 3,
+        };
+        let violations = checker().check(&order, &context);
+        assert_eq!(
+            violations,
+            vec![
+                RiskViolation::OrderNotional {
+                    notional: 12_000,
+                    limit: 10_000
+                },
+                RiskViolation::Position {
+                    resulting: 60,
+                    limit: 50
+                },
+                RiskViolation::PriceBand {
+                    price: 200,
+                    mid: 100.0,
+                    limit: 0.05
+                },
+                RiskViolation::OpenOrders { open: 3, limit: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn reducing_orders_pass_position_limit() {
+        let order = PlaceOrderParams::limit(OrderSide::Sell, 10, 100);
+        let context = RiskContext {
+            position: 70,
+            ..RiskContext::default()
+        };
+        assert!(checker().check(&order, &context).is_empty());
+        // 反手后敞口仍小于当前持仓
+        let flip = PlaceOrderParams::limit(OrderSide::Sell, 130, 1);
+        assert!(checker().check(&flip, &context).is_empty());
+        let small = RiskContext {
+            position: 10,
+            ..RiskContext::default()
+        };
+        assert_eq!(
+            checker().check(&flip, &small),
+            vec![RiskViolation::Position {
+                resulting: -120,
+                limit: 50
+            }]
+        );
+    }
+
+    #[test]
+    fn oversized_amounts_do_not_overflow() {
+        let context = RiskContext {
+            position: -10,
+            ..RiskContext::default()
+        };
+        let buy = PlaceOrderParams::limit(OrderSide::Buy, u64::MAX, 1);
+        let sell = PlaceOrderParams::limit(OrderSide::Sell, u64::MAX, 1);
+        let position = |order| {
+            checker()
+                .check(order, &context)
+                .into_iter()
+                .find(|violation| matches!(violation, RiskViolation::Position { .. }))
+        };
+        assert_eq!(
+            position(&buy),
+            Some(RiskViolation::Position {
+                resulting: u64::MAX as i128 - 10,
+                limit: 50
+            })
+        );
+        assert_eq!(
+            position(&sell),
+            Some(RiskViolation::Position {
+                resulting: -(u64::MAX as i128) - 10,
+                limit: 50
+            })
+        );
+    }
+
+    #[test]
+    fn limits_load_from_config() {
+        let limits: RiskLimits = serde_json::from_str(r#"{"max_open_orders":5}"#).unwrap();
+        assert_eq!(limits.max_open_orders, Some(5));
+        assert_eq!(limits.price_band, None);
+    }
+}
@@ -0,0 +1,613 @@
+//! Kafka 事件接收端（`kafka` feature）
+//!
+//! 订单更新、成交、订单簿更新与公共成交分别发布到 [`KafkaTopics`] 中的四个 topic，
+//! 消息 key 为市场名，按 key 的 CRC32 选择分区，同一市场的事件保持顺序。
+//! 消息头 `lightpool-event` 为事件类型（`order` / `fill` / `book` / `trade`），`content-type` 为编码格式。
+//!
+//! - [`KafkaFormat::Json`]：与推送消息相同的 JSON
+//! - [`KafkaFormat::Avro`]：Avro 单对象编码（`C3 01` + 8 字节 schema 指纹 + 数据），
+//!   schema 见 [`ORDER_UPDATE_SCHEMA`] 等常量；u64 字段为 `decimal(20, 0)`，时间为 `timestamp-millis`
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use apache_avro::types::Value;
+use apache_avro::{Decimal, GenericSingleObjectWriter, Schema};
+use futures_util::future::try_join_all;
+use rskafka::chrono::{TimeZone, Utc};
+use rskafka::client::partition::{Compression, PartitionClient, UnknownTopicHandling};
+use rskafka::client::{Client, ClientBuilder};
+use rskafka::record::Record;
+use serde::{Deserialize, Serialize};
+
+use super::{EventSink, TradingEvent};
+use crate::error::{Error, Result};
+use crate::types::OrderSide;
+use crate::ws::{OrderStatus, PriceLevel};
+
+/// 订单更新的 Avro schema
+pub const ORDER_UPDATE_SCHEMA: &str = r#"{
+  "type": "record", "name": "OrderUpdate", "namespace": "lightpool",
+  "fields": [
+    {"name": "account", "type": "string"},
+    {"name": "market", "type": "string"},
+    {"name": "order_id", "type": "string"},
+    {"name": "side", "type": {"type": "enum", "name": "OrderSide", "symbols": ["Buy", "Sell"]}},
+    {"name": "status", "type": {"type": "enum", "name": "OrderStatus",
+      "symbols": ["acked", "partially_filled", "filled", "cancelled", "rejected", "expired"]}},
+    {"name": "price", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+    {"name": "amount", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+    {"name": "filled", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+    {"name": "ts", "type": {"type": "long", "logicalType": "timestamp-millis"}}
+  ]
+}"#;
+
+/// 账户成交的 Avro schema
+pub const FILL_SCHEMA: &str = r#"{
+  "type": "record", "name": "Fill", "namespace": "lightpool",
+  "fields": [
+    {"name": "account", "type": "string"},
+    {"name": "market", "type": "string"},
+    {"name": "order_id", "type": "string"},
+    {"name": "side", "type": {"type": "enum", "name": "OrderSide", "symbols": ["Buy", "Sell"]}},
+    {"name": "price", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+    {"name": "size", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+    {"name": "fee", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+    {"name": "is_maker", "type": "boolean"},
+    {"name": "trade_id", "type": ["null",
+      {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}], "default": null},
+    {"name": "ts", "type": {"type": "long", "logicalType": "timestamp-millis"}}
+  ]
+}"#;
+
+/// 订单簿更新的 Avro schema
+pub const ORDERBOOK_UPDATE_SCHEMA: &str = r#"{
+  "type": "record", "name": "OrderbookUpdate", "namespace": "lightpool",
+  "fields": [
+    {"name": "market", "type": "string"},
+    {"name": "seq", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+    {"name": "snapshot", "type": "boolean"},
+    {"name": "bids", "type": {"type": "array", "items": {
+      "type": "record", "name": "PriceLevel",
+      "fields": [
+        {"name": "price", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+        {"name": "size", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}}
+      ]}}},
+    {"name": "asks", "type": {"type": "array", "items": "PriceLevel"}},
+    {"name": "ts", "type": {"type": "long", "logicalType": "timestamp-millis"}}
+  ]
+}"#;
+
+/// 公共成交的 Avro schema
+pub const TRADE_SCHEMA: &str = r#"{
+  "type": "record", "name": "Trade", "namespace": "lightpool",
+  "fields": [
+    {"name": "market", "type": "string"},
+    {"name": "price", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+    {"name": "size", "type": {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}},
+    {"name": "side", "type": {"type": "enum", "name": "OrderSide", "symbols": ["Buy", "Sell"]}},
+    {"name": "trade_id", "type": ["null",
+      {"type": "bytes", "logicalType": "decimal", "precision": 20, "scale": 0}], "default": null},
+    {"name": "ts", "type": {"type": "long", "logicalType": "timestamp-millis"}}
+  ]
+}"#;
+
+const STATUS_SYMBOLS: [(OrderStatus, &str); 6] = [
+    (OrderStatus::Acked, "acked"),
+    (OrderStatus::PartiallyFilled, "partially_filled"),
+    (OrderStatus::Filled, "filled"),
+    (OrderStatus::Cancelled, "cancelled"),
+    (OrderStatus::Rejected, "rejected"),
+    (OrderStatus::Expired, "expired"),
+];
+
+fn kafka_error(e: rskafka::client::error::Error) -> Error {
+    Error::Network(format!("kafka: {}", e))
+}
+
+fn avro_error(e: apache_avro::Error) -> Error {
+    Error::Validation(format!("avro encoding failed: {}", e))
+}
+
+/// 消息编码格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KafkaFormat {
+    #[default]
+    Json,
+    Avro,
+}
+
+impl KafkaFormat {
+    fn content_type(&self) -> &'static str {
+        match self {
+            KafkaFormat::Json => "application/json",
+            KafkaFormat::Avro => "application/vnd.apache.avro+binary",
+        }
+    }
+}
+
+/// 各类事件发布到的 topic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KafkaTopics {
+    pub orders: String,
+    pub fills: String,
+    pub book: String,
+    pub trades: String,
+}
+
+impl Default for KafkaTopics {
+    fn default() -> Self {
+        Self {
+            orders: "lightpool.orders".to_string(),
+            fills: "lightpool.fills".to_string(),
+            book: "lightpool.book".to_string(),
+            trades: "lightpool.trades".to_string(),
+        }
+    }
+}
+
+/// Kafka 连接与发布设置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KafkaConfig {
+    /// 引导 broker，如 `localhost:9092`
+    pub brokers: Vec<String>,
+    #[serde(default)]
+    pub topics: KafkaTopics,
+    #[serde(default)]
+    pub format: KafkaFormat,
+    /// 以 gzip 压缩发送的批次
+    #[serde(default)]
+    pub gzip: bool,
+}
+
+impl KafkaConfig {
+    /// 默认 topic、JSON 编码、不压缩
+    pub fn new(brokers: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            brokers: brokers.into_iter().map(Into::into).collect(),
+            topics: KafkaTopics::default(),
+            format: KafkaFormat::Json,
+            gzip: false,
+        }
+    }
+
+    pub fn format(mut self, format: KafkaFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// 编码后的一条消息
+struct Encoded {
+    topic: String,
+    key: String,
+    record: Record,
+}
+
+fn units(value: u64) -> Value {
+    // 大端补码，首字节为 0 以保证非负
+    let mut bytes = vec![0];
+    bytes.extend_from_slice(&value.to_be_bytes());
+    Value::Decimal(Decimal::from(bytes))
+}
+
+fn millis(ts: u64) -> Value {
+    Value::TimestampMillis(i64::try_from(ts).unwrap_or(i64::MAX))
+}
+
+fn side(side: OrderSide) -> Value {
+    match side {
+        OrderSide::Buy => Value::Enum(0, "Buy".to_string()),
+        OrderSide::Sell => Value::Enum(1, "Sell".to_string()),
+    }
+}
+
+fn optional_units(value: Option<u64>) -> Value {
+    match value {
+        Some(value) => Value::Union(1, Box::new(units(value))),
+        None => Value::Union(0, Box::new(Value::Null)),
+    }
+}
+
+fn record(fields: Vec<(&str, Value)>) -> Value {
+    Value::Record(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )
+}
+
+fn levels(levels: &[PriceLevel]) -> Value {
+    Value::Array(
+        levels
+            .iter()
+            .map(|level| {
+                record(vec![
+                    ("price", units(level.price)),
+                    ("size", units(level.size)),
+                ])
+            })
+            .collect(),
+    )
+}
+
+/// 事件的 Avro 值
+fn avro_value(event: &TradingEvent) -> Value {
+    match event {
+        TradingEvent::Order(update) => {
+            let (index, symbol) = STATUS_SYMBOLS
+                .iter()
+                .enumerate()
+                .find(|(_, (status, _))| *status == update.status)
+                .map(|(i, (_, symbol))| (i as u32, *symbol))
+                .expect("every order status has a symbol");
+            record(vec![
+                ("account", Value::String(update.account.clone())),
+                ("market", Value::String(update.market.clone())),
+                ("order_id", Value::String(update.order_id.clone())),
+                ("side", side(update.side)),
+                ("status", Value::Enum(index, symbol.to_string())),
+                ("price", units(update.price)),
+                ("amount", units(update.amount)),
+                ("filled", units(update.filled)),
+                ("ts", millis(update.ts)),
+            ])
+        }
+        TradingEvent::Fill(fill) => record(vec![
+            ("account", Value::String(fill.account.clone())),
+            ("market", Value::String(fill.market.clone())),
+            ("order_id", Value::String(fill.order_id.clone())),
+            ("side", side(fill.side)),
+            ("price", units(fill.price)),
+            ("size", units(fill.size)),
+            ("fee", units(fill.fee)),
+            ("is_maker", Value::Boolean(fill.is_maker)),
+            ("trade_id", optional_units(fill.trade_id)),
+            ("ts", millis(fill.ts)),
+        ]),
+        TradingEvent::Book(update) => record(vec![
+            ("market", Value::String(update.market.clone())),
+            ("seq", units(update.seq)),
+            ("snapshot", Value::Boolean(update.snapshot)),
+            ("bids", levels(&update.bids)),
+            ("asks", levels(&update.asks)),
+            ("ts", millis(update.ts)),
+        ]),
+        TradingEvent::Trade(trade) => record(vec![
+            ("market", Value::String(trade.market.clone())),
+            ("price", units(trade.price)),
+            ("size", units(trade.size)),
+            ("side", side(trade.side)),
+            ("trade_id", optional_units(trade.trade_id)),
+            ("ts", millis(trade.ts)),
+        ]),
+    }
+}
+
+/// 各类事件的 Avro 单对象编码器
+struct AvroWriters {
+    orders: GenericSingleObjectWriter,
+    fills: GenericSingleObjectWriter,
+    book: GenericSingleObjectWriter,
+    trades: GenericSingleObjectWriter,
+}
+
+impl AvroWriters {
+    fn new() -> Result<Self> {
+        let writer = |schema: &str| {
+            let schema = Schema::parse_str(schema).map_err(avro_error)?;
+            GenericSingleObjectWriter::new_with_capacity(&schema, 256).map_err(avro_error)
+        };
+        Ok(Self {
+            orders: writer(ORDER_UPDATE_SCHEMA)?,
+            fills: writer(FILL_SCHEMA)?,
+            book: writer(ORDERBOOK_UPDATE_SCHEMA)?,
+            trades: writer(TRADE_SCHEMA)?,
+        })
+    }
+
+    fn encode(&mut self, event: &TradingEvent) -> Result<Vec<u8>> {
+        let writer = match event {
+            TradingEvent::Order(_) => &mut self.orders,
+            TradingEvent::Fill(_) => &mut self.fills,
+            TradingEvent::Book(_) => &mut self.book,
+            TradingEvent::Trade(_) => &mut self.trades,
+        };
+        let mut out = Vec::new();
+        writer
+            .write_value(avro_value(event), &mut out)
+            .map_err(avro_error)?;
+        Ok(out)
+    }
+}
+
+/// 把事件编码为 Kafka 消息
+struct Encoder {
+    topics: KafkaTopics,
+    format: KafkaFormat,
+    avro: Option<AvroWriters>,
+}
+
+impl Encoder {
+    fn new(topics: KafkaTopics, format: KafkaFormat) -> Result<Self> {
+        let avro = match format {
+            KafkaFormat::Json => None,
+            KafkaFormat::Avro => Some(AvroWriters::new()?),
+        };
+        Ok(Self {
+            topics,
+            format,
+            avro,
+        })
+    }
+
+    fn encode(&mut self, event: &TradingEvent) -> Result<Encoded> {
+        let (kind, topic, market, ts, json) = match event {
+            TradingEvent::Order(update) => (
+                "order",
+                &self.topics.orders,
+                &update.market,
+                update.ts,
+                serde_json::to_vec(update),
+            ),
+            TradingEvent::Fill(fill) => (
+                "fill",
+                &self.topics.fills,
+                &fill.market,
+                fill.ts,
+                serde_json::to_vec(fill),
+            ),
+            TradingEvent::Book(update) => (
+                "book",
+                &self.topics.book,
+                &update.market,
+                update.ts,
+                serde_json::to_vec(update),
+            ),
+            TradingEvent::Trade(trade) => (
+                "trade",
+                &self.topics.trades,
+                &trade.market,
+                trade.ts,
+                serde_json::to_vec(trade),
+            ),
+        };
+        let value = match &mut self.avro {
+            Some(avro) => avro.encode(event)?,
+            None => json?,
+        };
+        let headers = BTreeMap::from([
+            ("lightpool-event".to_string(), kind.as_bytes().to_vec()),
+            (
+                "content-type".to_string(),
+                self.format.content_type().as_bytes().to_vec(),
+            ),
+        ]);
+        let timestamp = Utc
+            .timestamp_millis_opt(i64::try_from(ts).unwrap_or(i64::MAX))
+            .single()
+            .unwrap_or_default();
+        Ok(Encoded {
+            topic: topic.clone(),
+            key: market.clone(),
+            record: Record {
+                key: Some(market.as_bytes().to_vec()),
+                value: Some(value),
+                headers,
+                timestamp,
+            },
+        })
+    }
+}
+
+/// 按 key 选择分区
+fn partition_for(key: &str, partitions: i32) -> i32 {
+    (crc32fast::hash(key.as_bytes()) % partitions.max(1) as u32) as i32
+}
+
+/// 把交易事件发布到 Kafka 的 [`EventSink`]
+pub struct KafkaSink {
+    client: Client,
+    encoder: Encoder,
+    compression: Compression,
+    partition_counts: HashMap<String, i32>,
+    partitions: HashMap<(String, i32), Arc<PartitionClient>>,
+}
+
+impl KafkaSink {
+    /// 连接 broker；topic 需事先创建
+    pub async fn connect(config: KafkaConfig) -> Result<Self> {
+        let client = ClientBuilder::new(config.brokers)
+            .client_id("lightpool")
+            .build()
+            .await
+            .map_err(kafka_error)?;
+        Ok(Self {
+            client,
+            encoder: Encoder::new(config.topics, config.format)?,
+            compression: if config.gzip {
+                Compression::Gzip
+            } else {
+                Compression::NoCompression
+            },
+            partition_counts: HashMap::new(),
+            partitions: HashMap::new(),
+        })
+    }
+
+    async fn partition_count(&mut self, topic: &str) -> Result<i32> {
+        if let Some(count) = self.partition_counts.get(topic) {
+            return Ok(*count);
+        }
+        let topics = self.client.list_topics().await.map_err(kafka_error)?;
+        for listed in topics {
+            self.partition_counts
+                .insert(listed.name, listed.partitions.len() as i32);
+        }
+        match self.partition_counts.get(topic) {
+            Some(count) if *count > 0 => Ok(*count),
+            _ => Err(Error::Validation(format!(
+                "kafka topic not found: {}",
+                topic
+            ))),
+        }
+    }
+
+    async fn partition(&mut self, topic: &str, partition: i32) -> Result<Arc<PartitionClient>> {
+        let key = (topic.to_string(), partition);
+        if let Some(client) = self.partitions.get(&key) {
+            return Ok(Arc::clone(client));
+        }
+        let client = Arc::new(
+            self.client
+                .partition_client(topic, partition, UnknownTopicHandling::Retry)
+                .await
+                .map_err(kafka_error)?,
+        );
+        self.partitions.insert(key, Arc::clone(&client));
+        Ok(client)
+    }
+}
+
+impl EventSink for KafkaSink {
+    async fn write(&mut self, events: &[TradingEvent]) -> Result<()> {
+        // 按分区分组，组内保持事件顺序
+        let mut batches: Vec<(Arc<PartitionClient>, Vec<Record>)> = Vec::new();
+        let mut index: HashMap<(String, i32), usize> = HashMap::new();
+        for event in events {
+            let encoded = self.encoder.encode(event)?;
+            let count = self.partition_count(&encoded.topic).await?;
+            let partition = partition_for(&encoded.key, count);
+            let slot = match index.get(&(encoded.topic.clone(), partition)) {
+                Some(slot) => *slot,
+                None => {
+                    let client = self.partition(&encoded.topic, partition).await?;
+                    batches.push((client, Vec::new()));
+                    index.insert((encoded.topic, partition), batches.len() - 1);
+                    batches.len() - 1
+                }
+            };
+            batches[slot].1.push(encoded.record);
+        }
+        let compression = self.compression;
+        try_join_all(
+            batches
+                .into_iter()
+                .map(|(client, records)| async move { client.produce(records, compression).await }),
+        )
+        .await
+        .map_err(kafka_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::{Fill, OrderUpdate, OrderbookUpdate};
+    use apache_avro::from_avro_datum;
+    use apache_avro::rabin::Rabin;
+
+    fn fill(trade_id: Option<u64>) -> Fill {
+        Fill {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: "0xaa".to_string(),
+            side: OrderSide::Sell,
+            price: u64::MAX,
+            size: 2,
+            fee: 3,
+            is_maker: false,
+            trade_id,
+            ts: 1_700_000_000_000,
+        }
+    }
+
+    fn decode(schema: &str, bytes: &[u8]) -> Value {
+        let schema = Schema::parse_str(schema).unwrap();
+        assert_eq!(&bytes[..2], &[0xc3, 0x01]);
+        assert_eq!(&bytes[2..10], &schema.fingerprint::<Rabin>().bytes[..]);
+        from_avro_datum(&schema, &mut &bytes[10..], None).unwrap()
+    }
+
+    fn field<'a>(value: &'a Value, name: &str) -> &'a Value {
+        let Value::Record(fields) = value else {
+            panic!("not a record: {:?}", value);
+        };
+        &fields.iter().find(|(field, _)| field == name).unwrap().1
+    }
+
+    #[test]
+    fn json_messages_are_keyed_by_market() {
+        let mut encoder = Encoder::new(KafkaTopics::default(), KafkaFormat::Json).unwrap();
+        let encoded = encoder.encode(&TradingEvent::Fill(fill(Some(1)))).unwrap();
+        assert_eq!(encoded.topic, "lightpool.fills");
+        assert_eq!(encoded.record.key.as_deref(), Some(&b"BTC-USDC"[..]));
+        assert_eq!(encoded.record.headers["lightpool-event"], b"fill");
+        assert_eq!(
+            encoded.record.timestamp.timestamp_millis(),
+            1_700_000_000_000
+        );
+        let decoded: Fill = serde_json::from_slice(encoded.record.value.as_ref().unwrap()).unwrap();
+        assert_eq!(decoded, fill(Some(1)));
+    }
+
+    #[test]
+    fn avro_messages_use_single_object_encoding() {
+        let mut encoder = Encoder::new(KafkaTopics::default(), KafkaFormat::Avro).unwrap();
+
+        let encoded = encoder.encode(&TradingEvent::Fill(fill(None))).unwrap();
+        let value = decode(FILL_SCHEMA, encoded.record.value.as_ref().unwrap());
+        assert_eq!(field(&value, "price"), &units(u64::MAX));
+        assert_eq!(
+            field(&value, "trade_id"),
+            &Value::Union(0, Box::new(Value::Null))
+        );
+
+        let update = OrderUpdate {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: "0xaa".to_string(),
+            side: OrderSide::Buy,
+            status: OrderStatus::PartiallyFilled,
+            price: 100,
+            amount: 10,
+            filled: 4,
+            ts: 5,
+        };
+        let encoded = encoder.encode(&TradingEvent::Order(update)).unwrap();
+        let value = decode(ORDER_UPDATE_SCHEMA, encoded.record.value.as_ref().unwrap());
+        assert_eq!(
+            field(&value, "status"),
+            &Value::Enum(1, "partially_filled".to_string())
+        );
+
+        let book = OrderbookUpdate {
+            market: "BTC-USDC".to_string(),
+            seq: 3,
+            snapshot: false,
+            bids: vec![PriceLevel { price: 99, size: 1 }],
+            asks: Vec::new(),
+            ts: 6,
+        };
+        let encoded = encoder.encode(&TradingEvent::Book(book)).unwrap();
+        assert_eq!(encoded.topic, "lightpool.book");
+        let value = decode(
+            ORDERBOOK_UPDATE_SCHEMA,
+            encoded.record.value.as_ref().unwrap(),
+        );
+        let Value::Array(bids) = field(&value, "bids") else {
+            panic!("bids is not an array");
+        };
+        assert_eq!(field(&bids[0], "price"), &units(99));
+    }
+
+    #[test]
+    fn partitions_are_stable_per_market() {
+        assert_eq!(partition_for("BTC-USDC", 6), partition_for("BTC-USDC", 6));
+        assert!((0..6).contains(&partition_for("ETH-USDC", 6)));
+        assert_eq!(partition_for("BTC-USDC", 1), 0);
+    }
+}
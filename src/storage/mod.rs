@@ -0,0 +1,152 @@
+//! 交易记录的持久化存储
+//!
+//! - [`sqlite::SqliteStore`]（`sqlite` feature）把提交的订单、订单更新、成交与交易回执写入单个 SQLite 文件，
+//!   进程重启后可据此重建 [`OrderStore`](crate::order::OrderStore) 与 [`FillHistory`](crate::order::FillHistory)。
+//! - [`postgres::PostgresSink`]（`postgres` feature）实现 [`EventSink`]，把订单、成交与行情事件写入
+//!   Postgres，供分析看板查询。
+//! - [`kafka::KafkaSink`]（`kafka` feature）实现 [`EventSink`]，把同样的事件以 JSON 或 Avro 发布到 Kafka。
+//!
+//! [`forward`] 把事件流按批写入任意 [`EventSink`]。
+
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use std::future::Future;
+
+use futures_util::{Stream, StreamExt};
+
+use crate::error::Result;
+use crate::ws::{AccountEvent, Fill, OrderUpdate, OrderbookUpdate, Trade, WsMessage};
+
+#[cfg(feature = "kafka")]
+pub use kafka::{KafkaConfig, KafkaFormat, KafkaSink, KafkaTopics};
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresSink;
+#[cfg(feature = "sqlite")]
+pub use sqlite::{FillQuery, SqliteStore};
+
+/// 写入事件接收端的交易事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TradingEvent {
+    Order(OrderUpdate),
+    Fill(Fill),
+    Book(OrderbookUpdate),
+    Trade(Trade),
+}
+
+impl TradingEvent {
+    /// 从推送消息中取出交易事件，其他消息返回 None
+    pub fn from_message(message: WsMessage) -> Option<Self> {
+        match message {
+            WsMessage::Order(update) => Some(TradingEvent::Order(update)),
+            WsMessage::Fill(fill) => Some(TradingEvent::Fill(fill)),
+            WsMessage::Book(update) => Some(TradingEvent::Book(update)),
+            WsMessage::Trade(trade) => Some(TradingEvent::Trade(trade)),
+            _ => None,
+        }
+    }
+}
+
+impl From<AccountEvent> for TradingEvent {
+    fn from(event: AccountEvent) -> Self {
+        match event {
+            AccountEvent::Order(update) => TradingEvent::Order(update),
+            AccountEvent::Fill(fill) => TradingEvent::Fill(fill),
+        }
+    }
+}
+
+impl From<OrderbookUpdate> for TradingEvent {
+    fn from(update: OrderbookUpdate) -> Self {
+        TradingEvent::Book(update)
+    }
+}
+
+impl From<Trade> for TradingEvent {
+    fn from(trade: Trade) -> Self {
+        TradingEvent::Trade(trade)
+    }
+}
+
+/// 交易事件的接收端
+pub trait EventSink: Send {
+    /// 写入一批事件；返回成功后事件应已持久化
+    fn write(&mut self, events: &[TradingEvent]) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// 把事件流写入 `sink` 直到流结束，返回写入的事件数
+///
+/// 已就绪的事件合并为一批，每批最多 `max_batch` 条；写入失败时立即返回错误。
+pub async fn forward<S, E>(
+    events: impl Stream<Item = E>,
+    sink: &mut S,
+    max_batch: usize,
+) -> Result<u64>
+where
+    S: EventSink,
+    E: Into<TradingEvent>,
+{
+    let mut batches = std::pin::pin!(events.ready_chunks(max_batch.max(1)));
+    let mut written = 0;
+    while let Some(batch) = batches.next().await {
+        let batch: Vec<TradingEvent> = batch.into_iter().map(Into::into).collect();
+        sink.write(&batch).await?;
+        written += batch.len() as u64;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderSide;
+
+    #[derive(Default)]
+    struct Collect {
+        batches: Vec<Vec<TradingEvent>>,
+    }
+
+    impl EventSink for Collect {
+        async fn write(&mut self, events: &[TradingEvent]) -> Result<()> {
+            self.batches.push(events.to_vec());
+            Ok(())
+        }
+    }
+
+    fn trade(trade_id: u64) -> Trade {
+        Trade {
+            market: "BTC-USDC".to_string(),
+            price: 100,
+            size: 1,
+            side: OrderSide::Buy,
+            trade_id: Some(trade_id),
+            ts: trade_id,
+        }
+    }
+
+    #[tokio::test]
+    async fn ready_events_are_written_in_batches() {
+        let mut sink = Collect::default();
+        let events = futures_util::stream::iter((0..5).map(trade));
+        assert_eq!(forward(events, &mut sink, 2).await.unwrap(), 5);
+        let sizes: Vec<usize> = sink.batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert_eq!(sink.batches[2][0], TradingEvent::Trade(trade(4)));
+    }
+
+    #[test]
+    fn only_trading_messages_are_converted() {
+        assert_eq!(
+            TradingEvent::from_message(WsMessage::Trade(trade(1))),
+            Some(TradingEvent::Trade(trade(1)))
+        );
+        assert_eq!(
+            TradingEvent::from_message(WsMessage::Unknown(serde_json::json!({}))),
+            None
+        );
+    }
+}
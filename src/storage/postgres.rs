@@ -0,0 +1,391 @@
+//! Postgres 事件接收端（`postgres` feature）
+//!
+//! 表建在独立的 schema（默认 `lightpool`）下，每类事件一张表，按 `(market, ts)` 建索引：
+//!
+//! - `order_updates`：账户订单更新
+//! - `fills`：账户成交，`(order_id, trade_id)` 唯一
+//! - `trades`：公共成交，`(market, trade_id)` 唯一
+//! - `book_levels`：订单簿快照与增量按档位展开的长表，与 [`export`](crate::export) 的列一致
+//!
+//! 价格与数量为 `NUMERIC(20, 0)`，可容纳完整的 u64；时间为 `TIMESTAMPTZ`。
+//! 每批事件在一个事务中写入，每张表一条语句，行数据以 JSON 数组传入后由 `json_to_recordset` 展开。
+
+use serde::Serialize;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+
+use super::{EventSink, TradingEvent};
+use crate::error::{Error, Result};
+use crate::types::OrderSide;
+use crate::ws::OrderStatus;
+
+/// 默认的 schema 名
+pub const DEFAULT_SCHEMA: &str = "lightpool";
+
+const SCHEMA: &str = "
+CREATE SCHEMA IF NOT EXISTS {schema};
+CREATE TABLE IF NOT EXISTS {schema}.order_updates (
+    id BIGSERIAL PRIMARY KEY,
+    ts TIMESTAMPTZ NOT NULL,
+    account TEXT NOT NULL,
+    market TEXT NOT NULL,
+    order_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    status TEXT NOT NULL,
+    price NUMERIC(20, 0) NOT NULL,
+    amount NUMERIC(20, 0) NOT NULL,
+    filled NUMERIC(20, 0) NOT NULL
+);
+CREATE INDEX IF NOT EXISTS order_updates_market_ts ON {schema}.order_updates (market, ts);
+CREATE INDEX IF NOT EXISTS order_updates_order_id ON {schema}.order_updates (order_id);
+CREATE TABLE IF NOT EXISTS {schema}.fills (
+    id BIGSERIAL PRIMARY KEY,
+    ts TIMESTAMPTZ NOT NULL,
+    account TEXT NOT NULL,
+    market TEXT NOT NULL,
+    order_id TEXT NOT NULL,
+    trade_id NUMERIC(20, 0),
+    side TEXT NOT NULL,
+    price NUMERIC(20, 0) NOT NULL,
+    size NUMERIC(20, 0) NOT NULL,
+    fee NUMERIC(20, 0) NOT NULL,
+    is_maker BOOLEAN NOT NULL,
+    UNIQUE (order_id, trade_id)
+);
+CREATE INDEX IF NOT EXISTS fills_market_ts ON {schema}.fills (market, ts);
+CREATE TABLE IF NOT EXISTS {schema}.trades (
+    id BIGSERIAL PRIMARY KEY,
+    ts TIMESTAMPTZ NOT NULL,
+    market TEXT NOT NULL,
+    trade_id NUMERIC(20, 0),
+    side TEXT NOT NULL,
+    price NUMERIC(20, 0) NOT NULL,
+    size NUMERIC(20, 0) NOT NULL,
+    UNIQUE (market, trade_id)
+);
+CREATE INDEX IF NOT EXISTS trades_market_ts ON {schema}.trades (market, ts);
+CREATE TABLE IF NOT EXISTS {schema}.book_levels (
+    id BIGSERIAL PRIMARY KEY,
+    ts TIMESTAMPTZ NOT NULL,
+    market TEXT NOT NULL,
+    seq NUMERIC(20, 0) NOT NULL,
+    snapshot BOOLEAN NOT NULL,
+    side TEXT NOT NULL,
+    level INTEGER NOT NULL,
+    price NUMERIC(20, 0) NOT NULL,
+    size NUMERIC(20, 0) NOT NULL
+);
+CREATE INDEX IF NOT EXISTS book_levels_market_ts ON {schema}.book_levels (market, ts);
+";
+
+const INSERT_ORDER_UPDATES: &str = "
+INSERT INTO {schema}.order_updates (ts, account, market, order_id, side, status, price, amount, filled)
+SELECT TIMESTAMPTZ 'epoch' + r.ts * INTERVAL '1 millisecond', r.account, r.market, r.order_id,
+       r.side, r.status, r.price, r.amount, r.filled
+FROM json_to_recordset($1::json) AS r(
+    ts BIGINT, account TEXT, market TEXT, order_id TEXT, side TEXT, status TEXT,
+    price NUMERIC, amount NUMERIC, filled NUMERIC
+)";
+
+const INSERT_FILLS: &str = "
+INSERT INTO {schema}.fills (ts, account, market, order_id, trade_id, side, price, size, fee, is_maker)
+SELECT TIMESTAMPTZ 'epoch' + r.ts * INTERVAL '1 millisecond', r.account, r.market, r.order_id,
+       r.trade_id, r.side, r.price, r.size, r.fee, r.is_maker
+FROM json_to_recordset($1::json) AS r(
+    ts BIGINT, account TEXT, market TEXT, order_id TEXT, trade_id NUMERIC, side TEXT,
+    price NUMERIC, size NUMERIC, fee NUMERIC, is_maker BOOLEAN
+)
+ON CONFLICT DO NOTHING";
+
+const INSERT_TRADES: &str = "
+INSERT INTO {schema}.trades (ts, market, trade_id, side, price, size)
+SELECT TIMESTAMPTZ 'epoch' + r.ts * INTERVAL '1 millisecond', r.market, r.trade_id, r.side,
+       r.price, r.size
+FROM json_to_recordset($1::json) AS r(
+    ts BIGINT, market TEXT, trade_id NUMERIC, side TEXT, price NUMERIC, size NUMERIC
+)
+ON CONFLICT DO NOTHING";
+
+const INSERT_BOOK_LEVELS: &str = "
+INSERT INTO {schema}.book_levels (ts, market, seq, snapshot, side, level, price, size)
+SELECT TIMESTAMPTZ 'epoch' + r.ts * INTERVAL '1 millisecond', r.market, r.seq, r.snapshot,
+       r.side, r.level, r.price, r.size
+FROM json_to_recordset($1::json) AS r(
+    ts BIGINT, market TEXT, seq NUMERIC, snapshot BOOLEAN, side TEXT, level INTEGER,
+    price NUMERIC, size NUMERIC
+)";
+
+fn sql_error(e: sqlx::Error) -> Error {
+    Error::Io(std::io::Error::other(e))
+}
+
+fn side(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "Buy",
+        OrderSide::Sell => "Sell",
+    }
+}
+
+/// schema 名只允许小写字母、数字与下划线，且不以数字开头
+fn check_schema(name: &str) -> Result<()> {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Validation(format!("invalid schema name: {}", name)))
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct OrderRow<'a> {
+    ts: u64,
+    account: &'a str,
+    market: &'a str,
+    order_id: &'a str,
+    side: &'static str,
+    status: OrderStatus,
+    price: u64,
+    amount: u64,
+    filled: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct FillRow<'a> {
+    ts: u64,
+    account: &'a str,
+    market: &'a str,
+    order_id: &'a str,
+    trade_id: Option<u64>,
+    side: &'static str,
+    price: u64,
+    size: u64,
+    fee: u64,
+    is_maker: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct TradeRow<'a> {
+    ts: u64,
+    market: &'a str,
+    trade_id: Option<u64>,
+    side: &'static str,
+    price: u64,
+    size: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct LevelRow<'a> {
+    ts: u64,
+    market: &'a str,
+    seq: u64,
+    snapshot: bool,
+    side: &'static str,
+    level: u32,
+    price: u64,
+    size: u64,
+}
+
+/// 一批事件按表拆分后的行
+#[derive(Debug, Default, PartialEq)]
+struct Rows<'a> {
+    orders: Vec<OrderRow<'a>>,
+    fills: Vec<FillRow<'a>>,
+    trades: Vec<TradeRow<'a>>,
+    levels: Vec<LevelRow<'a>>,
+}
+
+impl<'a> Rows<'a> {
+    fn split(events: &'a [TradingEvent]) -> Self {
+        let mut rows = Rows::default();
+        for event in events {
+            match event {
+                TradingEvent::Order(update) => rows.orders.push(OrderRow {
+                    ts: update.ts,
+                    account: &update.account,
+                    market: &update.market,
+                    order_id: &update.order_id,
+                    side: side(update.side),
+                    status: update.status,
+                    price: update.price,
+                    amount: update.amount,
+                    filled: update.filled,
+                }),
+                TradingEvent::Fill(fill) => rows.fills.push(FillRow {
+                    ts: fill.ts,
+                    account: &fill.account,
+                    market: &fill.market,
+                    order_id: &fill.order_id,
+                    trade_id: fill.trade_id,
+                    side: side(fill.side),
+                    price: fill.price,
+                    size: fill.size,
+                    fee: fill.fee,
+                    is_maker: fill.is_maker,
+                }),
+                TradingEvent::Trade(trade) => rows.trades.push(TradeRow {
+                    ts: trade.ts,
+                    market: &trade.market,
+                    trade_id: trade.trade_id,
+                    side: side(trade.side),
+                    price: trade.price,
+                    size: trade.size,
+                }),
+                TradingEvent::Book(update) => {
+                    for (name, levels) in [("bid", &update.bids), ("ask", &update.asks)] {
+                        for (i, entry) in levels.iter().enumerate() {
+                            rows.levels.push(LevelRow {
+                                ts: update.ts,
+                                market: &update.market,
+                                seq: update.seq,
+                                snapshot: update.snapshot,
+                                side: name,
+                                level: i as u32,
+                                price: entry.price,
+                                size: entry.size,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        rows
+    }
+}
+
+/// 把交易事件写入 Postgres 的 [`EventSink`]
+#[derive(Debug, Clone)]
+pub struct PostgresSink {
+    pool: PgPool,
+    schema: String,
+}
+
+impl PostgresSink {
+    /// 连接数据库，在默认 schema 下建表
+    pub async fn connect(url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(4)
+            .connect(url)
+            .await
+            .map_err(sql_error)?;
+        Self::with_pool(pool, DEFAULT_SCHEMA).await
+    }
+
+    /// 使用已有连接池，在 `schema` 下建表（已存在时跳过）
+    pub async fn with_pool(pool: PgPool, schema: &str) -> Result<Self> {
+        check_schema(schema)?;
+        let sink = Self {
+            pool,
+            schema: schema.to_string(),
+        };
+        sqlx::raw_sql(&sink.sql(SCHEMA))
+            .execute(&sink.pool)
+            .await
+            .map_err(sql_error)?;
+        Ok(sink)
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    fn sql(&self, template: &str) -> String {
+        template.replace("{schema}", &self.schema)
+    }
+
+    async fn insert<T: Serialize>(
+        &self,
+        tx: &mut sqlx::PgConnection,
+        template: &str,
+        rows: &[T],
+    ) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        sqlx::query(&self.sql(template))
+            .bind(serde_json::to_string(rows)?)
+            .execute(tx)
+            .await
+            .map_err(sql_error)?;
+        Ok(())
+    }
+}
+
+impl EventSink for PostgresSink {
+    async fn write(&mut self, events: &[TradingEvent]) -> Result<()> {
+        let rows = Rows::split(events);
+        let mut tx = self.pool.begin().await.map_err(sql_error)?;
+        self.insert(&mut tx, INSERT_ORDER_UPDATES, &rows.orders)
+            .await?;
+        self.insert(&mut tx, INSERT_FILLS, &rows.fills).await?;
+        self.insert(&mut tx, INSERT_TRADES, &rows.trades).await?;
+        self.insert(&mut tx, INSERT_BOOK_LEVELS, &rows.levels)
+            .await?;
+        tx.commit().await.map_err(sql_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::{OrderbookUpdate, PriceLevel, Trade};
+
+    #[test]
+    fn schema_names_are_checked() {
+        assert!(check_schema("lightpool").is_ok());
+        assert!(check_schema("_bot_2").is_ok());
+        assert!(check_schema("").is_err());
+        assert!(check_schema("2fast").is_err());
+        assert!(check_schema("public; drop table x").is_err());
+    }
+
+    #[test]
+    fn book_updates_are_split_into_levels() {
+        let events = vec![
+            TradingEvent::Book(OrderbookUpdate {
+                market: "BTC-USDC".to_string(),
+                seq: 9,
+                snapshot: true,
+                bids: vec![
+                    PriceLevel { price: 99, size: 1 },
+                    PriceLevel { price: 98, size: 2 },
+                ],
+                asks: vec![PriceLevel {
+                    price: 101,
+                    size: 3,
+                }],
+                ts: 5,
+            }),
+            TradingEvent::Trade(Trade {
+                market: "BTC-USDC".to_string(),
+                price: u64::MAX,
+                size: 1,
+                side: OrderSide::Sell,
+                trade_id: None,
+                ts: 6,
+            }),
+        ];
+        let rows = Rows::split(&events);
+        let levels: Vec<(&str, u32, u64)> = rows
+            .levels
+            .iter()
+            .map(|row| (row.side, row.level, row.price))
+            .collect();
+        assert_eq!(
+            levels,
+            vec![("bid", 0, 99), ("bid", 1, 98), ("ask", 0, 101)]
+        );
+        assert_eq!(
+            serde_json::to_string(&rows.trades).unwrap(),
+            r#"[{"ts":6,"market":"BTC-USDC","trade_id":null,"side":"Sell","price":18446744073709551615,"size":1}]"#
+        );
+    }
+}
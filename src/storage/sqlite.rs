@@ -0,0 +1,442 @@
+//! SQLite 存储（`sqlite` feature）
+//!
+//! 每张表保存完整记录的 JSON（`data` 列），另有用于检索的索引列；整数列按 SQLite 的
+//! 有符号 64 位整数存储，价格与数量只在 JSON 中出现，不受范围限制。
+//! 重复的推送按唯一约束忽略：订单更新为 `(order_id, status, filled)`，成交为 `(order_id, trade_id)`，
+//! 未带 `trade_id` 的成交总是写入。
+
+use std::path::Path;
+
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::client::TransactionReceipt;
+use crate::error::{Error, Result};
+use crate::order::{FillHistory, OrderStore, TrackedOrder};
+use crate::ws::{AccountEvent, Fill, OrderUpdate};
+
+const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS orders (
+    cloid TEXT PRIMARY KEY,
+    order_id TEXT,
+    market TEXT NOT NULL,
+    state TEXT NOT NULL,
+    open INTEGER NOT NULL,
+    submitted_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS orders_order_id ON orders (order_id);
+CREATE TABLE IF NOT EXISTS order_updates (
+    id INTEGER PRIMARY KEY,
+    order_id TEXT NOT NULL,
+    market TEXT NOT NULL,
+    status TEXT NOT NULL,
+    filled INTEGER NOT NULL,
+    ts INTEGER NOT NULL,
+    data TEXT NOT NULL,
+    UNIQUE (order_id, status, filled)
+);
+CREATE TABLE IF NOT EXISTS fills (
+    id INTEGER PRIMARY KEY,
+    order_id TEXT NOT NULL,
+    trade_id INTEGER,
+    market TEXT NOT NULL,
+    ts INTEGER NOT NULL,
+    data TEXT NOT NULL,
+    UNIQUE (order_id, trade_id)
+);
+CREATE INDEX IF NOT EXISTS fills_market_ts ON fills (market, ts);
+CREATE TABLE IF NOT EXISTS receipts (
+    digest TEXT PRIMARY KEY,
+    status TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+";
+
+fn sql_error(e: rusqlite::Error) -> Error {
+    Error::Io(std::io::Error::other(e))
+}
+
+/// 时间戳等计数列，超出 i64 时饱和
+fn int(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+/// 枚举的 serde 名称，如 `partially_filled`
+fn label<T: Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(name) => Ok(name),
+        other => Ok(other.to_string()),
+    }
+}
+
+fn upsert_order(conn: &Connection, order: &TrackedOrder) -> Result<()> {
+    let state = order.lifecycle.state();
+    conn.execute(
+        "INSERT INTO orders (cloid, order_id, market, state, open, submitted_at, updated_at, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+         ON CONFLICT (cloid) DO UPDATE SET
+             order_id = excluded.order_id, state = excluded.state, open = excluded.open,
+             updated_at = excluded.updated_at, data = excluded.data",
+        params![
+            order.cloid,
+            order.order_id,
+            order.market,
+            label(&state)?,
+            !state.is_terminal(),
+            int(order.submitted_at),
+            int(order.updated_at),
+            serde_json::to_string(order)?,
+        ],
+    )
+    .map_err(sql_error)?;
+    Ok(())
+}
+
+/// [`SqliteStore::fills`] 的筛选条件，未设置的条件不限制
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FillQuery {
+    pub market: Option<String>,
+    pub order_id: Option<String>,
+    /// 起始时间（毫秒，含）
+    pub since: Option<u64>,
+    /// 截止时间（毫秒，不含）
+    pub until: Option<u64>,
+    /// 最多返回的条数
+    pub limit: Option<usize>,
+}
+
+/// 订单、成交与回执的 SQLite 存储
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// 打开数据库文件，不存在时创建；启用 WAL 以便其他进程只读查询
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path).map_err(sql_error)?;
+        conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+            .map_err(sql_error)?;
+        Self::init(conn)
+    }
+
+    /// 仅在内存中的数据库
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory().map_err(sql_error)?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(sql_error)?;
+        if version > SCHEMA_VERSION {
+            return Err(Error::Validation(format!(
+                "database schema version {} is newer than supported version {}",
+                version, SCHEMA_VERSION
+            )));
+        }
+        conn.execute_batch(SCHEMA).map_err(sql_error)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+            .map_err(sql_error)?;
+        Ok(Self { conn })
+    }
+
+    /// 写入或覆盖一笔订单
+    pub fn save_order(&self, order: &TrackedOrder) -> Result<()> {
+        upsert_order(&self.conn, order)
+    }
+
+    /// 在一个事务中写入 store 中的全部订单
+    pub fn save_order_store(&mut self, store: &OrderStore) -> Result<()> {
+        let tx = self.conn.transaction().map_err(sql_error)?;
+        for order in store.orders() {
+            upsert_order(&tx, order)?;
+        }
+        tx.commit().map_err(sql_error)
+    }
+
+    pub fn order(&self, cloid: &str) -> Result<Option<TrackedOrder>> {
+        let mut orders = self.select(
+            "SELECT data FROM orders WHERE cloid = ?1",
+            vec![Value::Text(cloid.to_string())],
+        )?;
+        Ok(orders.pop())
+    }
+
+    /// 全部订单，按提交时间与 cloid 排序
+    pub fn orders(&self) -> Result<Vec<TrackedOrder>> {
+        self.select(
+            "SELECT data FROM orders ORDER BY submitted_at, cloid",
+            Vec::new(),
+        )
+    }
+
+    /// 未到终态的订单，按提交时间与 cloid 排序
+    pub fn open_orders(&self) -> Result<Vec<TrackedOrder>> {
+        self.select(
+            "SELECT data FROM orders WHERE open ORDER BY submitted_at, cloid",
+            Vec::new(),
+        )
+    }
+
+    /// 由已保存的订单重建 [`OrderStore`]
+    pub fn load_order_store(&self) -> Result<OrderStore> {
+        Ok(OrderStore::from_orders(self.orders()?))
+    }
+
+    /// 记录一条订单更新，重复推送时返回 false
+    pub fn record_update(&self, update: &OrderUpdate) -> Result<bool> {
+        let inserted = self
+            .conn
+            .execute(
+                "INSERT OR IGNORE INTO order_updates (order_id, market, status, filled, ts, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    update.order_id,
+                    update.market,
+                    label(&update.status)?,
+                    int(update.filled),
+                    int(update.ts),
+                    serde_json::to_string(update)?,
+                ],
+            )
+            .map_err(sql_error)?;
+        Ok(inserted > 0)
+    }
+
+    /// 某订单收到的全部更新，按到达顺序
+    pub fn updates(&self, order_id: &str) -> Result<Vec<OrderUpdate>> {
+        self.select(
+            "SELECT data FROM order_updates WHERE order_id = ?1 ORDER BY id",
+            vec![Value::Text(order_id.to_string())],
+        )
+    }
+
+    /// 记录一笔成交，重复推送时返回 false
+    pub fn record_fill(&self, fill: &Fill) -> Result<bool> {
+        let inserted = self
+            .conn
+            .execute(
+                "INSERT OR IGNORE INTO fills (order_id, trade_id, market, ts, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    fill.order_id,
+                    fill.trade_id.map(int),
+                    fill.market,
+                    int(fill.ts),
+                    serde_json::to_string(fill)?,
+                ],
+            )
+            .map_err(sql_error)?;
+        Ok(inserted > 0)
+    }
+
+    /// 按条件查询成交，按时间与写入顺序排序
+    pub fn fills(&self, query: &FillQuery) -> Result<Vec<Fill>> {
+        let mut sql = "SELECT data FROM fills WHERE 1 = 1".to_string();
+        let mut values = Vec::new();
+        if let Some(market) = &query.market {
+            sql.push_str(" AND market = ?");
+            values.push(Value::Text(market.clone()));
+        }
+        if let Some(order_id) = &query.order_id {
+            sql.push_str(" AND order_id = ?");
+            values.push(Value::Text(order_id.clone()));
+        }
+        if let Some(since) = query.since {
+            sql.push_str(" AND ts >= ?");
+            values.push(Value::Integer(int(since)));
+        }
+        if let Some(until) = query.until {
+            sql.push_str(" AND ts < ?");
+            values.push(Value::Integer(int(until)));
+        }
+        sql.push_str(" ORDER BY ts, id");
+        if let Some(limit) = query.limit {
+            sql.push_str(" LIMIT ?");
+            values.push(Value::Integer(i64::try_from(limit).unwrap_or(i64::MAX)));
+        }
+        self.select(&sql, values)
+    }
+
+    /// 由已保存的成交重建 [`FillHistory`]
+    pub fn load_fill_history(&self) -> Result<FillHistory> {
+        let mut history = FillHistory::new();
+        for fill in self.fills(&FillQuery::default())? {
+            history.record(fill);
+        }
+        Ok(history)
+    }
+
+    /// 记录账户事件中的订单更新或成交，重复推送时返回 false
+    pub fn apply(&self, event: &AccountEvent) -> Result<bool> {
+        match event {
+            AccountEvent::Order(update) => self.record_update(update),
+            AccountEvent::Fill(fill) => self.record_fill(fill),
+        }
+    }
+
+    /// 写入或覆盖交易回执，`now` 为记录时间（毫秒）
+    pub fn save_receipt(&self, digest: &str, receipt: &TransactionReceipt, now: u64) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO receipts (digest, status, recorded_at, data) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (digest) DO UPDATE SET
+                     status = excluded.status, recorded_at = excluded.recorded_at, data = excluded.data",
+                params![
+                    digest,
+                    receipt.status,
+                    int(now),
+                    serde_json::to_string(receipt)?
+                ],
+            )
+            .map_err(sql_error)?;
+        Ok(())
+    }
+
+    pub fn receipt(&self, digest: &str) -> Result<Option<TransactionReceipt>> {
+        let mut receipts = self.select(
+            "SELECT data FROM receipts WHERE digest = ?1",
+            vec![Value::Text(digest.to_string())],
+        )?;
+        Ok(receipts.pop())
+    }
+
+    fn select<T: DeserializeOwned>(&self, sql: &str, values: Vec<Value>) -> Result<Vec<T>> {
+        let mut statement = self.conn.prepare_cached(sql).map_err(sql_error)?;
+        let rows = statement
+            .query_map(params_from_iter(values), |row| row.get::<_, String>(0))
+            .map_err(sql_error)?
+            .collect::<rusqlite::Result<Vec<String>>>()
+            .map_err(sql_error)?;
+        rows.iter()
+            .map(|data| Ok(serde_json::from_str(data)?))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::OrderState;
+    use crate::types::OrderSide;
+    use crate::ws::OrderStatus;
+
+    fn update(status: OrderStatus, filled: u64, ts: u64) -> OrderUpdate {
+        OrderUpdate {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: "0xaa".to_string(),
+            side: OrderSide::Buy,
+            status,
+            price: 100,
+            amount: 10,
+            filled,
+            ts,
+        }
+    }
+
+    fn fill(market: &str, trade_id: Option<u64>, ts: u64) -> Fill {
+        Fill {
+            account: "0x01".to_string(),
+            market: market.to_string(),
+            order_id: "0xaa".to_string(),
+            side: OrderSide::Buy,
+            price: u64::MAX,
+            size: 4,
+            fee: 1,
+            is_maker: false,
+            trade_id,
+            ts,
+        }
+    }
+
+    #[test]
+    fn duplicate_pushes_are_ignored_and_fills_are_queryable() {
+        let db = SqliteStore::open_in_memory().unwrap();
+        let ack = AccountEvent::Order(update(OrderStatus::Acked, 0, 1));
+        assert!(db.apply(&ack).unwrap());
+        assert!(!db.apply(&ack).unwrap());
+        assert!(db
+            .record_update(&update(OrderStatus::PartiallyFilled, 4, 2))
+            .unwrap());
+        assert_eq!(db.updates("0xaa").unwrap().len(), 2);
+
+        assert!(db.record_fill(&fill("BTC-USDC", Some(1), 10)).unwrap());
+        assert!(!db.record_fill(&fill("BTC-USDC", Some(1), 10)).unwrap());
+        assert!(db.record_fill(&fill("ETH-USDC", None, 20)).unwrap());
+        assert!(db.record_fill(&fill("BTC-USDC", Some(2), 30)).unwrap());
+
+        let btc = db
+            .fills(&FillQuery {
+                market: Some("BTC-USDC".to_string()),
+                ..FillQuery::default()
+            })
+            .unwrap();
+        assert_eq!(btc.len(), 2);
+        assert_eq!(btc[0].price, u64::MAX);
+        let window = db
+            .fills(&FillQuery {
+                since: Some(10),
+                until: Some(30),
+                limit: Some(1),
+                ..FillQuery::default()
+            })
+            .unwrap();
+        assert_eq!(window, vec![fill("BTC-USDC", Some(1), 10)]);
+        assert_eq!(db.load_fill_history().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn orders_and_receipts_survive_reopen() {
+        let path =
+            std::env::temp_dir().join(format!("lightpool-store-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let mut db = SqliteStore::open(&path).unwrap();
+            let mut store = OrderStore::new();
+            let first = store
+                .submit("BTC-USDC", OrderSide::Buy, 100, 10, 1)
+                .unwrap();
+            store
+                .submit("BTC-USDC", OrderSide::Buy, 100, 10, 2)
+                .unwrap();
+            store.record_digest(&first, "0xdd").unwrap();
+            store
+                .apply_update(&update(OrderStatus::Filled, 10, 3))
+                .unwrap();
+            db.save_order_store(&store).unwrap();
+            let receipt = TransactionReceipt {
+                status: "success".to_string(),
+                events: Vec::new(),
+            };
+            db.save_receipt("0xdd", &receipt, 4).unwrap();
+        }
+        let db = SqliteStore::open(&path).unwrap();
+        assert_eq!(db.orders().unwrap().len(), 2);
+        assert_eq!(db.open_orders().unwrap()[0].cloid, "c1");
+        assert_eq!(
+            db.order("c0").unwrap().unwrap().lifecycle.state(),
+            OrderState::Filled
+        );
+        assert!(db.receipt("0xdd").unwrap().unwrap().is_success());
+        assert!(db.receipt("0xee").unwrap().is_none());
+
+        let mut store = db.load_order_store().unwrap();
+        assert_eq!(store.get_by_order_id("0xaa").unwrap().cloid, "c0");
+        assert_eq!(
+            store.submit("BTC-USDC", OrderSide::Sell, 1, 1, 5).unwrap(),
+            "c2"
+        );
+        drop(db);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+}
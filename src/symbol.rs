@@ -0,0 +1,154 @@
+//! 交易对符号
+//!
+//! 行情频道使用 `BTC-USDC`，RPC 市场名称使用 `BTC/USDC`，外部系统常用 `BTCUSDC`。
+//! [`Symbol`] 解析这些写法并统一以 `BASE-QUOTE` 大写形式输出。
+//! 无分隔符的写法按常见报价币后缀拆分。
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::{Error, Result};
+
+/// 无分隔符写法中可识别的报价币，按长度优先匹配
+const KNOWN_QUOTES: &[&str] = &["USDC", "USDT", "BUSD", "USD", "DAI", "BTC", "ETH"];
+
+const SEPARATORS: &[char] = &['-', '/', '_', ':'];
+
+/// 交易对符号
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol {
+    base: String,
+    quote: String,
+}
+
+impl Symbol {
+    /// 由基础币与报价币创建，统一为大写
+    pub fn new(base: &str, quote: &str) -> Result<Self> {
+        let valid = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric());
+        if !valid(base) || !valid(quote) {
+            return Err(Error::Validation(format!(
+                "invalid symbol: {}/{}",
+                base, quote
+            )));
+        }
+        Ok(Self {
+            base: base.to_ascii_uppercase(),
+            quote: quote.to_ascii_uppercase(),
+        })
+    }
+
+    /// 解析 `BTC-USDC`、`btc/usdc`、`BTC_USDC`、`BTCUSDC` 等写法
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some((base, quote)) = s.split_once(SEPARATORS) {
+            return Self::new(base, quote);
+        }
+        let upper = s.to_ascii_uppercase();
+        let mut quotes: Vec<&str> = KNOWN_QUOTES.to_vec();
+        quotes.sort_by_key(|quote| std::cmp::Reverse(quote.len()));
+        for quote in quotes {
+            if let Some(base) = upper.strip_suffix(quote) {
+                if !base.is_empty() {
+                    return Self::new(base, quote);
+                }
+            }
+        }
+        Err(Error::Validation(format!("unrecognized symbol: {}", s)))
+    }
+
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    pub fn quote(&self) -> &str {
+        &self.quote
+    }
+
+    /// 行情频道使用的市场名，例如 `BTC-USDC`
+    pub fn market(&self) -> String {
+        self.to_string()
+    }
+
+    /// RPC 市场名称写法，例如 `BTC/USDC`
+    pub fn pair(&self) -> String {
+        format!("{}/{}", self.base, self.quote)
+    }
+
+    /// 无分隔符写法，例如 `BTCUSDC`
+    pub fn compact(&self) -> String {
+        format!("{}{}", self.base, self.quote)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.base, self.quote)
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_common_conventions() {
+        let expected = Symbol::new("BTC", "USDC").unwrap();
+        for s in [
+            "BTC-USDC",
+            "btc/usdc",
+            "BTC_USDC",
+            "BTC:USDC",
+            "BTCUSDC",
+            " btcusdc ",
+        ] {
+            assert_eq!(Symbol::parse(s).unwrap(), expected, "{}", s);
+        }
+        assert_eq!(expected.to_string(), "BTC-USDC");
+        assert_eq!(expected.pair(), "BTC/USDC");
+        assert_eq!(expected.compact(), "BTCUSDC");
+    }
+
+    #[test]
+    fn compact_form_prefers_longest_quote() {
+        let symbol = Symbol::parse("ETHUSDT").unwrap();
+        assert_eq!((symbol.base(), symbol.quote()), ("ETH", "USDT"));
+        assert_eq!(Symbol::parse("SOLUSD").unwrap().quote(), "USD");
+        assert_eq!(Symbol::parse("ETHBTC").unwrap().base(), "ETH");
+    }
+
+    #[test]
+    fn rejects_invalid_symbols() {
+        for s in ["", "USDC", "BTC-", "-USDC", "BTC XYZ", "FOOBAR"] {
+            assert!(Symbol::parse(s).is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn serializes_as_canonical_string() {
+        let symbol: Symbol = serde_json::from_str(r#""btc/usdc""#).unwrap();
+        assert_eq!(serde_json::to_string(&symbol).unwrap(), r#""BTC-USDC""#);
+    }
+}
@@ -0,0 +1,165 @@
+//! 运行指标
+//!
+//! SDK 通过 [`metrics`] 门面上报以下指标，应用可安装任意兼容的 recorder，
+//! 也可以调用 [`serve_prometheus`] 启动内置的 Prometheus 抓取端点：
+//!
+//! - `lightpool_orders_submitted_total{side}`：节点已受理的下单交易
+//! - `lightpool_order_rejects_total{reason}`：被拒绝的下单，`reason` 为 `risk`（风控）、`invalid`（参数或限额校验）、
+//!   `rpc`（提交失败）、`failure`（已执行但失败）或 `other`
+//! - `lightpool_rpc_requests_total{method,outcome}` 与 `lightpool_rpc_latency_seconds{method}`：RPC 调用次数与耗时
+//! - `lightpool_ws_lag_seconds`：行情推送相对服务端时间戳的接收延迟
+//! - `lightpool_ws_reconnects_total`：WebSocket 断线重连成功次数
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+
+use crate::client::SubmitResult;
+use crate::error::{Error, Result};
+use crate::types::OrderSide;
+
+pub const ORDERS_SUBMITTED: &str = "lightpool_orders_submitted_total";
+pub const ORDER_REJECTS: &str = "lightpool_order_rejects_total";
+pub const RPC_REQUESTS: &str = "lightpool_rpc_requests_total";
+pub const RPC_LATENCY: &str = "lightpool_rpc_latency_seconds";
+pub const WS_LAG: &str = "lightpool_ws_lag_seconds";
+pub const WS_RECONNECTS: &str = "lightpool_ws_reconnects_total";
+
+/// 耗时类直方图的桶边界（秒）
+const LATENCY_BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// 注册指标说明，安装 recorder 后调用一次
+pub fn describe() {
+    metrics::describe_counter!(ORDERS_SUBMITTED, "order transactions submitted to the node");
+    metrics::describe_counter!(ORDER_REJECTS, "orders rejected before or after submission");
+    metrics::describe_counter!(RPC_REQUESTS, "json-rpc requests by method and outcome");
+    metrics::describe_histogram!(
+        RPC_LATENCY,
+        metrics::Unit::Seconds,
+        "json-rpc request latency"
+    );
+    metrics::describe_histogram!(
+        WS_LAG,
+        metrics::Unit::Seconds,
+        "delay between the server timestamp and local receipt of a push"
+    );
+    metrics::describe_counter!(WS_RECONNECTS, "successful websocket reconnects");
+}
+
+fn builder() -> Result<PrometheusBuilder> {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), LATENCY_BUCKETS)
+        .map_err(prometheus_error)
+}
+
+fn prometheus_error(e: metrics_exporter_prometheus::BuildError) -> Error {
+    Error::Network(format!("prometheus: {}", e))
+}
+
+/// 安装全局 Prometheus recorder 并在 `addr` 上提供 `GET /metrics`；需在 tokio 运行时中调用
+///
+/// 进程内只能安装一个全局 recorder，重复调用返回错误。
+pub fn serve_prometheus(addr: SocketAddr) -> Result<()> {
+    builder()?
+        .with_http_listener(addr)
+        .install()
+        .map_err(prometheus_error)?;
+    describe();
+    Ok(())
+}
+
+/// 安装全局 Prometheus recorder 而不启动 HTTP 端点，由应用自行通过
+/// [`PrometheusHandle::render`] 输出文本格式
+pub fn install_prometheus_recorder() -> Result<PrometheusHandle> {
+    let handle = builder()?.install_recorder().map_err(prometheus_error)?;
+    describe();
+    Ok(handle)
+}
+
+fn side_label(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "buy",
+        OrderSide::Sell => "sell",
+    }
+}
+
+/// 按下单结果记录受理或拒绝
+pub(crate) fn order_result(side: OrderSide, result: &Result<SubmitResult>) {
+    let reason = match result {
+        Ok(submitted) => {
+            metrics::counter!(ORDERS_SUBMITTED, "side" => side_label(side)).increment(1);
+            if !submitted.receipt.is_final() || submitted.receipt.is_success() {
+                return;
+            }
+            "failure"
+        }
+        Err(Error::Risk(_)) => "risk",
+        Err(Error::Validation(_)) => "invalid",
+        Err(Error::Rpc { .. } | Error::Network(_) | Error::Json(_)) => "rpc",
+        Err(_) => "other",
+    };
+    metrics::counter!(ORDER_REJECTS, "reason" => reason).increment(1);
+}
+
+pub(crate) fn rpc_request(method: &str, elapsed: Duration, ok: bool) {
+    let method = method.to_string();
+    let outcome = if ok { "ok" } else { "error" };
+    metrics::counter!(RPC_REQUESTS, "method" => method.clone(), "outcome" => outcome).increment(1);
+    metrics::histogram!(RPC_LATENCY, "method" => method).record(elapsed.as_secs_f64());
+}
+
+/// 本地时钟落后于服务端时记为 0
+pub(crate) fn ws_lag(received_ms: u64, server_ts: u64) {
+    let lag_ms = received_ms.saturating_sub(server_ts);
+    metrics::histogram!(WS_LAG).record(lag_ms as f64 / 1000.0);
+}
+
+pub(crate) fn ws_reconnected() {
+    metrics::counter!(WS_RECONNECTS).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::TransactionReceipt;
+
+    fn submitted(status: &str) -> Result<SubmitResult> {
+        Ok(SubmitResult {
+            digest: "0x01".to_string(),
+            receipt: TransactionReceipt {
+                status: status.to_string(),
+                events: Vec::new(),
+            },
+        })
+    }
+
+    #[test]
+    fn recorded_metrics_are_rendered() {
+        let recorder = builder().unwrap().build_recorder();
+        let handle = recorder.handle();
+        metrics::with_local_recorder(&recorder, || {
+            order_result(OrderSide::Buy, &submitted("success"));
+            order_result(OrderSide::Buy, &submitted("failure"));
+            order_result(OrderSide::Sell, &Err(Error::Risk(Vec::new())));
+            rpc_request("getOrderBook", Duration::from_millis(3), true);
+            ws_lag(1_000, 1_200);
+            ws_reconnected();
+        });
+        let text = handle.render();
+        assert!(text.contains(r#"lightpool_orders_submitted_total{side="buy"} 2"#));
+        assert!(text.contains(r#"lightpool_order_rejects_total{reason="failure"} 1"#));
+        assert!(text.contains(r#"lightpool_order_rejects_total{reason="risk"} 1"#));
+        assert!(!text.contains(r#"side="sell""#));
+        assert!(
+            text.contains(r#"lightpool_rpc_requests_total{method="getOrderBook",outcome="ok"} 1"#)
+        );
+        assert!(text.contains(
+            r#"lightpool_rpc_latency_seconds_bucket{method="getOrderBook",le="0.005"} 1"#
+        ));
+        assert!(text.contains(r#"lightpool_ws_lag_seconds_bucket{le="0.001"} 1"#));
+        assert!(text.contains("lightpool_ws_reconnects_total 1"));
+    }
+}
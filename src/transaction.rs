@@ -0,0 +1,268 @@
+//! 交易构建与签名
+//!
+//! 结构与链上 Rust 定义一致，提交时以 JSON 编码（字节数组编码为整数数组）。
+
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::{address_from_public_key, verify, Signer};
+use crate::error::{Error, Result};
+use crate::types::{Address, ObjectId};
+
+/// 代币合约地址
+pub const TOKEN_CONTRACT: Address = contract(0x01);
+
+/// 现货合约地址
+pub const SPOT_CONTRACT: Address = contract(0x02);
+
+const fn contract(id: u8) -> Address {
+    let mut bytes = [0u8; 32];
+    bytes[0] = id;
+    Address(bytes)
+}
+
+const NAME_LENGTH: usize = 12;
+
+/// 将操作名编码为链上 `Name`（base-32，12个字符，不足补零）
+pub fn action_name(name: &str) -> Result<u64> {
+    if name.len() > NAME_LENGTH {
+        return Err(Error::Validation(format!("Action name too long: {}", name)));
+    }
+    let mut value = 0u64;
+    for c in name.chars() {
+        let digit = match c {
+            '_' => 0,
+            '1'..='5' => c as u64 - '1' as u64 + 1,
+            'a'..='z' => c as u64 - 'a' as u64 + 6,
+            _ => {
+                return Err(Error::Validation(format!(
+                    "Invalid character in action name: {}",
+                    c
+                )))
+            }
+        };
+        value = value * 32 + digit;
+    }
+    Ok(value << (5 * (NAME_LENGTH - name.len())))
+}
+
+/// 由链上 `Name` 还原操作名，末尾的补零被去掉；高 4 位非零时返回 None
+pub fn decode_action_name(value: u64) -> Option<String> {
+    if value >> (5 * NAME_LENGTH) != 0 {
+        return None;
+    }
+    let name: String = (0..NAME_LENGTH)
+        .map(|i| match (value >> (5 * (NAME_LENGTH - 1 - i))) & 31 {
+            0 => '_',
+            digit @ 1..=5 => (b'0' + digit as u8) as char,
+            digit => (b'a' + digit as u8 - 6) as char,
+        })
+        .collect();
+    Some(name.trim_end_matches('_').to_string())
+}
+
+/// 合约调用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Action {
+    /// 输入对象
+    pub inputs: Vec<ObjectId>,
+    /// 目标合约地址
+    pub contract: Address,
+    /// 操作名编码
+    pub action: u64,
+    /// bincode 编码的参数
+    pub params: Vec<u8>,
+}
+
+impl Action {
+    pub fn new(
+        contract: Address,
+        name: &str,
+        inputs: Vec<ObjectId>,
+        params: Vec<u8>,
+    ) -> Result<Self> {
+        Ok(Self {
+            inputs,
+            contract,
+            action: action_name(name)?,
+            params,
+        })
+    }
+}
+
+/// 未签名交易
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Transaction {
+    pub sender: Address,
+    pub expiration: u64,
+    pub actions: Vec<Action>,
+}
+
+impl Transaction {
+    /// 签名内容：交易的 bincode 编码
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("transaction is always serializable")
+    }
+
+    /// 签名交易
+    pub fn sign(self, signer: &dyn Signer) -> SignedTransaction {
+        let _span = tracing::debug_span!("sign_transaction", signer = %signer.address()).entered();
+        let signature = signer.sign(&self.signing_bytes());
+        let mut part1 = [0u8; 32];
+        let mut part2 = [0u8; 32];
+        part1.copy_from_slice(&signature[..32]);
+        part2.copy_from_slice(&signature[32..]);
+        SignedTransaction {
+            transaction: self,
+            signatures: vec![Signature { part1, part2 }],
+        }
+    }
+}
+
+/// 64 字节 Ed25519 签名，按链上格式拆为两段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signature {
+    pub part1: [u8; 32],
+    pub part2: [u8; 32],
+}
+
+impl Signature {
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.part1);
+        bytes[32..].copy_from_slice(&self.part2);
+        bytes
+    }
+}
+
+/// 已签名交易
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SignedTransaction {
+    pub transaction: Transaction,
+    pub signatures: Vec<Signature>,
+}
+
+impl SignedTransaction {
+    /// 校验公钥对应发送者地址且每个签名都有效
+    ///
+    /// 地址是公钥的哈希，无法从交易本身恢复公钥，须由调用方提供。
+    pub fn verify(&self, public_key: &[u8; 32]) -> bool {
+        let message = self.transaction.signing_bytes();
+        address_from_public_key(public_key) == self.transaction.sender
+            && !self.signatures.is_empty()
+            && self
+                .signatures
+                .iter()
+                .all(|signature| verify(public_key, &message, &signature.to_bytes()))
+    }
+}
+
+/// 交易构建器
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    sender: Option<Address>,
+    expiration: u64,
+    actions: Vec<Action>,
+}
+
+impl Default for TransactionBuilder {
+    fn default() -> Self {
+        Self {
+            sender: None,
+            expiration: u64::MAX,
+            actions: Vec::new(),
+        }
+    }
+}
+
+impl TransactionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置发送者地址
+    pub fn sender(mut self, sender: Address) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// 设置过期时间，默认不过期
+    pub fn expiration(mut self, expiration: u64) -> Self {
+        self.expiration = expiration;
+        self
+    }
+
+    /// 添加操作
+    pub fn action(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// 构建交易
+    pub fn build(self) -> Result<Transaction> {
+        let sender = self
+            .sender
+            .ok_or_else(|| Error::Validation("Sender address is required".to_string()))?;
+        if self.actions.is_empty() {
+            return Err(Error::Validation(
+                "At least one action is required".to_string(),
+            ));
+        }
+        Ok(Transaction {
+            sender,
+            expiration: self.expiration,
+            actions: self.actions,
+        })
+    }
+
+    /// 以签名者地址为发送者构建并签名交易
+    pub fn build_and_sign(self, signer: &dyn Signer) -> Result<SignedTransaction> {
+        Ok(self.sender(signer.address()).build()?.sign(signer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::Ed25519Signer;
+
+    #[test]
+    fn action_names_match_python_sdk() {
+        assert_eq!(action_name("ord_place").unwrap(), 746789037603618816);
+        assert!(action_name("too_long_action").is_err());
+        assert!(action_name("Bad").is_err());
+        for name in ["ord_place", "ord_cancel", "transfer", "a1_5z"] {
+            assert_eq!(
+                decode_action_name(action_name(name).unwrap()).as_deref(),
+                Some(name)
+            );
+        }
+        assert_eq!(decode_action_name(u64::MAX), None);
+    }
+
+    #[test]
+    fn signed_transaction_verifies() {
+        let signer = Ed25519Signer::from_secret_key_bytes(&[7u8; 32]);
+        let action =
+            Action::new(SPOT_CONTRACT, "ord_cancel", vec![ObjectId([1; 32])], vec![]).unwrap();
+        let signed = TransactionBuilder::new()
+            .action(action)
+            .build_and_sign(&signer)
+            .unwrap();
+        assert_eq!(signed.transaction.sender, signer.address());
+        assert!(verify(
+            &signer.public_key(),
+            &signed.transaction.signing_bytes(),
+            &signed.signatures[0].to_bytes()
+        ));
+        assert!(signed.verify(&signer.public_key()));
+        let other = Ed25519Signer::from_secret_key_bytes(&[8u8; 32]);
+        assert!(!signed.verify(&other.public_key()));
+        let mut tampered = signed.clone();
+        tampered.transaction.expiration = 1;
+        assert!(!tampered.verify(&signer.public_key()));
+        assert!(TransactionBuilder::new().build().is_err());
+    }
+}
@@ -0,0 +1,109 @@
+//! 与链上 Rust 定义保持一致的基础类型
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// 订单方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+/// LightPool地址类型（32字节）
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Address(pub [u8; 32]);
+
+impl Address {
+    /// 返回零地址
+    pub const ZERO: Address = Address([0; 32]);
+
+    /// 返回地址的字节数组表示
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+/// 以十六进制显示，便于在日志与笔记本中阅读
+impl fmt::Debug for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Address({})", self)
+    }
+}
+
+impl FromStr for Address {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let hex_str = s.strip_prefix("0x").unwrap_or(s);
+        if hex_str.len() != 64 {
+            return Err(Error::Validation(format!(
+                "Invalid address length: {}",
+                hex_str.len()
+            )));
+        }
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(hex_str, &mut bytes)
+            .map_err(|e| Error::Validation(format!("Invalid address: {}", e)))?;
+        Ok(Address(bytes))
+    }
+}
+
+/// 32字节十六进制标识符的 Display/Debug/FromStr 实现
+macro_rules! hex_id {
+    ($ty:ident, $what:literal) => {
+        impl fmt::Debug for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!(stringify!($ty), "({})"), self)
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "0x{}", hex::encode(self.0))
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self> {
+                let hex_str = s.strip_prefix("0x").unwrap_or(s);
+                if hex_str.len() != 64 {
+                    return Err(Error::Validation(format!(
+                        concat!("Invalid ", $what, " length: {}"),
+                        hex_str.len()
+                    )));
+                }
+                let mut bytes = [0u8; 32];
+                hex::decode_to_slice(hex_str, &mut bytes).map_err(|e| {
+                    Error::Validation(format!(concat!("Invalid ", $what, ": {}"), e))
+                })?;
+                Ok($ty(bytes))
+            }
+        }
+    };
+}
+
+/// 链上对象ID（32字节），如市场、余额对象
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ObjectId(pub [u8; 32]);
+
+hex_id!(ObjectId, "object id");
+
+/// 订单ID（32字节）
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct OrderId(pub [u8; 32]);
+
+hex_id!(OrderId, "order id");
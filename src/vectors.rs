@@ -0,0 +1,348 @@
+//! 跨语言测试向量
+//!
+//! [`wire_types`] 按类型列出每种链上类型的代表值，用例只依赖固定的私钥与字节模式，
+//! 重新生成的结果逐字节相同；命令行工具的 `gen-fixtures` 与 `gen-corpus` 也基于同一批用例。
+//! [`export_test_vectors`] 写出带版本号的向量文件，每个向量包含输入、bincode 编码、
+//! SHA-512 摘要与固定私钥的签名，Python SDK 的测试锁定 [`VECTORS_VERSION`] 并逐项比对。
+//! 编码有意变化时须提升版本号并重新生成 `tests/vectors.json`。
+
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha512};
+
+use crate::crypto::{Ed25519Signer, Signer};
+use crate::error::Result;
+use crate::order::{
+    cancel_action, CancelOrderParams, OrderRequest, OrderType, PlaceOrderParams, TimeInForce,
+};
+use crate::transaction::{Action, Transaction, SPOT_CONTRACT, TOKEN_CONTRACT};
+use crate::types::{Address, ObjectId, OrderId, OrderSide};
+
+/// 向量文件格式与内容的版本
+pub const VECTORS_VERSION: u32 = 1;
+
+/// 一个类型的一个代表值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vector {
+    pub name: &'static str,
+    /// 提交时使用的 JSON 编码
+    pub input: Value,
+    /// bincode 编码
+    pub bytes: Vec<u8>,
+}
+
+/// 向量文件
+#[derive(Debug, Clone, Serialize)]
+pub struct TestVectors {
+    pub version: u32,
+    /// 摘要算法
+    pub digest: &'static str,
+    /// 签名使用的固定密钥
+    pub signer: VectorSigner,
+    pub vectors: Vec<TestVector>,
+}
+
+/// 向量签名者，字节均为十六进制
+#[derive(Debug, Clone, Serialize)]
+pub struct VectorSigner {
+    pub secret_key: String,
+    pub public_key: String,
+    /// `0x` 前缀的地址
+    pub address: String,
+}
+
+/// 带摘要与签名的向量，字节均为十六进制
+#[derive(Debug, Clone, Serialize)]
+pub struct TestVector {
+    /// 类型名，沿用 `decode --type` 的写法
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub name: &'static str,
+    pub input: Value,
+    pub bytes: String,
+    /// `bytes` 的 SHA-512 摘要
+    pub digest: String,
+    /// 固定私钥对 `bytes` 的 Ed25519 签名
+    pub signature: String,
+}
+
+fn vector<T: Serialize>(name: &'static str, value: &T) -> Vector {
+    Vector {
+        name,
+        input: serde_json::to_value(value).expect("wire types are always serializable"),
+        bytes: bincode::serialize(value).expect("wire types are always serializable"),
+    }
+}
+
+/// 固定的字节模式：首字节为 `tag`，其余依次递增
+fn pattern(tag: u8) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = tag.wrapping_add(i as u8);
+    }
+    bytes
+}
+
+/// 用例使用的签名私钥
+pub fn signer() -> Ed25519Signer {
+    Ed25519Signer::from_secret_key_bytes(&[7u8; 32])
+}
+
+fn limit_order() -> PlaceOrderParams {
+    PlaceOrderParams::limit(OrderSide::Sell, 5_000_000, 50_000_000_000)
+}
+
+fn place_action() -> Action {
+    OrderRequest::spot(
+        ObjectId(pattern(0x10)),
+        ObjectId(pattern(0x20)),
+        limit_order(),
+    )
+    .to_action()
+    .expect("ord_place is a valid action name")
+}
+
+fn cancel() -> Action {
+    cancel_action(
+        SPOT_CONTRACT,
+        ObjectId(pattern(0x10)),
+        OrderId(pattern(0x30)),
+    )
+    .expect("ord_cancel is a valid action name")
+}
+
+fn transaction() -> Transaction {
+    Transaction {
+        sender: signer().address(),
+        expiration: 1_700_000_000,
+        actions: vec![place_action(), cancel()],
+    }
+}
+
+/// 按类型列出全部用例，类型名沿用 `decode --type` 的写法
+pub fn wire_types() -> Vec<(&'static str, Vec<Vector>)> {
+    let signer = signer();
+    let signed = transaction().sign(&signer);
+
+    vec![
+        (
+            "order-side",
+            vec![
+                vector("buy", &OrderSide::Buy),
+                vector("sell", &OrderSide::Sell),
+            ],
+        ),
+        (
+            "time-in-force",
+            vec![
+                vector("gtc", &TimeInForce::Gtc),
+                vector("ioc", &TimeInForce::Ioc),
+                vector("fok", &TimeInForce::Fok),
+            ],
+        ),
+        (
+            "order-type",
+            vec![
+                vector(
+                    "limit-gtc",
+                    &OrderType::Limit {
+                        tif: TimeInForce::Gtc,
+                    },
+                ),
+                vector(
+                    "limit-fok",
+                    &OrderType::Limit {
+                        tif: TimeInForce::Fok,
+                    },
+                ),
+                vector("market", &OrderType::Market { slippage: 50 }),
+                vector(
+                    "trigger",
+                    &OrderType::Trigger {
+                        trigger_price: 49_000_000_000,
+                        is_market: true,
+                        trigger_type: 1,
+                    },
+                ),
+            ],
+        ),
+        (
+            "address",
+            vec![
+                vector("zero", &Address::ZERO),
+                vector("token-contract", &TOKEN_CONTRACT),
+                vector("spot-contract", &SPOT_CONTRACT),
+                vector("from-public-key", &signer.address()),
+            ],
+        ),
+        (
+            "object-id",
+            vec![
+                vector("pattern", &ObjectId(pattern(0x10))),
+                vector("max", &ObjectId([0xff; 32])),
+            ],
+        ),
+        (
+            "order-id",
+            vec![
+                vector("pattern", &OrderId(pattern(0x30))),
+                vector("zero", &OrderId([0; 32])),
+            ],
+        ),
+        (
+            "place-order",
+            vec![
+                vector("limit-sell", &limit_order()),
+                vector(
+                    "limit-buy-ioc",
+                    &PlaceOrderParams {
+                        side: OrderSide::Buy,
+                        amount: 1,
+                        order_type: OrderType::Limit {
+                            tif: TimeInForce::Ioc,
+                        },
+                        limit_price: 1,
+                    },
+                ),
+                vector(
+                    "market-buy",
+                    &PlaceOrderParams::market(OrderSide::Buy, 2_500_000, 51_000_000_000, 100),
+                ),
+                vector(
+                    "trigger-sell",
+                    &PlaceOrderParams {
+                        side: OrderSide::Sell,
+                        amount: 1_000_000,
+                        order_type: OrderType::Trigger {
+                            trigger_price: 49_000_000_000,
+                            is_market: false,
+                            trigger_type: 2,
+                        },
+                        limit_price: 48_900_000_000,
+                    },
+                ),
+                vector(
+                    "max-values",
+                    &PlaceOrderParams::limit(OrderSide::Buy, u64::MAX, u64::MAX),
+                ),
+            ],
+        ),
+        (
+            "cancel-order",
+            vec![vector(
+                "pattern",
+                &CancelOrderParams {
+                    order_id: OrderId(pattern(0x30)),
+                },
+            )],
+        ),
+        (
+            "action",
+            vec![
+                vector("ord-place", &place_action()),
+                vector("ord-cancel", &cancel()),
+                vector(
+                    "empty",
+                    &Action {
+                        inputs: vec![],
+                        contract: TOKEN_CONTRACT,
+                        action: 0,
+                        params: vec![],
+                    },
+                ),
+            ],
+        ),
+        (
+            "transaction",
+            vec![
+                vector("place-and-cancel", &transaction()),
+                vector(
+                    "no-expiration",
+                    &Transaction {
+                        sender: signer.address(),
+                        expiration: u64::MAX,
+                        actions: vec![cancel()],
+                    },
+                ),
+            ],
+        ),
+        (
+            "signature",
+            vec![vector("place-and-cancel", &signed.signatures[0])],
+        ),
+        (
+            "signed-transaction",
+            vec![vector("place-and-cancel", &signed)],
+        ),
+    ]
+}
+
+/// 为全部用例计算摘要与签名
+pub fn test_vectors() -> TestVectors {
+    let signer = signer();
+    let vectors = wire_types()
+        .into_iter()
+        .flat_map(|(kind, vectors)| {
+            let signer = &signer;
+            vectors.into_iter().map(move |vector| TestVector {
+                kind,
+                name: vector.name,
+                digest: hex::encode(Sha512::digest(&vector.bytes)),
+                signature: hex::encode(signer.sign(&vector.bytes)),
+                bytes: hex::encode(&vector.bytes),
+                input: vector.input,
+            })
+        })
+        .collect();
+    TestVectors {
+        version: VECTORS_VERSION,
+        digest: "sha512",
+        signer: VectorSigner {
+            secret_key: hex::encode(signer.secret_key_bytes()),
+            public_key: hex::encode(signer.public_key()),
+            address: signer.address().to_string(),
+        },
+        vectors,
+    }
+}
+
+/// 将 [`test_vectors`] 以格式化 JSON 写入 `path`
+pub fn export_test_vectors(path: impl AsRef<Path>) -> Result<()> {
+    let mut text = serde_json::to_string_pretty(&test_vectors())?;
+    text.push('\n');
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committed_vectors_match_the_current_encoding() {
+        let committed: Value = serde_json::from_str(include_str!("../tests/vectors.json")).unwrap();
+        let current = serde_json::to_value(test_vectors()).unwrap();
+        assert!(
+            committed == current,
+            "tests/vectors.json is stale; regenerate it with `lightpool gen-vectors --out tests/vectors.json`"
+        );
+    }
+
+    #[test]
+    fn vectors_verify_under_the_fixed_key() {
+        let vectors = test_vectors();
+        let signer = signer();
+        assert_eq!(vectors.signer.address, signer.address().to_string());
+        for vector in &vectors.vectors {
+            let bytes = hex::decode(&vector.bytes).unwrap();
+            let signature: [u8; 64] = hex::decode(&vector.signature).unwrap().try_into().unwrap();
+            assert!(crate::crypto::verify(
+                &signer.public_key(),
+                &bytes,
+                &signature
+            ));
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! 浏览器与 JavaScript 绑定（`wasm` feature）
+//!
+//! 以 wasm-bindgen 导出下单参数编码、签名摘要与 Ed25519 签名，前端构造的交易与 Rust/Python SDK 字节一致。
+//! 地址与对象ID为 `0x` 前缀的十六进制字符串，字节内容为 `Uint8Array`，`u64` 为 `BigInt`；
+//! 操作与交易以节点接受的 JSON 字符串传递，参数不合法时抛出 `Error`。构建方式：
+//!
+//! ```text
+//! cargo rustc --release --lib --target wasm32-unknown-unknown --no-default-features --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/lightpool.wasm
+//! ```
+//!
+//! ```js
+//! const params = encodeLimitOrder("buy", 5000000n, 50000000000n);
+//! const action = placeOrderAction(marketId, balanceId, params);
+//! const tx = new Signer(secretKey).signTransaction(`[${action}]`);
+//! ```
+
+use sha2::{Digest, Sha512};
+use wasm_bindgen::prelude::*;
+
+use crate::crypto::{self, Ed25519Signer, Signer as _};
+use crate::error::{Error, Result};
+use crate::order::{self, OrderRequest, OrderType, PlaceOrderParams, TimeInForce};
+use crate::transaction::{Action, Transaction, SPOT_CONTRACT};
+use crate::types::{Address, OrderSide};
+
+fn side(value: &str) -> Result<OrderSide> {
+    match value.to_ascii_lowercase().as_str() {
+        "buy" => Ok(OrderSide::Buy),
+        "sell" => Ok(OrderSide::Sell),
+        _ => Err(Error::Validation(format!("invalid order side: {}", value))),
+    }
+}
+
+fn tif(value: &str) -> Result<TimeInForce> {
+    match value.to_ascii_uppercase().as_str() {
+        "GTC" => Ok(TimeInForce::Gtc),
+        "IOC" => Ok(TimeInForce::Ioc),
+        "FOK" => Ok(TimeInForce::Fok),
+        _ => Err(Error::Validation(format!(
+            "invalid time in force: {}",
+            value
+        ))),
+    }
+}
+
+fn fixed<const N: usize>(bytes: &[u8], what: &str) -> Result<[u8; N]> {
+    bytes.try_into().map_err(|_| {
+        Error::Validation(format!("{} must be {} bytes, got {}", what, N, bytes.len()))
+    })
+}
+
+fn params(data: &[u8]) -> Result<PlaceOrderParams> {
+    bincode::deserialize(data).map_err(|e| Error::Decode(format!("invalid order params: {}", e)))
+}
+
+fn transaction(sender: Address, actions: &str, expiration: Option<u64>) -> Result<Transaction> {
+    let actions: Vec<Action> = serde_json::from_str(actions)?;
+    if actions.is_empty() {
+        return Err(Error::Validation(
+            "at least one action is required".to_string(),
+        ));
+    }
+    Ok(Transaction {
+        sender,
+        expiration: expiration.unwrap_or(u64::MAX),
+        actions,
+    })
+}
+
+/// 限价单参数的 bincode 编码，`tif` 为 GTC（默认）、IOC 或 FOK
+#[wasm_bindgen(js_name = encodeLimitOrder)]
+pub fn encode_limit_order(
+    side: &str,
+    amount: u64,
+    limit_price: u64,
+    tif: Option<String>,
+) -> std::result::Result<Vec<u8>, JsError> {
+    let mut params = PlaceOrderParams::limit(self::side(side)?, amount, limit_price);
+    if let Some(tif) = tif {
+        params.order_type = OrderType::Limit {
+            tif: self::tif(&tif)?,
+        };
+    }
+    Ok(params.encode())
+}
+
+/// 市价单参数的 bincode 编码，`limit_price` 为滑点参考价
+#[wasm_bindgen(js_name = encodeMarketOrder)]
+pub fn encode_market_order(
+    side: &str,
+    amount: u64,
+    limit_price: u64,
+    slippage_bps: u64,
+) -> std::result::Result<Vec<u8>, JsError> {
+    Ok(PlaceOrderParams::market(self::side(side)?, amount, limit_price, slippage_bps).encode())
+}
+
+/// 将下单参数编码还原为 JSON，便于展示与核对
+#[wasm_bindgen(js_name = decodePlaceOrder)]
+pub fn decode_place_order(data: &[u8]) -> std::result::Result<String, JsError> {
+    Ok(serde_json::to_string(&params(data)?)?)
+}
+
+/// `ord_place` 操作的 JSON，`market_address` 默认为现货合约
+#[wasm_bindgen(js_name = placeOrderAction)]
+pub fn place_order_action(
+    market_id: &str,
+    balance_id: &str,
+    params: &[u8],
+    market_address: Option<String>,
+) -> std::result::Result<String, JsError> {
+    let mut request = OrderRequest::spot(
+        market_id.parse()?,
+        balance_id.parse()?,
+        self::params(params)?,
+    );
+    if let Some(address) = market_address {
+        request.market_address = address.parse()?;
+    }
+    Ok(serde_json::to_string(&request.to_action()?)?)
+}
+
+/// `ord_cancel` 操作的 JSON，`market_address` 默认为现货合约
+#[wasm_bindgen(js_name = cancelOrderAction)]
+pub fn cancel_order_action(
+    market_id: &str,
+    order_id: &str,
+    market_address: Option<String>,
+) -> std::result::Result<String, JsError> {
+    let market_address = match market_address {
+        Some(address) => address.parse()?,
+        None => SPOT_CONTRACT,
+    };
+    let action = order::cancel_action(market_address, market_id.parse()?, order_id.parse()?)?;
+    Ok(serde_json::to_string(&action)?)
+}
+
+/// 交易的签名内容（bincode 编码），`actions` 为操作 JSON 数组，`expiration` 默认不过期
+#[wasm_bindgen(js_name = signingBytes)]
+pub fn signing_bytes(
+    sender: &str,
+    actions: &str,
+    expiration: Option<u64>,
+) -> std::result::Result<Vec<u8>, JsError> {
+    Ok(transaction(sender.parse()?, actions, expiration)?.signing_bytes())
+}
+
+/// 签名内容的 SHA-512 摘要，与 `lightpool bench` 的 hash 一项相同
+#[wasm_bindgen(js_name = signingDigest)]
+pub fn signing_digest(
+    sender: &str,
+    actions: &str,
+    expiration: Option<u64>,
+) -> std::result::Result<Vec<u8>, JsError> {
+    let bytes = transaction(sender.parse()?, actions, expiration)?.signing_bytes();
+    Ok(Sha512::digest(bytes).to_vec())
+}
+
+/// 公钥对应的地址
+#[wasm_bindgen(js_name = addressFromPublicKey)]
+pub fn address_from_public_key(public_key: &[u8]) -> std::result::Result<String, JsError> {
+    let public_key = fixed::<32>(public_key, "public key")?;
+    Ok(crypto::address_from_public_key(&public_key).to_string())
+}
+
+/// 校验 Ed25519 签名
+#[wasm_bindgen]
+pub fn verify(
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> std::result::Result<bool, JsError> {
+    Ok(crypto::verify(
+        &fixed::<32>(public_key, "public key")?,
+        message,
+        &fixed::<64>(signature, "signature")?,
+    ))
+}
+
+/// Ed25519 签名者
+#[wasm_bindgen(js_name = Signer)]
+pub struct WasmSigner(Ed25519Signer);
+
+#[wasm_bindgen(js_class = Signer)]
+impl WasmSigner {
+    /// 由 32 字节私钥创建
+    #[wasm_bindgen(constructor)]
+    pub fn new(secret_key: &[u8]) -> std::result::Result<WasmSigner, JsError> {
+        let secret_key = fixed::<32>(secret_key, "secret key")?;
+        Ok(Self(Ed25519Signer::from_secret_key_bytes(&secret_key)))
+    }
+
+    /// 随机生成私钥
+    pub fn generate() -> WasmSigner {
+        Self(Ed25519Signer::generate())
+    }
+
+    /// 由十六进制私钥创建
+    #[wasm_bindgen(js_name = fromHex)]
+    pub fn from_hex(secret_key: &str) -> std::result::Result<WasmSigner, JsError> {
+        Ok(Self(Ed25519Signer::from_hex(secret_key)?))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        self.0.address().to_string()
+    }
+
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.0.public_key().to_vec()
+    }
+
+    /// 签名任意消息，返回 64 字节签名
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.0.sign(message).to_vec()
+    }
+
+    /// 以本签名者为发送者签名交易，返回 `submitTransaction` 的 `tx` 参数 JSON
+    #[wasm_bindgen(js_name = signTransaction)]
+    pub fn sign_transaction(
+        &self,
+        actions: &str,
+        expiration: Option<u64>,
+    ) -> std::result::Result<String, JsError> {
+        let signed = transaction(self.0.address(), actions, expiration)?.sign(&self.0);
+        Ok(serde_json::to_string(&signed)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::SignedTransaction;
+
+    #[test]
+    fn signed_transactions_match_the_rust_api() {
+        let params = encode_limit_order("buy", 5_000_000, 50_000_000_000, None).unwrap();
+        let expected = PlaceOrderParams::limit(OrderSide::Buy, 5_000_000, 50_000_000_000);
+        assert_eq!(params, expected.encode());
+
+        let market_id = format!("0x{}", "01".repeat(32));
+        let balance_id = format!("0x{}", "02".repeat(32));
+        let action = place_order_action(&market_id, &balance_id, &params, None).unwrap();
+        let signer = WasmSigner::new(&[7; 32]).unwrap();
+        let actions = format!("[{}]", action);
+        let signed: SignedTransaction =
+            serde_json::from_str(&signer.sign_transaction(&actions, None).unwrap()).unwrap();
+        assert!(signed.verify(&signer.0.public_key()));
+        assert_eq!(
+            signed.transaction.actions,
+            [OrderRequest::spot(
+                market_id.parse().unwrap(),
+                balance_id.parse().unwrap(),
+                expected
+            )
+            .to_action()
+            .unwrap()]
+        );
+        assert_eq!(
+            signing_digest(&signer.address(), &actions, None).unwrap(),
+            Sha512::digest(signed.transaction.signing_bytes()).to_vec()
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! 连接认证：签名挑战握手
+//!
+//! 1. 客户端发送 `{"op":"challenge"}`
+//! 2. 网关返回 `{"type":"challenge","nonce":"<hex>"}`
+//! 3. 客户端用 [`Signer`] 对 nonce 字节签名，发送 `{"op":"auth","address":...,"public_key":...,"signature":...}`
+//! 4. 网关返回 `{"type":"authenticated","address":...}`，或以 `error` 消息拒绝
+//!
+//! 握手期间收到的行情推送照常分发。
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::connection::{decode_message, send_request, Socket};
+use super::message::{ClientRequest, WsMessage};
+use crate::crypto::Signer;
+use crate::error::{Error, Result};
+
+/// 等待网关响应的超时时间
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 在连接上完成一次认证握手
+pub(crate) async fn handshake(
+    socket: &mut Socket,
+    signer: &dyn Signer,
+    mut dispatch: impl FnMut(WsMessage),
+) -> Result<()> {
+    tokio::time::timeout(AUTH_TIMEOUT, async {
+        send_request(socket, &ClientRequest::Challenge).await?;
+        let nonce = loop {
+            match next_message(socket, &mut dispatch).await? {
+                WsMessage::Challenge(challenge) => break challenge.nonce,
+                msg => reject_or_skip(msg)?,
+            }
+        };
+        let nonce = hex::decode(nonce.strip_prefix("0x").unwrap_or(&nonce))
+            .map_err(|e| Error::Auth(format!("invalid nonce: {}", e)))?;
+
+        let request = ClientRequest::Auth {
+            address: signer.address().to_string(),
+            public_key: hex::encode(signer.public_key()),
+            signature: hex::encode(signer.sign(&nonce)),
+        };
+        send_request(socket, &request).await?;
+        loop {
+            match next_message(socket, &mut dispatch).await? {
+                WsMessage::Authenticated(_) => return Ok(()),
+                msg => reject_or_skip(msg)?,
+            }
+        }
+    })
+    .await
+    .map_err(|_| Error::Auth("timed out waiting for gateway".to_string()))?
+}
+
+/// 读取下一条连接级消息，频道推送直接分发
+async fn next_message(
+    socket: &mut Socket,
+    dispatch: &mut impl FnMut(WsMessage),
+) -> Result<WsMessage> {
+    loop {
+        let frame = match socket.next().await {
+            Some(frame) => frame?,
+            None => return Err(Error::ConnectionClosed),
+        };
+        if let Message::Close(_) = frame {
+            return Err(Error::ConnectionClosed);
+        }
+        match decode_message(&frame) {
+            Ok(Some(msg)) if msg.channel().is_some() => dispatch(msg),
+            Ok(Some(msg)) => return Ok(msg),
+            Ok(None) | Err(_) => {}
+        }
+    }
+}
+
+/// 握手期间的网关错误视为认证被拒绝，其他连接级消息忽略
+fn reject_or_skip(msg: WsMessage) -> Result<()> {
+    match msg {
+        WsMessage::Error(error) => Err(Error::Auth(error.message)),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,44 @@
+//! 回调式订阅
+//!
+//! 每个回调由一个后台任务驱动：任务从订阅流中取出事件并依次调用回调，
+//! 因此同一回调不会被并发调用，回调中的耗时操作只会积压自身的订阅队列。
+
+use futures_util::{Stream, StreamExt};
+use tokio::task::JoinHandle;
+
+/// 回调注册句柄，释放或调用 [`CallbackHandle::cancel`] 时注销回调并退订
+#[derive(Debug)]
+pub struct CallbackHandle {
+    task: JoinHandle<()>,
+}
+
+impl CallbackHandle {
+    /// 注销回调
+    pub fn cancel(self) {}
+
+    /// 订阅流是否已结束（连接关闭或订阅溢出终止）
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl Drop for CallbackHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// 在后台任务中把流中的事件逐个交给回调
+pub(crate) fn spawn<S, F>(mut stream: S, mut callback: F) -> CallbackHandle
+where
+    S: Stream + Unpin + Send + 'static,
+    S::Item: Send,
+    F: FnMut(S::Item) + Send + 'static,
+{
+    let task = tokio::spawn(async move {
+        while let Some(event) = stream.next().await {
+            callback(event);
+        }
+    });
+    CallbackHandle { task }
+}
@@ -0,0 +1,413 @@
+//! WebSocket 客户端
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+use super::callback::{self, CallbackHandle};
+use super::config::WsConfig;
+use super::conflate::ConflatedOrderbook;
+use super::connection::{self, Connection, ConnectionEvent};
+use super::filter::{FilterState, SubscriptionFilter};
+use super::journal::Journal;
+use super::latency::{LatencyStats, LatencyTracker};
+use super::manager::{Command, SubscriptionHandle, SubscriptionManager};
+use super::message::{
+    AccountEvent, Candle, CandleInterval, Channel, Fill, FundingRate, IndexPrice, L3Event,
+    MarkPrice, MarketStats, OrderUpdate, OrderbookUpdate, Ticker, Trade, WsMessage,
+};
+use super::queue::{self, OverflowPolicy};
+use super::record::Recorder;
+use super::subscription::Subscription;
+use super::sync::{SnapshotSource, SyncedOrderbook};
+use crate::crypto::Signer;
+use crate::error::{Error, Result};
+use crate::symbol::Symbol;
+
+/// LightPool WebSocket 行情客户端
+///
+/// 克隆后共享同一条连接，同一频道的多个订阅只向网关订阅一次。
+/// 断线后后台任务自动重连并恢复所有订阅。
+#[derive(Clone)]
+pub struct WsClient {
+    commands: mpsc::UnboundedSender<Command>,
+    manager: Arc<Mutex<SubscriptionManager>>,
+    events: broadcast::Sender<ConnectionEvent>,
+    latency: Arc<Mutex<LatencyTracker>>,
+    filter: Option<Arc<SubscriptionFilter>>,
+    channel_capacity: usize,
+    overflow_policy: OverflowPolicy,
+}
+
+impl WsClient {
+    /// 使用默认配置连接到网关，例如 `ws://localhost:26300/ws`
+    pub async fn connect(url: &str) -> Result<Self> {
+        Self::connect_with_config(url, WsConfig::default()).await
+    }
+
+    /// 使用指定配置连接到网关
+    pub async fn connect_with_config(url: &str, config: WsConfig) -> Result<Self> {
+        let socket = connection::open(url).await?;
+        let (client, manager, command_rx) = Self::detached(&config);
+        let connection = Connection::new(
+            url.to_string(),
+            config,
+            command_rx,
+            client.events.clone(),
+            manager,
+            client.latency.clone(),
+        );
+        tokio::spawn(connection.run(socket));
+        Ok(client)
+    }
+
+    /// 创建尚未绑定连接的客户端，由调用方驱动订阅管理器与命令通道
+    pub(crate) fn detached(
+        config: &WsConfig,
+    ) -> (
+        Self,
+        Arc<Mutex<SubscriptionManager>>,
+        mpsc::UnboundedReceiver<Command>,
+    ) {
+        let (commands, command_rx) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(16);
+        let manager = Arc::new(Mutex::new(SubscriptionManager::new(config.metrics.clone())));
+        let client = Self {
+            commands,
+            manager: manager.clone(),
+            events,
+            latency: Arc::new(Mutex::new(LatencyTracker::new(config.latency_window))),
+            filter: None,
+            channel_capacity: config.channel_capacity,
+            overflow_policy: config.overflow_policy,
+        };
+        (client, manager, command_rx)
+    }
+
+    /// 开始把收到的所有消息录制到文件，返回的录制器释放时停止录制
+    ///
+    /// 录制文件可通过 [`Replay`](super::Replay) 回放。
+    pub fn start_recording(&self, path: impl AsRef<Path>) -> Result<Recorder> {
+        Recorder::start(path.as_ref(), self.manager.clone())
+    }
+
+    /// 使用签名者完成连接认证，之后可订阅该地址的私有频道
+    ///
+    /// 签名者与下单签名使用同一套密钥。认证状态在重连后自动恢复。
+    pub async fn authenticate<S>(&self, signer: S) -> Result<()>
+    where
+        S: Signer + 'static,
+    {
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(Command::Authenticate {
+                signer: Arc::new(signer),
+                reply,
+            })
+            .map_err(|_| Error::ConnectionClosed)?;
+        response.await.map_err(|_| Error::ConnectionClosed)?
+    }
+
+    /// 开始把收到的所有消息追加到持久化日志，返回的录制器释放时停止写入
+    pub fn start_journal(&self, journal: Journal) -> Recorder {
+        Recorder::spawn(journal, self.manager.clone())
+    }
+
+    pub(crate) fn event_sender(&self) -> broadcast::Sender<ConnectionEvent> {
+        self.events.clone()
+    }
+
+    /// 订阅连接状态事件（断开、重连成功）
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.events.subscribe()
+    }
+
+    /// 返回共享同一连接的客户端，之后通过它创建的订阅都按 `filter` 过滤
+    ///
+    /// 过滤在消息进入订阅队列前执行，不影响其他订阅者。
+    pub fn filtered(&self, filter: SubscriptionFilter) -> Self {
+        Self {
+            filter: Some(Arc::new(filter)),
+            ..self.clone()
+        }
+    }
+
+    /// 当前连接的推送延迟与时钟偏差统计；尚无样本时返回 None
+    ///
+    /// 重连后统计重新开始。
+    pub fn latency_stats(&self) -> Option<LatencyStats> {
+        self.latency.lock().unwrap().stats()
+    }
+
+    /// 当前活跃（至少有一个订阅者）的频道
+    pub fn active_channels(&self) -> Vec<Channel> {
+        self.manager.lock().unwrap().channels()
+    }
+
+    /// 频道当前的订阅者数量
+    pub fn subscriber_count(&self, channel: &Channel) -> usize {
+        self.manager.lock().unwrap().subscriber_count(channel)
+    }
+
+    /// 订阅指定市场的订单簿更新
+    pub fn subscribe_orderbook(&self, market: &str) -> Result<Subscription<OrderbookUpdate>> {
+        let channel = Channel::Orderbook {
+            market: market_name(market)?,
+        };
+        self.subscribe(channel, |msg| match msg {
+            WsMessage::Book(update) => Some(update),
+            _ => None,
+        })
+    }
+
+    /// 订阅指定市场的订单簿，并把 `window` 内的更新按档位合并后再交付
+    pub fn subscribe_orderbook_conflated(
+        &self,
+        market: &str,
+        window: Duration,
+    ) -> Result<ConflatedOrderbook<Subscription<OrderbookUpdate>>> {
+        Ok(ConflatedOrderbook::new(
+            self.subscribe_orderbook(market)?,
+            window,
+        ))
+    }
+
+    /// 订阅指定市场的订单簿，并校验序列号
+    ///
+    /// 发现缺口时通过 `source`（通常是 [`LightPoolClient`](crate::LightPoolClient)）拉取快照重同步。
+    pub fn subscribe_orderbook_synced<S: SnapshotSource>(
+        &self,
+        market: &str,
+        source: S,
+    ) -> Result<SyncedOrderbook<S>> {
+        let updates = self.subscribe_orderbook(market)?;
+        Ok(SyncedOrderbook::new(market_name(market)?, updates, source))
+    }
+
+    /// 订阅指定市场的公共成交
+    pub fn subscribe_trades(&self, market: &str) -> Result<Subscription<Trade>> {
+        let channel = Channel::Trades {
+            market: market_name(market)?,
+        };
+        self.subscribe(channel, |msg| match msg {
+            WsMessage::Trade(trade) => Some(trade),
+            _ => None,
+        })
+    }
+
+    /// 订阅指定市场的最优买卖价与行情摘要，适合不需要完整深度的场景
+    pub fn subscribe_ticker(&self, market: &str) -> Result<Subscription<Ticker>> {
+        let channel = Channel::Ticker {
+            market: market_name(market)?,
+        };
+        self.subscribe(channel, |msg| match msg {
+            WsMessage::Ticker(ticker) => Some(ticker),
+            _ => None,
+        })
+    }
+
+    /// 订阅指定市场与周期的K线
+    ///
+    /// 未收盘的K线会随成交不断推送，可通过 [`Candle::closed`] 区分终值。
+    pub fn subscribe_candles(
+        &self,
+        market: &str,
+        interval: CandleInterval,
+    ) -> Result<Subscription<Candle>> {
+        let channel = Channel::Candles {
+            market: market_name(market)?,
+            interval,
+        };
+        self.subscribe(channel, |msg| match msg {
+            WsMessage::Candle(candle) => Some(candle),
+            _ => None,
+        })
+    }
+
+    /// 订阅指定市场的逐笔订单簿事件，可用 [`L3Book`](crate::orderbook::L3Book) 维护本地状态
+    pub fn subscribe_l3(&self, market: &str) -> Result<Subscription<L3Event>> {
+        let channel = Channel::L3 {
+            market: market_name(market)?,
+        };
+        self.subscribe(channel, |msg| match msg {
+            WsMessage::L3(event) => Some(event),
+            _ => None,
+        })
+    }
+
+    /// 订阅永续合约市场的资金费率结算，可交给 [`PositionTracker::apply_funding`](crate::position::PositionTracker::apply_funding) 计提
+    pub fn subscribe_funding(&self, market: &str) -> Result<Subscription<FundingRate>> {
+        let channel = Channel::Funding {
+            market: market_name(market)?,
+        };
+        self.subscribe(channel, |msg| match msg {
+            WsMessage::Funding(funding) => Some(funding),
+            _ => None,
+        })
+    }
+
+    /// 订阅指定市场的标记价格，可交给 [`PositionTracker::apply_mark`](crate::position::PositionTracker::apply_mark) 更新盈亏
+    pub fn subscribe_mark_price(&self, market: &str) -> Result<Subscription<MarkPrice>> {
+        let channel = Channel::Prices {
+            market: market_name(market)?,
+        };
+        self.subscribe(channel, |msg| match msg {
+            WsMessage::MarkPrice(mark) => Some(mark),
+            _ => None,
+        })
+    }
+
+    /// 订阅指定市场的指数价格
+    pub fn subscribe_index_price(&self, market: &str) -> Result<Subscription<IndexPrice>> {
+        let channel = Channel::Prices {
+            market: market_name(market)?,
+        };
+        self.subscribe(channel, |msg| match msg {
+            WsMessage::IndexPrice(index) => Some(index),
+            _ => None,
+        })
+    }
+
+    /// 订阅指定市场的 24 小时统计与未平仓量
+    pub fn subscribe_stats(&self, market: &str) -> Result<Subscription<MarketStats>> {
+        let channel = Channel::Stats {
+            market: market_name(market)?,
+        };
+        self.subscribe(channel, |msg| match msg {
+            WsMessage::Stats(stats) => Some(stats),
+            _ => None,
+        })
+    }
+
+    /// 订阅账户的订单状态更新与成交回报
+    ///
+    /// 网关只向已认证的连接推送私有频道，需先调用 [`WsClient::authenticate`]。
+    pub fn subscribe_account(&self, address: &str) -> Result<Subscription<AccountEvent>> {
+        self.subscribe(account_channel(address), |msg| match msg {
+            WsMessage::Order(order) => Some(AccountEvent::Order(order)),
+            WsMessage::Fill(fill) => Some(AccountEvent::Fill(fill)),
+            _ => None,
+        })
+    }
+
+    /// 注册订单簿更新回调
+    ///
+    /// 回调在后台任务中依次调用，返回的句柄释放时注销回调。
+    pub fn on_book_update<F>(&self, market: &str, callback: F) -> Result<CallbackHandle>
+    where
+        F: FnMut(OrderbookUpdate) + Send + 'static,
+    {
+        Ok(callback::spawn(self.subscribe_orderbook(market)?, callback))
+    }
+
+    /// 注册公共成交回调
+    pub fn on_trade<F>(&self, market: &str, callback: F) -> Result<CallbackHandle>
+    where
+        F: FnMut(Trade) + Send + 'static,
+    {
+        Ok(callback::spawn(self.subscribe_trades(market)?, callback))
+    }
+
+    /// 注册行情摘要回调
+    pub fn on_ticker<F>(&self, market: &str, callback: F) -> Result<CallbackHandle>
+    where
+        F: FnMut(Ticker) + Send + 'static,
+    {
+        Ok(callback::spawn(self.subscribe_ticker(market)?, callback))
+    }
+
+    /// 注册K线回调
+    pub fn on_candle<F>(
+        &self,
+        market: &str,
+        interval: CandleInterval,
+        callback: F,
+    ) -> Result<CallbackHandle>
+    where
+        F: FnMut(Candle) + Send + 'static,
+    {
+        Ok(callback::spawn(
+            self.subscribe_candles(market, interval)?,
+            callback,
+        ))
+    }
+
+    /// 注册账户订单状态回调，需先完成连接认证
+    pub fn on_order_update<F>(&self, address: &str, callback: F) -> Result<CallbackHandle>
+    where
+        F: FnMut(OrderUpdate) + Send + 'static,
+    {
+        let orders = self.subscribe(account_channel(address), |msg| match msg {
+            WsMessage::Order(order) => Some(order),
+            _ => None,
+        })?;
+        Ok(callback::spawn(orders, callback))
+    }
+
+    /// 注册账户成交回报回调，需先完成连接认证
+    pub fn on_fill<F>(&self, address: &str, callback: F) -> Result<CallbackHandle>
+    where
+        F: FnMut(Fill) + Send + 'static,
+    {
+        let fills = self.subscribe(account_channel(address), |msg| match msg {
+            WsMessage::Fill(fill) => Some(fill),
+            _ => None,
+        })?;
+        Ok(callback::spawn(fills, callback))
+    }
+
+    /// 订阅频道的原始推送消息，供转发到其他传输使用
+    #[cfg(feature = "redis")]
+    pub(crate) fn subscribe_channel(&self, channel: Channel) -> Result<Subscription<WsMessage>> {
+        self.subscribe(channel, Some)
+    }
+
+    fn subscribe<T>(
+        &self,
+        channel: Channel,
+        extract: fn(WsMessage) -> Option<T>,
+    ) -> Result<Subscription<T>> {
+        let (sender, rx) = queue::channel(self.channel_capacity, self.overflow_policy);
+        let mut manager = self.manager.lock().unwrap();
+        let filter = self.filter.clone().map(FilterState::new);
+        let (id, first) = manager.add(channel.clone(), sender, filter);
+        // 持锁发送命令，保证与并发退订产生的命令顺序一致
+        if first
+            && self
+                .commands
+                .send(Command::Subscribe(channel.clone()))
+                .is_err()
+        {
+            manager.remove(&channel, id);
+            return Err(Error::ConnectionClosed);
+        }
+        drop(manager);
+
+        let handle = SubscriptionHandle::new(
+            id,
+            channel.clone(),
+            self.manager.clone(),
+            self.commands.clone(),
+        );
+        Ok(Subscription::new(
+            channel,
+            rx,
+            extract,
+            handle,
+            self.channel_capacity,
+        ))
+    }
+}
+
+/// 频道使用的规范市场名；`BTCUSDC`、`btc/usdc` 等写法统一为 `BTC-USDC`
+fn market_name(market: &str) -> Result<String> {
+    Ok(Symbol::parse(market)?.market())
+}
+
+fn account_channel(address: &str) -> Channel {
+    Channel::Account {
+        address: address.to_string(),
+    }
+}
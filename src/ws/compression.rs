@@ -0,0 +1,71 @@
+//! 压缩帧解码
+//!
+//! tungstenite 不支持 permessage-deflate 扩展协商，网关因此以二进制帧下发压缩消息：
+//!
+//! - 以 zstd 魔数 `28 b5 2f fd` 开头的帧按 zstd 解压（需启用 `zstd` feature）
+//! - 其余二进制帧按 RFC 7692 的 permessage-deflate 格式解压（raw DEFLATE，省略尾部 `00 00 ff ff`）
+
+use std::io::Read;
+
+use flate2::read::DeflateDecoder;
+
+use crate::error::{Error, Result};
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// RFC 7692 要求发送方去掉的 DEFLATE 块尾
+const DEFLATE_TAIL: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// 解码二进制帧为 JSON 文本
+pub(crate) fn decode_binary(payload: &[u8]) -> Result<String> {
+    let bytes = if payload.starts_with(&ZSTD_MAGIC) {
+        decode_zstd(payload)?
+    } else {
+        decode_deflate(payload)?
+    };
+    String::from_utf8(bytes).map_err(|e| Error::Decode(e.to_string()))
+}
+
+fn decode_deflate(payload: &[u8]) -> Result<Vec<u8>> {
+    let input = payload.chain(&DEFLATE_TAIL[..]);
+    let mut out = Vec::new();
+    // 块尾之后没有 final block，解码器读到输入结束时报 UnexpectedEof，此时数据已完整输出
+    match DeflateDecoder::new(input).read_to_end(&mut out) {
+        Ok(_) => Ok(out),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof && !out.is_empty() => Ok(out),
+        Err(e) => Err(Error::Decode(format!("deflate: {}", e))),
+    }
+}
+
+#[cfg(feature = "zstd")]
+fn decode_zstd(payload: &[u8]) -> Result<Vec<u8>> {
+    zstd::stream::decode_all(payload).map_err(|e| Error::Decode(format!("zstd: {}", e)))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decode_zstd(_payload: &[u8]) -> Result<Vec<u8>> {
+    Err(Error::Decode(
+        "received zstd frame but the `zstd` feature is disabled".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::DeflateEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    #[test]
+    fn decodes_permessage_deflate_payload() {
+        let text = r#"{"type":"trade","market":"BTC-USDC"}"#;
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        let mut payload = encoder.flush_finish().unwrap();
+        // 与 permessage-deflate 一致：sync flush 后去掉块尾
+        if payload.ends_with(&DEFLATE_TAIL) {
+            payload.truncate(payload.len() - DEFLATE_TAIL.len());
+        }
+        assert_eq!(decode_binary(&payload).unwrap(), text);
+    }
+}
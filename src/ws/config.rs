@@ -0,0 +1,47 @@
+//! WebSocket 客户端配置
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::metrics::MetricsSink;
+use super::queue::OverflowPolicy;
+
+/// WebSocket 客户端配置
+#[derive(Debug, Clone)]
+pub struct WsConfig {
+    /// 断线后首次重连的等待时间，之后每次失败翻倍
+    pub reconnect_initial_delay: Duration,
+    /// 重连等待时间上限
+    pub reconnect_max_delay: Duration,
+    /// 连续重连失败的次数上限，达到后发出 [`ConnectionFailed`](super::ConnectionEvent::ConnectionFailed)
+    /// 并停止重连；None 表示无限重试
+    pub reconnect_max_attempts: Option<u32>,
+    /// 心跳 Ping 发送间隔
+    pub ping_interval: Duration,
+    /// 超过该时长未收到任何消息即判定连接失效并强制重连
+    pub stale_timeout: Duration,
+    /// 每个订阅的消息队列容量
+    pub channel_capacity: usize,
+    /// 订阅队列满时的处理策略
+    pub overflow_policy: OverflowPolicy,
+    /// 延迟统计保留的最近样本数
+    pub latency_window: usize,
+    /// 订阅指标接收端
+    pub metrics: Option<Arc<dyn MetricsSink>>,
+}
+
+impl Default for WsConfig {
+    fn default() -> Self {
+        Self {
+            reconnect_initial_delay: Duration::from_millis(500),
+            reconnect_max_delay: Duration::from_secs(30),
+            reconnect_max_attempts: None,
+            ping_interval: Duration::from_secs(15),
+            stale_timeout: Duration::from_secs(30),
+            channel_capacity: 1024,
+            overflow_policy: OverflowPolicy::DropOldest,
+            latency_window: 1000,
+            metrics: None,
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! 订单簿更新合并
+//!
+//! 在时间窗口内把同一档位的多次更新合并为最新状态，窗口结束时产出一条更新。
+//! 窗口内出现快照时，后续增量直接合并进快照，产出的仍是快照。
+//! 适合只需要约 100ms 粒度、不关心中间状态的消费者。
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::Stream;
+use tokio::time::Sleep;
+
+use super::message::{OrderbookUpdate, PriceLevel};
+
+/// 窗口内累积的更新
+#[derive(Debug)]
+struct PendingBook {
+    market: String,
+    seq: u64,
+    snapshot: bool,
+    bids: BTreeMap<u64, u64>,
+    asks: BTreeMap<u64, u64>,
+    ts: u64,
+}
+
+impl PendingBook {
+    fn new(update: OrderbookUpdate) -> Self {
+        let mut pending = Self {
+            market: update.market,
+            seq: update.seq,
+            snapshot: update.snapshot,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            ts: update.ts,
+        };
+        pending.apply_levels(&update.bids, &update.asks);
+        pending
+    }
+
+    fn merge(&mut self, update: OrderbookUpdate) {
+        if update.snapshot {
+            *self = Self::new(update);
+            return;
+        }
+        self.seq = update.seq;
+        self.ts = update.ts;
+        self.apply_levels(&update.bids, &update.asks);
+    }
+
+    fn apply_levels(&mut self, bids: &[PriceLevel], asks: &[PriceLevel]) {
+        let snapshot = self.snapshot;
+        for (side, levels) in [(&mut self.bids, bids), (&mut self.asks, asks)] {
+            for level in levels {
+                // 快照中不保留已移除的档位，增量中需要保留 size 0 以通知移除
+                if snapshot && level.size == 0 {
+                    side.remove(&level.price);
+                } else {
+                    side.insert(level.price, level.size);
+                }
+            }
+        }
+    }
+
+    fn into_update(self) -> OrderbookUpdate {
+        let to_levels = |side: BTreeMap<u64, u64>| {
+            side.into_iter()
+                .map(|(price, size)| PriceLevel { price, size })
+        };
+        OrderbookUpdate {
+            market: self.market,
+            seq: self.seq,
+            snapshot: self.snapshot,
+            // 买盘按价格降序，卖盘按价格升序
+            bids: to_levels(self.bids).rev().collect(),
+            asks: to_levels(self.asks).collect(),
+            ts: self.ts,
+        }
+    }
+}
+
+/// 合并后的订单簿更新流
+///
+/// 产出更新的 `seq` 与 `ts` 取窗口内最后一条更新的值。
+pub struct ConflatedOrderbook<S> {
+    updates: S,
+    window: Duration,
+    pending: Option<PendingBook>,
+    deadline: Option<Pin<Box<Sleep>>>,
+    finished: bool,
+}
+
+impl<S> ConflatedOrderbook<S>
+where
+    S: Stream<Item = OrderbookUpdate> + Unpin,
+{
+    /// 以 `window` 为合并窗口包装订单簿更新流
+    pub fn new(updates: S, window: Duration) -> Self {
+        Self {
+            updates,
+            window,
+            pending: None,
+            deadline: None,
+            finished: false,
+        }
+    }
+
+    /// 被包装的原始更新流
+    pub fn get_ref(&self) -> &S {
+        &self.updates
+    }
+
+    fn flush(&mut self) -> Option<OrderbookUpdate> {
+        self.deadline = None;
+        self.pending.take().map(PendingBook::into_update)
+    }
+}
+
+impl<S> Stream for ConflatedOrderbook<S>
+where
+    S: Stream<Item = OrderbookUpdate> + Unpin,
+{
+    type Item = OrderbookUpdate;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<OrderbookUpdate>> {
+        let this = self.get_mut();
+        while !this.finished {
+            match Pin::new(&mut this.updates).poll_next(cx) {
+                Poll::Ready(Some(update)) => match this.pending.as_mut() {
+                    Some(pending) => pending.merge(update),
+                    None => {
+                        this.pending = Some(PendingBook::new(update));
+                        this.deadline = Some(Box::pin(tokio::time::sleep(this.window)));
+                    }
+                },
+                Poll::Ready(None) => this.finished = true,
+                Poll::Pending => break,
+            }
+        }
+        if this.finished {
+            // 上游结束时立即交付剩余更新
+            return Poll::Ready(this.flush());
+        }
+        let expired = this
+            .deadline
+            .as_mut()
+            .is_some_and(|deadline| deadline.as_mut().poll(cx).is_ready());
+        if expired {
+            return Poll::Ready(this.flush());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    fn update(
+        seq: u64,
+        snapshot: bool,
+        bids: &[(u64, u64)],
+        asks: &[(u64, u64)],
+    ) -> OrderbookUpdate {
+        let levels = |side: &[(u64, u64)]| {
+            side.iter()
+                .map(|&(price, size)| PriceLevel { price, size })
+                .collect()
+        };
+        OrderbookUpdate {
+            market: "BTC-USDC".to_string(),
+            seq,
+            snapshot,
+            bids: levels(bids),
+            asks: levels(asks),
+            ts: seq,
+        }
+    }
+
+    #[tokio::test]
+    async fn merges_levels_within_window() {
+        let updates = futures_util::stream::iter(vec![
+            update(1, false, &[(100, 1), (99, 2)], &[]),
+            update(2, false, &[(100, 5)], &[(101, 1)]),
+            update(3, false, &[(99, 0)], &[]),
+        ]);
+        let merged: Vec<_> = ConflatedOrderbook::new(updates, Duration::from_millis(100))
+            .collect()
+            .await;
+        assert_eq!(
+            merged,
+            vec![update(3, false, &[(100, 5), (99, 0)], &[(101, 1)])]
+        );
+    }
+
+    #[tokio::test]
+    async fn diffs_fold_into_snapshot() {
+        let updates = futures_util::stream::iter(vec![
+            update(1, false, &[(98, 1)], &[]),
+            update(2, true, &[(100, 1), (99, 2)], &[(101, 3)]),
+            update(3, false, &[(99, 0)], &[(102, 1)]),
+        ]);
+        let merged: Vec<_> = ConflatedOrderbook::new(updates, Duration::from_millis(100))
+            .collect()
+            .await;
+        assert_eq!(
+            merged,
+            vec![update(3, true, &[(100, 1)], &[(101, 3), (102, 1)])]
+        );
+    }
+}
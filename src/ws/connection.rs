@@ -0,0 +1,373 @@
+//! 后台连接任务：维护连接、断线重连并分发推送消息
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::{Instant, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use super::auth;
+use super::compression;
+use super::config::WsConfig;
+use super::latency::LatencyTracker;
+use super::manager::{Command, SubscriptionManager};
+use super::message::{ClientRequest, ErrorMessage, WsMessage};
+use super::record::now_millis;
+use crate::crypto::Signer;
+use crate::error::{Error, Result};
+
+pub(crate) type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// 连接状态事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// 连接断开，后台正在重连
+    Disconnected,
+    /// 重连成功且所有订阅已重新发送，调用方应重新同步本地状态
+    Reconnected,
+    /// 网关返回的错误（例如订阅了不存在的市场）
+    ServerError(ErrorMessage),
+    /// 重连后重新认证失败，私有频道不会再收到推送
+    AuthFailed(String),
+    /// 重连后恢复会话（重新认证或重新订阅）出错，后台将再次重连
+    RestoreFailed(String),
+    /// 连续重连失败次数达到上限，后台任务已停止，所有订阅流随之结束
+    ConnectionFailed(ConnectionDiagnostics),
+}
+
+/// 放弃重连时的诊断信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionDiagnostics {
+    pub url: String,
+    /// 失败的重连次数
+    pub attempts: u32,
+    /// 最后一次重连的错误
+    pub last_error: String,
+    /// 从断开到放弃重连经过的时间
+    pub elapsed: Duration,
+}
+
+/// 重连结束原因
+enum ReconnectEnd {
+    ClientDropped,
+    GaveUp(ConnectionDiagnostics),
+}
+
+/// 会话结束原因
+enum SessionEnd {
+    /// 所有 WsClient 句柄与订阅都已释放
+    ClientDropped,
+    Disconnected,
+}
+
+pub(crate) async fn open(url: &str) -> Result<Socket> {
+    let (socket, _) = connect_async(url).await?;
+    Ok(socket)
+}
+
+pub(crate) struct Connection {
+    url: String,
+    config: WsConfig,
+    commands: mpsc::UnboundedReceiver<Command>,
+    events: broadcast::Sender<ConnectionEvent>,
+    manager: Arc<Mutex<SubscriptionManager>>,
+    latency: Arc<Mutex<LatencyTracker>>,
+    /// 认证成功后保存，重连时用于重新认证
+    signer: Option<Arc<dyn Signer>>,
+    /// 重连期间收到的命令，恢复会话后依次执行
+    queued: VecDeque<Command>,
+}
+
+impl Connection {
+    pub(crate) fn new(
+        url: String,
+        config: WsConfig,
+        commands: mpsc::UnboundedReceiver<Command>,
+        events: broadcast::Sender<ConnectionEvent>,
+        manager: Arc<Mutex<SubscriptionManager>>,
+        latency: Arc<Mutex<LatencyTracker>>,
+    ) -> Self {
+        Self {
+            url,
+            config,
+            commands,
+            events,
+            manager,
+            latency,
+            signer: None,
+            queued: VecDeque::new(),
+        }
+    }
+
+    pub(crate) async fn run(mut self, mut socket: Socket) {
+        loop {
+            if let SessionEnd::ClientDropped = self.session(&mut socket).await {
+                let _ = socket.close(None).await;
+                return;
+            }
+            tracing::warn!(url = %self.url, "websocket disconnected, reconnecting");
+            let _ = self.events.send(ConnectionEvent::Disconnected);
+            self.latency.lock().unwrap().reset();
+
+            socket = loop {
+                let mut socket = match self.reconnect().await {
+                    Ok(socket) => socket,
+                    Err(ReconnectEnd::ClientDropped) => return,
+                    Err(ReconnectEnd::GaveUp(diagnostics)) => {
+                        tracing::error!(
+                            url = %diagnostics.url,
+                            attempts = diagnostics.attempts,
+                            error = %diagnostics.last_error,
+                            "websocket reconnect gave up"
+                        );
+                        let _ = self
+                            .events
+                            .send(ConnectionEvent::ConnectionFailed(diagnostics));
+                        self.manager.lock().unwrap().close();
+                        return;
+                    }
+                };
+                match self.restore(&mut socket).await {
+                    Ok(()) => break socket,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "websocket session restore failed");
+                        let _ = self
+                            .events
+                            .send(ConnectionEvent::RestoreFailed(e.to_string()));
+                    }
+                }
+            };
+            tracing::info!(url = %self.url, "websocket reconnected");
+            #[cfg(feature = "metrics")]
+            crate::telemetry::ws_reconnected();
+            let _ = self.events.send(ConnectionEvent::Reconnected);
+        }
+    }
+
+    /// 在一条已建立的连接上处理命令与推送，直到连接断开
+    ///
+    /// 先执行重连期间排队的命令；定期发送 Ping，超过 `stale_timeout` 未收到任何帧则视为连接失效。
+    async fn session(&mut self, socket: &mut Socket) -> SessionEnd {
+        while let Some(command) = self.queued.pop_front() {
+            if let Some(end) = self.handle_command(socket, command).await {
+                return end;
+            }
+        }
+        let mut heartbeat = tokio::time::interval(self.config.ping_interval);
+        heartbeat.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let stale = tokio::time::sleep(self.config.stale_timeout);
+        tokio::pin!(stale);
+
+        loop {
+            tokio::select! {
+                _ = heartbeat.tick() => {
+                    if socket.send(Message::Ping(Default::default())).await.is_err() {
+                        return SessionEnd::Disconnected;
+                    }
+                    self.latency.lock().unwrap().ping_sent(Instant::now());
+                }
+                _ = &mut stale => return SessionEnd::Disconnected,
+                command = self.commands.recv() => {
+                    let Some(command) = command else {
+                        return SessionEnd::ClientDropped;
+                    };
+                    if let Some(end) = self.handle_command(socket, command).await {
+                        return end;
+                    }
+                }
+                frame = socket.next() => {
+                    stale.as_mut().reset(Instant::now() + self.config.stale_timeout);
+                    if let Some(end) = self.handle_frame(frame) {
+                        return end;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 执行一条客户端命令；返回 Some 表示会话结束
+    async fn handle_command(
+        &mut self,
+        socket: &mut Socket,
+        command: Command,
+    ) -> Option<SessionEnd> {
+        let result = match command {
+            Command::Subscribe(channel) => {
+                send_request(socket, &ClientRequest::Subscribe { channel: &channel }).await
+            }
+            Command::Unsubscribe(channel) => {
+                send_request(socket, &ClientRequest::Unsubscribe { channel: &channel }).await
+            }
+            Command::Authenticate { signer, reply } => {
+                let result = self.authenticate(socket, &*signer).await;
+                if result.is_ok() {
+                    self.signer = Some(signer);
+                }
+                // 认证被拒绝不影响连接本身
+                let end = match &result {
+                    Err(Error::Auth(_)) | Ok(()) => None,
+                    Err(_) => Some(SessionEnd::Disconnected),
+                };
+                let _ = reply.send(result);
+                return end;
+            }
+        };
+        result.err().map(|_| SessionEnd::Disconnected)
+    }
+
+    #[tracing::instrument(
+        name = "ws_authenticate",
+        skip_all,
+        fields(url = %self.url, signer = %signer.address())
+    )]
+    async fn authenticate(&self, socket: &mut Socket, signer: &dyn Signer) -> Result<()> {
+        let manager = &self.manager;
+        auth::handshake(socket, signer, |msg| {
+            let mut manager = manager.lock().unwrap();
+            manager.record(&msg);
+            manager.dispatch(msg);
+        })
+        .await
+    }
+
+    /// 处理一个入站帧；返回 Some 表示会话结束
+    fn handle_frame(&self, frame: Option<tungstenite::Result<Message>>) -> Option<SessionEnd> {
+        let Some(Ok(frame)) = frame else {
+            return Some(SessionEnd::Disconnected);
+        };
+        match frame {
+            Message::Close(_) => return Some(SessionEnd::Disconnected),
+            Message::Pong(_) => self.latency.lock().unwrap().pong_received(Instant::now()),
+            _ => {}
+        }
+        let msg = match decode_message(&frame) {
+            Ok(msg) => msg?,
+            // 压缩帧解码失败、非 JSON 消息与字段不符的已知类型消息计为解码错误后忽略
+            Err(e) => {
+                tracing::debug!(error = %e, "dropped undecodable frame");
+                if let Some(metrics) = &self.config.metrics {
+                    metrics.on_decode_error(&e.to_string());
+                }
+                return None;
+            }
+        };
+        if let Some(ts) = msg.server_ts() {
+            let received = now_millis();
+            self.latency.lock().unwrap().record(received, ts);
+            #[cfg(feature = "metrics")]
+            crate::telemetry::ws_lag(received, ts);
+        }
+        // 账户推送带订单号记录 span，便于与下单链路关联
+        let span = match &msg {
+            WsMessage::Order(update) => tracing::info_span!(
+                "ws_order_update",
+                order_id = %update.order_id,
+                status = ?update.status
+            ),
+            WsMessage::Fill(fill) => tracing::info_span!(
+                "ws_fill",
+                order_id = %fill.order_id,
+                trade_id = fill.trade_id
+            ),
+            _ => tracing::Span::none(),
+        };
+        let _entered = span.entered();
+        let mut manager = self.manager.lock().unwrap();
+        manager.record(&msg);
+        match msg {
+            WsMessage::Error(error) => {
+                let _ = self.events.send(ConnectionEvent::ServerError(error));
+            }
+            msg => manager.dispatch(msg),
+        }
+        None
+    }
+
+    /// 按指数退避重连；期间的订阅变更在重连后统一按订阅表恢复，其余命令排队到恢复会话后执行
+    ///
+    /// 连续失败次数达到 `reconnect_max_attempts` 时放弃，排队命令随之丢弃。
+    async fn reconnect(&mut self) -> std::result::Result<Socket, ReconnectEnd> {
+        let started = Instant::now();
+        let mut delay = self.config.reconnect_initial_delay;
+        let mut attempts = 0;
+        loop {
+            let sleep = tokio::time::sleep(delay);
+            tokio::pin!(sleep);
+            loop {
+                tokio::select! {
+                    _ = &mut sleep => break,
+                    command = self.commands.recv() => match command {
+                        None => return Err(ReconnectEnd::ClientDropped),
+                        // 订阅表已记录变更，restore 会按当前订阅表重新订阅
+                        Some(Command::Subscribe(_) | Command::Unsubscribe(_)) => {}
+                        Some(command) => self.queued.push_back(command),
+                    },
+                }
+            }
+
+            let error = match open(&self.url).await {
+                Ok(socket) => return Ok(socket),
+                Err(e) => e,
+            };
+            attempts += 1;
+            tracing::debug!(attempts, delay_ms = delay.as_millis() as u64, error = %error, "websocket reconnect failed");
+            if self
+                .config
+                .reconnect_max_attempts
+                .is_some_and(|max| attempts >= max)
+            {
+                return Err(ReconnectEnd::GaveUp(ConnectionDiagnostics {
+                    url: self.url.clone(),
+                    attempts,
+                    last_error: error.to_string(),
+                    elapsed: started.elapsed(),
+                }));
+            }
+            delay = (delay * 2).min(self.config.reconnect_max_delay);
+        }
+    }
+
+    /// 重连后恢复会话：先重新认证，再重新发送所有活跃订阅
+    async fn restore(&mut self, socket: &mut Socket) -> Result<()> {
+        if let Some(signer) = self.signer.clone() {
+            match self.authenticate(socket, &*signer).await {
+                Ok(()) => {}
+                Err(Error::Auth(reason)) => {
+                    tracing::warn!(reason = %reason, "websocket re-authentication failed");
+                    let _ = self.events.send(ConnectionEvent::AuthFailed(reason));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        let channels = self.manager.lock().unwrap().channels();
+        for channel in &channels {
+            send_request(socket, &ClientRequest::Subscribe { channel }).await?;
+        }
+        Ok(())
+    }
+}
+
+/// 解码数据帧；控制帧返回 Ok(None)
+pub(crate) fn decode_message(frame: &Message) -> Result<Option<WsMessage>> {
+    let decoded;
+    let text = match frame {
+        Message::Text(text) => text.as_str(),
+        Message::Binary(payload) => {
+            decoded = compression::decode_binary(payload)?;
+            decoded.as_str()
+        }
+        _ => return Ok(None),
+    };
+    WsMessage::decode(text).map(Some)
+}
+
+pub(crate) async fn send_request(socket: &mut Socket, request: &ClientRequest<'_>) -> Result<()> {
+    let text = serde_json::to_string(request)?;
+    socket.send(Message::Text(text.into())).await?;
+    Ok(())
+}
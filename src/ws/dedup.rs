@@ -0,0 +1,255 @@
+//! 成交去重
+//!
+//! 断线重连或回放时网关可能重发已推送过的成交。带 `trade_id` 的成交按 ID 去重；
+//! 旧版网关不下发 ID，此时按市场、订单、价格、数量与时间戳组合去重，
+//! 同一订单在同一毫秒以相同价格数量成交多笔的情况会被误判为重复。
+
+use std::collections::{HashSet, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::Stream;
+
+use super::message::{AccountEvent, Fill, Trade, WsMessage};
+use crate::types::OrderSide;
+
+/// 去重键
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EventKey {
+    market: String,
+    /// 成交回报为订单ID，公共成交为空
+    order_id: String,
+    side: OrderSide,
+    identity: Identity,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Identity {
+    TradeId(u64),
+    Fields { price: u64, size: u64, ts: u64 },
+}
+
+impl Identity {
+    fn new(trade_id: Option<u64>, price: u64, size: u64, ts: u64) -> Self {
+        match trade_id {
+            Some(id) => Identity::TradeId(id),
+            None => Identity::Fields { price, size, ts },
+        }
+    }
+}
+
+/// 可去重的事件
+pub trait Dedup {
+    /// 去重键；None 表示该事件不参与去重，总是放行
+    fn dedup_key(&self) -> Option<EventKey>;
+}
+
+impl Dedup for Trade {
+    fn dedup_key(&self) -> Option<EventKey> {
+        Some(EventKey {
+            market: self.market.clone(),
+            order_id: String::new(),
+            side: self.side,
+            identity: Identity::new(self.trade_id, self.price, self.size, self.ts),
+        })
+    }
+}
+
+impl Dedup for Fill {
+    fn dedup_key(&self) -> Option<EventKey> {
+        // 自成交时同一 trade_id 对应买卖两笔回报，键中保留订单ID与方向
+        Some(EventKey {
+            market: self.market.clone(),
+            order_id: self.order_id.clone(),
+            side: self.side,
+            identity: Identity::new(self.trade_id, self.price, self.size, self.ts),
+        })
+    }
+}
+
+impl Dedup for AccountEvent {
+    fn dedup_key(&self) -> Option<EventKey> {
+        match self {
+            AccountEvent::Fill(fill) => fill.dedup_key(),
+            AccountEvent::Order(_) => None,
+        }
+    }
+}
+
+impl Dedup for WsMessage {
+    fn dedup_key(&self) -> Option<EventKey> {
+        match self {
+            WsMessage::Trade(trade) => trade.dedup_key(),
+            WsMessage::Fill(fill) => fill.dedup_key(),
+            _ => None,
+        }
+    }
+}
+
+/// 记住最近 `window` 个去重键的去重器
+#[derive(Debug, Clone)]
+pub struct Deduplicator {
+    window: usize,
+    seen: HashSet<EventKey>,
+    order: VecDeque<EventKey>,
+}
+
+impl Deduplicator {
+    /// `window` 为记住的键数量，应覆盖重连时可能重放的成交条数
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// 事件是否首次出现；首次出现时记录其去重键
+    pub fn is_new<T: Dedup + ?Sized>(&mut self, event: &T) -> bool {
+        let Some(key) = event.dedup_key() else {
+            return true;
+        };
+        if !self.seen.insert(key.clone()) {
+            return false;
+        }
+        self.order.push_back(key);
+        if self.order.len() > self.window {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    /// 当前记住的键数量
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
+/// 丢弃重复事件的流，由 [`Subscription::dedup`](super::Subscription::dedup) 创建
+pub struct Deduped<S> {
+    inner: S,
+    dedup: Deduplicator,
+}
+
+impl<S> Deduped<S> {
+    /// 包装任意事件流
+    pub fn new(inner: S, window: usize) -> Self {
+        Self {
+            inner,
+            dedup: Deduplicator::new(window),
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> Stream for Deduped<S>
+where
+    S: Stream + Unpin,
+    S::Item: Dedup,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        let this = self.get_mut();
+        loop {
+            match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if this.dedup.is_new(&item) {
+                        return Poll::Ready(Some(item));
+                    }
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{stream, StreamExt};
+
+    use super::*;
+
+    fn trade(trade_id: Option<u64>, ts: u64) -> Trade {
+        Trade {
+            market: "BTC-USDC".to_string(),
+            price: 100,
+            size: 1,
+            side: OrderSide::Buy,
+            trade_id,
+            ts,
+        }
+    }
+
+    #[test]
+    fn trade_id_takes_precedence_over_fields() {
+        let mut dedup = Deduplicator::new(16);
+        assert!(dedup.is_new(&trade(Some(1), 5)));
+        assert!(!dedup.is_new(&trade(Some(1), 6)));
+        // 字段完全相同但 ID 不同，是两笔成交
+        assert!(dedup.is_new(&trade(Some(2), 5)));
+        assert!(dedup.is_new(&trade(None, 5)));
+        assert!(!dedup.is_new(&trade(None, 5)));
+        assert!(dedup.is_new(&trade(None, 6)));
+    }
+
+    #[test]
+    fn window_forgets_oldest_keys() {
+        let mut dedup = Deduplicator::new(2);
+        for id in 1..=3 {
+            assert!(dedup.is_new(&trade(Some(id), 0)));
+        }
+        assert_eq!(dedup.len(), 2);
+        assert!(!dedup.is_new(&trade(Some(3), 0)));
+        assert!(dedup.is_new(&trade(Some(1), 0)));
+    }
+
+    #[test]
+    fn self_trade_fills_are_kept() {
+        let fill = |order_id: &str, side| Fill {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: order_id.to_string(),
+            side,
+            price: 100,
+            size: 1,
+            fee: 0,
+            is_maker: side == OrderSide::Sell,
+            trade_id: Some(7),
+            ts: 0,
+        };
+        let mut dedup = Deduplicator::new(16);
+        assert!(dedup.is_new(&AccountEvent::Fill(fill("a", OrderSide::Buy))));
+        assert!(dedup.is_new(&AccountEvent::Fill(fill("b", OrderSide::Sell))));
+        assert!(!dedup.is_new(&AccountEvent::Fill(fill("a", OrderSide::Buy))));
+    }
+
+    #[tokio::test]
+    async fn deduped_stream_drops_replayed_trades() {
+        let replay = vec![
+            trade(Some(1), 0),
+            trade(Some(2), 0),
+            trade(Some(2), 0),
+            trade(Some(1), 0),
+            trade(Some(3), 0),
+        ];
+        let ids: Vec<_> = Deduped::new(stream::iter(replay), 16)
+            .map(|trade| trade.trade_id.unwrap())
+            .collect()
+            .await;
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+}
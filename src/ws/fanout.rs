@@ -0,0 +1,80 @@
+//! 单个订阅的广播分发
+//!
+//! 多个策略任务消费同一行情时，无需各自订阅：[`Subscription::broadcast`](super::Subscription::broadcast)
+//! 用一个后台任务把订阅流转发到 tokio broadcast 通道，所有接收端释放后自动退订。
+
+use futures_util::{Stream, StreamExt};
+use tokio::sync::broadcast;
+
+/// 广播接收端，克隆后从克隆时刻开始接收
+///
+/// 消费过慢落后超过通道容量时，最旧的消息被跳过并计入 [`BroadcastReceiver::lagged`]。
+pub struct BroadcastReceiver<T> {
+    rx: broadcast::Receiver<T>,
+    lagged: u64,
+}
+
+impl<T: Clone> BroadcastReceiver<T> {
+    /// 下一条消息；订阅流结束后返回 None
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            match self.rx.recv().await {
+                Ok(item) => return Some(item),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => self.lagged += skipped,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// 因落后而跳过的消息数
+    pub fn lagged(&self) -> u64 {
+        self.lagged
+    }
+
+    /// 转换为异步流
+    pub fn into_stream(self) -> impl Stream<Item = T> {
+        futures_util::stream::unfold(self, |mut rx| async move {
+            let item = rx.recv().await?;
+            Some((item, rx))
+        })
+    }
+}
+
+impl<T: Clone> Clone for BroadcastReceiver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            rx: self.rx.resubscribe(),
+            lagged: 0,
+        }
+    }
+}
+
+/// 启动转发任务，返回 `n` 个接收端
+pub(crate) fn spawn<S>(mut stream: S, capacity: usize, n: usize) -> Vec<BroadcastReceiver<S::Item>>
+where
+    S: Stream + Unpin + Send + 'static,
+    S::Item: Clone + Send + 'static,
+{
+    let (tx, rx) = broadcast::channel(capacity.max(1));
+    let receivers = (0..n)
+        .map(|_| BroadcastReceiver {
+            rx: rx.resubscribe(),
+            lagged: 0,
+        })
+        .collect();
+    drop(rx);
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                item = stream.next() => match item {
+                    // 所有接收端都已释放
+                    Some(item) => if tx.send(item).is_err() { return },
+                    None => return,
+                },
+                // 没有消息时也能及时发现接收端已全部释放
+                _ = tx.closed() => return,
+            }
+        }
+    });
+    receivers
+}
@@ -0,0 +1,156 @@
+//! 客户端订阅过滤
+//!
+//! 过滤在消息进入订阅队列之前执行，被过滤的消息不占用队列容量。
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::message::{OrderbookUpdate, PriceLevel, WsMessage};
+
+/// 订阅过滤条件，未设置的条件不生效
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SubscriptionFilter {
+    /// 只保留这些市场的消息，主要用于跨市场的账户频道
+    pub markets: Option<HashSet<String>>,
+    /// 只保留数量不小于该值的成交与成交回报
+    pub min_size: Option<u64>,
+    /// 只保留距中间价不超过该比例的订单簿档位，例如 0.01 表示 1%
+    ///
+    /// 中间价取最近一次快照的买一卖一，收到快照之前不过滤。
+    /// 两次快照之间中间价不更新，离开价格带后又回到价格带内的档位需等下一次快照才会出现。
+    pub price_band: Option<f64>,
+}
+
+impl SubscriptionFilter {
+    /// 只保留指定市场的消息
+    pub fn markets<I, S>(markets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            markets: Some(markets.into_iter().map(Into::into).collect()),
+            ..Self::default()
+        }
+    }
+}
+
+/// 单个订阅者的过滤状态
+pub(crate) struct FilterState {
+    filter: Arc<SubscriptionFilter>,
+    mid: Option<f64>,
+}
+
+impl FilterState {
+    pub(crate) fn new(filter: Arc<SubscriptionFilter>) -> Self {
+        Self { filter, mid: None }
+    }
+
+    /// 应用过滤条件；返回 None 表示丢弃该消息
+    pub(crate) fn apply(&mut self, msg: WsMessage) -> Option<WsMessage> {
+        if let (Some(markets), Some(market)) = (&self.filter.markets, msg.market()) {
+            if !markets.contains(market) {
+                return None;
+            }
+        }
+        match msg {
+            WsMessage::Trade(trade) if self.below_min_size(trade.size) => None,
+            WsMessage::Fill(fill) if self.below_min_size(fill.size) => None,
+            WsMessage::Book(update) => Some(WsMessage::Book(self.apply_band(update))),
+            msg => Some(msg),
+        }
+    }
+
+    fn below_min_size(&self, size: u64) -> bool {
+        self.filter.min_size.is_some_and(|min| size < min)
+    }
+
+    /// 增量即使所有档位都被过滤也会交付，以保持序列号连续
+    fn apply_band(&mut self, mut update: OrderbookUpdate) -> OrderbookUpdate {
+        let Some(band) = self.filter.price_band else {
+            return update;
+        };
+        if update.snapshot {
+            self.mid = snapshot_mid(&update);
+        }
+        if let Some(mid) = self.mid {
+            let in_band = |level: &PriceLevel| ((level.price as f64 - mid) / mid).abs() <= band;
+            update.bids.retain(in_band);
+            update.asks.retain(in_band);
+        }
+        update
+    }
+}
+
+fn snapshot_mid(update: &OrderbookUpdate) -> Option<f64> {
+    let live = |level: &&PriceLevel| level.size > 0;
+    let best_bid = update.bids.iter().filter(live).map(|l| l.price).max()?;
+    let best_ask = update.asks.iter().filter(live).map(|l| l.price).min()?;
+    Some((best_bid as f64 + best_ask as f64) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderSide;
+    use crate::ws::message::Trade;
+
+    fn book(snapshot: bool, bids: &[u64], asks: &[u64]) -> WsMessage {
+        let levels = |prices: &[u64]| {
+            prices
+                .iter()
+                .map(|&price| PriceLevel { price, size: 1 })
+                .collect()
+        };
+        WsMessage::Book(OrderbookUpdate {
+            market: "BTC-USDC".to_string(),
+            seq: 1,
+            snapshot,
+            bids: levels(bids),
+            asks: levels(asks),
+            ts: 0,
+        })
+    }
+
+    #[test]
+    fn price_band_uses_snapshot_mid() {
+        let mut state = FilterState::new(Arc::new(SubscriptionFilter {
+            price_band: Some(0.05),
+            ..SubscriptionFilter::default()
+        }));
+        // 尚无快照，不过滤
+        assert_eq!(
+            state.apply(book(false, &[1], &[])),
+            Some(book(false, &[1], &[]))
+        );
+        assert_eq!(
+            state.apply(book(true, &[99, 90], &[101, 120])),
+            Some(book(true, &[99], &[101]))
+        );
+        assert_eq!(
+            state.apply(book(false, &[96, 50], &[])),
+            Some(book(false, &[96], &[]))
+        );
+    }
+
+    #[test]
+    fn market_and_size_filters() {
+        let mut state = FilterState::new(Arc::new(SubscriptionFilter {
+            min_size: Some(10),
+            ..SubscriptionFilter::markets(["BTC-USDC"])
+        }));
+        let trade = |market: &str, size| {
+            WsMessage::Trade(Trade {
+                market: market.to_string(),
+                price: 1,
+                size,
+                side: OrderSide::Buy,
+                trade_id: None,
+                ts: 0,
+            })
+        };
+        assert!(state.apply(trade("ETH-USDC", 50)).is_none());
+        assert!(state.apply(trade("BTC-USDC", 5)).is_none());
+        assert!(state.apply(trade("BTC-USDC", 10)).is_some());
+    }
+}
@@ -0,0 +1,392 @@
+//! 持久化行情日志
+//!
+//! 日志目录由若干段文件组成，每段包含：
+//!
+//! - `<首条序号>.seg`：追加写入的记录，每条为 `seq(u64) | len(u32) | crc32(u32) | payload`（小端），
+//!   payload 与录制文件中的一行相同
+//! - `<首条序号>.idx`：每条记录的 `seq(u64) | offset(u64)`，用于按序号定位
+//!
+//! 打开日志时校验最后一段，截掉崩溃时写了一半的尾部记录并据此重建索引，
+//! 之后从最后一条已持久化的序号继续写入。
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use super::record::{Record, RecordWriter};
+use crate::error::{Error, Result};
+
+const HEADER_LEN: u64 = 16;
+const INDEX_ENTRY_LEN: u64 = 16;
+
+/// 日志配置
+#[derive(Debug, Clone)]
+pub struct JournalConfig {
+    /// 段文件超过该大小后滚动到新段
+    pub segment_bytes: u64,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            segment_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// 日志中的一条记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    /// 日志内的序号，从 1 开始连续递增
+    pub seq: u64,
+    pub record: Record,
+}
+
+struct Segment {
+    log: BufWriter<File>,
+    index: BufWriter<File>,
+    len: u64,
+}
+
+/// 追加写入的持久化行情日志
+///
+/// 通过 [`WsClient::start_journal`](super::WsClient::start_journal) 挂到连接上持续写入，
+/// 或用 [`Journal::read_from`] 从指定序号读取。
+pub struct Journal {
+    dir: PathBuf,
+    config: JournalConfig,
+    /// 已存在的段的首条序号，升序
+    segments: Vec<u64>,
+    active: Option<Segment>,
+    last_seq: u64,
+}
+
+impl Journal {
+    /// 打开或创建日志目录
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with_config(dir, JournalConfig::default())
+    }
+
+    /// 使用指定配置打开或创建日志目录
+    pub fn open_with_config(dir: impl AsRef<Path>, config: JournalConfig) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let segments = list_segments(&dir)?;
+        let mut journal = Self {
+            dir,
+            config,
+            segments,
+            active: None,
+            last_seq: 0,
+        };
+        if let Some(&first_seq) = journal.segments.last() {
+            journal.last_seq = journal.recover(first_seq)?;
+        }
+        Ok(journal)
+    }
+
+    /// 最后一条已写入的序号；空日志返回 0
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+
+    /// 追加一条记录（payload 为录制格式的一行），返回分配的序号
+    pub(crate) fn append(&mut self, payload: &[u8]) -> Result<u64> {
+        let seq = self.last_seq + 1;
+        let rollover = self
+            .active
+            .as_ref()
+            .is_none_or(|segment| segment.len >= self.config.segment_bytes);
+        if rollover {
+            self.roll(seq)?;
+        }
+        let segment = self.active.as_mut().expect("active segment");
+        let offset = segment.len;
+        segment.log.write_all(&seq.to_le_bytes())?;
+        segment
+            .log
+            .write_all(&(payload.len() as u32).to_le_bytes())?;
+        segment
+            .log
+            .write_all(&crc32fast::hash(payload).to_le_bytes())?;
+        segment.log.write_all(payload)?;
+        segment.index.write_all(&seq.to_le_bytes())?;
+        segment.index.write_all(&offset.to_le_bytes())?;
+        segment.len += HEADER_LEN + payload.len() as u64;
+        self.last_seq = seq;
+        Ok(seq)
+    }
+
+    /// 把缓冲写入磁盘并 fsync
+    pub fn sync(&mut self) -> Result<()> {
+        if let Some(segment) = self.active.as_mut() {
+            segment.log.flush()?;
+            segment.log.get_ref().sync_data()?;
+            segment.index.flush()?;
+            segment.index.get_ref().sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// 从序号 `seq`（含）开始读取记录
+    ///
+    /// 读取的是已刷到文件的数据，写入中的日志可先调用 [`Journal::sync`]。
+    pub fn read_from(&self, seq: u64) -> Result<JournalReader> {
+        let seq = seq.max(1);
+        // 包含 seq 的段：首条序号不大于 seq 的最后一段
+        let start = self
+            .segments
+            .partition_point(|&first| first <= seq)
+            .saturating_sub(1);
+        let segments = self.segments[start..].to_vec();
+        let mut reader = JournalReader {
+            dir: self.dir.clone(),
+            segments: segments.into_iter(),
+            current: None,
+            next_seq: seq,
+        };
+        if let Some(first_seq) = reader.segments.next() {
+            let offset = index_lookup(&self.dir, first_seq, seq)?;
+            reader.current = Some(open_segment_at(&self.dir, first_seq, offset)?);
+        }
+        Ok(reader)
+    }
+
+    /// 在序号 `first_seq` 处开启新段
+    fn roll(&mut self, first_seq: u64) -> Result<()> {
+        self.sync()?;
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(segment_path(&self.dir, first_seq, "seg"))?;
+        let index = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(segment_path(&self.dir, first_seq, "idx"))?;
+        self.active = Some(Segment {
+            log: BufWriter::new(log),
+            index: BufWriter::new(index),
+            len: 0,
+        });
+        if self.segments.last() != Some(&first_seq) {
+            self.segments.push(first_seq);
+        }
+        Ok(())
+    }
+
+    /// 校验最后一段：截掉不完整或校验失败的尾部记录并重建索引，返回最后一条有效序号
+    fn recover(&mut self, first_seq: u64) -> Result<u64> {
+        let log_path = segment_path(&self.dir, first_seq, "seg");
+        let mut reader = BufReader::new(File::open(&log_path)?);
+        let mut index = Vec::new();
+        let mut valid_len = 0u64;
+        let mut last_seq = first_seq - 1;
+        while let Some((seq, payload)) = read_entry(&mut reader)? {
+            if seq != last_seq + 1 {
+                break;
+            }
+            index.extend_from_slice(&seq.to_le_bytes());
+            index.extend_from_slice(&valid_len.to_le_bytes());
+            valid_len += HEADER_LEN + payload.len() as u64;
+            last_seq = seq;
+        }
+
+        OpenOptions::new()
+            .write(true)
+            .open(&log_path)?
+            .set_len(valid_len)?;
+        fs::write(segment_path(&self.dir, first_seq, "idx"), &index)?;
+
+        self.roll(first_seq)?;
+        if let Some(segment) = self.active.as_mut() {
+            segment.len = valid_len;
+        }
+        Ok(last_seq)
+    }
+}
+
+impl Drop for Journal {
+    fn drop(&mut self) {
+        let _ = self.sync();
+    }
+}
+
+impl RecordWriter for Journal {
+    fn write_record(&mut self, line: &str) -> io::Result<()> {
+        self.append(line.as_bytes()).map(|_| ()).map_err(into_io)
+    }
+
+    fn commit(&mut self) -> io::Result<()> {
+        self.sync().map_err(into_io)
+    }
+}
+
+fn into_io(e: Error) -> io::Error {
+    match e {
+        Error::Io(e) => e,
+        e => io::Error::other(e.to_string()),
+    }
+}
+
+/// 按序号顺序读取日志
+pub struct JournalReader {
+    dir: PathBuf,
+    segments: std::vec::IntoIter<u64>,
+    current: Option<BufReader<File>>,
+    next_seq: u64,
+}
+
+impl Iterator for JournalReader {
+    type Item = Result<JournalEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let current = self.current.as_mut()?;
+            match read_entry(current) {
+                Ok(Some((seq, payload))) => {
+                    // 段首到目标序号之间的记录跳过
+                    if seq < self.next_seq {
+                        continue;
+                    }
+                    self.next_seq = seq + 1;
+                    return Some(
+                        serde_json::from_slice(&payload)
+                            .map(|record| JournalEntry { seq, record })
+                            .map_err(Error::from),
+                    );
+                }
+                Ok(None) => {
+                    let first_seq = self.segments.next()?;
+                    match open_segment_at(&self.dir, first_seq, 0) {
+                        Ok(reader) => self.current = Some(reader),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// 读取一条记录；文件结束或尾部记录不完整、校验失败时返回 None
+fn read_entry(reader: &mut impl Read) -> Result<Option<(u64, Vec<u8>)>> {
+    let mut header = [0u8; HEADER_LEN as usize];
+    if !read_full(reader, &mut header)? {
+        return Ok(None);
+    }
+    let seq = u64::from_le_bytes(header[0..8].try_into().unwrap());
+    let len = u32::from_le_bytes(header[8..12].try_into().unwrap()) as usize;
+    let crc = u32::from_le_bytes(header[12..16].try_into().unwrap());
+    let mut payload = vec![0u8; len];
+    if !read_full(reader, &mut payload)? || crc32fast::hash(&payload) != crc {
+        return Ok(None);
+    }
+    Ok(Some((seq, payload)))
+}
+
+/// 读满缓冲区；数据不足时返回 false
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => return Ok(false),
+            n => filled += n,
+        }
+    }
+    Ok(true)
+}
+
+/// 在段索引中查找不晚于 `seq` 的最后一条记录的偏移
+fn index_lookup(dir: &Path, first_seq: u64, seq: u64) -> Result<u64> {
+    let index = fs::read(segment_path(dir, first_seq, "idx"))?;
+    let entries: Vec<(u64, u64)> = index
+        .chunks_exact(INDEX_ENTRY_LEN as usize)
+        .map(|entry| {
+            (
+                u64::from_le_bytes(entry[0..8].try_into().unwrap()),
+                u64::from_le_bytes(entry[8..16].try_into().unwrap()),
+            )
+        })
+        .collect();
+    let pos = entries.partition_point(|&(entry_seq, _)| entry_seq <= seq);
+    Ok(pos.checked_sub(1).map_or(0, |i| entries[i].1))
+}
+
+fn open_segment_at(dir: &Path, first_seq: u64, offset: u64) -> Result<BufReader<File>> {
+    let mut file = File::open(segment_path(dir, first_seq, "seg"))?;
+    file.seek(SeekFrom::Start(offset))?;
+    Ok(BufReader::new(file))
+}
+
+fn segment_path(dir: &Path, first_seq: u64, ext: &str) -> PathBuf {
+    dir.join(format!("{:020}.{}", first_seq, ext))
+}
+
+fn list_segments(dir: &Path) -> Result<Vec<u64>> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "seg") {
+            if let Some(first_seq) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse().ok())
+            {
+                segments.push(first_seq);
+            }
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::message::{Pong, WsMessage};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("lightpool-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn payload(ts: u64) -> Vec<u8> {
+        format!(r#"{{"ts":{},"message":{{"type":"pong","ts":{}}}}}"#, ts, ts).into_bytes()
+    }
+
+    #[test]
+    fn resumes_after_torn_write_and_reads_across_segments() {
+        let dir = temp_dir("journal");
+        let config = JournalConfig { segment_bytes: 200 };
+        {
+            let mut journal = Journal::open_with_config(&dir, config.clone()).unwrap();
+            for ts in 1..=6 {
+                assert_eq!(journal.append(&payload(ts)).unwrap(), ts);
+            }
+        }
+        // 模拟崩溃：最后一段末尾留下半条记录
+        let last = *list_segments(&dir).unwrap().last().unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(segment_path(&dir, last, "seg"))
+            .unwrap();
+        file.write_all(&7u64.to_le_bytes()).unwrap();
+        drop(file);
+
+        let mut journal = Journal::open_with_config(&dir, config).unwrap();
+        assert_eq!(journal.last_seq(), 6);
+        assert_eq!(journal.append(&payload(7)).unwrap(), 7);
+        journal.sync().unwrap();
+        assert!(list_segments(&dir).unwrap().len() > 1);
+
+        let entries: Vec<JournalEntry> =
+            journal.read_from(3).unwrap().map(Result::unwrap).collect();
+        assert_eq!(
+            entries.iter().map(|entry| entry.seq).collect::<Vec<_>>(),
+            vec![3, 4, 5, 6, 7]
+        );
+        assert_eq!(entries[0].record.message, WsMessage::Pong(Pong { ts: 3 }));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+//! 延迟与时钟偏差统计
+//!
+//! 每条带服务端时间戳的推送记录一次 `本地接收时间 - 服务端时间`，保留最近的若干样本。
+//! 该差值包含单程网络延迟与两端时钟偏差；结合心跳往返时间可估算时钟偏差：
+//! `偏差 ≈ 最小差值 - RTT / 2`。
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// 滚动窗口内的延迟统计，单位均为毫秒
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyStats {
+    /// 窗口内样本数
+    pub samples: usize,
+    pub mean_ms: f64,
+    pub min_ms: i64,
+    pub max_ms: i64,
+    pub p50_ms: i64,
+    pub p99_ms: i64,
+    /// 最近一次心跳往返时间
+    pub rtt_ms: Option<u64>,
+    /// 估算的本地时钟相对网关的偏差，正值表示本地时钟超前
+    pub clock_skew_ms: Option<i64>,
+}
+
+pub(crate) struct LatencyTracker {
+    window: VecDeque<i64>,
+    capacity: usize,
+    ping_sent: Option<Instant>,
+    rtt: Option<Duration>,
+}
+
+impl LatencyTracker {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            window: VecDeque::new(),
+            capacity: capacity.max(1),
+            ping_sent: None,
+            rtt: None,
+        }
+    }
+
+    /// 记录一条推送的接收延迟
+    pub(crate) fn record(&mut self, received_ms: u64, server_ts: u64) {
+        if self.window.len() == self.capacity {
+            self.window.pop_front();
+        }
+        self.window.push_back(received_ms as i64 - server_ts as i64);
+    }
+
+    pub(crate) fn ping_sent(&mut self, at: Instant) {
+        self.ping_sent = Some(at);
+    }
+
+    pub(crate) fn pong_received(&mut self, at: Instant) {
+        if let Some(sent) = self.ping_sent.take() {
+            self.rtt = Some(at.saturating_duration_since(sent));
+        }
+    }
+
+    /// 连接重建后旧样本不再代表当前链路
+    pub(crate) fn reset(&mut self) {
+        self.window.clear();
+        self.ping_sent = None;
+        self.rtt = None;
+    }
+
+    pub(crate) fn stats(&self) -> Option<LatencyStats> {
+        if self.window.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<i64> = self.window.iter().copied().collect();
+        sorted.sort_unstable();
+        let percentile = |p: f64| sorted[((sorted.len() - 1) as f64 * p).round() as usize];
+        let min_ms = sorted[0];
+        let rtt_ms = self.rtt.map(|rtt| rtt.as_millis() as u64);
+        Some(LatencyStats {
+            samples: sorted.len(),
+            mean_ms: sorted.iter().sum::<i64>() as f64 / sorted.len() as f64,
+            min_ms,
+            max_ms: sorted[sorted.len() - 1],
+            p50_ms: percentile(0.5),
+            p99_ms: percentile(0.99),
+            rtt_ms,
+            clock_skew_ms: rtt_ms.map(|rtt| min_ms - (rtt / 2) as i64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolling_window_and_skew_estimate() {
+        let mut tracker = LatencyTracker::new(3);
+        assert!(tracker.stats().is_none());
+        for (received, server) in [(1_000, 900), (1_050, 1_000), (1_080, 1_000), (1_120, 1_000)] {
+            tracker.record(received, server);
+        }
+        let start = Instant::now();
+        tracker.ping_sent(start);
+        tracker.pong_received(start + Duration::from_millis(40));
+
+        let stats = tracker.stats().unwrap();
+        // 第一条样本已滑出窗口
+        assert_eq!(stats.samples, 3);
+        assert_eq!((stats.min_ms, stats.p50_ms, stats.max_ms), (50, 80, 120));
+        assert_eq!(stats.rtt_ms, Some(40));
+        assert_eq!(stats.clock_skew_ms, Some(30));
+    }
+}
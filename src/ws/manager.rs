@@ -0,0 +1,183 @@
+//! 订阅管理：多个逻辑订阅共享一条连接
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{mpsc, oneshot};
+
+use super::filter::FilterState;
+use super::message::{Channel, WsMessage};
+use super::metrics::MetricsSink;
+use super::queue;
+use super::record::{self, RecordSink};
+use crate::crypto::Signer;
+use crate::error::Result;
+
+/// 发往后台连接任务的网关请求
+pub(crate) enum Command {
+    Subscribe(Channel),
+    Unsubscribe(Channel),
+    Authenticate {
+        signer: Arc<dyn Signer>,
+        reply: oneshot::Sender<Result<()>>,
+    },
+}
+
+/// 单个订阅者
+struct Subscriber {
+    sender: queue::Sender<WsMessage>,
+    filter: Option<FilterState>,
+}
+
+/// 订阅管理器
+///
+/// 按频道维护订阅者引用计数：频道的第一个订阅者触发网关订阅，
+/// 最后一个订阅者释放时触发网关退订；推送消息按频道分发给所有订阅者。
+#[derive(Default)]
+pub(crate) struct SubscriptionManager {
+    next_id: u64,
+    routes: HashMap<Channel, HashMap<u64, Subscriber>>,
+    recorders: HashMap<u64, RecordSink>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+}
+
+impl SubscriptionManager {
+    pub(crate) fn new(metrics: Option<Arc<dyn MetricsSink>>) -> Self {
+        Self {
+            metrics,
+            ..Self::default()
+        }
+    }
+
+    /// 登记订阅者；返回订阅者ID，以及该频道是否为新频道
+    pub(crate) fn add(
+        &mut self,
+        channel: Channel,
+        sender: queue::Sender<WsMessage>,
+        filter: Option<FilterState>,
+    ) -> (u64, bool) {
+        let id = self.next_id;
+        self.next_id += 1;
+        let subscribers = self.routes.entry(channel).or_default();
+        subscribers.insert(id, Subscriber { sender, filter });
+        (id, subscribers.len() == 1)
+    }
+
+    /// 移除订阅者；返回该频道是否已无订阅者
+    pub(crate) fn remove(&mut self, channel: &Channel, id: u64) -> bool {
+        let Some(subscribers) = self.routes.get_mut(channel) else {
+            return false;
+        };
+        subscribers.remove(&id);
+        if subscribers.is_empty() {
+            self.routes.remove(channel);
+            return true;
+        }
+        false
+    }
+
+    /// 把消息分发给所属频道的所有订阅者
+    pub(crate) fn dispatch(&mut self, msg: WsMessage) {
+        let Some(channel) = msg.channel() else {
+            return;
+        };
+        if let Some(subscribers) = self.routes.get_mut(&channel) {
+            for (&id, subscriber) in subscribers.iter_mut() {
+                let msg = match subscriber.filter.as_mut() {
+                    Some(filter) => match filter.apply(msg.clone()) {
+                        Some(msg) => msg,
+                        None => continue,
+                    },
+                    None => msg.clone(),
+                };
+                let pushed = subscriber.sender.push(msg);
+                if let Some(metrics) = &self.metrics {
+                    if pushed.dropped > 0 {
+                        metrics.on_dropped(&channel, id, pushed.dropped);
+                    }
+                    if pushed.accepted {
+                        metrics.on_delivered(&channel, id, pushed.depth);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 结束所有订阅流，订阅者随后释放时不再触发退订
+    pub(crate) fn close(&mut self) {
+        self.routes.clear();
+    }
+
+    pub(crate) fn add_recorder(&mut self, sink: RecordSink) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.recorders.insert(id, sink);
+        id
+    }
+
+    pub(crate) fn remove_recorder(&mut self, id: u64) {
+        self.recorders.remove(&id);
+    }
+
+    /// 把收到的消息写入所有录制器
+    pub(crate) fn record(&self, msg: &WsMessage) {
+        if self.recorders.is_empty() {
+            return;
+        }
+        if let Some(line) = record::encode_record(msg) {
+            for sink in self.recorders.values() {
+                let _ = sink.send(line.clone());
+            }
+        }
+    }
+
+    /// 当前活跃的频道
+    pub(crate) fn channels(&self) -> Vec<Channel> {
+        self.routes.keys().cloned().collect()
+    }
+
+    /// 频道的订阅者数量
+    pub(crate) fn subscriber_count(&self, channel: &Channel) -> usize {
+        self.routes.get(channel).map_or(0, HashMap::len)
+    }
+}
+
+/// 订阅句柄，释放时减少频道引用计数
+pub(crate) struct SubscriptionHandle {
+    id: u64,
+    channel: Channel,
+    manager: Arc<Mutex<SubscriptionManager>>,
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl SubscriptionHandle {
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub(crate) fn new(
+        id: u64,
+        channel: Channel,
+        manager: Arc<Mutex<SubscriptionManager>>,
+        commands: mpsc::UnboundedSender<Command>,
+    ) -> Self {
+        Self {
+            id,
+            channel,
+            manager,
+            commands,
+        }
+    }
+}
+
+impl Drop for SubscriptionHandle {
+    fn drop(&mut self) {
+        // 持锁发送命令，保证与并发订阅产生的命令顺序一致
+        let mut manager = self.manager.lock().unwrap();
+        if manager.remove(&self.channel, self.id) {
+            let _ = self
+                .commands
+                .send(Command::Unsubscribe(self.channel.clone()));
+        }
+    }
+}
@@ -0,0 +1,605 @@
+//! WebSocket 消息类型定义
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::types::OrderSide;
+
+/// 订阅频道
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "channel", rename_all = "snake_case")]
+pub enum Channel {
+    /// 订单簿频道
+    Orderbook { market: String },
+    /// 公共成交频道
+    Trades { market: String },
+    /// 最优买卖价与行情摘要频道
+    Ticker { market: String },
+    /// K线频道
+    Candles {
+        market: String,
+        interval: CandleInterval,
+    },
+    /// 账户私有频道（订单状态与成交），需先完成连接认证
+    Account { address: String },
+    /// 逐笔订单簿频道，推送每笔挂单的新增、修改与撤出
+    L3 { market: String },
+    /// 永续合约资金费率结算频道
+    Funding { market: String },
+    /// 指数价格与标记价格频道
+    Prices { market: String },
+    /// 24 小时统计与未平仓量频道
+    Stats { market: String },
+}
+
+/// 价格档位，size 为 0 表示该档位被移除
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceLevel {
+    pub price: u64,
+    pub size: u64,
+}
+
+/// 订单簿更新事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderbookUpdate {
+    pub market: String,
+    pub seq: u64,
+    /// true 表示全量快照，false 表示增量更新
+    #[serde(default)]
+    pub snapshot: bool,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+    /// 服务端时间戳（毫秒）
+    pub ts: u64,
+}
+
+/// 公共成交事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trade {
+    pub market: String,
+    pub price: u64,
+    pub size: u64,
+    /// 主动成交方（taker）方向
+    pub side: OrderSide,
+    /// 成交ID，同一市场内单调递增；旧版网关不下发
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<u64>,
+    /// 成交时间戳（毫秒）
+    pub ts: u64,
+}
+
+/// 最优买卖价（BBO）与行情摘要
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ticker {
+    pub market: String,
+    /// 最优买价，买盘为空时为 None
+    #[serde(default)]
+    pub best_bid: Option<u64>,
+    /// 最优卖价，卖盘为空时为 None
+    #[serde(default)]
+    pub best_ask: Option<u64>,
+    pub last_price: u64,
+    /// 24小时成交量
+    pub volume_24h: u64,
+    pub ts: u64,
+}
+
+/// K线周期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum CandleInterval {
+    #[serde(rename = "1s")]
+    Sec1,
+    #[serde(rename = "1m")]
+    Min1,
+    #[serde(rename = "5m")]
+    Min5,
+    #[serde(rename = "15m")]
+    Min15,
+    #[serde(rename = "1h")]
+    Hour1,
+    #[serde(rename = "4h")]
+    Hour4,
+    #[serde(rename = "1d")]
+    Day1,
+}
+
+impl CandleInterval {
+    /// 周期长度（毫秒）
+    pub fn as_millis(&self) -> u64 {
+        match self {
+            CandleInterval::Sec1 => 1_000,
+            CandleInterval::Min1 => 60_000,
+            CandleInterval::Min5 => 5 * 60_000,
+            CandleInterval::Min15 => 15 * 60_000,
+            CandleInterval::Hour1 => 3_600_000,
+            CandleInterval::Hour4 => 4 * 3_600_000,
+            CandleInterval::Day1 => 24 * 3_600_000,
+        }
+    }
+}
+
+/// K线（OHLCV）
+///
+/// 同一根K线在收盘前会以 `closed == false` 多次推送，`open_time` 相同；
+/// 收盘时推送最后一次 `closed == true` 的终值。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Candle {
+    pub market: String,
+    pub interval: CandleInterval,
+    /// 开盘时间（毫秒）
+    pub open_time: u64,
+    pub open: u64,
+    pub high: u64,
+    pub low: u64,
+    pub close: u64,
+    pub volume: u64,
+    /// 是否已收盘
+    pub closed: bool,
+}
+
+impl Candle {
+    /// 收盘时间（毫秒，不含）
+    pub fn close_time(&self) -> u64 {
+        self.open_time + self.interval.as_millis()
+    }
+}
+
+/// 订单状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    /// 已被撮合引擎接受
+    Acked,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Rejected,
+    /// 有效期届满，被撮合引擎移除
+    Expired,
+}
+
+/// 永续合约资金费率结算
+///
+/// `rate` 为正时多头向空头支付 `持仓数量 × mark_price × rate`，为负时相反。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FundingRate {
+    pub market: String,
+    /// 本期资金费率（小数，例如 0.0001 表示 1 个基点）
+    pub rate: f64,
+    /// 结算使用的标记价格
+    pub mark_price: u64,
+    /// 结算时间（毫秒），同一期的重复推送时间相同
+    pub funding_time: u64,
+    pub ts: u64,
+}
+
+/// 指数价格，由预言机汇总外部现货价格
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexPrice {
+    pub market: String,
+    pub price: u64,
+    pub ts: u64,
+}
+
+/// 标记价格，用于计算未实现盈亏、强平与触发单
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarkPrice {
+    pub market: String,
+    pub price: u64,
+    /// 计算标记价格时使用的指数价格
+    #[serde(default)]
+    pub index_price: Option<u64>,
+    pub ts: u64,
+}
+
+/// 市场 24 小时统计
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketStats {
+    pub market: String,
+    pub last_price: u64,
+    /// 24 小时前的成交价
+    pub open_24h: u64,
+    pub high_24h: u64,
+    pub low_24h: u64,
+    /// 24 小时成交量（基础币）
+    pub volume_24h: u64,
+    /// 24 小时成交额（报价币）
+    #[serde(default)]
+    pub quote_volume_24h: u64,
+    /// 未平仓量，现货市场为 None
+    #[serde(default)]
+    pub open_interest: Option<u64>,
+    pub ts: u64,
+}
+
+impl MarketStats {
+    /// 24 小时价格变化
+    pub fn price_change(&self) -> i64 {
+        self.last_price as i64 - self.open_24h as i64
+    }
+
+    /// 24 小时涨跌幅（小数，例如 0.05 表示 5%）；24 小时前无成交时返回 None
+    pub fn price_change_pct(&self) -> Option<f64> {
+        if self.open_24h == 0 {
+            return None;
+        }
+        Some(self.price_change() as f64 / self.open_24h as f64)
+    }
+}
+
+/// 逐笔订单簿事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum L3Action {
+    /// 新挂单进入订单簿
+    Add,
+    /// 挂单价格或剩余数量变化（部分成交、改单）
+    Modify,
+    /// 挂单完全成交或撤单后离开订单簿
+    Remove,
+}
+
+/// 逐笔订单簿事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L3Event {
+    pub market: String,
+    pub seq: u64,
+    pub action: L3Action,
+    /// 订单ID（32字节十六进制）
+    pub order_id: String,
+    pub side: OrderSide,
+    pub price: u64,
+    /// 剩余数量，Remove 时为 0
+    #[serde(default)]
+    pub size: u64,
+    pub ts: u64,
+}
+
+/// 账户订单状态更新
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderUpdate {
+    pub account: String,
+    pub market: String,
+    /// 订单ID（32字节十六进制）
+    pub order_id: String,
+    pub side: OrderSide,
+    pub status: OrderStatus,
+    pub price: u64,
+    pub amount: u64,
+    /// 累计成交数量
+    pub filled: u64,
+    pub ts: u64,
+}
+
+/// 账户成交回报
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fill {
+    pub account: String,
+    pub market: String,
+    pub order_id: String,
+    pub side: OrderSide,
+    pub price: u64,
+    pub size: u64,
+    pub fee: u64,
+    /// 是否为挂单方成交
+    pub is_maker: bool,
+    /// 成交ID，与公共成交的 `trade_id` 一致；旧版网关不下发
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<u64>,
+    pub ts: u64,
+}
+
+/// 账户私有频道事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountEvent {
+    Order(OrderUpdate),
+    Fill(Fill),
+}
+
+/// 客户端发往网关的请求
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub(crate) enum ClientRequest<'a> {
+    Subscribe {
+        #[serde(flatten)]
+        channel: &'a Channel,
+    },
+    Unsubscribe {
+        #[serde(flatten)]
+        channel: &'a Channel,
+    },
+    /// 请求认证挑战
+    Challenge,
+    /// 提交挑战签名，公钥与签名均为十六进制
+    Auth {
+        address: String,
+        public_key: String,
+        signature: String,
+    },
+}
+
+/// 网关错误消息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorMessage {
+    #[serde(default)]
+    pub code: Option<i64>,
+    pub message: String,
+}
+
+/// 认证挑战，客户端需对 nonce 解码后的字节签名
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Challenge {
+    pub nonce: String,
+}
+
+/// 认证成功
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Authenticated {
+    pub address: String,
+}
+
+/// 心跳响应
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pong {
+    #[serde(default)]
+    pub ts: u64,
+}
+
+/// 网关推送的消息
+///
+/// 已知 `type` 的消息按对应结构严格解码，字段缺失或类型不符时解码失败；
+/// 只有无法识别的 `type` 才保留为 [`WsMessage::Unknown`]。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsMessage {
+    Book(OrderbookUpdate),
+    Trade(Trade),
+    Ticker(Ticker),
+    Candle(Candle),
+    Order(OrderUpdate),
+    Fill(Fill),
+    L3(L3Event),
+    Funding(FundingRate),
+    IndexPrice(IndexPrice),
+    MarkPrice(MarkPrice),
+    Stats(MarketStats),
+    Error(ErrorMessage),
+    Pong(Pong),
+    Challenge(Challenge),
+    Authenticated(Authenticated),
+    /// 无法识别的消息，保留原始 JSON
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
+}
+
+impl<'de> Deserialize<'de> for WsMessage {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error as _;
+        use serde_json::{from_value, Value};
+
+        let value = Value::deserialize(deserializer)?;
+        let Some(kind) = value.get("type").and_then(Value::as_str) else {
+            return Ok(WsMessage::Unknown(value));
+        };
+        let payload = || {
+            let mut payload = value.clone();
+            if let Some(object) = payload.as_object_mut() {
+                object.remove("type");
+            }
+            payload
+        };
+        let message = match kind {
+            "book" => from_value(payload()).map(WsMessage::Book),
+            "trade" => from_value(payload()).map(WsMessage::Trade),
+            "ticker" => from_value(payload()).map(WsMessage::Ticker),
+            "candle" => from_value(payload()).map(WsMessage::Candle),
+            "order" => from_value(payload()).map(WsMessage::Order),
+            "fill" => from_value(payload()).map(WsMessage::Fill),
+            "l3" => from_value(payload()).map(WsMessage::L3),
+            "funding" => from_value(payload()).map(WsMessage::Funding),
+            "index_price" => from_value(payload()).map(WsMessage::IndexPrice),
+            "mark_price" => from_value(payload()).map(WsMessage::MarkPrice),
+            "stats" => from_value(payload()).map(WsMessage::Stats),
+            "error" => from_value(payload()).map(WsMessage::Error),
+            "pong" => from_value(payload()).map(WsMessage::Pong),
+            "challenge" => from_value(payload()).map(WsMessage::Challenge),
+            "authenticated" => from_value(payload()).map(WsMessage::Authenticated),
+            _ => return Ok(WsMessage::Unknown(value)),
+        };
+        message.map_err(|e| D::Error::custom(format!("invalid {} message: {}", kind, e)))
+    }
+}
+
+impl WsMessage {
+    /// 从 JSON 文本解码
+    pub fn decode(text: &str) -> Result<Self> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    /// 消息所属的市场；连接级消息返回 None
+    pub fn market(&self) -> Option<&str> {
+        match self {
+            WsMessage::Book(update) => Some(&update.market),
+            WsMessage::Trade(trade) => Some(&trade.market),
+            WsMessage::Ticker(ticker) => Some(&ticker.market),
+            WsMessage::Candle(candle) => Some(&candle.market),
+            WsMessage::Order(order) => Some(&order.market),
+            WsMessage::Fill(fill) => Some(&fill.market),
+            WsMessage::L3(event) => Some(&event.market),
+            WsMessage::Funding(funding) => Some(&funding.market),
+            WsMessage::IndexPrice(index) => Some(&index.market),
+            WsMessage::MarkPrice(mark) => Some(&mark.market),
+            WsMessage::Stats(stats) => Some(&stats.market),
+            _ => None,
+        }
+    }
+
+    /// 消息的服务端时间戳（毫秒）；K线等不带推送时间的消息返回 None
+    pub fn server_ts(&self) -> Option<u64> {
+        match self {
+            WsMessage::Book(update) => Some(update.ts),
+            WsMessage::Trade(trade) => Some(trade.ts),
+            WsMessage::Ticker(ticker) => Some(ticker.ts),
+            WsMessage::Order(order) => Some(order.ts),
+            WsMessage::Fill(fill) => Some(fill.ts),
+            WsMessage::L3(event) => Some(event.ts),
+            WsMessage::Funding(funding) => Some(funding.ts),
+            WsMessage::IndexPrice(index) => Some(index.ts),
+            WsMessage::MarkPrice(mark) => Some(mark.ts),
+            WsMessage::Stats(stats) => Some(stats.ts),
+            WsMessage::Pong(pong) => Some(pong.ts),
+            _ => None,
+        }
+    }
+
+    /// 消息所属的订阅频道；错误、心跳等连接级消息返回 None
+    pub fn channel(&self) -> Option<Channel> {
+        match self {
+            WsMessage::Book(update) => Some(Channel::Orderbook {
+                market: update.market.clone(),
+            }),
+            WsMessage::Trade(trade) => Some(Channel::Trades {
+                market: trade.market.clone(),
+            }),
+            WsMessage::Ticker(ticker) => Some(Channel::Ticker {
+                market: ticker.market.clone(),
+            }),
+            WsMessage::Candle(candle) => Some(Channel::Candles {
+                market: candle.market.clone(),
+                interval: candle.interval,
+            }),
+            WsMessage::Order(order) => Some(Channel::Account {
+                address: order.account.clone(),
+            }),
+            WsMessage::Fill(fill) => Some(Channel::Account {
+                address: fill.account.clone(),
+            }),
+            WsMessage::L3(event) => Some(Channel::L3 {
+                market: event.market.clone(),
+            }),
+            WsMessage::Funding(funding) => Some(Channel::Funding {
+                market: funding.market.clone(),
+            }),
+            WsMessage::IndexPrice(index) => Some(Channel::Prices {
+                market: index.market.clone(),
+            }),
+            WsMessage::MarkPrice(mark) => Some(Channel::Prices {
+                market: mark.market.clone(),
+            }),
+            WsMessage::Stats(stats) => Some(Channel::Stats {
+                market: stats.market.clone(),
+            }),
+            WsMessage::Error(_)
+            | WsMessage::Pong(_)
+            | WsMessage::Challenge(_)
+            | WsMessage::Authenticated(_)
+            | WsMessage::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_known_messages() {
+        let msg = WsMessage::decode(r#"{"type":"error","code":4001,"message":"unknown market"}"#)
+            .unwrap();
+        assert_eq!(
+            msg,
+            WsMessage::Error(ErrorMessage {
+                code: Some(4001),
+                message: "unknown market".to_string(),
+            })
+        );
+        assert_eq!(
+            WsMessage::decode(r#"{"type":"pong","ts":5}"#).unwrap(),
+            WsMessage::Pong(Pong { ts: 5 })
+        );
+    }
+
+    #[test]
+    fn unknown_messages_fall_back() {
+        let text = r#"{"type":"maintenance","eta":60}"#;
+        let msg = WsMessage::decode(text).unwrap();
+        assert!(matches!(msg, WsMessage::Unknown(_)));
+        assert_eq!(msg.channel(), None);
+        // 原样保留，可重新编码
+        assert_eq!(
+            serde_json::to_value(&msg).unwrap(),
+            serde_json::from_str::<serde_json::Value>(text).unwrap()
+        );
+    }
+
+    #[test]
+    fn malformed_known_messages_fail_to_decode() {
+        let err =
+            WsMessage::decode(r#"{"type":"trade","market":"BTC-USDC","price":"x"}"#).unwrap_err();
+        assert!(err.to_string().contains("invalid trade message"), "{}", err);
+        assert!(WsMessage::decode(r#"{"type":"book"}"#).is_err());
+        // 未知类型与缺少 type 的消息仍然保留
+        assert!(matches!(
+            WsMessage::decode(r#"{"eta":60}"#).unwrap(),
+            WsMessage::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn price_messages_share_prices_channel() {
+        let mark = WsMessage::decode(
+            r#"{"type":"mark_price","market":"BTC-PERP","price":101,"index_price":100,"ts":1}"#,
+        )
+        .unwrap();
+        let index =
+            WsMessage::decode(r#"{"type":"index_price","market":"BTC-PERP","price":100,"ts":1}"#)
+                .unwrap();
+        let channel = Some(Channel::Prices {
+            market: "BTC-PERP".to_string(),
+        });
+        assert_eq!(mark.channel(), channel);
+        assert_eq!(index.channel(), channel);
+        assert!(matches!(
+            mark,
+            WsMessage::MarkPrice(MarkPrice { price: 101, .. })
+        ));
+    }
+
+    #[test]
+    fn decodes_expired_order_updates() {
+        let msg = WsMessage::decode(
+            r#"{"type":"order","account":"0x01","market":"BTC-USDC","order_id":"00","side":"Buy","status":"expired","price":100,"amount":10,"filled":4,"ts":1}"#,
+        )
+        .unwrap();
+        let WsMessage::Order(update) = msg else {
+            panic!("unexpected message: {:?}", msg);
+        };
+        assert_eq!(update.status, OrderStatus::Expired);
+        assert_eq!(update.filled, 4);
+    }
+
+    #[test]
+    fn decodes_market_stats() {
+        let msg = WsMessage::decode(
+            r#"{"type":"stats","market":"BTC-PERP","last_price":105,"open_24h":100,"high_24h":110,"low_24h":95,"volume_24h":7,"open_interest":42,"ts":1}"#,
+        )
+        .unwrap();
+        let WsMessage::Stats(stats) = msg else {
+            panic!("unexpected message: {:?}", msg);
+        };
+        assert_eq!(stats.open_interest, Some(42));
+        assert_eq!(stats.quote_volume_24h, 0);
+        assert_eq!(stats.price_change(), 5);
+        assert_eq!(stats.price_change_pct(), Some(0.05));
+        assert_eq!(
+            MarketStats {
+                open_24h: 0,
+                ..stats
+            }
+            .price_change_pct(),
+            None
+        );
+    }
+}
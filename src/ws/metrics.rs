@@ -0,0 +1,123 @@
+//! 订阅指标
+//!
+//! 通过 [`WsConfig::metrics`](super::WsConfig::metrics) 挂载 [`MetricsSink`]，
+//! 由运维方接入 Prometheus 等监控系统；[`InMemoryMetrics`] 提供一个可直接读取的简单实现。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use super::message::Channel;
+
+/// 指标接收端
+///
+/// 回调在连接任务中同步调用，实现应尽量轻量。所有方法默认不做任何事。
+pub trait MetricsSink: Send + Sync + fmt::Debug {
+    /// 一条消息进入订阅队列，`queue_depth` 为写入后的队列长度
+    fn on_delivered(&self, _channel: &Channel, _subscription: u64, _queue_depth: usize) {}
+
+    /// 订阅队列溢出丢弃了 `count` 条消息
+    fn on_dropped(&self, _channel: &Channel, _subscription: u64, _count: usize) {}
+
+    /// 收到无法解码的数据帧
+    fn on_decode_error(&self, _error: &str) {}
+}
+
+/// 单个频道的指标快照
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelMetrics {
+    /// 累计交付的消息数（每个订阅者分别计数）
+    pub delivered: u64,
+    /// 累计丢弃的消息数
+    pub dropped: u64,
+    /// 各订阅者中最大的当前队列长度
+    pub queue_depth: usize,
+    /// 自上次快照以来的交付速率
+    pub messages_per_sec: f64,
+}
+
+#[derive(Debug, Default)]
+struct ChannelCounters {
+    delivered: u64,
+    dropped: u64,
+    depths: HashMap<u64, usize>,
+    delivered_at_snapshot: u64,
+}
+
+#[derive(Debug)]
+struct State {
+    channels: HashMap<Channel, ChannelCounters>,
+    decode_errors: u64,
+    last_snapshot: Instant,
+}
+
+/// 内存中的指标汇总
+#[derive(Debug)]
+pub struct InMemoryMetrics {
+    state: Mutex<State>,
+}
+
+impl Default for InMemoryMetrics {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(State {
+                channels: HashMap::new(),
+                decode_errors: 0,
+                last_snapshot: Instant::now(),
+            }),
+        }
+    }
+}
+
+impl InMemoryMetrics {
+    /// 各频道的指标；交付速率按距上次调用的时间计算
+    pub fn snapshot(&self) -> HashMap<Channel, ChannelMetrics> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_snapshot).as_secs_f64();
+        state.last_snapshot = now;
+        state
+            .channels
+            .iter_mut()
+            .map(|(channel, counters)| {
+                let recent = counters.delivered - counters.delivered_at_snapshot;
+                counters.delivered_at_snapshot = counters.delivered;
+                let metrics = ChannelMetrics {
+                    delivered: counters.delivered,
+                    dropped: counters.dropped,
+                    queue_depth: counters.depths.values().copied().max().unwrap_or(0),
+                    messages_per_sec: if elapsed > 0.0 {
+                        recent as f64 / elapsed
+                    } else {
+                        0.0
+                    },
+                };
+                (channel.clone(), metrics)
+            })
+            .collect()
+    }
+
+    /// 累计解码失败次数
+    pub fn decode_errors(&self) -> u64 {
+        self.state.lock().unwrap().decode_errors
+    }
+}
+
+impl MetricsSink for InMemoryMetrics {
+    fn on_delivered(&self, channel: &Channel, subscription: u64, queue_depth: usize) {
+        let mut state = self.state.lock().unwrap();
+        let counters = state.channels.entry(channel.clone()).or_default();
+        counters.delivered += 1;
+        counters.depths.insert(subscription, queue_depth);
+    }
+
+    fn on_dropped(&self, channel: &Channel, _subscription: u64, count: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.channels.entry(channel.clone()).or_default().dropped += count as u64;
+    }
+
+    fn on_decode_error(&self, _error: &str) {
+        self.state.lock().unwrap().decode_errors += 1;
+    }
+}
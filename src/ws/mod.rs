@@ -0,0 +1,107 @@
+//! LightPool WebSocket 行情客户端
+//!
+//! 网关协议为 JSON 文本帧：
+//!
+//! - 客户端订阅: `{"op":"subscribe","channel":"orderbook","market":"BTC-USDC"}`
+//! - 客户端退订: `{"op":"unsubscribe","channel":"orderbook","market":"BTC-USDC"}`
+//! - 订单簿推送: `{"type":"book","market":"BTC-USDC","seq":1,"snapshot":false,"bids":[...],"asks":[...],"ts":0}`
+//! - 成交推送: `{"type":"trade","market":"BTC-USDC","price":1,"size":1,"side":"Buy","trade_id":1,"ts":0}`
+//! - 行情摘要: `{"type":"ticker","market":"BTC-USDC","best_bid":1,"best_ask":2,"last_price":1,"volume_24h":0,"ts":0}`
+//! - K线推送: `{"type":"candle","market":"BTC-USDC","interval":"1m","open_time":0,...,"closed":false}`
+//! - 逐笔订单簿: `{"type":"l3","market":"BTC-USDC","seq":1,"action":"add","order_id":"...","side":"Buy","price":1,"size":1,"ts":0}`
+//! - 资金费率: `{"type":"funding","market":"BTC-PERP","rate":0.0001,"mark_price":1,"funding_time":0,"ts":0}`
+//! - 指数/标记价格: `{"type":"index_price","market":"BTC-PERP","price":1,"ts":0}` / `{"type":"mark_price",...}`
+//! - 24 小时统计: `{"type":"stats","market":"BTC-PERP","last_price":1,"open_24h":1,"high_24h":1,"low_24h":1,"volume_24h":0,"open_interest":0,"ts":0}`
+//! - 错误与心跳: `{"type":"error","code":4001,"message":"..."}` / `{"type":"pong","ts":0}`
+//! - 账户推送: `{"type":"order",...}` / `{"type":"fill",...}`，按 `account` 路由
+//! - 连接认证: `{"op":"challenge"}` → `{"type":"challenge","nonce":"..."}` → `{"op":"auth",...}` → `{"type":"authenticated",...}`
+//!
+//! 一个 [`WsClient`] 对应一条连接，连接由后台任务维护，
+//! 每个订阅以 [`Subscription`] 流的形式交付给调用方，也可通过 `on_*` 方法注册回调。
+//! 订单簿增量带连续的 `seq`，[`SyncedOrderbook`] 在出现缺口时自动拉取快照重同步。
+//! 断线后按指数退避重连，重新订阅后发出 [`ConnectionEvent::Reconnected`]；
+//! 重连后可能重发的成交可通过 [`Subscription::dedup`] 过滤。
+//! WebSocket 不可用时可改用 [`SseClient`] 通过 Server-Sent Events 接收相同的推送，
+//! 或用 [`RestPoller`] 轮询 RPC；三者都实现 [`MarketStream`]，可通过 [`Transport`] 配置切换。
+//! 启用 `redis` feature 后，`RedisBridge` 把一条上游连接的推送转发到 Redis pub/sub，
+//! 本机其他进程用 `RedisClient` 订阅，共用同一条上游连接。
+//!
+//! wasm32 下只提供推送消息类型与不依赖连接的工具，连接相关的类型仅在原生平台可用。
+
+// 连接相关模块在 wasm32 下不编译，其内部工具在该平台上未被使用
+#![cfg_attr(target_arch = "wasm32", allow(dead_code))]
+
+#[cfg(not(target_arch = "wasm32"))]
+mod auth;
+mod callback;
+#[cfg(not(target_arch = "wasm32"))]
+mod client;
+mod compression;
+mod config;
+mod conflate;
+#[cfg(not(target_arch = "wasm32"))]
+mod connection;
+mod dedup;
+mod fanout;
+mod filter;
+#[cfg(not(target_arch = "wasm32"))]
+mod journal;
+mod latency;
+#[cfg(not(target_arch = "wasm32"))]
+mod manager;
+mod message;
+mod metrics;
+#[cfg(not(target_arch = "wasm32"))]
+mod poller;
+#[cfg(all(feature = "redis", not(target_arch = "wasm32")))]
+mod pubsub;
+mod queue;
+#[cfg(all(feature = "quic", not(target_arch = "wasm32")))]
+mod quic;
+#[cfg(not(target_arch = "wasm32"))]
+mod record;
+#[cfg(not(target_arch = "wasm32"))]
+mod sse;
+#[cfg(not(target_arch = "wasm32"))]
+mod stream;
+#[cfg(not(target_arch = "wasm32"))]
+mod subscription;
+#[cfg(not(target_arch = "wasm32"))]
+mod sync;
+
+pub use callback::CallbackHandle;
+#[cfg(not(target_arch = "wasm32"))]
+pub use client::WsClient;
+pub use config::WsConfig;
+pub use conflate::ConflatedOrderbook;
+#[cfg(not(target_arch = "wasm32"))]
+pub use connection::{ConnectionDiagnostics, ConnectionEvent};
+pub use dedup::{Dedup, Deduped, Deduplicator, EventKey};
+pub use fanout::BroadcastReceiver;
+pub use filter::SubscriptionFilter;
+#[cfg(not(target_arch = "wasm32"))]
+pub use journal::{Journal, JournalConfig, JournalEntry, JournalReader};
+pub use latency::LatencyStats;
+pub use message::{
+    AccountEvent, Authenticated, Candle, CandleInterval, Challenge, Channel, ErrorMessage, Fill,
+    FundingRate, IndexPrice, L3Action, L3Event, MarkPrice, MarketStats, OrderStatus, OrderUpdate,
+    OrderbookUpdate, Pong, PriceLevel, Ticker, Trade, WsMessage,
+};
+pub use metrics::{ChannelMetrics, InMemoryMetrics, MetricsSink};
+#[cfg(not(target_arch = "wasm32"))]
+pub use poller::RestPoller;
+#[cfg(all(feature = "redis", not(target_arch = "wasm32")))]
+pub use pubsub::{redis_channel, RedisBridge, RedisClient, DEFAULT_PREFIX};
+pub use queue::OverflowPolicy;
+#[cfg(all(feature = "quic", not(target_arch = "wasm32")))]
+pub use quic::{QuicClient, QUIC_ALPN};
+#[cfg(not(target_arch = "wasm32"))]
+pub use record::{Record, Recorder, Replay, ReplaySpeed};
+#[cfg(not(target_arch = "wasm32"))]
+pub use sse::SseClient;
+#[cfg(not(target_arch = "wasm32"))]
+pub use stream::{MarketStream, Transport};
+#[cfg(not(target_arch = "wasm32"))]
+pub use subscription::Subscription;
+#[cfg(not(target_arch = "wasm32"))]
+pub use sync::{SnapshotSource, SyncedOrderbook};
@@ -0,0 +1,217 @@
+//! REST 轮询传输
+//!
+//! 按固定间隔通过 RPC 拉取数据并转换为推送消息，适用于既不能使用 WebSocket 也不能使用 SSE 的环境：
+//!
+//! - 订单簿：每次轮询推送一次全量快照
+//! - 成交：推送时间戳晚于上次轮询的成交，首次轮询只记录基线
+//! - 行情摘要：由订单簿最优价与最新成交价组成，`volume_24h` 恒为 0
+//! - 24 小时统计：每次轮询推送一次 `getMarketStats` 的结果
+//!
+//! K线不提供轮询接口。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::MissedTickBehavior;
+
+use super::client::WsClient;
+use super::config::WsConfig;
+use super::connection::ConnectionEvent;
+use super::manager::{Command, SubscriptionManager};
+use super::message::{
+    Candle, CandleInterval, Channel, MarketStats, OrderbookUpdate, Ticker, Trade, WsMessage,
+};
+use super::record::now_millis;
+use super::subscription::Subscription;
+use crate::client::LightPoolClient;
+use crate::error::{Error, Result};
+
+/// 订单簿快照的请求深度
+const BOOK_DEPTH: u32 = 1000;
+
+/// 每次拉取的成交条数
+const TRADES_LIMIT: u32 = 100;
+
+/// 通过 RPC 轮询实现的行情客户端，提供与 [`WsClient`] 相同的公共频道订阅接口
+#[derive(Clone)]
+pub struct RestPoller {
+    client: WsClient,
+}
+
+impl RestPoller {
+    /// 以 `interval` 为间隔轮询；需在 tokio 运行时中调用
+    pub fn new(rpc: LightPoolClient, interval: Duration) -> Self {
+        Self::with_config(rpc, interval, WsConfig::default())
+    }
+
+    /// 使用指定的订阅队列配置创建轮询客户端
+    pub fn with_config(rpc: LightPoolClient, interval: Duration, config: WsConfig) -> Self {
+        let (client, manager, commands) = WsClient::detached(&config);
+        let poller = Poller {
+            rpc,
+            interval,
+            events: client.event_sender(),
+            manager,
+            channels: HashSet::new(),
+            last_trade_ts: HashMap::new(),
+            healthy: true,
+        };
+        tokio::spawn(poller.run(commands));
+        Self { client }
+    }
+
+    /// 订阅轮询状态事件：拉取失败时发出 `Disconnected`，恢复后发出 `Reconnected`
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.client.connection_events()
+    }
+
+    /// 订阅指定市场的订单簿，每次轮询产出一个全量快照
+    pub fn subscribe_orderbook(&self, market: &str) -> Result<Subscription<OrderbookUpdate>> {
+        self.client.subscribe_orderbook(market)
+    }
+
+    /// 订阅指定市场的公共成交
+    pub fn subscribe_trades(&self, market: &str) -> Result<Subscription<Trade>> {
+        self.client.subscribe_trades(market)
+    }
+
+    /// 订阅指定市场的最优买卖价
+    pub fn subscribe_ticker(&self, market: &str) -> Result<Subscription<Ticker>> {
+        self.client.subscribe_ticker(market)
+    }
+
+    /// 订阅指定市场的 24 小时统计与未平仓量
+    pub fn subscribe_stats(&self, market: &str) -> Result<Subscription<MarketStats>> {
+        self.client.subscribe_stats(market)
+    }
+
+    /// K线不支持轮询，总是返回错误
+    pub fn subscribe_candles(
+        &self,
+        _market: &str,
+        _interval: CandleInterval,
+    ) -> Result<Subscription<Candle>> {
+        Err(Error::Validation(
+            "candles are not available via REST polling".to_string(),
+        ))
+    }
+}
+
+struct Poller {
+    rpc: LightPoolClient,
+    interval: Duration,
+    events: broadcast::Sender<ConnectionEvent>,
+    manager: Arc<Mutex<SubscriptionManager>>,
+    channels: HashSet<Channel>,
+    /// 每个市场已推送的最新成交时间戳；None 表示尚未建立基线
+    last_trade_ts: HashMap<String, Option<u64>>,
+    healthy: bool,
+}
+
+impl Poller {
+    async fn run(mut self, mut commands: mpsc::UnboundedReceiver<Command>) {
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => self.poll().await,
+                command = commands.recv() => match command {
+                    Some(command) => self.handle_command(command),
+                    None => return,
+                },
+            }
+        }
+    }
+
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Subscribe(channel) => {
+                self.channels.insert(channel);
+            }
+            Command::Unsubscribe(channel) => {
+                if let Channel::Trades { market } = &channel {
+                    self.last_trade_ts.remove(market);
+                }
+                self.channels.remove(&channel);
+            }
+            Command::Authenticate { reply, .. } => {
+                let _ = reply.send(Err(Error::Auth(
+                    "authentication is not supported by the REST poller".to_string(),
+                )));
+            }
+        }
+    }
+
+    async fn poll(&mut self) {
+        let channels: Vec<Channel> = self.channels.iter().cloned().collect();
+        let mut healthy = true;
+        for channel in channels {
+            match self.poll_channel(&channel).await {
+                Ok(messages) => {
+                    let mut manager = self.manager.lock().unwrap();
+                    for msg in messages {
+                        manager.record(&msg);
+                        manager.dispatch(msg);
+                    }
+                }
+                Err(_) => healthy = false,
+            }
+        }
+        if healthy != self.healthy {
+            let event = if healthy {
+                ConnectionEvent::Reconnected
+            } else {
+                ConnectionEvent::Disconnected
+            };
+            let _ = self.events.send(event);
+            self.healthy = healthy;
+        }
+    }
+
+    async fn poll_channel(&mut self, channel: &Channel) -> Result<Vec<WsMessage>> {
+        match channel {
+            Channel::Orderbook { market } => {
+                let book = self.rpc.get_order_book(market, BOOK_DEPTH).await?;
+                Ok(vec![WsMessage::Book(book)])
+            }
+            Channel::Trades { market } => {
+                let trades = self.rpc.get_trades(market, TRADES_LIMIT).await?;
+                let newest = trades.last().map(|trade| trade.ts);
+                let last = self.last_trade_ts.entry(market.clone()).or_default();
+                let fresh = match *last {
+                    Some(last) => trades
+                        .into_iter()
+                        .filter(|trade| trade.ts > last)
+                        .map(WsMessage::Trade)
+                        .collect(),
+                    None => Vec::new(),
+                };
+                *last = newest.max(*last).or(Some(0));
+                Ok(fresh)
+            }
+            Channel::Ticker { market } => {
+                let book = self.rpc.get_order_book(market, 1).await?;
+                let last_trade = self.rpc.get_trades(market, 1).await?.pop();
+                Ok(vec![WsMessage::Ticker(Ticker {
+                    market: market.clone(),
+                    best_bid: book.bids.first().map(|level| level.price),
+                    best_ask: book.asks.first().map(|level| level.price),
+                    last_price: last_trade.map_or(0, |trade| trade.price),
+                    volume_24h: 0,
+                    ts: now_millis(),
+                })])
+            }
+            Channel::Stats { market } => {
+                let stats = self.rpc.get_market_stats(market).await?;
+                Ok(vec![WsMessage::Stats(stats)])
+            }
+            Channel::Candles { .. }
+            | Channel::Account { .. }
+            | Channel::L3 { .. }
+            | Channel::Funding { .. }
+            | Channel::Prices { .. } => Ok(Vec::new()),
+        }
+    }
+}
@@ -0,0 +1,480 @@
+//! 经 Redis pub/sub 在本机多个进程间共享行情
+//!
+//! [`RedisBridge`] 在持有上游 WebSocket 连接的进程中运行，把订阅到的推送消息原样（与网关相同的 JSON）
+//! 发布到 Redis 频道；其他进程用 [`RedisClient`] 订阅这些频道，得到与 [`WsClient`] 相同的类型化流，
+//! 从而共用一条上游连接。频道名由 [`redis_channel`] 生成，例如 `lightpool:orderbook:BTC-USDC`。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures_util::StreamExt;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+use super::client::WsClient;
+use super::config::WsConfig;
+use super::connection::ConnectionEvent;
+use super::manager::{Command, SubscriptionManager};
+use super::message::{
+    AccountEvent, Candle, CandleInterval, Channel, OrderbookUpdate, Ticker, Trade, WsMessage,
+};
+use super::subscription::Subscription;
+use crate::error::{Error, Result};
+
+/// 默认的 Redis 频道名前缀
+pub const DEFAULT_PREFIX: &str = "lightpool";
+
+/// 行情频道对应的 Redis 频道名：`{prefix}:{channel}:{market}`，K线附加周期，账户频道使用地址
+pub fn redis_channel(prefix: &str, channel: &Channel) -> String {
+    match channel {
+        Channel::Orderbook { market } => format!("{}:orderbook:{}", prefix, market),
+        Channel::Trades { market } => format!("{}:trades:{}", prefix, market),
+        Channel::Ticker { market } => format!("{}:ticker:{}", prefix, market),
+        Channel::Candles { market, interval } => {
+            format!("{}:candles:{}:{}", prefix, market, interval_name(*interval))
+        }
+        Channel::Account { address } => format!("{}:account:{}", prefix, address),
+        Channel::L3 { market } => format!("{}:l3:{}", prefix, market),
+        Channel::Funding { market } => format!("{}:funding:{}", prefix, market),
+        Channel::Prices { market } => format!("{}:prices:{}", prefix, market),
+        Channel::Stats { market } => format!("{}:stats:{}", prefix, market),
+    }
+}
+
+fn interval_name(interval: CandleInterval) -> String {
+    match serde_json::to_value(interval) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => format!("{}ms", interval.as_millis()),
+    }
+}
+
+fn redis_error(e: redis::RedisError) -> Error {
+    Error::Network(format!("redis: {}", e))
+}
+
+/// 把 [`WsClient`] 上的订阅转发到 Redis 的后台任务
+///
+/// 句柄释放或调用 [`RedisBridge::stop`] 时任务结束并退订上游频道。
+pub struct RedisBridge {
+    task: JoinHandle<()>,
+    published: Arc<AtomicU64>,
+}
+
+impl RedisBridge {
+    /// 在 `client` 上订阅 `channels`，并把收到的消息发布到 `url` 指向的 Redis
+    ///
+    /// 私有账户频道需先在 `client` 上完成认证。发布失败只记录日志，不中断转发。
+    pub async fn start(
+        client: &WsClient,
+        url: &str,
+        prefix: &str,
+        channels: impl IntoIterator<Item = Channel>,
+    ) -> Result<Self> {
+        let redis = redis::Client::open(url).map_err(redis_error)?;
+        let mut conn = redis.get_connection_manager().await.map_err(redis_error)?;
+        let mut subscriptions = Vec::new();
+        for channel in channels {
+            let name = redis_channel(prefix, &channel);
+            let subscription = client.subscribe_channel(channel)?;
+            subscriptions.push(subscription.map(move |msg| (name.clone(), msg)));
+        }
+
+        let published = Arc::new(AtomicU64::new(0));
+        let counter = published.clone();
+        let task = tokio::spawn(async move {
+            let mut messages = futures_util::stream::select_all(subscriptions);
+            while let Some((name, msg)) = messages.next().await {
+                let payload = match serde_json::to_string(&msg) {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        tracing::warn!(channel = %name, error = %e, "failed to encode message");
+                        continue;
+                    }
+                };
+                let result: redis::RedisResult<i64> = redis::cmd("PUBLISH")
+                    .arg(&name)
+                    .arg(payload)
+                    .query_async(&mut conn)
+                    .await;
+                match result {
+                    Ok(_) => {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(e) => {
+                        tracing::warn!(channel = %name, error = %e, "failed to publish to redis")
+                    }
+                }
+            }
+        });
+        Ok(Self { task, published })
+    }
+
+    /// 已成功发布的消息数
+    pub fn published(&self) -> u64 {
+        self.published.load(Ordering::Relaxed)
+    }
+
+    /// 停止转发
+    pub fn stop(self) {}
+}
+
+impl Drop for RedisBridge {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// 从 Redis 频道接收 [`RedisBridge`] 转发的行情，提供与 [`WsClient`] 相同的订阅接口
+///
+/// 认证由转发端的上游连接完成，本客户端不支持 [`WsClient::authenticate`]，
+/// 但可以订阅转发端已发布的账户频道。
+#[derive(Clone)]
+pub struct RedisClient {
+    client: WsClient,
+}
+
+impl RedisClient {
+    /// 使用默认配置连接 `url`（例如 `redis://127.0.0.1/`）并订阅 `prefix` 下的频道
+    pub fn connect(url: &str, prefix: &str) -> Result<Self> {
+        Self::connect_with_config(url, prefix, WsConfig::default())
+    }
+
+    /// 使用指定配置连接；需在 tokio 运行时中调用，断开后按配置的退避策略重连
+    pub fn connect_with_config(url: &str, prefix: &str, config: WsConfig) -> Result<Self> {
+        let redis = redis::Client::open(url).map_err(redis_error)?;
+        let (client, manager, commands) = WsClient::detached(&config);
+        let transport = RedisTransport {
+            redis,
+            prefix: prefix.to_string(),
+            config,
+            events: client.event_sender(),
+            manager,
+        };
+        tokio::spawn(transport.run(commands));
+        Ok(Self { client })
+    }
+
+    /// 订阅连接状态事件
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.client.connection_events()
+    }
+
+    /// 订阅指定市场的订单簿更新
+    pub fn subscribe_orderbook(&self, market: &str) -> Result<Subscription<OrderbookUpdate>> {
+        self.client.subscribe_orderbook(market)
+    }
+
+    /// 订阅指定市场的公共成交
+    pub fn subscribe_trades(&self, market: &str) -> Result<Subscription<Trade>> {
+        self.client.subscribe_trades(market)
+    }
+
+    /// 订阅指定市场的最优买卖价与行情摘要
+    pub fn subscribe_ticker(&self, market: &str) -> Result<Subscription<Ticker>> {
+        self.client.subscribe_ticker(market)
+    }
+
+    /// 订阅指定市场与周期的K线
+    pub fn subscribe_candles(
+        &self,
+        market: &str,
+        interval: CandleInterval,
+    ) -> Result<Subscription<Candle>> {
+        self.client.subscribe_candles(market, interval)
+    }
+
+    /// 订阅转发端发布的账户订单状态与成交回报
+    pub fn subscribe_account(&self, address: &str) -> Result<Subscription<AccountEvent>> {
+        self.client.subscribe_account(address)
+    }
+}
+
+/// 后台任务：按订阅命令维护 Redis 订阅，把收到的消息分发给订阅者
+struct RedisTransport {
+    redis: redis::Client,
+    prefix: String,
+    config: WsConfig,
+    events: broadcast::Sender<ConnectionEvent>,
+    manager: Arc<Mutex<SubscriptionManager>>,
+}
+
+impl RedisTransport {
+    async fn run(self, mut commands: mpsc::UnboundedReceiver<Command>) {
+        let mut delay = self.config.reconnect_initial_delay;
+        let mut reconnecting = false;
+        loop {
+            match self.redis.get_async_pubsub().await {
+                Ok(pubsub) => {
+                    if !self.serve(pubsub, &mut commands, reconnecting).await {
+                        return;
+                    }
+                    delay = self.config.reconnect_initial_delay;
+                }
+                Err(e) => tracing::warn!(error = %e, "failed to connect to redis"),
+            }
+            if !reconnecting {
+                let _ = self.events.send(ConnectionEvent::Disconnected);
+            }
+            reconnecting = true;
+            // 等待重连期间的订阅变更在重连后按订阅管理器的频道列表恢复
+            let sleep = tokio::time::sleep(delay);
+            tokio::pin!(sleep);
+            loop {
+                tokio::select! {
+                    _ = &mut sleep => break,
+                    command = commands.recv() => match command {
+                        None => return,
+                        Some(Command::Authenticate { reply, .. }) => reject_auth(reply),
+                        Some(_) => {}
+                    },
+                }
+            }
+            delay = (delay * 2).min(self.config.reconnect_max_delay);
+        }
+    }
+
+    /// 在一条连接上处理命令与消息；连接断开返回 true，命令通道关闭返回 false
+    async fn serve(
+        &self,
+        pubsub: redis::aio::PubSub,
+        commands: &mut mpsc::UnboundedReceiver<Command>,
+        reconnecting: bool,
+    ) -> bool {
+        let (mut sink, mut stream) = pubsub.split();
+        let channels = self.manager.lock().unwrap().channels();
+        for channel in &channels {
+            if sink
+                .subscribe(redis_channel(&self.prefix, channel))
+                .await
+                .is_err()
+            {
+                return true;
+            }
+        }
+        if reconnecting {
+            let _ = self.events.send(ConnectionEvent::Reconnected);
+        }
+        loop {
+            tokio::select! {
+                command = commands.recv() => {
+                    let result = match command {
+                        None => return false,
+                        Some(Command::Subscribe(channel)) => {
+                            sink.subscribe(redis_channel(&self.prefix, &channel)).await
+                        }
+                        Some(Command::Unsubscribe(channel)) => {
+                            sink.unsubscribe(redis_channel(&self.prefix, &channel)).await
+                        }
+                        Some(Command::Authenticate { reply, .. }) => {
+                            reject_auth(reply);
+                            Ok(())
+                        }
+                    };
+                    if result.is_err() {
+                        return true;
+                    }
+                }
+                msg = stream.next() => {
+                    let Some(msg) = msg else { return true };
+                    let Ok(payload) = msg.get_payload::<String>() else { continue };
+                    match WsMessage::decode(&payload) {
+                        Ok(WsMessage::Error(error)) => {
+                            let _ = self.events.send(ConnectionEvent::ServerError(error));
+                        }
+                        Ok(msg) => {
+                            let mut manager = self.manager.lock().unwrap();
+                            manager.record(&msg);
+                            manager.dispatch(msg);
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn reject_auth(reply: oneshot::Sender<Result<()>>) {
+    let _ = reply.send(Err(Error::Auth(
+        "authentication is not supported over redis".to_string(),
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderSide;
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    type Subscribers = Arc<Mutex<HashMap<String, Vec<mpsc::UnboundedSender<Vec<u8>>>>>>;
+
+    fn bulk(s: &str) -> String {
+        format!("${}\r\n{}\r\n", s.len(), s)
+    }
+
+    /// 只实现 SUBSCRIBE/UNSUBSCRIBE/PUBLISH 的 RESP2 服务端，其余命令回复 OK
+    async fn fake_redis() -> (String, Subscribers) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("redis://{}/", listener.local_addr().unwrap());
+        let subscribers = Subscribers::default();
+        let shared = subscribers.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let subscribers = shared.clone();
+                tokio::spawn(async move {
+                    let (read, mut write) = socket.into_split();
+                    let (tx, mut rx) = mpsc::unbounded_channel::<Vec<u8>>();
+                    tokio::spawn(async move {
+                        while let Some(bytes) = rx.recv().await {
+                            if write.write_all(&bytes).await.is_err() {
+                                break;
+                            }
+                        }
+                    });
+                    let mut read = BufReader::new(read);
+                    let mut subscribed = 0;
+                    loop {
+                        let mut line = String::new();
+                        if read.read_line(&mut line).await.unwrap_or(0) == 0 {
+                            return;
+                        }
+                        let n: usize = line.trim()[1..].parse().unwrap();
+                        let mut args = Vec::new();
+                        for _ in 0..n {
+                            line.clear();
+                            read.read_line(&mut line).await.unwrap();
+                            let len: usize = line.trim()[1..].parse().unwrap();
+                            let mut buf = vec![0; len + 2];
+                            read.read_exact(&mut buf).await.unwrap();
+                            buf.truncate(len);
+                            args.push(String::from_utf8(buf).unwrap());
+                        }
+                        let reply = match args[0].to_ascii_uppercase().as_str() {
+                            "SUBSCRIBE" => {
+                                let mut reply = String::new();
+                                for channel in &args[1..] {
+                                    subscribed += 1;
+                                    subscribers
+                                        .lock()
+                                        .unwrap()
+                                        .entry(channel.clone())
+                                        .or_default()
+                                        .push(tx.clone());
+                                    reply += &format!(
+                                        "*3\r\n{}{}:{}\r\n",
+                                        bulk("subscribe"),
+                                        bulk(channel),
+                                        subscribed
+                                    );
+                                }
+                                reply
+                            }
+                            "PUBLISH" => {
+                                let message = format!(
+                                    "*3\r\n{}{}{}",
+                                    bulk("message"),
+                                    bulk(&args[1]),
+                                    bulk(&args[2])
+                                );
+                                let receivers = subscribers
+                                    .lock()
+                                    .unwrap()
+                                    .get(&args[1])
+                                    .cloned()
+                                    .unwrap_or_default();
+                                for receiver in &receivers {
+                                    let _ = receiver.send(message.clone().into_bytes());
+                                }
+                                format!(":{}\r\n", receivers.len())
+                            }
+                            _ => "+OK\r\n".to_string(),
+                        };
+                        let _ = tx.send(reply.into_bytes());
+                    }
+                });
+            }
+        });
+        (url, subscribers)
+    }
+
+    #[test]
+    fn channel_names() {
+        let market = "BTC-USDC".to_string();
+        assert_eq!(
+            redis_channel(
+                DEFAULT_PREFIX,
+                &Channel::Orderbook {
+                    market: market.clone()
+                }
+            ),
+            "lightpool:orderbook:BTC-USDC"
+        );
+        assert_eq!(
+            redis_channel(
+                "md",
+                &Channel::Candles {
+                    market,
+                    interval: CandleInterval::Min5
+                }
+            ),
+            "md:candles:BTC-USDC:5m"
+        );
+        assert_eq!(
+            redis_channel(
+                DEFAULT_PREFIX,
+                &Channel::Account {
+                    address: "0x01".to_string()
+                }
+            ),
+            "lightpool:account:0x01"
+        );
+    }
+
+    #[tokio::test]
+    async fn bridged_messages_reach_redis_subscribers() {
+        let (url, subscribers) = fake_redis().await;
+        let (upstream, manager, _commands) = WsClient::detached(&WsConfig::default());
+
+        let consumer = RedisClient::connect(&url, "test").unwrap();
+        let mut trades = consumer.subscribe_trades("BTC-USDC").unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !subscribers
+                .lock()
+                .unwrap()
+                .contains_key("test:trades:BTC-USDC")
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let channel = Channel::Trades {
+            market: "BTC-USDC".to_string(),
+        };
+        let bridge = RedisBridge::start(&upstream, &url, "test", [channel])
+            .await
+            .unwrap();
+        let trade = Trade {
+            market: "BTC-USDC".to_string(),
+            price: 100,
+            size: 2,
+            side: OrderSide::Sell,
+            trade_id: Some(7),
+            ts: 1,
+        };
+        manager
+            .lock()
+            .unwrap()
+            .dispatch(WsMessage::Trade(trade.clone()));
+
+        let received = tokio::time::timeout(Duration::from_secs(5), trades.next())
+            .await
+            .unwrap();
+        assert_eq!(received, Some(trade));
+        bridge.stop();
+    }
+}
@@ -0,0 +1,197 @@
+//! 订阅使用的有界队列
+//!
+//! 连接任务不能被慢消费者阻塞，因此写入永不等待，队列满时按 [`OverflowPolicy`] 处理。
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// 队列满时的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// 丢弃最旧的消息
+    #[default]
+    DropOldest,
+    /// 丢弃所有积压消息，只保留最新一条
+    Conflate,
+    /// 终止该订阅，流结束后 [`Subscription::is_overflowed`](super::Subscription::is_overflowed) 返回 true
+    Error,
+}
+
+struct State<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    /// 发送端已释放
+    closed: bool,
+    /// 接收端已释放
+    receiver_closed: bool,
+    overflowed: bool,
+    waker: Option<Waker>,
+}
+
+pub(crate) fn channel<T>(capacity: usize, policy: OverflowPolicy) -> (Sender<T>, Receiver<T>) {
+    let state = Arc::new(Mutex::new(State {
+        items: VecDeque::new(),
+        capacity: capacity.max(1),
+        policy,
+        closed: false,
+        receiver_closed: false,
+        overflowed: false,
+        waker: None,
+    }));
+    (
+        Sender {
+            state: state.clone(),
+        },
+        Receiver { state },
+    )
+}
+
+pub(crate) struct Sender<T> {
+    state: Arc<Mutex<State<T>>>,
+}
+
+/// 一次写入的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Pushed {
+    /// false 表示订阅已失效（接收端释放或已溢出终止）
+    pub(crate) accepted: bool,
+    /// 因队列满被丢弃的消息数
+    pub(crate) dropped: usize,
+    /// 写入后的队列长度
+    pub(crate) depth: usize,
+}
+
+impl<T> Sender<T> {
+    /// 写入一条消息，队列满时按溢出策略丢弃
+    pub(crate) fn push(&self, item: T) -> Pushed {
+        let mut state = self.state.lock().unwrap();
+        if state.receiver_closed || state.overflowed {
+            return Pushed {
+                accepted: false,
+                dropped: 0,
+                depth: 0,
+            };
+        }
+        let mut dropped = 0;
+        if state.items.len() >= state.capacity {
+            match state.policy {
+                OverflowPolicy::DropOldest => {
+                    state.items.pop_front();
+                    dropped = 1;
+                }
+                OverflowPolicy::Conflate => {
+                    dropped = state.items.len();
+                    state.items.clear();
+                }
+                OverflowPolicy::Error => {
+                    state.overflowed = true;
+                    let dropped = state.items.len() + 1;
+                    state.items.clear();
+                    wake(&mut state);
+                    return Pushed {
+                        accepted: false,
+                        dropped,
+                        depth: 0,
+                    };
+                }
+            }
+        }
+        state.items.push_back(item);
+        wake(&mut state);
+        Pushed {
+            accepted: true,
+            dropped,
+            depth: state.items.len(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+        wake(&mut state);
+    }
+}
+
+pub(crate) struct Receiver<T> {
+    state: Arc<Mutex<State<T>>>,
+}
+
+impl<T> Receiver<T> {
+    pub(crate) fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(item) = state.items.pop_front() {
+            return Poll::Ready(Some(item));
+        }
+        if state.closed || state.overflowed {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    pub(crate) fn is_overflowed(&self) -> bool {
+        self.state.lock().unwrap().overflowed
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        state.receiver_closed = true;
+        state.items.clear();
+    }
+}
+
+fn wake<T>(state: &mut State<T>) {
+    if let Some(waker) = state.waker.take() {
+        waker.wake();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::task::noop_waker;
+
+    fn drain(rx: &mut Receiver<u32>) -> Vec<u32> {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut out = Vec::new();
+        while let Poll::Ready(Some(item)) = rx.poll_recv(&mut cx) {
+            out.push(item);
+        }
+        out
+    }
+
+    #[test]
+    fn drop_oldest_keeps_latest_items() {
+        let (tx, mut rx) = channel(2, OverflowPolicy::DropOldest);
+        for i in 0..4 {
+            assert!(tx.push(i).accepted);
+        }
+        assert_eq!(drain(&mut rx), vec![2, 3]);
+    }
+
+    #[test]
+    fn conflate_keeps_only_newest() {
+        let (tx, mut rx) = channel(2, OverflowPolicy::Conflate);
+        for i in 0..3 {
+            assert!(tx.push(i).accepted);
+        }
+        assert_eq!(drain(&mut rx), vec![2]);
+    }
+
+    #[test]
+    fn error_policy_terminates_subscription() {
+        let (tx, mut rx) = channel(1, OverflowPolicy::Error);
+        assert!(tx.push(0).accepted);
+        assert_eq!(tx.push(1).dropped, 2);
+        assert!(!tx.push(2).accepted);
+        assert!(drain(&mut rx).is_empty());
+        assert!(rx.is_overflowed());
+    }
+}
@@ -0,0 +1,263 @@
+//! 实验性 QUIC 行情传输
+//!
+//! 网关开放 QUIC 端点（ALPN `lightpool-md`）时，客户端建立一条双向流：
+//! 客户端按行写入与 WebSocket 相同的 JSON 请求，网关按行推送与 WebSocket 相同的 JSON 消息。
+//! 避免 TCP 队头阻塞，面向对尾延迟敏感的部署。接口可能随网关实现调整。
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use quinn::crypto::rustls::QuicClientConfig;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::{broadcast, mpsc};
+
+use super::client::WsClient;
+use super::config::WsConfig;
+use super::connection::ConnectionEvent;
+use super::manager::{Command, SubscriptionManager};
+use super::message::{
+    Candle, CandleInterval, ClientRequest, OrderbookUpdate, Ticker, Trade, WsMessage,
+};
+use super::subscription::Subscription;
+use crate::error::{Error, Result};
+
+/// QUIC 端点协商的 ALPN
+pub const QUIC_ALPN: &[u8] = b"lightpool-md";
+
+/// 基于 QUIC 的行情客户端，提供与 [`WsClient`] 相同的公共频道订阅接口
+#[derive(Clone)]
+pub struct QuicClient {
+    client: WsClient,
+}
+
+impl QuicClient {
+    /// 使用 webpki 根证书连接到网关的 QUIC 端点
+    pub async fn connect(addr: SocketAddr, server_name: &str) -> Result<Self> {
+        let roots =
+            rustls::RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        let tls = rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| Error::Network(e.to_string()))?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+        Self::connect_with_tls(addr, server_name, tls, WsConfig::default()).await
+    }
+
+    /// 使用自定义 TLS 配置连接，例如信任自签名证书的测试网关
+    pub async fn connect_with_tls(
+        addr: SocketAddr,
+        server_name: &str,
+        mut tls: rustls::ClientConfig,
+        config: WsConfig,
+    ) -> Result<Self> {
+        tls.alpn_protocols = vec![QUIC_ALPN.to_vec()];
+        let crypto = QuicClientConfig::try_from(tls).map_err(|e| Error::Network(e.to_string()))?;
+        let bind: SocketAddr = if addr.is_ipv6() {
+            "[::]:0".parse().unwrap()
+        } else {
+            "0.0.0.0:0".parse().unwrap()
+        };
+        let mut endpoint =
+            quinn::Endpoint::client(bind).map_err(|e| Error::Network(e.to_string()))?;
+        endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(crypto)));
+
+        let (client, manager, commands) = WsClient::detached(&config);
+        let transport = QuicTransport {
+            endpoint,
+            addr,
+            server_name: server_name.to_string(),
+            events: client.event_sender(),
+            manager,
+            config,
+        };
+        let stream = transport.open().await?;
+        tokio::spawn(transport.run(stream, commands));
+        Ok(Self { client })
+    }
+
+    /// 订阅连接状态事件
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.client.connection_events()
+    }
+
+    /// 订阅指定市场的订单簿更新
+    pub fn subscribe_orderbook(&self, market: &str) -> Result<Subscription<OrderbookUpdate>> {
+        self.client.subscribe_orderbook(market)
+    }
+
+    /// 订阅指定市场的公共成交
+    pub fn subscribe_trades(&self, market: &str) -> Result<Subscription<Trade>> {
+        self.client.subscribe_trades(market)
+    }
+
+    /// 订阅指定市场的最优买卖价与行情摘要
+    pub fn subscribe_ticker(&self, market: &str) -> Result<Subscription<Ticker>> {
+        self.client.subscribe_ticker(market)
+    }
+
+    /// 订阅指定市场与周期的K线
+    pub fn subscribe_candles(
+        &self,
+        market: &str,
+        interval: CandleInterval,
+    ) -> Result<Subscription<Candle>> {
+        self.client.subscribe_candles(market, interval)
+    }
+}
+
+type Stream = (quinn::SendStream, BufReader<quinn::RecvStream>);
+
+/// 后台任务：维护 QUIC 连接上的双向流
+struct QuicTransport {
+    endpoint: quinn::Endpoint,
+    addr: SocketAddr,
+    server_name: String,
+    config: WsConfig,
+    events: broadcast::Sender<ConnectionEvent>,
+    manager: Arc<Mutex<SubscriptionManager>>,
+}
+
+impl QuicTransport {
+    async fn open(&self) -> Result<Stream> {
+        let connection = self
+            .endpoint
+            .connect(self.addr, &self.server_name)
+            .map_err(|e| Error::Network(e.to_string()))?
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+        let (send, recv) = connection
+            .open_bi()
+            .await
+            .map_err(|e| Error::Network(e.to_string()))?;
+        Ok((send, BufReader::new(recv)))
+    }
+
+    async fn run(self, mut stream: Stream, mut commands: mpsc::UnboundedReceiver<Command>) {
+        loop {
+            if !self.session(&mut stream, &mut commands).await {
+                return;
+            }
+            let _ = self.events.send(ConnectionEvent::Disconnected);
+
+            let mut delay = self.config.reconnect_initial_delay;
+            stream = loop {
+                tokio::time::sleep(delay).await;
+                if let Ok(stream) = self.open().await {
+                    break stream;
+                }
+                delay = (delay * 2).min(self.config.reconnect_max_delay);
+            };
+            let channels = self.manager.lock().unwrap().channels();
+            let mut restored = true;
+            for channel in &channels {
+                restored &= send_line(&mut stream.0, &ClientRequest::Subscribe { channel })
+                    .await
+                    .is_ok();
+            }
+            if restored {
+                let _ = self.events.send(ConnectionEvent::Reconnected);
+            }
+        }
+    }
+
+    /// 处理命令与推送直到流断开；返回 false 表示客户端已释放
+    async fn session(
+        &self,
+        (send, recv): &mut Stream,
+        commands: &mut mpsc::UnboundedReceiver<Command>,
+    ) -> bool {
+        let mut line = String::new();
+        loop {
+            tokio::select! {
+                command = commands.recv() => {
+                    let result = match command {
+                        Some(Command::Subscribe(channel)) => {
+                            send_line(send, &ClientRequest::Subscribe { channel: &channel }).await
+                        }
+                        Some(Command::Unsubscribe(channel)) => {
+                            send_line(send, &ClientRequest::Unsubscribe { channel: &channel }).await
+                        }
+                        Some(Command::Authenticate { reply, .. }) => {
+                            let _ = reply.send(Err(Error::Auth(
+                                "authentication is not supported over QUIC yet".to_string(),
+                            )));
+                            Ok(())
+                        }
+                        None => {
+                            let _ = send.finish();
+                            return false;
+                        }
+                    };
+                    if result.is_err() {
+                        return true;
+                    }
+                }
+                read = recv.read_line(&mut line) => {
+                    match read {
+                        Ok(0) | Err(_) => return true,
+                        Ok(_) => {}
+                    }
+                    self.handle_line(line.trim_end());
+                    line.clear();
+                }
+            }
+        }
+    }
+
+    fn handle_line(&self, line: &str) {
+        match WsMessage::decode(line) {
+            Ok(WsMessage::Error(error)) => {
+                let _ = self.events.send(ConnectionEvent::ServerError(error));
+            }
+            Ok(msg) => {
+                let mut manager = self.manager.lock().unwrap();
+                manager.record(&msg);
+                manager.dispatch(msg);
+            }
+            // 字段不符的已知类型消息同样计为解码错误
+            Err(e) => {
+                if let Some(metrics) = &self.config.metrics {
+                    metrics.on_decode_error(&e.to_string());
+                }
+            }
+        }
+    }
+}
+
+async fn send_line(send: &mut quinn::SendStream, request: &ClientRequest<'_>) -> Result<()> {
+    let mut line = serde_json::to_vec(request)?;
+    line.push(b'\n');
+    send.write_all(&line)
+        .await
+        .map_err(|e| Error::Network(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::InMemoryMetrics;
+
+    #[tokio::test]
+    async fn malformed_known_messages_count_as_decode_errors() {
+        let metrics = Arc::new(InMemoryMetrics::default());
+        let config = WsConfig {
+            metrics: Some(metrics.clone()),
+            ..WsConfig::default()
+        };
+        let (client, manager, _commands) = WsClient::detached(&config);
+        let transport = QuicTransport {
+            endpoint: quinn::Endpoint::client("127.0.0.1:0".parse().unwrap()).unwrap(),
+            addr: "127.0.0.1:1".parse().unwrap(),
+            server_name: "localhost".to_string(),
+            events: client.event_sender(),
+            manager,
+            config,
+        };
+        transport.handle_line("not json");
+        transport.handle_line(r#"{"type":"trade","market":"BTC-USDC","price":"x"}"#);
+        transport.handle_line(r#"{"type":"maintenance","eta":60}"#);
+        assert_eq!(metrics.decode_errors(), 2);
+    }
+}
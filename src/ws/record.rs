@@ -0,0 +1,234 @@
+//! 行情录制与回放
+//!
+//! 录制文件为 JSON Lines，每行一条 `{"ts":<本地接收时间毫秒>,"message":{...}}`。
+//! [`Replay`] 按录制时的时间间隔（或加速后）把消息重新分发给订阅，
+//! 回放端使用与实盘相同的 [`WsClient`] 订阅接口，策略代码无需修改即可回测。
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc as std_mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use super::client::WsClient;
+use super::config::WsConfig;
+use super::manager::{Command, SubscriptionManager};
+use super::message::WsMessage;
+use crate::error::{Error, Result};
+
+#[derive(Serialize)]
+struct RecordRef<'a> {
+    ts: u64,
+    message: &'a WsMessage,
+}
+
+/// 录制文件中的一条记录
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Record {
+    /// 本地接收时间（Unix 毫秒）
+    pub ts: u64,
+    pub message: WsMessage,
+}
+
+/// 写入录制文件的通道，由订阅管理器在收到消息时调用
+pub(crate) type RecordSink = std_mpsc::Sender<String>;
+
+/// 把消息编码为一行录制记录
+pub(crate) fn encode_record(message: &WsMessage) -> Option<String> {
+    serde_json::to_string(&RecordRef {
+        ts: now_millis(),
+        message,
+    })
+    .ok()
+}
+
+/// 本地当前时间（Unix 毫秒）
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// 录制记录的写入目标
+pub(crate) trait RecordWriter: Send + 'static {
+    fn write_record(&mut self, line: &str) -> std::io::Result<()>;
+    /// 一批记录写完后调用
+    fn commit(&mut self) -> std::io::Result<()>;
+}
+
+impl RecordWriter for BufWriter<File> {
+    fn write_record(&mut self, line: &str) -> std::io::Result<()> {
+        self.write_all(line.as_bytes())?;
+        self.write_all(b"\n")
+    }
+
+    fn commit(&mut self) -> std::io::Result<()> {
+        self.flush()
+    }
+}
+
+/// 行情录制器
+///
+/// 由 [`WsClient::start_recording`] 或 [`WsClient::start_journal`] 创建，释放时停止录制并刷新文件。
+/// 文件写入在独立线程中进行，不会阻塞连接任务。
+pub struct Recorder {
+    id: u64,
+    manager: Arc<Mutex<SubscriptionManager>>,
+    writer: Option<thread::JoinHandle<std::io::Result<()>>>,
+}
+
+impl Recorder {
+    pub(crate) fn start(path: &Path, manager: Arc<Mutex<SubscriptionManager>>) -> Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        Ok(Self::spawn(file, manager))
+    }
+
+    /// 在独立线程中把记录交给 `writer`；每批积压记录写完后调用一次 commit
+    pub(crate) fn spawn<W: RecordWriter>(
+        mut writer: W,
+        manager: Arc<Mutex<SubscriptionManager>>,
+    ) -> Self {
+        let (sink, lines) = std_mpsc::channel::<String>();
+        let thread = thread::spawn(move || {
+            while let Ok(line) = lines.recv() {
+                writer.write_record(&line)?;
+                while let Ok(line) = lines.try_recv() {
+                    writer.write_record(&line)?;
+                }
+                writer.commit()?;
+            }
+            writer.commit()
+        });
+        let id = manager.lock().unwrap().add_recorder(sink);
+        Self {
+            id,
+            manager,
+            writer: Some(thread),
+        }
+    }
+
+    /// 停止录制，等待所有记录写入文件
+    pub fn finish(mut self) -> Result<()> {
+        self.stop()
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.manager.lock().unwrap().remove_recorder(self.id);
+        match self.writer.take().map(thread::JoinHandle::join) {
+            Some(Ok(result)) => Ok(result?),
+            Some(Err(_)) => Err(Error::Io(std::io::Error::other("recorder thread panicked"))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+/// 回放速度
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ReplaySpeed {
+    /// 按录制时的时间间隔
+    #[default]
+    Original,
+    /// 按倍数加速，例如 10.0 表示十倍速
+    Accelerated(f64),
+    /// 不等待，尽快回放
+    Unthrottled,
+}
+
+/// 录制文件回放器
+///
+/// 先通过 [`Replay::client`] 订阅所需频道，再调用 [`Replay::run`] 开始回放。
+/// 回放结束后所有订阅流随之结束。
+pub struct Replay {
+    path: PathBuf,
+    speed: ReplaySpeed,
+    client: WsClient,
+    manager: Arc<Mutex<SubscriptionManager>>,
+    commands: mpsc::UnboundedReceiver<Command>,
+}
+
+impl Replay {
+    /// 打开录制文件
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with_config(path, WsConfig::default())
+    }
+
+    /// 打开录制文件，订阅队列按 `config` 配置
+    pub fn open_with_config(path: impl AsRef<Path>, config: WsConfig) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        // 提前检查文件是否可读
+        File::open(&path)?;
+        let (client, manager, commands) = WsClient::detached(&config);
+        Ok(Self {
+            path,
+            speed: ReplaySpeed::default(),
+            client,
+            manager,
+            commands,
+        })
+    }
+
+    /// 设置回放速度
+    pub fn speed(mut self, speed: ReplaySpeed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// 用于订阅回放数据的客户端
+    pub fn client(&self) -> &WsClient {
+        &self.client
+    }
+
+    /// 回放整个文件；返回回放的消息数
+    pub async fn run(mut self) -> Result<u64> {
+        let reader = BufReader::new(File::open(&self.path)?);
+        let start = Instant::now();
+        let mut first_ts = None;
+        let mut count = 0;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: Record = serde_json::from_str(&line)?;
+            let first = *first_ts.get_or_insert(record.ts);
+            if let Some(delay) = self.delay(record.ts.saturating_sub(first)) {
+                tokio::time::sleep_until(start + delay).await;
+            }
+            self.handle_commands();
+            self.manager.lock().unwrap().dispatch(record.message);
+            count += 1;
+        }
+        self.handle_commands();
+        self.manager.lock().unwrap().close();
+        Ok(count)
+    }
+
+    fn delay(&self, elapsed_ms: u64) -> Option<Duration> {
+        let elapsed = Duration::from_millis(elapsed_ms);
+        match self.speed {
+            ReplaySpeed::Original => Some(elapsed),
+            ReplaySpeed::Accelerated(factor) if factor > 0.0 => Some(elapsed.div_f64(factor)),
+            ReplaySpeed::Accelerated(_) | ReplaySpeed::Unthrottled => None,
+        }
+    }
+
+    /// 回放没有网关，订阅命令直接忽略，认证总是成功
+    fn handle_commands(&mut self) {
+        while let Ok(command) = self.commands.try_recv() {
+            if let Command::Authenticate { reply, .. } = command {
+                let _ = reply.send(Ok(()));
+            }
+        }
+    }
+}
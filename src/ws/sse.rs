@@ -0,0 +1,261 @@
+//! Server-Sent Events 备用传输
+//!
+//! 在无法建立 WebSocket 的网络环境下，通过 HTTP 长连接接收推送：
+//! 每个频道一条 `GET {base}/stream?channel=...&market=...` 请求（`Accept: text/event-stream`），
+//! 每个事件的 `data` 字段为与 WebSocket 相同的 JSON 消息。
+//! 订阅管理、队列与类型化流与 [`WsClient`] 共用，断开后按相同的退避策略重连。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures_util::StreamExt;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+
+use super::client::WsClient;
+use super::config::WsConfig;
+use super::connection::ConnectionEvent;
+use super::manager::{Command, SubscriptionManager};
+use super::message::{Candle, CandleInterval, Channel, OrderbookUpdate, Ticker, Trade, WsMessage};
+use super::subscription::Subscription;
+use crate::error::{Error, Result};
+
+/// 基于 SSE 的行情客户端，提供与 [`WsClient`] 相同的公共频道订阅接口
+///
+/// SSE 为单向传输，不支持连接认证，因此没有私有频道。
+#[derive(Clone)]
+pub struct SseClient {
+    client: WsClient,
+}
+
+impl SseClient {
+    /// 使用默认配置创建客户端，`base_url` 例如 `http://localhost:26300`
+    pub fn new(base_url: &str) -> Result<Self> {
+        Self::with_config(base_url, WsConfig::default())
+    }
+
+    /// 使用指定配置创建客户端；需在 tokio 运行时中调用
+    pub fn with_config(base_url: &str, config: WsConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .build()
+            .map_err(|e| Error::Network(e.to_string()))?;
+        let (client, manager, commands) = WsClient::detached(&config);
+        let transport = SseTransport {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http,
+            config,
+            events: client.event_sender(),
+            manager,
+            streams: HashMap::new(),
+        };
+        tokio::spawn(transport.run(commands));
+        Ok(Self { client })
+    }
+
+    /// 订阅连接状态事件
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.client.connection_events()
+    }
+
+    /// 订阅指定市场的订单簿更新
+    pub fn subscribe_orderbook(&self, market: &str) -> Result<Subscription<OrderbookUpdate>> {
+        self.client.subscribe_orderbook(market)
+    }
+
+    /// 订阅指定市场的公共成交
+    pub fn subscribe_trades(&self, market: &str) -> Result<Subscription<Trade>> {
+        self.client.subscribe_trades(market)
+    }
+
+    /// 订阅指定市场的最优买卖价与行情摘要
+    pub fn subscribe_ticker(&self, market: &str) -> Result<Subscription<Ticker>> {
+        self.client.subscribe_ticker(market)
+    }
+
+    /// 订阅指定市场与周期的K线
+    pub fn subscribe_candles(
+        &self,
+        market: &str,
+        interval: CandleInterval,
+    ) -> Result<Subscription<Candle>> {
+        self.client.subscribe_candles(market, interval)
+    }
+}
+
+/// 后台任务：按订阅命令为每个频道维护一条 SSE 请求
+struct SseTransport {
+    base_url: String,
+    http: reqwest::Client,
+    config: WsConfig,
+    events: broadcast::Sender<ConnectionEvent>,
+    manager: Arc<Mutex<SubscriptionManager>>,
+    streams: HashMap<Channel, JoinHandle<()>>,
+}
+
+impl SseTransport {
+    async fn run(mut self, mut commands: mpsc::UnboundedReceiver<Command>) {
+        while let Some(command) = commands.recv().await {
+            match command {
+                Command::Subscribe(channel) => {
+                    let task = tokio::spawn(stream_channel(
+                        self.http.clone(),
+                        self.base_url.clone(),
+                        channel.clone(),
+                        self.config.clone(),
+                        self.events.clone(),
+                        self.manager.clone(),
+                    ));
+                    if let Some(old) = self.streams.insert(channel, task) {
+                        old.abort();
+                    }
+                }
+                Command::Unsubscribe(channel) => {
+                    if let Some(task) = self.streams.remove(&channel) {
+                        task.abort();
+                    }
+                }
+                Command::Authenticate { reply, .. } => {
+                    let _ = reply.send(Err(Error::Auth(
+                        "authentication is not supported over SSE".to_string(),
+                    )));
+                }
+            }
+        }
+        for task in self.streams.into_values() {
+            task.abort();
+        }
+    }
+}
+
+/// 持续读取一个频道的事件流，断开后按指数退避重连
+async fn stream_channel(
+    http: reqwest::Client,
+    base_url: String,
+    channel: Channel,
+    config: WsConfig,
+    events: broadcast::Sender<ConnectionEvent>,
+    manager: Arc<Mutex<SubscriptionManager>>,
+) {
+    let query = channel_query(&channel);
+    let mut delay = config.reconnect_initial_delay;
+    let mut reconnecting = false;
+    loop {
+        let response = http
+            .get(format!("{}/stream", base_url))
+            .query(&query)
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status);
+        if let Ok(response) = response {
+            if reconnecting {
+                let _ = events.send(ConnectionEvent::Reconnected);
+            }
+            delay = config.reconnect_initial_delay;
+            let mut body = response.bytes_stream();
+            let mut parser = SseParser::default();
+            while let Some(Ok(chunk)) = body.next().await {
+                for data in parser.feed(&chunk) {
+                    match WsMessage::decode(&data) {
+                        Ok(WsMessage::Error(error)) => {
+                            let _ = events.send(ConnectionEvent::ServerError(error));
+                        }
+                        Ok(msg) => {
+                            let mut manager = manager.lock().unwrap();
+                            manager.record(&msg);
+                            manager.dispatch(msg);
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+        }
+        if !reconnecting {
+            let _ = events.send(ConnectionEvent::Disconnected);
+        }
+        reconnecting = true;
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(config.reconnect_max_delay);
+    }
+}
+
+/// 频道的 JSON 表示展开为查询参数
+fn channel_query(channel: &Channel) -> Vec<(String, String)> {
+    let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(channel) else {
+        return Vec::new();
+    };
+    fields
+        .into_iter()
+        .map(|(key, value)| match value {
+            serde_json::Value::String(s) => (key, s),
+            other => (key, other.to_string()),
+        })
+        .collect()
+}
+
+/// 增量解析 `text/event-stream`，产出每个事件的 data
+#[derive(Default)]
+struct SseParser {
+    line: Vec<u8>,
+    data: String,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        let mut out = Vec::new();
+        for &byte in chunk {
+            if byte != b'\n' {
+                self.line.push(byte);
+                continue;
+            }
+            let mut line = String::from_utf8_lossy(&self.line).into_owned();
+            self.line.clear();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+            if line.is_empty() {
+                // 空行表示事件结束
+                if !self.data.is_empty() {
+                    out.push(std::mem::take(&mut self.data));
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                if !self.data.is_empty() {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value.strip_prefix(' ').unwrap_or(value));
+            }
+            // 其余字段（event、id、retry）与以冒号开头的注释行忽略
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_events_split_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed(b": keep-alive\n\ndata: {\"type\":").is_empty());
+        let events = parser.feed(b"\"pong\"}\r\n\r\nevent: x\ndata: a\ndata: b\n\n");
+        assert_eq!(events, vec![r#"{"type":"pong"}"#, "a\nb"]);
+    }
+
+    #[test]
+    fn channel_becomes_query_pairs() {
+        let mut query = channel_query(&Channel::Candles {
+            market: "BTC-USDC".to_string(),
+            interval: CandleInterval::Min1,
+        });
+        query.sort();
+        assert_eq!(
+            query,
+            vec![
+                ("channel".to_string(), "candles".to_string()),
+                ("interval".to_string(), "1m".to_string()),
+                ("market".to_string(), "BTC-USDC".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! 统一的行情流接口
+//!
+//! 策略面向 [`MarketStream`] 编写，通过 [`Transport`] 配置选择 WebSocket、SSE 或 REST 轮询。
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use super::client::WsClient;
+use super::config::WsConfig;
+use super::connection::ConnectionEvent;
+use super::message::{Candle, CandleInterval, OrderbookUpdate, Ticker, Trade};
+use super::poller::RestPoller;
+use super::sse::SseClient;
+use super::subscription::Subscription;
+use crate::client::LightPoolClient;
+use crate::error::Result;
+
+/// 公共行情流
+pub trait MarketStream: Send + Sync {
+    /// 订阅指定市场的订单簿更新
+    fn subscribe_orderbook(&self, market: &str) -> Result<Subscription<OrderbookUpdate>>;
+
+    /// 订阅指定市场的公共成交
+    fn subscribe_trades(&self, market: &str) -> Result<Subscription<Trade>>;
+
+    /// 订阅指定市场的最优买卖价与行情摘要
+    fn subscribe_ticker(&self, market: &str) -> Result<Subscription<Ticker>>;
+
+    /// 订阅指定市场与周期的K线
+    fn subscribe_candles(
+        &self,
+        market: &str,
+        interval: CandleInterval,
+    ) -> Result<Subscription<Candle>>;
+
+    /// 订阅连接状态事件
+    fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent>;
+}
+
+macro_rules! impl_market_stream {
+    ($($ty:ty),*) => {$(
+        impl MarketStream for $ty {
+            fn subscribe_orderbook(&self, market: &str) -> Result<Subscription<OrderbookUpdate>> {
+                <$ty>::subscribe_orderbook(self, market)
+            }
+
+            fn subscribe_trades(&self, market: &str) -> Result<Subscription<Trade>> {
+                <$ty>::subscribe_trades(self, market)
+            }
+
+            fn subscribe_ticker(&self, market: &str) -> Result<Subscription<Ticker>> {
+                <$ty>::subscribe_ticker(self, market)
+            }
+
+            fn subscribe_candles(
+                &self,
+                market: &str,
+                interval: CandleInterval,
+            ) -> Result<Subscription<Candle>> {
+                <$ty>::subscribe_candles(self, market, interval)
+            }
+
+            fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+                <$ty>::connection_events(self)
+            }
+        }
+    )*};
+}
+
+impl_market_stream!(WsClient, SseClient, RestPoller);
+
+#[cfg(feature = "quic")]
+impl_market_stream!(super::quic::QuicClient);
+
+#[cfg(feature = "redis")]
+impl_market_stream!(super::pubsub::RedisClient);
+
+/// 行情传输方式，可从配置文件反序列化，例如 `{"transport":"sse","base_url":"http://localhost:26300"}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "transport", rename_all = "snake_case")]
+pub enum Transport {
+    /// WebSocket，`url` 例如 `ws://localhost:26300/ws`
+    #[serde(rename = "websocket")]
+    WebSocket { url: String },
+    /// Server-Sent Events，`base_url` 例如 `http://localhost:26300`
+    Sse { base_url: String },
+    /// 通过 RPC 轮询
+    RestPolling {
+        base_url: String,
+        /// 轮询间隔（毫秒）
+        interval_ms: u64,
+    },
+}
+
+impl Transport {
+    /// 按配置建立行情流
+    pub async fn connect(&self, config: WsConfig) -> Result<Box<dyn MarketStream>> {
+        Ok(match self {
+            Transport::WebSocket { url } => {
+                Box::new(WsClient::connect_with_config(url, config).await?)
+            }
+            Transport::Sse { base_url } => Box::new(SseClient::with_config(base_url, config)?),
+            Transport::RestPolling {
+                base_url,
+                interval_ms,
+            } => {
+                let rpc = LightPoolClient::new(base_url, Duration::from_secs(30))?;
+                Box::new(RestPoller::with_config(
+                    rpc,
+                    Duration::from_millis(*interval_ms),
+                    config,
+                ))
+            }
+        })
+    }
+}
@@ -0,0 +1,92 @@
+//! 订阅流
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::Stream;
+
+use super::dedup::{Dedup, Deduped};
+use super::fanout::{self, BroadcastReceiver};
+use super::manager::SubscriptionHandle;
+use super::message::{Channel, WsMessage};
+use super::queue;
+
+/// 单个频道的订阅，以异步流形式产出类型化事件
+///
+/// 释放时自动退订。连接关闭后流结束；消息积压超过队列容量时按 [`OverflowPolicy`](super::OverflowPolicy) 处理。
+pub struct Subscription<T> {
+    channel: Channel,
+    rx: queue::Receiver<WsMessage>,
+    extract: fn(WsMessage) -> Option<T>,
+    handle: SubscriptionHandle,
+    capacity: usize,
+}
+
+impl<T> Subscription<T> {
+    pub(crate) fn new(
+        channel: Channel,
+        rx: queue::Receiver<WsMessage>,
+        extract: fn(WsMessage) -> Option<T>,
+        handle: SubscriptionHandle,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            channel,
+            rx,
+            extract,
+            handle,
+            capacity,
+        }
+    }
+
+    /// 订阅ID，与 [`MetricsSink`](super::MetricsSink) 回调中的 `subscription` 对应
+    pub fn id(&self) -> u64 {
+        self.handle.id()
+    }
+
+    /// 订阅的频道
+    pub fn channel(&self) -> &Channel {
+        &self.channel
+    }
+
+    /// 是否因积压溢出（[`OverflowPolicy::Error`](super::OverflowPolicy::Error)）而被终止
+    pub fn is_overflowed(&self) -> bool {
+        self.rx.is_overflowed()
+    }
+}
+
+impl<T: Dedup> Subscription<T> {
+    /// 丢弃重连或回放时重发的成交，`window` 为记住的最近成交数量
+    pub fn dedup(self, window: usize) -> Deduped<Self> {
+        Deduped::new(self, window)
+    }
+}
+
+impl<T: Clone + Send + 'static> Subscription<T> {
+    /// 把订阅转为 `n` 个广播接收端，多个任务可共享同一路行情
+    ///
+    /// 接收端可继续克隆；广播通道容量与订阅队列容量相同。所有接收端释放后自动退订。
+    pub fn broadcast(self, n: usize) -> Vec<BroadcastReceiver<T>> {
+        let capacity = self.capacity;
+        fanout::spawn(self, capacity, n)
+    }
+}
+
+impl<T> Stream for Subscription<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        loop {
+            match this.rx.poll_recv(cx) {
+                Poll::Ready(Some(msg)) => {
+                    if let Some(item) = (this.extract)(msg) {
+                        return Poll::Ready(Some(item));
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! 订单簿序列号校验与快照重同步
+//!
+//! 增量更新的 `seq` 必须连续。发现缺口后通过 [`SnapshotSource`] 拉取快照，
+//! 丢弃快照已包含的增量，回放其余缓冲增量，从而保证本地订单簿正确。
+//! 快照拉取按指数退避重试，多次失败后由 [`SyncedOrderbook::next`] 返回错误。
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+
+use super::message::OrderbookUpdate;
+use super::subscription::Subscription;
+use crate::client::LightPoolClient;
+use crate::error::{Error, Result};
+
+/// 快照拉取失败或快照过旧时的首次重试间隔，之后每次翻倍
+const RESYNC_INITIAL_DELAY: Duration = Duration::from_millis(200);
+
+/// 重试间隔上限
+const RESYNC_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// 单次重同步最多拉取快照的次数
+const RESYNC_MAX_ATTEMPTS: u32 = 8;
+
+/// 通过 RPC 拉取快照时请求的深度
+const SNAPSHOT_DEPTH: u32 = 1000;
+
+/// 订单簿快照来源
+pub trait SnapshotSource {
+    /// 拉取指定市场的全量订单簿快照
+    fn fetch_snapshot(&self, market: &str) -> impl Future<Output = Result<OrderbookUpdate>> + Send;
+}
+
+impl SnapshotSource for LightPoolClient {
+    fn fetch_snapshot(&self, market: &str) -> impl Future<Output = Result<OrderbookUpdate>> + Send {
+        self.get_order_book(market, SNAPSHOT_DEPTH)
+    }
+}
+
+/// 序列号校验结果
+#[derive(Debug, PartialEq, Eq)]
+enum Sequenced {
+    Emit(OrderbookUpdate),
+    /// 重复或过期的增量
+    Drop,
+    /// 出现缺口，需要拉取快照
+    NeedSnapshot,
+}
+
+/// 单个订单簿频道的序列号状态
+#[derive(Debug, Default)]
+struct BookSequencer {
+    last_seq: Option<u64>,
+    /// 等待快照期间缓冲的增量
+    buffer: Vec<OrderbookUpdate>,
+}
+
+impl BookSequencer {
+    fn on_update(&mut self, update: OrderbookUpdate) -> Sequenced {
+        if update.snapshot {
+            self.last_seq = Some(update.seq);
+            self.buffer.clear();
+            return Sequenced::Emit(update);
+        }
+        if !self.buffer.is_empty() {
+            self.buffer.push(update);
+            return Sequenced::NeedSnapshot;
+        }
+        match self.last_seq {
+            Some(last) if update.seq <= last => Sequenced::Drop,
+            Some(last) if update.seq == last + 1 => {
+                self.last_seq = Some(update.seq);
+                Sequenced::Emit(update)
+            }
+            // 尚未收到快照或出现缺口
+            _ => {
+                self.buffer.push(update);
+                Sequenced::NeedSnapshot
+            }
+        }
+    }
+
+    /// 应用快照并回放缓冲增量；快照与缓冲之间仍有缺口时返回 None
+    fn on_snapshot(&mut self, snapshot: OrderbookUpdate) -> Option<Vec<OrderbookUpdate>> {
+        self.buffer.retain(|diff| diff.seq > snapshot.seq);
+        let mut expected = snapshot.seq + 1;
+        for diff in &self.buffer {
+            if diff.seq != expected {
+                return None;
+            }
+            expected += 1;
+        }
+
+        self.last_seq = Some(expected - 1);
+        let mut replay = Vec::with_capacity(self.buffer.len() + 1);
+        replay.push(OrderbookUpdate {
+            snapshot: true,
+            ..snapshot
+        });
+        replay.append(&mut self.buffer);
+        Some(replay)
+    }
+}
+
+/// 带序列号校验的订单簿订阅
+///
+/// 产出的第一条（以及每次重同步后的第一条）为 `snapshot == true` 的全量快照。
+pub struct SyncedOrderbook<S> {
+    market: String,
+    updates: Subscription<OrderbookUpdate>,
+    source: S,
+    sequencer: BookSequencer,
+    pending: VecDeque<OrderbookUpdate>,
+}
+
+impl<S: SnapshotSource> SyncedOrderbook<S> {
+    pub(crate) fn new(market: String, updates: Subscription<OrderbookUpdate>, source: S) -> Self {
+        Self {
+            market,
+            updates,
+            source,
+            sequencer: BookSequencer::default(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// 下一条按序的订单簿更新；连接关闭后返回 None
+    ///
+    /// 重同步失败时返回错误，缓冲的增量保留，再次调用会在下一条增量到达时重新同步。
+    pub async fn next(&mut self) -> Option<Result<OrderbookUpdate>> {
+        loop {
+            if let Some(update) = self.pending.pop_front() {
+                return Some(Ok(update));
+            }
+            let update = self.updates.next().await?;
+            match self.sequencer.on_update(update) {
+                Sequenced::Emit(update) => return Some(Ok(update)),
+                Sequenced::Drop => {}
+                Sequenced::NeedSnapshot => {
+                    match resync(&self.source, &self.market, &mut self.sequencer).await {
+                        Ok(replay) => self.pending.extend(replay),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 拉取快照直到能与缓冲增量衔接，返回待回放的更新
+async fn resync<S: SnapshotSource>(
+    source: &S,
+    market: &str,
+    sequencer: &mut BookSequencer,
+) -> Result<Vec<OrderbookUpdate>> {
+    let mut delay = RESYNC_INITIAL_DELAY;
+    let mut attempts = 0;
+    loop {
+        let reason = match source.fetch_snapshot(market).await {
+            Ok(snapshot) => match sequencer.on_snapshot(snapshot) {
+                Some(replay) => return Ok(replay),
+                None => "snapshot does not cover buffered diffs".to_string(),
+            },
+            Err(e) => e.to_string(),
+        };
+        attempts += 1;
+        tracing::debug!(market, attempts, reason = %reason, "orderbook resync failed");
+        if attempts >= RESYNC_MAX_ATTEMPTS {
+            return Err(Error::Network(format!(
+                "orderbook resync for {} failed after {} attempts: {}",
+                market, attempts, reason
+            )));
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(RESYNC_MAX_DELAY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(seq: u64, snapshot: bool) -> OrderbookUpdate {
+        OrderbookUpdate {
+            market: "BTC-USDC".to_string(),
+            seq,
+            snapshot,
+            bids: vec![],
+            asks: vec![],
+            ts: 0,
+        }
+    }
+
+    #[test]
+    fn contiguous_diffs_are_emitted() {
+        let mut seq = BookSequencer::default();
+        assert_eq!(
+            seq.on_update(update(10, true)),
+            Sequenced::Emit(update(10, true))
+        );
+        assert_eq!(
+            seq.on_update(update(11, false)),
+            Sequenced::Emit(update(11, false))
+        );
+        assert_eq!(seq.on_update(update(11, false)), Sequenced::Drop);
+    }
+
+    #[test]
+    fn gap_replays_buffered_diffs_after_snapshot() {
+        let mut seq = BookSequencer::default();
+        seq.on_update(update(10, true));
+        assert_eq!(seq.on_update(update(13, false)), Sequenced::NeedSnapshot);
+        assert_eq!(seq.on_update(update(14, false)), Sequenced::NeedSnapshot);
+
+        let replay = seq.on_snapshot(update(12, false)).unwrap();
+        let seqs: Vec<u64> = replay.iter().map(|u| u.seq).collect();
+        assert_eq!(seqs, vec![12, 13, 14]);
+        assert!(replay[0].snapshot);
+        assert_eq!(
+            seq.on_update(update(15, false)),
+            Sequenced::Emit(update(15, false))
+        );
+    }
+
+    /// 始终失败的快照来源，记录拉取次数
+    struct Unavailable(std::sync::atomic::AtomicU32);
+
+    impl SnapshotSource for Unavailable {
+        fn fetch_snapshot(
+            &self,
+            _market: &str,
+        ) -> impl Future<Output = Result<OrderbookUpdate>> + Send {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::future::ready(Err(Error::Network("connection refused".to_string())))
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn resync_gives_up_after_max_attempts() {
+        let source = Unavailable(Default::default());
+        let mut seq = BookSequencer::default();
+        seq.on_update(update(10, true));
+        assert_eq!(seq.on_update(update(12, false)), Sequenced::NeedSnapshot);
+
+        let err = resync(&source, "BTC-USDC", &mut seq).await.unwrap_err();
+        assert!(matches!(err, Error::Network(_)));
+        assert!(err.to_string().contains("connection refused"));
+        assert_eq!(
+            source.0.load(std::sync::atomic::Ordering::SeqCst),
+            RESYNC_MAX_ATTEMPTS
+        );
+        // 缓冲增量保留，快照恢复后仍可衔接
+        assert_eq!(seq.on_snapshot(update(11, true)).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn stale_snapshot_is_rejected() {
+        let mut seq = BookSequencer::default();
+        seq.on_update(update(10, true));
+        seq.on_update(update(13, false));
+        assert!(seq.on_snapshot(update(11, true)).is_none());
+        // 更新的快照覆盖全部缓冲增量
+        let replay = seq.on_snapshot(update(13, true)).unwrap();
+        assert_eq!(replay.len(), 1);
+    }
+}
@@ -0,0 +1,1229 @@
+// lightpool 命令行工具的离线命令测试
+use std::process::Command;
+
+fn lightpool(args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_lightpool"))
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn encode_and_decode_place_order() {
+    let (ok, hex, _) = lightpool(&[
+        "encode",
+        "place-order",
+        "--side",
+        "sell",
+        "--amount",
+        "5000000",
+        "--price",
+        "50000000000",
+    ]);
+    assert!(ok);
+    assert_eq!(
+        hex.trim(),
+        "01000000404b4c0000000000000000000000000000743ba40b000000"
+    );
+
+    let (ok, decoded, _) = lightpool(&["decode", hex.trim()]);
+    assert!(ok);
+    let lines: Vec<&str> = decoded.lines().collect();
+    assert_eq!(lines[0], "place-order (28 bytes)");
+    assert!(decoded.contains("side           = Sell"));
+    assert!(decoded.contains("20..28  limit_price    = 50000000000"));
+
+    // 任意 32 字节都可视为撤单参数，但不是合法的下单参数
+    let (ok, decoded, stderr) = lightpool(&["decode", &"ab".repeat(32)]);
+    assert!(ok);
+    assert!(decoded.starts_with("cancel-order (32 bytes)"));
+    assert!(stderr.is_empty());
+    let (ok, _, _) = lightpool(&["decode", "--type", "place-order", &"ab".repeat(32)]);
+    assert!(!ok);
+
+    let (ok, _, stderr) = lightpool(&["decode", "zz"]);
+    assert!(!ok);
+    assert!(stderr.starts_with("error: decode error"));
+}
+
+#[test]
+fn encode_place_order_from_file() {
+    let market_id = format!("0x{}", "11".repeat(32));
+    let balance_id = format!("0x{}", "22".repeat(32));
+    let path = std::env::temp_dir().join(format!("lightpool-order-{}.json", std::process::id()));
+    std::fs::write(
+        &path,
+        serde_json::json!({
+            "side": "buy",
+            "amount": 10,
+            "price": 20,
+            "tif": "ioc",
+            "market_id": market_id,
+            "balance_id": balance_id,
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let (ok, stdout, stderr) = lightpool(&[
+        "encode",
+        "place-order",
+        "--file",
+        path.to_str().unwrap(),
+        "--amount",
+        "11",
+    ]);
+    std::fs::remove_file(&path).unwrap();
+    assert!(ok, "{}", stderr);
+    let output: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    // 命令行参数覆盖文件中的 amount
+    assert_eq!(
+        output["params"],
+        "000000000b0000000000000000000000010000001400000000000000"
+    );
+    assert_eq!(output["action"]["action"], 746789037603618816u64);
+    assert_eq!(output["action"]["inputs"][1][0], 0x22);
+    assert_eq!(output["action"]["contract"][0], 2);
+}
+
+#[test]
+fn sign_with_keystore_from_env_password() {
+    use lightpool::crypto::{verify, Ed25519Signer, Signer};
+    use lightpool::keystore::{Keystore, ScryptParams};
+    use lightpool::transaction::{Action, SignedTransaction, Transaction, SPOT_CONTRACT};
+    use lightpool::types::Address;
+
+    let dir = std::env::temp_dir().join(format!("lightpool-sign-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let signer = Ed25519Signer::from_secret_key_bytes(&[5u8; 32]);
+    let params = ScryptParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+    let key = dir.join("key.json");
+    Keystore::encrypt_with(&signer, "pw", params)
+        .unwrap()
+        .save(&key)
+        .unwrap();
+    let tx = dir.join("tx.json");
+    let unsigned = Transaction {
+        sender: Address::ZERO,
+        expiration: 100,
+        actions: vec![Action::new(SPOT_CONTRACT, "ord_place", Vec::new(), Vec::new()).unwrap()],
+    };
+    std::fs::write(&tx, serde_json::to_string(&unsigned).unwrap()).unwrap();
+
+    let sign = |password: &str| {
+        Command::new(env!("CARGO_BIN_EXE_lightpool"))
+            .args(["sign", "--key", key.to_str().unwrap(), tx.to_str().unwrap()])
+            .env("LIGHTPOOL_KEYSTORE_PASSWORD", password)
+            .env_remove("LIGHTPOOL_PRIVATE_KEY")
+            .output()
+            .unwrap()
+    };
+    let output = sign("pw");
+    let wrong = sign("nope");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success());
+    let signed: SignedTransaction = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(signed.transaction.sender, signer.address());
+    assert!(verify(
+        &signer.public_key(),
+        &signed.transaction.signing_bytes(),
+        &signed.signatures[0].to_bytes()
+    ));
+    assert!(!wrong.status.success());
+}
+
+#[test]
+fn keygen_writes_keystore_matching_mnemonic() {
+    use lightpool::crypto::Signer;
+    use lightpool::keystore::{signer_from_mnemonic, Keystore};
+
+    let dir = std::env::temp_dir().join(format!("lightpool-keygen-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let key = dir.join("key.json");
+    let keygen = || {
+        Command::new(env!("CARGO_BIN_EXE_lightpool"))
+            .args([
+                "keygen",
+                "--out",
+                key.to_str().unwrap(),
+                "--scrypt-log-n",
+                "4",
+            ])
+            .env("LIGHTPOOL_KEYSTORE_PASSWORD", "pw")
+            .output()
+            .unwrap()
+    };
+    let output = keygen();
+    // 已存在的私钥文件不会被覆盖
+    let again = keygen();
+    let keystore = Keystore::load(&key).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success());
+    assert!(!again.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let phrase = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("mnemonic: "))
+        .unwrap();
+    assert_eq!(phrase.split_whitespace().count(), 12);
+    let signer = keystore.decrypt("pw").unwrap();
+    assert_eq!(
+        signer.address(),
+        signer_from_mnemonic(phrase).unwrap().address()
+    );
+    assert!(stdout.contains(&signer.address().to_string()));
+}
+
+#[test]
+fn inspect_tx_decodes_actions_and_checks_signature() {
+    use lightpool::crypto::{Ed25519Signer, Signer};
+    use lightpool::order::PlaceOrderParams;
+    use lightpool::transaction::{Action, TransactionBuilder, SPOT_CONTRACT};
+    use lightpool::types::{ObjectId, OrderSide};
+
+    let signer = Ed25519Signer::from_secret_key_bytes(&[9u8; 32]);
+    let params = PlaceOrderParams::limit(OrderSide::Buy, 3, 4).encode();
+    let action = Action::new(SPOT_CONTRACT, "ord_place", vec![ObjectId([1; 32])], params).unwrap();
+    let signed = TransactionBuilder::new()
+        .expiration(42)
+        .action(action)
+        .build_and_sign(&signer)
+        .unwrap();
+    let hex = hex::encode(bincode::serialize(&signed).unwrap());
+
+    let public_key = hex::encode(signer.public_key());
+    let (ok, out, _) = lightpool(&["inspect-tx", &hex, "--public-key", &public_key]);
+    assert!(ok);
+    assert!(out.contains(&format!("sender:     {}", signer.address())));
+    assert!(out.contains("expiration: 42"));
+    assert!(out.contains("[0] ord_place @ "));
+    assert!(out.contains("params.amount         = 3"));
+    assert!(out.contains("params.order_type.tif = Gtc"));
+    assert!(out.contains("[0] valid"));
+
+    let (ok, out, _) = lightpool(&["inspect-tx", &hex]);
+    assert!(ok);
+    assert!(out.contains("[0] unverified"));
+
+    let other = hex::encode(Ed25519Signer::from_secret_key_bytes(&[1u8; 32]).public_key());
+    let (ok, out, err) = lightpool(&["inspect-tx", &hex, "--public-key", &other]);
+    assert!(!ok);
+    assert!(out.contains("public key does not match sender"));
+    assert!(err.contains("signature verification failed"));
+}
+
+#[test]
+fn repl_places_orders_from_registry() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    use lightpool::transaction::SignedTransaction;
+
+    let dir = std::env::temp_dir().join(format!("lightpool-repl-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let markets = dir.join("markets.json");
+    let market_id = format!("0x{}", "11".repeat(32));
+    let quote_balance = format!("0x{}", "22".repeat(32));
+    std::fs::write(
+        &markets,
+        serde_json::json!({
+            "markets": { "btc/usdc": { "market_id": market_id, "quote_balance": quote_balance } }
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lightpool"))
+        .args(["repl", "--dry-run", "--markets", markets.to_str().unwrap()])
+        .env("LIGHTPOOL_PRIVATE_KEY", "07".repeat(32))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(
+            b"markets\nbuy BTC-USDC 0.5 @ 50000\nsell BTC-USDC 1 @ 1\nbogus\nquit\nbalances\n",
+        )
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let (name, tx) = stdout.split_once('\n').unwrap();
+    assert_eq!(name, "BTC-USDC");
+    let tx: SignedTransaction = serde_json::from_str(tx).unwrap();
+    let action = &tx.transaction.actions[0];
+    assert_eq!(action.inputs[0].to_string(), market_id);
+    assert_eq!(action.inputs[1].to_string(), quote_balance);
+    assert_eq!(
+        hex::encode(&action.params),
+        "0000000020a1070000000000000000000000000000743ba40b000000"
+    );
+
+    // 卖单缺少基础币余额对象，未知命令不中断会话，quit 之后的命令不执行
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let errors: Vec<&str> = stderr.lines().collect();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains("no base_balance"));
+    assert!(errors[1].contains("unknown command: bogus"));
+}
+
+#[test]
+fn completions_include_registry_markets() {
+    let dir = std::env::temp_dir().join(format!("lightpool-completions-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let markets = dir.join("markets.json");
+    std::fs::write(
+        &markets,
+        serde_json::json!({
+            "markets": { "eth_usdt": { "market_id": format!("0x{}", "33".repeat(32)) } }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let markets = markets.to_str().unwrap();
+    let bash = lightpool(&["completions", "bash", "--markets", markets]);
+    let fish = lightpool(&["completions", "fish", "--markets", markets]);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let (ok, script, _) = bash;
+    assert!(ok);
+    assert!(script.contains("lightpool"));
+    assert!(script.contains("inspect-tx"));
+    assert!(script.contains("compgen -W \"ETH-USDT\""));
+    let (ok, script, _) = fish;
+    assert!(ok);
+    assert!(script.contains("-l market"));
+    assert!(script.contains("ETH-USDT"));
+    assert!(!lightpool(&["completions", "tcsh"]).0);
+}
+
+#[test]
+fn profile_supplies_defaults() {
+    use lightpool::crypto::{Ed25519Signer, Signer};
+    use lightpool::keystore::{Keystore, ScryptParams};
+
+    let dir = std::env::temp_dir().join(format!("lightpool-profile-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let signer = Ed25519Signer::from_secret_key_bytes(&[6u8; 32]);
+    let key = dir.join("key.json");
+    let params = ScryptParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+    Keystore::encrypt_with(&signer, "pw", params)
+        .unwrap()
+        .save(&key)
+        .unwrap();
+    let config = dir.join("config.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "default_profile = \"dev\"\n\n[profiles.dev]\nnetwork = \"testnet\"\nrpc_url = \"http://127.0.0.1:1\"\nkey = {:?}\nmarket = \"BTC-USDC\"\n\n[profiles.other]\ntimeout = 3\n",
+            key.to_str().unwrap()
+        ),
+    )
+    .unwrap();
+    let tx = dir.join("tx.json");
+    std::fs::write(
+        &tx,
+        serde_json::json!({ "sender": vec![0u8; 32], "expiration": 1, "actions": [] }).to_string(),
+    )
+    .unwrap();
+
+    let command = |args: &[&str]| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_lightpool"));
+        command
+            .args(args)
+            .env("LIGHTPOOL_CONFIG", &config)
+            .env("LIGHTPOOL_KEYSTORE_PASSWORD", "pw")
+            .env_remove("LIGHTPOOL_PROFILE")
+            .env_remove("LIGHTPOOL_NETWORK")
+            .env_remove("LIGHTPOOL_RPC_URL")
+            .env_remove("LIGHTPOOL_WS_URL")
+            .env_remove("LIGHTPOOL_PRIVATE_KEY");
+        command
+    };
+    let run = |args: &[&str]| command(args).output().unwrap();
+    let dev = run(&["profile"]);
+    let mainnet = command(&["profile"])
+        .env("LIGHTPOOL_NETWORK", "mainnet")
+        .output()
+        .unwrap();
+    let other = run(&[
+        "profile",
+        "--profile",
+        "other",
+        "--rpc-url",
+        "http://node:1",
+    ]);
+    let missing = run(&["profile", "--profile", "mainnet"]);
+    let signed = run(&["sign", tx.to_str().unwrap()]);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let dev: serde_json::Value = serde_json::from_slice(&dev.stdout).unwrap();
+    assert_eq!(dev["profile"], "dev");
+    assert_eq!(dev["network"], "testnet");
+    assert_eq!(dev["rpc_url"], "http://127.0.0.1:1");
+    assert_eq!(dev["ws_url"], "wss://testnet-rpc.lightpool.io/ws");
+    // LIGHTPOOL_NETWORK 优先于 profile 中的 network
+    let mainnet: serde_json::Value = serde_json::from_slice(&mainnet.stdout).unwrap();
+    assert_eq!(mainnet["network"], "mainnet");
+    assert_eq!(mainnet["ws_url"], "wss://rpc.lightpool.io/ws");
+    assert_eq!(dev["timeout"], 30);
+    let other: serde_json::Value = serde_json::from_slice(&other.stdout).unwrap();
+    assert_eq!(other["profile"], "other");
+    assert_eq!(other["rpc_url"], "http://node:1");
+    assert_eq!(other["timeout"], 3);
+    assert!(!missing.status.success());
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("profile mainnet is not defined"));
+
+    // 未传 --key 时使用 profile 中的私钥文件签名
+    assert!(signed.status.success());
+    let signed: serde_json::Value = serde_json::from_slice(&signed.stdout).unwrap();
+    assert_eq!(
+        signed["transaction"]["sender"],
+        serde_json::to_value(signer.address()).unwrap()
+    );
+}
+
+#[test]
+fn output_flag_selects_format() {
+    let hex = "01000000050000000000000000000000000000000700000000000000";
+    let (ok, json, _) = lightpool(&["decode", hex, "--output", "json"]);
+    assert!(ok);
+    let decoded: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded["type"], "place-order");
+    assert_eq!(decoded["length"], 28);
+    assert_eq!(decoded["fields"][0]["name"], "side");
+    assert_eq!(decoded["fields"][0]["bytes"], "01000000");
+
+    let (ok, json, _) = lightpool(&[
+        "encode",
+        "place-order",
+        "--side",
+        "sell",
+        "--amount",
+        "5",
+        "--price",
+        "7",
+        "--output",
+        "json",
+    ]);
+    assert!(ok);
+    let encoded: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(encoded, serde_json::json!({ "params": hex }));
+
+    // 不产出字节的命令拒绝十六进制输出，且不会访问节点
+    let (ok, _, err) = lightpool(&["balances", "--address", &"ab".repeat(32), "--output", "hex"]);
+    assert!(!ok);
+    assert!(err.contains("--output hex is not supported by balances"));
+}
+
+#[test]
+fn submit_dry_run_prints_without_submitting() {
+    use lightpool::crypto::Ed25519Signer;
+    use lightpool::transaction::{SignedTransaction, TransactionBuilder};
+    use lightpool::types::{ObjectId, OrderId};
+
+    let dir = std::env::temp_dir().join(format!("lightpool-dry-run-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let signer = Ed25519Signer::from_secret_key_bytes(&[4u8; 32]);
+    let action = lightpool::order::cancel_action(
+        lightpool::transaction::SPOT_CONTRACT,
+        ObjectId([1; 32]),
+        OrderId([2; 32]),
+    )
+    .unwrap();
+    let signed = TransactionBuilder::new()
+        .action(action)
+        .build_and_sign(&signer)
+        .unwrap();
+    let tx = dir.join("tx.json");
+    std::fs::write(&tx, serde_json::to_string(&signed).unwrap()).unwrap();
+    let tx = tx.to_str().unwrap();
+
+    // 节点地址不可达：dry-run 不应发出任何请求
+    let node = "http://127.0.0.1:1";
+    let json = lightpool(&["submit", tx, "--dry-run", "--rpc-url", node]);
+    let hex = lightpool(&[
+        "--dry-run",
+        "--output",
+        "hex",
+        "submit",
+        tx,
+        "--rpc-url",
+        node,
+    ]);
+    let simulate = lightpool(&["submit", tx, "--simulate", "--rpc-url", node]);
+    let submit_hex = lightpool(&["submit", tx, "--output", "hex", "--rpc-url", node]);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(json.0);
+    assert_eq!(
+        serde_json::from_str::<SignedTransaction>(&json.1).unwrap(),
+        signed
+    );
+    assert!(hex.0);
+    assert_eq!(
+        hex.1.trim(),
+        hex::encode(bincode::serialize(&signed).unwrap())
+    );
+    assert!(!simulate.0);
+    assert!(simulate.2.contains("--dry-run"));
+    assert!(!submit_hex.0);
+    assert!(submit_hex.2.contains("not supported by submit"));
+}
+
+#[tokio::test]
+async fn watch_trades_prints_ndjson() {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let request = loop {
+            if let Message::Text(text) = ws.next().await.unwrap().unwrap() {
+                break serde_json::from_str::<serde_json::Value>(&text).unwrap();
+            }
+        };
+        assert_eq!(request["channel"], "trades");
+        assert_eq!(request["market"], "BTC-USDC");
+        for (price, side) in [(50000, "Buy"), (49990, "Sell")] {
+            let trade = format!(
+                r#"{{"type":"trade","market":"BTC-USDC","price":{},"size":2,"side":"{}","trade_id":{},"ts":3}}"#,
+                price, side, price
+            );
+            ws.send(Message::Text(trade.into())).await.unwrap();
+        }
+        while ws.next().await.is_some() {}
+    });
+
+    let run = |args: Vec<String>| {
+        tokio::task::spawn_blocking(move || {
+            lightpool(&args.iter().map(String::as_str).collect::<Vec<_>>())
+        })
+    };
+    let (ok, stdout, stderr) = run(vec![
+        "watch".into(),
+        "trades".into(),
+        "btc/usdc".into(),
+        "--count".into(),
+        "2".into(),
+        "--output".into(),
+        "json".into(),
+        "--ws-url".into(),
+        url,
+    ])
+    .await
+    .unwrap();
+    assert!(ok, "{}", stderr);
+    let trades: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades[0]["price"], 50000);
+    assert_eq!(trades[1]["side"], "Sell");
+
+    let (ok, _, stderr) = run(vec![
+        "watch".into(),
+        "trades".into(),
+        "--output".into(),
+        "hex".into(),
+    ])
+    .await
+    .unwrap();
+    assert!(!ok);
+    assert!(stderr.contains("not supported by watch trades"));
+}
+
+#[test]
+fn gen_fixtures_writes_golden_files() {
+    let dir = std::env::temp_dir().join(format!("lightpool-fixtures-{}", std::process::id()));
+    let out = dir.to_str().unwrap();
+    let (ok, stdout, stderr) = lightpool(&["gen-fixtures", "--out", out, "--output", "json"]);
+    assert!(ok, "{}", stderr);
+    let written: Vec<serde_json::Value> = serde_json::from_str(&stdout).unwrap();
+    assert!(written
+        .iter()
+        .any(|file| file["type"] == "signed-transaction"));
+
+    let read = |name: &str| -> Vec<serde_json::Value> {
+        let text = std::fs::read_to_string(dir.join(format!("{}.json", name))).unwrap();
+        serde_json::from_str(&text).unwrap()
+    };
+    let orders = read("place-order");
+    let cancels = read("cancel-order");
+    let signed = read("signed-transaction");
+    // 再次生成的文件内容不变
+    assert!(lightpool(&["gen-fixtures", "--out", out]).0);
+    assert_eq!(read("place-order"), orders);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(orders[0]["name"], "limit-sell");
+    assert_eq!(
+        orders[0]["hex"],
+        "01000000404b4c0000000000000000000000000000743ba40b000000"
+    );
+    let params: lightpool::order::PlaceOrderParams =
+        serde_json::from_value(orders[0]["json"].clone()).unwrap();
+    assert_eq!(params.amount, 5_000_000);
+    let (ok, decoded, _) = lightpool(&["decode", cancels[0]["hex"].as_str().unwrap()]);
+    assert!(ok);
+    assert!(decoded.starts_with("cancel-order (32 bytes)"));
+    let tx: lightpool::transaction::SignedTransaction =
+        serde_json::from_value(signed[0]["json"].clone()).unwrap();
+    let public_key: [u8; 32] = hex::decode(signed[0]["signer"]["public_key"].as_str().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert!(tx.verify(&public_key));
+}
+
+#[test]
+fn gen_vectors_matches_the_committed_file() {
+    let path = std::env::temp_dir().join(format!("lightpool-vectors-{}.json", std::process::id()));
+    let (ok, _, _) = lightpool(&["gen-vectors", "--out", path.to_str().unwrap()]);
+    assert!(ok);
+    let written = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let committed =
+        std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors.json"))
+            .unwrap();
+    assert_eq!(written, committed);
+}
+
+#[test]
+fn codegen_python_prints_the_committed_module() {
+    let (ok, stdout, _) = lightpool(&["codegen", "python"]);
+    assert!(ok);
+    let committed = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/lightpool_sdk/wire.py"
+    ))
+    .unwrap();
+    assert_eq!(stdout, committed);
+}
+
+#[test]
+fn codegen_typescript_prints_the_committed_module() {
+    let (ok, stdout, _) = lightpool(&["codegen", "typescript"]);
+    assert!(ok);
+    let committed =
+        std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/idl/lightpool.ts")).unwrap();
+    assert_eq!(stdout, committed);
+}
+
+#[test]
+fn codegen_kaitai_prints_the_committed_spec() {
+    let (ok, stdout, _) = lightpool(&["codegen", "kaitai"]);
+    assert!(ok);
+    let committed =
+        std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/idl/lightpool.ksy")).unwrap();
+    assert_eq!(stdout, committed);
+
+    let (ok, stdout, _) = lightpool(&["codegen", "kaitai", "--root", "PlaceOrderParams"]);
+    assert!(ok);
+    assert!(stdout.contains("seq:\n  - id: value\n    type: place_order_params\n"));
+    let (ok, _, stderr) = lightpool(&["codegen", "kaitai", "--root", "Nope"]);
+    assert!(!ok);
+    assert!(stderr.contains("unknown type: Nope"));
+}
+
+#[test]
+fn gen_corpus_matches_fixtures() {
+    let dir = std::env::temp_dir().join(format!("lightpool-corpus-{}", std::process::id()));
+    let corpus = dir.join("corpus");
+    let fixtures = dir.join("fixtures");
+    assert!(lightpool(&["gen-corpus", "--out", corpus.to_str().unwrap()]).0);
+    assert!(lightpool(&["gen-fixtures", "--out", fixtures.to_str().unwrap()]).0);
+
+    let mut seeds = 0;
+    for entry in std::fs::read_dir(&fixtures).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+        let cases: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        for case in cases {
+            let seed =
+                std::fs::read(corpus.join(&name).join(case["name"].as_str().unwrap())).unwrap();
+            assert_eq!(hex::encode(seed), case["hex"].as_str().unwrap());
+            seeds += 1;
+        }
+    }
+    let seed = std::fs::read(corpus.join("signed-transaction/place-and-cancel")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(seeds > 20);
+    bincode::deserialize::<lightpool::transaction::SignedTransaction>(&seed).unwrap();
+}
+
+#[test]
+fn diff_reports_field_divergence() {
+    let expected = "01000000404b4c0000000000000000000000000000743ba40b000000";
+    let path = std::env::temp_dir().join(format!("lightpool-diff-{}.hex", std::process::id()));
+    std::fs::write(&path, format!("0x{}\n", expected)).unwrap();
+    let file = path.to_str().unwrap();
+
+    let (ok, stdout, _) = lightpool(&["diff", file, expected]);
+    assert!(ok);
+    assert!(stdout.contains("identical"));
+
+    let actual = expected.replace("404b4c", "414b4c");
+    let (ok, stdout, stderr) = lightpool(&["diff", file, &actual, "--type", "place-order"]);
+    assert!(!ok);
+    assert!(stdout.contains("amount  expected 5000000, actual 5000001"));
+    assert!(stdout.contains("first difference at byte 4 (amount)"));
+    assert!(stderr.contains("1 field(s) differ"));
+
+    let (ok, stdout, _) = lightpool(&["diff", file, &expected[..40], "--output", "json"]);
+    std::fs::remove_file(&path).unwrap();
+    assert!(!ok);
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(report["first_difference"], 20);
+    assert_eq!(report["first_difference_field"], "limit_price");
+    assert!(report["actual_error"]
+        .as_str()
+        .unwrap()
+        .contains("unexpected end"));
+}
+
+#[test]
+fn orders_place_csv_batches_rows() {
+    use lightpool::transaction::SignedTransaction;
+
+    let dir = std::env::temp_dir().join(format!("lightpool-place-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let markets = dir.join("markets.json");
+    std::fs::write(
+        &markets,
+        serde_json::json!({
+            "markets": { "btc/usdc": {
+                "market_id": format!("0x{}", "11".repeat(32)),
+                "base_balance": format!("0x{}", "21".repeat(32)),
+                "quote_balance": format!("0x{}", "22".repeat(32)),
+            } }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let csv = dir.join("orders.csv");
+    std::fs::write(
+        &csv,
+        "market,side,size,price,tif\n\
+         BTC-USDC,buy,0.5,50000,gtc\n\
+         BTC-USDC, sell ,0.25,51000,\n\
+         ETH-USDC,buy,1,2000,\n\
+         BTC-USDC,sell,1,52000,ioc\n",
+    )
+    .unwrap();
+    let place = |extra: &[&str]| {
+        let mut args = vec![
+            "orders",
+            "place",
+            "--csv",
+            csv.to_str().unwrap(),
+            "--batch-size",
+            "2",
+            "--dry-run",
+            "--markets",
+            markets.to_str().unwrap(),
+        ];
+        args.extend_from_slice(extra);
+        let output = Command::new(env!("CARGO_BIN_EXE_lightpool"))
+            .args(args)
+            .env("LIGHTPOOL_PRIVATE_KEY", "07".repeat(32))
+            .output()
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        (
+            output.status.success(),
+            report,
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    };
+
+    // 有无效行时不签名任何交易
+    let (ok, report, stderr) = place(&[]);
+    assert!(!ok);
+    assert!(stderr.contains("1 invalid row(s)"));
+    let statuses: Vec<&str> = report["rows"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["skipped", "skipped", "invalid", "skipped"]);
+    assert_eq!(report["rows"][2]["row"], 4);
+    assert!(report["rows"][2]["error"]
+        .as_str()
+        .unwrap()
+        .contains("ETH-USDC"));
+    assert!(report["transactions"].as_array().unwrap().is_empty());
+
+    // profile 中的风控限额在签名前检查每一行
+    let config = dir.join("config.toml");
+    std::fs::write(&config, "[profiles.default.risk]\nmax_position = 1\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_lightpool"))
+        .args([
+            "orders",
+            "place",
+            "--csv",
+            csv.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .args(["--markets", markets.to_str().unwrap()])
+        .env("LIGHTPOOL_CONFIG", &config)
+        .env_remove("LIGHTPOOL_PROFILE")
+        .env("LIGHTPOOL_PRIVATE_KEY", "07".repeat(32))
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let risky: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(risky["rows"][0]["status"], "invalid");
+    assert!(risky["rows"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("resulting position"));
+
+    let (ok, report, _) = place(&["--skip-invalid"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(ok);
+    let batches: Vec<&serde_json::Value> = report["rows"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| &row["batch"])
+        .collect();
+    assert_eq!(
+        batches,
+        [&0.into(), &0.into(), &serde_json::Value::Null, &1.into()]
+    );
+    let transactions = report["transactions"].as_array().unwrap();
+    assert_eq!(transactions.len(), 2);
+    let tx: SignedTransaction =
+        serde_json::from_value(transactions[0]["transaction"].clone()).unwrap();
+    assert_eq!(tx.transaction.actions.len(), 2);
+    // 卖单从基础币余额扣款
+    assert_eq!(
+        tx.transaction.actions[1].inputs[1].to_string(),
+        format!("0x{}", "21".repeat(32))
+    );
+}
+
+/// 依次以 `results` 应答 JSON-RPC 请求，返回收到的请求体
+async fn rpc_node(
+    results: Vec<serde_json::Value>,
+) -> (String, tokio::task::JoinHandle<Vec<serde_json::Value>>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for result in results {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let body_start = loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+            };
+            let head = String::from_utf8_lossy(&request[..body_start]).to_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .unwrap()
+                .trim()
+                .parse()
+                .unwrap();
+            while request.len() < body_start + length {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            requests.push(serde_json::from_slice(&request[body_start..]).unwrap());
+            let body =
+                serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+        requests
+    });
+    (url, server)
+}
+
+#[tokio::test]
+async fn cancel_all_batches_open_orders() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    use lightpool::transaction::{SignedTransaction, SPOT_CONTRACT};
+
+    let market_id = format!("0x{}", "11".repeat(32));
+    let market_address = format!("0x{}", "44".repeat(32));
+    let dir = std::env::temp_dir().join(format!("lightpool-cancel-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let markets = dir.join("markets.json");
+    std::fs::write(
+        &markets,
+        serde_json::json!({
+            "markets": { "btc/usdc": { "market_id": market_id, "market_address": market_address } }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let other_market = format!("0x{}", "12".repeat(32));
+    let orders = serde_json::json!({ "orders": [
+        { "order_id": format!("0x{}", "31".repeat(32)), "market_id": market_id },
+        { "orderId": format!("0x{}", "32".repeat(32)), "marketId": other_market },
+        { "order_id": "bogus", "market_id": market_id },
+        { "id": format!("0x{}", "33".repeat(32)), "market_id": market_id },
+    ] });
+    let (url, server) = rpc_node(vec![orders.clone(), orders]).await;
+
+    let run = |extra: &'static [&'static str], stdin: &'static [u8]| {
+        let (url, markets) = (url.clone(), markets.clone());
+        tokio::task::spawn_blocking(move || {
+            let mut child = Command::new(env!("CARGO_BIN_EXE_lightpool"))
+                .args(["cancel-all", "--batch-size", "2", "--rpc-url", &url])
+                .args(["--markets", markets.to_str().unwrap()])
+                .args(extra)
+                .env("LIGHTPOOL_PRIVATE_KEY", "07".repeat(32))
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .unwrap();
+            child.stdin.take().unwrap().write_all(stdin).unwrap();
+            child.wait_with_output().unwrap()
+        })
+    };
+
+    // 拒绝确认时不提交
+    let output = run(&[], b"n\n").await.unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cancel 3 open order(s)"));
+    assert!(stderr.contains("aborted"));
+    assert!(output.stdout.is_empty());
+
+    let output = run(&["--dry-run"], b"").await.unwrap();
+    let requests = server.await.unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1]["method"], "getOrders");
+
+    // 无法解析的订单计为失败，其余照常签名
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let statuses: Vec<&str> = report["rows"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["signed", "signed", "invalid", "signed"]);
+    let transactions = report["transactions"].as_array().unwrap();
+    assert_eq!(transactions.len(), 2);
+    let tx: SignedTransaction =
+        serde_json::from_value(transactions[0]["transaction"].clone()).unwrap();
+    // 合约地址取自登记表，不在登记表中的市场使用现货合约
+    assert_eq!(
+        tx.transaction.actions[0].contract.to_string(),
+        market_address
+    );
+    assert_eq!(tx.transaction.actions[1].contract, SPOT_CONTRACT);
+}
+
+#[tokio::test]
+async fn portfolio_combines_balances_positions_and_orders() {
+    use lightpool::crypto::{Ed25519Signer, Signer};
+
+    let address = Ed25519Signer::from_secret_key_bytes(&[7; 32])
+        .address()
+        .to_string();
+    let market_id = format!("0x{}", "11".repeat(32));
+    let dir = std::env::temp_dir().join(format!("lightpool-portfolio-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let markets = dir.join("markets.json");
+    std::fs::write(
+        &markets,
+        serde_json::json!({ "markets": { "btc/usdc": { "market_id": market_id } } }).to_string(),
+    )
+    .unwrap();
+    let fills = dir.join("fills.ndjson");
+    let fill = |account: &str, side: &str, price: u64, size: u64| {
+        serde_json::json!({
+            "account": account, "market": "BTC-USDC", "order_id": "o", "side": side,
+            "price": price, "size": size, "fee": 1, "is_maker": true, "ts": 1,
+        })
+        .to_string()
+    };
+    std::fs::write(
+        &fills,
+        [
+            fill(&address, "Buy", 100, 2),
+            String::new(),
+            fill(&address, "Sell", 120, 1),
+            fill("0x02", "Buy", 1, 1000),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+
+    let (url, server) = rpc_node(vec![
+        serde_json::json!({ "balances": [{ "token_address": "0x03", "balance": 500 }] }),
+        serde_json::json!({ "orders": [
+            { "side": "Buy", "price": 90, "remaining": 4, "market_id": market_id },
+            { "side": "Sell", "price": 150, "remaining": 1, "market_id": market_id },
+        ] }),
+        serde_json::json!({ "market": "BTC-USDC", "price": 130, "ts": 2 }),
+    ])
+    .await;
+    let args: Vec<String> = [
+        "portfolio",
+        "--address",
+        &address,
+        "--fills",
+        fills.to_str().unwrap(),
+        "--markets",
+        markets.to_str().unwrap(),
+        "--rpc-url",
+        &url,
+    ]
+    .iter()
+    .map(|arg| arg.to_string())
+    .collect();
+    let (ok, stdout, stderr) = tokio::task::spawn_blocking(move || {
+        lightpool(&args.iter().map(String::as_str).collect::<Vec<_>>())
+    })
+    .await
+    .unwrap();
+    let requests = server.await.unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(ok, "{}", stderr);
+    assert_eq!(requests[2]["method"], "getMarkPrice");
+    assert_eq!(requests[2]["params"][0]["marketId"], market_id);
+
+    let portfolio: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(portfolio["balances"][0]["balance"], 500);
+    // 其他账户的成交不计入
+    let position = &portfolio["positions"][0];
+    assert_eq!(position["size"], 1);
+    assert_eq!(position["mark_price"], 130);
+    assert_eq!(position["realized_pnl"], 20.0);
+    assert_eq!(position["unrealized_pnl"], 30.0);
+    let exposure = &portfolio["open_orders"][0];
+    assert_eq!(exposure["market"], "BTC-USDC");
+    assert_eq!(exposure["buy_notional"], 360.0);
+    assert_eq!(exposure["sell_size"], 1);
+    assert_eq!(portfolio["totals"]["open_orders"], 2);
+}
+
+#[tokio::test]
+async fn markets_refresh_updates_registry() {
+    let dir = std::env::temp_dir().join(format!("lightpool-markets-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let markets = dir.join("markets.json");
+    let btc = format!("0x{}", "11".repeat(32));
+    let contract = format!("0x{}", "44".repeat(32));
+    std::fs::write(
+        &markets,
+        serde_json::json!({ "markets": {
+            "btc/usdc": { "market_id": btc, "size_decimals": 8 },
+            "eth_usdc": { "market_id": format!("0x{}", "12".repeat(32)) },
+        } })
+        .to_string(),
+    )
+    .unwrap();
+    let (url, server) = rpc_node(vec![
+        serde_json::json!({
+            "market_id": btc, "market_address": contract, "name": "BTC/USDC",
+            "min_order_size": 1000, "tick_size": 10, "maker_fee_bps": 1, "taker_fee_bps": 5,
+            "status": "active",
+        }),
+        serde_json::Value::Null,
+    ])
+    .await;
+    let run = |args: Vec<String>| {
+        tokio::task::spawn_blocking(move || {
+            lightpool(&args.iter().map(String::as_str).collect::<Vec<_>>())
+        })
+    };
+    let args = |refresh: bool| {
+        let mut args = vec![
+            "markets".to_string(),
+            "--output".to_string(),
+            "json".to_string(),
+            "--markets".to_string(),
+            markets.to_str().unwrap().to_string(),
+            "--rpc-url".to_string(),
+            url.clone(),
+        ];
+        if refresh {
+            args.push("--refresh".to_string());
+        }
+        args
+    };
+
+    let (ok, stdout, stderr) = run(args(true)).await.unwrap();
+    assert!(ok, "{}", stderr);
+    assert!(stderr.contains("ETH-USDC"));
+    server.await.unwrap();
+    let listing: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(listing[0]["name"], "BTC-USDC");
+    assert_eq!(listing[0]["market_address"], contract);
+    assert_eq!(listing[0]["tick_size"], 10);
+    assert_eq!(listing[0]["lot_size"], 1000);
+    assert_eq!(listing[0]["status"], "active");
+    assert_eq!(listing[0]["size_decimals"], 8);
+    assert_eq!(listing[1]["status"], "unavailable");
+
+    // 刷新结果已写回，不带 --refresh 时不访问节点
+    let (ok, cached, _) = run(args(false)).await.unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(ok);
+    assert_eq!(cached, stdout);
+}
+
+#[test]
+fn bench_reports_each_operation() {
+    let (ok, stdout, stderr) = lightpool(&[
+        "bench",
+        "--iterations",
+        "20",
+        "--warmup",
+        "0",
+        "--actions",
+        "3",
+        "--output",
+        "json",
+    ]);
+    assert!(ok, "{}", stderr);
+    let reports: Vec<serde_json::Value> = serde_json::from_str(&stdout).unwrap();
+    let ops: Vec<&str> = reports.iter().map(|r| r["op"].as_str().unwrap()).collect();
+    assert_eq!(ops, ["encode", "hash", "sign", "encode+sign"]);
+    for report in &reports {
+        assert_eq!(report["iterations"], 20);
+        assert!(report["p50_us"].as_f64().unwrap() <= report["p99_us"].as_f64().unwrap());
+        assert!(report["p99_us"].as_f64().unwrap() <= report["max_us"].as_f64().unwrap());
+    }
+}
+
+#[test]
+fn verbose_logs_go_to_stderr() {
+    let address = format!("0x{}", "11".repeat(32));
+    let args = [
+        "balances",
+        "--address",
+        &address,
+        "--rpc-url",
+        "http://127.0.0.1:1",
+    ];
+    let (ok, stdout, quiet) = lightpool(&args);
+    assert!(!ok);
+    assert!(stdout.is_empty());
+    assert_eq!(quiet.lines().count(), 1);
+
+    let (_, stdout, stderr) = lightpool(&[&["-vv", "--log-json"], &args[..]].concat());
+    assert!(stdout.is_empty());
+    let (logs, error) = stderr.trim_end().rsplit_once('\n').unwrap();
+    let logs: Vec<serde_json::Value> = logs
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(logs[0]["fields"]["command"], "balances");
+    assert!(logs
+        .iter()
+        .any(|log| log["level"] == "DEBUG" && log["fields"]["method"] == "getAllBalance"));
+    assert!(logs
+        .iter()
+        .all(|log| log["target"].as_str().unwrap().starts_with("lightpool")));
+    assert!(error.starts_with("error: network error"));
+}
+
+#[test]
+fn errors_have_stable_exit_codes() {
+    let exit = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_lightpool"))
+            .args(args)
+            .env_remove("LIGHTPOOL_PRIVATE_KEY")
+            .output()
+            .unwrap();
+        (
+            output.status.code().unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    };
+    let address = format!("0x{}", "11".repeat(32));
+
+    let (code, stderr) = exit(&["decode", "--type", "place-order", "zz", "--output", "json"]);
+    assert_eq!(code, 3);
+    let envelope: serde_json::Value = serde_json::from_str(stderr.trim()).unwrap();
+    assert_eq!(envelope["error"]["kind"], "validation");
+    assert_eq!(envelope["error"]["exit_code"], 3);
+
+    let (code, stderr) = exit(&[
+        "balances",
+        "--address",
+        &address,
+        "--rpc-url",
+        "http://127.0.0.1:1",
+    ]);
+    assert_eq!(code, 4);
+    assert!(stderr.starts_with("error: network error"));
+
+    let (code, _) = exit(&["cancel-all", "--private-key", "zz", "--dry-run"]);
+    assert_eq!(code, 6);
+    let (code, _) = exit(&["balances", "--no-such-flag"]);
+    assert_eq!(code, 2);
+}
+
+#[test]
+fn environment_overrides_without_config_file() {
+    let output = Command::new(env!("CARGO_BIN_EXE_lightpool"))
+        .args(["profile", "--config", "/nonexistent/config.toml"])
+        .env("LIGHTPOOL_NETWORK", "mainnet")
+        .env("LIGHTPOOL_KEYSTORE", "/run/secrets/key.json")
+        .env("LIGHTPOOL_RPC_URL", "http://rpc:26300")
+        .env("LIGHTPOOL_TIMEOUT", "7")
+        .env_remove("LIGHTPOOL_PROFILE")
+        .env_remove("LIGHTPOOL_WS_URL")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let profile: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(profile["profile"], serde_json::Value::Null);
+    assert_eq!(profile["network"], "mainnet");
+    assert_eq!(profile["ws_url"], "wss://rpc.lightpool.io/ws");
+    assert_eq!(profile["key"], "/run/secrets/key.json");
+    assert_eq!(profile["rpc_url"], "http://rpc:26300");
+    assert_eq!(profile["timeout"], 7);
+}
@@ -0,0 +1,69 @@
+#!/usr/bin/env python3
+"""
+tests/conformance.rs 的 Python 一侧
+
+从标准输入逐行读取 JSON 用例，以 Python SDK 编码后逐行输出十六进制。
+只加载编码相关模块，不执行 lightpool_sdk/__init__.py，无需安装网络依赖。
+下单参数需要 attrs2bin（pip install -r requirements.txt），合约调用与交易由 wire.py 编码，只需标准库。
+缺少依赖时以退出码 3 结束，由 Rust 一侧决定跳过还是失败。
+"""
+
+import importlib.util
+import json
+import os
+import sys
+import types
+
+ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+package = types.ModuleType("lightpool_sdk")
+package.__path__ = [os.path.join(ROOT, "lightpool_sdk")]
+sys.modules["lightpool_sdk"] = package
+
+# 按路径加载生成的 wire.py，与 tests/test_wire.py 相同
+_spec = importlib.util.spec_from_file_location("lightpool_wire", os.path.join(ROOT, "lightpool_sdk", "wire.py"))
+wire = importlib.util.module_from_spec(_spec)
+sys.modules[_spec.name] = wire
+_spec.loader.exec_module(wire)
+
+
+def action(case):
+    return wire.Action(
+        inputs=[wire.ObjectId(bytes.fromhex(value)) for value in case["inputs"]],
+        contract=wire.Address(bytes.fromhex(case["contract"])),
+        action=case["action"],
+        params=bytes.fromhex(case["params"]),
+    )
+
+
+def encode(case):
+    if case["kind"] == "action":
+        return action(case).encode()
+    if case["kind"] == "transaction":
+        transaction = wire.Transaction(
+            sender=wire.Address(bytes.fromhex(case["sender"])),
+            expiration=case["expiration"],
+            actions=[action(item) for item in case["actions"]],
+        )
+        return transaction.encode()
+    if case["kind"] == "place_order":
+        try:
+            from lightpool_sdk.bincode import serialize_place_order_params
+            from lightpool_sdk.types import PlaceOrderParams
+        except ImportError as e:
+            print(f"python sdk unavailable: {e}", file=sys.stderr)
+            sys.exit(3)
+        params = PlaceOrderParams(
+            side=case["side"],
+            amount=case["amount"],
+            order_type=case["order_type"],
+            limit_price=case["limit_price"],
+            tif=case["tif"],
+            slippage=case["slippage"],
+        )
+        return serialize_place_order_params(params)
+    raise ValueError(f"unknown case kind: {case['kind']}")
+
+
+for line in sys.stdin:
+    print(encode(json.loads(line)).hex())
@@ -0,0 +1,245 @@
+// Rust 与 Python SDK 的编码一致性差分测试
+//
+// 以随机参数生成用例，经 tests/conformance.py 交给 Python SDK 编码，逐字节比对两边结果。
+// 下单参数由 lightpool_sdk/bincode.py 编码，需先 `pip install -r requirements.txt`（依赖 attrs2bin）；
+// 合约调用与交易由生成的 lightpool_sdk/wire.py 编码，只需标准库。
+// CI 中应以 `LIGHTPOOL_CONFORMANCE_REQUIRED=1 cargo test --test conformance` 运行，避免静默跳过。
+// 环境变量：
+//   LIGHTPOOL_PYTHON               Python 解释器，默认 python3
+//   LIGHTPOOL_CONFORMANCE_SEED     随机种子，失败信息中会给出以便复现
+//   LIGHTPOOL_CONFORMANCE_CASES    用例数，默认 256
+//   LIGHTPOOL_CONFORMANCE_REQUIRED 设置后，Python SDK 不可用时失败而不是跳过
+//
+// 暂不覆盖两边已知不一致的编码：Python 把触发单的 trigger_type 编码为 u32（Rust 为 u8），
+// 撤单参数只接受 16 字节的 ObjectID（Rust 的 OrderId 为 32 字节）。
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use lightpool::order::{OrderType, PlaceOrderParams, TimeInForce};
+use lightpool::transaction::{Action, Transaction};
+use lightpool::types::{Address, ObjectId, OrderSide};
+use serde_json::{json, Value};
+
+/// SplitMix64，测试无需密码学强度的随机数
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    /// 偏向边界值的 u64
+    fn amount(&mut self) -> u64 {
+        match self.below(8) {
+            0 => 0,
+            1 => u64::MAX,
+            2 => self.below(1_000),
+            _ => self.next() >> self.below(64),
+        }
+    }
+
+    fn bytes<const N: usize>(&mut self) -> [u8; N] {
+        std::array::from_fn(|_| self.next() as u8)
+    }
+}
+
+fn place_order_case(rng: &mut Rng) -> (Value, PlaceOrderParams) {
+    let side = rng.below(2);
+    let (amount, limit_price) = (rng.amount(), rng.amount());
+    let (order_type, tif, slippage) = match rng.below(2) {
+        0 => (0, rng.below(3), 0),
+        _ => (1, 0, rng.amount()),
+    };
+    let params = PlaceOrderParams {
+        side: if side == 0 {
+            OrderSide::Buy
+        } else {
+            OrderSide::Sell
+        },
+        amount,
+        order_type: match (order_type, tif) {
+            (0, 0) => OrderType::Limit {
+                tif: TimeInForce::Gtc,
+            },
+            (0, 1) => OrderType::Limit {
+                tif: TimeInForce::Ioc,
+            },
+            (0, _) => OrderType::Limit {
+                tif: TimeInForce::Fok,
+            },
+            _ => OrderType::Market { slippage },
+        },
+        limit_price,
+    };
+    let case = json!({
+        "kind": "place_order",
+        "side": side,
+        "amount": amount,
+        "order_type": order_type,
+        "tif": tif,
+        "slippage": slippage,
+        "limit_price": limit_price,
+    });
+    (case, params)
+}
+
+fn action_case(rng: &mut Rng) -> (Value, Action) {
+    let action = Action {
+        inputs: (0..rng.below(4)).map(|_| ObjectId(rng.bytes())).collect(),
+        contract: Address(rng.bytes()),
+        action: rng.amount(),
+        params: (0..rng.below(64)).map(|_| rng.next() as u8).collect(),
+    };
+    (action_json(&action), action)
+}
+
+fn action_json(action: &Action) -> Value {
+    json!({
+        "kind": "action",
+        "inputs": action.inputs.iter().map(|id| hex::encode(id.0)).collect::<Vec<_>>(),
+        "contract": hex::encode(action.contract.0),
+        "action": action.action,
+        "params": hex::encode(&action.params),
+    })
+}
+
+fn transaction_case(rng: &mut Rng) -> (Value, Transaction) {
+    let transaction = Transaction {
+        sender: Address(rng.bytes()),
+        expiration: rng.amount(),
+        actions: (0..rng.below(4)).map(|_| action_case(rng).1).collect(),
+    };
+    let case = json!({
+        "kind": "transaction",
+        "sender": hex::encode(transaction.sender.0),
+        "expiration": transaction.expiration,
+        "actions": transaction.actions.iter().map(action_json).collect::<Vec<_>>(),
+    });
+    (case, transaction)
+}
+
+fn env_u64(name: &str) -> Option<u64> {
+    std::env::var(name).ok().map(|value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("{} must be a u64", name))
+    })
+}
+
+/// 随机种子与用例，未设置种子时取当前时间
+fn generate(case: impl Fn(&mut Rng) -> (Value, Vec<u8>)) -> (u64, Vec<(Value, Vec<u8>)>) {
+    let seed = env_u64("LIGHTPOOL_CONFORMANCE_SEED").unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64
+    });
+    let count = env_u64("LIGHTPOOL_CONFORMANCE_CASES").unwrap_or(256);
+    let mut rng = Rng(seed);
+    (seed, (0..count).map(|_| case(&mut rng)).collect())
+}
+
+/// 把用例交给 Python 一侧编码；Python 或其依赖不可用且未要求必须运行时返回 None
+fn python_encode(cases: &[(Value, Vec<u8>)]) -> Option<Vec<String>> {
+    let required = std::env::var_os("LIGHTPOOL_CONFORMANCE_REQUIRED").is_some();
+    let python = std::env::var("LIGHTPOOL_PYTHON").unwrap_or_else(|_| "python3".to_string());
+    let input: String = cases
+        .iter()
+        .map(|(case, _)| format!("{}\n", case))
+        .collect();
+
+    let child = Command::new(&python)
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/conformance.py"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) if !required => {
+            eprintln!("skipping conformance: cannot run {}: {}", python, e);
+            return None;
+        }
+        Err(e) => panic!("cannot run {}: {}", python, e),
+    };
+    // 另起线程写入，避免两端管道同时写满而互相等待
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output().unwrap();
+    // Python 一侧提前退出时写入会失败，先按退出码判断
+    let written = writer.join().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.code() == Some(3) && !required {
+        eprintln!(
+            "skipping conformance: {} (pip install -r requirements.txt)",
+            stderr.trim()
+        );
+        return None;
+    }
+    assert!(output.status.success(), "python side failed: {}", stderr);
+    written.unwrap();
+
+    let encoded: Vec<String> = std::str::from_utf8(&output.stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect();
+    assert_eq!(encoded.len(), cases.len(), "python returned a short batch");
+    Some(encoded)
+}
+
+fn assert_conformance(case: impl Fn(&mut Rng) -> (Value, Vec<u8>)) {
+    let (seed, cases) = generate(case);
+    let Some(encoded) = python_encode(&cases) else {
+        return;
+    };
+    let mismatches: Vec<String> = cases
+        .iter()
+        .zip(&encoded)
+        .filter_map(|((case, bytes), python)| {
+            let rust = hex::encode(bytes);
+            (rust != *python).then(|| format!("{}\n  rust   {}\n  python {}", case, rust, python))
+        })
+        .collect();
+    assert!(
+        mismatches.is_empty(),
+        "{} of {} cases differ (LIGHTPOOL_CONFORMANCE_SEED={}):\n{}",
+        mismatches.len(),
+        cases.len(),
+        seed,
+        mismatches[..mismatches.len().min(5)].join("\n")
+    );
+}
+
+#[test]
+fn place_order_encoding_matches_python_sdk() {
+    assert_conformance(|rng| {
+        let (case, params) = place_order_case(rng);
+        (case, params.encode())
+    });
+}
+
+#[test]
+fn action_encoding_matches_python_wire() {
+    assert_conformance(|rng| {
+        let (case, action) = action_case(rng);
+        (case, bincode::serialize(&action).unwrap())
+    });
+}
+
+#[test]
+fn transaction_encoding_matches_python_wire() {
+    assert_conformance(|rng| {
+        let (case, transaction) = transaction_case(rng);
+        (case, transaction.signing_bytes())
+    });
+}
@@ -0,0 +1,76 @@
+// Kafka 接收端集成测试：需设置 LIGHTPOOL_TEST_KAFKA_BROKERS（逗号分隔，如 localhost:9092），
+// 未设置时跳过；每次运行创建独立的单分区 topic
+#![cfg(feature = "kafka")]
+
+use lightpool::storage::{
+    EventSink, KafkaConfig, KafkaFormat, KafkaSink, KafkaTopics, TradingEvent,
+};
+use lightpool::types::OrderSide;
+use lightpool::ws::{Fill, Trade};
+use rskafka::client::partition::UnknownTopicHandling;
+use rskafka::client::ClientBuilder;
+
+#[tokio::test]
+async fn events_are_published_to_their_topics() {
+    let Ok(brokers) = std::env::var("LIGHTPOOL_TEST_KAFKA_BROKERS") else {
+        eprintln!("LIGHTPOOL_TEST_KAFKA_BROKERS is not set, skipping");
+        return;
+    };
+    let brokers: Vec<String> = brokers.split(',').map(str::to_string).collect();
+    let prefix = format!("lightpool-test-{}", std::process::id());
+    let topics = KafkaTopics {
+        orders: format!("{}.orders", prefix),
+        fills: format!("{}.fills", prefix),
+        book: format!("{}.book", prefix),
+        trades: format!("{}.trades", prefix),
+    };
+    let client = ClientBuilder::new(brokers.clone()).build().await.unwrap();
+    let controller = client.controller_client().unwrap();
+    for topic in [&topics.orders, &topics.fills, &topics.book, &topics.trades] {
+        controller.create_topic(topic, 1, 1, 5_000).await.unwrap();
+    }
+
+    let config = KafkaConfig {
+        topics: topics.clone(),
+        ..KafkaConfig::new(brokers).format(KafkaFormat::Avro)
+    };
+    let mut sink = KafkaSink::connect(config).await.unwrap();
+    let fill = Fill {
+        account: "0x01".to_string(),
+        market: "BTC-USDC".to_string(),
+        order_id: "0xaa".to_string(),
+        side: OrderSide::Buy,
+        price: 100,
+        size: 2,
+        fee: 1,
+        is_maker: true,
+        trade_id: Some(1),
+        ts: 1_700_000_000_000,
+    };
+    let trade = Trade {
+        market: "BTC-USDC".to_string(),
+        price: 100,
+        size: 2,
+        side: OrderSide::Buy,
+        trade_id: Some(1),
+        ts: 1_700_000_000_000,
+    };
+    sink.write(&[
+        TradingEvent::Fill(fill.clone()),
+        TradingEvent::Trade(trade),
+        TradingEvent::Fill(fill),
+    ])
+    .await
+    .unwrap();
+
+    let fills = client
+        .partition_client(&topics.fills, 0, UnknownTopicHandling::Retry)
+        .await
+        .unwrap();
+    let (records, _) = fills.fetch_records(0, 1..1_000_000, 1_000).await.unwrap();
+    assert_eq!(records.len(), 2);
+    let record = &records[0].record;
+    assert_eq!(record.key.as_deref(), Some(&b"BTC-USDC"[..]));
+    assert_eq!(record.headers["lightpool-event"], b"fill");
+    assert_eq!(&record.value.as_ref().unwrap()[..2], &[0xc3, 0x01]);
+}
@@ -0,0 +1,116 @@
+// Postgres 接收端集成测试：需设置 LIGHTPOOL_TEST_DATABASE_URL（如 postgres://postgres@127.0.0.1/postgres），
+// 未设置时跳过；每次运行使用独立的 schema，结束后删除
+#![cfg(feature = "postgres")]
+
+use lightpool::storage::{forward, EventSink, PostgresSink, TradingEvent};
+use lightpool::types::OrderSide;
+use lightpool::ws::{Fill, OrderStatus, OrderUpdate, OrderbookUpdate, PriceLevel, Trade};
+use sqlx::postgres::PgPoolOptions;
+
+fn fill(trade_id: Option<u64>, ts: u64) -> Fill {
+    Fill {
+        account: "0x01".to_string(),
+        market: "BTC-USDC".to_string(),
+        order_id: "0xaa".to_string(),
+        side: OrderSide::Buy,
+        price: u64::MAX,
+        size: 2,
+        fee: 3,
+        is_maker: true,
+        trade_id,
+        ts,
+    }
+}
+
+async fn count(sink: &PostgresSink, table: &str) -> i64 {
+    sqlx::query_scalar(&format!("SELECT count(*) FROM {}.{}", sink.schema(), table))
+        .fetch_one(sink.pool())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn events_are_written_to_tables() {
+    let Ok(url) = std::env::var("LIGHTPOOL_TEST_DATABASE_URL") else {
+        eprintln!("LIGHTPOOL_TEST_DATABASE_URL is not set, skipping");
+        return;
+    };
+    let pool = PgPoolOptions::new().connect(&url).await.unwrap();
+    let schema = format!("lightpool_test_{}", std::process::id());
+    let mut sink = PostgresSink::with_pool(pool, &schema).await.unwrap();
+
+    let events = vec![
+        TradingEvent::Order(OrderUpdate {
+            account: "0x01".to_string(),
+            market: "BTC-USDC".to_string(),
+            order_id: "0xaa".to_string(),
+            side: OrderSide::Buy,
+            status: OrderStatus::PartiallyFilled,
+            price: 100,
+            amount: 10,
+            filled: 2,
+            ts: 1_700_000_000_123,
+        }),
+        TradingEvent::Fill(fill(Some(1), 1_700_000_000_124)),
+        TradingEvent::Fill(fill(None, 1_700_000_000_125)),
+        TradingEvent::Trade(Trade {
+            market: "BTC-USDC".to_string(),
+            price: 100,
+            size: 2,
+            side: OrderSide::Buy,
+            trade_id: Some(1),
+            ts: 1_700_000_000_124,
+        }),
+        TradingEvent::Book(OrderbookUpdate {
+            market: "BTC-USDC".to_string(),
+            seq: 7,
+            snapshot: true,
+            bids: vec![PriceLevel { price: 99, size: 1 }],
+            asks: vec![
+                PriceLevel {
+                    price: 101,
+                    size: 1,
+                },
+                PriceLevel {
+                    price: 102,
+                    size: 4,
+                },
+            ],
+            ts: 1_700_000_000_126,
+        }),
+    ];
+    let written = forward(futures_util::stream::iter(events), &mut sink, 2)
+        .await
+        .unwrap();
+    assert_eq!(written, 5);
+    // 重复的成交按唯一约束忽略
+    sink.write(&[TradingEvent::Fill(fill(Some(1), 1_700_000_000_124))])
+        .await
+        .unwrap();
+
+    assert_eq!(count(&sink, "order_updates").await, 1);
+    assert_eq!(count(&sink, "fills").await, 2);
+    assert_eq!(count(&sink, "trades").await, 1);
+    assert_eq!(count(&sink, "book_levels").await, 3);
+
+    let (price, ms, status): (String, i64, String) = sqlx::query_as(&format!(
+        "SELECT f.price::text, (extract(epoch FROM f.ts) * 1000)::bigint, o.status
+         FROM {schema}.fills f JOIN {schema}.order_updates o USING (order_id)
+         WHERE f.trade_id = 1"
+    ))
+    .fetch_one(sink.pool())
+    .await
+    .unwrap();
+    assert_eq!(price, u64::MAX.to_string());
+    assert_eq!(ms, 1_700_000_000_124);
+    assert_eq!(status, "partially_filled");
+
+    // 重复建表不报错
+    let again = PostgresSink::with_pool(sink.pool().clone(), &schema)
+        .await
+        .unwrap();
+    sqlx::query(&format!("DROP SCHEMA {} CASCADE", again.schema()))
+        .execute(again.pool())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,228 @@
+// RPC 客户端集成测试：在本地启动一个最简 JSON-RPC 服务
+use std::time::Duration;
+
+use lightpool::crypto::{Ed25519Signer, Signer};
+use lightpool::exchange::{ExchangeAdapter, LightPoolAdapter};
+use lightpool::order::{MarginMode, Market, OrderRequest, PlaceOrderParams};
+use lightpool::transaction::SignedTransaction;
+use lightpool::types::{Address, ObjectId, OrderSide};
+use lightpool::Error;
+use lightpool::{LightPoolClient, Symbol};
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// 依次以 `results` 应答请求，返回收到的请求体
+async fn rpc_server(results: Vec<Value>) -> (String, JoinHandle<Vec<Value>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for result in results {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let body_start = loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+            };
+            let head = String::from_utf8_lossy(&request[..body_start]).to_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .unwrap()
+                .trim()
+                .parse()
+                .unwrap();
+            while request.len() < body_start + length {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            requests.push(serde_json::from_slice(&request[body_start..]).unwrap());
+
+            let body = json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+        requests
+    });
+    (url, server)
+}
+
+fn order() -> OrderRequest {
+    OrderRequest::spot(
+        ObjectId([1; 32]),
+        ObjectId([2; 32]),
+        PlaceOrderParams::limit(OrderSide::Buy, 1_000_000, 50_000_000_000),
+    )
+}
+
+/// 非现货合约的下单请求
+fn margin_order() -> OrderRequest {
+    OrderRequest {
+        market_address: Address([9; 32]),
+        ..order()
+    }
+}
+
+#[tokio::test]
+async fn margin_orders_are_rejected_before_submission() {
+    let (url, server) = rpc_server(vec![
+        json!({ "digest": "0xabc", "receipt": { "status": "success", "events": [] } }),
+    ])
+    .await;
+    let client = LightPoolClient::new(&url, Duration::from_secs(5)).unwrap();
+    let signer = Ed25519Signer::generate();
+
+    // 协议不支持保证金，带保证金设置的订单在本地即被拒绝，不发出请求
+    for margined in [
+        order().with_margin(MarginMode::Cross, 1),
+        margin_order().with_margin(MarginMode::Cross, 5),
+    ] {
+        let err = client.place_order(&signer, &margined).await.unwrap_err();
+        assert!(err.to_string().contains("margin not supported by protocol"));
+    }
+
+    let result = client.place_order(&signer, &margin_order()).await.unwrap();
+    assert!(result.receipt.is_success());
+    assert_eq!(result.digest, "0xabc");
+
+    let requests = server.await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["method"], "submitTransaction");
+    let tx: SignedTransaction =
+        serde_json::from_value(requests[0]["params"][0]["tx"].clone()).unwrap();
+    assert!(tx.verify(&signer.public_key()));
+    let action = &tx.transaction.actions[0];
+    assert_eq!(action.contract, Address([9; 32]));
+    assert_eq!(action.inputs, vec![ObjectId([1; 32]), ObjectId([2; 32])]);
+    assert_eq!(action.action, 746789037603618816u64);
+    // 买入 1_000_000 @ 50_000_000_000 的限价 GTC 订单
+    assert_eq!(
+        action.params,
+        vec![
+            0, 0, 0, 0, 64, 66, 15, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 116, 59, 164, 11, 0,
+            0, 0
+        ]
+    );
+}
+
+#[tokio::test]
+async fn waits_for_final_receipt() {
+    let (url, server) = rpc_server(vec![
+        Value::Null,
+        json!({ "status": "pending" }),
+        json!({ "status": "success", "events": [{ "type": "order_placed" }] }),
+    ])
+    .await;
+    let client = LightPoolClient::new(&url, Duration::from_secs(5)).unwrap();
+
+    let receipt = client
+        .wait_for_receipt("0xabc", Duration::from_millis(10), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert!(receipt.is_success());
+    assert_eq!(receipt.events.len(), 1);
+
+    let requests = server.await.unwrap();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0]["method"], "getTransactionReceipt");
+    assert_eq!(requests[0]["params"][0]["digest"], "0xabc");
+}
+
+#[tokio::test]
+async fn simulates_without_submitting() {
+    let (url, server) = rpc_server(vec![json!({ "status": "failure", "events": [] })]).await;
+    let client = LightPoolClient::new(&url, Duration::from_secs(5)).unwrap();
+    let signer = Ed25519Signer::generate();
+    let tx = lightpool::transaction::TransactionBuilder::new()
+        .action(order().to_action().unwrap())
+        .build_and_sign(&signer)
+        .unwrap();
+
+    let receipt = client.simulate_transaction(&tx).await.unwrap();
+    assert!(receipt.is_final() && !receipt.is_success());
+
+    let requests = server.await.unwrap();
+    assert_eq!(requests[0]["method"], "simulateTransaction");
+    assert_eq!(
+        requests[0]["params"][0]["tx"]["signatures"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+/// 只依赖 [`ExchangeAdapter`] 的框架代码
+async fn quote_and_requote(
+    exchange: &dyn ExchangeAdapter,
+    symbol: &str,
+    order_id: &str,
+) -> lightpool::Result<(u64, String, String)> {
+    let book = exchange.order_book(symbol, 1).await?;
+    let best_bid = book.bids.first().map_or(0, |level| level.price);
+    let placed = exchange
+        .place_order(
+            symbol,
+            &PlaceOrderParams::limit(OrderSide::Buy, 1_000_000, best_bid),
+        )
+        .await?;
+    let cancelled = exchange.cancel_order(symbol, order_id).await?;
+    Ok((best_bid, placed, cancelled))
+}
+
+#[tokio::test]
+async fn exchange_adapter_places_cancels_and_queries() {
+    let (url, server) = rpc_server(vec![
+        json!({ "market": "BTC-USDC", "seq": 7, "bids": [{ "price": 49_000, "size": 3 }], "asks": [], "ts": 1 }),
+        json!({ "digest": "0x01", "receipt": { "status": "success", "events": [] } }),
+        json!({ "digest": "0x02", "receipt": { "status": "failure", "events": [] } }),
+        json!({ "balances": [{ "token_address": "0x03", "balance": 42 }] }),
+    ])
+    .await;
+    let client = LightPoolClient::new(&url, Duration::from_secs(5)).unwrap();
+    let signer = Ed25519Signer::generate();
+    let address = signer.address().to_string();
+    let adapter = LightPoolAdapter::new(client, signer).market(
+        Symbol::parse("BTC-USDC").unwrap(),
+        Market::spot(ObjectId([1; 32]), ObjectId([2; 32]), ObjectId([3; 32])),
+    );
+    // 多交易所框架以 trait 对象持有各适配器
+    let exchanges: Vec<Box<dyn ExchangeAdapter>> = vec![Box::new(adapter)];
+    let adapter = exchanges[0].as_ref();
+    assert_eq!(adapter.name(), "lightpool");
+
+    let order_id = "ab".repeat(32);
+    let rejected = quote_and_requote(adapter, "BTC-USDC", &order_id).await;
+    assert!(matches!(
+        rejected,
+        Err(Error::Rejected { ref digest, .. }) if digest == "0x02"
+    ));
+    let balances = adapter.balances().await.unwrap();
+    assert_eq!(balances[0].balance, 42);
+
+    let requests = server.await.unwrap();
+    let methods: Vec<_> = requests.iter().map(|r| r["method"].clone()).collect();
+    assert_eq!(
+        methods,
+        vec![
+            "getOrderBook",
+            "submitTransaction",
+            "submitTransaction",
+            "getAllBalance"
+        ]
+    );
+    assert_eq!(requests[0]["params"][0]["marketId"], "BTC-USDC");
+    let place = &requests[1]["params"][0]["tx"]["transaction"]["actions"][0];
+    assert_eq!(place["inputs"][1], json!(ObjectId([3; 32])));
+    assert_eq!(requests[3]["params"][0]["address"], address);
+}
@@ -0,0 +1,48 @@
+// SSE 备用传输集成测试：在本地启动一个最简 HTTP 服务
+use futures_util::StreamExt;
+use lightpool::ws::SseClient;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+#[tokio::test]
+async fn trades_stream_over_sse() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let n = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+        }
+        let head = String::from_utf8(request).unwrap();
+
+        let body = concat!(
+            ": connected\n\n",
+            r#"data: {"type":"trade","market":"BTC-USDC","price":5,"size":1,"side":"Buy","ts":0}"#,
+            "\n\n"
+        );
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ntransfer-encoding: chunked\r\n\r\n{:x}\r\n{}\r\n",
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        // 保持连接直到客户端退订
+        let _ = stream.read(&mut buf).await;
+        head
+    });
+
+    let client = SseClient::new(&format!("http://{}", addr)).unwrap();
+    let mut trades = client.subscribe_trades("BTC-USDC").unwrap();
+    let trade = trades.next().await.unwrap();
+    assert_eq!(trade.price, 5);
+    drop(trades);
+
+    let head = server.await.unwrap();
+    assert!(head.starts_with("GET /stream?"));
+    assert!(head.contains("channel=trades"));
+    assert!(head.contains("market=BTC-USDC"));
+}
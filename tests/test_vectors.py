@@ -0,0 +1,125 @@
+#!/usr/bin/env python3
+"""
+与 Rust SDK 共用的测试向量
+
+tests/vectors.json 由 `lightpool gen-vectors --out tests/vectors.json` 生成。
+向量版本变化说明 Rust 一侧的编码有意调整，须同步修改 Python 实现后再更新 VECTORS_VERSION。
+"""
+
+import hashlib
+import json
+import os
+
+import pytest
+
+from lightpool_sdk import Signer
+from lightpool_sdk.bincode import serialize_place_order_params
+from lightpool_sdk.transaction import Action, TransactionBuilder
+from lightpool_sdk.types import Address, ObjectID, PlaceOrderParams
+
+# 本 SDK 已对齐的向量版本
+VECTORS_VERSION = 1
+
+with open(os.path.join(os.path.dirname(__file__), "vectors.json")) as f:
+    VECTORS = json.load(f)
+
+SIDES = {"Buy": 0, "Sell": 1}
+TIFS = {"GTC": 0, "IOC": 1, "FOK": 2}
+
+
+def vectors_of(kind):
+    return [v for v in VECTORS["vectors"] if v["type"] == kind]
+
+
+def place_order_params(value):
+    (kind, fields), = value["order_type"].items()
+    params = PlaceOrderParams(
+        side=SIDES[value["side"]],
+        amount=value["amount"],
+        order_type={"Limit": 0, "Market": 1, "Trigger": 2}[kind],
+        limit_price=value["limit_price"],
+    )
+    if kind == "Limit":
+        params.tif = TIFS[fields["tif"]]
+    elif kind == "Market":
+        params.slippage = fields["slippage"]
+    else:
+        params.trigger_price = fields["trigger_price"]
+        params.is_market = fields["is_market"]
+        params.trigger_type = fields["trigger_type"]
+    return params
+
+
+def action_name(value):
+    """Rust Name 的 u64 编码还原为操作名：12 个 5 位字符，不足部分以 0 填充"""
+    chars = []
+    for _ in range(12):
+        value, digit = divmod(value, 32)
+        if digit == 0:
+            chars.append("_")
+        elif digit <= 5:
+            chars.append(str(digit))
+        else:
+            chars.append(chr(ord("a") + digit - 6))
+    return "".join(reversed(chars)).rstrip("_")
+
+
+def place_order_cases():
+    for vector in vectors_of("place-order"):
+        marks = []
+        if "Trigger" in vector["input"]["order_type"]:
+            # Python 把 trigger_type 编码为 u32，Rust 为 u8；修复后此用例会以 XPASS 失败提醒移除标记
+            marks.append(pytest.mark.xfail(strict=True, reason="trigger_type width differs"))
+        yield pytest.param(vector, id=vector["name"], marks=marks)
+
+
+class TestVectors:
+    """Rust SDK 测试向量比对"""
+
+    def test_version_is_pinned(self):
+        assert VECTORS["version"] == VECTORS_VERSION
+        assert VECTORS["digest"] == "sha512"
+
+    def test_signer_matches(self):
+        signer = Signer.from_secret_key_bytes(bytes.fromhex(VECTORS["signer"]["secret_key"]))
+        assert signer.public_key_bytes().hex() == VECTORS["signer"]["public_key"]
+        assert str(signer.address()) == VECTORS["signer"]["address"]
+
+    @pytest.mark.parametrize("vector", VECTORS["vectors"], ids=lambda v: f"{v['type']}/{v['name']}")
+    def test_digest_and_signature(self, vector):
+        data = bytes.fromhex(vector["bytes"])
+        signer = Signer.from_secret_key_bytes(bytes.fromhex(VECTORS["signer"]["secret_key"]))
+        assert hashlib.sha512(data).hexdigest() == vector["digest"]
+        assert signer.sign(data).hex() == vector["signature"]
+
+    @pytest.mark.parametrize("vector", place_order_cases())
+    def test_place_order_encoding(self, vector):
+        params = place_order_params(vector["input"])
+        assert serialize_place_order_params(params).hex() == vector["bytes"]
+
+    @pytest.mark.xfail(
+        strict=True,
+        reason="Python SDK 对排序后的 JSON 签名，Rust 对 bincode 编码签名；ObjectID 也只有 16 字节",
+    )
+    def test_transaction_signing(self):
+        vector = next(v for v in vectors_of("transaction") if v["name"] == "place-and-cancel")
+        value = vector["input"]
+        builder = (
+            TransactionBuilder.new()
+            .sender(Address(bytes(value["sender"])))
+            .expiration(value["expiration"])
+        )
+        for action in value["actions"]:
+            builder.add_action(
+                Action(
+                    input_objects=[ObjectID(bytes(input)) for input in action["inputs"]],
+                    target_address=Address(bytes(action["contract"])),
+                    action_name=action_name(action["action"]),
+                    params=bytes(action["params"]),
+                )
+            )
+        signer = Signer.from_secret_key_bytes(bytes.fromhex(VECTORS["signer"]["secret_key"]))
+        signing_bytes = builder._serialize_transaction(builder.build())
+        assert signing_bytes.hex() == vector["bytes"]
+        signed = builder.build_and_sign(signer)
+        assert signed.signed_transaction.signatures[0].hex() == vector["signature"]
@@ -0,0 +1,516 @@
+// WebSocket 客户端集成测试：在本地启动一个模拟网关
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use lightpool::crypto::{address_from_public_key, verify, Ed25519Signer};
+use lightpool::ws::{
+    Channel, ConnectionEvent, InMemoryMetrics, Replay, ReplaySpeed, Transport, WsClient, WsConfig,
+};
+use lightpool::Error;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+/// 读取客户端的下一条 JSON 请求，跳过心跳等控制帧
+async fn next_request<S>(ws: &mut tokio_tungstenite::WebSocketStream<S>) -> serde_json::Value
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    loop {
+        if let Message::Text(text) = ws.next().await.unwrap().unwrap() {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+/// 模拟网关完成认证握手：回复挑战并确认签名者地址
+async fn accept_auth<S>(ws: &mut tokio_tungstenite::WebSocketStream<S>)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let request = next_request(ws).await;
+    assert_eq!(request["op"], "challenge");
+    let challenge = format!(
+        r#"{{"type":"challenge","nonce":"{}"}}"#,
+        hex::encode([7u8; 16])
+    );
+    ws.send(Message::Text(challenge.into())).await.unwrap();
+    let request = next_request(ws).await;
+    assert_eq!(request["op"], "auth");
+    let reply = format!(
+        r#"{{"type":"authenticated","address":"{}"}}"#,
+        request["address"].as_str().unwrap()
+    );
+    ws.send(Message::Text(reply.into())).await.unwrap();
+}
+
+#[tokio::test]
+async fn orderbook_subscription_receives_updates() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        let request = next_request(&mut ws).await;
+        assert_eq!(request["op"], "subscribe");
+        assert_eq!(request["channel"], "orderbook");
+        assert_eq!(request["market"], "BTC-USDC");
+
+        let update = r#"{"type":"book","market":"BTC-USDC","seq":1,"snapshot":true,
+            "bids":[{"price":50000000000,"size":5000000}],"asks":[],"ts":1700000000000}"#;
+        ws.send(Message::Text(update.into())).await.unwrap();
+        // 保持连接直到客户端断开
+        while ws.next().await.is_some() {}
+    });
+
+    let client = WsClient::connect(&format!("ws://{}", addr)).await.unwrap();
+    // 无分隔符写法按规范市场名订阅
+    let mut book = client.subscribe_orderbook("btcusdc").unwrap();
+    assert_eq!(
+        book.channel(),
+        &Channel::Orderbook {
+            market: "BTC-USDC".to_string()
+        }
+    );
+    let update = book.next().await.unwrap();
+    assert_eq!(update.seq, 1);
+    assert!(update.snapshot);
+    assert_eq!(update.bids[0].price, 50_000_000_000);
+    assert_eq!(update.bids[0].size, 5_000_000);
+}
+
+#[tokio::test]
+async fn reconnects_and_resubscribes_after_disconnect() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        // 第一条连接：收到订阅后立即断开
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        next_request(&mut ws).await;
+        ws.close(None).await.unwrap();
+        drop(ws);
+
+        // 第二条连接：客户端应自动重新订阅
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let request = next_request(&mut ws).await;
+        assert_eq!(request["op"], "subscribe");
+        assert_eq!(request["channel"], "trades");
+
+        let trade =
+            r#"{"type":"trade","market":"BTC-USDC","price":1,"size":2,"side":"Sell","ts":3}"#;
+        ws.send(Message::Text(trade.into())).await.unwrap();
+        while ws.next().await.is_some() {}
+    });
+
+    let config = WsConfig {
+        reconnect_initial_delay: Duration::from_millis(10),
+        ..WsConfig::default()
+    };
+    let client = WsClient::connect_with_config(&format!("ws://{}", addr), config)
+        .await
+        .unwrap();
+    let mut events = client.connection_events();
+    let mut trades = client.subscribe_trades("BTC-USDC").unwrap();
+
+    assert_eq!(events.recv().await.unwrap(), ConnectionEvent::Disconnected);
+    assert_eq!(events.recv().await.unwrap(), ConnectionEvent::Reconnected);
+    let trade = trades.next().await.unwrap();
+    assert_eq!(trade.size, 2);
+}
+
+#[tokio::test]
+async fn shared_channel_is_subscribed_once_and_released_on_drop() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let mut ops = Vec::new();
+        while let Some(Ok(frame)) = ws.next().await {
+            if let Message::Text(text) = frame {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                ops.push(request["op"].as_str().unwrap().to_string());
+                if ops.len() == 2 {
+                    break;
+                }
+            }
+        }
+        ops
+    });
+
+    let client = WsClient::connect(&format!("ws://{}", addr)).await.unwrap();
+    let first = client.subscribe_orderbook("BTC-USDC").unwrap();
+    let second = client.subscribe_orderbook("BTC-USDC").unwrap();
+    assert_eq!(client.subscriber_count(first.channel()), 2);
+    drop(first);
+    assert_eq!(client.active_channels().len(), 1);
+    drop(second);
+    assert!(client.active_channels().is_empty());
+
+    assert_eq!(server.await.unwrap(), vec!["subscribe", "unsubscribe"]);
+}
+
+#[tokio::test]
+async fn authenticates_with_signed_challenge() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let nonce = [9u8; 16];
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        let request = next_request(&mut ws).await;
+        assert_eq!(request["op"], "challenge");
+        let challenge = format!(r#"{{"type":"challenge","nonce":"{}"}}"#, hex::encode(nonce));
+        ws.send(Message::Text(challenge.into())).await.unwrap();
+
+        let request = next_request(&mut ws).await;
+        assert_eq!(request["op"], "auth");
+        let mut public_key = [0u8; 32];
+        hex::decode_to_slice(request["public_key"].as_str().unwrap(), &mut public_key).unwrap();
+        let mut signature = [0u8; 64];
+        hex::decode_to_slice(request["signature"].as_str().unwrap(), &mut signature).unwrap();
+        assert!(verify(&public_key, &nonce, &signature));
+        assert_eq!(
+            request["address"].as_str().unwrap(),
+            address_from_public_key(&public_key).to_string()
+        );
+
+        let reply = format!(
+            r#"{{"type":"authenticated","address":"{}"}}"#,
+            request["address"].as_str().unwrap()
+        );
+        ws.send(Message::Text(reply.into())).await.unwrap();
+        while ws.next().await.is_some() {}
+    });
+
+    let client = WsClient::connect(&format!("ws://{}", addr)).await.unwrap();
+    client
+        .authenticate(Ed25519Signer::from_secret_key_bytes(&[1u8; 32]))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rejected_authentication_returns_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        next_request(&mut ws).await;
+        let reply = r#"{"type":"error","code":4010,"message":"auth disabled"}"#;
+        ws.send(Message::Text(reply.into())).await.unwrap();
+        while ws.next().await.is_some() {}
+    });
+
+    let client = WsClient::connect(&format!("ws://{}", addr)).await.unwrap();
+    let err = client
+        .authenticate(Ed25519Signer::generate())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Auth(message) if message == "auth disabled"));
+}
+
+#[tokio::test]
+async fn callbacks_receive_events_until_cancelled() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        next_request(&mut ws).await;
+        let trade =
+            r#"{"type":"trade","market":"BTC-USDC","price":7,"size":1,"side":"Buy","ts":0}"#;
+        ws.send(Message::Text(trade.into())).await.unwrap();
+        // 注销回调后应收到退订
+        let request = next_request(&mut ws).await;
+        request["op"].as_str().unwrap().to_string()
+    });
+
+    let client = WsClient::connect(&format!("ws://{}", addr)).await.unwrap();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let handle = client
+        .on_trade("BTC-USDC", move |trade| {
+            let _ = tx.send(trade.price);
+        })
+        .unwrap();
+    assert_eq!(rx.recv().await, Some(7));
+
+    handle.cancel();
+    assert_eq!(server.await.unwrap(), "unsubscribe");
+}
+
+#[tokio::test]
+async fn recorded_messages_replay_through_subscriptions() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        next_request(&mut ws).await;
+        for price in [10, 11] {
+            let trade = format!(
+                r#"{{"type":"trade","market":"BTC-USDC","price":{},"size":1,"side":"Buy","ts":0}}"#,
+                price
+            );
+            ws.send(Message::Text(trade.into())).await.unwrap();
+        }
+        while ws.next().await.is_some() {}
+    });
+
+    let path = std::env::temp_dir().join(format!("lightpool-replay-{}.jsonl", std::process::id()));
+    let client = WsClient::connect(&format!("ws://{}", addr)).await.unwrap();
+    let recorder = client.start_recording(&path).unwrap();
+    let mut trades = client.subscribe_trades("BTC-USDC").unwrap();
+    trades.next().await.unwrap();
+    trades.next().await.unwrap();
+    recorder.finish().unwrap();
+
+    let replay = Replay::open(&path).unwrap().speed(ReplaySpeed::Unthrottled);
+    let replayed = replay.client().subscribe_trades("BTC-USDC").unwrap();
+    assert_eq!(replay.run().await.unwrap(), 2);
+    let prices: Vec<u64> = replayed.map(|trade| trade.price).collect().await;
+    assert_eq!(prices, vec![10, 11]);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn transport_config_selects_market_stream() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let request = next_request(&mut ws).await;
+        assert_eq!(request["channel"], "ticker");
+        let ticker = r#"{"type":"ticker","market":"BTC-USDC","best_bid":9,"best_ask":11,
+            "last_price":10,"volume_24h":3,"ts":0}"#;
+        ws.send(Message::Text(ticker.into())).await.unwrap();
+        while ws.next().await.is_some() {}
+    });
+
+    let transport: Transport = serde_json::from_str(&format!(
+        r#"{{"transport":"websocket","url":"ws://{}"}}"#,
+        addr
+    ))
+    .unwrap();
+    let stream = transport.connect(WsConfig::default()).await.unwrap();
+    let ticker = stream
+        .subscribe_ticker("BTC-USDC")
+        .unwrap()
+        .next()
+        .await
+        .unwrap();
+    assert_eq!((ticker.best_bid, ticker.best_ask), (Some(9), Some(11)));
+}
+
+#[tokio::test]
+async fn gives_up_after_max_reconnect_attempts() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        next_request(&mut ws).await;
+        // 关闭连接与监听端口，后续重连全部失败
+        ws.close(None).await.unwrap();
+    });
+
+    let config = WsConfig {
+        reconnect_initial_delay: Duration::from_millis(10),
+        reconnect_max_attempts: Some(2),
+        ..WsConfig::default()
+    };
+    let client = WsClient::connect_with_config(&format!("ws://{}", addr), config)
+        .await
+        .unwrap();
+    let mut events = client.connection_events();
+    let mut trades = client.subscribe_trades("BTC-USDC").unwrap();
+
+    assert_eq!(events.recv().await.unwrap(), ConnectionEvent::Disconnected);
+    match events.recv().await.unwrap() {
+        ConnectionEvent::ConnectionFailed(diagnostics) => {
+            assert_eq!(diagnostics.attempts, 2);
+            assert!(!diagnostics.last_error.is_empty());
+        }
+        event => panic!("unexpected event: {:?}", event),
+    }
+    assert!(trades.next().await.is_none());
+    assert!(client.subscribe_trades("ETH-USDC").is_err());
+}
+
+#[tokio::test]
+async fn commands_during_reconnect_run_after_restore() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        next_request(&mut ws).await;
+        ws.close(None).await.unwrap();
+        drop(ws);
+
+        // 重连后先按订阅表恢复订阅，再执行退避期间发起的认证
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let request = next_request(&mut ws).await;
+        assert_eq!(request["op"], "subscribe");
+        accept_auth(&mut ws).await;
+        while ws.next().await.is_some() {}
+    });
+
+    let config = WsConfig {
+        reconnect_initial_delay: Duration::from_millis(200),
+        ..WsConfig::default()
+    };
+    let client = WsClient::connect_with_config(&format!("ws://{}", addr), config)
+        .await
+        .unwrap();
+    let mut events = client.connection_events();
+    let _trades = client.subscribe_trades("BTC-USDC").unwrap();
+
+    assert_eq!(events.recv().await.unwrap(), ConnectionEvent::Disconnected);
+    client
+        .authenticate(Ed25519Signer::generate())
+        .await
+        .unwrap();
+    assert_eq!(events.recv().await.unwrap(), ConnectionEvent::Reconnected);
+}
+
+#[tokio::test]
+async fn restore_failure_is_reported_and_retried() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        accept_auth(&mut ws).await;
+        ws.close(None).await.unwrap();
+        drop(ws);
+
+        // 第二条连接在重新认证途中断开，恢复会话失败
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        next_request(&mut ws).await;
+        drop(ws);
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        accept_auth(&mut ws).await;
+        while ws.next().await.is_some() {}
+    });
+
+    let config = WsConfig {
+        reconnect_initial_delay: Duration::from_millis(10),
+        ..WsConfig::default()
+    };
+    let client = WsClient::connect_with_config(&format!("ws://{}", addr), config)
+        .await
+        .unwrap();
+    let mut events = client.connection_events();
+    client
+        .authenticate(Ed25519Signer::generate())
+        .await
+        .unwrap();
+
+    assert_eq!(events.recv().await.unwrap(), ConnectionEvent::Disconnected);
+    assert!(matches!(
+        events.recv().await.unwrap(),
+        ConnectionEvent::RestoreFailed(_)
+    ));
+    assert_eq!(events.recv().await.unwrap(), ConnectionEvent::Reconnected);
+}
+
+#[tokio::test]
+async fn metrics_sink_counts_deliveries_drops_and_decode_errors() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        next_request(&mut ws).await;
+        ws.send(Message::Text("not json".into())).await.unwrap();
+        // 已知类型但字段不符
+        ws.send(Message::Text(
+            r#"{"type":"trade","market":"BTC-USDC","price":"x"}"#.into(),
+        ))
+        .await
+        .unwrap();
+        for price in 1..=3 {
+            let trade = format!(
+                r#"{{"type":"trade","market":"BTC-USDC","price":{},"size":1,"side":"Buy","ts":0}}"#,
+                price
+            );
+            ws.send(Message::Text(trade.into())).await.unwrap();
+        }
+        while ws.next().await.is_some() {}
+    });
+
+    let metrics = Arc::new(InMemoryMetrics::default());
+    let config = WsConfig {
+        channel_capacity: 1,
+        metrics: Some(metrics.clone()),
+        ..WsConfig::default()
+    };
+    let client = WsClient::connect_with_config(&format!("ws://{}", addr), config)
+        .await
+        .unwrap();
+    let mut trades = client.subscribe_trades("BTC-USDC").unwrap();
+
+    // 队列容量为 1，未及时消费的旧成交被丢弃
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(trades.next().await.unwrap().price, 3);
+
+    let snapshot = metrics.snapshot();
+    let channel_metrics = &snapshot[trades.channel()];
+    assert_eq!(channel_metrics.delivered, 3);
+    assert_eq!(channel_metrics.dropped, 2);
+    assert_eq!(metrics.decode_errors(), 2);
+}
+
+#[tokio::test]
+async fn broadcast_fans_out_one_subscription() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let subscribe = next_request(&mut ws).await;
+        let trade =
+            r#"{"type":"trade","market":"BTC-USDC","price":8,"size":1,"side":"Sell","ts":0}"#;
+        ws.send(Message::Text(trade.into())).await.unwrap();
+        let unsubscribe = next_request(&mut ws).await;
+        (subscribe["op"].clone(), unsubscribe["op"].clone())
+    });
+
+    let client = WsClient::connect(&format!("ws://{}", addr)).await.unwrap();
+    let mut receivers = client.subscribe_trades("BTC-USDC").unwrap().broadcast(2);
+    assert_eq!(client.active_channels().len(), 1);
+    for rx in &mut receivers {
+        assert_eq!(rx.recv().await.unwrap().price, 8);
+    }
+
+    drop(receivers);
+    let (subscribe, unsubscribe) = server.await.unwrap();
+    assert_eq!(
+        (subscribe, unsubscribe),
+        ("subscribe".into(), "unsubscribe".into())
+    );
+}